@@ -0,0 +1,86 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+use crate::blend::BlendMode;
+
+#[inline(always)]
+unsafe fn avx_blend_function<const MODE: u8>(backdrop: __m256, source: __m256) -> __m256 {
+    let mode: BlendMode = MODE.into();
+    match mode {
+        BlendMode::Multiply => _mm256_mul_ps(backdrop, source),
+        BlendMode::Screen => _mm256_sub_ps(
+            _mm256_add_ps(backdrop, source),
+            _mm256_mul_ps(backdrop, source),
+        ),
+        BlendMode::Darken => _mm256_min_ps(backdrop, source),
+        BlendMode::Lighten => _mm256_max_ps(backdrop, source),
+        BlendMode::Difference => {
+            _mm256_andnot_ps(_mm256_set1_ps(-0.), _mm256_sub_ps(backdrop, source))
+        }
+        BlendMode::Exclusion => _mm256_sub_ps(
+            _mm256_add_ps(backdrop, source),
+            _mm256_mul_ps(_mm256_set1_ps(2.), _mm256_mul_ps(backdrop, source)),
+        ),
+        BlendMode::LinearBurn => _mm256_max_ps(
+            _mm256_sub_ps(_mm256_add_ps(backdrop, source), _mm256_set1_ps(1.)),
+            _mm256_setzero_ps(),
+        ),
+        _ => source,
+    }
+}
+
+/// Composites two RGBA pixels at once
+#[inline(always)]
+unsafe fn avx_composite_pixels<const MODE: u8>(source: __m256, backdrop: __m256) -> __m256 {
+    let source_alpha = _mm256_shuffle_ps::<0xFF>(source, source);
+    let backdrop_alpha = _mm256_shuffle_ps::<0xFF>(backdrop, backdrop);
+    let both = _mm256_mul_ps(source_alpha, backdrop_alpha);
+    let alpha = _mm256_sub_ps(_mm256_add_ps(source_alpha, backdrop_alpha), both);
+    let blended = avx_blend_function::<MODE>(backdrop, source);
+    let color = _mm256_add_ps(
+        _mm256_add_ps(
+            _mm256_mul_ps(source, _mm256_sub_ps(source_alpha, both)),
+            _mm256_mul_ps(backdrop, _mm256_sub_ps(backdrop_alpha, both)),
+        ),
+        _mm256_mul_ps(blended, both),
+    );
+    let non_zero = _mm256_cmp_ps::<_CMP_NEQ_OQ>(alpha, _mm256_setzero_ps());
+    let color = _mm256_and_ps(_mm256_div_ps(color, alpha), non_zero);
+    _mm256_blend_ps::<0b10001000>(color, alpha)
+}
+
+/// Composites RGBA f32 rows with separable blend mode, result is written into backdrop
+#[target_feature(enable = "avx2")]
+pub unsafe fn avx_blend_row<const MODE: u8>(
+    start_cx: usize,
+    source: *const f32,
+    backdrop: *mut f32,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+
+    while cx + 4 < width {
+        let src_ptr = source.add(cx * 4);
+        let dst_ptr = backdrop.add(cx * 4);
+        let result0 =
+            avx_composite_pixels::<MODE>(_mm256_loadu_ps(src_ptr), _mm256_loadu_ps(dst_ptr));
+        let result1 = avx_composite_pixels::<MODE>(
+            _mm256_loadu_ps(src_ptr.add(8)),
+            _mm256_loadu_ps(dst_ptr.add(8)),
+        );
+        _mm256_storeu_ps(dst_ptr, result0);
+        _mm256_storeu_ps(dst_ptr.add(8), result1);
+        cx += 4;
+    }
+
+    cx
+}
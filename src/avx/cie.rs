@@ -12,8 +12,8 @@ use crate::luv::{
     LUV_WHITE_V_PRIME,
 };
 use erydanos::{
-    _mm256_atan2_ps, _mm256_cbrt_ps, _mm256_cos_ps, _mm256_hypot_ps, _mm256_prefer_fma_ps,
-    _mm256_select_ps, _mm256_sin_ps,
+    _mm256_atan2_ps, _mm256_cos_ps, _mm256_hypot_ps, _mm256_prefer_fma_ps, _mm256_select_ps,
+    _mm256_sin_ps,
 };
 #[cfg(target_arch = "x86")]
 use std::arch::x86::*;
@@ -10,13 +10,30 @@ use std::arch::x86::*;
 #[cfg(target_arch = "x86_64")]
 use std::arch::x86_64::*;
 
-use erydanos::{_mm256_pow_fast_ps, _mm256_prefer_fma_ps};
+use erydanos::{_mm256_pow_fast_ps, _mm256_prefer_fma_ps, _mm_cbrt_fast_ps, _mm_cbrt_ps};
 
 #[inline(always)]
 pub unsafe fn _mm256_cube_ps(x: __m256) -> __m256 {
     _mm256_mul_ps(_mm256_mul_ps(x, x), x)
 }
 
+/// Cube root taken per 128-bit half, erydanos AVX cube root seeds
+/// Halley iterations of lanes 2..5 from the wrong lanes
+#[inline(always)]
+pub unsafe fn _mm256_cbrt_ps(x: __m256) -> __m256 {
+    let low = _mm_cbrt_ps(_mm256_castps256_ps128(x));
+    let high = _mm_cbrt_ps(_mm256_extractf128_ps::<1>(x));
+    _mm256_insertf128_ps::<1>(_mm256_castps128_ps256(low), high)
+}
+
+/// Cube root without NaN and infinity checks taken per 128-bit half
+#[inline(always)]
+pub unsafe fn _mm256_cbrt_fast_ps(x: __m256) -> __m256 {
+    let low = _mm_cbrt_fast_ps(_mm256_castps256_ps128(x));
+    let high = _mm_cbrt_fast_ps(_mm256_extractf128_ps::<1>(x));
+    _mm256_insertf128_ps::<1>(_mm256_castps128_ps256(low), high)
+}
+
 #[inline(always)]
 pub unsafe fn _mm256_pow_ps(x: __m256, n: __m256) -> __m256 {
     _mm256_pow_fast_ps(x, n)
@@ -64,3 +81,41 @@ pub unsafe fn _mm256_color_matrix_ps(
     let new_b = _mm256_prefer_fma_ps(_mm256_prefer_fma_ps(_mm256_mul_ps(g, c8), b, c9), r, c7);
     (new_r, new_g, new_b)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cube_root_keeps_lanes_apart() {
+        if !std::arch::is_x86_feature_detected!("avx2") {
+            return;
+        }
+        let values = [
+            0.34635597f32,
+            0.063069396,
+            0.06639972,
+            0.057194527,
+            2.9542272e-5,
+            0.0008588651,
+            0.013085125,
+            0.1915492,
+        ];
+        let mut fast = [0f32; 8];
+        let mut precise = [0f32; 8];
+        unsafe {
+            let v = _mm256_loadu_ps(values.as_ptr());
+            _mm256_storeu_ps(fast.as_mut_ptr(), _mm256_cbrt_fast_ps(v));
+            _mm256_storeu_ps(precise.as_mut_ptr(), _mm256_cbrt_ps(v));
+        }
+        for ((value, fast), precise) in values.iter().zip(fast).zip(precise) {
+            assert!((fast - value.cbrt()).abs() < 1e-6, "{} {}", value, fast);
+            assert!(
+                (precise - value.cbrt()).abs() < 1e-6,
+                "{} {}",
+                value,
+                precise
+            );
+        }
+    }
+}
@@ -5,6 +5,7 @@
  * // license that can be found in the LICENSE file.
  */
 
+mod blend;
 mod cie;
 mod from_sigmoidal;
 mod gamma_curves;
@@ -14,17 +15,20 @@ mod oklab_to_image;
 mod routines;
 mod sigmoidal;
 mod support;
+mod swizzle;
 mod to_sigmoidal;
 mod to_xyz_lab;
 mod utils;
 mod xyz_lab_to_image;
 mod xyza_laba_to_image;
 
+pub use blend::avx_blend_row;
 pub use from_sigmoidal::avx_from_sigmoidal_row;
 pub use image_to_oklab::avx_image_to_oklab;
 pub use math::*;
 pub use oklab_to_image::avx_oklab_to_image;
 pub use support::*;
+pub use swizzle::avx_swizzle_row;
 pub use to_sigmoidal::avx_image_to_sigmoidal_row;
 pub use to_xyz_lab::*;
 pub use utils::*;
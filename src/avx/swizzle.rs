@@ -0,0 +1,67 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+
+use crate::swizzle::SwizzleShuffle;
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+/// Reorders channels of a row two blocks at once, each 128 bit lane holds one block.
+/// Lanes are stored in order, so zeroed tail of the first block is overwritten by the second one.
+#[target_feature(enable = "avx2")]
+pub unsafe fn avx_swizzle_row(
+    start_cx: usize,
+    src: *const u8,
+    dst: *mut u8,
+    width: usize,
+    shuffle: &SwizzleShuffle,
+) -> usize {
+    let mut cx = start_cx;
+
+    let mask =
+        _mm256_broadcastsi128_si256(_mm_loadu_si128(shuffle.mask.as_ptr() as *const __m128i));
+    let fill =
+        _mm256_broadcastsi128_si256(_mm_loadu_si128(shuffle.fill.as_ptr() as *const __m128i));
+    let src_step = shuffle.pixels * shuffle.src_pixel_bytes;
+    let dst_step = shuffle.pixels * shuffle.dst_pixel_bytes;
+
+    while (width - cx) * shuffle.src_pixel_bytes >= src_step + 16
+        && (width - cx) * shuffle.dst_pixel_bytes >= dst_step + 16
+    {
+        let src_ptr = src.add(cx * shuffle.src_pixel_bytes);
+        let low = _mm_loadu_si128(src_ptr as *const __m128i);
+        let high = _mm_loadu_si128(src_ptr.add(src_step) as *const __m128i);
+        let pixels = _mm256_inserti128_si256::<1>(_mm256_castsi128_si256(low), high);
+        let reordered = _mm256_or_si256(_mm256_shuffle_epi8(pixels, mask), fill);
+
+        let dst_ptr = dst.add(cx * shuffle.dst_pixel_bytes);
+        _mm_storeu_si128(dst_ptr as *mut __m128i, _mm256_castsi256_si128(reordered));
+        _mm_storeu_si128(
+            dst_ptr.add(dst_step) as *mut __m128i,
+            _mm256_extracti128_si256::<1>(reordered),
+        );
+        cx += shuffle.pixels * 2;
+    }
+
+    while (width - cx) * shuffle.src_pixel_bytes >= 16
+        && (width - cx) * shuffle.dst_pixel_bytes >= 16
+    {
+        let pixels = _mm_loadu_si128(src.add(cx * shuffle.src_pixel_bytes) as *const __m128i);
+        let reordered = _mm_or_si128(
+            _mm_shuffle_epi8(pixels, _mm256_castsi256_si128(mask)),
+            _mm256_castsi256_si128(fill),
+        );
+        _mm_storeu_si128(
+            dst.add(cx * shuffle.dst_pixel_bytes) as *mut __m128i,
+            reordered,
+        );
+        cx += shuffle.pixels;
+    }
+
+    cx
+}
@@ -141,14 +141,13 @@ pub unsafe fn avx_xyza_to_image<const CHANNELS_CONFIGURATION: u8, const TARGET:
 
         let dst_ptr = ((dst as *mut u8).add(dst_offset) as *mut f32).add(cx * channels);
 
-        let (rgba0, rgba1, rgba2, rgba3) = match image_configuration {
-            ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
-                sse_interleave_ps_rgba(r_row0_, g_row0_, b_row0_, a_row0_)
-            }
-            ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
-                sse_interleave_ps_rgba(b_row0_, g_row0_, r_row0_, a_row0_)
-            }
-        };
+        let mut lanes = [a_row0_; 4];
+        lanes[image_configuration.get_r_channel_offset()] = r_row0_;
+        lanes[image_configuration.get_g_channel_offset()] = g_row0_;
+        lanes[image_configuration.get_b_channel_offset()] = b_row0_;
+        lanes[image_configuration.get_a_channel_offset()] = a_row0_;
+        let (rgba0, rgba1, rgba2, rgba3) =
+            sse_interleave_ps_rgba(lanes[0], lanes[1], lanes[2], lanes[3]);
 
         _mm_storeu_ps(dst_ptr, rgba0);
         _mm_storeu_ps(dst_ptr.add(4), rgba1);
@@ -0,0 +1,404 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::gamma_curves::TransferFunction;
+use crate::rgba::Rgba;
+use crate::routines::{
+    op_color_burn, op_color_dodge, op_darken, op_difference, op_exclusion, op_hard_light,
+    op_hard_mix, op_lighten, op_linear_burn, op_linear_light, op_multiply, op_overlay,
+    op_pin_light, op_reflect, op_screen, op_soft_light, op_vivid_light,
+};
+
+/// Image compositing operator, Porter-Duff operators, separable and non-separable blend modes.
+///
+/// Blend modes are composited with source-over as W3C Compositing and Blending defines.
+///
+/// [BlendMode::SrcOver], [BlendMode::Multiply], [BlendMode::Screen], [BlendMode::Darken],
+/// [BlendMode::Lighten], [BlendMode::Difference], [BlendMode::Exclusion] and [BlendMode::LinearBurn]
+/// have SIMD row kernels on SSE 4.1, AVX2 and NEON, other modes are composited by scalar code.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
+pub enum BlendMode {
+    /// Porter-Duff clear, result is transparent
+    Clear = 0,
+    /// Porter-Duff copy, source replaces backdrop
+    Src = 1,
+    /// Porter-Duff destination, backdrop is kept
+    Dst = 2,
+    /// Porter-Duff source-over, normal alpha blending
+    #[default]
+    SrcOver = 3,
+    /// Porter-Duff destination-over
+    DstOver = 4,
+    /// Porter-Duff source-in
+    SrcIn = 5,
+    /// Porter-Duff destination-in
+    DstIn = 6,
+    /// Porter-Duff source-out
+    SrcOut = 7,
+    /// Porter-Duff destination-out
+    DstOut = 8,
+    /// Porter-Duff source-atop
+    SrcAtop = 9,
+    /// Porter-Duff destination-atop
+    DstAtop = 10,
+    /// Porter-Duff xor
+    Xor = 11,
+    /// Plus-lighter, colours and alphas are summed and clamped
+    Add = 12,
+    /// Saturate operator, source is added as much as backdrop alpha allows
+    Saturate = 13,
+    Multiply = 14,
+    Screen = 15,
+    Overlay = 16,
+    Darken = 17,
+    Lighten = 18,
+    ColorDodge = 19,
+    ColorBurn = 20,
+    HardLight = 21,
+    SoftLight = 22,
+    Difference = 23,
+    Exclusion = 24,
+    /// Linear burn, also known as subtract
+    LinearBurn = 25,
+    LinearLight = 26,
+    VividLight = 27,
+    PinLight = 28,
+    HardMix = 29,
+    Reflect = 30,
+    /// Hue of source with saturation and luminosity of backdrop
+    Hue = 31,
+    /// Saturation of source with hue and luminosity of backdrop
+    Saturation = 32,
+    /// Hue and saturation of source with luminosity of backdrop
+    Color = 33,
+    /// Luminosity of source with hue and saturation of backdrop
+    Luminosity = 34,
+}
+
+impl From<u8> for BlendMode {
+    #[inline(always)]
+    fn from(value: u8) -> Self {
+        match value {
+            0 => BlendMode::Clear,
+            1 => BlendMode::Src,
+            2 => BlendMode::Dst,
+            3 => BlendMode::SrcOver,
+            4 => BlendMode::DstOver,
+            5 => BlendMode::SrcIn,
+            6 => BlendMode::DstIn,
+            7 => BlendMode::SrcOut,
+            8 => BlendMode::DstOut,
+            9 => BlendMode::SrcAtop,
+            10 => BlendMode::DstAtop,
+            11 => BlendMode::Xor,
+            12 => BlendMode::Add,
+            13 => BlendMode::Saturate,
+            14 => BlendMode::Multiply,
+            15 => BlendMode::Screen,
+            16 => BlendMode::Overlay,
+            17 => BlendMode::Darken,
+            18 => BlendMode::Lighten,
+            19 => BlendMode::ColorDodge,
+            20 => BlendMode::ColorBurn,
+            21 => BlendMode::HardLight,
+            22 => BlendMode::SoftLight,
+            23 => BlendMode::Difference,
+            24 => BlendMode::Exclusion,
+            25 => BlendMode::LinearBurn,
+            26 => BlendMode::LinearLight,
+            27 => BlendMode::VividLight,
+            28 => BlendMode::PinLight,
+            29 => BlendMode::HardMix,
+            30 => BlendMode::Reflect,
+            31 => BlendMode::Hue,
+            32 => BlendMode::Saturation,
+            33 => BlendMode::Color,
+            34 => BlendMode::Luminosity,
+            _ => {
+                panic!("Unknown value")
+            }
+        }
+    }
+}
+
+/// Space where colour channels are blended
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+pub enum BlendingSpace {
+    /// Channels are blended as stored
+    Gamma,
+    /// Channels are linearized with transfer function before blending and encoded back after
+    Linear(TransferFunction),
+}
+
+/// Porter-Duff source and backdrop factors of operator
+#[inline(always)]
+fn porter_duff_factors(mode: BlendMode, source_alpha: f32, backdrop_alpha: f32) -> (f32, f32) {
+    match mode {
+        BlendMode::Clear => (0., 0.),
+        BlendMode::Src => (1., 0.),
+        BlendMode::Dst => (0., 1.),
+        BlendMode::DstOver => (1. - backdrop_alpha, 1.),
+        BlendMode::SrcIn => (backdrop_alpha, 0.),
+        BlendMode::DstIn => (0., source_alpha),
+        BlendMode::SrcOut => (1. - backdrop_alpha, 0.),
+        BlendMode::DstOut => (0., 1. - source_alpha),
+        BlendMode::SrcAtop => (backdrop_alpha, 1. - source_alpha),
+        BlendMode::DstAtop => (1. - backdrop_alpha, source_alpha),
+        BlendMode::Xor => (1. - backdrop_alpha, 1. - source_alpha),
+        BlendMode::Saturate => {
+            if source_alpha == 0. {
+                (1., 1.)
+            } else {
+                (((1. - backdrop_alpha) / source_alpha).min(1.), 1.)
+            }
+        }
+        _ => (1., 1. - source_alpha),
+    }
+}
+
+/// Composites RGBA pixel with Porter-Duff operator, colours are not premultiplied
+#[inline(always)]
+fn composite_porter_duff(mode: BlendMode, source: [f32; 4], backdrop: [f32; 4]) -> [f32; 4] {
+    let (source_factor, backdrop_factor) = porter_duff_factors(mode, source[3], backdrop[3]);
+    let source_weight = source[3] * source_factor;
+    let backdrop_weight = backdrop[3] * backdrop_factor;
+    let alpha = source_weight + backdrop_weight;
+    if alpha == 0. {
+        return [0.; 4];
+    }
+    let recip_alpha = 1. / alpha;
+    [
+        (source[0] * source_weight + backdrop[0] * backdrop_weight) * recip_alpha,
+        (source[1] * source_weight + backdrop[1] * backdrop_weight) * recip_alpha,
+        (source[2] * source_weight + backdrop[2] * backdrop_weight) * recip_alpha,
+        alpha,
+    ]
+}
+
+/// Composites RGBA pixel with plus-lighter operator, colours are not premultiplied
+#[inline(always)]
+fn composite_add(source: [f32; 4], backdrop: [f32; 4]) -> [f32; 4] {
+    let alpha = (source[3] + backdrop[3]).min(1.);
+    if alpha == 0. {
+        return [0.; 4];
+    }
+    let recip_alpha = 1. / alpha;
+    let channel = |i: usize| -> f32 {
+        (source[i] * source[3] + backdrop[i] * backdrop[3]).min(1.) * recip_alpha
+    };
+    [channel(0), channel(1), channel(2), alpha]
+}
+
+/// Composites RGBA pixel with separable blend function and source-over, colours are not premultiplied.
+/// `blend` receives backdrop and source channel in that order
+#[inline(always)]
+fn composite_separable<F: Fn(f32, f32) -> f32>(
+    source: [f32; 4],
+    backdrop: [f32; 4],
+    blend: F,
+) -> [f32; 4] {
+    let both = source[3] * backdrop[3];
+    let alpha = source[3] + backdrop[3] - both;
+    if alpha == 0. {
+        return [0.; 4];
+    }
+    let recip_alpha = 1. / alpha;
+    let source_only = source[3] - both;
+    let backdrop_only = backdrop[3] - both;
+    let channel = |i: usize| -> f32 {
+        (source[i] * source_only
+            + backdrop[i] * backdrop_only
+            + blend(backdrop[i], source[i]) * both)
+            * recip_alpha
+    };
+    [channel(0), channel(1), channel(2), alpha]
+}
+
+/// W3C Lum(C)
+#[inline(always)]
+fn lum(c: Rgba<f32>) -> f32 {
+    0.3 * c.r + 0.59 * c.g + 0.11 * c.b
+}
+
+/// W3C ClipColor(C)
+#[inline(always)]
+fn clip_color(c: Rgba<f32>) -> Rgba<f32> {
+    let l = lum(c);
+    let n = c.r.min(c.g).min(c.b);
+    let x = c.r.max(c.g).max(c.b);
+    let mut clipped = c;
+    if n < 0. {
+        let scale = l / (l - n);
+        clipped.r = l + (clipped.r - l) * scale;
+        clipped.g = l + (clipped.g - l) * scale;
+        clipped.b = l + (clipped.b - l) * scale;
+    }
+    if x > 1. {
+        let scale = (1. - l) / (x - l);
+        clipped.r = l + (clipped.r - l) * scale;
+        clipped.g = l + (clipped.g - l) * scale;
+        clipped.b = l + (clipped.b - l) * scale;
+    }
+    clipped
+}
+
+/// W3C SetLum(C, l)
+#[inline(always)]
+fn set_lum(c: Rgba<f32>, l: f32) -> Rgba<f32> {
+    let d = l - lum(c);
+    clip_color(Rgba::<f32>::new(c.r + d, c.g + d, c.b + d, c.a))
+}
+
+/// W3C Sat(C)
+#[inline(always)]
+fn sat(c: Rgba<f32>) -> f32 {
+    c.r.max(c.g).max(c.b) - c.r.min(c.g).min(c.b)
+}
+
+/// W3C SetSat(C, s), Cmin goes to 0, Cmax to `s` and Cmid is scaled between
+#[inline(always)]
+fn set_sat(c: Rgba<f32>, s: f32) -> Rgba<f32> {
+    let cmax = c.r.max(c.g).max(c.b);
+    let cmin = c.r.min(c.g).min(c.b);
+    if cmax > cmin {
+        let scale = s / (cmax - cmin);
+        Rgba::<f32>::new(
+            (c.r - cmin) * scale,
+            (c.g - cmin) * scale,
+            (c.b - cmin) * scale,
+            c.a,
+        )
+    } else {
+        Rgba::<f32>::new(0., 0., 0., c.a)
+    }
+}
+
+#[inline(always)]
+fn blend_hue(cs: Rgba<f32>, cb: Rgba<f32>) -> Rgba<f32> {
+    set_lum(set_sat(cs, sat(cb)), lum(cb))
+}
+
+#[inline(always)]
+fn blend_saturation(cs: Rgba<f32>, cb: Rgba<f32>) -> Rgba<f32> {
+    set_lum(set_sat(cb, sat(cs)), lum(cb))
+}
+
+#[inline(always)]
+fn blend_color(cs: Rgba<f32>, cb: Rgba<f32>) -> Rgba<f32> {
+    set_lum(cs, lum(cb))
+}
+
+#[inline(always)]
+fn blend_luminosity(cs: Rgba<f32>, cb: Rgba<f32>) -> Rgba<f32> {
+    set_lum(cb, lum(cs))
+}
+
+/// Composites RGBA pixel with non-separable blend function and source-over, colours are not premultiplied.
+/// `blend` receives source and backdrop in that order and returns blended colour
+#[inline(always)]
+fn composite_non_separable<F: Fn(Rgba<f32>, Rgba<f32>) -> Rgba<f32>>(
+    source: [f32; 4],
+    backdrop: [f32; 4],
+    blend: F,
+) -> [f32; 4] {
+    let both = source[3] * backdrop[3];
+    let alpha = source[3] + backdrop[3] - both;
+    if alpha == 0. {
+        return [0.; 4];
+    }
+    let recip_alpha = 1. / alpha;
+    let source_only = source[3] - both;
+    let backdrop_only = backdrop[3] - both;
+    let blended = blend(
+        Rgba::<f32>::new(source[0], source[1], source[2], source[3]),
+        Rgba::<f32>::new(backdrop[0], backdrop[1], backdrop[2], backdrop[3]),
+    );
+    let channel = |i: usize, b: f32| -> f32 {
+        (source[i] * source_only + backdrop[i] * backdrop_only + b * both) * recip_alpha
+    };
+    [
+        channel(0, blended.r),
+        channel(1, blended.g),
+        channel(2, blended.b),
+        alpha,
+    ]
+}
+
+/// Composites rows of RGBA f32 pixels, result is written into backdrop.
+/// Operators in routines.rs take operands in different order, they're called here
+/// so results match W3C definitions where `Cb` is backdrop and `Cs` is source
+#[inline(always)]
+pub(crate) fn composite_row(mode: BlendMode, source: &[f32], backdrop: &mut [f32]) {
+    #[inline(always)]
+    fn for_each_pixel<F: Fn([f32; 4], [f32; 4]) -> [f32; 4]>(
+        source: &[f32],
+        backdrop: &mut [f32],
+        composite: F,
+    ) {
+        for (dst, src) in backdrop.chunks_exact_mut(4).zip(source.chunks_exact(4)) {
+            let result = composite(
+                [src[0], src[1], src[2], src[3]],
+                [dst[0], dst[1], dst[2], dst[3]],
+            );
+            dst.copy_from_slice(&result);
+        }
+    }
+
+    macro_rules! non_separable {
+        ($blend: expr) => {
+            for_each_pixel(source, backdrop, |s, b| {
+                composite_non_separable(s, b, $blend)
+            })
+        };
+    }
+
+    macro_rules! separable {
+        ($blend: expr) => {
+            for_each_pixel(source, backdrop, |s, b| composite_separable(s, b, $blend))
+        };
+    }
+
+    match mode {
+        BlendMode::Clear
+        | BlendMode::Src
+        | BlendMode::Dst
+        | BlendMode::DstOver
+        | BlendMode::SrcIn
+        | BlendMode::DstIn
+        | BlendMode::SrcOut
+        | BlendMode::DstOut
+        | BlendMode::SrcAtop
+        | BlendMode::DstAtop
+        | BlendMode::Xor
+        | BlendMode::Saturate => {
+            for_each_pixel(source, backdrop, |s, b| composite_porter_duff(mode, s, b))
+        }
+        BlendMode::Add => for_each_pixel(source, backdrop, composite_add),
+        BlendMode::SrcOver => separable!(|_, cs| cs),
+        BlendMode::Multiply => separable!(op_multiply),
+        BlendMode::Screen => separable!(op_screen),
+        BlendMode::Overlay => separable!(|cb, cs| op_overlay(cs, cb)),
+        BlendMode::Darken => separable!(op_darken),
+        BlendMode::Lighten => separable!(op_lighten),
+        BlendMode::ColorDodge => separable!(op_color_dodge),
+        BlendMode::ColorBurn => separable!(op_color_burn),
+        BlendMode::HardLight => separable!(|cb, cs| op_hard_light(cs, cb)),
+        BlendMode::SoftLight => separable!(|cb, cs| op_soft_light(cs, cb)),
+        BlendMode::Difference => separable!(op_difference),
+        BlendMode::Exclusion => separable!(op_exclusion),
+        BlendMode::LinearBurn => separable!(op_linear_burn),
+        BlendMode::LinearLight => separable!(op_linear_light),
+        BlendMode::VividLight => separable!(op_vivid_light),
+        BlendMode::PinLight => separable!(op_pin_light),
+        BlendMode::HardMix => separable!(op_hard_mix),
+        BlendMode::Reflect => separable!(op_reflect),
+        BlendMode::Hue => non_separable!(blend_hue),
+        BlendMode::Saturation => non_separable!(blend_saturation),
+        BlendMode::Color => non_separable!(blend_color),
+        BlendMode::Luminosity => non_separable!(blend_luminosity),
+    }
+}
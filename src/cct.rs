@@ -0,0 +1,401 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::chromatic_adaptation::{adaptation_matrix, ChromaticAdaptation};
+use crate::gamma_curves::{linearization_table_u8, TransferFunction};
+use crate::image::ImageConfiguration;
+use crate::image_view::check_image;
+use crate::matrix::{matrix_inverse, matrix_mul, matrix_vec_mul};
+use crate::threading::ForEachRow;
+use crate::Xyz;
+#[cfg(feature = "rayon")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "rayon")]
+use rayon::prelude::{ParallelSlice, ParallelSliceMut};
+
+/// Lowest temperature the Planckian locus approximation is valid for
+pub const MIN_PLANCKIAN_TEMPERATURE: f32 = 1000.;
+/// Highest temperature the Planckian locus approximation is valid for
+pub const MAX_PLANCKIAN_TEMPERATURE: f32 = 15000.;
+
+/// Method of correlated colour temperature estimation
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
+pub enum CctMethod {
+    /// Ohno 2013 triangular and parabolic solutions over cascaded Planckian table
+    #[default]
+    Ohno2013,
+    /// McCamy 1992 cubic approximation, reasonable only in 2856..6504K and near the locus
+    McCamy,
+}
+
+/// Colour temperature in Kelvin with distance from Planckian locus
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Default)]
+pub struct ColorTemperature {
+    /// Correlated colour temperature in Kelvin
+    pub kelvin: f32,
+    /// Signed distance from Planckian locus in CIE 1960 UCS, positive is above the locus, towards green
+    pub duv: f32,
+}
+
+/// CIE 1960 UCS chromaticity of XYZ colour
+#[inline]
+pub(crate) fn xyz_to_uv(xyz: Xyz) -> (f64, f64) {
+    let denominator = xyz.x as f64 + 15. * xyz.y as f64 + 3. * xyz.z as f64;
+    if denominator == 0. {
+        return (0., 0.);
+    }
+    (
+        4. * xyz.x as f64 / denominator,
+        6. * xyz.y as f64 / denominator,
+    )
+}
+
+#[inline]
+fn uv_to_xy(u: f64, v: f64) -> (f64, f64) {
+    let denominator = 2. * u - 8. * v + 4.;
+    (3. * u / denominator, 2. * v / denominator)
+}
+
+/// Krystek 1985 rational approximation of Planckian locus in CIE 1960 UCS
+#[inline]
+fn planckian_uv(kelvin: f64) -> (f64, f64) {
+    let t = kelvin;
+    let t2 = t * t;
+    let u = (0.860117757 + 1.54118254e-4 * t + 1.28641212e-7 * t2)
+        / (1. + 8.42420235e-4 * t + 7.08145163e-7 * t2);
+    let v = (0.317398726 + 4.22806245e-5 * t + 4.20481691e-8 * t2)
+        / (1. - 2.89741816e-5 * t + 1.61456053e-7 * t2);
+    (u, v)
+}
+
+/// Unit normal to Planckian locus pointing above it
+#[inline]
+fn planckian_normal(kelvin: f64) -> (f64, f64) {
+    let (u0, v0) = planckian_uv(kelvin - 0.5);
+    let (u1, v1) = planckian_uv(kelvin + 0.5);
+    let (du, dv) = (u1 - u0, v1 - v0);
+    let length = du.hypot(dv);
+    let (nu, nv) = (-dv / length, du / length);
+    if nv < 0. {
+        (-nu, -nv)
+    } else {
+        (nu, nv)
+    }
+}
+
+/// Returns CIE 1931 xy chromaticity of black body radiator at `kelvin`.
+///
+/// Uses Krystek approximation, valid from [MIN_PLANCKIAN_TEMPERATURE] to [MAX_PLANCKIAN_TEMPERATURE]
+pub fn planckian_chromaticity(kelvin: f32) -> (f32, f32) {
+    let kelvin = (kelvin as f64).max(MIN_PLANCKIAN_TEMPERATURE as f64);
+    let (u, v) = planckian_uv(kelvin);
+    let (x, y) = uv_to_xy(u, v);
+    (x as f32, y as f32)
+}
+
+/// Returns CIE 1931 xy chromaticity of CIE daylight illuminant at `kelvin`, defined from 4000 to 25000K
+pub fn daylight_chromaticity(kelvin: f32) -> (f32, f32) {
+    let t = (kelvin as f64).max(4000.).min(25000.);
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let x = if t <= 7000. {
+        -4.6070e9 / t3 + 2.9678e6 / t2 + 0.09911e3 / t + 0.244063
+    } else {
+        -2.0064e9 / t3 + 1.9018e6 / t2 + 0.24748e3 / t + 0.237040
+    };
+    let y = -3. * x * x + 2.87 * x - 0.275;
+    (x as f32, y as f32)
+}
+
+impl ColorTemperature {
+    /// Constructs new instance
+    #[inline]
+    pub fn new(kelvin: f32, duv: f32) -> ColorTemperature {
+        ColorTemperature { kelvin, duv }
+    }
+
+    /// Estimates correlated colour temperature and Duv of the colour
+    pub fn from_xyz(xyz: Xyz, method: CctMethod) -> ColorTemperature {
+        let (u, v) = xyz_to_uv(xyz);
+        match method {
+            CctMethod::Ohno2013 => ohno_2013(u, v),
+            CctMethod::McCamy => {
+                let (x, y) = uv_to_xy(u, v);
+                let n = (x - 0.3320) / (0.1858 - y);
+                let kelvin = 449. * n * n * n + 3525. * n * n + 6823.3 * n + 5520.33;
+                let kelvin = kelvin.max(MIN_PLANCKIAN_TEMPERATURE as f64);
+                let (up, vp) = planckian_uv(kelvin);
+                let (nu, nv) = planckian_normal(kelvin);
+                let duv = (u - up) * nu + (v - vp) * nv;
+                ColorTemperature::new(kelvin as f32, duv as f32)
+            }
+        }
+    }
+
+    /// Returns white point for the temperature, Y is normalized to 1.
+    ///
+    /// Chromaticity is taken on Planckian locus and moved by `duv` along its normal
+    pub fn white_point(&self) -> Xyz {
+        let kelvin = (self.kelvin as f64).max(MIN_PLANCKIAN_TEMPERATURE as f64);
+        let (u, v) = planckian_uv(kelvin);
+        let (nu, nv) = planckian_normal(kelvin);
+        let (x, y) = uv_to_xy(u + nu * self.duv as f64, v + nv * self.duv as f64);
+        Xyz::new((x / y) as f32, 1., ((1. - x - y) / y) as f32)
+    }
+}
+
+/// Ohno 2013, "Practical Use and Calculation of CCT and Duv"
+fn ohno_2013(u: f64, v: f64) -> ColorTemperature {
+    const SAMPLES: usize = 15;
+    let distance = |kelvin: f64| -> f64 {
+        let (up, vp) = planckian_uv(kelvin);
+        (u - up).hypot(v - vp)
+    };
+
+    let mut low = MIN_PLANCKIAN_TEMPERATURE as f64;
+    let mut high = MAX_PLANCKIAN_TEMPERATURE as f64;
+    let mut temperatures = [0f64; SAMPLES];
+    let mut distances = [0f64; SAMPLES];
+    let mut index = 1usize;
+    // Cascade expansion of the table until neighbours are 0.01% apart
+    for _ in 0..16 {
+        let ratio = (high / low).powf(1. / (SAMPLES - 1) as f64);
+        let mut kelvin = low;
+        for (temperature, distance_to) in temperatures.iter_mut().zip(distances.iter_mut()) {
+            *temperature = kelvin;
+            *distance_to = distance(kelvin);
+            kelvin *= ratio;
+        }
+        index = distances
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(b.1))
+            .map(|x| x.0)
+            .unwrap_or(0)
+            .max(1)
+            .min(SAMPLES - 2);
+        low = temperatures[index - 1];
+        high = temperatures[index + 1];
+        if ratio < 1.0001 {
+            break;
+        }
+    }
+
+    let (t0, t1, t2) = (
+        temperatures[index - 1],
+        temperatures[index],
+        temperatures[index + 1],
+    );
+    let (d0, d1, d2) = (distances[index - 1], distances[index], distances[index + 1]);
+
+    // Triangular solution
+    let (u0, v0) = planckian_uv(t0);
+    let (u2, v2) = planckian_uv(t2);
+    let l = (u2 - u0).hypot(v2 - v0);
+    let x = (d0 * d0 - d2 * d2 + l * l) / (2. * l);
+    let mut kelvin = t0 + (t2 - t0) * x / l;
+    let vx = v0 + (v2 - v0) * x / l;
+    let mut duv = (d0 * d0 - x * x).max(0.).sqrt().copysign(v - vx);
+
+    // Parabolic solution is more accurate away from the locus
+    if duv.abs() >= 0.002 {
+        let denominator = (t2 - t1) * (t0 - t2) * (t1 - t0);
+        let a = (t0 * (d2 - d1) + t1 * (d0 - d2) + t2 * (d1 - d0)) / denominator;
+        let b = -(t0 * t0 * (d2 - d1) + t1 * t1 * (d0 - d2) + t2 * t2 * (d1 - d0)) / denominator;
+        let c = -(d0 * (t2 - t1) * t1 * t2 + d1 * (t0 - t2) * t0 * t2 + d2 * (t1 - t0) * t0 * t1)
+            / denominator;
+        if a != 0. {
+            kelvin = -b / (2. * a);
+            let (_, vp) = planckian_uv(kelvin);
+            duv = (a * kelvin * kelvin + b * kelvin + c).copysign(v - vp);
+        }
+    }
+
+    ColorTemperature::new(kelvin as f32, duv as f32)
+}
+
+fn white_balance_impl<const CHANNELS_CONFIGURATION: u8>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    source: ColorTemperature,
+    target: ColorTemperature,
+    adaptation: ChromaticAdaptation,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let src_row_length = check_image::<u8>(
+        src.len(),
+        src_stride,
+        width,
+        height,
+        image_configuration,
+        "Source",
+    );
+    let dst_row_length = check_image::<u8>(
+        dst.len(),
+        dst_stride,
+        width,
+        height,
+        image_configuration,
+        "Destination",
+    );
+    if width == 0 || height == 0 {
+        return;
+    }
+    let channels = image_configuration.get_channels_count();
+
+    let inverse_matrix = matrix_inverse(matrix).expect("RGB to XYZ matrix must be invertible");
+    let adaptation = adaptation_matrix(source.white_point(), target.white_point(), adaptation);
+    // Linear RGB -> XYZ -> adapted XYZ -> linear RGB folded into one matrix
+    let rgb_matrix = matrix_mul(&inverse_matrix, &matrix_mul(&adaptation, matrix));
+    let linearization_table = linearization_table_u8(transfer_function);
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = dst
+            .par_chunks_mut(dst_row_length)
+            .zip(src.par_chunks(src_row_length))
+            .take(height as usize);
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = dst
+            .chunks_mut(dst_row_length)
+            .zip(src.chunks(src_row_length))
+            .take(height as usize);
+    }
+
+    iter.for_each_row(width as usize, |(dst_row, src_row)| {
+        let width = width as usize;
+        let mut transient_row = vec![0f32; width * 3];
+        for (linear, src) in transient_row
+            .chunks_exact_mut(3)
+            .zip(src_row[..width * channels].chunks_exact(channels))
+        {
+            let rgb = [
+                linearization_table[src[image_configuration.get_r_channel_offset()] as usize],
+                linearization_table[src[image_configuration.get_g_channel_offset()] as usize],
+                linearization_table[src[image_configuration.get_b_channel_offset()] as usize],
+            ];
+            linear.copy_from_slice(&matrix_vec_mul(&rgb_matrix, rgb));
+        }
+
+        for ((dst, src), linear) in dst_row[..width * channels]
+            .chunks_exact_mut(channels)
+            .zip(src_row.chunks_exact(channels))
+            .zip(transient_row.chunks_exact(3))
+        {
+            let encode = |v: f32| -> u8 {
+                (transfer_function.gamma(v.max(0.).min(1.)) * 255.)
+                    .round()
+                    .min(255.) as u8
+            };
+            dst[image_configuration.get_r_channel_offset()] = encode(linear[0]);
+            dst[image_configuration.get_g_channel_offset()] = encode(linear[1]);
+            dst[image_configuration.get_b_channel_offset()] = encode(linear[2]);
+            if image_configuration.has_alpha() {
+                dst[image_configuration.get_a_channel_offset()] =
+                    src[image_configuration.get_a_channel_offset()];
+            }
+        }
+    });
+}
+
+/// This function white balances RGBA image from source colour temperature into target in linear light, alpha is preserved
+///
+/// Colours lit by `source` white are mapped to appear lit by `target` white,
+/// sRGB white D65 is near to 6504K with Duv 0.0032
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGBA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `source` - Temperature and tint of the light the image was taken under
+/// * `target` - Temperature and tint the light should become
+/// * `adaptation` - Chromatic adaptation transform, if you don't have specific pick `Bradford`
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn rgba_white_balance(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    source: ColorTemperature,
+    target: ColorTemperature,
+    adaptation: ChromaticAdaptation,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    white_balance_impl::<{ ImageConfiguration::Rgba as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        source,
+        target,
+        adaptation,
+        matrix,
+        transfer_function,
+    );
+}
+
+/// This function white balances BGRA image from source colour temperature into target in linear light, alpha is preserved
+///
+/// Colours lit by `source` white are mapped to appear lit by `target` white,
+/// sRGB white D65 is near to 6504K with Duv 0.0032
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGRA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `source` - Temperature and tint of the light the image was taken under
+/// * `target` - Temperature and tint the light should become
+/// * `adaptation` - Chromatic adaptation transform, if you don't have specific pick `Bradford`
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn bgra_white_balance(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    source: ColorTemperature,
+    target: ColorTemperature,
+    adaptation: ChromaticAdaptation,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    white_balance_impl::<{ ImageConfiguration::Bgra as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        source,
+        target,
+        adaptation,
+        matrix,
+        transfer_function,
+    );
+}
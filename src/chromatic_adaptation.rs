@@ -0,0 +1,134 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::matrix::{matrix_inverse, matrix_mul, matrix_vec_mul};
+use crate::Xyz;
+
+/// CIE D65 white point, Y normalized to 1, CIE 1931 2° observer
+pub const WHITE_POINT_D65: Xyz = Xyz {
+    x: 0.95047,
+    y: 1.,
+    z: 1.08883,
+};
+
+/// CIE D50 white point, Y normalized to 1, CIE 1931 2° observer
+pub const WHITE_POINT_D50: Xyz = Xyz {
+    x: 0.96422,
+    y: 1.,
+    z: 0.82521,
+};
+
+/// CIE standard illuminant A white point, Y normalized to 1, CIE 1931 2° observer
+pub const WHITE_POINT_A: Xyz = Xyz {
+    x: 1.09850,
+    y: 1.,
+    z: 0.35585,
+};
+
+/// CIE equal energy illuminant E white point
+pub const WHITE_POINT_E: Xyz = Xyz {
+    x: 1.,
+    y: 1.,
+    z: 1.,
+};
+
+/// Bradford cone response matrix
+pub const BRADFORD: [[f32; 3]; 3] = [
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296],
+];
+
+/// Von Kries cone response matrix, Hunt-Pointer-Estevez normalized to D65
+pub const VON_KRIES: [[f32; 3]; 3] = [
+    [0.40024, 0.70760, -0.08081],
+    [-0.22630, 1.16532, 0.04570],
+    [0., 0., 0.91822],
+];
+
+/// CIECAM02 chromatic adaptation matrix
+pub const CAT02: [[f32; 3]; 3] = [
+    [0.7328, 0.4296, -0.1624],
+    [-0.7036, 1.6975, 0.0061],
+    [0.0030, 0.0136, 0.9834],
+];
+
+/// Declares chromatic adaptation transform
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
+pub enum ChromaticAdaptation {
+    /// Bradford transform, default in ICC and most of colour management
+    #[default]
+    Bradford = 0,
+    /// Von Kries transform with Hunt-Pointer-Estevez cone responses
+    VonKries = 1,
+    /// CIECAM02 transform
+    Cat02 = 2,
+    /// Naive scaling in XYZ
+    XyzScaling = 3,
+}
+
+impl ChromaticAdaptation {
+    /// Returns cone response matrix of the transform
+    #[inline]
+    pub fn cone_response(&self) -> [[f32; 3]; 3] {
+        match self {
+            ChromaticAdaptation::Bradford => BRADFORD,
+            ChromaticAdaptation::VonKries => VON_KRIES,
+            ChromaticAdaptation::Cat02 => CAT02,
+            ChromaticAdaptation::XyzScaling => [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+        }
+    }
+}
+
+/// Computes XYZ to XYZ chromatic adaptation matrix from source white point to target white point
+///
+/// # Arguments
+/// * `source_white` - Source white point, absolute scale doesn't matter
+/// * `target_white` - Target white point, absolute scale doesn't matter
+/// * `method` - Chromatic adaptation transform
+pub fn adaptation_matrix(
+    source_white: Xyz,
+    target_white: Xyz,
+    method: ChromaticAdaptation,
+) -> [[f32; 3]; 3] {
+    let cone_response = method.cone_response();
+    let inverse_response = matrix_inverse(&cone_response).unwrap();
+    let source_white = source_white.scale(1. / source_white.y);
+    let target_white = target_white.scale(1. / target_white.y);
+    let source_cone = matrix_vec_mul(
+        &cone_response,
+        [source_white.x, source_white.y, source_white.z],
+    );
+    let target_cone = matrix_vec_mul(
+        &cone_response,
+        [target_white.x, target_white.y, target_white.z],
+    );
+    let scale = [
+        [target_cone[0] / source_cone[0], 0., 0.],
+        [0., target_cone[1] / source_cone[1], 0.],
+        [0., 0., target_cone[2] / source_cone[2]],
+    ];
+    matrix_mul(&inverse_response, &matrix_mul(&scale, &cone_response))
+}
+
+/// Adapts colour from source white point to target white point
+///
+/// # Arguments
+/// * `xyz` - Colour to adapt
+/// * `source_white` - Source white point
+/// * `target_white` - Target white point
+/// * `method` - Chromatic adaptation transform
+#[inline]
+pub fn adapt_xyz(
+    xyz: Xyz,
+    source_white: Xyz,
+    target_white: Xyz,
+    method: ChromaticAdaptation,
+) -> Xyz {
+    let matrix = adaptation_matrix(source_white, target_white, method);
+    xyz.transform(&matrix)
+}
@@ -0,0 +1,625 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::gamma_curves::TransferFunction;
+use crate::image::ImageConfiguration;
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use crate::neon::neon_adjust_colors_row;
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+use crate::sse::sse_adjust_colors_row;
+use crate::threading::ForEachRow;
+use crate::xyz_transform::{SRGB_TO_XYZ_D65, XYZ_TO_SRGB_D65};
+use crate::{Jzazbz, Oklab, Rgb, Xyz};
+#[cfg(feature = "rayon")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "rayon")]
+use rayon::prelude::{ParallelSlice, ParallelSliceMut};
+
+/// Chroma of most saturated sRGB primary in Oklab, vibrance boost fades out towards it
+const OKLAB_REFERENCE_CHROMA: f32 = 0.32;
+/// Chroma of most saturated sRGB primary in Jzazbz at 200 nits
+const JZAZBZ_REFERENCE_CHROMA: f32 = 0.19;
+/// Display luminance Jzazbz is evaluated at
+const JZAZBZ_DISPLAY_LUMINANCE: f32 = 200.;
+
+const LIGHTNESS_CURVE_SIZE: usize = 1025;
+
+/// Perceptual space where hue, chroma and lightness are adjusted
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
+pub enum AdjustmentSpace {
+    /// Oklch, runs with SIMD
+    #[default]
+    Oklch,
+    /// Jzczhz at 200 nits display luminance
+    Jzczhz,
+}
+
+/// Tone curve applied to perceptual lightness normalized to [0, 1]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LightnessCurve {
+    table: Vec<f32>,
+}
+
+impl LightnessCurve {
+    /// Builds monotone cubic curve through control points.
+    ///
+    /// Points are `(input, output)` in range [0, 1] and will be sorted by input,
+    /// outside of covered input range the nearest control point output is used.
+    /// Monotone points always produce monotone curve, Fritsch-Carlson method is used
+    pub fn new(points: &[(f32, f32)]) -> LightnessCurve {
+        let mut points = points.to_vec();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        points.dedup_by(|a, b| a.0 == b.0);
+        if points.is_empty() {
+            points = vec![(0., 0.), (1., 1.)];
+        }
+
+        let n = points.len();
+        let mut tangents = vec![0f32; n];
+        if n > 1 {
+            let secants = points
+                .windows(2)
+                .map(|p| (p[1].1 - p[0].1) / (p[1].0 - p[0].0))
+                .collect::<Vec<f32>>();
+            tangents[0] = secants[0];
+            tangents[n - 1] = secants[n - 2];
+            for i in 1..n - 1 {
+                tangents[i] = if secants[i - 1] * secants[i] <= 0. {
+                    0.
+                } else {
+                    (secants[i - 1] + secants[i]) * 0.5
+                };
+            }
+            for (i, &secant) in secants.iter().enumerate() {
+                if secant == 0. {
+                    tangents[i] = 0.;
+                    tangents[i + 1] = 0.;
+                    continue;
+                }
+                let alpha = tangents[i] / secant;
+                let beta = tangents[i + 1] / secant;
+                let radius = alpha.hypot(beta);
+                if radius > 3. {
+                    let tau = 3. / radius;
+                    tangents[i] = tau * alpha * secant;
+                    tangents[i + 1] = tau * beta * secant;
+                }
+            }
+        }
+
+        let mut table = vec![0f32; LIGHTNESS_CURVE_SIZE];
+        for (i, value) in table.iter_mut().enumerate() {
+            let x = i as f32 / (LIGHTNESS_CURVE_SIZE - 1) as f32;
+            *value = if x <= points[0].0 {
+                points[0].1
+            } else if x >= points[n - 1].0 {
+                points[n - 1].1
+            } else {
+                let segment = points.partition_point(|p| p.0 <= x) - 1;
+                let (x0, y0) = points[segment];
+                let (x1, y1) = points[segment + 1];
+                let h = x1 - x0;
+                let t = (x - x0) / h;
+                let t2 = t * t;
+                let t3 = t2 * t;
+                (2. * t3 - 3. * t2 + 1.) * y0
+                    + (t3 - 2. * t2 + t) * h * tangents[segment]
+                    + (-2. * t3 + 3. * t2) * y1
+                    + (t3 - t2) * h * tangents[segment + 1]
+            };
+        }
+        LightnessCurve { table }
+    }
+
+    /// Evaluates curve, lightness above 1 keeps slope 1 from the curve end
+    #[inline]
+    pub fn evaluate(&self, lightness: f32) -> f32 {
+        evaluate_lightness_table(&self.table, lightness)
+    }
+}
+
+#[inline]
+pub(crate) fn evaluate_lightness_table(table: &[f32], lightness: f32) -> f32 {
+    let last = table.len() - 1;
+    if lightness >= 1. {
+        return table[last] + (lightness - 1.);
+    }
+    let position = lightness.max(0.) * last as f32;
+    let index = (position as usize).min(last - 1);
+    let fraction = position - index as f32;
+    table[index] + (table[index + 1] - table[index]) * fraction
+}
+
+/// Set of adjustments applied in a single pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorAdjustments {
+    /// Exposure in stops applied in linear light before everything else
+    pub exposure: f32,
+    /// Hue rotation in degrees
+    pub hue_rotation: f32,
+    /// Chroma multiplier, 1 keeps chroma, 0 makes image gray
+    pub chroma: f32,
+    /// Vibrance in range [-1, 1], boosts or reduces low chroma colours more than saturated ones
+    pub vibrance: f32,
+    /// Optional curve applied to perceptual lightness
+    pub lightness: Option<LightnessCurve>,
+    /// Space where hue, chroma, vibrance and lightness are adjusted
+    pub space: AdjustmentSpace,
+}
+
+impl Default for ColorAdjustments {
+    fn default() -> Self {
+        ColorAdjustments {
+            exposure: 0.,
+            hue_rotation: 0.,
+            chroma: 1.,
+            vibrance: 0.,
+            lightness: None,
+            space: AdjustmentSpace::Oklch,
+        }
+    }
+}
+
+/// Adjustments resolved into constants used by row handlers
+pub(crate) struct AdjustmentParameters<'a> {
+    pub(crate) exposure_gain: f32,
+    pub(crate) hue_cos: f32,
+    pub(crate) hue_sin: f32,
+    pub(crate) chroma: f32,
+    pub(crate) vibrance: f32,
+    pub(crate) recip_reference_chroma: f32,
+    pub(crate) lightness: Option<&'a [f32]>,
+}
+
+impl<'a> AdjustmentParameters<'a> {
+    fn new(adjustments: &'a ColorAdjustments) -> AdjustmentParameters<'a> {
+        let reference_chroma = match adjustments.space {
+            AdjustmentSpace::Oklch => OKLAB_REFERENCE_CHROMA,
+            AdjustmentSpace::Jzczhz => JZAZBZ_REFERENCE_CHROMA,
+        };
+        let (hue_sin, hue_cos) = adjustments.hue_rotation.to_radians().sin_cos();
+        AdjustmentParameters {
+            exposure_gain: adjustments.exposure.exp2(),
+            hue_cos,
+            hue_sin,
+            chroma: adjustments.chroma.max(0.),
+            vibrance: adjustments.vibrance.max(-1.).min(1.),
+            recip_reference_chroma: 1. / reference_chroma,
+            lightness: adjustments.lightness.as_ref().map(|x| x.table.as_slice()),
+        }
+    }
+
+    /// Returns adjusted lightness, a, b of opponent space
+    #[inline]
+    pub(crate) fn adjust(&self, lightness: f32, a: f32, b: f32) -> (f32, f32, f32) {
+        let lightness = match self.lightness {
+            None => lightness,
+            Some(table) => evaluate_lightness_table(table, lightness),
+        };
+        let mut scale = self.chroma;
+        if self.vibrance != 0. {
+            let saturation = (a.hypot(b) * self.recip_reference_chroma).min(1.);
+            let remainder = 1. - saturation;
+            scale *= (1. + self.vibrance * remainder * remainder).max(0.);
+        }
+        let rotated_a = (a * self.hue_cos - b * self.hue_sin) * scale;
+        let rotated_b = (a * self.hue_sin + b * self.hue_cos) * scale;
+        (lightness, rotated_a, rotated_b)
+    }
+}
+
+#[inline]
+fn adjust_oklab_pixel(rgb: Rgb<f32>, parameters: &AdjustmentParameters) -> Rgb<f32> {
+    let oklab = Oklab::from_linear_rgb(Rgb::<f32>::new(
+        rgb.r * parameters.exposure_gain,
+        rgb.g * parameters.exposure_gain,
+        rgb.b * parameters.exposure_gain,
+    ));
+    let (l, a, b) = parameters.adjust(oklab.l, oklab.a, oklab.b);
+    Oklab::new(l, a, b).to_linear_rgb()
+}
+
+#[inline]
+fn adjust_jzazbz_pixel(
+    rgb: Rgb<f32>,
+    parameters: &AdjustmentParameters,
+    white_jz: f32,
+) -> Rgb<f32> {
+    let exposed = Rgb::<f32>::new(
+        rgb.r * parameters.exposure_gain,
+        rgb.g * parameters.exposure_gain,
+        rgb.b * parameters.exposure_gain,
+    );
+    let xyz = Xyz::from_linear_rgb(exposed, &SRGB_TO_XYZ_D65);
+    let jzazbz = Jzazbz::from_xyz_with_display_luminance(xyz, JZAZBZ_DISPLAY_LUMINANCE);
+    let (jz, az, bz) = parameters.adjust(jzazbz.jz / white_jz, jzazbz.az, jzazbz.bz);
+    Jzazbz::new_with_luminance(jz * white_jz, az, bz, JZAZBZ_DISPLAY_LUMINANCE)
+        .to_xyz()
+        .to_linear_rgb(&XYZ_TO_SRGB_D65)
+}
+
+/// Adjusts row of linear interleaved 4 channels pixels in place
+#[allow(clippy::type_complexity)]
+fn adjust_linear_row<const CHANNELS_CONFIGURATION: u8>(
+    row: &mut [f32],
+    width: u32,
+    parameters: &AdjustmentParameters,
+    space: AdjustmentSpace,
+    white_jz: f32,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+
+    let mut _wide_row_handle: Option<
+        unsafe fn(usize, u32, *mut f32, &AdjustmentParameters) -> usize,
+    > = None;
+
+    if space == AdjustmentSpace::Oklch {
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            _wide_row_handle = Some(neon_adjust_colors_row::<CHANNELS_CONFIGURATION>);
+        }
+
+        #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+        if std::arch::is_x86_feature_detected!("sse4.1") {
+            _wide_row_handle = Some(sse_adjust_colors_row::<CHANNELS_CONFIGURATION>);
+        }
+    }
+
+    let mut _cx = 0usize;
+    if let Some(dispatcher) = _wide_row_handle {
+        _cx = unsafe { dispatcher(_cx, width, row.as_mut_ptr(), parameters) };
+    }
+
+    for pixel in row
+        .chunks_exact_mut(channels)
+        .skip(_cx)
+        .take(width as usize - _cx)
+    {
+        let rgb = Rgb::<f32>::new(
+            pixel[image_configuration.get_r_channel_offset()],
+            pixel[image_configuration.get_g_channel_offset()],
+            pixel[image_configuration.get_b_channel_offset()],
+        );
+        let adjusted = match space {
+            AdjustmentSpace::Oklch => adjust_oklab_pixel(rgb, parameters),
+            AdjustmentSpace::Jzczhz => adjust_jzazbz_pixel(rgb, parameters, white_jz),
+        };
+        pixel[image_configuration.get_r_channel_offset()] = adjusted.r;
+        pixel[image_configuration.get_g_channel_offset()] = adjusted.g;
+        pixel[image_configuration.get_b_channel_offset()] = adjusted.b;
+    }
+}
+
+fn white_jz() -> f32 {
+    let white = Xyz::from_linear_rgb(Rgb::<f32>::new(1., 1., 1.), &SRGB_TO_XYZ_D65);
+    Jzazbz::from_xyz_with_display_luminance(white, JZAZBZ_DISPLAY_LUMINANCE).jz
+}
+
+fn adjust_colors_u8<const CHANNELS_CONFIGURATION: u8>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    adjustments: &ColorAdjustments,
+    transfer_function: TransferFunction,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+    let parameters = AdjustmentParameters::new(adjustments);
+    let white_jz = white_jz();
+
+    let mut linearize_table = [0f32; 256];
+    for (i, value) in linearize_table.iter_mut().enumerate() {
+        *value = transfer_function.linearize(i as f32 * (1. / 255.));
+    }
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = dst
+            .par_chunks_exact_mut(dst_stride as usize)
+            .zip(src.par_chunks_exact(src_stride as usize))
+            .take(height as usize);
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = dst
+            .chunks_exact_mut(dst_stride as usize)
+            .zip(src.chunks_exact(src_stride as usize))
+            .take(height as usize);
+    }
+
+    iter.for_each_row(width as usize, |(dst_row, src_row)| {
+        let mut linear_row = vec![0f32; width as usize * channels];
+        for (linear, &src) in linear_row
+            .iter_mut()
+            .zip(src_row.iter().take(width as usize * channels))
+        {
+            *linear = linearize_table[src as usize];
+        }
+
+        adjust_linear_row::<CHANNELS_CONFIGURATION>(
+            &mut linear_row,
+            width,
+            &parameters,
+            adjustments.space,
+            white_jz,
+        );
+
+        for ((dst, src), linear) in dst_row
+            .chunks_exact_mut(channels)
+            .zip(src_row.chunks_exact(channels))
+            .zip(linear_row.chunks_exact(channels))
+            .take(width as usize)
+        {
+            let encode = |v: f32| -> u8 {
+                (transfer_function.gamma(v.max(0.).min(1.)) * 255.)
+                    .round()
+                    .min(255.) as u8
+            };
+            dst[image_configuration.get_r_channel_offset()] =
+                encode(linear[image_configuration.get_r_channel_offset()]);
+            dst[image_configuration.get_g_channel_offset()] =
+                encode(linear[image_configuration.get_g_channel_offset()]);
+            dst[image_configuration.get_b_channel_offset()] =
+                encode(linear[image_configuration.get_b_channel_offset()]);
+            if image_configuration.has_alpha() {
+                dst[image_configuration.get_a_channel_offset()] =
+                    src[image_configuration.get_a_channel_offset()];
+            }
+        }
+    });
+}
+
+fn adjust_colors_f32<const CHANNELS_CONFIGURATION: u8>(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    adjustments: &ColorAdjustments,
+    transfer_function: TransferFunction,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+    let parameters = AdjustmentParameters::new(adjustments);
+    let white_jz = white_jz();
+
+    let src_row_length = src_stride as usize / std::mem::size_of::<f32>();
+    let dst_row_length = dst_stride as usize / std::mem::size_of::<f32>();
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = dst
+            .par_chunks_exact_mut(dst_row_length)
+            .zip(src.par_chunks_exact(src_row_length))
+            .take(height as usize);
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = dst
+            .chunks_exact_mut(dst_row_length)
+            .zip(src.chunks_exact(src_row_length))
+            .take(height as usize);
+    }
+
+    iter.for_each_row(width as usize, |(dst_row, src_row)| {
+        let row_length = width as usize * channels;
+        let dst_row = &mut dst_row[..row_length];
+        for (dst, src) in dst_row
+            .chunks_exact_mut(channels)
+            .zip(src_row.chunks_exact(channels))
+        {
+            dst[image_configuration.get_r_channel_offset()] =
+                transfer_function.linearize(src[image_configuration.get_r_channel_offset()]);
+            dst[image_configuration.get_g_channel_offset()] =
+                transfer_function.linearize(src[image_configuration.get_g_channel_offset()]);
+            dst[image_configuration.get_b_channel_offset()] =
+                transfer_function.linearize(src[image_configuration.get_b_channel_offset()]);
+            if image_configuration.has_alpha() {
+                dst[image_configuration.get_a_channel_offset()] =
+                    src[image_configuration.get_a_channel_offset()];
+            }
+        }
+
+        adjust_linear_row::<CHANNELS_CONFIGURATION>(
+            dst_row,
+            width,
+            &parameters,
+            adjustments.space,
+            white_jz,
+        );
+
+        for dst in dst_row.chunks_exact_mut(channels) {
+            for offset in [
+                image_configuration.get_r_channel_offset(),
+                image_configuration.get_g_channel_offset(),
+                image_configuration.get_b_channel_offset(),
+            ] {
+                dst[offset] = transfer_function.gamma(dst[offset].max(0.));
+            }
+        }
+    });
+}
+
+/// This function applies exposure, lightness curve, vibrance, chroma and hue adjustments to RGBA image in one pass, alpha is preserved
+///
+/// Exposure is applied in linear light, everything else in perceptual space from `adjustments`,
+/// colours falling out of gamut are clipped
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGBA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `adjustments` - Adjustments to apply
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn rgba_adjust_colors(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    adjustments: &ColorAdjustments,
+    transfer_function: TransferFunction,
+) {
+    adjust_colors_u8::<{ ImageConfiguration::Rgba as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        adjustments,
+        transfer_function,
+    );
+}
+
+/// This function applies exposure, lightness curve, vibrance, chroma and hue adjustments to BGRA image in one pass, alpha is preserved
+///
+/// Exposure is applied in linear light, everything else in perceptual space from `adjustments`,
+/// colours falling out of gamut are clipped
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGRA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `adjustments` - Adjustments to apply
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn bgra_adjust_colors(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    adjustments: &ColorAdjustments,
+    transfer_function: TransferFunction,
+) {
+    adjust_colors_u8::<{ ImageConfiguration::Bgra as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        adjustments,
+        transfer_function,
+    );
+}
+
+/// This function applies exposure, lightness curve, vibrance, chroma and hue adjustments to RGBA f32 image in one pass, alpha is preserved
+///
+/// Exposure is applied in linear light, everything else in perceptual space from `adjustments`,
+/// negative values after the adjustment are clipped, values above 1 are kept
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGBA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `adjustments` - Adjustments to apply
+/// * `transfer_function` - Transfer function of the data, pick `Linear` for linear light
+pub fn rgba_f32_adjust_colors(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    adjustments: &ColorAdjustments,
+    transfer_function: TransferFunction,
+) {
+    adjust_colors_f32::<{ ImageConfiguration::Rgba as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        adjustments,
+        transfer_function,
+    );
+}
+
+/// This function applies exposure, lightness curve, vibrance, chroma and hue adjustments to BGRA f32 image in one pass, alpha is preserved
+///
+/// Exposure is applied in linear light, everything else in perceptual space from `adjustments`,
+/// negative values after the adjustment are clipped, values above 1 are kept
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGRA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `adjustments` - Adjustments to apply
+/// * `transfer_function` - Transfer function of the data, pick `Linear` for linear light
+pub fn bgra_f32_adjust_colors(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    adjustments: &ColorAdjustments,
+    transfer_function: TransferFunction,
+) {
+    adjust_colors_f32::<{ ImageConfiguration::Bgra as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        adjustments,
+        transfer_function,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_adjustments_keep_every_u8_value() {
+        let width = 256u32;
+        let src: Vec<u8> = (0..width as usize * 4)
+            .map(|i| match i % 4 {
+                0 => (i / 4) as u8,
+                1 => (255 - i / 4) as u8,
+                2 => (i / 4 * 7) as u8,
+                _ => (i / 4 * 3) as u8,
+            })
+            .collect();
+        let mut dst = vec![0u8; src.len()];
+        rgba_adjust_colors(
+            &src,
+            width * 4,
+            &mut dst,
+            width * 4,
+            width,
+            1,
+            &ColorAdjustments::default(),
+            TransferFunction::Srgb,
+        );
+        assert_eq!(dst, src);
+    }
+}
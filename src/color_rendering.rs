@@ -0,0 +1,414 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+//! Colour rendition metrics of light sources.
+//!
+//! CIE 13.3-1995 test colour samples TCS01..TCS14 are bundled with the crate, see [Spectrum::cie_test_color_samples].
+//! The [TM30_EVALUATION_SAMPLES] colour evaluation samples from IES TM-30-20 calculator are not
+//! and should be supplied by caller, sampled at 5nm or resampled with [Spectrum::new].
+use crate::cct::{xyz_to_uv, CctMethod, ColorTemperature};
+use crate::chromatic_adaptation::CAT02;
+use crate::matrix::{matrix_inverse, matrix_mul, matrix_vec_mul};
+use crate::spectral::{Spectrum, StandardObserver};
+use crate::Xyz;
+
+/// Number of CIE 13.3 samples general colour rendering index Ra is averaged over
+pub const CRI_GENERAL_SAMPLES: usize = 8;
+
+/// Number of CIE 13.3 test colour samples, R1..R14 are defined for them
+pub const CRI_TEST_SAMPLES: usize = 14;
+
+/// Number of IES TM-30-20 colour evaluation samples Rf and Rg are defined for
+pub const TM30_EVALUATION_SAMPLES: usize = 99;
+
+/// Number of hue bins TM-30 gamut index is computed over
+const TM30_HUE_BINS: usize = 16;
+
+/// Colour rendering index of a light source as CIE 13.3 defines it
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColorRenderingIndex {
+    /// General colour rendering index Ra, mean of the first eight special indices
+    pub ra: f32,
+    /// Special colour rendering indices Ri for every supplied sample, in the same order
+    pub special: Vec<f32>,
+    /// Colour temperature of the source, CIE 13.3 considers result meaningful only when |Duv| < 0.0054
+    pub temperature: ColorTemperature,
+}
+
+/// IES TM-30-20 colour fidelity and gamut indices
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Tm30Metrics {
+    /// Fidelity index Rf
+    pub fidelity: f32,
+    /// Gamut index Rg
+    pub gamut: f32,
+    /// Fidelity index Rf,i for every supplied sample, in the same order
+    pub sample_fidelity: Vec<f32>,
+    /// Colour temperature of the source
+    pub temperature: ColorTemperature,
+}
+
+#[inline]
+fn uv(xyz: Xyz) -> (f32, f32) {
+    let (u, v) = xyz_to_uv(xyz);
+    (u as f32, v as f32)
+}
+
+/// Chromaticity coordinates c and d of CIE 13.3 von Kries adaptation
+#[inline]
+fn von_kries_cd(u: f32, v: f32) -> (f32, f32) {
+    ((4. - u - 10. * v) / v, (1.708 * v + 0.404 - 1.481 * u) / v)
+}
+
+/// CIE 1964 U*V*W* of a sample
+#[inline]
+fn uvw(y: f32, u: f32, v: f32, white_u: f32, white_v: f32) -> [f32; 3] {
+    let w = 25. * y.max(0.).cbrt() - 17.;
+    [13. * w * (u - white_u), 13. * w * (v - white_v), w]
+}
+
+/// Computes colour rendering index of light source with spectral power distribution `source`
+/// over CIE 13.3 test colour samples TCS01..TCS14, special indices are R1..R14.
+///
+/// Reference is Planckian radiator below 5000K and CIE daylight above with the same CCT,
+/// CIE 1931 2° observer is used as CIE 13.3 requires
+///
+/// # Arguments
+/// * `source` - Spectral power distribution of the light source
+pub fn color_rendering_index(source: &Spectrum) -> ColorRenderingIndex {
+    color_rendering_index_with_samples(source, &Spectrum::cie_test_color_samples())
+}
+
+/// Computes colour rendering index of light source with spectral power distribution `source`
+/// over custom test colour samples.
+///
+/// # Arguments
+/// * `source` - Spectral power distribution of the light source
+/// * `samples` - Spectral reflectances of test colour samples, Ra is averaged over the first [CRI_GENERAL_SAMPLES]
+pub fn color_rendering_index_with_samples(
+    source: &Spectrum,
+    samples: &[Spectrum],
+) -> ColorRenderingIndex {
+    let observer = StandardObserver::Cie1931TwoDegree;
+    let source_white = source.emission_to_xyz(observer);
+    let temperature = ColorTemperature::from_xyz(source_white, CctMethod::Ohno2013);
+    let reference = if temperature.kelvin < 5000. {
+        Spectrum::blackbody(temperature.kelvin)
+    } else {
+        Spectrum::daylight(temperature.kelvin)
+    };
+    let reference_white = reference.emission_to_xyz(observer);
+
+    let (uk, vk) = uv(source_white);
+    let (ur, vr) = uv(reference_white);
+    let (ck, dk) = von_kries_cd(uk, vk);
+    let (cr, dr) = von_kries_cd(ur, vr);
+
+    let special = samples
+        .iter()
+        .map(|sample| {
+            let test = sample.to_xyz(source, observer).scale(100.);
+            let (u, v) = uv(test);
+            let (c, d) = von_kries_cd(u, v);
+            // Test sample is adapted to reference white, source itself moves into reference white
+            let denominator = 16.518 + 1.481 * cr / ck * c - dr / dk * d;
+            let adapted_u = (10.872 + 0.404 * cr / ck * c - 4. * dr / dk * d) / denominator;
+            let adapted_v = 5.520 / denominator;
+            let test_uvw = uvw(test.y, adapted_u, adapted_v, ur, vr);
+
+            let reference_xyz = sample.to_xyz(&reference, observer).scale(100.);
+            let (u, v) = uv(reference_xyz);
+            let reference_uvw = uvw(reference_xyz.y, u, v, ur, vr);
+
+            let delta_e = ((test_uvw[0] - reference_uvw[0]).powi(2)
+                + (test_uvw[1] - reference_uvw[1]).powi(2)
+                + (test_uvw[2] - reference_uvw[2]).powi(2))
+            .sqrt();
+            100. - 4.6 * delta_e
+        })
+        .collect::<Vec<f32>>();
+
+    let general = special
+        .iter()
+        .take(CRI_GENERAL_SAMPLES)
+        .collect::<Vec<&f32>>();
+    let ra = if general.is_empty() {
+        0.
+    } else {
+        general.iter().copied().sum::<f32>() / general.len() as f32
+    };
+
+    ColorRenderingIndex {
+        ra,
+        special,
+        temperature,
+    }
+}
+
+/// Hunt-Pointer-Estevez cone response matrix used by CIECAM02
+const HPE: [[f32; 3]; 3] = [
+    [0.38971, 0.68898, -0.07868],
+    [-0.22981, 1.18340, 0.04641],
+    [0., 0., 1.],
+];
+
+/// CIECAM02 viewing conditions TM-30 prescribes, average surround with full adaptation
+struct Cam02 {
+    white: [f32; 3],
+    hpe_from_cat02: [[f32; 3]; 3],
+    fl: f32,
+    n: f32,
+    nbb: f32,
+    z: f32,
+    aw: f32,
+}
+
+impl Cam02 {
+    const ADAPTING_LUMINANCE: f32 = 100.;
+    const BACKGROUND_LUMINANCE: f32 = 20.;
+    const SURROUND_C: f32 = 0.69;
+    const SURROUND_NC: f32 = 1.;
+
+    fn new(white: Xyz) -> Cam02 {
+        let la = Cam02::ADAPTING_LUMINANCE;
+        let k = 1. / (5. * la + 1.);
+        let k4 = k * k * k * k;
+        let fl = 0.2 * k4 * (5. * la) + 0.1 * (1. - k4).powi(2) * (5. * la).cbrt();
+        let n = Cam02::BACKGROUND_LUMINANCE / white.y;
+        let nbb = 0.725 * n.powf(-0.2);
+        let z = 1.48 + n.sqrt();
+        let cat02_inverse = matrix_inverse(&CAT02).unwrap();
+        let white_rgb = matrix_vec_mul(&CAT02, [white.x, white.y, white.z]);
+        let mut cam = Cam02 {
+            white: white_rgb,
+            hpe_from_cat02: matrix_mul(&HPE, &cat02_inverse),
+            fl,
+            n,
+            nbb,
+            z,
+            aw: 0.,
+        };
+        let adapted_white = cam.post_adaptation([white.x, white.y, white.z]);
+        cam.aw = cam.achromatic(&adapted_white);
+        cam
+    }
+
+    fn compress(&self, v: f32) -> f32 {
+        let p = (self.fl * v.abs() / 100.).powf(0.42);
+        400. * p / (p + 27.13) * v.signum() + 0.1
+    }
+
+    /// Post adaptation cone responses with degree of adaptation 1
+    fn post_adaptation(&self, xyz: [f32; 3]) -> [f32; 3] {
+        let rgb = matrix_vec_mul(&CAT02, xyz);
+        let y_w = 100.;
+        let adapted = [
+            y_w / self.white[0] * rgb[0],
+            y_w / self.white[1] * rgb[1],
+            y_w / self.white[2] * rgb[2],
+        ];
+        let hpe = matrix_vec_mul(&self.hpe_from_cat02, adapted);
+        [
+            self.compress(hpe[0]),
+            self.compress(hpe[1]),
+            self.compress(hpe[2]),
+        ]
+    }
+
+    #[inline]
+    fn achromatic(&self, rgb: &[f32; 3]) -> f32 {
+        (2. * rgb[0] + rgb[1] + rgb[2] / 20. - 0.305) * self.nbb
+    }
+
+    /// Returns CAM02-UCS J', a', b'
+    fn ucs(&self, xyz: Xyz) -> [f32; 3] {
+        let rgb = self.post_adaptation([xyz.x, xyz.y, xyz.z]);
+        let a = rgb[0] - 12. * rgb[1] / 11. + rgb[2] / 11.;
+        let b = (rgb[0] + rgb[1] - 2. * rgb[2]) / 9.;
+        let hue = b.atan2(a);
+        let et = 0.25 * ((hue + 2.).cos() + 3.8);
+        let achromatic = self.achromatic(&rgb);
+        let j = 100.
+            * (achromatic / self.aw)
+                .max(0.)
+                .powf(Cam02::SURROUND_C * self.z);
+        let t = (50000. / 13. * Cam02::SURROUND_NC * self.nbb * et * a.hypot(b))
+            / (rgb[0] + rgb[1] + 21. / 20. * rgb[2]);
+        let chroma =
+            t.max(0.).powf(0.9) * (j / 100.).sqrt() * (1.64 - 0.29f32.powf(self.n)).powf(0.73);
+        let colorfulness = chroma * self.fl.powf(0.25);
+        let j_ucs = 1.7 * j / (1. + 0.007 * j);
+        let m_ucs = (1. + 0.0228 * colorfulness).ln() / 0.0228;
+        [j_ucs, m_ucs * hue.cos(), m_ucs * hue.sin()]
+    }
+}
+
+#[inline]
+fn tm30_fidelity_scale(delta_e: f32) -> f32 {
+    10. * (((100. - 6.73 * delta_e) / 10.).exp() + 1.).ln()
+}
+
+/// Computes IES TM-30-20 fidelity index Rf and gamut index Rg of light source with spectral power distribution `source`.
+///
+/// Reference is Planckian radiator below 4000K, CIE daylight above 5000K and their mix in between,
+/// colours are compared in CAM02-UCS under CIE 1964 10° observer
+///
+/// # Arguments
+/// * `source` - Spectral power distribution of the light source
+/// * `samples` - Spectral reflectances of colour evaluation samples, pass all [TM30_EVALUATION_SAMPLES] CES
+///   to get Rf and Rg comparable with other TM-30 reports
+pub fn tm30_metrics(source: &Spectrum, samples: &[Spectrum]) -> Tm30Metrics {
+    let observer = StandardObserver::Cie1964TenDegree;
+    let temperature = ColorTemperature::from_xyz(
+        source.emission_to_xyz(StandardObserver::Cie1931TwoDegree),
+        CctMethod::Ohno2013,
+    );
+
+    let normalized = |spectrum: Spectrum| -> Spectrum {
+        let luminance = spectrum.luminance(observer);
+        let values = spectrum
+            .values()
+            .iter()
+            .map(|&v| v * 100. / luminance)
+            .collect::<Vec<f32>>();
+        Spectrum::new(spectrum.wavelengths(), &values)
+    };
+    let kelvin = temperature.kelvin;
+    let reference = if kelvin < 4000. {
+        Spectrum::blackbody(kelvin)
+    } else if kelvin > 5000. {
+        Spectrum::daylight(kelvin)
+    } else {
+        let planckian = normalized(Spectrum::blackbody(kelvin));
+        let daylight = normalized(Spectrum::daylight(kelvin));
+        let daylight_weight = (kelvin - 4000.) / 1000.;
+        let values = planckian
+            .wavelengths()
+            .iter()
+            .map(|&w| {
+                planckian.value_at(w) * (1. - daylight_weight)
+                    + daylight.value_at(w) * daylight_weight
+            })
+            .collect::<Vec<f32>>();
+        Spectrum::new(planckian.wavelengths(), &values)
+    };
+
+    let test_model = Cam02::new(source.emission_to_xyz(observer).scale(100.));
+    let reference_model = Cam02::new(reference.emission_to_xyz(observer).scale(100.));
+
+    let mut bins_test = [[0f32; 2]; TM30_HUE_BINS];
+    let mut bins_reference = [[0f32; 2]; TM30_HUE_BINS];
+    let mut bins_count = [0usize; TM30_HUE_BINS];
+
+    let mut delta_sum = 0f32;
+    let sample_fidelity = samples
+        .iter()
+        .map(|sample| {
+            let test = test_model.ucs(sample.to_xyz(source, observer).scale(100.));
+            let reference = reference_model.ucs(sample.to_xyz(&reference, observer).scale(100.));
+            let delta_e = ((test[0] - reference[0]).powi(2)
+                + (test[1] - reference[1]).powi(2)
+                + (test[2] - reference[2]).powi(2))
+            .sqrt();
+            delta_sum += delta_e;
+
+            let hue = reference[2]
+                .atan2(reference[1])
+                .rem_euclid(std::f32::consts::TAU);
+            let bin = ((hue / std::f32::consts::TAU * TM30_HUE_BINS as f32) as usize)
+                .min(TM30_HUE_BINS - 1);
+            bins_test[bin][0] += test[1];
+            bins_test[bin][1] += test[2];
+            bins_reference[bin][0] += reference[1];
+            bins_reference[bin][1] += reference[2];
+            bins_count[bin] += 1;
+
+            tm30_fidelity_scale(delta_e)
+        })
+        .collect::<Vec<f32>>();
+
+    let fidelity = if samples.is_empty() {
+        0.
+    } else {
+        tm30_fidelity_scale(delta_sum / samples.len() as f32)
+    };
+
+    let polygon_area = |bins: &[[f32; 2]; TM30_HUE_BINS]| -> f32 {
+        let vertices = bins
+            .iter()
+            .zip(bins_count.iter())
+            .filter(|(_, &count)| count > 0)
+            .map(|(v, &count)| [v[0] / count as f32, v[1] / count as f32])
+            .collect::<Vec<[f32; 2]>>();
+        if vertices.is_empty() {
+            return 0.;
+        }
+        let mut area = 0f32;
+        for (i, current) in vertices.iter().enumerate() {
+            let next = vertices[(i + 1) % vertices.len()];
+            area += current[0] * next[1] - next[0] * current[1];
+        }
+        area.abs() * 0.5
+    };
+    let reference_area = polygon_area(&bins_reference);
+    let gamut = if reference_area > 0. {
+        100. * polygon_area(&bins_test) / reference_area
+    } else {
+        0.
+    };
+
+    Tm30Metrics {
+        fidelity,
+        gamut,
+        sample_fidelity,
+        temperature,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StandardIlluminant;
+
+    #[test]
+    fn fluorescent_illuminants_match_published_ra() {
+        // CIE 15 general colour rendering indices of F1..F12
+        let expected = [
+            (StandardIlluminant::F1, 76.),
+            (StandardIlluminant::F2, 64.),
+            (StandardIlluminant::F3, 57.),
+            (StandardIlluminant::F4, 51.),
+            (StandardIlluminant::F5, 72.),
+            (StandardIlluminant::F6, 59.),
+            (StandardIlluminant::F7, 90.),
+            (StandardIlluminant::F8, 95.),
+            (StandardIlluminant::F9, 90.),
+            (StandardIlluminant::F10, 81.),
+            (StandardIlluminant::F11, 83.),
+            (StandardIlluminant::F12, 83.),
+        ];
+        for (illuminant, ra) in expected {
+            let index = color_rendering_index(&illuminant.spectrum());
+            assert_eq!(index.special.len(), CRI_TEST_SAMPLES);
+            assert!(
+                (index.ra - ra).abs() < 1.,
+                "{:?} Ra is {}, expected {}",
+                illuminant,
+                index.ra,
+                ra
+            );
+        }
+    }
+
+    #[test]
+    fn reference_illuminant_renders_perfectly() {
+        let index = color_rendering_index(&StandardIlluminant::A.spectrum());
+        assert!((index.ra - 100.).abs() < 0.1, "Ra is {}", index.ra);
+        for special in index.special {
+            assert!((special - 100.).abs() < 0.1, "Ri is {}", special);
+        }
+    }
+}
@@ -0,0 +1,250 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::gamma_curves::TransferFunction;
+use crate::image::ImageConfiguration;
+use crate::{Xyz, SRGB_TO_XYZ_D65, WHITE_POINT_D65};
+
+/// Storage type of the samples of an image
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SampleType {
+    /// 8 bit unsigned integers, full range 0..=255
+    U8,
+    /// 16 bit unsigned integers holding `bit_depth` significant bits, 1..=16
+    U16 { bit_depth: u32 },
+    /// 32 bit floats
+    F32,
+}
+
+impl SampleType {
+    /// Returns the value representing 1.0 for integer samples, and 1.0 for floats
+    #[inline]
+    pub fn max_value(&self) -> f32 {
+        match self {
+            SampleType::U8 => 255f32,
+            SampleType::U16 { bit_depth } => ((1u32 << *bit_depth) - 1) as f32,
+            SampleType::F32 => 1f32,
+        }
+    }
+}
+
+/// Colour model in which the channels of an image are expressed
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ColorModel {
+    /// Transfer encoded RGB with the primaries of the descriptor matrix
+    Rgb,
+    /// CIE XYZ, Y of the white point is 1
+    Xyz,
+    /// CIE L\*a\*b\*
+    Lab,
+    /// CIE L\*u\*v\*
+    Luv,
+    /// CIE LCh(uv), cylindrical form of [ColorModel::Luv]
+    Lch,
+    /// Oklab
+    Oklab,
+    /// Oklch, cylindrical form of [ColorModel::Oklab]
+    Oklch,
+    /// Jzazbz, scaled by the descriptor display luminance
+    Jzazbz,
+    /// Jzczhz, cylindrical form of [ColorModel::Jzazbz]
+    Jzczhz,
+    /// l-alpha-beta
+    LAlphaBeta,
+    /// HSV of the transfer encoded RGB, hue in degrees, saturation and value in [0, 1]
+    Hsv,
+    /// HSL of the transfer encoded RGB, hue in degrees, saturation and lightness in [0, 1]
+    Hsl,
+}
+
+impl ColorModel {
+    /// Returns true when the model is defined against D65 and ignores the descriptor white point
+    #[inline]
+    pub const fn has_fixed_white_point(&self) -> bool {
+        matches!(
+            self,
+            ColorModel::Oklab
+                | ColorModel::Oklch
+                | ColorModel::Jzazbz
+                | ColorModel::Jzczhz
+                | ColorModel::LAlphaBeta
+        )
+    }
+
+    /// Returns true when the model channels are derived from transfer encoded RGB
+    #[inline]
+    pub const fn is_rgb_based(&self) -> bool {
+        matches!(self, ColorModel::Rgb | ColorModel::Hsv | ColorModel::Hsl)
+    }
+
+    /// Returns true when the model may be stored in gray layouts, RGB as transfer encoded
+    /// luminance and lightness based models as their lightness channel alone
+    #[inline]
+    pub const fn supports_gray(&self) -> bool {
+        matches!(
+            self,
+            ColorModel::Rgb
+                | ColorModel::Lab
+                | ColorModel::Luv
+                | ColorModel::Lch
+                | ColorModel::Oklab
+                | ColorModel::Oklch
+                | ColorModel::Jzazbz
+                | ColorModel::Jzczhz
+        )
+    }
+}
+
+/// Complete description of an image colour encoding.
+///
+/// Channels of non RGB models are stored in the positions of R, G and B of the layout,
+/// so [ImageConfiguration::Rgba] with [ColorModel::Lab] holds L, a, b and alpha.
+/// Gray layouts hold luminance Y of the primaries encoded with transfer function for [ColorModel::Rgb],
+/// or lightness alone for models listed by [ColorModel::supports_gray], chroma of gray source is zero.
+/// Integer samples are only supported for [ColorModel::Rgb].
+#[derive(Debug, Copy, Clone)]
+pub struct ColorSpace {
+    /// Interleaved channel layout
+    pub layout: ImageConfiguration,
+    /// Storage type of the samples
+    pub sample_type: SampleType,
+    /// Colour model of the channels
+    pub model: ColorModel,
+    /// Transfer function of RGB based models
+    pub transfer_function: TransferFunction,
+    /// Transformation matrix from linear RGB to XYZ for RGB based models
+    pub matrix: [[f32; 3]; 3],
+    /// Adopted white point, Y normalized to 1
+    pub white_point: Xyz,
+    /// Display luminance in nits used by Jzazbz and Jzczhz
+    pub display_luminance: f32,
+}
+
+impl ColorSpace {
+    /// Creates descriptor of `model` with sRGB primaries, sRGB transfer function, D65 white point
+    /// and 200 nits display luminance
+    ///
+    /// # Arguments
+    /// * `layout` - Interleaved channel layout
+    /// * `sample_type` - Storage type of the samples
+    /// * `model` - Colour model of the channels
+    pub fn new(layout: ImageConfiguration, sample_type: SampleType, model: ColorModel) -> Self {
+        ColorSpace {
+            layout,
+            sample_type,
+            model,
+            transfer_function: TransferFunction::Srgb,
+            matrix: SRGB_TO_XYZ_D65,
+            white_point: WHITE_POINT_D65,
+            display_luminance: 200f32,
+        }
+    }
+
+    /// Creates descriptor of sRGB image
+    pub fn srgb(layout: ImageConfiguration, sample_type: SampleType) -> Self {
+        ColorSpace::new(layout, sample_type, ColorModel::Rgb)
+    }
+
+    /// Creates descriptor of RGB image with arbitrary primaries
+    ///
+    /// # Arguments
+    /// * `matrix` - Transformation matrix from linear RGB to XYZ
+    /// * `transfer_function` - Transfer function of the encoding
+    /// * `white_point` - White point the matrix maps RGB white to
+    pub fn rgb(
+        layout: ImageConfiguration,
+        sample_type: SampleType,
+        matrix: [[f32; 3]; 3],
+        transfer_function: TransferFunction,
+        white_point: Xyz,
+    ) -> Self {
+        ColorSpace {
+            matrix,
+            transfer_function,
+            white_point,
+            ..ColorSpace::new(layout, sample_type, ColorModel::Rgb)
+        }
+    }
+
+    /// Returns white point the colour model is actually relative to
+    #[inline]
+    pub fn model_white_point(&self) -> Xyz {
+        if self.model.has_fixed_white_point() {
+            WHITE_POINT_D65
+        } else {
+            self.white_point
+        }
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+    impl Sealed for f32 {}
+}
+
+/// Sample types accepted by generic image converters, implemented for `u8`, `u16` and `f32`
+pub trait ImageSample: Copy + Default + Send + Sync + 'static + private::Sealed {
+    /// Returns true if `sample_type` is stored as `Self`
+    fn is_stored_as(sample_type: SampleType) -> bool;
+    /// Widens sample into float without scaling
+    fn to_f32(self) -> f32;
+    /// Narrows float into sample, integers are rounded and saturated
+    fn from_f32(value: f32) -> Self;
+}
+
+impl ImageSample for u8 {
+    #[inline]
+    fn is_stored_as(sample_type: SampleType) -> bool {
+        sample_type == SampleType::U8
+    }
+
+    #[inline(always)]
+    fn to_f32(self) -> f32 {
+        self as f32
+    }
+
+    #[inline(always)]
+    fn from_f32(value: f32) -> Self {
+        value.round().max(0f32).min(255f32) as u8
+    }
+}
+
+impl ImageSample for u16 {
+    #[inline]
+    fn is_stored_as(sample_type: SampleType) -> bool {
+        matches!(sample_type, SampleType::U16 { .. })
+    }
+
+    #[inline(always)]
+    fn to_f32(self) -> f32 {
+        self as f32
+    }
+
+    #[inline(always)]
+    fn from_f32(value: f32) -> Self {
+        value.round().max(0f32).min(65535f32) as u16
+    }
+}
+
+impl ImageSample for f32 {
+    #[inline]
+    fn is_stored_as(sample_type: SampleType) -> bool {
+        sample_type == SampleType::F32
+    }
+
+    #[inline(always)]
+    fn to_f32(self) -> f32 {
+        self
+    }
+
+    #[inline(always)]
+    fn from_f32(value: f32) -> Self {
+        value
+    }
+}
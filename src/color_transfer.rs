@@ -0,0 +1,397 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::gamma_curves::TransferFunction;
+use crate::image::ImageConfiguration;
+use crate::image_to_lalphabeta::channels_to_lalphabeta;
+use crate::image_to_oklab::{channels_to_oklab, OklabTarget};
+use crate::lalphabeta_to_image::lalphabeta_to_image;
+use crate::oklab_to_image::oklab_to_image;
+use crate::threading::ForEachRow;
+#[cfg(feature = "rayon")]
+use rayon::prelude::ParallelSliceMut;
+
+/// Colour space where image statistics are matched
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
+pub enum ColorTransferSpace {
+    /// *lαβ* as Reinhard et al. "Color Transfer between Images" defines it
+    #[default]
+    LAlphaBeta,
+    Oklab,
+}
+
+/// Converts image into colour transfer space keeping `channels` per pixel in tightly packed storage
+fn image_to_transfer_space<const CHANNELS_CONFIGURATION: u8>(
+    src: &[u8],
+    src_stride: u32,
+    width: u32,
+    height: u32,
+    space: ColorTransferSpace,
+    transfer_function: TransferFunction,
+) -> Vec<f32> {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+    let mut converted = vec![0f32; width as usize * height as usize * channels];
+    let converted_stride = width * channels as u32 * std::mem::size_of::<f32>() as u32;
+    match space {
+        ColorTransferSpace::LAlphaBeta => channels_to_lalphabeta::<CHANNELS_CONFIGURATION>(
+            src,
+            src_stride,
+            &mut converted,
+            converted_stride,
+            width,
+            height,
+            transfer_function,
+        ),
+        ColorTransferSpace::Oklab => {
+            channels_to_oklab::<CHANNELS_CONFIGURATION, { OklabTarget::Oklab as u8 }>(
+                src,
+                src_stride,
+                &mut converted,
+                converted_stride,
+                width,
+                height,
+                transfer_function,
+                false,
+            )
+        }
+    }
+    converted
+}
+
+/// Returns per channel mean and standard deviation, fully transparent pixels are skipped
+fn channel_statistics(
+    converted: &[f32],
+    image_configuration: ImageConfiguration,
+) -> ([f64; 3], [f64; 3]) {
+    let channels = image_configuration.get_channels_count();
+    let mut sum = [0f64; 3];
+    let mut sum_squared = [0f64; 3];
+    let mut count = 0usize;
+    for pixel in converted.chunks_exact(channels) {
+        if image_configuration.has_alpha() && pixel[3] == 0. {
+            continue;
+        }
+        for c in 0..3 {
+            let value = pixel[c] as f64;
+            sum[c] += value;
+            sum_squared[c] += value * value;
+        }
+        count += 1;
+    }
+    if count == 0 {
+        return ([0.; 3], [0.; 3]);
+    }
+    let recip_count = 1. / count as f64;
+    let mut mean = [0f64; 3];
+    let mut deviation = [0f64; 3];
+    for c in 0..3 {
+        mean[c] = sum[c] * recip_count;
+        deviation[c] = (sum_squared[c] * recip_count - mean[c] * mean[c])
+            .max(0.)
+            .sqrt();
+    }
+    (mean, deviation)
+}
+
+fn transfer_colors<const CHANNELS_CONFIGURATION: u8>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    reference: &[u8],
+    reference_stride: u32,
+    reference_width: u32,
+    reference_height: u32,
+    space: ColorTransferSpace,
+    strength: f32,
+    transfer_function: TransferFunction,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+
+    let reference_converted = image_to_transfer_space::<CHANNELS_CONFIGURATION>(
+        reference,
+        reference_stride,
+        reference_width,
+        reference_height,
+        space,
+        transfer_function,
+    );
+    let (reference_mean, reference_deviation) =
+        channel_statistics(&reference_converted, image_configuration);
+    drop(reference_converted);
+
+    let mut converted = image_to_transfer_space::<CHANNELS_CONFIGURATION>(
+        src,
+        src_stride,
+        width,
+        height,
+        space,
+        transfer_function,
+    );
+    let (source_mean, source_deviation) = channel_statistics(&converted, image_configuration);
+
+    let strength = strength.max(0.).min(1.);
+    let mut scale = [1f32; 3];
+    let mut source_offset = [0f32; 3];
+    let mut reference_offset = [0f32; 3];
+    for c in 0..3 {
+        if source_deviation[c] > 0. {
+            scale[c] = (reference_deviation[c] / source_deviation[c]) as f32;
+        }
+        source_offset[c] = source_mean[c] as f32;
+        reference_offset[c] = reference_mean[c] as f32;
+    }
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = converted.par_chunks_exact_mut(width as usize * channels);
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = converted.chunks_exact_mut(width as usize * channels);
+    }
+
+    iter.for_each_row(width as usize, |row| {
+        for pixel in row.chunks_exact_mut(channels) {
+            for c in 0..3 {
+                let matched = (pixel[c] - source_offset[c]) * scale[c] + reference_offset[c];
+                pixel[c] += (matched - pixel[c]) * strength;
+            }
+        }
+    });
+
+    let converted_stride = width * channels as u32 * std::mem::size_of::<f32>() as u32;
+    match space {
+        ColorTransferSpace::LAlphaBeta => lalphabeta_to_image::<CHANNELS_CONFIGURATION>(
+            &converted,
+            converted_stride,
+            dst,
+            dst_stride,
+            width,
+            height,
+            transfer_function,
+        ),
+        ColorTransferSpace::Oklab => {
+            oklab_to_image::<CHANNELS_CONFIGURATION, { OklabTarget::Oklab as u8 }>(
+                &converted,
+                converted_stride,
+                dst,
+                dst_stride,
+                width,
+                height,
+                transfer_function,
+                false,
+            )
+        }
+    }
+}
+
+/// This function transfers colours of reference RGB image onto source as Reinhard et al. "Color Transfer between Images" describes.
+///
+/// Mean and standard deviation of every channel of source are matched with reference in *lαβ* or Oklab.
+///
+/// # Arguments
+/// * `src` - A slice contains RGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `reference` - A slice contains RGB data of reference image
+/// * `reference_stride` - Bytes per row for reference data
+/// * `reference_width` - Reference image width
+/// * `reference_height` - Reference image height
+/// * `space` - Colour space where statistics are matched
+/// * `strength` - Strength of transfer in range [0, 1], 0 keeps source unchanged
+/// * `transfer_function` - Transfer function to linear colorspace. If you don't have specific pick `Srgb`
+pub fn rgb_color_transfer(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    reference: &[u8],
+    reference_stride: u32,
+    reference_width: u32,
+    reference_height: u32,
+    space: ColorTransferSpace,
+    strength: f32,
+    transfer_function: TransferFunction,
+) {
+    transfer_colors::<{ ImageConfiguration::Rgb as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        reference,
+        reference_stride,
+        reference_width,
+        reference_height,
+        space,
+        strength,
+        transfer_function,
+    );
+}
+
+/// This function transfers colours of reference RGBA image onto source as Reinhard et al. "Color Transfer between Images" describes.
+///
+/// Mean and standard deviation of every channel of source are matched with reference in *lαβ* or Oklab,
+/// fully transparent pixels are excluded from statistics and alpha is preserved.
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGBA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `reference` - A slice contains RGBA data of reference image
+/// * `reference_stride` - Bytes per row for reference data
+/// * `reference_width` - Reference image width
+/// * `reference_height` - Reference image height
+/// * `space` - Colour space where statistics are matched
+/// * `strength` - Strength of transfer in range [0, 1], 0 keeps source unchanged
+/// * `transfer_function` - Transfer function to linear colorspace. If you don't have specific pick `Srgb`
+pub fn rgba_color_transfer(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    reference: &[u8],
+    reference_stride: u32,
+    reference_width: u32,
+    reference_height: u32,
+    space: ColorTransferSpace,
+    strength: f32,
+    transfer_function: TransferFunction,
+) {
+    transfer_colors::<{ ImageConfiguration::Rgba as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        reference,
+        reference_stride,
+        reference_width,
+        reference_height,
+        space,
+        strength,
+        transfer_function,
+    );
+}
+
+/// This function transfers colours of reference BGRA image onto source as Reinhard et al. "Color Transfer between Images" describes.
+///
+/// Mean and standard deviation of every channel of source are matched with reference in *lαβ* or Oklab,
+/// fully transparent pixels are excluded from statistics and alpha is preserved.
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGRA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `reference` - A slice contains BGRA data of reference image
+/// * `reference_stride` - Bytes per row for reference data
+/// * `reference_width` - Reference image width
+/// * `reference_height` - Reference image height
+/// * `space` - Colour space where statistics are matched
+/// * `strength` - Strength of transfer in range [0, 1], 0 keeps source unchanged
+/// * `transfer_function` - Transfer function to linear colorspace. If you don't have specific pick `Srgb`
+pub fn bgra_color_transfer(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    reference: &[u8],
+    reference_stride: u32,
+    reference_width: u32,
+    reference_height: u32,
+    space: ColorTransferSpace,
+    strength: f32,
+    transfer_function: TransferFunction,
+) {
+    transfer_colors::<{ ImageConfiguration::Bgra as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        reference,
+        reference_stride,
+        reference_width,
+        reference_height,
+        space,
+        strength,
+        transfer_function,
+    );
+}
+
+/// This function transfers colours of reference BGR image onto source as Reinhard et al. "Color Transfer between Images" describes.
+///
+/// Mean and standard deviation of every channel of source are matched with reference in *lαβ* or Oklab.
+///
+/// # Arguments
+/// * `src` - A slice contains BGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `reference` - A slice contains BGR data of reference image
+/// * `reference_stride` - Bytes per row for reference data
+/// * `reference_width` - Reference image width
+/// * `reference_height` - Reference image height
+/// * `space` - Colour space where statistics are matched
+/// * `strength` - Strength of transfer in range [0, 1], 0 keeps source unchanged
+/// * `transfer_function` - Transfer function to linear colorspace. If you don't have specific pick `Srgb`
+pub fn bgr_color_transfer(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    reference: &[u8],
+    reference_stride: u32,
+    reference_width: u32,
+    reference_height: u32,
+    space: ColorTransferSpace,
+    strength: f32,
+    transfer_function: TransferFunction,
+) {
+    transfer_colors::<{ ImageConfiguration::Bgr as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        reference,
+        reference_stride,
+        reference_width,
+        reference_height,
+        space,
+        strength,
+        transfer_function,
+    );
+}
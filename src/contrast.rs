@@ -0,0 +1,266 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::gamma_curves::TransferFunction;
+use crate::utils::mlaf;
+use crate::{Lab, Oklch, Rgb};
+
+/// WCAG conformance level
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum WcagLevel {
+    /// Level AA, 4.5:1 for normal text and 3:1 for large text
+    Aa = 0,
+    /// Level AAA, 7:1 for normal text and 4.5:1 for large text
+    Aaa = 1,
+}
+
+/// Text size category as WCAG defines it
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum WcagTextSize {
+    /// Regular text
+    Normal = 0,
+    /// At least 18pt or 14pt bold
+    Large = 1,
+}
+
+impl WcagLevel {
+    /// Minimal contrast ratio required by the level for the text size
+    #[inline]
+    pub fn min_contrast_ratio(&self, text_size: WcagTextSize) -> f32 {
+        match (self, text_size) {
+            (WcagLevel::Aa, WcagTextSize::Normal) => 4.5,
+            (WcagLevel::Aa, WcagTextSize::Large) => 3.,
+            (WcagLevel::Aaa, WcagTextSize::Normal) => 7.,
+            (WcagLevel::Aaa, WcagTextSize::Large) => 4.5,
+        }
+    }
+}
+
+/// Contrast metric used for the lightness search
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
+pub enum ContrastMetric {
+    /// WCAG 2.x contrast ratio, target is a ratio e.g. 4.5
+    Wcag,
+    /// APCA lightness contrast, target is an absolute Lc value e.g. 60
+    Apca,
+}
+
+/// Colour space whose lightness is adjusted while chroma and hue stay fixed
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum LightnessSpace {
+    Oklch = 0,
+    Lab = 1,
+}
+
+/// Computes WCAG relative luminance of sRGB colour
+#[inline]
+pub fn relative_luminance(rgb: Rgb<u8>) -> f32 {
+    let linear = rgb.to_linear(TransferFunction::Srgb);
+    mlaf(mlaf(0.2126 * linear.r, 0.7152, linear.g), 0.0722, linear.b)
+}
+
+/// Computes WCAG 2.x contrast ratio between two sRGB colours, order doesn't matter.
+/// Result is in range [1, 21]
+#[inline]
+pub fn contrast_ratio(first: Rgb<u8>, second: Rgb<u8>) -> f32 {
+    let l1 = relative_luminance(first);
+    let l2 = relative_luminance(second);
+    let (lighter, darker) = if l1 > l2 { (l1, l2) } else { (l2, l1) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Checks if colours pass WCAG at requested level
+#[inline]
+pub fn passes_wcag(
+    foreground: Rgb<u8>,
+    background: Rgb<u8>,
+    level: WcagLevel,
+    text_size: WcagTextSize,
+) -> bool {
+    contrast_ratio(foreground, background) >= level.min_contrast_ratio(text_size)
+}
+
+/// Checks if colours pass WCAG level AA
+#[inline]
+pub fn passes_wcag_aa(foreground: Rgb<u8>, background: Rgb<u8>, text_size: WcagTextSize) -> bool {
+    passes_wcag(foreground, background, WcagLevel::Aa, text_size)
+}
+
+/// Checks if colours pass WCAG level AAA
+#[inline]
+pub fn passes_wcag_aaa(foreground: Rgb<u8>, background: Rgb<u8>, text_size: WcagTextSize) -> bool {
+    passes_wcag(foreground, background, WcagLevel::Aaa, text_size)
+}
+
+// SAPC 0.0.98G-4g constants
+const APCA_MAIN_TRC: f32 = 2.4;
+const APCA_R_COEFFICIENT: f32 = 0.2126729;
+const APCA_G_COEFFICIENT: f32 = 0.7151522;
+const APCA_B_COEFFICIENT: f32 = 0.0721750;
+const APCA_NORM_BG: f32 = 0.56;
+const APCA_NORM_TXT: f32 = 0.57;
+const APCA_REV_TXT: f32 = 0.62;
+const APCA_REV_BG: f32 = 0.65;
+const APCA_BLACK_THRESHOLD: f32 = 0.022;
+const APCA_BLACK_CLAMP: f32 = 1.414;
+const APCA_SCALE_BOW: f32 = 1.14;
+const APCA_SCALE_WOB: f32 = 1.14;
+const APCA_LO_BOW_OFFSET: f32 = 0.027;
+const APCA_LO_WOB_OFFSET: f32 = 0.027;
+const APCA_DELTA_Y_MIN: f32 = 0.0005;
+const APCA_LO_CLIP: f32 = 0.1;
+
+/// Computes APCA screen luminance `Ys` of sRGB colour
+#[inline]
+pub fn apca_luminance(rgb: Rgb<u8>) -> f32 {
+    let rgb = rgb.to_rgb_f32();
+    mlaf(
+        mlaf(
+            APCA_R_COEFFICIENT * rgb.r.powf(APCA_MAIN_TRC),
+            APCA_G_COEFFICIENT,
+            rgb.g.powf(APCA_MAIN_TRC),
+        ),
+        APCA_B_COEFFICIENT,
+        rgb.b.powf(APCA_MAIN_TRC),
+    )
+}
+
+#[inline]
+fn apca_soft_clamp(y: f32) -> f32 {
+    if y > APCA_BLACK_THRESHOLD {
+        y
+    } else {
+        y + (APCA_BLACK_THRESHOLD - y).powf(APCA_BLACK_CLAMP)
+    }
+}
+
+/// Computes APCA-W3 lightness contrast Lc (SAPC 0.0.98G) of text against background.
+///
+/// Polarity is encoded in the sign: positive values are dark text on light background,
+/// negative values are light text on dark background. Result is roughly in range [-108, 106]
+#[inline]
+pub fn apca_contrast(text: Rgb<u8>, background: Rgb<u8>) -> f32 {
+    let text_y = apca_soft_clamp(apca_luminance(text));
+    let background_y = apca_soft_clamp(apca_luminance(background));
+
+    if (background_y - text_y).abs() < APCA_DELTA_Y_MIN {
+        return 0.;
+    }
+
+    let output = if background_y > text_y {
+        let sapc = (background_y.powf(APCA_NORM_BG) - text_y.powf(APCA_NORM_TXT)) * APCA_SCALE_BOW;
+        if sapc < APCA_LO_CLIP {
+            0.
+        } else {
+            sapc - APCA_LO_BOW_OFFSET
+        }
+    } else {
+        let sapc = (background_y.powf(APCA_REV_BG) - text_y.powf(APCA_REV_TXT)) * APCA_SCALE_WOB;
+        if sapc > -APCA_LO_CLIP {
+            0.
+        } else {
+            sapc + APCA_LO_WOB_OFFSET
+        }
+    };
+    output * 100.
+}
+
+#[inline]
+fn measure_contrast(metric: ContrastMetric, foreground: Rgb<u8>, background: Rgb<u8>) -> f32 {
+    match metric {
+        ContrastMetric::Wcag => contrast_ratio(foreground, background),
+        ContrastMetric::Apca => apca_contrast(foreground, background).abs(),
+    }
+}
+
+/// Searches lightness from `start` towards `end` for the closest value reaching target
+fn search_lightness<F: Fn(f32) -> Rgb<u8>>(
+    start: f32,
+    end: f32,
+    target: f32,
+    metric: ContrastMetric,
+    background: Rgb<u8>,
+    make_color: F,
+) -> Option<(f32, Rgb<u8>)> {
+    let end_color = make_color(end);
+    if measure_contrast(metric, end_color, background) < target {
+        return None;
+    }
+    let mut failing = start;
+    let mut passing = end;
+    let mut passing_color = end_color;
+    for _ in 0..24 {
+        let middle = (failing + passing) * 0.5;
+        let color = make_color(middle);
+        if measure_contrast(metric, color, background) >= target {
+            passing = middle;
+            passing_color = color;
+        } else {
+            failing = middle;
+        }
+    }
+    Some(((passing - start).abs(), passing_color))
+}
+
+/// Adjusts lightness of foreground colour until it reaches target contrast against background.
+///
+/// Chroma and hue of foreground are kept, only lightness is changed in the selected space.
+/// The direction which requires the smallest lightness change is chosen.
+/// Returns foreground as is if it already reaches target, and `None` if target is unreachable
+///
+/// # Arguments
+/// * `foreground` - Colour to adjust
+/// * `background` - Background colour
+/// * `target` - Target contrast, WCAG ratio or absolute APCA Lc depending on metric
+/// * `metric` - Contrast metric
+/// * `space` - Colour space where lightness is adjusted
+pub fn adjust_lightness_for_contrast(
+    foreground: Rgb<u8>,
+    background: Rgb<u8>,
+    target: f32,
+    metric: ContrastMetric,
+    space: LightnessSpace,
+) -> Option<Rgb<u8>> {
+    if measure_contrast(metric, foreground, background) >= target {
+        return Some(foreground);
+    }
+
+    let oklch = foreground.to_oklch(TransferFunction::Srgb);
+    let lab = foreground.to_lab();
+
+    let (start, max_lightness) = match space {
+        LightnessSpace::Oklch => (oklch.l, 1f32),
+        LightnessSpace::Lab => (lab.l, 100f32),
+    };
+
+    let make_color = |lightness: f32| -> Rgb<u8> {
+        match space {
+            LightnessSpace::Oklch => {
+                Oklch::new(lightness, oklch.c, oklch.h).to_rgb(TransferFunction::Srgb)
+            }
+            LightnessSpace::Lab => Lab::new(lightness, lab.a, lab.b).to_rgb(),
+        }
+    };
+
+    let darker = search_lightness(start, 0., target, metric, background, make_color);
+    let lighter = search_lightness(start, max_lightness, target, metric, background, make_color);
+
+    match (darker, lighter) {
+        (Some(darker), Some(lighter)) => {
+            if darker.0 <= lighter.0 {
+                Some(darker.1)
+            } else {
+                Some(lighter.1)
+            }
+        }
+        (Some(darker), None) => Some(darker.1),
+        (None, Some(lighter)) => Some(lighter.1),
+        (None, None) => None,
+    }
+}
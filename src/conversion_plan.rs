@@ -0,0 +1,973 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::color_space::{ColorModel, ColorSpace, ImageSample, SampleType};
+use crate::convert_image::{matrices_match, whites_match, xyz_to_basis, ConversionChain};
+use crate::gamma_curves::{gamma_table_u8, linearization_table_u8, TransferFunction};
+use crate::gray_lab::{
+    gray_to_lab_impl, gray_to_lightness_table_u8, lab_to_gray_impl, lightness_to_gray_table_u8,
+};
+use crate::image::ImageConfiguration;
+use crate::image_to_jzazbz::{channels_to_jzaz_impl, channels_to_jzaz_row_handler, JzazbzTarget};
+use crate::image_to_linear::channels_to_linear_impl;
+use crate::image_to_oklab::{channels_to_oklab_impl, channels_to_oklab_row_handler, OklabTarget};
+use crate::image_to_xyz_lab::{channels_to_xyz_impl, channels_to_xyz_row_handler};
+use crate::image_view::{
+    check_image, convert_views, samples_stride, ImageMut, ImageRef, ImageRegion,
+};
+use crate::image_xyza_laba::{channels_to_xyz_with_alpha_impl, channels_to_xyza_row_handler};
+use crate::jzazbz_to_image::{jzazbz_to_image_impl, jzazbz_to_image_row_handler};
+use crate::linear_to_image::linear_to_gamma_channels_impl;
+use crate::oklab_to_image::{oklab_to_image_impl, oklab_to_image_row_handler};
+use crate::planar::{
+    convert_rows, interleaved_rows, interleaved_rows_mut, planar_rows, planar_rows_mut, SourceRow,
+    TargetRow,
+};
+use crate::threading::ForEachRow;
+use crate::threading::{current_threading, ThreadingPolicy};
+use crate::xyz_lab_to_image::{xyz_to_channels_impl, xyz_to_channels_row_handler};
+use crate::xyz_target::XyzTarget;
+use crate::xyza_laba_to_image::{xyz_with_alpha_to_channels_impl, xyza_to_channels_row_handler};
+use crate::{SRGB_TO_XYZ_D65, WHITE_POINT_D65};
+#[cfg(feature = "rayon")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "rayon")]
+use rayon::prelude::{ParallelSlice, ParallelSliceMut};
+use std::ops::Range;
+use std::slice;
+
+macro_rules! with_layout {
+    ($layout:expr, $func:ident, [$($generics:tt)*], ($($args:expr),* $(,)?)) => {
+        match $layout {
+            ImageConfiguration::Rgb => {
+                $func::<{ ImageConfiguration::Rgb as u8 }, $($generics)*>($($args),*)
+            }
+            ImageConfiguration::Rgba => {
+                $func::<{ ImageConfiguration::Rgba as u8 }, $($generics)*>($($args),*)
+            }
+            ImageConfiguration::Bgra => {
+                $func::<{ ImageConfiguration::Bgra as u8 }, $($generics)*>($($args),*)
+            }
+            ImageConfiguration::Bgr => {
+                $func::<{ ImageConfiguration::Bgr as u8 }, $($generics)*>($($args),*)
+            }
+            ImageConfiguration::Argb => {
+                $func::<{ ImageConfiguration::Argb as u8 }, $($generics)*>($($args),*)
+            }
+            ImageConfiguration::Abgr => {
+                $func::<{ ImageConfiguration::Abgr as u8 }, $($generics)*>($($args),*)
+            }
+            ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                unreachable!("Gray layouts are dispatched by with_gray_layout")
+            }
+        }
+    };
+}
+
+macro_rules! with_gray_layout {
+    ($layout:expr, $func:ident, ($($args:expr),* $(,)?)) => {
+        match $layout {
+            ImageConfiguration::Gray => $func::<{ ImageConfiguration::Gray as u8 }>($($args),*),
+            ImageConfiguration::GrayAlpha => {
+                $func::<{ ImageConfiguration::GrayAlpha as u8 }>($($args),*)
+            }
+            _ => unreachable!("Colour layouts are dispatched by with_layout"),
+        }
+    };
+}
+
+type ForwardKernel =
+    Box<dyn Fn(&[u8], u32, &mut [f32], u32, u32, u32, &ThreadingPolicy) + Send + Sync>;
+type BackwardKernel =
+    Box<dyn Fn(&[f32], u32, &mut [u8], u32, u32, u32, &ThreadingPolicy) + Send + Sync>;
+
+/// Dedicated image kernel with its tables and SIMD row handler already resolved
+enum PlanKernel {
+    /// 8 bit RGB into floating point model
+    Forward(ForwardKernel),
+    /// Floating point model into 8 bit RGB
+    Backward(BackwardKernel),
+}
+
+fn forward_xyz<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    matrix: [[f32; 3]; 3],
+    transfer_function: TransferFunction,
+    with_alpha: bool,
+) -> ForwardKernel {
+    let lut_table = linearization_table_u8(transfer_function);
+    if with_alpha {
+        let handler = channels_to_xyza_row_handler::<CHANNELS_CONFIGURATION, TARGET>();
+        Box::new(
+            move |src, src_stride, dst, dst_stride, width, height, policy| {
+                channels_to_xyz_with_alpha_impl::<CHANNELS_CONFIGURATION, TARGET>(
+                    src, src_stride, dst, dst_stride, width, height, &matrix, &lut_table, None,
+                    handler, policy,
+                )
+            },
+        )
+    } else {
+        let handler = channels_to_xyz_row_handler::<CHANNELS_CONFIGURATION, false, TARGET>();
+        Box::new(
+            move |src, src_stride, dst, dst_stride, width, height, policy| {
+                channels_to_xyz_impl::<CHANNELS_CONFIGURATION, false, TARGET>(
+                    src,
+                    src_stride,
+                    dst,
+                    dst_stride,
+                    &mut [],
+                    0,
+                    width,
+                    height,
+                    &matrix,
+                    &lut_table,
+                    None,
+                    handler,
+                    policy,
+                )
+            },
+        )
+    }
+}
+
+fn backward_xyz<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    matrix: [[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) -> BackwardKernel {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let lut_table = gamma_table_u8(transfer_function);
+    if image_configuration.has_alpha() {
+        let handler = xyza_to_channels_row_handler::<CHANNELS_CONFIGURATION, TARGET>();
+        Box::new(
+            move |src, src_stride, dst, dst_stride, width, height, policy| {
+                xyz_with_alpha_to_channels_impl::<CHANNELS_CONFIGURATION, TARGET>(
+                    src, src_stride, dst, dst_stride, width, height, &matrix, &lut_table, false,
+                    handler, policy,
+                )
+            },
+        )
+    } else {
+        let handler = xyz_to_channels_row_handler::<CHANNELS_CONFIGURATION, false, TARGET>();
+        Box::new(
+            move |src, src_stride, dst, dst_stride, width, height, policy| {
+                xyz_to_channels_impl::<CHANNELS_CONFIGURATION, false, TARGET>(
+                    src,
+                    src_stride,
+                    &[],
+                    0,
+                    dst,
+                    dst_stride,
+                    width,
+                    height,
+                    &matrix,
+                    &lut_table,
+                    false,
+                    handler,
+                    policy,
+                )
+            },
+        )
+    }
+}
+
+fn forward_oklab<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    transfer_function: TransferFunction,
+) -> ForwardKernel {
+    let lut_table = linearization_table_u8(transfer_function);
+    let handler = channels_to_oklab_row_handler::<CHANNELS_CONFIGURATION, TARGET>();
+    Box::new(
+        move |src, src_stride, dst, dst_stride, width, height, policy| {
+            channels_to_oklab_impl::<CHANNELS_CONFIGURATION, TARGET>(
+                src, src_stride, dst, dst_stride, width, height, &lut_table, None, handler, policy,
+            )
+        },
+    )
+}
+
+fn backward_oklab<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    transfer_function: TransferFunction,
+) -> BackwardKernel {
+    let lut_table = gamma_table_u8(transfer_function);
+    let handler = oklab_to_image_row_handler::<CHANNELS_CONFIGURATION, TARGET>();
+    Box::new(
+        move |src, src_stride, dst, dst_stride, width, height, policy| {
+            oklab_to_image_impl::<CHANNELS_CONFIGURATION, TARGET>(
+                src, src_stride, dst, dst_stride, width, height, &lut_table, false, handler, policy,
+            )
+        },
+    )
+}
+
+fn forward_jzazbz<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    transfer_function: TransferFunction,
+    display_luminance: f32,
+) -> ForwardKernel {
+    let lut_table = linearization_table_u8(transfer_function);
+    let handler = channels_to_jzaz_row_handler::<CHANNELS_CONFIGURATION, TARGET>();
+    Box::new(
+        move |src, src_stride, dst, dst_stride, width, height, policy| {
+            channels_to_jzaz_impl::<CHANNELS_CONFIGURATION, TARGET>(
+                src,
+                src_stride,
+                dst,
+                dst_stride,
+                width,
+                height,
+                display_luminance,
+                &lut_table,
+                handler,
+                policy,
+            )
+        },
+    )
+}
+
+fn backward_jzazbz<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    transfer_function: TransferFunction,
+    display_luminance: f32,
+) -> BackwardKernel {
+    let lut_table = gamma_table_u8(transfer_function);
+    let handler = jzazbz_to_image_row_handler::<CHANNELS_CONFIGURATION, TARGET>();
+    Box::new(
+        move |src, src_stride, dst, dst_stride, width, height, policy| {
+            jzazbz_to_image_impl::<CHANNELS_CONFIGURATION, TARGET>(
+                src,
+                src_stride,
+                dst,
+                dst_stride,
+                width,
+                height,
+                display_luminance,
+                &lut_table,
+                handler,
+                policy,
+            )
+        },
+    )
+}
+
+fn forward_linear<const CHANNELS_CONFIGURATION: u8>(
+    transfer_function: TransferFunction,
+) -> ForwardKernel {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let lut_table = linearization_table_u8(transfer_function);
+    if image_configuration.has_alpha() {
+        Box::new(
+            move |src, src_stride, dst, dst_stride, width, height, policy| {
+                channels_to_linear_impl::<CHANNELS_CONFIGURATION, true>(
+                    src, src_stride, dst, dst_stride, width, height, &lut_table, policy,
+                )
+            },
+        )
+    } else {
+        Box::new(
+            move |src, src_stride, dst, dst_stride, width, height, policy| {
+                channels_to_linear_impl::<CHANNELS_CONFIGURATION, false>(
+                    src, src_stride, dst, dst_stride, width, height, &lut_table, policy,
+                )
+            },
+        )
+    }
+}
+
+fn backward_linear<const CHANNELS_CONFIGURATION: u8>(
+    transfer_function: TransferFunction,
+) -> BackwardKernel {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let lut_table = gamma_table_u8(transfer_function);
+    if image_configuration.has_alpha() {
+        Box::new(
+            move |src, src_stride, dst, dst_stride, width, height, policy| {
+                linear_to_gamma_channels_impl::<CHANNELS_CONFIGURATION, true>(
+                    src, src_stride, dst, dst_stride, width, height, &lut_table, policy,
+                )
+            },
+        )
+    } else {
+        Box::new(
+            move |src, src_stride, dst, dst_stride, width, height, policy| {
+                linear_to_gamma_channels_impl::<CHANNELS_CONFIGURATION, false>(
+                    src, src_stride, dst, dst_stride, width, height, &lut_table, policy,
+                )
+            },
+        )
+    }
+}
+
+fn forward_lightness<const CHANNELS_CONFIGURATION: u8>(
+    transfer_function: TransferFunction,
+) -> ForwardKernel {
+    let lut_table = gray_to_lightness_table_u8(transfer_function);
+    Box::new(
+        move |src, src_stride, dst, dst_stride, width, height, policy| {
+            gray_to_lab_impl::<CHANNELS_CONFIGURATION>(
+                src, src_stride, dst, dst_stride, width, height, &lut_table, policy,
+            )
+        },
+    )
+}
+
+fn backward_lightness<const CHANNELS_CONFIGURATION: u8>(
+    transfer_function: TransferFunction,
+) -> BackwardKernel {
+    let lut_table = lightness_to_gray_table_u8(transfer_function);
+    Box::new(
+        move |src, src_stride, dst, dst_stride, width, height, policy| {
+            lab_to_gray_impl::<CHANNELS_CONFIGURATION>(
+                src, src_stride, dst, dst_stride, width, height, &lut_table, policy,
+            )
+        },
+    )
+}
+
+/// Looks for dedicated gray kernel, gray images have them for linearization
+/// and for L\* of [ColorModel::Lab] kept in the same gray layout
+fn select_gray_kernel(source: &ColorSpace, target: &ColorSpace) -> Option<PlanKernel> {
+    if source.layout != target.layout {
+        return None;
+    }
+    let (encoded, encoded_space, linear_space) = match (source.sample_type, target.sample_type) {
+        (SampleType::U8, SampleType::F32) => (true, source, target),
+        (SampleType::F32, SampleType::U8) => (false, target, source),
+        _ => return None,
+    };
+    if encoded_space.model != ColorModel::Rgb {
+        return None;
+    }
+    let supported = match linear_space.model {
+        ColorModel::Lab => {
+            whites_match(encoded_space.white_point, linear_space.white_point)
+                && whites_match(linear_space.white_point, WHITE_POINT_D65)
+        }
+        ColorModel::Rgb => {
+            linear_space.transfer_function == TransferFunction::Linear
+                && encoded_space.transfer_function != TransferFunction::Linear
+                && matrices_match(&encoded_space.matrix, &linear_space.matrix)
+                && whites_match(encoded_space.white_point, linear_space.white_point)
+        }
+        _ => false,
+    };
+    if !supported {
+        return None;
+    }
+
+    let layout = encoded_space.layout;
+    let transfer_function = encoded_space.transfer_function;
+    let lightness = linear_space.model == ColorModel::Lab;
+    if encoded {
+        let kernel = if lightness {
+            with_gray_layout!(layout, forward_lightness, (transfer_function))
+        } else {
+            with_gray_layout!(layout, forward_linear, (transfer_function))
+        };
+        Some(PlanKernel::Forward(kernel))
+    } else {
+        let kernel = if lightness {
+            with_gray_layout!(layout, backward_lightness, (transfer_function))
+        } else {
+            with_gray_layout!(layout, backward_linear, (transfer_function))
+        };
+        Some(PlanKernel::Backward(kernel))
+    }
+}
+
+/// Looks for dedicated image kernel matching both colour spaces exactly
+fn select_kernel(source: &ColorSpace, target: &ColorSpace) -> Option<PlanKernel> {
+    if source.layout.is_gray() || target.layout.is_gray() {
+        return select_gray_kernel(source, target);
+    }
+    let (encoded, encoded_space, linear_space) = match (source.sample_type, target.sample_type) {
+        (SampleType::U8, SampleType::F32) => (true, source, target),
+        (SampleType::F32, SampleType::U8) => (false, target, source),
+        _ => return None,
+    };
+    // Kernels store non RGB channels in natural order only
+    let natural_order = matches!(
+        linear_space.layout,
+        ImageConfiguration::Rgb | ImageConfiguration::Rgba
+    );
+    if encoded_space.model != ColorModel::Rgb
+        || (linear_space.model != ColorModel::Rgb && !natural_order)
+    {
+        return None;
+    }
+    let same_alpha = encoded_space.layout.has_alpha() == linear_space.layout.has_alpha();
+    let same_channels =
+        encoded_space.layout.get_channels_count() == linear_space.layout.get_channels_count();
+    let srgb_primaries = matrices_match(&encoded_space.matrix, &SRGB_TO_XYZ_D65)
+        && whites_match(encoded_space.white_point, WHITE_POINT_D65);
+
+    let supported = match linear_space.model {
+        ColorModel::Xyz | ColorModel::Lab | ColorModel::Luv | ColorModel::Lch => {
+            let whites_supported =
+                whites_match(encoded_space.white_point, linear_space.white_point)
+                    && (linear_space.model == ColorModel::Xyz
+                        || whites_match(linear_space.white_point, WHITE_POINT_D65));
+            let alpha_supported = if encoded {
+                !linear_space.layout.has_alpha() || encoded_space.layout.has_alpha()
+            } else {
+                same_alpha
+            };
+            whites_supported && alpha_supported
+        }
+        ColorModel::Oklab | ColorModel::Oklch | ColorModel::Jzazbz | ColorModel::Jzczhz => {
+            srgb_primaries && same_channels
+        }
+        ColorModel::Rgb => {
+            linear_space.layout == encoded_space.layout
+                && linear_space.transfer_function == TransferFunction::Linear
+                && encoded_space.transfer_function != TransferFunction::Linear
+                && matrices_match(&encoded_space.matrix, &linear_space.matrix)
+                && whites_match(encoded_space.white_point, linear_space.white_point)
+        }
+        _ => false,
+    };
+    if !supported {
+        return None;
+    }
+
+    let layout = encoded_space.layout;
+    let transfer_function = encoded_space.transfer_function;
+    let display_luminance = linear_space.display_luminance;
+
+    if encoded {
+        let matrix = encoded_space.matrix;
+        let with_alpha = linear_space.layout.has_alpha();
+        let kernel = match linear_space.model {
+            ColorModel::Xyz => with_layout!(
+                layout,
+                forward_xyz,
+                [{ XyzTarget::Xyz as u8 }],
+                (matrix, transfer_function, with_alpha)
+            ),
+            ColorModel::Lab => with_layout!(
+                layout,
+                forward_xyz,
+                [{ XyzTarget::Lab as u8 }],
+                (matrix, transfer_function, with_alpha)
+            ),
+            ColorModel::Luv => with_layout!(
+                layout,
+                forward_xyz,
+                [{ XyzTarget::Luv as u8 }],
+                (matrix, transfer_function, with_alpha)
+            ),
+            ColorModel::Lch => with_layout!(
+                layout,
+                forward_xyz,
+                [{ XyzTarget::Lch as u8 }],
+                (matrix, transfer_function, with_alpha)
+            ),
+            ColorModel::Oklab => with_layout!(
+                layout,
+                forward_oklab,
+                [{ OklabTarget::Oklab as u8 }],
+                (transfer_function)
+            ),
+            ColorModel::Oklch => with_layout!(
+                layout,
+                forward_oklab,
+                [{ OklabTarget::Oklch as u8 }],
+                (transfer_function)
+            ),
+            ColorModel::Jzazbz => with_layout!(
+                layout,
+                forward_jzazbz,
+                [{ JzazbzTarget::Jzazbz as u8 }],
+                (transfer_function, display_luminance)
+            ),
+            ColorModel::Jzczhz => with_layout!(
+                layout,
+                forward_jzazbz,
+                [{ JzazbzTarget::Jzczhz as u8 }],
+                (transfer_function, display_luminance)
+            ),
+            _ => with_layout!(layout, forward_linear, [], (transfer_function)),
+        };
+        Some(PlanKernel::Forward(kernel))
+    } else {
+        let matrix = xyz_to_basis(encoded_space);
+        let kernel = match linear_space.model {
+            ColorModel::Xyz => with_layout!(
+                layout,
+                backward_xyz,
+                [{ XyzTarget::Xyz as u8 }],
+                (matrix, transfer_function)
+            ),
+            ColorModel::Lab => with_layout!(
+                layout,
+                backward_xyz,
+                [{ XyzTarget::Lab as u8 }],
+                (matrix, transfer_function)
+            ),
+            ColorModel::Luv => with_layout!(
+                layout,
+                backward_xyz,
+                [{ XyzTarget::Luv as u8 }],
+                (matrix, transfer_function)
+            ),
+            ColorModel::Lch => with_layout!(
+                layout,
+                backward_xyz,
+                [{ XyzTarget::Lch as u8 }],
+                (matrix, transfer_function)
+            ),
+            ColorModel::Oklab => with_layout!(
+                layout,
+                backward_oklab,
+                [{ OklabTarget::Oklab as u8 }],
+                (transfer_function)
+            ),
+            ColorModel::Oklch => with_layout!(
+                layout,
+                backward_oklab,
+                [{ OklabTarget::Oklch as u8 }],
+                (transfer_function)
+            ),
+            ColorModel::Jzazbz => with_layout!(
+                layout,
+                backward_jzazbz,
+                [{ JzazbzTarget::Jzazbz as u8 }],
+                (transfer_function, display_luminance)
+            ),
+            ColorModel::Jzczhz => with_layout!(
+                layout,
+                backward_jzazbz,
+                [{ JzazbzTarget::Jzczhz as u8 }],
+                (transfer_function, display_luminance)
+            ),
+            _ => with_layout!(layout, backward_linear, [], (transfer_function)),
+        };
+        Some(PlanKernel::Backward(kernel))
+    }
+}
+
+/// Precomputed conversion between two colour spaces.
+///
+/// Plan resolves everything that does not depend on pixels once: transfer function tables,
+/// fused RGB to RGB matrix including its inverse and chromatic adaptation, dedicated kernel
+/// and SIMD row handler available on the current CPU.
+/// The plan is `Send + Sync`, so the same plan may be applied to many frames from many threads.
+pub struct ConversionPlan {
+    chain: ConversionChain,
+    kernel: Option<PlanKernel>,
+    threading: Option<ThreadingPolicy>,
+}
+
+impl ConversionPlan {
+    /// Creates plan converting images in `src_color_space` into `dst_color_space`
+    ///
+    /// # Panics
+    /// Panics if integer samples are requested for non RGB model or if bit depth is out of range 1..=16
+    pub fn new(src_color_space: &ColorSpace, dst_color_space: &ColorSpace) -> Self {
+        ConversionPlan {
+            chain: ConversionChain::new(src_color_space, dst_color_space),
+            kernel: select_kernel(src_color_space, dst_color_space),
+            threading: None,
+        }
+    }
+
+    /// Sets threading policy used by every apply call of the plan instead of policy of the calling thread.
+    /// The policy is carried to all rows of the image, also to rows converted on worker threads
+    pub fn with_threading(mut self, policy: ThreadingPolicy) -> Self {
+        self.threading = Some(policy);
+        self
+    }
+
+    /// Returns threading policy of the plan, if it was set.
+    /// Plan without policy takes [crate::current_threading] of the thread calling apply
+    pub fn threading(&self) -> Option<&ThreadingPolicy> {
+        self.threading.as_ref()
+    }
+
+    /// Returns policy the plan applies when called from the current thread
+    fn resolve_threading(&self) -> ThreadingPolicy {
+        self.threading.clone().unwrap_or_else(current_threading)
+    }
+
+    /// Returns descriptor of images the plan accepts
+    pub fn source(&self) -> &ColorSpace {
+        self.chain.source()
+    }
+
+    /// Returns descriptor of images the plan produces
+    pub fn target(&self) -> &ColorSpace {
+        self.chain.target()
+    }
+
+    /// This function converts image using the plan
+    ///
+    /// # Arguments
+    /// * `src` - A slice contains source image
+    /// * `src_stride` - Bytes per row for src data.
+    /// * `dst` - A mutable slice to receive converted image
+    /// * `dst_stride` - Bytes per row for dst data
+    /// * `width` - Image width
+    /// * `height` - Image height
+    ///
+    /// # Panics
+    /// Panics if sample types of slices doesn't match descriptors of the plan
+    pub fn apply<S: ImageSample, D: ImageSample>(
+        &self,
+        src: &[S],
+        src_stride: u32,
+        dst: &mut [D],
+        dst_stride: u32,
+        width: u32,
+        height: u32,
+    ) {
+        self.check_sample_types::<S, D>();
+        let src_stride_samples = check_image::<S>(
+            src.len(),
+            src_stride,
+            width,
+            height,
+            self.source().layout,
+            "Source",
+        );
+        let dst_stride_samples = check_image::<D>(
+            dst.len(),
+            dst_stride,
+            width,
+            height,
+            self.target().layout,
+            "Destination",
+        );
+
+        let policy = self.resolve_threading();
+        match &self.kernel {
+            Some(PlanKernel::Forward(kernel)) => {
+                // Sample types were validated against descriptors, so `S` is `u8` and `D` is `f32`
+                let src = unsafe { slice::from_raw_parts(src.as_ptr() as *const u8, src.len()) };
+                let dst =
+                    unsafe { slice::from_raw_parts_mut(dst.as_mut_ptr() as *mut f32, dst.len()) };
+                kernel(src, src_stride, dst, dst_stride, width, height, &policy);
+                return;
+            }
+            Some(PlanKernel::Backward(kernel)) => {
+                // Sample types were validated against descriptors, so `S` is `f32` and `D` is `u8`
+                let src = unsafe { slice::from_raw_parts(src.as_ptr() as *const f32, src.len()) };
+                let dst =
+                    unsafe { slice::from_raw_parts_mut(dst.as_mut_ptr() as *mut u8, dst.len()) };
+                kernel(src, src_stride, dst, dst_stride, width, height, &policy);
+                return;
+            }
+            None => {}
+        }
+
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let chain = &self.chain;
+        let width = width as usize;
+        let src_row_length = width * self.source().layout.get_channels_count();
+        let dst_row_length = width * self.target().layout.get_channels_count();
+
+        let iter;
+        #[cfg(feature = "rayon")]
+        {
+            iter = dst
+                .par_chunks_mut(dst_stride_samples)
+                .zip(src.par_chunks(src_stride_samples))
+                .take(height as usize);
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            iter = dst
+                .chunks_mut(dst_stride_samples)
+                .zip(src.chunks(src_stride_samples))
+                .take(height as usize);
+        }
+
+        iter.for_each_row_with(&policy, width, |(dst, src)| {
+            // Geometry was validated, so every row holds at least `width` pixels
+            let src = &src[..src_row_length];
+            let dst = &mut dst[..dst_row_length];
+            unsafe {
+                chain.convert_row(src.as_ptr(), dst.as_mut_ptr(), width);
+            }
+        });
+    }
+
+    fn check_sample_types<S: ImageSample, D: ImageSample>(&self) {
+        let source = self.source();
+        let target = self.target();
+        if !S::is_stored_as(source.sample_type) {
+            panic!(
+                "Source slice doesn't match sample type {:?}",
+                source.sample_type
+            );
+        }
+        if !D::is_stored_as(target.sample_type) {
+            panic!(
+                "Destination slice doesn't match sample type {:?}",
+                target.sample_type
+            );
+        }
+    }
+
+    /// Converts one interleaved row, sample types must be already validated
+    fn convert_row<S: ImageSample, D: ImageSample>(
+        &self,
+        src: &[S],
+        dst: &mut [D],
+        width: u32,
+        policy: &ThreadingPolicy,
+    ) {
+        let src_stride = std::mem::size_of_val(src) as u32;
+        let dst_stride = std::mem::size_of_val(dst) as u32;
+        match &self.kernel {
+            Some(PlanKernel::Forward(kernel)) => {
+                let src = unsafe { slice::from_raw_parts(src.as_ptr() as *const u8, src.len()) };
+                let dst =
+                    unsafe { slice::from_raw_parts_mut(dst.as_mut_ptr() as *mut f32, dst.len()) };
+                kernel(src, src_stride, dst, dst_stride, width, 1, policy);
+            }
+            Some(PlanKernel::Backward(kernel)) => {
+                let src = unsafe { slice::from_raw_parts(src.as_ptr() as *const f32, src.len()) };
+                let dst =
+                    unsafe { slice::from_raw_parts_mut(dst.as_mut_ptr() as *mut u8, dst.len()) };
+                kernel(src, src_stride, dst, dst_stride, width, 1, policy);
+            }
+            None => unsafe {
+                self.chain
+                    .convert_row(src.as_ptr(), dst.as_mut_ptr(), width as usize);
+            },
+        }
+    }
+
+    fn apply_rows_of<S: ImageSample, D: ImageSample>(
+        &self,
+        src_rows: Vec<SourceRow<S>>,
+        dst_rows: Vec<TargetRow<D>>,
+        width: u32,
+    ) {
+        self.check_sample_types::<S, D>();
+        let src_channels = self.source().layout.get_channels_count();
+        let dst_channels = self.target().layout.get_channels_count();
+        let policy = self.resolve_threading();
+        convert_rows(
+            src_rows,
+            src_channels,
+            dst_rows,
+            dst_channels,
+            width,
+            &policy,
+            |src, dst| self.convert_row(src, dst, width, &policy),
+        );
+    }
+
+    /// This function converts interleaved image into planar one using the plan.
+    /// Planes follow channel order of the target layout, so [ImageConfiguration::Rgb] target with
+    /// [crate::ColorModel::Lab] expects L, a and b planes.
+    ///
+    /// # Arguments
+    /// * `src` - A slice contains source image
+    /// * `src_stride` - Bytes per row for src data.
+    /// * `dst_planes` - Mutable slices to receive planes, one for every channel of the target layout
+    /// * `dst_strides` - Bytes per row of every destination plane
+    /// * `width` - Image width
+    /// * `height` - Image height
+    ///
+    /// # Panics
+    /// Panics if count of planes doesn't match layout, images doesn't fit into slices
+    /// or sample types of slices doesn't match descriptors of the plan
+    pub fn apply_to_planar<S: ImageSample, D: ImageSample>(
+        &self,
+        src: &[S],
+        src_stride: u32,
+        dst_planes: &mut [&mut [D]],
+        dst_strides: &[u32],
+        width: u32,
+        height: u32,
+    ) {
+        let src_channels = self.source().layout.get_channels_count();
+        let dst_channels = self.target().layout.get_channels_count();
+        let src_rows = interleaved_rows(src, src_stride, src_channels, width, height);
+        let dst_rows = planar_rows_mut(dst_planes, dst_strides, dst_channels, width, height);
+        self.apply_rows_of(src_rows, dst_rows, width);
+    }
+
+    /// This function converts planar image into interleaved one using the plan.
+    /// Planes follow channel order of the source layout.
+    ///
+    /// # Arguments
+    /// * `src_planes` - Slices contains planes, one for every channel of the source layout
+    /// * `src_strides` - Bytes per row of every source plane
+    /// * `dst` - A mutable slice to receive converted image
+    /// * `dst_stride` - Bytes per row for dst data
+    /// * `width` - Image width
+    /// * `height` - Image height
+    ///
+    /// # Panics
+    /// Panics if count of planes doesn't match layout, images doesn't fit into slices
+    /// or sample types of slices doesn't match descriptors of the plan
+    pub fn apply_from_planar<S: ImageSample, D: ImageSample>(
+        &self,
+        src_planes: &[&[S]],
+        src_strides: &[u32],
+        dst: &mut [D],
+        dst_stride: u32,
+        width: u32,
+        height: u32,
+    ) {
+        let src_channels = self.source().layout.get_channels_count();
+        let dst_channels = self.target().layout.get_channels_count();
+        let src_rows = planar_rows(src_planes, src_strides, src_channels, width, height);
+        let dst_rows = interleaved_rows_mut(dst, dst_stride, dst_channels, width, height);
+        self.apply_rows_of(src_rows, dst_rows, width);
+    }
+
+    /// This function converts planar image into planar one using the plan.
+    /// Planes follow channel order of the respective layouts.
+    ///
+    /// # Arguments
+    /// * `src_planes` - Slices contains planes, one for every channel of the source layout
+    /// * `src_strides` - Bytes per row of every source plane
+    /// * `dst_planes` - Mutable slices to receive planes, one for every channel of the target layout
+    /// * `dst_strides` - Bytes per row of every destination plane
+    /// * `width` - Image width
+    /// * `height` - Image height
+    ///
+    /// # Panics
+    /// Panics if count of planes doesn't match layouts, images doesn't fit into slices
+    /// or sample types of slices doesn't match descriptors of the plan
+    pub fn apply_planar<S: ImageSample, D: ImageSample>(
+        &self,
+        src_planes: &[&[S]],
+        src_strides: &[u32],
+        dst_planes: &mut [&mut [D]],
+        dst_strides: &[u32],
+        width: u32,
+        height: u32,
+    ) {
+        let src_channels = self.source().layout.get_channels_count();
+        let dst_channels = self.target().layout.get_channels_count();
+        let src_rows = planar_rows(src_planes, src_strides, src_channels, width, height);
+        let dst_rows = planar_rows_mut(dst_planes, dst_strides, dst_channels, width, height);
+        self.apply_rows_of(src_rows, dst_rows, width);
+    }
+
+    /// This function converts image view using the plan, views may be regions of larger images
+    ///
+    /// # Arguments
+    /// * `src` - Source view, layout must match source descriptor
+    /// * `dst` - Destination view, layout must match target descriptor
+    ///
+    /// # Panics
+    /// Panics if layouts or sample types of views doesn't match descriptors of the plan,
+    /// or if views have different dimensions
+    pub fn apply_views<S: ImageSample, D: ImageSample>(
+        &self,
+        src: &ImageRef<S>,
+        dst: &mut ImageMut<D>,
+    ) {
+        if src.layout() != self.source().layout {
+            panic!(
+                "Source view layout {:?} doesn't match plan layout {:?}",
+                src.layout(),
+                self.source().layout
+            );
+        }
+        if dst.layout() != self.target().layout {
+            panic!(
+                "Destination view layout {:?} doesn't match plan layout {:?}",
+                dst.layout(),
+                self.target().layout
+            );
+        }
+        convert_views(
+            src,
+            dst,
+            |src, src_stride, dst, dst_stride, width, height| {
+                self.apply(src, src_stride, dst, dst_stride, width, height)
+            },
+        );
+    }
+
+    /// This function converts only a region of the image, strides are of the full images.
+    /// Region has the same position in source and destination images.
+    ///
+    /// # Arguments
+    /// * `src` - A slice contains source image
+    /// * `src_stride` - Bytes per row for src data.
+    /// * `dst` - A mutable slice to receive converted image
+    /// * `dst_stride` - Bytes per row for dst data
+    /// * `width` - Image width
+    /// * `height` - Image height
+    /// * `region` - Region to convert
+    ///
+    /// # Panics
+    /// Panics if images doesn't fit into slices, region is out of image bounds
+    /// or sample types of slices doesn't match descriptors of the plan
+    pub fn apply_region<S: ImageSample, D: ImageSample>(
+        &self,
+        src: &[S],
+        src_stride: u32,
+        dst: &mut [D],
+        dst_stride: u32,
+        width: u32,
+        height: u32,
+        region: ImageRegion,
+    ) {
+        let src_view = ImageRef::with_stride(
+            src,
+            samples_stride::<S>(src_stride),
+            width,
+            height,
+            self.source().layout,
+        )
+        .and_then(|view| view.sub_view(region))
+        .unwrap_or_else(|err| panic!("Invalid source image: {}", err));
+        let mut dst_view = ImageMut::with_stride(
+            dst,
+            samples_stride::<D>(dst_stride),
+            width,
+            height,
+            self.target().layout,
+        )
+        .unwrap_or_else(|err| panic!("Invalid destination image: {}", err));
+        let mut dst_region = dst_view
+            .sub_view_mut(region)
+            .unwrap_or_else(|err| panic!("Invalid destination image: {}", err));
+        self.apply_views(&src_view, &mut dst_region);
+    }
+
+    /// This function converts only rows `rows` of the image, useful for strips of streaming decoders
+    ///
+    /// # Arguments
+    /// * `src` - A slice contains source image
+    /// * `src_stride` - Bytes per row for src data.
+    /// * `dst` - A mutable slice to receive converted image
+    /// * `dst_stride` - Bytes per row for dst data
+    /// * `width` - Image width
+    /// * `height` - Image height
+    /// * `rows` - Range of rows to convert
+    ///
+    /// # Panics
+    /// Panics if images doesn't fit into slices, rows are out of image bounds
+    /// or sample types of slices doesn't match descriptors of the plan
+    pub fn apply_rows<S: ImageSample, D: ImageSample>(
+        &self,
+        src: &[S],
+        src_stride: u32,
+        dst: &mut [D],
+        dst_stride: u32,
+        width: u32,
+        height: u32,
+        rows: Range<u32>,
+    ) {
+        self.apply_region(
+            src,
+            src_stride,
+            dst,
+            dst_stride,
+            width,
+            height,
+            ImageRegion::rows(rows, width),
+        );
+    }
+}
@@ -0,0 +1,582 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::chromatic_adaptation::{adaptation_matrix, ChromaticAdaptation};
+use crate::color_space::{ColorModel, ColorSpace, ImageSample, SampleType};
+use crate::conversion_plan::ConversionPlan;
+use crate::hsl::{hsl_to_rgb, rgb_to_hsl};
+use crate::hsv::{hsv_to_rgb, rgb_to_hsv};
+use crate::matrix::{matrix_inverse, matrix_mul};
+use crate::{
+    Jzazbz, Jzczhz, LAlphaBeta, LCh, Lab, Luv, Oklab, Oklch, Rgb, TransferFunction, Xyz,
+    SRGB_TO_XYZ_D65, WHITE_POINT_D65, XYZ_TO_SRGB_D65,
+};
+
+const IDENTITY: [[f32; 3]; 3] = [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+
+#[inline]
+pub(crate) fn matrices_match(a: &[[f32; 3]; 3], b: &[[f32; 3]; 3]) -> bool {
+    a.iter()
+        .flatten()
+        .zip(b.iter().flatten())
+        .all(|(x, y)| (x - y).abs() < 1e-5)
+}
+
+#[inline]
+pub(crate) fn whites_match(a: Xyz, b: Xyz) -> bool {
+    let a = a.scale(1. / a.y);
+    let b = b.scale(1. / b.y);
+    (a.x - b.x).abs() < 1e-4 && (a.z - b.z).abs() < 1e-4
+}
+
+/// Linear transfer passes values through as they are, so float images keep values outside of [0, 1]
+#[inline(always)]
+fn linearize(transfer_function: TransferFunction, v: f32) -> f32 {
+    match transfer_function {
+        TransferFunction::Linear => v,
+        _ => transfer_function.linearize(v),
+    }
+}
+
+#[inline(always)]
+fn gamma(transfer_function: TransferFunction, v: f32) -> f32 {
+    match transfer_function {
+        TransferFunction::Linear => v,
+        _ => transfer_function.gamma(v),
+    }
+}
+
+/// Matrix taking the linear basis of the model into XYZ
+fn basis_to_xyz(color_space: &ColorSpace) -> [[f32; 3]; 3] {
+    match color_space.model {
+        ColorModel::Rgb | ColorModel::Hsv | ColorModel::Hsl => color_space.matrix,
+        ColorModel::Oklab | ColorModel::Oklch => SRGB_TO_XYZ_D65,
+        _ => IDENTITY,
+    }
+}
+
+/// Matrix taking XYZ into the linear basis of the model
+pub(crate) fn xyz_to_basis(color_space: &ColorSpace) -> [[f32; 3]; 3] {
+    match color_space.model {
+        ColorModel::Rgb | ColorModel::Hsv | ColorModel::Hsl => {
+            matrix_inverse(&color_space.matrix).expect("RGB to XYZ matrix must be invertible")
+        }
+        ColorModel::Oklab | ColorModel::Oklch => XYZ_TO_SRGB_D65,
+        _ => IDENTITY,
+    }
+}
+
+/// Planned chain of steps converting pixels between two colour spaces
+pub(crate) struct ConversionChain {
+    source: ColorSpace,
+    target: ColorSpace,
+    source_white: Xyz,
+    target_white: Xyz,
+    decoding_table: Vec<f32>,
+    matrix: Option<[[f32; 3]; 3]>,
+    /// Row of target RGB to XYZ matrix giving luminance of gray RGB target
+    luminance: Option<[f32; 3]>,
+}
+
+impl ConversionChain {
+    pub(crate) fn new(source: &ColorSpace, target: &ColorSpace) -> Self {
+        for color_space in [source, target] {
+            if let SampleType::U16 { bit_depth } = color_space.sample_type {
+                if !(1..=16).contains(&bit_depth) {
+                    panic!(
+                        "Bit depth must be in range 1..=16, but it was {}",
+                        bit_depth
+                    );
+                }
+            }
+            if color_space.layout.is_gray() && !color_space.model.supports_gray() {
+                panic!(
+                    "Gray layouts are not supported for {:?} model",
+                    color_space.model
+                );
+            }
+            if color_space.sample_type != SampleType::F32 && color_space.model != ColorModel::Rgb {
+                panic!(
+                    "Integer samples are supported only for RGB, but {:?} was requested",
+                    color_space.model
+                );
+            }
+        }
+
+        let source_white = source.model_white_point();
+        let source_white = source_white.scale(1. / source_white.y);
+        let target_white = target.model_white_point();
+        let target_white = target_white.scale(1. / target_white.y);
+
+        let mut matrix = basis_to_xyz(source);
+        if !whites_match(source_white, target_white) {
+            let adaptation =
+                adaptation_matrix(source_white, target_white, ChromaticAdaptation::Bradford);
+            matrix = matrix_mul(&adaptation, &matrix);
+        }
+        matrix = matrix_mul(&xyz_to_basis(target), &matrix);
+
+        let decoding_table =
+            if source.model == ColorModel::Rgb && source.sample_type != SampleType::F32 {
+                let max_value = source.sample_type.max_value();
+                (0..=max_value as usize)
+                    .map(|i| linearize(source.transfer_function, i as f32 / max_value))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+        ConversionChain {
+            source: *source,
+            target: *target,
+            source_white,
+            target_white,
+            decoding_table,
+            matrix: if matrices_match(&matrix, &IDENTITY) {
+                None
+            } else {
+                Some(matrix)
+            },
+            luminance: if target.layout.is_gray() && target.model == ColorModel::Rgb {
+                Some(target.matrix[1])
+            } else {
+                None
+            },
+        }
+    }
+
+    pub(crate) fn source(&self) -> &ColorSpace {
+        &self.source
+    }
+
+    pub(crate) fn target(&self) -> &ColorSpace {
+        &self.target
+    }
+
+    /// Decodes source channels into linear basis of the source model
+    #[inline(always)]
+    fn decode(&self, c0: f32, c1: f32, c2: f32) -> [f32; 3] {
+        let white = self.source_white;
+        let transfer_function = self.source.transfer_function;
+        match self.source.model {
+            ColorModel::Rgb => {
+                if self.decoding_table.is_empty() {
+                    [
+                        linearize(transfer_function, c0),
+                        linearize(transfer_function, c1),
+                        linearize(transfer_function, c2),
+                    ]
+                } else {
+                    let last = self.decoding_table.len() - 1;
+                    [
+                        self.decoding_table[(c0 as usize).min(last)],
+                        self.decoding_table[(c1 as usize).min(last)],
+                        self.decoding_table[(c2 as usize).min(last)],
+                    ]
+                }
+            }
+            ColorModel::Hsv | ColorModel::Hsl => {
+                let (r, g, b) = if self.source.model == ColorModel::Hsv {
+                    hsv_to_rgb(c0.rem_euclid(360.), c1, c2)
+                } else {
+                    hsl_to_rgb(c0.rem_euclid(360.), c1, c2)
+                };
+                [
+                    linearize(transfer_function, r),
+                    linearize(transfer_function, g),
+                    linearize(transfer_function, b),
+                ]
+            }
+            ColorModel::Xyz => [c0, c1, c2],
+            ColorModel::Lab => {
+                let xyz = Lab::new(c0, c1, c2).to_xyz();
+                [
+                    xyz.x * (white.x / 0.95047),
+                    xyz.y,
+                    xyz.z * (white.z / 1.08883),
+                ]
+            }
+            ColorModel::Luv | ColorModel::Lch => {
+                let mut luv = if self.source.model == ColorModel::Luv {
+                    Luv::new(c0, c1, c2)
+                } else {
+                    LCh::new(c0, c1, c2).to_luv()
+                };
+                let (u_shift, v_shift) = white_uv_shift(white);
+                luv.u += 13. * luv.l * u_shift;
+                luv.v += 13. * luv.l * v_shift;
+                let xyz = luv.to_xyz();
+                [xyz.x, xyz.y, xyz.z]
+            }
+            ColorModel::Oklab => {
+                let rgb = Oklab::new(c0, c1, c2).to_linear_rgb();
+                [rgb.r, rgb.g, rgb.b]
+            }
+            ColorModel::Oklch => {
+                let rgb = Oklch::new(c0, c1, c2).to_linear_rgb();
+                [rgb.r, rgb.g, rgb.b]
+            }
+            ColorModel::Jzazbz => {
+                let xyz =
+                    Jzazbz::new_with_luminance(c0, c1, c2, self.source.display_luminance).to_xyz();
+                [xyz.x, xyz.y, xyz.z]
+            }
+            ColorModel::Jzczhz => {
+                let xyz = Jzczhz::new(c0, c1, c2)
+                    .to_jzazbz_with_luminance(self.source.display_luminance)
+                    .to_xyz();
+                [xyz.x, xyz.y, xyz.z]
+            }
+            ColorModel::LAlphaBeta => {
+                let xyz = LAlphaBeta::new(c0, c1, c2).to_xyz();
+                [xyz.x, xyz.y, xyz.z]
+            }
+        }
+    }
+
+    /// Encodes linear basis of the target model into target channels
+    #[inline(always)]
+    fn encode(&self, v: [f32; 3]) -> [f32; 3] {
+        let white = self.target_white;
+        let transfer_function = self.target.transfer_function;
+        match self.target.model {
+            ColorModel::Rgb => {
+                if self.target.sample_type == SampleType::F32 {
+                    [
+                        gamma(transfer_function, v[0]),
+                        gamma(transfer_function, v[1]),
+                        gamma(transfer_function, v[2]),
+                    ]
+                } else {
+                    let max_value = self.target.sample_type.max_value();
+                    [
+                        gamma(transfer_function, v[0].max(0.).min(1.)) * max_value,
+                        gamma(transfer_function, v[1].max(0.).min(1.)) * max_value,
+                        gamma(transfer_function, v[2].max(0.).min(1.)) * max_value,
+                    ]
+                }
+            }
+            ColorModel::Hsv | ColorModel::Hsl => {
+                let r = gamma(transfer_function, v[0].max(0.).min(1.));
+                let g = gamma(transfer_function, v[1].max(0.).min(1.));
+                let b = gamma(transfer_function, v[2].max(0.).min(1.));
+                let (h, s, l) = if self.target.model == ColorModel::Hsv {
+                    rgb_to_hsv(r, g, b)
+                } else {
+                    rgb_to_hsl(r, g, b)
+                };
+                [h, s, l]
+            }
+            ColorModel::Xyz => v,
+            ColorModel::Lab => {
+                let lab = Lab::from_xyz(Xyz::new(
+                    v[0] * (0.95047 / white.x),
+                    v[1],
+                    v[2] * (1.08883 / white.z),
+                ));
+                [lab.l, lab.a, lab.b]
+            }
+            ColorModel::Luv | ColorModel::Lch => {
+                let mut luv = Luv::from_xyz(Xyz::new(v[0], v[1], v[2]));
+                let (u_shift, v_shift) = white_uv_shift(white);
+                luv.u -= 13. * luv.l * u_shift;
+                luv.v -= 13. * luv.l * v_shift;
+                if self.target.model == ColorModel::Luv {
+                    [luv.l, luv.u, luv.v]
+                } else {
+                    let lch = LCh::from_luv(luv);
+                    [lch.l, lch.c, lch.h]
+                }
+            }
+            ColorModel::Oklab => {
+                let oklab = Oklab::from_linear_rgb(Rgb::new(v[0], v[1], v[2]));
+                [oklab.l, oklab.a, oklab.b]
+            }
+            ColorModel::Oklch => {
+                let oklch = Oklch::from_linear_rgb(Rgb::new(v[0], v[1], v[2]));
+                [oklch.l, oklch.c, oklch.h]
+            }
+            ColorModel::Jzazbz => {
+                let jzazbz = Jzazbz::from_xyz_with_display_luminance(
+                    Xyz::new(v[0], v[1], v[2]),
+                    self.target.display_luminance,
+                );
+                [jzazbz.jz, jzazbz.az, jzazbz.bz]
+            }
+            ColorModel::Jzczhz => {
+                let jzczhz = Jzczhz::from_xyz_with_display_luminance(
+                    Xyz::new(v[0], v[1], v[2]),
+                    self.target.display_luminance,
+                );
+                [jzczhz.jz, jzczhz.cz, jzczhz.hz]
+            }
+            ColorModel::LAlphaBeta => {
+                let lab = LAlphaBeta::from_xyz(Xyz::new(v[0], v[1], v[2]));
+                [lab.l, lab.alpha, lab.beta]
+            }
+        }
+    }
+
+    /// Converts one row of `width` pixels
+    ///
+    /// # Safety
+    /// Pointers must be valid for `width` pixels of the respective layouts
+    #[inline]
+    pub(crate) unsafe fn convert_row<S: ImageSample, D: ImageSample>(
+        &self,
+        src: *const S,
+        dst: *mut D,
+        width: usize,
+    ) {
+        let src_layout = self.source.layout;
+        let dst_layout = self.target.layout;
+        let src_channels = src_layout.get_channels_count();
+        let dst_channels = dst_layout.get_channels_count();
+        let src_alpha_scale = 1. / self.source.sample_type.max_value();
+        let dst_alpha_scale = self.target.sample_type.max_value();
+        let saturate_alpha = self.target.sample_type != SampleType::F32;
+
+        // Gray RGB is neutral, other gray models carry lightness without chroma
+        let gray_chroma = self.source.model != ColorModel::Rgb;
+
+        for x in 0..width {
+            let src_px = src.add(x * src_channels);
+            let c0 = src_px
+                .add(src_layout.get_r_channel_offset())
+                .read_unaligned()
+                .to_f32();
+            let (c1, c2) = if !src_layout.is_gray() {
+                (
+                    src_px
+                        .add(src_layout.get_g_channel_offset())
+                        .read_unaligned()
+                        .to_f32(),
+                    src_px
+                        .add(src_layout.get_b_channel_offset())
+                        .read_unaligned()
+                        .to_f32(),
+                )
+            } else if gray_chroma {
+                (0., 0.)
+            } else {
+                (c0, c0)
+            };
+            let alpha = if src_layout.has_alpha() {
+                src_px
+                    .add(src_layout.get_a_channel_offset())
+                    .read_unaligned()
+                    .to_f32()
+                    * src_alpha_scale
+            } else {
+                1.
+            };
+
+            let mut v = self.decode(c0, c1, c2);
+            if let Some(m) = &self.matrix {
+                v = [
+                    m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+                    m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+                    m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+                ];
+            }
+            if let Some(row) = &self.luminance {
+                let y = row[0] * v[0] + row[1] * v[1] + row[2] * v[2];
+                v = [y, y, y];
+            }
+            let encoded = self.encode(v);
+
+            let dst_px = dst.add(x * dst_channels);
+            dst_px
+                .add(dst_layout.get_r_channel_offset())
+                .write_unaligned(D::from_f32(encoded[0]));
+            if !dst_layout.is_gray() {
+                dst_px
+                    .add(dst_layout.get_g_channel_offset())
+                    .write_unaligned(D::from_f32(encoded[1]));
+                dst_px
+                    .add(dst_layout.get_b_channel_offset())
+                    .write_unaligned(D::from_f32(encoded[2]));
+            }
+            if dst_layout.has_alpha() {
+                let alpha = if saturate_alpha {
+                    alpha.max(0.).min(1.)
+                } else {
+                    alpha
+                };
+                dst_px
+                    .add(dst_layout.get_a_channel_offset())
+                    .write_unaligned(D::from_f32(alpha * dst_alpha_scale));
+            }
+        }
+    }
+}
+
+#[inline]
+fn uv_prime(xyz: Xyz) -> (f32, f32) {
+    let den = xyz.x + 15. * xyz.y + 3. * xyz.z;
+    (4. * xyz.x / den, 9. * xyz.y / den)
+}
+
+/// Offset of white chromaticity from D65 the [Luv] type is relative to
+#[inline]
+fn white_uv_shift(white: Xyz) -> (f32, f32) {
+    let (u_white, v_white) = uv_prime(white);
+    let (u_d65, v_d65) = uv_prime(WHITE_POINT_D65);
+    (u_white - u_d65, v_white - v_d65)
+}
+
+/// This function converts image between any two supported colour spaces.
+/// Conversion is planned as decoding into linear basis of the source model, single fused
+/// matrix including chromatic adaptation when white points differ, and encoding into target model.
+/// When a dedicated SIMD kernel exists for the pair, as for RGB to Lab or Oklab, it is used instead.
+///
+/// # Arguments
+/// * `src` - A slice contains source image
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_color_space` - Descriptor of the source image
+/// * `dst` - A mutable slice to receive converted image
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_color_space` - Descriptor of the destination image
+/// * `width` - Image width
+/// * `height` - Image height
+///
+/// # Panics
+/// Panics if sample types of slices doesn't match descriptors, if integer samples are requested
+/// for non RGB model or if bit depth is out of range 1..=16
+pub fn convert_image<S: ImageSample, D: ImageSample>(
+    src: &[S],
+    src_stride: u32,
+    src_color_space: &ColorSpace,
+    dst: &mut [D],
+    dst_stride: u32,
+    dst_color_space: &ColorSpace,
+    width: u32,
+    height: u32,
+) {
+    ConversionPlan::new(src_color_space, dst_color_space)
+        .apply(src, src_stride, dst, dst_stride, width, height);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::ImageConfiguration;
+    use crate::{DISPLAY_P3_TO_XYZ_D65, WHITE_POINT_D50};
+
+    #[test]
+    fn srgb_lab_round_trip_keeps_u8_values() {
+        let values = (0..=255u8).step_by(3).collect::<Vec<u8>>();
+        let mut src = Vec::with_capacity(values.len().pow(3) * 3);
+        for &r in values.iter() {
+            for &g in values.iter() {
+                for &b in values.iter() {
+                    src.extend_from_slice(&[r, g, b]);
+                }
+            }
+        }
+        let width = (src.len() / 3) as u32;
+        let srgb = ColorSpace::srgb(ImageConfiguration::Rgb, SampleType::U8);
+        let lab = ColorSpace::new(ImageConfiguration::Rgb, SampleType::F32, ColorModel::Lab);
+
+        let mut lab_image = vec![0f32; src.len()];
+        convert_image(
+            &src,
+            width * 3,
+            &srgb,
+            &mut lab_image,
+            width * 3 * 4,
+            &lab,
+            width,
+            1,
+        );
+        let mut dst = vec![0u8; src.len()];
+        convert_image(
+            &lab_image,
+            width * 3 * 4,
+            &lab,
+            &mut dst,
+            width * 3,
+            &srgb,
+            width,
+            1,
+        );
+
+        for (pixel, (&expected, &actual)) in src.iter().zip(dst.iter()).enumerate() {
+            assert!(
+                (expected as i32 - actual as i32).abs() <= 1,
+                "sample {} was {} and became {}",
+                pixel,
+                expected,
+                actual
+            );
+        }
+
+        // sRGB red is L* 53.24, a* 80.09, b* 67.20 under D65
+        let red = [255u8, 0, 0];
+        let mut red_lab = [0f32; 3];
+        convert_image(&red, 3, &srgb, &mut red_lab, 12, &lab, 1, 1);
+        for (actual, expected) in red_lab.iter().zip([53.24f32, 80.09, 67.20]) {
+            assert!((actual - expected).abs() < 0.05, "{:?}", red_lab);
+        }
+    }
+
+    #[test]
+    fn display_p3_primaries_map_into_linear_srgb() {
+        // Display P3 to linear sRGB, columns are the P3 primaries
+        let expected = [
+            [1.2249401, -0.2249404, 0.],
+            [-0.0420569, 1.0420571, 0.],
+            [-0.0196376, -0.0786361, 1.0982735],
+        ];
+        let mut target = ColorSpace::srgb(ImageConfiguration::Rgb, SampleType::F32);
+        target.transfer_function = TransferFunction::Linear;
+
+        let p3_d65 = ColorSpace::rgb(
+            ImageConfiguration::Rgb,
+            SampleType::F32,
+            DISPLAY_P3_TO_XYZ_D65,
+            TransferFunction::Linear,
+            WHITE_POINT_D65,
+        );
+        // The same primaries referred to D50 with Bradford, as ICC profiles store them,
+        // so the conversion has to adapt D50 back to D65
+        let bradford_d65_to_d50 = [
+            [1.0478112, 0.0228866, -0.0501270],
+            [0.0295424, 0.9904844, -0.0170491],
+            [-0.0092345, 0.0150436, 0.7521316],
+        ];
+        let p3_d50 = ColorSpace::rgb(
+            ImageConfiguration::Rgb,
+            SampleType::F32,
+            matrix_mul(&bradford_d65_to_d50, &DISPLAY_P3_TO_XYZ_D65),
+            TransferFunction::Linear,
+            WHITE_POINT_D50,
+        );
+
+        let src = [1f32, 0., 0., 0., 1., 0., 0., 0., 1., 1., 1., 1.];
+        for source in [p3_d65, p3_d50] {
+            let mut dst = [0f32; 12];
+            convert_image(&src, 48, &source, &mut dst, 48, &target, 4, 1);
+            for primary in 0..3 {
+                for channel in 0..3 {
+                    let actual = dst[primary * 3 + channel];
+                    let wanted = expected[channel][primary];
+                    assert!(
+                        (actual - wanted).abs() < 2e-3,
+                        "primary {} channel {} is {}, expected {}",
+                        primary,
+                        channel,
+                        actual,
+                        wanted
+                    );
+                }
+            }
+            for white in &dst[9..12] {
+                assert!((white - 1.).abs() < 2e-3, "{:?}", &dst[9..12]);
+            }
+        }
+    }
+}
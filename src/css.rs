@@ -0,0 +1,1501 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::css_named_colors::{find_named_color, named_color_name};
+use crate::gamma_curves::{pure_gamma_function, TransferFunction};
+use crate::xyz_transform::*;
+use crate::{Lab, Oklab, Oklch, Rgb, Rgba, Xyz};
+use std::fmt;
+use std::str::FromStr;
+
+/// Bradford D50 to D65 adaptation as CSS Color 4 defines it
+const CSS_D50_TO_D65: [[f32; 3]; 3] = [
+    [0.955473421488075, -0.02309845494876471, 0.06325924320057072],
+    [
+        -0.0283697093338637,
+        1.0099953980813041,
+        0.021041441191917323,
+    ],
+    [
+        0.012314014864481998,
+        -0.020507649298898964,
+        1.330365926242124,
+    ],
+];
+
+/// Bradford D65 to D50 adaptation as CSS Color 4 defines it
+const CSS_D65_TO_D50: [[f32; 3]; 3] = [
+    [
+        1.0479297925449969,
+        0.022946870601609652,
+        -0.05019226628920524,
+    ],
+    [
+        0.02962780877005599,
+        0.9904344267538799,
+        -0.017073799063418826,
+    ],
+    [
+        -0.009243040646204504,
+        0.015055191490298152,
+        0.7518742814281371,
+    ],
+];
+
+/// D50 white as CSS Color 4 defines it
+const CSS_D50_WHITE: [f32; 3] = [0.3457 / 0.3585, 1., (1. - 0.3457 - 0.3585) / 0.3585];
+
+const DEFAULT_PRECISION: usize = 4;
+
+/// Colour space or notation of CSS colour
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum CssColorSpace {
+    /// `#hex`, named colours and `rgb()`, components in [0, 1]
+    Srgb,
+    /// `hsl()`, hue in degrees, saturation and lightness in [0, 100]
+    Hsl,
+    /// `hwb()`, hue in degrees, whiteness and blackness in [0, 100]
+    Hwb,
+    /// `lab()`, CIE Lab against D50, lightness in [0, 100]
+    Lab,
+    /// `lch()`, CIE LCh(ab) against D50, hue in degrees
+    Lch,
+    /// `oklab()`, lightness in [0, 1]
+    Oklab,
+    /// `oklch()`, lightness in [0, 1], hue in degrees
+    Oklch,
+    /// `color(srgb-linear)`
+    SrgbLinear,
+    /// `color(display-p3)`
+    DisplayP3,
+    /// `color(a98-rgb)`
+    A98Rgb,
+    /// `color(prophoto-rgb)`
+    ProphotoRgb,
+    /// `color(rec2020)`
+    Rec2020,
+    /// `color(xyz-d50)`
+    XyzD50,
+    /// `color(xyz-d65)` and `color(xyz)`
+    XyzD65,
+}
+
+impl CssColorSpace {
+    #[inline]
+    fn from_color_function_name(name: &str) -> Option<CssColorSpace> {
+        match name {
+            "srgb" => Some(CssColorSpace::Srgb),
+            "srgb-linear" => Some(CssColorSpace::SrgbLinear),
+            "display-p3" => Some(CssColorSpace::DisplayP3),
+            "a98-rgb" => Some(CssColorSpace::A98Rgb),
+            "prophoto-rgb" => Some(CssColorSpace::ProphotoRgb),
+            "rec2020" => Some(CssColorSpace::Rec2020),
+            "xyz" | "xyz-d65" => Some(CssColorSpace::XyzD65),
+            "xyz-d50" => Some(CssColorSpace::XyzD50),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn color_function_name(&self) -> Option<&'static str> {
+        match self {
+            CssColorSpace::SrgbLinear => Some("srgb-linear"),
+            CssColorSpace::DisplayP3 => Some("display-p3"),
+            CssColorSpace::A98Rgb => Some("a98-rgb"),
+            CssColorSpace::ProphotoRgb => Some("prophoto-rgb"),
+            CssColorSpace::Rec2020 => Some("rec2020"),
+            CssColorSpace::XyzD50 => Some("xyz-d50"),
+            CssColorSpace::XyzD65 => Some("xyz-d65"),
+            _ => None,
+        }
+    }
+}
+
+/// Kind of CSS colour parsing error
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CssParseErrorKind {
+    /// Input is empty
+    Empty,
+    /// Unknown named colour
+    UnknownColorName(String),
+    /// Hex colour with invalid digits or length
+    InvalidHex(String),
+    /// Unknown colour function
+    UnknownFunction(String),
+    /// Unknown colour space in `color()`
+    UnknownColorSpace(String),
+    /// Unexpected token
+    UnexpectedToken {
+        expected: &'static str,
+        found: String,
+    },
+    /// Input ended too early
+    UnexpectedEnd { expected: &'static str },
+    /// Function received wrong number of components
+    InvalidComponentCount {
+        function: String,
+        expected: usize,
+        found: usize,
+    },
+    /// Comma separated syntax is used where it is not allowed or mixed with space separated
+    InvalidLegacySyntax(String),
+    /// Something left after colour
+    TrailingInput(String),
+}
+
+/// Error produced by CSS colour parser
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CssParseError {
+    /// Error kind
+    pub kind: CssParseErrorKind,
+    /// Byte position in input where error occurred
+    pub position: usize,
+}
+
+impl CssParseError {
+    #[inline]
+    fn new(kind: CssParseErrorKind, position: usize) -> CssParseError {
+        CssParseError { kind, position }
+    }
+}
+
+impl fmt::Display for CssParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            CssParseErrorKind::Empty => write!(f, "empty color string"),
+            CssParseErrorKind::UnknownColorName(name) => {
+                write!(f, "unknown color name `{}`", name)
+            }
+            CssParseErrorKind::InvalidHex(hex) => write!(
+                f,
+                "invalid hex color `#{}`, expected 3, 4, 6 or 8 hex digits",
+                hex
+            ),
+            CssParseErrorKind::UnknownFunction(name) => {
+                write!(f, "unknown color function `{}()`", name)
+            }
+            CssParseErrorKind::UnknownColorSpace(name) => {
+                write!(f, "unknown color space `{}` in color()", name)
+            }
+            CssParseErrorKind::UnexpectedToken { expected, found } => {
+                write!(f, "expected {}, found `{}`", expected, found)
+            }
+            CssParseErrorKind::UnexpectedEnd { expected } => {
+                write!(f, "unexpected end of input, expected {}", expected)
+            }
+            CssParseErrorKind::InvalidComponentCount {
+                function,
+                expected,
+                found,
+            } => write!(
+                f,
+                "`{}()` expects {} components, found {}",
+                function, expected, found
+            ),
+            CssParseErrorKind::InvalidLegacySyntax(function) => write!(
+                f,
+                "invalid comma separated syntax in `{}()`, commas allowed only in rgb() and hsl() and can't be mixed with spaces",
+                function
+            ),
+            CssParseErrorKind::TrailingInput(rest) => {
+                write!(f, "unexpected trailing input `{}`", rest)
+            }
+        }?;
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl std::error::Error for CssParseError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Function(String),
+    Hash(String),
+    Number(f32),
+    Percentage(f32),
+    Dimension(f32, String),
+    Comma,
+    Slash,
+    CloseParen,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(ident) => write!(f, "{}", ident),
+            Token::Function(name) => write!(f, "{}(", name),
+            Token::Hash(hash) => write!(f, "#{}", hash),
+            Token::Number(number) => write!(f, "{}", number),
+            Token::Percentage(number) => write!(f, "{}%", number),
+            Token::Dimension(number, unit) => write!(f, "{}{}", number, unit),
+            Token::Comma => write!(f, ","),
+            Token::Slash => write!(f, "/"),
+            Token::CloseParen => write!(f, ")"),
+        }
+    }
+}
+
+struct Tokenizer<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(input: &'a str) -> Tokenizer<'a> {
+        Tokenizer { input, position: 0 }
+    }
+
+    #[inline]
+    fn peek_byte(&self, offset: usize) -> Option<u8> {
+        self.input.as_bytes().get(self.position + offset).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(byte) = self.peek_byte(0) {
+            if byte.is_ascii_whitespace() {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    #[inline]
+    fn is_ident_byte(byte: u8) -> bool {
+        byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_'
+    }
+
+    fn consume_ident(&mut self) -> String {
+        let start = self.position;
+        while let Some(byte) = self.peek_byte(0) {
+            if Self::is_ident_byte(byte) {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+        self.input[start..self.position].to_ascii_lowercase()
+    }
+
+    fn consume_number(&mut self) -> Result<f32, CssParseError> {
+        let start = self.position;
+        if matches!(self.peek_byte(0), Some(b'+') | Some(b'-')) {
+            self.position += 1;
+        }
+        while matches!(self.peek_byte(0), Some(b'0'..=b'9')) {
+            self.position += 1;
+        }
+        if self.peek_byte(0) == Some(b'.') && matches!(self.peek_byte(1), Some(b'0'..=b'9')) {
+            self.position += 1;
+            while matches!(self.peek_byte(0), Some(b'0'..=b'9')) {
+                self.position += 1;
+            }
+        }
+        if matches!(self.peek_byte(0), Some(b'e') | Some(b'E')) {
+            let exponent_digits = match self.peek_byte(1) {
+                Some(b'+') | Some(b'-') => 2,
+                _ => 1,
+            };
+            if matches!(self.peek_byte(exponent_digits), Some(b'0'..=b'9')) {
+                self.position += exponent_digits;
+                while matches!(self.peek_byte(0), Some(b'0'..=b'9')) {
+                    self.position += 1;
+                }
+            }
+        }
+        let text = &self.input[start..self.position];
+        text.parse::<f32>().map_err(|_| {
+            CssParseError::new(
+                CssParseErrorKind::UnexpectedToken {
+                    expected: "number",
+                    found: text.to_string(),
+                },
+                start,
+            )
+        })
+    }
+
+    /// Returns next token with its position, or `None` at the end of input
+    fn next_token(&mut self) -> Result<Option<(Token, usize)>, CssParseError> {
+        self.skip_whitespace();
+        let start = self.position;
+        let byte = match self.peek_byte(0) {
+            None => return Ok(None),
+            Some(byte) => byte,
+        };
+        let token = match byte {
+            b',' => {
+                self.position += 1;
+                Token::Comma
+            }
+            b'/' => {
+                self.position += 1;
+                Token::Slash
+            }
+            b')' => {
+                self.position += 1;
+                Token::CloseParen
+            }
+            b'#' => {
+                self.position += 1;
+                let start = self.position;
+                while let Some(byte) = self.peek_byte(0) {
+                    if Self::is_ident_byte(byte) {
+                        self.position += 1;
+                    } else {
+                        break;
+                    }
+                }
+                Token::Hash(self.input[start..self.position].to_string())
+            }
+            b'0'..=b'9' | b'.' | b'+' => self.consume_numeric()?,
+            b'-' => {
+                if matches!(self.peek_byte(1), Some(b'0'..=b'9') | Some(b'.')) {
+                    self.consume_numeric()?
+                } else {
+                    self.consume_ident_like()
+                }
+            }
+            _ if byte.is_ascii_alphabetic() || byte == b'_' => self.consume_ident_like(),
+            _ => {
+                let found = self.input[start..]
+                    .chars()
+                    .next()
+                    .map(|c| c.to_string())
+                    .unwrap_or_default();
+                return Err(CssParseError::new(
+                    CssParseErrorKind::UnexpectedToken {
+                        expected: "color",
+                        found,
+                    },
+                    start,
+                ));
+            }
+        };
+        Ok(Some((token, start)))
+    }
+
+    fn consume_numeric(&mut self) -> Result<Token, CssParseError> {
+        let number = self.consume_number()?;
+        if self.peek_byte(0) == Some(b'%') {
+            self.position += 1;
+            return Ok(Token::Percentage(number));
+        }
+        if matches!(self.peek_byte(0), Some(byte) if byte.is_ascii_alphabetic()) {
+            let unit = self.consume_ident();
+            return Ok(Token::Dimension(number, unit));
+        }
+        Ok(Token::Number(number))
+    }
+
+    fn consume_ident_like(&mut self) -> Token {
+        let ident = self.consume_ident();
+        if self.peek_byte(0) == Some(b'(') {
+            self.position += 1;
+            Token::Function(ident)
+        } else {
+            Token::Ident(ident)
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Component {
+    Number(f32),
+    Percentage(f32),
+    /// Angle in degrees
+    Angle(f32),
+    None,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum ComponentKind {
+    /// Number, percentage maps 100% to the scale
+    Scaled { percent_scale: f32 },
+    /// Hue, number or angle
+    Hue,
+}
+
+struct FunctionArguments {
+    components: Vec<(Component, usize)>,
+    alpha: Option<(Component, usize)>,
+}
+
+fn component_value(
+    component: (Component, usize),
+    kind: ComponentKind,
+) -> Result<f32, CssParseError> {
+    let (value, position) = component;
+    match (value, kind) {
+        (Component::None, _) => Ok(0.),
+        (Component::Number(number), _) => Ok(number),
+        (Component::Percentage(percentage), ComponentKind::Scaled { percent_scale }) => {
+            Ok(percentage * percent_scale / 100.)
+        }
+        (Component::Angle(degrees), ComponentKind::Hue) => Ok(degrees),
+        (Component::Percentage(percentage), ComponentKind::Hue) => Err(CssParseError::new(
+            CssParseErrorKind::UnexpectedToken {
+                expected: "hue as number or angle",
+                found: format!("{}%", percentage),
+            },
+            position,
+        )),
+        (Component::Angle(degrees), ComponentKind::Scaled { .. }) => Err(CssParseError::new(
+            CssParseErrorKind::UnexpectedToken {
+                expected: "number or percentage",
+                found: format!("{}deg", degrees),
+            },
+            position,
+        )),
+    }
+}
+
+fn parse_component(token: Token, position: usize) -> Result<Component, CssParseError> {
+    match token {
+        Token::Number(number) => Ok(Component::Number(number)),
+        Token::Percentage(percentage) => Ok(Component::Percentage(percentage)),
+        Token::Ident(ident) if ident == "none" => Ok(Component::None),
+        Token::Dimension(number, unit) => {
+            let degrees = match unit.as_str() {
+                "deg" => number,
+                "rad" => number.to_degrees(),
+                "grad" => number * 0.9,
+                "turn" => number * 360.,
+                _ => {
+                    return Err(CssParseError::new(
+                        CssParseErrorKind::UnexpectedToken {
+                            expected: "angle unit deg, rad, grad or turn",
+                            found: unit,
+                        },
+                        position,
+                    ))
+                }
+            };
+            Ok(Component::Angle(degrees))
+        }
+        token => Err(CssParseError::new(
+            CssParseErrorKind::UnexpectedToken {
+                expected: "number, percentage, angle or `none`",
+                found: token.to_string(),
+            },
+            position,
+        )),
+    }
+}
+
+fn parse_arguments(
+    tokenizer: &mut Tokenizer,
+    function: &str,
+    allows_legacy: bool,
+) -> Result<FunctionArguments, CssParseError> {
+    let mut components = Vec::with_capacity(4);
+    let mut alpha = None;
+    let mut legacy: Option<bool> = None;
+    let mut expects_value = true;
+
+    loop {
+        let (token, position) = tokenizer.next_token()?.ok_or(CssParseError::new(
+            CssParseErrorKind::UnexpectedEnd { expected: "`)`" },
+            tokenizer.position,
+        ))?;
+        match token {
+            Token::CloseParen => {
+                if legacy == Some(true) && expects_value {
+                    return Err(CssParseError::new(
+                        CssParseErrorKind::UnexpectedToken {
+                            expected: "component after `,`",
+                            found: ")".to_string(),
+                        },
+                        position,
+                    ));
+                }
+                break;
+            }
+            Token::Comma => {
+                if !allows_legacy || legacy == Some(false) || components.is_empty() {
+                    return Err(CssParseError::new(
+                        CssParseErrorKind::InvalidLegacySyntax(function.to_string()),
+                        position,
+                    ));
+                }
+                if expects_value {
+                    return Err(CssParseError::new(
+                        CssParseErrorKind::UnexpectedToken {
+                            expected: "component",
+                            found: ",".to_string(),
+                        },
+                        position,
+                    ));
+                }
+                legacy = Some(true);
+                expects_value = true;
+            }
+            Token::Slash => {
+                if legacy == Some(true) {
+                    return Err(CssParseError::new(
+                        CssParseErrorKind::InvalidLegacySyntax(function.to_string()),
+                        position,
+                    ));
+                }
+                let (token, position) = tokenizer.next_token()?.ok_or(CssParseError::new(
+                    CssParseErrorKind::UnexpectedEnd { expected: "alpha" },
+                    tokenizer.position,
+                ))?;
+                alpha = Some((parse_component(token, position)?, position));
+                let (token, position) = tokenizer.next_token()?.ok_or(CssParseError::new(
+                    CssParseErrorKind::UnexpectedEnd { expected: "`)`" },
+                    tokenizer.position,
+                ))?;
+                if token != Token::CloseParen {
+                    return Err(CssParseError::new(
+                        CssParseErrorKind::UnexpectedToken {
+                            expected: "`)` after alpha",
+                            found: token.to_string(),
+                        },
+                        position,
+                    ));
+                }
+                break;
+            }
+            token => {
+                if !expects_value && legacy == Some(true) {
+                    return Err(CssParseError::new(
+                        CssParseErrorKind::InvalidLegacySyntax(function.to_string()),
+                        position,
+                    ));
+                }
+                if !components.is_empty() && legacy.is_none() {
+                    legacy = Some(false);
+                }
+                components.push((parse_component(token, position)?, position));
+                expects_value = false;
+            }
+        }
+    }
+
+    if legacy == Some(true) && components.len() == 4 {
+        alpha = components.pop();
+    }
+
+    if components.len() != 3 {
+        return Err(CssParseError::new(
+            CssParseErrorKind::InvalidComponentCount {
+                function: function.to_string(),
+                expected: 3,
+                found: components.len(),
+            },
+            tokenizer.position.saturating_sub(1),
+        ));
+    }
+
+    Ok(FunctionArguments { components, alpha })
+}
+
+#[inline]
+fn alpha_value(alpha: Option<(Component, usize)>) -> Result<f32, CssParseError> {
+    match alpha {
+        None => Ok(1.),
+        Some(alpha) => Ok(
+            component_value(alpha, ComponentKind::Scaled { percent_scale: 1. })?
+                .max(0.)
+                .min(1.),
+        ),
+    }
+}
+
+fn parse_hex(hex: &str, position: usize) -> Result<CssColor, CssParseError> {
+    let invalid = || CssParseError::new(CssParseErrorKind::InvalidHex(hex.to_string()), position);
+    if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let digit = |index: usize| -> u8 {
+        let byte = hex.as_bytes()[index];
+        (byte as char).to_digit(16).unwrap() as u8
+    };
+    let rgba: [u8; 4] = match hex.len() {
+        3 | 4 => {
+            let mut rgba = [255u8; 4];
+            for (i, dst) in rgba.iter_mut().enumerate().take(hex.len()) {
+                *dst = digit(i) * 17;
+            }
+            rgba
+        }
+        6 | 8 => {
+            let mut rgba = [255u8; 4];
+            for (i, dst) in rgba.iter_mut().enumerate().take(hex.len() / 2) {
+                *dst = digit(i * 2) * 16 + digit(i * 2 + 1);
+            }
+            rgba
+        }
+        _ => return Err(invalid()),
+    };
+    Ok(CssColor::from_rgba8(Rgba::<u8>::new(
+        rgba[0], rgba[1], rgba[2], rgba[3],
+    )))
+}
+
+fn parse_function(
+    tokenizer: &mut Tokenizer,
+    name: &str,
+    position: usize,
+) -> Result<CssColor, CssParseError> {
+    let scaled = |percent_scale: f32| ComponentKind::Scaled { percent_scale };
+    let (space, kinds, arguments) = match name {
+        "rgb" | "rgba" => (
+            CssColorSpace::Srgb,
+            [scaled(255.), scaled(255.), scaled(255.)],
+            parse_arguments(tokenizer, name, true)?,
+        ),
+        "hsl" | "hsla" => (
+            CssColorSpace::Hsl,
+            [ComponentKind::Hue, scaled(100.), scaled(100.)],
+            parse_arguments(tokenizer, name, true)?,
+        ),
+        "hwb" => (
+            CssColorSpace::Hwb,
+            [ComponentKind::Hue, scaled(100.), scaled(100.)],
+            parse_arguments(tokenizer, name, false)?,
+        ),
+        "lab" => (
+            CssColorSpace::Lab,
+            [scaled(100.), scaled(125.), scaled(125.)],
+            parse_arguments(tokenizer, name, false)?,
+        ),
+        "lch" => (
+            CssColorSpace::Lch,
+            [scaled(100.), scaled(150.), ComponentKind::Hue],
+            parse_arguments(tokenizer, name, false)?,
+        ),
+        "oklab" => (
+            CssColorSpace::Oklab,
+            [scaled(1.), scaled(0.4), scaled(0.4)],
+            parse_arguments(tokenizer, name, false)?,
+        ),
+        "oklch" => (
+            CssColorSpace::Oklch,
+            [scaled(1.), scaled(0.4), ComponentKind::Hue],
+            parse_arguments(tokenizer, name, false)?,
+        ),
+        "color" => {
+            let (token, space_position) = tokenizer.next_token()?.ok_or(CssParseError::new(
+                CssParseErrorKind::UnexpectedEnd {
+                    expected: "color space",
+                },
+                tokenizer.position,
+            ))?;
+            let space = match token {
+                Token::Ident(space_name) => CssColorSpace::from_color_function_name(&space_name)
+                    .ok_or(CssParseError::new(
+                        CssParseErrorKind::UnknownColorSpace(space_name),
+                        space_position,
+                    ))?,
+                token => {
+                    return Err(CssParseError::new(
+                        CssParseErrorKind::UnexpectedToken {
+                            expected: "color space",
+                            found: token.to_string(),
+                        },
+                        space_position,
+                    ))
+                }
+            };
+            (
+                space,
+                [scaled(1.), scaled(1.), scaled(1.)],
+                parse_arguments(tokenizer, name, false)?,
+            )
+        }
+        _ => {
+            return Err(CssParseError::new(
+                CssParseErrorKind::UnknownFunction(name.to_string()),
+                position,
+            ))
+        }
+    };
+
+    let mut components = [0f32; 3];
+    for ((dst, component), kind) in components
+        .iter_mut()
+        .zip(arguments.components.iter())
+        .zip(kinds.iter())
+    {
+        *dst = component_value(*component, *kind)?;
+    }
+
+    if space == CssColorSpace::Srgb && (name == "rgb" || name == "rgba") {
+        for component in components.iter_mut() {
+            *component *= 1. / 255.;
+        }
+    }
+
+    match space {
+        CssColorSpace::Lab | CssColorSpace::Lch | CssColorSpace::Oklab | CssColorSpace::Oklch => {
+            let max_lightness = if space == CssColorSpace::Lab || space == CssColorSpace::Lch {
+                100.
+            } else {
+                1.
+            };
+            components[0] = components[0].max(0.).min(max_lightness);
+            if space == CssColorSpace::Lch || space == CssColorSpace::Oklch {
+                components[1] = components[1].max(0.);
+            }
+        }
+        _ => {}
+    }
+
+    Ok(CssColor {
+        space,
+        components,
+        alpha: alpha_value(arguments.alpha)?,
+    })
+}
+
+/// Parsed CSS colour, components are kept in the notation they were written in
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CssColor {
+    /// Colour space or notation
+    pub space: CssColorSpace,
+    /// Components in CSS units of the colour space, see [CssColorSpace]
+    pub components: [f32; 3],
+    /// Alpha in range [0, 1]
+    pub alpha: f32,
+}
+
+#[inline]
+fn hue_to_rgb(hue: f32, saturation: f32, lightness: f32) -> [f32; 3] {
+    let hue = hue.rem_euclid(360.);
+    let a = saturation * lightness.min(1. - lightness);
+    let f = |n: f32| -> f32 {
+        let k = (n + hue / 30.).rem_euclid(12.);
+        lightness - a * (k - 3.).min(9. - k).min(1.).max(-1.)
+    };
+    [f(0.), f(8.), f(4.)]
+}
+
+#[inline]
+fn sign_preserving<F: Fn(f32) -> f32>(value: f32, transfer: F) -> f32 {
+    if value < 0. {
+        -transfer(-value)
+    } else {
+        transfer(value)
+    }
+}
+
+#[inline]
+fn prophoto_to_linear(value: f32) -> f32 {
+    if value <= 16. / 512. {
+        value / 16.
+    } else {
+        value.powf(1.8)
+    }
+}
+
+#[inline]
+fn prophoto_from_linear(value: f32) -> f32 {
+    if value >= 1. / 512. {
+        value.powf(1. / 1.8)
+    } else {
+        value * 16.
+    }
+}
+
+#[inline]
+fn format_number(value: f32, precision: usize) -> String {
+    let mut formatted = format!("{:.*}", precision, value);
+    if formatted.contains('.') {
+        while formatted.ends_with('0') {
+            formatted.pop();
+        }
+        if formatted.ends_with('.') {
+            formatted.pop();
+        }
+    }
+    if formatted == "-0" {
+        formatted = "0".to_string();
+    }
+    formatted
+}
+
+impl CssColor {
+    /// Constructs new instance
+    #[inline]
+    pub fn new(space: CssColorSpace, components: [f32; 3], alpha: f32) -> CssColor {
+        CssColor {
+            space,
+            components,
+            alpha,
+        }
+    }
+
+    /// Parses CSS colour string.
+    ///
+    /// Supports hex notation, 148 named colours, `transparent`, `rgb()`, `rgba()`, `hsl()`, `hsla()`,
+    /// `hwb()`, `lab()`, `lch()`, `oklab()`, `oklch()` and `color()` with predefined colour spaces
+    pub fn parse(input: &str) -> Result<CssColor, CssParseError> {
+        let mut tokenizer = Tokenizer::new(input);
+        let (token, position) = tokenizer
+            .next_token()?
+            .ok_or(CssParseError::new(CssParseErrorKind::Empty, 0))?;
+        let color = match token {
+            Token::Hash(hex) => parse_hex(&hex, position)?,
+            Token::Ident(name) => {
+                if name == "transparent" {
+                    CssColor::new(CssColorSpace::Srgb, [0., 0., 0.], 0.)
+                } else {
+                    let rgb = find_named_color(&name).ok_or(CssParseError::new(
+                        CssParseErrorKind::UnknownColorName(name),
+                        position,
+                    ))?;
+                    CssColor::from_rgba8(Rgba::<u8>::new(rgb[0], rgb[1], rgb[2], 255))
+                }
+            }
+            Token::Function(name) => parse_function(&mut tokenizer, &name, position)?,
+            token => {
+                return Err(CssParseError::new(
+                    CssParseErrorKind::UnexpectedToken {
+                        expected: "color",
+                        found: token.to_string(),
+                    },
+                    position,
+                ))
+            }
+        };
+        tokenizer.skip_whitespace();
+        if tokenizer.position < input.len() {
+            return Err(CssParseError::new(
+                CssParseErrorKind::TrailingInput(input[tokenizer.position..].to_string()),
+                tokenizer.position,
+            ));
+        }
+        Ok(color)
+    }
+
+    /// Creates sRGB colour
+    #[inline]
+    pub fn from_rgba8(rgba: Rgba<u8>) -> CssColor {
+        const SCALE: f32 = 1. / 255.;
+        CssColor::new(
+            CssColorSpace::Srgb,
+            [
+                rgba.r as f32 * SCALE,
+                rgba.g as f32 * SCALE,
+                rgba.b as f32 * SCALE,
+            ],
+            rgba.a as f32 * SCALE,
+        )
+    }
+
+    /// Creates `lab()` colour from CIE Lab against D65, colour is adapted to D50 as CSS requires
+    #[inline]
+    pub fn from_lab(lab: Lab, alpha: f32) -> CssColor {
+        let xyz = lab.to_xyz().transform(&CSS_D65_TO_D50);
+        let f = |t: f32| -> f32 {
+            const EPSILON: f32 = 216. / 24389.;
+            const KAPPA: f32 = 24389. / 27.;
+            if t > EPSILON {
+                t.cbrt()
+            } else {
+                (KAPPA * t + 16.) / 116.
+            }
+        };
+        let fx = f(xyz.x / CSS_D50_WHITE[0]);
+        let fy = f(xyz.y / CSS_D50_WHITE[1]);
+        let fz = f(xyz.z / CSS_D50_WHITE[2]);
+        CssColor::new(
+            CssColorSpace::Lab,
+            [116. * fy - 16., 500. * (fx - fy), 200. * (fy - fz)],
+            alpha,
+        )
+    }
+
+    /// Creates `oklab()` colour
+    #[inline]
+    pub fn from_oklab(oklab: Oklab, alpha: f32) -> CssColor {
+        CssColor::new(CssColorSpace::Oklab, [oklab.l, oklab.a, oklab.b], alpha)
+    }
+
+    /// Creates `oklch()` colour
+    #[inline]
+    pub fn from_oklch(oklch: Oklch, alpha: f32) -> CssColor {
+        CssColor::new(
+            CssColorSpace::Oklch,
+            [oklch.l, oklch.c, oklch.h.to_degrees().rem_euclid(360.)],
+            alpha,
+        )
+    }
+
+    /// Gamma encoded sRGB for sRGB based notations
+    #[inline]
+    fn srgb_components(&self) -> Option<[f32; 3]> {
+        let c = self.components;
+        match self.space {
+            CssColorSpace::Srgb => Some(c),
+            CssColorSpace::Hsl => Some(hue_to_rgb(c[0], c[1] / 100., c[2] / 100.)),
+            CssColorSpace::Hwb => {
+                let whiteness = c[1] / 100.;
+                let blackness = c[2] / 100.;
+                if whiteness + blackness >= 1. {
+                    let gray = whiteness / (whiteness + blackness);
+                    Some([gray, gray, gray])
+                } else {
+                    let rgb = hue_to_rgb(c[0], 1., 0.5);
+                    let scale = 1. - whiteness - blackness;
+                    Some([
+                        rgb[0] * scale + whiteness,
+                        rgb[1] * scale + whiteness,
+                        rgb[2] * scale + whiteness,
+                    ])
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Converts colour to CIE XYZ against D65, Y of white is 1
+    pub fn to_xyz(&self) -> Xyz {
+        let c = self.components;
+        let linear_with = |transfer: &dyn Fn(f32) -> f32, c: [f32; 3]| -> Rgb<f32> {
+            Rgb::<f32>::new(
+                sign_preserving(c[0], transfer),
+                sign_preserving(c[1], transfer),
+                sign_preserving(c[2], transfer),
+            )
+        };
+        let srgb_linearize = |v: f32| TransferFunction::Srgb.linearize(v);
+        if let Some(srgb) = self.srgb_components() {
+            return Xyz::from_linear_rgb(linear_with(&srgb_linearize, srgb), &SRGB_TO_XYZ_D65);
+        }
+        match self.space {
+            CssColorSpace::SrgbLinear => {
+                Xyz::from_linear_rgb(Rgb::<f32>::new(c[0], c[1], c[2]), &SRGB_TO_XYZ_D65)
+            }
+            CssColorSpace::DisplayP3 => {
+                Xyz::from_linear_rgb(linear_with(&srgb_linearize, c), &DISPLAY_P3_TO_XYZ_D65)
+            }
+            CssColorSpace::A98Rgb => Xyz::from_linear_rgb(
+                linear_with(&|v: f32| pure_gamma_function(v, 563. / 256.), c),
+                &A98_RGB_TO_XYZ_D65,
+            ),
+            CssColorSpace::ProphotoRgb => Xyz::from_linear_rgb(
+                linear_with(&prophoto_to_linear, c),
+                &PROPHOTO_RGB_TO_XYZ_D50,
+            )
+            .transform(&CSS_D50_TO_D65),
+            CssColorSpace::Rec2020 => Xyz::from_linear_rgb(
+                linear_with(&|v: f32| TransferFunction::Rec709.linearize(v), c),
+                &BT2020_TO_XYZ_D65,
+            ),
+            CssColorSpace::XyzD65 => Xyz::new(c[0], c[1], c[2]),
+            CssColorSpace::XyzD50 => Xyz::new(c[0], c[1], c[2]).transform(&CSS_D50_TO_D65),
+            CssColorSpace::Lab | CssColorSpace::Lch => {
+                let (l, a, b) = if self.space == CssColorSpace::Lch {
+                    let hue = c[2].to_radians();
+                    (c[0], c[1] * hue.cos(), c[1] * hue.sin())
+                } else {
+                    (c[0], c[1], c[2])
+                };
+                const EPSILON: f32 = 216. / 24389.;
+                const KAPPA: f32 = 24389. / 27.;
+                let fy = (l + 16.) / 116.;
+                let fx = a / 500. + fy;
+                let fz = fy - b / 200.;
+                let x = if fx * fx * fx > EPSILON {
+                    fx * fx * fx
+                } else {
+                    (116. * fx - 16.) / KAPPA
+                };
+                let y = if l > KAPPA * EPSILON {
+                    fy * fy * fy
+                } else {
+                    l / KAPPA
+                };
+                let z = if fz * fz * fz > EPSILON {
+                    fz * fz * fz
+                } else {
+                    (116. * fz - 16.) / KAPPA
+                };
+                Xyz::new(
+                    x * CSS_D50_WHITE[0],
+                    y * CSS_D50_WHITE[1],
+                    z * CSS_D50_WHITE[2],
+                )
+                .transform(&CSS_D50_TO_D65)
+            }
+            CssColorSpace::Oklab => Xyz::from_linear_rgb(
+                Oklab::new(c[0], c[1], c[2]).to_linear_rgb(),
+                &SRGB_TO_XYZ_D65,
+            ),
+            CssColorSpace::Oklch => Xyz::from_linear_rgb(
+                Oklch::new(c[0], c[1], c[2].to_radians()).to_linear_rgb(),
+                &SRGB_TO_XYZ_D65,
+            ),
+            CssColorSpace::Srgb | CssColorSpace::Hsl | CssColorSpace::Hwb => unreachable!(),
+        }
+    }
+
+    /// Converts colour to linear sRGB, out of gamut values are not clipped
+    #[inline]
+    pub fn to_linear_rgb(&self) -> Rgb<f32> {
+        if let Some(srgb) = self.srgb_components() {
+            return Rgb::<f32>::new(srgb[0], srgb[1], srgb[2]).linearize(TransferFunction::Srgb);
+        }
+        match self.space {
+            CssColorSpace::SrgbLinear => {
+                Rgb::<f32>::new(self.components[0], self.components[1], self.components[2])
+            }
+            CssColorSpace::Oklab | CssColorSpace::Oklch => self.to_oklab().to_linear_rgb(),
+            _ => self.to_xyz().to_linear_rgb(&XYZ_TO_SRGB_D65),
+        }
+    }
+
+    /// Converts colour to gamma encoded sRGB, out of gamut colours are clipped
+    #[inline]
+    pub fn to_rgba_f32(&self) -> Rgba<f32> {
+        let rgb = match self.srgb_components() {
+            Some(srgb) => Rgb::<f32>::new(srgb[0], srgb[1], srgb[2]),
+            None => {
+                let linear = self.to_linear_rgb();
+                Rgb::<f32>::new(
+                    linear.r.max(0.).min(1.),
+                    linear.g.max(0.).min(1.),
+                    linear.b.max(0.).min(1.),
+                )
+                .gamma(TransferFunction::Srgb)
+            }
+        };
+        Rgba::<f32>::new(
+            rgb.r.max(0.).min(1.),
+            rgb.g.max(0.).min(1.),
+            rgb.b.max(0.).min(1.),
+            self.alpha,
+        )
+    }
+
+    /// Converts colour to 8-bit sRGB, out of gamut colours are clipped
+    #[inline]
+    pub fn to_rgba8(&self) -> Rgba<u8> {
+        let rgba = self.to_rgba_f32();
+        Rgba::<u8>::new(
+            (rgba.r * 255.).round() as u8,
+            (rgba.g * 255.).round() as u8,
+            (rgba.b * 255.).round() as u8,
+            (rgba.a * 255.).round() as u8,
+        )
+    }
+
+    /// Returns CSS named colour matching this colour exactly after 8-bit quantization, if any.
+    /// Only opaque colours have names
+    #[inline]
+    pub fn named_color(&self) -> Option<&'static str> {
+        let rgba = self.to_rgba8();
+        if rgba.a != 255 {
+            return None;
+        }
+        named_color_name([rgba.r, rgba.g, rgba.b])
+    }
+
+    /// Converts colour to CIE Lab against D65
+    #[inline]
+    pub fn to_lab(&self) -> Lab {
+        Lab::from_xyz(self.to_xyz())
+    }
+
+    /// Converts colour to *Oklab*
+    #[inline]
+    pub fn to_oklab(&self) -> Oklab {
+        let c = self.components;
+        match self.space {
+            CssColorSpace::Oklab => Oklab::new(c[0], c[1], c[2]),
+            CssColorSpace::Oklch => Oklch::new(c[0], c[1], c[2].to_radians()).to_oklab(),
+            _ => Oklab::from_linear_rgb(self.to_linear_rgb()),
+        }
+    }
+
+    /// Converts colour to *Oklch*, hue in radians as [Oklch] keeps it
+    #[inline]
+    pub fn to_oklch(&self) -> Oklch {
+        let c = self.components;
+        match self.space {
+            CssColorSpace::Oklch => Oklch::new(c[0], c[1], c[2].to_radians()),
+            _ => {
+                let oklab = self.to_oklab();
+                Oklch::new(oklab.l, oklab.a.hypot(oklab.b), oklab.b.atan2(oklab.a))
+            }
+        }
+    }
+
+    /// Converts colour into another CSS colour space
+    pub fn convert(&self, space: CssColorSpace) -> CssColor {
+        if space == self.space {
+            return *self;
+        }
+        let alpha = self.alpha;
+        match space {
+            CssColorSpace::Srgb => {
+                let linear = self.to_linear_rgb();
+                let srgb = Rgb::<f32>::new(
+                    sign_preserving(linear.r, |v| TransferFunction::Srgb.gamma(v)),
+                    sign_preserving(linear.g, |v| TransferFunction::Srgb.gamma(v)),
+                    sign_preserving(linear.b, |v| TransferFunction::Srgb.gamma(v)),
+                );
+                CssColor::new(space, [srgb.r, srgb.g, srgb.b], alpha)
+            }
+            CssColorSpace::Hsl | CssColorSpace::Hwb => {
+                let rgb = self.convert(CssColorSpace::Srgb).components;
+                let max = rgb[0].max(rgb[1]).max(rgb[2]);
+                let min = rgb[0].min(rgb[1]).min(rgb[2]);
+                let delta = max - min;
+                let mut hue = if delta == 0. {
+                    0.
+                } else if max == rgb[0] {
+                    (rgb[1] - rgb[2]) / delta + if rgb[1] < rgb[2] { 6. } else { 0. }
+                } else if max == rgb[1] {
+                    (rgb[2] - rgb[0]) / delta + 2.
+                } else {
+                    (rgb[0] - rgb[1]) / delta + 4.
+                };
+                hue *= 60.;
+                if space == CssColorSpace::Hsl {
+                    let lightness = (min + max) / 2.;
+                    let saturation = if lightness == 0. || lightness == 1. {
+                        0.
+                    } else {
+                        (max - lightness) / lightness.min(1. - lightness)
+                    };
+                    CssColor::new(space, [hue, saturation * 100., lightness * 100.], alpha)
+                } else {
+                    CssColor::new(space, [hue, min * 100., (1. - max) * 100.], alpha)
+                }
+            }
+            CssColorSpace::Lab | CssColorSpace::Lch => {
+                let lab = CssColor::from_lab(self.to_lab(), alpha);
+                if space == CssColorSpace::Lab {
+                    lab
+                } else {
+                    let c = lab.components;
+                    let chroma = c[1].hypot(c[2]);
+                    let hue = c[2].atan2(c[1]).to_degrees().rem_euclid(360.);
+                    CssColor::new(space, [c[0], chroma, hue], alpha)
+                }
+            }
+            CssColorSpace::Oklab => CssColor::from_oklab(self.to_oklab(), alpha),
+            CssColorSpace::Oklch => CssColor::from_oklch(self.to_oklch(), alpha),
+            CssColorSpace::SrgbLinear => {
+                let linear = self.to_linear_rgb();
+                CssColor::new(space, [linear.r, linear.g, linear.b], alpha)
+            }
+            CssColorSpace::XyzD65 => {
+                let xyz = self.to_xyz();
+                CssColor::new(space, [xyz.x, xyz.y, xyz.z], alpha)
+            }
+            CssColorSpace::XyzD50 => {
+                let xyz = self.to_xyz().transform(&CSS_D65_TO_D50);
+                CssColor::new(space, [xyz.x, xyz.y, xyz.z], alpha)
+            }
+            CssColorSpace::DisplayP3
+            | CssColorSpace::A98Rgb
+            | CssColorSpace::ProphotoRgb
+            | CssColorSpace::Rec2020 => {
+                let xyz = self.to_xyz();
+                let (linear, transfer): (Xyz, &dyn Fn(f32) -> f32) = match space {
+                    CssColorSpace::DisplayP3 => (xyz.transform(&XYZ_TO_DISPLAY_P3_D65), &|v| {
+                        TransferFunction::Srgb.gamma(v)
+                    }),
+                    CssColorSpace::A98Rgb => (xyz.transform(&XYZ_TO_A98_RGB_D65), &|v| {
+                        pure_gamma_function(v, 256. / 563.)
+                    }),
+                    CssColorSpace::ProphotoRgb => (
+                        xyz.transform(&CSS_D65_TO_D50)
+                            .transform(&XYZ_TO_PROPHOTO_RGB_D50),
+                        &prophoto_from_linear,
+                    ),
+                    _ => (xyz.transform(&XYZ_TO_BT2020_D65), &|v| {
+                        TransferFunction::Rec709.gamma(v)
+                    }),
+                };
+                CssColor::new(
+                    space,
+                    [
+                        sign_preserving(linear.x, transfer),
+                        sign_preserving(linear.y, transfer),
+                        sign_preserving(linear.z, transfer),
+                    ],
+                    alpha,
+                )
+            }
+        }
+    }
+
+    /// Serializes colour in its own notation
+    ///
+    /// # Arguments
+    /// * `precision` - Maximum number of decimal places, trailing zeros are omitted
+    pub fn to_css_string(&self, precision: usize) -> String {
+        let c = self.components;
+        let n = |value: f32| format_number(value, precision);
+        let body = match self.space {
+            CssColorSpace::Srgb => format!(
+                "rgb({} {} {}",
+                n(c[0] * 255.),
+                n(c[1] * 255.),
+                n(c[2] * 255.)
+            ),
+            CssColorSpace::Hsl => format!("hsl({} {}% {}%", n(c[0]), n(c[1]), n(c[2])),
+            CssColorSpace::Hwb => format!("hwb({} {}% {}%", n(c[0]), n(c[1]), n(c[2])),
+            CssColorSpace::Lab => format!("lab({} {} {}", n(c[0]), n(c[1]), n(c[2])),
+            CssColorSpace::Lch => format!("lch({} {} {}", n(c[0]), n(c[1]), n(c[2])),
+            CssColorSpace::Oklab => format!("oklab({} {} {}", n(c[0]), n(c[1]), n(c[2])),
+            CssColorSpace::Oklch => format!("oklch({} {} {}", n(c[0]), n(c[1]), n(c[2])),
+            _ => format!(
+                "color({} {} {} {}",
+                self.space.color_function_name().unwrap(),
+                n(c[0]),
+                n(c[1]),
+                n(c[2])
+            ),
+        };
+        if self.alpha < 1. {
+            format!("{} / {})", body, n(self.alpha))
+        } else {
+            format!("{})", body)
+        }
+    }
+}
+
+impl FromStr for CssColor {
+    type Err = CssParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CssColor::parse(s)
+    }
+}
+
+impl fmt::Display for CssColor {
+    /// Formats colour as CSS string, formatter precision sets maximum number of decimal places
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_css_string(f.precision().unwrap_or(DEFAULT_PRECISION)))
+    }
+}
+
+impl FromStr for Rgba<u8> {
+    type Err = CssParseError;
+
+    /// Parses any CSS colour and converts it into 8-bit sRGB
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(CssColor::parse(s)?.to_rgba8())
+    }
+}
+
+impl fmt::Display for Rgba<u8> {
+    /// Formats as `#rrggbb`, or `#rrggbbaa` when colour is not opaque
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.a == 255 {
+            write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        } else {
+            write!(
+                f,
+                "#{:02x}{:02x}{:02x}{:02x}",
+                self.r, self.g, self.b, self.a
+            )
+        }
+    }
+}
+
+impl FromStr for Rgb<u8> {
+    type Err = CssParseError;
+
+    /// Parses any CSS colour and converts it into 8-bit sRGB, alpha is discarded
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rgba = CssColor::parse(s)?.to_rgba8();
+        Ok(Rgb::<u8>::new(rgba.r, rgba.g, rgba.b))
+    }
+}
+
+impl fmt::Display for Rgb<u8> {
+    /// Formats as `#rrggbb`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl FromStr for Lab {
+    type Err = CssParseError;
+
+    /// Parses any CSS colour and converts it into CIE Lab against D65, alpha is discarded
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(CssColor::parse(s)?.to_lab())
+    }
+}
+
+impl fmt::Display for Lab {
+    /// Formats as CSS `lab()`, colour is adapted to D50 as CSS requires
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&CssColor::from_lab(*self, 1.), f)
+    }
+}
+
+impl FromStr for Oklab {
+    type Err = CssParseError;
+
+    /// Parses any CSS colour and converts it into *Oklab*, alpha is discarded
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(CssColor::parse(s)?.to_oklab())
+    }
+}
+
+impl fmt::Display for Oklab {
+    /// Formats as CSS `oklab()`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&CssColor::from_oklab(*self, 1.), f)
+    }
+}
+
+impl FromStr for Oklch {
+    type Err = CssParseError;
+
+    /// Parses any CSS colour and converts it into *Oklch*, alpha is discarded
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(CssColor::parse(s)?.to_oklch())
+    }
+}
+
+impl fmt::Display for Oklch {
+    /// Formats as CSS `oklch()`, hue is written in degrees
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&CssColor::from_oklch(*self, 1.), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsed_colors_serialize_in_their_notation() {
+        let cases = [
+            // Hex, 3, 4, 6 and 8 digits
+            ("#f00", "rgb(255 0 0)"),
+            ("#FFF", "rgb(255 255 255)"),
+            ("#f008", "rgb(255 0 0 / 0.5333)"),
+            ("#ff8000", "rgb(255 128 0)"),
+            ("#ff800080", "rgb(255 128 0 / 0.502)"),
+            // Named colours
+            ("red", "rgb(255 0 0)"),
+            ("  rebeccapurple  ", "rgb(102 51 153)"),
+            ("transparent", "rgb(0 0 0 / 0)"),
+            // rgb() legacy and modern syntax
+            ("rgb(255, 128, 0)", "rgb(255 128 0)"),
+            ("rgba(255, 128, 0, 0.5)", "rgb(255 128 0 / 0.5)"),
+            ("rgb(255 128 0 / 50%)", "rgb(255 128 0 / 0.5)"),
+            ("rgb(100% 50% 0%)", "rgb(255 127.5 0)"),
+            ("rgb(none 128 0)", "rgb(0 128 0)"),
+            // hsl() legacy and modern syntax, angle units
+            ("hsl(120, 100%, 50%)", "hsl(120 100% 50%)"),
+            ("hsla(120deg, 100%, 50%, 0.25)", "hsl(120 100% 50% / 0.25)"),
+            ("hsl(0.5turn 50% 25% / 10%)", "hsl(180 50% 25% / 0.1)"),
+            ("hsl(200grad 50 25)", "hsl(180 50% 25%)"),
+            // hwb() has modern syntax only
+            ("hwb(90 10% 20%)", "hwb(90 10% 20%)"),
+            ("hwb(none 10% 20% / none)", "hwb(0 10% 20% / 0)"),
+            // lab() and lch(), percentages map to 100 and 125 or 150
+            ("lab(50% 40 -20)", "lab(50 40 -20)"),
+            ("lab(50 100% -100%)", "lab(50 125 -125)"),
+            ("lab(150 0 0)", "lab(100 0 0)"),
+            ("lch(52.2 72.2 50)", "lch(52.2 72.2 50)"),
+            ("lch(50% 50% 30deg / 0.75)", "lch(50 75 30 / 0.75)"),
+            // oklab() and oklch(), percentages map to 1 and 0.4
+            ("oklab(0.5 0.1 -0.1)", "oklab(0.5 0.1 -0.1)"),
+            ("oklab(50% 25% -25%)", "oklab(0.5 0.1 -0.1)"),
+            ("oklch(0.7 0.1 2rad)", "oklch(0.7 0.1 114.5916)"),
+            ("oklch(0.5 none none)", "oklch(0.5 0 0)"),
+            ("oklch(0.5 -0.1 30)", "oklch(0.5 0 30)"),
+            // color() with predefined spaces
+            ("color(display-p3 1 0.5 0)", "color(display-p3 1 0.5 0)"),
+            (
+                "color(display-p3 100% 50% 0% / 0.5)",
+                "color(display-p3 1 0.5 0 / 0.5)",
+            ),
+            ("color(srgb 1 0.5 0)", "rgb(255 127.5 0)"),
+            ("color(xyz 0.2 0.3 0.4)", "color(xyz-d65 0.2 0.3 0.4)"),
+            ("color(rec2020 0.1 none 0.3)", "color(rec2020 0.1 0 0.3)"),
+        ];
+        for (input, expected) in cases {
+            let color = CssColor::parse(input)
+                .unwrap_or_else(|err| panic!("`{}` failed to parse: {}", input, err));
+            assert_eq!(color.to_string(), expected, "input `{}`", input);
+            // Serialized colour parses back into the same colour
+            let reparsed = CssColor::parse(expected).unwrap();
+            assert_eq!(reparsed.to_string(), expected, "input `{}`", expected);
+        }
+    }
+
+    #[test]
+    fn invalid_colors_are_rejected() {
+        let cases: [(&str, fn(&CssParseErrorKind) -> bool); 14] = [
+            ("", |kind| *kind == CssParseErrorKind::Empty),
+            ("#ff", |kind| {
+                matches!(kind, CssParseErrorKind::InvalidHex(_))
+            }),
+            ("#ggg", |kind| {
+                matches!(kind, CssParseErrorKind::InvalidHex(_))
+            }),
+            ("notacolor", |kind| {
+                matches!(kind, CssParseErrorKind::UnknownColorName(_))
+            }),
+            ("foo(1 2 3)", |kind| {
+                matches!(kind, CssParseErrorKind::UnknownFunction(_))
+            }),
+            ("color(cmyk 1 2 3)", |kind| {
+                matches!(kind, CssParseErrorKind::UnknownColorSpace(_))
+            }),
+            ("rgb(1 2)", |kind| {
+                matches!(
+                    kind,
+                    CssParseErrorKind::InvalidComponentCount {
+                        expected: 3,
+                        found: 2,
+                        ..
+                    }
+                )
+            }),
+            ("rgb(1, 2 3)", |kind| {
+                matches!(kind, CssParseErrorKind::InvalidLegacySyntax(_))
+            }),
+            ("rgb(1, 2, 3 / 0.5)", |kind| {
+                matches!(kind, CssParseErrorKind::InvalidLegacySyntax(_))
+            }),
+            ("hwb(90, 10%, 20%)", |kind| {
+                matches!(kind, CssParseErrorKind::InvalidLegacySyntax(_))
+            }),
+            ("lab(1, 2, 3)", |kind| {
+                matches!(kind, CssParseErrorKind::InvalidLegacySyntax(_))
+            }),
+            ("rgb(1 2 3", |kind| {
+                matches!(kind, CssParseErrorKind::UnexpectedEnd { .. })
+            }),
+            ("hsl(10% 20% 30%)", |kind| {
+                matches!(kind, CssParseErrorKind::UnexpectedToken { .. })
+            }),
+            ("red blue", |kind| {
+                matches!(kind, CssParseErrorKind::TrailingInput(_))
+            }),
+        ];
+        for (input, is_expected) in cases {
+            match CssColor::parse(input) {
+                Ok(color) => panic!("`{}` parsed as {}", input, color),
+                Err(err) => assert!(is_expected(&err.kind), "`{}` failed with {:?}", input, err),
+            }
+        }
+        assert_eq!(CssColor::parse("red blue").unwrap_err().position, 4);
+    }
+}
@@ -0,0 +1,445 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::utils::mlaf;
+use crate::Rgb;
+
+/// Declares colour vision deficiency type.
+///
+/// Every type covers both dichromacy and the matching anomalous trichromacy,
+/// severity `1.0` is protanopia/deuteranopia/tritanopia, values in between are
+/// protanomaly/deuteranomaly/tritanomaly
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum ColorVisionDeficiency {
+    /// L-cones deficiency
+    Protan = 0,
+    /// M-cones deficiency
+    Deutan = 1,
+    /// S-cones deficiency
+    Tritan = 2,
+}
+
+impl From<u8> for ColorVisionDeficiency {
+    #[inline(always)]
+    fn from(value: u8) -> Self {
+        match value {
+            0 => ColorVisionDeficiency::Protan,
+            1 => ColorVisionDeficiency::Deutan,
+            2 => ColorVisionDeficiency::Tritan,
+            _ => {
+                panic!("Unknown value")
+            }
+        }
+    }
+}
+
+/// Declares model used for colour vision deficiency simulation
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum CvdModel {
+    /// Brettel, Viénot and Mollon 1997, two half-planes projection in LMS.
+    /// Anomalous trichromacy is approximated by linear interpolation to the dichromat result.
+    Brettel1997 = 0,
+    /// Machado, Oliveira and Fernandes 2009 physiologically based model
+    Machado2009 = 1,
+}
+
+impl From<u8> for CvdModel {
+    #[inline(always)]
+    fn from(value: u8) -> Self {
+        match value {
+            0 => CvdModel::Brettel1997,
+            1 => CvdModel::Machado2009,
+            _ => {
+                panic!("Unknown value")
+            }
+        }
+    }
+}
+
+/// Machado 2009 matrices for linear RGB, severity from 0.1 to 1.0 with step 0.1
+static MACHADO_PROTAN: [[[f32; 3]; 3]; 10] = [
+    [
+        [0.856167, 0.182038, -0.038205],
+        [0.029342, 0.955115, 0.015544],
+        [-0.002880, -0.001563, 1.004443],
+    ],
+    [
+        [0.734766, 0.334872, -0.069637],
+        [0.051840, 0.919198, 0.028963],
+        [-0.004928, -0.004209, 1.009137],
+    ],
+    [
+        [0.630323, 0.465641, -0.095964],
+        [0.069181, 0.890046, 0.040773],
+        [-0.006308, -0.007724, 1.014032],
+    ],
+    [
+        [0.539009, 0.579343, -0.118352],
+        [0.082546, 0.866121, 0.051332],
+        [-0.007136, -0.011959, 1.019095],
+    ],
+    [
+        [0.458064, 0.679578, -0.137642],
+        [0.092785, 0.846313, 0.060902],
+        [-0.007494, -0.016807, 1.024301],
+    ],
+    [
+        [0.385450, 0.769005, -0.154455],
+        [0.100526, 0.829802, 0.069673],
+        [-0.007442, -0.022190, 1.029632],
+    ],
+    [
+        [0.319627, 0.849633, -0.169261],
+        [0.106241, 0.815969, 0.077790],
+        [-0.007025, -0.028051, 1.035076],
+    ],
+    [
+        [0.259411, 0.923008, -0.182420],
+        [0.110296, 0.804340, 0.085364],
+        [-0.006276, -0.034346, 1.040622],
+    ],
+    [
+        [0.203876, 0.990338, -0.194214],
+        [0.112975, 0.794542, 0.092483],
+        [-0.005222, -0.041043, 1.046265],
+    ],
+    [
+        [0.152286, 1.052583, -0.204868],
+        [0.114503, 0.786281, 0.099216],
+        [-0.003882, -0.048116, 1.051998],
+    ],
+];
+
+static MACHADO_DEUTAN: [[[f32; 3]; 3]; 10] = [
+    [
+        [0.866435, 0.177704, -0.044139],
+        [0.049567, 0.939063, 0.011370],
+        [-0.003453, 0.007233, 0.996220],
+    ],
+    [
+        [0.760729, 0.319078, -0.079807],
+        [0.090568, 0.889315, 0.020117],
+        [-0.006027, 0.013325, 0.992702],
+    ],
+    [
+        [0.675425, 0.433850, -0.109275],
+        [0.125303, 0.847755, 0.026942],
+        [-0.007950, 0.018572, 0.989378],
+    ],
+    [
+        [0.605511, 0.528560, -0.134071],
+        [0.155318, 0.812366, 0.032316],
+        [-0.009376, 0.023176, 0.986200],
+    ],
+    [
+        [0.547494, 0.607765, -0.155259],
+        [0.181692, 0.781742, 0.036566],
+        [-0.010410, 0.027275, 0.983136],
+    ],
+    [
+        [0.498864, 0.674741, -0.173604],
+        [0.205199, 0.754872, 0.039929],
+        [-0.011131, 0.030969, 0.980162],
+    ],
+    [
+        [0.457771, 0.731899, -0.189670],
+        [0.226409, 0.731012, 0.042579],
+        [-0.011595, 0.034333, 0.977261],
+    ],
+    [
+        [0.422823, 0.781057, -0.203881],
+        [0.245752, 0.709602, 0.044646],
+        [-0.011843, 0.037423, 0.974421],
+    ],
+    [
+        [0.392952, 0.823610, -0.216562],
+        [0.263559, 0.690210, 0.046232],
+        [-0.011910, 0.040281, 0.971630],
+    ],
+    [
+        [0.367322, 0.860646, -0.227968],
+        [0.280085, 0.672501, 0.047413],
+        [-0.011820, 0.042940, 0.968881],
+    ],
+];
+
+static MACHADO_TRITAN: [[[f32; 3]; 3]; 10] = [
+    [
+        [0.926670, 0.092514, -0.019184],
+        [0.021191, 0.964503, 0.014306],
+        [0.008437, 0.054813, 0.936750],
+    ],
+    [
+        [0.895720, 0.133330, -0.029050],
+        [0.029997, 0.945400, 0.024603],
+        [0.013027, 0.104707, 0.882266],
+    ],
+    [
+        [0.905871, 0.127791, -0.033662],
+        [0.026856, 0.941251, 0.031893],
+        [0.013410, 0.148296, 0.838294],
+    ],
+    [
+        [0.948035, 0.089490, -0.037526],
+        [0.014364, 0.946792, 0.038844],
+        [0.010853, 0.193991, 0.795156],
+    ],
+    [
+        [1.017277, 0.027029, -0.044306],
+        [-0.006113, 0.958479, 0.047634],
+        [0.006379, 0.248708, 0.744913],
+    ],
+    [
+        [1.104996, -0.046633, -0.058363],
+        [-0.032137, 0.971635, 0.060503],
+        [0.001336, 0.317922, 0.680742],
+    ],
+    [
+        [1.193214, -0.109812, -0.083402],
+        [-0.058496, 0.979410, 0.079086],
+        [-0.002346, 0.403492, 0.598854],
+    ],
+    [
+        [1.257728, -0.139648, -0.118081],
+        [-0.078003, 0.975409, 0.102594],
+        [-0.003316, 0.501214, 0.502102],
+    ],
+    [
+        [1.278864, -0.125333, -0.153531],
+        [-0.084748, 0.957674, 0.127074],
+        [-0.000989, 0.601151, 0.399838],
+    ],
+    [
+        [1.255528, -0.076749, -0.178779],
+        [-0.078411, 0.930809, 0.147602],
+        [0.004733, 0.691367, 0.303900],
+    ],
+];
+
+/// Brettel 1997 projection for linear sRGB, precomputed for sRGB primaries and
+/// Smith & Pokorny cone fundamentals.
+/// Matrices for the first and the second half-plane and the separation plane normal
+struct BrettelParams {
+    first: [[f32; 3]; 3],
+    second: [[f32; 3]; 3],
+    separation_normal: [f32; 3],
+}
+
+static BRETTEL_PROTAN: BrettelParams = BrettelParams {
+    first: [
+        [0.14510, 1.20165, -0.34675],
+        [0.10447, 0.85316, 0.04237],
+        [0.00429, -0.00603, 1.00174],
+    ],
+    second: [
+        [0.14115, 1.16782, -0.30897],
+        [0.10495, 0.85730, 0.03776],
+        [0.00431, -0.00586, 1.00155],
+    ],
+    separation_normal: [0.00048, 0.00416, -0.00464],
+};
+
+static BRETTEL_DEUTAN: BrettelParams = BrettelParams {
+    first: [
+        [0.36198, 0.86755, -0.22953],
+        [0.26099, 0.64512, 0.09389],
+        [-0.01975, 0.02686, 0.99289],
+    ],
+    second: [
+        [0.37009, 0.88540, -0.25549],
+        [0.25767, 0.63782, 0.10451],
+        [-0.01950, 0.02741, 0.99209],
+    ],
+    separation_normal: [-0.00293, -0.00645, 0.00938],
+};
+
+static BRETTEL_TRITAN: BrettelParams = BrettelParams {
+    first: [
+        [1.01354, 0.14268, -0.15622],
+        [-0.01181, 0.87561, 0.13619],
+        [0.07707, 0.81208, 0.11085],
+    ],
+    second: [
+        [0.93337, 0.19999, -0.13336],
+        [0.05809, 0.82565, 0.11626],
+        [-0.37923, 1.13825, 0.24098],
+    ],
+    separation_normal: [0.03960, -0.02831, -0.01129],
+};
+
+/// Fidaner et al. error redistribution for daltonization, protan and deutan
+const DALTONIZE_RED_GREEN_SHIFT: [[f32; 3]; 3] = [[0., 0., 0.], [0.7, 1., 0.], [0.7, 0., 1.]];
+
+/// Fidaner et al. error redistribution for daltonization, tritan
+const DALTONIZE_BLUE_YELLOW_SHIFT: [[f32; 3]; 3] = [[1., 0., 0.7], [0., 1., 0.7], [0., 0., 0.]];
+
+#[inline(always)]
+fn apply_matrix(matrix: &[[f32; 3]; 3], rgb: Rgb<f32>) -> Rgb<f32> {
+    Rgb::<f32>::new(
+        mlaf(
+            mlaf(rgb.r * matrix[0][0], rgb.g, matrix[0][1]),
+            rgb.b,
+            matrix[0][2],
+        ),
+        mlaf(
+            mlaf(rgb.r * matrix[1][0], rgb.g, matrix[1][1]),
+            rgb.b,
+            matrix[1][2],
+        ),
+        mlaf(
+            mlaf(rgb.r * matrix[2][0], rgb.g, matrix[2][1]),
+            rgb.b,
+            matrix[2][2],
+        ),
+    )
+}
+
+#[inline(always)]
+fn lerp_matrix(a: &[[f32; 3]; 3], b: &[[f32; 3]; 3], t: f32) -> [[f32; 3]; 3] {
+    let mut matrix = [[0f32; 3]; 3];
+    for (dst_row, (a_row, b_row)) in matrix.iter_mut().zip(a.iter().zip(b.iter())) {
+        for (dst, (&a, &b)) in dst_row.iter_mut().zip(a_row.iter().zip(b_row.iter())) {
+            *dst = mlaf(a, b - a, t);
+        }
+    }
+    matrix
+}
+
+const IDENTITY: [[f32; 3]; 3] = [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+
+/// Returns Machado 2009 simulation matrix for linear RGB, severity is linearly interpolated
+/// between tabulated values
+pub fn machado_cvd_matrix(deficiency: ColorVisionDeficiency, severity: f32) -> [[f32; 3]; 3] {
+    let table = match deficiency {
+        ColorVisionDeficiency::Protan => &MACHADO_PROTAN,
+        ColorVisionDeficiency::Deutan => &MACHADO_DEUTAN,
+        ColorVisionDeficiency::Tritan => &MACHADO_TRITAN,
+    };
+    let scaled = severity.max(0.).min(1.) * 10.;
+    let lower = scaled.floor() as usize;
+    let fraction = scaled - lower as f32;
+    let matrix_at = |index: usize| -> &[[f32; 3]; 3] {
+        if index == 0 {
+            &IDENTITY
+        } else {
+            &table[index - 1]
+        }
+    };
+    if lower >= 10 {
+        return table[9];
+    }
+    lerp_matrix(matrix_at(lower), matrix_at(lower + 1), fraction)
+}
+
+/// Prepared colour vision deficiency simulation
+#[derive(Copy, Clone)]
+pub(crate) struct CvdSimulation {
+    deficiency: ColorVisionDeficiency,
+    model: CvdModel,
+    severity: f32,
+    machado: [[f32; 3]; 3],
+}
+
+impl CvdSimulation {
+    pub(crate) fn new(deficiency: ColorVisionDeficiency, severity: f32, model: CvdModel) -> Self {
+        let severity = severity.max(0.).min(1.);
+        let machado = match model {
+            CvdModel::Brettel1997 => IDENTITY,
+            CvdModel::Machado2009 => machado_cvd_matrix(deficiency, severity),
+        };
+        CvdSimulation {
+            deficiency,
+            model,
+            severity,
+            machado,
+        }
+    }
+
+    /// Simulates deficiency on linear RGB
+    #[inline]
+    pub(crate) fn simulate(&self, rgb: Rgb<f32>) -> Rgb<f32> {
+        match self.model {
+            CvdModel::Brettel1997 => {
+                let params = match self.deficiency {
+                    ColorVisionDeficiency::Protan => &BRETTEL_PROTAN,
+                    ColorVisionDeficiency::Deutan => &BRETTEL_DEUTAN,
+                    ColorVisionDeficiency::Tritan => &BRETTEL_TRITAN,
+                };
+                let normal = params.separation_normal;
+                let dot = mlaf(mlaf(rgb.r * normal[0], rgb.g, normal[1]), rgb.b, normal[2]);
+                let dichromat = if dot >= 0. {
+                    apply_matrix(&params.first, rgb)
+                } else {
+                    apply_matrix(&params.second, rgb)
+                };
+                let severity = Rgb::<f32>::dup(self.severity);
+                rgb + (dichromat - rgb) * severity
+            }
+            CvdModel::Machado2009 => apply_matrix(&self.machado, rgb),
+        }
+    }
+
+    /// Daltonizes linear RGB, simulated error is redistributed to the channels
+    /// which remain visible for this deficiency
+    #[inline]
+    pub(crate) fn daltonize(&self, rgb: Rgb<f32>) -> Rgb<f32> {
+        let simulated = self.simulate(rgb);
+        let error = rgb - simulated;
+        let shift = match self.deficiency {
+            ColorVisionDeficiency::Protan | ColorVisionDeficiency::Deutan => {
+                &DALTONIZE_RED_GREEN_SHIFT
+            }
+            ColorVisionDeficiency::Tritan => &DALTONIZE_BLUE_YELLOW_SHIFT,
+        };
+        let corrected = rgb + apply_matrix(shift, error);
+        Rgb::<f32>::new(
+            corrected.r.max(0.).min(1.),
+            corrected.g.max(0.).min(1.),
+            corrected.b.max(0.).min(1.),
+        )
+    }
+}
+
+/// Simulates colour vision deficiency on linear RGB
+///
+/// # Arguments
+/// * `rgb` - Linear RGB
+/// * `deficiency` - Deficiency type
+/// * `severity` - Deficiency severity in range [0, 1], 1 is dichromacy
+/// * `model` - Simulation model
+#[inline]
+pub fn simulate_cvd_linear(
+    rgb: Rgb<f32>,
+    deficiency: ColorVisionDeficiency,
+    severity: f32,
+    model: CvdModel,
+) -> Rgb<f32> {
+    let simulated = CvdSimulation::new(deficiency, severity, model).simulate(rgb);
+    Rgb::<f32>::new(
+        simulated.r.max(0.).min(1.),
+        simulated.g.max(0.).min(1.),
+        simulated.b.max(0.).min(1.),
+    )
+}
+
+/// Daltonizes linear RGB, making colours distinguishable for people with colour vision deficiency
+///
+/// # Arguments
+/// * `rgb` - Linear RGB
+/// * `deficiency` - Deficiency type
+/// * `severity` - Deficiency severity in range [0, 1], 1 is dichromacy
+/// * `model` - Simulation model
+#[inline]
+pub fn daltonize_linear(
+    rgb: Rgb<f32>,
+    deficiency: ColorVisionDeficiency,
+    severity: f32,
+    model: CvdModel,
+) -> Rgb<f32> {
+    CvdSimulation::new(deficiency, severity, model).daltonize(rgb)
+}
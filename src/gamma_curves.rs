@@ -381,3 +381,21 @@ impl TransferFunction {
         }
     }
 }
+
+/// Builds table of linear values for every 8 bit encoded value
+pub(crate) fn linearization_table_u8(transfer_function: TransferFunction) -> Vec<f32> {
+    let mut lut_table = vec![0f32; 256];
+    for (i, lut) in lut_table.iter_mut().enumerate() {
+        *lut = transfer_function.linearize(i as f32 * (1. / 255.0));
+    }
+    lut_table
+}
+
+/// Builds table of 8 bit encoded values for linear values sampled with 1/2048 step
+pub(crate) fn gamma_table_u8(transfer_function: TransferFunction) -> Vec<u8> {
+    let mut lut_table = vec![0u8; 2049];
+    for (i, lut) in lut_table.iter_mut().enumerate() {
+        *lut = (transfer_function.gamma(i as f32 * (1. / 2048.0)) * 255.).min(255.) as u8;
+    }
+    lut_table
+}
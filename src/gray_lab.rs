@@ -0,0 +1,383 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::gamma_curves::TransferFunction;
+use crate::image::ImageConfiguration;
+use crate::image_to_linear::channels_to_linear_impl;
+use crate::threading::{current_threading, ForEachRow, ThreadingPolicy};
+use crate::{Lab, Xyz};
+#[cfg(feature = "rayon")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "rayon")]
+use rayon::prelude::{ParallelSlice, ParallelSliceMut};
+use std::slice;
+
+/// Number of steps L\* range is sampled with in [lightness_to_gray_table_u8]
+const LIGHTNESS_STEPS: usize = 4096;
+
+/// Builds table of CIE L\* for 8 bit gray values, gray holds luminance with Y of white at 1
+pub(crate) fn gray_to_lightness_table_u8(transfer_function: TransferFunction) -> Vec<f32> {
+    let mut lut_table = vec![0f32; 256];
+    for (i, lut) in lut_table.iter_mut().enumerate() {
+        let y = transfer_function.linearize(i as f32 * (1. / 255.0));
+        *lut = Lab::from_xyz(Xyz::new(0., y, 0.)).l;
+    }
+    lut_table
+}
+
+/// Builds table of 8 bit gray values for CIE L\* sampled with 100/4096 step
+pub(crate) fn lightness_to_gray_table_u8(transfer_function: TransferFunction) -> Vec<u8> {
+    let mut lut_table = vec![0u8; LIGHTNESS_STEPS + 1];
+    let scale = 100f32 / LIGHTNESS_STEPS as f32;
+    for (i, lut) in lut_table.iter_mut().enumerate() {
+        let y = Lab::new(i as f32 * scale, 0., 0.).to_xyz().y;
+        *lut = (transfer_function.gamma(y.max(0.)) * 255.)
+            .round()
+            .min(255.) as u8;
+    }
+    lut_table
+}
+
+/// Converts gray image into L\* with precomputed lightness table, alpha is normalized
+pub(crate) fn gray_to_lab_impl<const CHANNELS_CONFIGURATION: u8>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    lut_table: &[f32],
+    policy: &ThreadingPolicy,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    if !image_configuration.is_gray() {
+        panic!("Lightness may be taken only from gray images");
+    }
+    // Gray channel takes the place of R, G and B, so linearization kernel applies lightness table as is
+    if image_configuration.has_alpha() {
+        channels_to_linear_impl::<CHANNELS_CONFIGURATION, true>(
+            src, src_stride, dst, dst_stride, width, height, lut_table, policy,
+        );
+    } else {
+        channels_to_linear_impl::<CHANNELS_CONFIGURATION, false>(
+            src, src_stride, dst, dst_stride, width, height, lut_table, policy,
+        );
+    }
+}
+
+/// Converts L\* into gray image with precomputed gray table, alpha is denormalized
+pub(crate) fn lab_to_gray_impl<const CHANNELS_CONFIGURATION: u8>(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    lut_table: &[u8],
+    policy: &ThreadingPolicy,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    if !image_configuration.is_gray() {
+        panic!("Lightness may be stored only into gray images");
+    }
+
+    let channels = image_configuration.get_channels_count();
+    let scale = LIGHTNESS_STEPS as f32 / 100f32;
+
+    let src_slice_safe_align = unsafe {
+        slice::from_raw_parts(
+            src.as_ptr() as *const u8,
+            src_stride as usize * height as usize,
+        )
+    };
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = dst
+            .par_chunks_exact_mut(dst_stride as usize)
+            .zip(src_slice_safe_align.par_chunks_exact(src_stride as usize));
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = dst
+            .chunks_exact_mut(dst_stride as usize)
+            .zip(src_slice_safe_align.chunks_exact(src_stride as usize));
+    }
+
+    iter.for_each_row_with(policy, width as usize, |(dst, src)| unsafe {
+        let src_ptr = src.as_ptr() as *const f32;
+        let dst_ptr = dst.as_mut_ptr();
+
+        for x in 0..width as usize {
+            let px = x * channels;
+            let src_slice = src_ptr.add(px);
+            let l = src_slice.read_unaligned();
+            let index = (l.min(100f32).max(0f32) * scale).round() as usize;
+            let dst = dst_ptr.add(px);
+            dst.write_unaligned(*lut_table.get_unchecked(index.min(LIGHTNESS_STEPS)));
+
+            if image_configuration.has_alpha() {
+                let a = src_slice
+                    .add(image_configuration.get_a_channel_offset())
+                    .read_unaligned();
+                let a_lin = (a * 255f32).round().min(255f32).max(0f32) as u8;
+                dst.add(image_configuration.get_a_channel_offset())
+                    .write_unaligned(a_lin);
+            }
+        }
+    });
+}
+
+/// This function converts Gray to CIE L\*ab lightness. Gray holds transfer encoded luminance,
+/// destination receives L\* only, chroma of gray is zero. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains Gray data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive L\* data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn gray_to_lab(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    gray_to_lab_impl::<{ ImageConfiguration::Gray as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        &gray_to_lightness_table_u8(transfer_function),
+        &current_threading(),
+    );
+}
+
+/// This function converts Gray with alpha to CIE L\*ab lightness with alpha. Destination receives L\*
+/// and normalized alpha. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains Gray with alpha data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive L\* and alpha data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn gray_alpha_to_lab(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    gray_to_lab_impl::<{ ImageConfiguration::GrayAlpha as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        &gray_to_lightness_table_u8(transfer_function),
+        &current_threading(),
+    );
+}
+
+/// This function converts CIE L\*ab lightness to Gray. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains L\* data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive Gray data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn lab_to_gray(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    lab_to_gray_impl::<{ ImageConfiguration::Gray as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        &lightness_to_gray_table_u8(transfer_function),
+        &current_threading(),
+    );
+}
+
+/// This function converts CIE L\*ab lightness with alpha to Gray with alpha. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains L\* and normalized alpha data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive Gray with alpha data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn lab_to_gray_alpha(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    lab_to_gray_impl::<{ ImageConfiguration::GrayAlpha as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        &lightness_to_gray_table_u8(transfer_function),
+        &current_threading(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        convert_image, ColorModel, ColorSpace, ConversionPlan, SampleType, SRGB_TO_XYZ_D65,
+    };
+
+    #[test]
+    fn gray_lightness_round_trips_and_matches_generic_path() {
+        let width = 256u32;
+        let gray: Vec<u8> = (0..=255u8).collect();
+        let mut lab = vec![0f32; 256];
+        gray_to_lab(
+            &gray,
+            width,
+            &mut lab,
+            width * 4,
+            width,
+            1,
+            TransferFunction::Srgb,
+        );
+
+        let mut expected = vec![0f32; 256];
+        convert_image(
+            &gray,
+            width,
+            &ColorSpace::srgb(ImageConfiguration::Gray, SampleType::U8),
+            &mut expected,
+            width * 4,
+            &ColorSpace::new(ImageConfiguration::Gray, SampleType::F32, ColorModel::Lab),
+            width,
+            1,
+        );
+        for (v, e) in lab.iter().zip(expected.iter()) {
+            assert!((v - e).abs() < 1e-2, "{} != {}", v, e);
+        }
+        assert!(lab[0].abs() < 1e-3 && (lab[255] - 100.).abs() < 1e-2);
+
+        let mut back = vec![0u8; 256];
+        lab_to_gray(
+            &lab,
+            width * 4,
+            &mut back,
+            width,
+            width,
+            1,
+            TransferFunction::Srgb,
+        );
+        assert_eq!(back, gray);
+    }
+
+    #[test]
+    fn gray_alpha_keeps_alpha_through_lightness() {
+        let gray = [0u8, 255, 128, 64, 255, 0];
+        let mut lab = [0f32; 6];
+        gray_alpha_to_lab(&gray, 6, &mut lab, 24, 3, 1, TransferFunction::Srgb);
+        assert_eq!([lab[1], lab[3], lab[5]], [1., 64. / 255., 0.]);
+
+        let mut back = [0u8; 6];
+        lab_to_gray_alpha(&lab, 24, &mut back, 6, 3, 1, TransferFunction::Srgb);
+        assert_eq!(back, gray);
+    }
+
+    #[test]
+    fn plan_uses_gray_kernels() {
+        let gray_alpha: Vec<u8> = (0..64u32).map(|i| (i * 37 % 256) as u8).collect();
+        let (width, height) = (8u32, 4u32);
+        for model in [ColorModel::Lab, ColorModel::Rgb] {
+            let mut target = ColorSpace::new(ImageConfiguration::GrayAlpha, SampleType::F32, model);
+            target.transfer_function = TransferFunction::Linear;
+            let plan = ConversionPlan::new(
+                &ColorSpace::srgb(ImageConfiguration::GrayAlpha, SampleType::U8),
+                &target,
+            );
+            let mut planned = vec![0f32; 64];
+            plan.apply(&gray_alpha, 16, &mut planned, 64, width, height);
+
+            let mut expected = vec![0f32; 64];
+            if model == ColorModel::Lab {
+                gray_alpha_to_lab(
+                    &gray_alpha,
+                    16,
+                    &mut expected,
+                    64,
+                    width,
+                    height,
+                    TransferFunction::Srgb,
+                );
+            } else {
+                crate::gray_alpha_to_linear(
+                    &gray_alpha,
+                    16,
+                    &mut expected,
+                    64,
+                    width,
+                    height,
+                    TransferFunction::Srgb,
+                );
+            }
+            for (v, e) in planned.iter().zip(expected.iter()) {
+                assert!((v - e).abs() < 1e-4, "{:?}: {} != {}", model, v, e);
+            }
+
+            let mut restored = vec![0u8; 64];
+            let inverse = ConversionPlan::new(
+                &target,
+                &ColorSpace::rgb(
+                    ImageConfiguration::GrayAlpha,
+                    SampleType::U8,
+                    SRGB_TO_XYZ_D65,
+                    TransferFunction::Srgb,
+                    crate::WHITE_POINT_D65,
+                ),
+            );
+            inverse.apply(&planned, 64, &mut restored, 16, width, height);
+            // Gamma table of linear kernel truncates, so linear gray may come back one lower
+            let tolerance = if model == ColorModel::Lab { 0 } else { 1 };
+            for (v, e) in restored.iter().zip(gray_alpha.iter()) {
+                assert!(v.abs_diff(*e) <= tolerance, "{:?}: {} != {}", model, v, e);
+            }
+        }
+    }
+}
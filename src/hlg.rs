@@ -0,0 +1,365 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::gamma_curves::{pq_from_linear, pq_to_linear};
+use crate::image::ImageConfiguration;
+use crate::threading::ForEachRow;
+use crate::tone_mapping::{BT2020_LUMINANCE, SDR_WHITE_NITS};
+use crate::Rgb;
+#[cfg(feature = "rayon")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "rayon")]
+use rayon::prelude::{ParallelSlice, ParallelSliceMut};
+
+/// Nominal peak luminance of HLG reference display as ITU-R BT.2408 defines it
+pub const HLG_REFERENCE_PEAK_NITS: f32 = 1000.;
+
+const HLG_A: f32 = 0.17883277;
+const HLG_B: f32 = 0.28466892;
+const HLG_C: f32 = 0.55991073;
+
+#[inline]
+/// HLG OETF, converts normalized scene linear light in range [0, 1] into HLG signal
+pub fn hlg_oetf(scene: f32) -> f32 {
+    if scene <= 0. {
+        0.
+    } else if scene <= 1. / 12. {
+        (3. * scene).sqrt()
+    } else {
+        HLG_A * (12. * scene - HLG_B).ln() + HLG_C
+    }
+}
+
+#[inline]
+/// HLG inverse OETF, converts HLG signal into normalized scene linear light in range [0, 1]
+pub fn hlg_inverse_oetf(signal: f32) -> f32 {
+    if signal <= 0. {
+        0.
+    } else if signal <= 0.5 {
+        signal * signal * (1. / 3.)
+    } else {
+        (((signal - HLG_C) / HLG_A).exp() + HLG_B) * (1. / 12.)
+    }
+}
+
+#[inline]
+/// HLG system gamma for display with nominal peak luminance, ITU-R BT.2100 Note 5f.
+///
+/// 1.2 at 1000 nits, extended formula is used outside of 400..2000 nits range
+pub fn hlg_system_gamma(nominal_peak_nits: f32) -> f32 {
+    if (400. ..=2000.).contains(&nominal_peak_nits) {
+        1.2 + 0.42 * (nominal_peak_nits / 1000.).log10()
+    } else {
+        1.2 * 1.111f32.powf((nominal_peak_nits / 1000.).log2())
+    }
+}
+
+/// HLG OOTF, converts normalized scene linear BT.2020 light into display light in nits.
+///
+/// System gamma is applied to luminance, so hue and saturation are kept
+pub fn hlg_ootf(scene: Rgb<f32>, nominal_peak_nits: f32) -> Rgb<f32> {
+    let gamma = hlg_system_gamma(nominal_peak_nits);
+    let luminance = scene.r * BT2020_LUMINANCE[0]
+        + scene.g * BT2020_LUMINANCE[1]
+        + scene.b * BT2020_LUMINANCE[2];
+    if luminance <= 0. {
+        return Rgb::<f32>::new(0., 0., 0.);
+    }
+    let scale = nominal_peak_nits * luminance.powf(gamma - 1.);
+    Rgb::<f32>::new(scene.r * scale, scene.g * scale, scene.b * scale)
+}
+
+/// HLG inverse OOTF, converts BT.2020 display light in nits into normalized scene linear light
+pub fn hlg_inverse_ootf(display: Rgb<f32>, nominal_peak_nits: f32) -> Rgb<f32> {
+    let gamma = hlg_system_gamma(nominal_peak_nits);
+    let recip_peak = 1. / nominal_peak_nits;
+    let luminance = (display.r * BT2020_LUMINANCE[0]
+        + display.g * BT2020_LUMINANCE[1]
+        + display.b * BT2020_LUMINANCE[2])
+        * recip_peak;
+    if luminance <= 0. {
+        return Rgb::<f32>::new(0., 0., 0.);
+    }
+    let scale = luminance.powf((1. - gamma) / gamma) * recip_peak;
+    Rgb::<f32>::new(display.r * scale, display.g * scale, display.b * scale)
+}
+
+/// Converts HLG signal into BT.2020 display light in nits, see [hlg_ootf]
+pub fn hlg_to_display_light(signal: Rgb<f32>, nominal_peak_nits: f32) -> Rgb<f32> {
+    hlg_ootf(
+        Rgb::<f32>::new(
+            hlg_inverse_oetf(signal.r),
+            hlg_inverse_oetf(signal.g),
+            hlg_inverse_oetf(signal.b),
+        ),
+        nominal_peak_nits,
+    )
+}
+
+/// Converts BT.2020 display light in nits into HLG signal, light above nominal peak is clipped
+pub fn hlg_from_display_light(display: Rgb<f32>, nominal_peak_nits: f32) -> Rgb<f32> {
+    let scene = hlg_inverse_ootf(
+        Rgb::<f32>::new(
+            display.r.max(0.).min(nominal_peak_nits),
+            display.g.max(0.).min(nominal_peak_nits),
+            display.b.max(0.).min(nominal_peak_nits),
+        ),
+        nominal_peak_nits,
+    );
+    Rgb::<f32>::new(
+        hlg_oetf(scene.r.min(1.)),
+        hlg_oetf(scene.g.min(1.)),
+        hlg_oetf(scene.b.min(1.)),
+    )
+}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+enum HdrTranscoding {
+    HlgToPq = 0,
+    PqToHlg = 1,
+}
+
+impl From<u8> for HdrTranscoding {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => HdrTranscoding::HlgToPq,
+            1 => HdrTranscoding::PqToHlg,
+            _ => {
+                panic!("Not implemented")
+            }
+        }
+    }
+}
+
+fn transcode_hdr<const CHANNELS_CONFIGURATION: u8, const TRANSCODING: u8>(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+    nominal_peak_nits: f32,
+) {
+    if !(1..=16).contains(&bit_depth) {
+        panic!(
+            "Bit depth must be in range [1, 16], but it was {}",
+            bit_depth
+        );
+    }
+    let transcoding: HdrTranscoding = TRANSCODING.into();
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+    let max_value = ((1u32 << bit_depth) - 1) as f32;
+    let recip_max_value = 1. / max_value;
+
+    // Source code values are decoded into scene linear light for HLG and into nits for PQ
+    let mut decoding_table = vec![0f32; 1usize << bit_depth];
+    for (i, value) in decoding_table.iter_mut().enumerate() {
+        let signal = i as f32 * recip_max_value;
+        *value = match transcoding {
+            HdrTranscoding::HlgToPq => hlg_inverse_oetf(signal),
+            HdrTranscoding::PqToHlg => pq_to_linear(signal) * SDR_WHITE_NITS,
+        };
+    }
+
+    let src_row_length = src_stride as usize / std::mem::size_of::<u16>();
+    let dst_row_length = dst_stride as usize / std::mem::size_of::<u16>();
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = dst
+            .par_chunks_exact_mut(dst_row_length)
+            .zip(src.par_chunks_exact(src_row_length))
+            .take(height as usize);
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = dst
+            .chunks_exact_mut(dst_row_length)
+            .zip(src.chunks_exact(src_row_length))
+            .take(height as usize);
+    }
+
+    iter.for_each_row(width as usize, |(dst_row, src_row)| {
+        for (dst, src) in dst_row
+            .chunks_exact_mut(channels)
+            .zip(src_row.chunks_exact(channels))
+            .take(width as usize)
+        {
+            let decode = |offset: usize| -> f32 {
+                decoding_table[(src[offset] as usize).min(decoding_table.len() - 1)]
+            };
+            let rgb = Rgb::<f32>::new(
+                decode(image_configuration.get_r_channel_offset()),
+                decode(image_configuration.get_g_channel_offset()),
+                decode(image_configuration.get_b_channel_offset()),
+            );
+            let encoded = match transcoding {
+                HdrTranscoding::HlgToPq => {
+                    let display = hlg_ootf(rgb, nominal_peak_nits);
+                    let recip_white = 1. / SDR_WHITE_NITS;
+                    Rgb::<f32>::new(
+                        pq_from_linear(display.r * recip_white),
+                        pq_from_linear(display.g * recip_white),
+                        pq_from_linear(display.b * recip_white),
+                    )
+                }
+                HdrTranscoding::PqToHlg => hlg_from_display_light(rgb, nominal_peak_nits),
+            };
+            let quantize =
+                |v: f32| -> u16 { (v * max_value).round().max(0.).min(max_value) as u16 };
+            dst[image_configuration.get_r_channel_offset()] = quantize(encoded.r);
+            dst[image_configuration.get_g_channel_offset()] = quantize(encoded.g);
+            dst[image_configuration.get_b_channel_offset()] = quantize(encoded.b);
+            if image_configuration.has_alpha() {
+                dst[image_configuration.get_a_channel_offset()] =
+                    src[image_configuration.get_a_channel_offset()];
+            }
+        }
+    });
+}
+
+/// This function transcodes HLG encoded BT.2020 RGB image into PQ as ITU-R BT.2408 describes.
+///
+/// HLG is rendered with OOTF on display with nominal peak luminance and encoded with PQ
+///
+/// # Arguments
+/// * `src` - A slice contains HLG RGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive PQ RGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of source and destination, 10, 12 or 16 etc.
+/// * `nominal_peak_nits` - Nominal peak of HLG display, BT.2408 reference is [HLG_REFERENCE_PEAK_NITS]
+pub fn rgb_u16_hlg_to_pq(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+    nominal_peak_nits: f32,
+) {
+    transcode_hdr::<{ ImageConfiguration::Rgb as u8 }, { HdrTranscoding::HlgToPq as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        bit_depth,
+        nominal_peak_nits,
+    );
+}
+
+/// This function transcodes HLG encoded BT.2020 RGBA image into PQ as ITU-R BT.2408 describes, alpha is copied.
+///
+/// HLG is rendered with OOTF on display with nominal peak luminance and encoded with PQ
+///
+/// # Arguments
+/// * `src` - A slice contains HLG RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive PQ RGBA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of source and destination, 10, 12 or 16 etc.
+/// * `nominal_peak_nits` - Nominal peak of HLG display, BT.2408 reference is [HLG_REFERENCE_PEAK_NITS]
+pub fn rgba_u16_hlg_to_pq(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+    nominal_peak_nits: f32,
+) {
+    transcode_hdr::<{ ImageConfiguration::Rgba as u8 }, { HdrTranscoding::HlgToPq as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        bit_depth,
+        nominal_peak_nits,
+    );
+}
+
+/// This function transcodes PQ encoded BT.2020 RGB image into HLG as ITU-R BT.2408 describes.
+///
+/// Display light above nominal peak is clipped, tone map content mastered brighter beforehand
+///
+/// # Arguments
+/// * `src` - A slice contains PQ RGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive HLG RGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of source and destination, 10, 12 or 16 etc.
+/// * `nominal_peak_nits` - Nominal peak of HLG display, BT.2408 reference is [HLG_REFERENCE_PEAK_NITS]
+pub fn rgb_u16_pq_to_hlg(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+    nominal_peak_nits: f32,
+) {
+    transcode_hdr::<{ ImageConfiguration::Rgb as u8 }, { HdrTranscoding::PqToHlg as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        bit_depth,
+        nominal_peak_nits,
+    );
+}
+
+/// This function transcodes PQ encoded BT.2020 RGBA image into HLG as ITU-R BT.2408 describes, alpha is copied.
+///
+/// Display light above nominal peak is clipped, tone map content mastered brighter beforehand
+///
+/// # Arguments
+/// * `src` - A slice contains PQ RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive HLG RGBA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of source and destination, 10, 12 or 16 etc.
+/// * `nominal_peak_nits` - Nominal peak of HLG display, BT.2408 reference is [HLG_REFERENCE_PEAK_NITS]
+pub fn rgba_u16_pq_to_hlg(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+    nominal_peak_nits: f32,
+) {
+    transcode_hdr::<{ ImageConfiguration::Rgba as u8 }, { HdrTranscoding::PqToHlg as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        bit_depth,
+        nominal_peak_nits,
+    );
+}
@@ -40,28 +40,11 @@ impl Hsl {
 
     #[inline]
     pub fn to_rgb8(&self) -> Rgb<u8> {
-        let c = (1f32 - (2f32 * self.l - 1f32).abs()) * self.s;
-        let x = c * (1f32 - ((self.h / 60f32) % 2f32 - 1f32).abs());
-        let m = self.l - c / 2f32;
-
-        let (r, g, b) = if self.h >= 0f32 && self.h < 60f32 {
-            (c, x, 0f32)
-        } else if self.h >= 60f32 && self.h < 120f32 {
-            (x, c, 0f32)
-        } else if self.h >= 120f32 && self.h < 180f32 {
-            (0f32, c, x)
-        } else if self.h >= 180f32 && self.h < 240f32 {
-            (0f32, x, c)
-        } else if self.h >= 240f32 && self.h < 300f32 {
-            (x, 0f32, c)
-        } else {
-            (c, 0f32, x)
-        };
-
+        let (r, g, b) = hsl_to_rgb(self.h, self.s, self.l);
         Rgb::<u8> {
-            r: ((r + m) * 255f32).round() as u8,
-            g: ((g + m) * 255f32).round() as u8,
-            b: ((b + m) * 255f32).round() as u8,
+            r: (r * 255f32).round() as u8,
+            g: (g * 255f32).round() as u8,
+            b: (b * 255f32).round() as u8,
         }
     }
 
@@ -88,10 +71,17 @@ impl Hsl {
 
 #[inline]
 fn rgb2hsl(o_r: u8, o_g: u8, o_b: u8) -> Hsl {
-    let r = o_r as f32 / 255f32;
-    let g = o_g as f32 / 255f32;
-    let b = o_b as f32 / 255f32;
+    let (h, s, l) = rgb_to_hsl(
+        o_r as f32 / 255f32,
+        o_g as f32 / 255f32,
+        o_b as f32 / 255f32,
+    );
+    Hsl { h, s, l }
+}
 
+/// Converts normalized RGB into hue in degrees, saturation and lightness in [0, 1]
+#[inline]
+pub(crate) fn rgb_to_hsl(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
     let c_max = r.max(g).max(b);
     let c_min = r.min(g).min(b);
     let delta = c_max - c_min;
@@ -113,5 +103,29 @@ fn rgb2hsl(o_r: u8, o_g: u8, o_b: u8) -> Hsl {
     let l = 0.5f32 * (c_max + c_min);
     let s = delta / (1f32 - (2f32 * l - 1f32).abs());
 
-    Hsl { h, s, l }
+    (h, s, l)
+}
+
+/// Converts hue in degrees, saturation and lightness into normalized RGB
+#[inline]
+pub(crate) fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (f32, f32, f32) {
+    let c = (1f32 - (2f32 * l - 1f32).abs()) * s;
+    let x = c * (1f32 - ((h / 60f32) % 2f32 - 1f32).abs());
+    let m = l - c / 2f32;
+
+    let (r, g, b) = if (0f32..60f32).contains(&h) {
+        (c, x, 0f32)
+    } else if (60f32..120f32).contains(&h) {
+        (x, c, 0f32)
+    } else if (120f32..180f32).contains(&h) {
+        (0f32, c, x)
+    } else if (180f32..240f32).contains(&h) {
+        (0f32, x, c)
+    } else if (240f32..300f32).contains(&h) {
+        (x, 0f32, c)
+    } else {
+        (c, 0f32, x)
+    };
+
+    (r + m, g + m, b + m)
 }
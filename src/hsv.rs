@@ -79,7 +79,7 @@ impl Hsv {
 }
 
 #[inline]
-fn rgb_to_hsv(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+pub(crate) fn rgb_to_hsv(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
     let c_max = r.max(g).max(b);
     let c_min = r.min(g).min(b);
     let delta = c_max - c_min;
@@ -111,7 +111,7 @@ fn rgb_to_hsv(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
 
 #[inline]
 #[allow(clippy::manual_range_contains)]
-fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (f32, f32, f32) {
+pub(crate) fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (f32, f32, f32) {
     let c = v * s;
     let h_prime = (h / 60f32) % 6f32;
     let x = c * (1f32 - ((h_prime % 2f32) - 1f32).abs());
@@ -5,6 +5,8 @@
  * // license that can be found in the LICENSE file.
  */
 
+#[cfg(feature = "rayon")]
+use crate::threading::ForEachRow;
 use std::slice;
 
 use crate::image::ImageConfiguration;
@@ -15,12 +17,12 @@ use crate::neon::neon_hsv_u16_to_image;
 use crate::sse::sse_hsv_u16_to_image;
 use crate::{Hsl, Hsv};
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 
 #[allow(clippy::type_complexity)]
-fn hsv_u16_to_channels<
+pub(crate) fn hsv_u16_to_channels<
     const CHANNELS_CONFIGURATION: u8,
     const USE_ALPHA: bool,
     const TARGET: u8,
@@ -68,7 +70,7 @@ fn hsv_u16_to_channels<
         };
         dst.par_chunks_exact_mut(dst_stride as usize)
             .zip(src_slice_safe_align.par_chunks_exact(src_stride as usize))
-            .for_each(|(dst, src)| unsafe {
+            .for_each_row(width as usize, |(dst, src)| unsafe {
                 let mut _cx = 0usize;
 
                 if let Some(dispatcher) = _wide_row_handler {
@@ -250,6 +252,30 @@ pub fn hsv_to_bgra(
     );
 }
 
+/// This function converts HSV to ABGR. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains HSV data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive ABGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `scale` - Natural range for S and V is [0,1] it may be more convenient and required for u16 transformation to scale it by 100 or any other number to keep S,V in range [0, scale]
+pub fn hsv_to_abgr(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    scale: f32,
+) {
+    hsv_u16_to_channels::<{ ImageConfiguration::Abgr as u8 }, true, { HsvTarget::Hsv as u8 }>(
+        src, src_stride, dst, dst_stride, width, height, scale,
+    );
+}
+
 /// This function converts HSV to RGBA. Alpha channel is copied and leaved unchanged. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -274,6 +300,30 @@ pub fn hsv_to_rgba(
     );
 }
 
+/// This function converts HSV to ARGB. Alpha channel is copied and leaved unchanged. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains HSV data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive ARGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `scale` - Natural range for S and V is [0,1] it may be more convenient and required for u16 transformation to scale it by 100 or any other number to keep S,V in range [0, scale]
+pub fn hsv_to_argb(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    scale: f32,
+) {
+    hsv_u16_to_channels::<{ ImageConfiguration::Argb as u8 }, true, { HsvTarget::Hsv as u8 }>(
+        src, src_stride, dst, dst_stride, width, height, scale,
+    );
+}
+
 /// This function converts HSL to RGB. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -322,6 +372,30 @@ pub fn hsl_to_bgra(
     );
 }
 
+/// This function converts HSL to ABGR. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains HSL data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive ABGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `scale` - Natural range for S and V is [0,1] it may be more convenient and required for u16 transformation to scale it by 100 or any other number to keep S,V in range [0, scale]
+pub fn hsl_to_abgr(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    scale: f32,
+) {
+    hsv_u16_to_channels::<{ ImageConfiguration::Abgr as u8 }, true, { HsvTarget::Hsl as u8 }>(
+        src, src_stride, dst, dst_stride, width, height, scale,
+    );
+}
+
 /// This function converts HSL to RGBA. Alpha channel is copied and leaved unchanged. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -345,3 +419,27 @@ pub fn hsl_to_rgba(
         src, src_stride, dst, dst_stride, width, height, scale,
     );
 }
+
+/// This function converts HSL to ARGB. Alpha channel is copied and leaved unchanged. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains HSL data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive ARGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `scale` - Natural range for S and V is [0,1] it may be more convenient and required for u16 transformation to scale it by 100 or any other number to keep S,V in range [0, scale]
+pub fn hsl_to_argb(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    scale: f32,
+) {
+    hsv_u16_to_channels::<{ ImageConfiguration::Argb as u8 }, true, { HsvTarget::Hsl as u8 }>(
+        src, src_stride, dst, dst_stride, width, height, scale,
+    );
+}
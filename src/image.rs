@@ -5,37 +5,77 @@
  * // license that can be found in the LICENSE file.
  */
 
-#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+/// Interleaved pixel layout of an image
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum ImageConfiguration {
+    /// Three channels, red first
     Rgb = 0,
+    /// Four channels, red first, alpha last
     Rgba = 1,
+    /// Four channels, blue first, alpha last
     Bgra = 2,
+    /// Three channels, blue first
     Bgr = 3,
+    /// Four channels, alpha first, then red
+    ///
+    /// Named converters take it through `argb_` functions next to `rgba_` ones, such as
+    /// `argb_to_lab` or `oklab_to_argb`. Sigmoidal converters have no ARGB entry point.
+    Argb = 4,
+    /// Four channels, alpha first, then blue
+    ///
+    /// Named converters take it through `abgr_` functions next to `bgra_` ones, such as
+    /// `abgr_to_laba` or `oklab_to_abgr`. Sigmoidal converters have no ABGR entry point.
+    Abgr = 5,
+    /// Single luma channel
+    Gray = 6,
+    /// Luma followed by alpha
+    GrayAlpha = 7,
 }
 
 impl ImageConfiguration {
     #[inline(always)]
     pub const fn get_channels_count(&self) -> usize {
         match self {
+            ImageConfiguration::Gray => 1,
+            ImageConfiguration::GrayAlpha => 2,
             ImageConfiguration::Rgb | ImageConfiguration::Bgr => 3,
-            ImageConfiguration::Rgba | ImageConfiguration::Bgra => 4,
+            ImageConfiguration::Rgba
+            | ImageConfiguration::Bgra
+            | ImageConfiguration::Argb
+            | ImageConfiguration::Abgr => 4,
         }
     }
 
     #[inline(always)]
     pub const fn has_alpha(&self) -> bool {
         match self {
-            ImageConfiguration::Rgb | ImageConfiguration::Bgr => false,
-            ImageConfiguration::Rgba | ImageConfiguration::Bgra => true,
+            ImageConfiguration::Rgb | ImageConfiguration::Bgr | ImageConfiguration::Gray => false,
+            ImageConfiguration::Rgba
+            | ImageConfiguration::Bgra
+            | ImageConfiguration::Argb
+            | ImageConfiguration::Abgr
+            | ImageConfiguration::GrayAlpha => true,
         }
     }
 
+    /// Returns true for single colour channel layouts, where red, green and blue share one channel
+    #[inline(always)]
+    pub const fn is_gray(&self) -> bool {
+        matches!(
+            self,
+            ImageConfiguration::Gray | ImageConfiguration::GrayAlpha
+        )
+    }
+
     #[inline(always)]
     pub const fn get_r_channel_offset(&self) -> usize {
         match self {
             ImageConfiguration::Rgb => 0,
             ImageConfiguration::Rgba => 0,
             ImageConfiguration::Bgra | ImageConfiguration::Bgr => 2,
+            ImageConfiguration::Argb => 1,
+            ImageConfiguration::Abgr => 3,
+            ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => 0,
         }
     }
 
@@ -44,6 +84,8 @@ impl ImageConfiguration {
         match self {
             ImageConfiguration::Rgb | ImageConfiguration::Bgr => 1,
             ImageConfiguration::Rgba | ImageConfiguration::Bgra => 1,
+            ImageConfiguration::Argb | ImageConfiguration::Abgr => 2,
+            ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => 0,
         }
     }
 
@@ -53,6 +95,9 @@ impl ImageConfiguration {
             ImageConfiguration::Rgb => 2,
             ImageConfiguration::Rgba => 2,
             ImageConfiguration::Bgra | ImageConfiguration::Bgr => 0,
+            ImageConfiguration::Argb => 3,
+            ImageConfiguration::Abgr => 1,
+            ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => 0,
         }
     }
     #[inline(always)]
@@ -60,6 +105,9 @@ impl ImageConfiguration {
         match self {
             ImageConfiguration::Rgb | ImageConfiguration::Bgr => 0,
             ImageConfiguration::Rgba | ImageConfiguration::Bgra => 3,
+            ImageConfiguration::Argb | ImageConfiguration::Abgr => 0,
+            ImageConfiguration::Gray => 0,
+            ImageConfiguration::GrayAlpha => 1,
         }
     }
 }
@@ -72,6 +120,10 @@ impl From<u8> for ImageConfiguration {
             1 => ImageConfiguration::Rgba,
             2 => ImageConfiguration::Bgra,
             3 => ImageConfiguration::Bgr,
+            4 => ImageConfiguration::Argb,
+            5 => ImageConfiguration::Abgr,
+            6 => ImageConfiguration::Gray,
+            7 => ImageConfiguration::GrayAlpha,
             _ => {
                 panic!("Unknown value")
             }
@@ -0,0 +1,988 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+use crate::avx::avx_blend_row;
+use crate::blend::{composite_row, BlendMode, BlendingSpace};
+use crate::image::ImageConfiguration;
+use crate::image_view::check_image;
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use crate::neon::neon_blend_row;
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+use crate::sse::sse_blend_row;
+use crate::threading::ForEachRow;
+use crate::TransferFunction;
+#[cfg(feature = "rayon")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "rayon")]
+use rayon::prelude::ParallelSliceMut;
+use std::sync::{Arc, Mutex};
+
+type BlendRowHandler = unsafe fn(usize, *const f32, *mut f32, usize) -> usize;
+
+/// Selects SIMD row handler if the mode has one, keep the list in [BlendMode] docs in sync
+fn wide_row_handler(mode: BlendMode) -> Option<BlendRowHandler> {
+    let mut _wide_row_handler: Option<BlendRowHandler> = None;
+
+    macro_rules! select_handler {
+        ($mode: expr) => {{
+            const MODE: u8 = $mode as u8;
+            #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+            {
+                _wide_row_handler = Some(neon_blend_row::<MODE>);
+            }
+            #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+            if std::arch::is_x86_feature_detected!("sse4.1") {
+                _wide_row_handler = Some(sse_blend_row::<MODE>);
+            }
+            #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+            if std::arch::is_x86_feature_detected!("avx2") {
+                _wide_row_handler = Some(avx_blend_row::<MODE>);
+            }
+        }};
+    }
+
+    match mode {
+        BlendMode::SrcOver => select_handler!(BlendMode::SrcOver),
+        BlendMode::Multiply => select_handler!(BlendMode::Multiply),
+        BlendMode::Screen => select_handler!(BlendMode::Screen),
+        BlendMode::Darken => select_handler!(BlendMode::Darken),
+        BlendMode::Lighten => select_handler!(BlendMode::Lighten),
+        BlendMode::Difference => select_handler!(BlendMode::Difference),
+        BlendMode::Exclusion => select_handler!(BlendMode::Exclusion),
+        BlendMode::LinearBurn => select_handler!(BlendMode::LinearBurn),
+        _ => {}
+    }
+
+    _wide_row_handler
+}
+
+/// Blends two images through RGBA f32 transient rows.
+/// `load` and `store` convert colour samples, `load_alpha` and `store_alpha` convert alpha and mask samples
+fn blend_images<T: Copy + Send + Sync, const CHANNELS_CONFIGURATION: u8>(
+    src: &[T],
+    src_stride: u32,
+    dst: &mut [T],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    mode: BlendMode,
+    opacity: f32,
+    mask: Option<&[T]>,
+    mask_stride: u32,
+    load: impl Fn(T) -> f32 + Sync,
+    load_alpha: impl Fn(T) -> f32 + Sync,
+    store: impl Fn(f32) -> T + Sync,
+    store_alpha: impl Fn(f32) -> T + Sync,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    if !image_configuration.has_alpha() {
+        panic!("Blending may be done only on images with alpha");
+    }
+    let src_row_length = check_image::<T>(
+        src.len(),
+        src_stride,
+        width,
+        height,
+        image_configuration,
+        "Source",
+    );
+    let dst_row_length = check_image::<T>(
+        dst.len(),
+        dst_stride,
+        width,
+        height,
+        image_configuration,
+        "Destination",
+    );
+    let mask_row_length = match mask {
+        None => 0,
+        Some(mask) => check_image::<T>(
+            mask.len(),
+            mask_stride,
+            width,
+            height,
+            ImageConfiguration::Gray,
+            "Mask",
+        ),
+    };
+    if width == 0 || height == 0 {
+        return;
+    }
+    let channels = image_configuration.get_channels_count();
+    let opacity = opacity.max(0.).min(1.);
+    let width = width as usize;
+
+    let wide_row_handler = wide_row_handler(mode);
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = dst
+            .par_chunks_mut(dst_row_length)
+            .take(height as usize)
+            .enumerate();
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = dst
+            .chunks_mut(dst_row_length)
+            .take(height as usize)
+            .enumerate();
+    }
+
+    iter.for_each_row_init(
+        width,
+        || (vec![0f32; width * 4], vec![0f32; width * 4]),
+        |(source, backdrop), (y, dst_row)| {
+            let src_row = &src[y * src_row_length..];
+            let mask_row = mask.map(|mask| &mask[y * mask_row_length..]);
+
+            for (x, (src, dst)) in src_row
+                .chunks_exact(channels)
+                .zip(dst_row.chunks_exact(channels))
+                .take(width)
+                .enumerate()
+            {
+                let coverage = match mask_row {
+                    None => opacity,
+                    Some(mask_row) => load_alpha(mask_row[x]) * opacity,
+                };
+                let source = &mut source[x * 4..x * 4 + 4];
+                source[0] = load(src[image_configuration.get_r_channel_offset()]);
+                source[1] = load(src[image_configuration.get_g_channel_offset()]);
+                source[2] = load(src[image_configuration.get_b_channel_offset()]);
+                source[3] = load_alpha(src[image_configuration.get_a_channel_offset()]) * coverage;
+                let backdrop = &mut backdrop[x * 4..x * 4 + 4];
+                backdrop[0] = load(dst[image_configuration.get_r_channel_offset()]);
+                backdrop[1] = load(dst[image_configuration.get_g_channel_offset()]);
+                backdrop[2] = load(dst[image_configuration.get_b_channel_offset()]);
+                backdrop[3] = load_alpha(dst[image_configuration.get_a_channel_offset()]);
+            }
+
+            let mut _cx = 0usize;
+            if let Some(dispatcher) = wide_row_handler {
+                unsafe {
+                    _cx = dispatcher(_cx, source.as_ptr(), backdrop.as_mut_ptr(), width);
+                }
+            }
+            composite_row(mode, &source[_cx * 4..], &mut backdrop[_cx * 4..]);
+
+            for (dst, backdrop) in dst_row
+                .chunks_exact_mut(channels)
+                .zip(backdrop.chunks_exact(4))
+                .take(width)
+            {
+                dst[image_configuration.get_r_channel_offset()] = store(backdrop[0]);
+                dst[image_configuration.get_g_channel_offset()] = store(backdrop[1]);
+                dst[image_configuration.get_b_channel_offset()] = store(backdrop[2]);
+                dst[image_configuration.get_a_channel_offset()] = store_alpha(backdrop[3]);
+            }
+        },
+    );
+}
+
+fn blend_images_u8<const CHANNELS_CONFIGURATION: u8>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    mode: BlendMode,
+    opacity: f32,
+    mask: Option<&[u8]>,
+    mask_stride: u32,
+    blending_space: BlendingSpace,
+) {
+    const SCALE: f32 = 1. / 255.;
+    let load_alpha = |v: u8| v as f32 * SCALE;
+    let store_alpha = |v: f32| (v * 255.).round().max(0.).min(255.) as u8;
+    match blending_space {
+        BlendingSpace::Gamma => blend_images::<u8, CHANNELS_CONFIGURATION>(
+            src,
+            src_stride,
+            dst,
+            dst_stride,
+            width,
+            height,
+            mode,
+            opacity,
+            mask,
+            mask_stride,
+            load_alpha,
+            load_alpha,
+            store_alpha,
+            store_alpha,
+        ),
+        BlendingSpace::Linear(transfer_function) => {
+            let mut linearize_table = vec![0f32; 256];
+            for (i, lut) in linearize_table.iter_mut().enumerate() {
+                *lut = transfer_function.linearize(i as f32 * SCALE);
+            }
+
+            let mut gamma_table = vec![0u8; 4097];
+            for (i, lut) in gamma_table.iter_mut().enumerate() {
+                *lut = (transfer_function.gamma(i as f32 * (1. / 4096.)) * 255.)
+                    .round()
+                    .min(255.) as u8;
+            }
+
+            blend_images::<u8, CHANNELS_CONFIGURATION>(
+                src,
+                src_stride,
+                dst,
+                dst_stride,
+                width,
+                height,
+                mode,
+                opacity,
+                mask,
+                mask_stride,
+                |v: u8| unsafe { *linearize_table.get_unchecked(v as usize) },
+                load_alpha,
+                |v: f32| unsafe {
+                    *gamma_table.get_unchecked((v * 4096.).round().max(0.).min(4096.) as usize)
+                },
+                store_alpha,
+            )
+        }
+    }
+}
+
+const U16_GAMMA_TABLE_SIZE: usize = 65536;
+
+/// Linearization and gamma tables of one transfer function and bit depth
+struct U16LinearTables {
+    linearize: Vec<f32>,
+    gamma: Vec<u16>,
+}
+
+/// Count of recently used u16 tables kept between calls
+const U16_LINEAR_TABLES_CACHE_SIZE: usize = 4;
+
+type U16LinearTablesCache = Mutex<Vec<(TransferFunction, u32, Arc<U16LinearTables>)>>;
+
+static U16_LINEAR_TABLES: U16LinearTablesCache = Mutex::new(Vec::new());
+
+/// Returns tables for `transfer_function` and `bit_depth`, they're built once and reused by later calls
+fn u16_linear_tables(transfer_function: TransferFunction, bit_depth: u32) -> Arc<U16LinearTables> {
+    let mut cache = U16_LINEAR_TABLES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(position) = cache
+        .iter()
+        .position(|(tf, depth, _)| *tf == transfer_function && *depth == bit_depth)
+    {
+        let entry = cache.remove(position);
+        let tables = entry.2.clone();
+        cache.push(entry);
+        return tables;
+    }
+
+    let max_value = ((1u32 << bit_depth) - 1) as f32;
+    let scale = 1. / max_value;
+    let mut linearize = vec![0f32; 1usize << bit_depth];
+    for (i, lut) in linearize.iter_mut().enumerate() {
+        *lut = transfer_function.linearize(i as f32 * scale);
+    }
+    let mut gamma = vec![0u16; U16_GAMMA_TABLE_SIZE];
+    for (i, lut) in gamma.iter_mut().enumerate() {
+        *lut = (transfer_function.gamma(i as f32 * (1. / (U16_GAMMA_TABLE_SIZE - 1) as f32))
+            * max_value)
+            .round()
+            .min(max_value) as u16;
+    }
+    let tables = Arc::new(U16LinearTables { linearize, gamma });
+
+    if cache.len() == U16_LINEAR_TABLES_CACHE_SIZE {
+        cache.remove(0);
+    }
+    cache.push((transfer_function, bit_depth, tables.clone()));
+    tables
+}
+
+fn blend_images_u16<const CHANNELS_CONFIGURATION: u8>(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    mode: BlendMode,
+    opacity: f32,
+    mask: Option<&[u16]>,
+    mask_stride: u32,
+    blending_space: BlendingSpace,
+    bit_depth: u32,
+) {
+    if !(1..=16).contains(&bit_depth) {
+        panic!(
+            "Bit depth must be in range [1, 16], but it was {}",
+            bit_depth
+        );
+    }
+    let max_value = ((1u32 << bit_depth) - 1) as f32;
+    let scale = 1. / max_value;
+    let load_alpha = |v: u16| (v as f32 * scale).min(1.);
+    let store_alpha = |v: f32| (v * max_value).round().max(0.).min(max_value) as u16;
+    match blending_space {
+        BlendingSpace::Gamma => blend_images::<u16, CHANNELS_CONFIGURATION>(
+            src,
+            src_stride,
+            dst,
+            dst_stride,
+            width,
+            height,
+            mode,
+            opacity,
+            mask,
+            mask_stride,
+            load_alpha,
+            load_alpha,
+            store_alpha,
+            store_alpha,
+        ),
+        BlendingSpace::Linear(transfer_function) => {
+            let tables = u16_linear_tables(transfer_function, bit_depth);
+            let linearize_table = &tables.linearize;
+            let gamma_table = &tables.gamma;
+
+            blend_images::<u16, CHANNELS_CONFIGURATION>(
+                src,
+                src_stride,
+                dst,
+                dst_stride,
+                width,
+                height,
+                mode,
+                opacity,
+                mask,
+                mask_stride,
+                |v: u16| {
+                    let index = (v as usize).min(linearize_table.len() - 1);
+                    unsafe { *linearize_table.get_unchecked(index) }
+                },
+                load_alpha,
+                |v: f32| unsafe {
+                    *gamma_table.get_unchecked(
+                        (v * (U16_GAMMA_TABLE_SIZE - 1) as f32)
+                            .round()
+                            .max(0.)
+                            .min((U16_GAMMA_TABLE_SIZE - 1) as f32)
+                            as usize,
+                    )
+                },
+                store_alpha,
+            )
+        }
+    }
+}
+
+fn blend_images_f32<const CHANNELS_CONFIGURATION: u8>(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    mode: BlendMode,
+    opacity: f32,
+    mask: Option<&[f32]>,
+    mask_stride: u32,
+    blending_space: BlendingSpace,
+) {
+    let load_alpha = |v: f32| v.max(0.).min(1.);
+    match blending_space {
+        BlendingSpace::Gamma => blend_images::<f32, CHANNELS_CONFIGURATION>(
+            src,
+            src_stride,
+            dst,
+            dst_stride,
+            width,
+            height,
+            mode,
+            opacity,
+            mask,
+            mask_stride,
+            |v: f32| v,
+            load_alpha,
+            |v: f32| v,
+            |v: f32| v,
+        ),
+        BlendingSpace::Linear(transfer_function) => blend_images::<f32, CHANNELS_CONFIGURATION>(
+            src,
+            src_stride,
+            dst,
+            dst_stride,
+            width,
+            height,
+            mode,
+            opacity,
+            mask,
+            mask_stride,
+            |v: f32| transfer_function.linearize(v),
+            load_alpha,
+            |v: f32| transfer_function.gamma(v),
+            |v: f32| v,
+        ),
+    }
+}
+
+/// This function composites RGBA image over RGBA backdrop, result is written into backdrop
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA source layer
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice contains RGBA backdrop and receives result
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `mode` - Porter-Duff operator or blend mode
+/// * `opacity` - Source layer opacity in range [0, 1]
+/// * `mask` - Optional single channel plane which multiplies source alpha
+/// * `mask_stride` - Bytes per row for mask data
+/// * `blending_space` - Blend encoded values or linearize them before blending
+pub fn rgba_blend_images(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    mode: BlendMode,
+    opacity: f32,
+    mask: Option<&[u8]>,
+    mask_stride: u32,
+    blending_space: BlendingSpace,
+) {
+    blend_images_u8::<{ ImageConfiguration::Rgba as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        mode,
+        opacity,
+        mask,
+        mask_stride,
+        blending_space,
+    );
+}
+
+/// This function composites BGRA image over BGRA backdrop, result is written into backdrop
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA source layer
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice contains BGRA backdrop and receives result
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `mode` - Porter-Duff operator or blend mode
+/// * `opacity` - Source layer opacity in range [0, 1]
+/// * `mask` - Optional single channel plane which multiplies source alpha
+/// * `mask_stride` - Bytes per row for mask data
+/// * `blending_space` - Blend encoded values or linearize them before blending
+pub fn bgra_blend_images(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    mode: BlendMode,
+    opacity: f32,
+    mask: Option<&[u8]>,
+    mask_stride: u32,
+    blending_space: BlendingSpace,
+) {
+    blend_images_u8::<{ ImageConfiguration::Bgra as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        mode,
+        opacity,
+        mask,
+        mask_stride,
+        blending_space,
+    );
+}
+
+/// This function composites RGBA image over RGBA backdrop with 16 bit storage, result is written into backdrop
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA source layer
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice contains RGBA backdrop and receives result
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `mode` - Porter-Duff operator or blend mode
+/// * `opacity` - Source layer opacity in range [0, 1]
+/// * `mask` - Optional single channel plane with the same bit depth which multiplies source alpha
+/// * `mask_stride` - Bytes per row for mask data
+/// * `blending_space` - Blend encoded values or linearize them before blending
+/// * `bit_depth` - Bit depth of samples, 10, 12 or 16 etc.
+pub fn rgba_u16_blend_images(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    mode: BlendMode,
+    opacity: f32,
+    mask: Option<&[u16]>,
+    mask_stride: u32,
+    blending_space: BlendingSpace,
+    bit_depth: u32,
+) {
+    blend_images_u16::<{ ImageConfiguration::Rgba as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        mode,
+        opacity,
+        mask,
+        mask_stride,
+        blending_space,
+        bit_depth,
+    );
+}
+
+/// This function composites BGRA image over BGRA backdrop with 16 bit storage, result is written into backdrop
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA source layer
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice contains BGRA backdrop and receives result
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `mode` - Porter-Duff operator or blend mode
+/// * `opacity` - Source layer opacity in range [0, 1]
+/// * `mask` - Optional single channel plane with the same bit depth which multiplies source alpha
+/// * `mask_stride` - Bytes per row for mask data
+/// * `blending_space` - Blend encoded values or linearize them before blending
+/// * `bit_depth` - Bit depth of samples, 10, 12 or 16 etc.
+pub fn bgra_u16_blend_images(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    mode: BlendMode,
+    opacity: f32,
+    mask: Option<&[u16]>,
+    mask_stride: u32,
+    blending_space: BlendingSpace,
+    bit_depth: u32,
+) {
+    blend_images_u16::<{ ImageConfiguration::Bgra as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        mode,
+        opacity,
+        mask,
+        mask_stride,
+        blending_space,
+        bit_depth,
+    );
+}
+
+/// This function composites RGBA f32 image over RGBA f32 backdrop, result is written into backdrop
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA source layer
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice contains RGBA backdrop and receives result
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `mode` - Porter-Duff operator or blend mode
+/// * `opacity` - Source layer opacity in range [0, 1]
+/// * `mask` - Optional single channel plane in range [0, 1] which multiplies source alpha
+/// * `mask_stride` - Bytes per row for mask data
+/// * `blending_space` - Blend values as is or linearize them before blending
+pub fn rgba_f32_blend_images(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    mode: BlendMode,
+    opacity: f32,
+    mask: Option<&[f32]>,
+    mask_stride: u32,
+    blending_space: BlendingSpace,
+) {
+    blend_images_f32::<{ ImageConfiguration::Rgba as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        mode,
+        opacity,
+        mask,
+        mask_stride,
+        blending_space,
+    );
+}
+
+/// This function composites BGRA f32 image over BGRA f32 backdrop, result is written into backdrop
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA source layer
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice contains BGRA backdrop and receives result
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `mode` - Porter-Duff operator or blend mode
+/// * `opacity` - Source layer opacity in range [0, 1]
+/// * `mask` - Optional single channel plane in range [0, 1] which multiplies source alpha
+/// * `mask_stride` - Bytes per row for mask data
+/// * `blending_space` - Blend values as is or linearize them before blending
+pub fn bgra_f32_blend_images(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    mode: BlendMode,
+    opacity: f32,
+    mask: Option<&[f32]>,
+    mask_stride: u32,
+    blending_space: BlendingSpace,
+) {
+    blend_images_f32::<{ ImageConfiguration::Bgra as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        mode,
+        opacity,
+        mask,
+        mask_stride,
+        blending_space,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Width covers SIMD bodies and scalar tails of every backend
+    const WIDTH: usize = 19;
+
+    fn blend_pixel(mode: BlendMode, source: [f32; 4], backdrop: [f32; 4]) -> [f32; 4] {
+        let src = source.repeat(WIDTH);
+        let mut dst = backdrop.repeat(WIDTH);
+        let stride = WIDTH as u32 * 4 * 4;
+        rgba_f32_blend_images(
+            &src,
+            stride,
+            &mut dst,
+            stride,
+            WIDTH as u32,
+            1,
+            mode,
+            1.,
+            None,
+            0,
+            BlendingSpace::Gamma,
+        );
+        for pixel in dst.chunks_exact(4) {
+            assert_eq!(pixel, &dst[..4], "{:?} differs between lanes", mode);
+        }
+        [dst[0], dst[1], dst[2], dst[3]]
+    }
+
+    fn assert_close(mode: BlendMode, actual: [f32; 4], expected: [f32; 4]) {
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!(
+                (a - e).abs() < 1e-4,
+                "{:?}: expected {:?}, got {:?}",
+                mode,
+                expected,
+                actual
+            );
+        }
+    }
+
+    #[test]
+    fn src_over_matches_specification() {
+        let red_half = [1., 0., 0., 0.5];
+        let result = blend_pixel(BlendMode::SrcOver, red_half, [0., 0., 1., 1.]);
+        assert_close(BlendMode::SrcOver, result, [0.5, 0., 0.5, 1.]);
+        // αo = 0.5 + 0.5 * 0.5, co = (1 * 0.5 + 0 * 0.25) / αo and (0 * 0.5 + 1 * 0.25) / αo
+        let result = blend_pixel(BlendMode::SrcOver, red_half, [0., 0., 1., 0.5]);
+        assert_close(BlendMode::SrcOver, result, [2. / 3., 0., 1. / 3., 0.75]);
+    }
+
+    #[test]
+    fn porter_duff_operators_match_specification() {
+        let source = [0.2, 0.4, 0.6, 0.6];
+        let backdrop = [0.9, 0.5, 0.1, 0.3];
+        // Fa and Fb from Compositing and Blending Level 1 with αs = 0.6 and αb = 0.3
+        let operators = [
+            (BlendMode::Clear, 0., 0.),
+            (BlendMode::Src, 1., 0.),
+            (BlendMode::Dst, 0., 1.),
+            (BlendMode::SrcOver, 1., 0.4),
+            (BlendMode::DstOver, 0.7, 1.),
+            (BlendMode::SrcIn, 0.3, 0.),
+            (BlendMode::DstIn, 0., 0.6),
+            (BlendMode::SrcOut, 0.7, 0.),
+            (BlendMode::DstOut, 0., 0.4),
+            (BlendMode::SrcAtop, 0.3, 0.4),
+            (BlendMode::DstAtop, 0.7, 0.6),
+            (BlendMode::Xor, 0.7, 0.4),
+        ];
+        for (mode, fa, fb) in operators {
+            let source_weight = source[3] * fa;
+            let backdrop_weight = backdrop[3] * fb;
+            let alpha = source_weight + backdrop_weight;
+            let expected = if alpha == 0. {
+                [0.; 4]
+            } else {
+                let channel =
+                    |i: usize| (source[i] * source_weight + backdrop[i] * backdrop_weight) / alpha;
+                [channel(0), channel(1), channel(2), alpha]
+            };
+            assert_close(mode, blend_pixel(mode, source, backdrop), expected);
+        }
+        // Plus-lighter sums premultiplied colours: 0.2 * 0.6 + 0.9 * 0.3 = 0.39
+        let result = blend_pixel(BlendMode::Add, source, backdrop);
+        assert_close(
+            BlendMode::Add,
+            result,
+            [0.39 / 0.9, 0.39 / 0.9, 0.39 / 0.9, 0.9],
+        );
+    }
+
+    #[test]
+    fn separable_modes_keep_identities() {
+        let backdrop = [0.9, 0.5, 0.1, 1.];
+        let white = [1., 1., 1., 1.];
+        let black = [0., 0., 0., 1.];
+        assert_close(
+            BlendMode::Multiply,
+            blend_pixel(BlendMode::Multiply, white, backdrop),
+            backdrop,
+        );
+        assert_close(
+            BlendMode::Multiply,
+            blend_pixel(BlendMode::Multiply, black, backdrop),
+            black,
+        );
+        assert_close(
+            BlendMode::Multiply,
+            blend_pixel(BlendMode::Multiply, [0.5, 0.5, 0.5, 1.], backdrop),
+            [0.45, 0.25, 0.05, 1.],
+        );
+        assert_close(
+            BlendMode::Screen,
+            blend_pixel(BlendMode::Screen, black, backdrop),
+            backdrop,
+        );
+        assert_close(
+            BlendMode::Screen,
+            blend_pixel(BlendMode::Screen, white, backdrop),
+            white,
+        );
+        assert_close(
+            BlendMode::Screen,
+            blend_pixel(BlendMode::Screen, [0.5, 0.5, 0.5, 1.], backdrop),
+            [0.95, 0.75, 0.55, 1.],
+        );
+        assert_close(
+            BlendMode::Difference,
+            blend_pixel(BlendMode::Difference, backdrop, backdrop),
+            black,
+        );
+        assert_close(
+            BlendMode::Darken,
+            blend_pixel(BlendMode::Darken, [0.5, 0.5, 0.5, 1.], backdrop),
+            [0.5, 0.5, 0.1, 1.],
+        );
+        assert_close(
+            BlendMode::Lighten,
+            blend_pixel(BlendMode::Lighten, [0.5, 0.5, 0.5, 1.], backdrop),
+            [0.9, 0.5, 0.5, 1.],
+        );
+        // Half transparent source keeps half of backdrop
+        assert_close(
+            BlendMode::Multiply,
+            blend_pixel(BlendMode::Multiply, [0., 0., 0., 0.5], backdrop),
+            [0.45, 0.25, 0.05, 1.],
+        );
+    }
+
+    #[test]
+    fn non_separable_modes_match_specification() {
+        let red = [1., 0., 0., 1.];
+        let blue = [0., 0., 1., 1.];
+        // SetLum(red, Lum(blue) = 0.11) clips to (0.11 + 0.7 * 0.11 / 0.3, 0, 0)
+        let clipped_red = [0.11 + 0.7 * 0.11 / 0.3, 0., 0., 1.];
+        assert_close(
+            BlendMode::Color,
+            blend_pixel(BlendMode::Color, red, blue),
+            clipped_red,
+        );
+        assert_close(
+            BlendMode::Luminosity,
+            blend_pixel(BlendMode::Luminosity, blue, red),
+            clipped_red,
+        );
+        // Gray backdrop has no saturation, so hue of source is dropped
+        assert_close(
+            BlendMode::Hue,
+            blend_pixel(BlendMode::Hue, blue, [0.5, 0.5, 0.5, 1.]),
+            [0.5, 0.5, 0.5, 1.],
+        );
+        // Gray source removes saturation and keeps luminosity 0.3 * 0.8 + 0.59 * 0.5 + 0.11 * 0.3
+        let lum = 0.3 * 0.8 + 0.59 * 0.5 + 0.11 * 0.3;
+        assert_close(
+            BlendMode::Saturation,
+            blend_pixel(
+                BlendMode::Saturation,
+                [0.5, 0.5, 0.5, 1.],
+                [0.8, 0.5, 0.3, 1.],
+            ),
+            [lum, lum, lum, 1.],
+        );
+    }
+
+    #[test]
+    fn mask_and_opacity_scale_source_alpha() {
+        let src = [255u8, 0, 0, 255, 255, 0, 0, 255];
+        let mut dst = [0u8, 0, 255, 255, 0, 0, 255, 255];
+        let mask = [255u8, 0];
+        rgba_blend_images(
+            &src,
+            8,
+            &mut dst,
+            8,
+            2,
+            1,
+            BlendMode::SrcOver,
+            0.5,
+            Some(&mask),
+            2,
+            BlendingSpace::Gamma,
+        );
+        assert_eq!(dst, [128, 0, 128, 255, 0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn u16_linear_blend_reuses_tables() {
+        let src = [1023u16, 0, 0, 1023];
+        let expected = {
+            let mut dst = [0u16, 0, 1023, 1023];
+            rgba_u16_blend_images(
+                &src,
+                8,
+                &mut dst,
+                8,
+                1,
+                1,
+                BlendMode::SrcOver,
+                1.,
+                None,
+                0,
+                BlendingSpace::Linear(TransferFunction::Srgb),
+                10,
+            );
+            dst
+        };
+        assert_eq!(expected, [1023, 0, 0, 1023]);
+        let first = u16_linear_tables(TransferFunction::Srgb, 10);
+        let second = u16_linear_tables(TransferFunction::Srgb, 10);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(first.linearize.len(), 1024);
+    }
+
+    #[test]
+    fn last_row_may_be_shorter_than_stride() {
+        let src = [255u8; 4 * 3];
+        let mut dst = [0u8; 4 * 3];
+        rgba_blend_images(
+            &src,
+            8,
+            &mut dst,
+            8,
+            1,
+            2,
+            BlendMode::Src,
+            1.,
+            None,
+            0,
+            BlendingSpace::Gamma,
+        );
+        assert_eq!(dst, [255, 255, 255, 255, 0, 0, 0, 0, 255, 255, 255, 255]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Destination image doesn't fit into slice")]
+    fn short_destination_panics() {
+        let src = [0u8; 16];
+        let mut dst = [0u8; 12];
+        rgba_blend_images(
+            &src,
+            8,
+            &mut dst,
+            8,
+            2,
+            2,
+            BlendMode::SrcOver,
+            1.,
+            None,
+            0,
+            BlendingSpace::Gamma,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Mask image doesn't fit into slice")]
+    fn short_mask_panics() {
+        let src = [0u8; 16];
+        let mut dst = [0u8; 16];
+        let mask = [0u8; 3];
+        rgba_blend_images(
+            &src,
+            8,
+            &mut dst,
+            8,
+            2,
+            2,
+            BlendMode::SrcOver,
+            1.,
+            Some(&mask),
+            2,
+            BlendingSpace::Gamma,
+        );
+    }
+}
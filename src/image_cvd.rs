@@ -0,0 +1,441 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::cvd::{ColorVisionDeficiency, CvdModel, CvdSimulation};
+use crate::gamma_curves::TransferFunction;
+use crate::image::ImageConfiguration;
+use crate::threading::ForEachRow;
+use crate::Rgb;
+#[cfg(feature = "rayon")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "rayon")]
+use rayon::prelude::{ParallelSlice, ParallelSliceMut};
+
+fn image_cvd<const CHANNELS_CONFIGURATION: u8, const DALTONIZE: bool>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    deficiency: ColorVisionDeficiency,
+    severity: f32,
+    model: CvdModel,
+    transfer_function: TransferFunction,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+
+    let simulation = CvdSimulation::new(deficiency, severity, model);
+
+    let mut linearize_table = vec![0f32; 256];
+    for (i, lut) in linearize_table.iter_mut().enumerate() {
+        *lut = transfer_function.linearize(i as f32 * (1. / 255.0));
+    }
+
+    let mut gamma_table = vec![0u8; 2049];
+    for (i, lut) in gamma_table.iter_mut().enumerate() {
+        *lut = (transfer_function.gamma(i as f32 * (1. / 2048.0)) * 255.)
+            .round()
+            .min(255.) as u8;
+    }
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = dst
+            .par_chunks_exact_mut(dst_stride as usize)
+            .zip(src.par_chunks_exact(src_stride as usize))
+            .take(height as usize);
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = dst
+            .chunks_exact_mut(dst_stride as usize)
+            .zip(src.chunks_exact(src_stride as usize))
+            .take(height as usize);
+    }
+
+    iter.for_each_row(width as usize, |(dst_row, src_row)| {
+        for (dst, src) in dst_row
+            .chunks_exact_mut(channels)
+            .zip(src_row.chunks_exact(channels))
+            .take(width as usize)
+        {
+            let r = src[image_configuration.get_r_channel_offset()];
+            let g = src[image_configuration.get_g_channel_offset()];
+            let b = src[image_configuration.get_b_channel_offset()];
+
+            let linear = unsafe {
+                Rgb::<f32>::new(
+                    *linearize_table.get_unchecked(r as usize),
+                    *linearize_table.get_unchecked(g as usize),
+                    *linearize_table.get_unchecked(b as usize),
+                )
+            };
+
+            let processed = if DALTONIZE {
+                simulation.daltonize(linear)
+            } else {
+                simulation.simulate(linear)
+            };
+
+            let rgb = (Rgb::<f32>::new(
+                processed.r.min(1f32).max(0f32),
+                processed.g.min(1f32).max(0f32),
+                processed.b.min(1f32).max(0f32),
+            ) * Rgb::<f32>::dup(2048f32))
+            .round()
+            .cast::<u16>();
+
+            unsafe {
+                dst[image_configuration.get_r_channel_offset()] =
+                    *gamma_table.get_unchecked(rgb.r.min(2048) as usize);
+                dst[image_configuration.get_g_channel_offset()] =
+                    *gamma_table.get_unchecked(rgb.g.min(2048) as usize);
+                dst[image_configuration.get_b_channel_offset()] =
+                    *gamma_table.get_unchecked(rgb.b.min(2048) as usize);
+            }
+
+            if image_configuration.has_alpha() {
+                dst[image_configuration.get_a_channel_offset()] =
+                    src[image_configuration.get_a_channel_offset()];
+            }
+        }
+    });
+}
+
+/// This function simulates colour vision deficiency on RGB image
+///
+/// Simulation is done in linear light
+///
+/// # Arguments
+/// * `src` - A slice contains RGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive simulated RGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `deficiency` - Colour vision deficiency type
+/// * `severity` - Deficiency severity in range [0, 1], 1 is dichromacy
+/// * `model` - Simulation model, see [CvdModel]
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn rgb_simulate_cvd(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    deficiency: ColorVisionDeficiency,
+    severity: f32,
+    model: CvdModel,
+    transfer_function: TransferFunction,
+) {
+    image_cvd::<{ ImageConfiguration::Rgb as u8 }, false>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        deficiency,
+        severity,
+        model,
+        transfer_function,
+    );
+}
+
+/// This function daltonizes RGB image
+///
+/// Information lost for the selected deficiency is redistributed into visible channels,
+/// processing is done in linear light
+///
+/// # Arguments
+/// * `src` - A slice contains RGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive daltonized RGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `deficiency` - Colour vision deficiency type
+/// * `severity` - Deficiency severity in range [0, 1], 1 is dichromacy
+/// * `model` - Simulation model, see [CvdModel]
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn rgb_daltonize(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    deficiency: ColorVisionDeficiency,
+    severity: f32,
+    model: CvdModel,
+    transfer_function: TransferFunction,
+) {
+    image_cvd::<{ ImageConfiguration::Rgb as u8 }, true>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        deficiency,
+        severity,
+        model,
+        transfer_function,
+    );
+}
+
+/// This function simulates colour vision deficiency on RGBA image
+///
+/// Simulation is done in linear light, alpha channel is copied as is
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive simulated RGBA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `deficiency` - Colour vision deficiency type
+/// * `severity` - Deficiency severity in range [0, 1], 1 is dichromacy
+/// * `model` - Simulation model, see [CvdModel]
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn rgba_simulate_cvd(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    deficiency: ColorVisionDeficiency,
+    severity: f32,
+    model: CvdModel,
+    transfer_function: TransferFunction,
+) {
+    image_cvd::<{ ImageConfiguration::Rgba as u8 }, false>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        deficiency,
+        severity,
+        model,
+        transfer_function,
+    );
+}
+
+/// This function daltonizes RGBA image
+///
+/// Information lost for the selected deficiency is redistributed into visible channels,
+/// processing is done in linear light, alpha channel is copied as is
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive daltonized RGBA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `deficiency` - Colour vision deficiency type
+/// * `severity` - Deficiency severity in range [0, 1], 1 is dichromacy
+/// * `model` - Simulation model, see [CvdModel]
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn rgba_daltonize(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    deficiency: ColorVisionDeficiency,
+    severity: f32,
+    model: CvdModel,
+    transfer_function: TransferFunction,
+) {
+    image_cvd::<{ ImageConfiguration::Rgba as u8 }, true>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        deficiency,
+        severity,
+        model,
+        transfer_function,
+    );
+}
+
+/// This function simulates colour vision deficiency on BGRA image
+///
+/// Simulation is done in linear light, alpha channel is copied as is
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive simulated BGRA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `deficiency` - Colour vision deficiency type
+/// * `severity` - Deficiency severity in range [0, 1], 1 is dichromacy
+/// * `model` - Simulation model, see [CvdModel]
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn bgra_simulate_cvd(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    deficiency: ColorVisionDeficiency,
+    severity: f32,
+    model: CvdModel,
+    transfer_function: TransferFunction,
+) {
+    image_cvd::<{ ImageConfiguration::Bgra as u8 }, false>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        deficiency,
+        severity,
+        model,
+        transfer_function,
+    );
+}
+
+/// This function daltonizes BGRA image
+///
+/// Information lost for the selected deficiency is redistributed into visible channels,
+/// processing is done in linear light, alpha channel is copied as is
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive daltonized BGRA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `deficiency` - Colour vision deficiency type
+/// * `severity` - Deficiency severity in range [0, 1], 1 is dichromacy
+/// * `model` - Simulation model, see [CvdModel]
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn bgra_daltonize(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    deficiency: ColorVisionDeficiency,
+    severity: f32,
+    model: CvdModel,
+    transfer_function: TransferFunction,
+) {
+    image_cvd::<{ ImageConfiguration::Bgra as u8 }, true>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        deficiency,
+        severity,
+        model,
+        transfer_function,
+    );
+}
+
+/// This function simulates colour vision deficiency on BGR image
+///
+/// Simulation is done in linear light
+///
+/// # Arguments
+/// * `src` - A slice contains BGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive simulated BGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `deficiency` - Colour vision deficiency type
+/// * `severity` - Deficiency severity in range [0, 1], 1 is dichromacy
+/// * `model` - Simulation model, see [CvdModel]
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn bgr_simulate_cvd(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    deficiency: ColorVisionDeficiency,
+    severity: f32,
+    model: CvdModel,
+    transfer_function: TransferFunction,
+) {
+    image_cvd::<{ ImageConfiguration::Bgr as u8 }, false>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        deficiency,
+        severity,
+        model,
+        transfer_function,
+    );
+}
+
+/// This function daltonizes BGR image
+///
+/// Information lost for the selected deficiency is redistributed into visible channels,
+/// processing is done in linear light
+///
+/// # Arguments
+/// * `src` - A slice contains BGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive daltonized BGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `deficiency` - Colour vision deficiency type
+/// * `severity` - Deficiency severity in range [0, 1], 1 is dichromacy
+/// * `model` - Simulation model, see [CvdModel]
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn bgr_daltonize(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    deficiency: ColorVisionDeficiency,
+    severity: f32,
+    model: CvdModel,
+    transfer_function: TransferFunction,
+) {
+    image_cvd::<{ ImageConfiguration::Bgr as u8 }, true>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        deficiency,
+        severity,
+        model,
+        transfer_function,
+    );
+}
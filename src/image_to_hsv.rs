@@ -11,16 +11,18 @@ use crate::image_to_hsv_support::HsvTarget;
 use crate::neon::neon_channels_to_hsv_u16;
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::sse::sse_channels_to_hsv_u16;
+#[cfg(feature = "rayon")]
+use crate::threading::ForEachRow;
 use crate::Rgb;
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 #[cfg(feature = "rayon")]
 use std::slice;
 
 #[allow(clippy::type_complexity)]
-fn channels_to_hsv_u16<
+pub(crate) fn channels_to_hsv_u16<
     const CHANNELS_CONFIGURATION: u8,
     const USE_ALPHA: bool,
     const TARGET: u8,
@@ -68,7 +70,7 @@ fn channels_to_hsv_u16<
         dst_slice_safe_align
             .par_chunks_exact_mut(dst_stride as usize)
             .zip(src.par_chunks_exact(src_stride as usize))
-            .for_each(|(dst, src)| unsafe {
+            .for_each_row(width as usize, |(dst, src)| unsafe {
                 let mut _cx = 0usize;
 
                 if let Some(dispatcher) = _wide_row_handler {
@@ -257,6 +259,30 @@ pub fn bgra_to_hsv(
     );
 }
 
+/// This function converts ABGR to HSV. Alpha channel is copied and leaved unchanged. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive HSV data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `scale` - Natural range for S and V is [0,1] it may be more convenient and required for u16 transformation to scale it by 100 or any other number to keep S,V in range [0, scale]
+pub fn abgr_to_hsv(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    scale: f32,
+) {
+    channels_to_hsv_u16::<{ ImageConfiguration::Abgr as u8 }, true, { HsvTarget::Hsv as u8 }>(
+        src, src_stride, dst, dst_stride, width, height, scale,
+    );
+}
+
 /// This function converts RGBA to HSV. Alpha channel is copied and leaved unchanged. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -281,6 +307,30 @@ pub fn rgba_to_hsv(
     );
 }
 
+/// This function converts ARGB to HSV. Alpha channel is copied and leaved unchanged. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive HSV data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `scale` - Natural range for S and V is [0,1] it may be more convenient and required for u16 transformation to scale it by 100 or any other number to keep S,V in range [0, scale]
+pub fn argb_to_hsv(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    scale: f32,
+) {
+    channels_to_hsv_u16::<{ ImageConfiguration::Argb as u8 }, true, { HsvTarget::Hsv as u8 }>(
+        src, src_stride, dst, dst_stride, width, height, scale,
+    );
+}
+
 /// This function converts RGB to HSL. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -329,6 +379,30 @@ pub fn bgra_to_hsl(
     );
 }
 
+/// This function converts ABGR to HSL. Alpha channel is copied and leaved unchanged. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive HSL data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `scale` - Natural range for S and V is [0,1] it may be more convenient and required for u16 transformation to scale it by 100 or any other number to keep S,V in range [0, scale]
+pub fn abgr_to_hsl(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    scale: f32,
+) {
+    channels_to_hsv_u16::<{ ImageConfiguration::Abgr as u8 }, true, { HsvTarget::Hsl as u8 }>(
+        src, src_stride, dst, dst_stride, width, height, scale,
+    );
+}
+
 /// This function converts RGBA to HSL. Alpha channel is copied and leaved unchanged. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -352,3 +426,27 @@ pub fn rgba_to_hsl(
         src, src_stride, dst, dst_stride, width, height, scale,
     );
 }
+
+/// This function converts ARGB to HSL. Alpha channel is copied and leaved unchanged. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive HSL data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `scale` - Natural range for S and V is [0,1] it may be more convenient and required for u16 transformation to scale it by 100 or any other number to keep S,V in range [0, scale]
+pub fn argb_to_hsl(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    scale: f32,
+) {
+    channels_to_hsv_u16::<{ ImageConfiguration::Argb as u8 }, true, { HsvTarget::Hsl as u8 }>(
+        src, src_stride, dst, dst_stride, width, height, scale,
+    );
+}
@@ -4,14 +4,16 @@
  * // Use of this source code is governed by a BSD-style
  * // license that can be found in the LICENSE file.
  */
+use crate::gamma_curves::linearization_table_u8;
 use crate::image::ImageConfiguration;
 #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
 use crate::neon::neon_image_to_jzazbz;
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::sse::sse_image_to_jzazbz;
+use crate::threading::{current_threading, ForEachRow, ThreadingPolicy};
 use crate::{Jzazbz, Jzczhz, Rgb, TransferFunction, Xyz, SRGB_TO_XYZ_D65};
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 use std::slice;
@@ -35,8 +37,28 @@ impl From<u8> for JzazbzTarget {
     }
 }
 
-#[allow(clippy::type_complexity)]
-fn channels_to_jzaz<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+pub(crate) type ChannelsToJzazbzRowHandler =
+    unsafe fn(usize, *const f32, usize, u32, *mut f32, usize, f32) -> usize;
+
+/// Selects the widest available SIMD row handler for the layout
+pub(crate) fn channels_to_jzaz_row_handler<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+) -> Option<ChannelsToJzazbzRowHandler> {
+    let mut _wide_row_handle: Option<ChannelsToJzazbzRowHandler> = None;
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        _wide_row_handle = Some(neon_image_to_jzazbz::<CHANNELS_CONFIGURATION, TARGET>);
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        _wide_row_handle = Some(sse_image_to_jzazbz::<CHANNELS_CONFIGURATION, TARGET>);
+    }
+
+    _wide_row_handle
+}
+
+pub(crate) fn channels_to_jzaz<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
     src: &[u8],
     src_stride: u32,
     dst: &mut [f32],
@@ -45,26 +67,39 @@ fn channels_to_jzaz<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
     height: u32,
     display_luminance: f32,
     transfer_function: TransferFunction,
+) {
+    channels_to_jzaz_impl::<CHANNELS_CONFIGURATION, TARGET>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        display_luminance,
+        &linearization_table_u8(transfer_function),
+        channels_to_jzaz_row_handler::<CHANNELS_CONFIGURATION, TARGET>(),
+        &current_threading(),
+    );
+}
+
+/// Converts image with precomputed linearization table and row handler
+pub(crate) fn channels_to_jzaz_impl<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    display_luminance: f32,
+    lut_table: &[f32],
+    _wide_row_handle: Option<ChannelsToJzazbzRowHandler>,
+    policy: &ThreadingPolicy,
 ) {
     let target: JzazbzTarget = TARGET.into();
     let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
 
     let channels = image_configuration.get_channels_count();
 
-    let mut _wide_row_handle: Option<
-        unsafe fn(usize, *const f32, usize, u32, *mut f32, usize, f32) -> usize,
-    > = None;
-
-    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
-    {
-        _wide_row_handle = Some(neon_image_to_jzazbz::<CHANNELS_CONFIGURATION, TARGET>);
-    }
-
-    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
-    if std::arch::is_x86_feature_detected!("sse4.1") {
-        _wide_row_handle = Some(sse_image_to_jzazbz::<CHANNELS_CONFIGURATION, TARGET>);
-    }
-
     let dst_slice_safe_align = unsafe {
         slice::from_raw_parts_mut(
             dst.as_mut_ptr() as *mut u8,
@@ -72,11 +107,6 @@ fn channels_to_jzaz<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
         )
     };
 
-    let mut lut_table = vec![0f32; 256];
-    for (i, table) in lut_table.iter_mut().enumerate() {
-        *table = transfer_function.linearize(i as f32 * (1. / 255.0));
-    }
-
     let iter;
     #[cfg(feature = "rayon")]
     {
@@ -91,7 +121,7 @@ fn channels_to_jzaz<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
             .zip(src.chunks_exact(src_stride as usize));
     }
 
-    iter.for_each(|(dst, src)| unsafe {
+    iter.for_each_row_with(policy, width as usize, |(dst, src)| unsafe {
         let mut _cx = 0usize;
 
         let dst_ptr = dst.as_mut_ptr() as *mut f32;
@@ -228,6 +258,39 @@ pub fn rgba_to_jzazbz(
     );
 }
 
+/// This function converts ARGB to Jzazbz against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive Jzazbz(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `display_luminance` - Target display luminance
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn argb_to_jzazbz(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    display_luminance: f32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_jzaz::<{ ImageConfiguration::Argb as u8 }, { JzazbzTarget::Jzazbz as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        display_luminance,
+        transfer_function,
+    );
+}
+
 /// This function converts BGRA to Jzazbz against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -261,6 +324,39 @@ pub fn bgra_to_jzazbz(
     );
 }
 
+/// This function converts ABGR to Jzazbz against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive Jzazbz(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `display_luminance` - Target display luminance
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn abgr_to_jzazbz(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    display_luminance: f32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_jzaz::<{ ImageConfiguration::Abgr as u8 }, { JzazbzTarget::Jzazbz as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        display_luminance,
+        transfer_function,
+    );
+}
+
 /// This function converts BGR to Jzazbz against D65 white point. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -360,6 +456,39 @@ pub fn rgba_to_jzczhz(
     );
 }
 
+/// This function converts ARGB to Jzczhz against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive Jzczhz(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `display_luminance` - Target display luminance
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn argb_to_jzczhz(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    display_luminance: f32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_jzaz::<{ ImageConfiguration::Argb as u8 }, { JzazbzTarget::Jzczhz as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        display_luminance,
+        transfer_function,
+    );
+}
+
 /// This function converts BGRA to Jzczhz against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -393,6 +522,39 @@ pub fn bgra_to_jzczhz(
     );
 }
 
+/// This function converts ABGR to Jzczhz against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive Jzczhz(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `display_luminance` - Target display luminance
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn abgr_to_jzczhz(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    display_luminance: f32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_jzaz::<{ ImageConfiguration::Abgr as u8 }, { JzazbzTarget::Jzczhz as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        display_luminance,
+        transfer_function,
+    );
+}
+
 /// This function converts BGR to Jzczhz against D65 white point. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -425,3 +587,109 @@ pub fn bgr_to_jzczhz(
         transfer_function,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        jzazbz_to_abgr, jzazbz_to_argb, jzazbz_to_bgra, jzazbz_to_rgba, jzczhz_to_abgr,
+        jzczhz_to_argb, jzczhz_to_bgra, jzczhz_to_rgba,
+    };
+
+    /// Moves alpha of every four channel pixel from the last position to the first
+    fn alpha_to_front<T: Copy>(pixels: &[T]) -> Vec<T> {
+        pixels
+            .chunks_exact(4)
+            .flat_map(|px| [px[3], px[0], px[1], px[2]])
+            .collect()
+    }
+
+    #[test]
+    fn alpha_first_layouts_match_alpha_last_ones() {
+        // Width isn't a multiple of any vector length, so SIMD and scalar tails both run
+        let (width, height) = (67u32, 3u32);
+        let rgba: Vec<u8> = (0..width * height * 4)
+            .map(|i| ((i * 97 + 13) % 256) as u8)
+            .collect();
+        let argb = alpha_to_front(&rgba);
+        let stride = width * 4 * std::mem::size_of::<f32>() as u32;
+        type Forward = fn(&[u8], u32, &mut [f32], u32, u32, u32, f32, TransferFunction);
+        type Inverse = fn(&[f32], u32, &mut [u8], u32, u32, u32, f32, TransferFunction);
+        let converters: [(Forward, Forward, Inverse, Inverse); 4] = [
+            (
+                rgba_to_jzazbz,
+                argb_to_jzazbz,
+                jzazbz_to_rgba,
+                jzazbz_to_argb,
+            ),
+            (
+                bgra_to_jzazbz,
+                abgr_to_jzazbz,
+                jzazbz_to_bgra,
+                jzazbz_to_abgr,
+            ),
+            (
+                rgba_to_jzczhz,
+                argb_to_jzczhz,
+                jzczhz_to_rgba,
+                jzczhz_to_argb,
+            ),
+            (
+                bgra_to_jzczhz,
+                abgr_to_jzczhz,
+                jzczhz_to_bgra,
+                jzczhz_to_abgr,
+            ),
+        ];
+
+        for (forward, forward_alpha_first, inverse, inverse_alpha_first) in converters {
+            let mut expected = vec![0f32; rgba.len()];
+            let mut actual = vec![0f32; rgba.len()];
+            forward(
+                &rgba,
+                width * 4,
+                &mut expected,
+                stride,
+                width,
+                height,
+                200.,
+                TransferFunction::Srgb,
+            );
+            forward_alpha_first(
+                &argb,
+                width * 4,
+                &mut actual,
+                stride,
+                width,
+                height,
+                200.,
+                TransferFunction::Srgb,
+            );
+            assert_eq!(actual, expected);
+
+            let mut expected_rgba = vec![0u8; rgba.len()];
+            let mut actual_argb = vec![0u8; rgba.len()];
+            inverse(
+                &expected,
+                stride,
+                &mut expected_rgba,
+                width * 4,
+                width,
+                height,
+                200.,
+                TransferFunction::Srgb,
+            );
+            inverse_alpha_first(
+                &actual,
+                stride,
+                &mut actual_argb,
+                width * 4,
+                width,
+                height,
+                200.,
+                TransferFunction::Srgb,
+            );
+            assert_eq!(actual_argb, alpha_to_front(&expected_rgba));
+        }
+    }
+}
@@ -5,15 +5,16 @@
  * // license that can be found in the LICENSE file.
  */
 use crate::image::ImageConfiguration;
+use crate::threading::ForEachRow;
 use crate::{LAlphaBeta, Rgb, TransferFunction, SRGB_TO_XYZ_D65};
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 use std::slice;
 
 #[inline(always)]
-fn channels_to_lalphabeta<const CHANNELS_CONFIGURATION: u8>(
+pub(crate) fn channels_to_lalphabeta<const CHANNELS_CONFIGURATION: u8>(
     src: &[u8],
     src_stride: u32,
     dst: &mut [f32],
@@ -53,7 +54,7 @@ fn channels_to_lalphabeta<const CHANNELS_CONFIGURATION: u8>(
             .zip(src.chunks_exact(src_stride as usize));
     }
 
-    iter.for_each(|(dst, src)| unsafe {
+    iter.for_each_row(width as usize, |(dst, src)| unsafe {
         let mut _cx = 0usize;
 
         let mut linearized_row = vec![0f32; width as usize * channels];
@@ -158,6 +159,36 @@ pub fn rgba_to_lalphabeta(
     );
 }
 
+/// This function converts ARGB to *lαβ* against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn argb_to_lalphabeta(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_lalphabeta::<{ ImageConfiguration::Argb as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+    );
+}
+
 /// This function converts BGRA to *lαβ* against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -188,6 +219,36 @@ pub fn bgra_to_lalphabeta(
     );
 }
 
+/// This function converts ABGR to *lαβ* against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn abgr_to_lalphabeta(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_lalphabeta::<{ ImageConfiguration::Abgr as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+    );
+}
+
 /// This function converts BGR to *lαβ* against D65 white point. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -217,3 +278,90 @@ pub fn bgr_to_lalphabeta(
         transfer_function,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lalphabeta_to_abgr, lalphabeta_to_argb, lalphabeta_to_bgra, lalphabeta_to_rgba};
+
+    /// Moves alpha of every four channel pixel from the last position to the first
+    fn alpha_to_front<T: Copy>(pixels: &[T]) -> Vec<T> {
+        pixels
+            .chunks_exact(4)
+            .flat_map(|px| [px[3], px[0], px[1], px[2]])
+            .collect()
+    }
+
+    #[test]
+    fn alpha_first_layouts_match_alpha_last_ones() {
+        // Width isn't a multiple of any vector length, so SIMD and scalar tails both run
+        let (width, height) = (67u32, 3u32);
+        let rgba: Vec<u8> = (0..width * height * 4)
+            .map(|i| ((i * 97 + 13) % 256) as u8)
+            .collect();
+        let argb = alpha_to_front(&rgba);
+        let stride = width * 4 * std::mem::size_of::<f32>() as u32;
+        type Forward = fn(&[u8], u32, &mut [f32], u32, u32, u32, TransferFunction);
+        type Inverse = fn(&[f32], u32, &mut [u8], u32, u32, u32, TransferFunction);
+        let converters: [(Forward, Forward, Inverse, Inverse); 2] = [
+            (
+                rgba_to_lalphabeta,
+                argb_to_lalphabeta,
+                lalphabeta_to_rgba,
+                lalphabeta_to_argb,
+            ),
+            (
+                bgra_to_lalphabeta,
+                abgr_to_lalphabeta,
+                lalphabeta_to_bgra,
+                lalphabeta_to_abgr,
+            ),
+        ];
+
+        for (forward, forward_alpha_first, inverse, inverse_alpha_first) in converters {
+            let mut expected = vec![0f32; rgba.len()];
+            let mut actual = vec![0f32; rgba.len()];
+            forward(
+                &rgba,
+                width * 4,
+                &mut expected,
+                stride,
+                width,
+                height,
+                TransferFunction::Srgb,
+            );
+            forward_alpha_first(
+                &argb,
+                width * 4,
+                &mut actual,
+                stride,
+                width,
+                height,
+                TransferFunction::Srgb,
+            );
+            assert_eq!(actual, expected);
+
+            let mut expected_rgba = vec![0u8; rgba.len()];
+            let mut actual_argb = vec![0u8; rgba.len()];
+            inverse(
+                &expected,
+                stride,
+                &mut expected_rgba,
+                width * 4,
+                width,
+                height,
+                TransferFunction::Srgb,
+            );
+            inverse_alpha_first(
+                &actual,
+                stride,
+                &mut actual_argb,
+                width * 4,
+                width,
+                height,
+                TransferFunction::Srgb,
+            );
+            assert_eq!(actual_argb, alpha_to_front(&expected_rgba));
+        }
+    }
+}
@@ -4,17 +4,17 @@
  * // Use of this source code is governed by a BSD-style
  * // license that can be found in the LICENSE file.
  */
-use crate::gamma_curves::TransferFunction;
+use crate::gamma_curves::{linearization_table_u8, TransferFunction};
 use crate::image::ImageConfiguration;
+use crate::threading::{current_threading, ForEachRow, ThreadingPolicy};
 use crate::Rgb;
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 use std::slice;
 
-#[allow(clippy::type_complexity)]
-fn channels_to_linear<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
+pub(crate) fn channels_to_linear<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
     src: &[u8],
     src_stride: u32,
     dst: &mut [f32],
@@ -22,6 +22,29 @@ fn channels_to_linear<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
     width: u32,
     height: u32,
     transfer_function: TransferFunction,
+) {
+    channels_to_linear_impl::<CHANNELS_CONFIGURATION, USE_ALPHA>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        &linearization_table_u8(transfer_function),
+        &current_threading(),
+    );
+}
+
+/// Converts image with precomputed linearization table
+pub(crate) fn channels_to_linear_impl<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    lut_table: &[f32],
+    policy: &ThreadingPolicy,
 ) {
     let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
     if USE_ALPHA && !image_configuration.has_alpha() {
@@ -30,11 +53,6 @@ fn channels_to_linear<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
 
     let channels = image_configuration.get_channels_count();
 
-    let mut lut_table = vec![0f32; 256];
-    for (i, lut) in lut_table.iter_mut().enumerate() {
-        *lut = transfer_function.linearize(i as f32 * (1. / 255.0));
-    }
-
     let dst_slice_safe_align = unsafe {
         slice::from_raw_parts_mut(
             dst.as_mut_ptr() as *mut u8,
@@ -58,7 +76,7 @@ fn channels_to_linear<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
             .zip(src.chunks_exact(src_stride as usize));
     }
 
-    iter.for_each(|(dst_row, src_row)| unsafe {
+    iter.for_each_row_with(policy, width as usize, |(dst_row, src_row)| unsafe {
         let mut _cx = 0usize;
 
         let src_ptr = src_row.as_ptr();
@@ -163,6 +181,38 @@ pub fn rgba_to_linear(
     );
 }
 
+/// This function converts ARGB to liner color space
+///
+/// This function converts ARGB to Linear, Alpha channel is normalized. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive Linear data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn argb_to_linear(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_linear::<{ ImageConfiguration::Argb as u8 }, true>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+    );
+}
+
 /// This function converts BGRA to Linear.
 ///
 /// This function converts BGRA to Linear, alpha channel is normalized. This is much more effective than naive direct transformation
@@ -195,6 +245,38 @@ pub fn bgra_to_linear(
     );
 }
 
+/// This function converts ABGR to Linear.
+///
+/// This function converts ABGR to Linear, alpha channel is normalized. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive linear data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn abgr_to_linear(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_linear::<{ ImageConfiguration::Abgr as u8 }, true>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+    );
+}
+
 /// This function converts BGR to linear
 ///
 /// This function converts BGR to linear color space. This is much more effective than naive direct transformation
@@ -226,3 +308,154 @@ pub fn bgr_to_linear(
         transfer_function,
     );
 }
+
+/// This function converts Gray to linear
+///
+/// This function converts Gray holding transfer encoded luminance to linear luminance. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains Gray data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive Linear data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn gray_to_linear(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_linear::<{ ImageConfiguration::Gray as u8 }, false>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+    );
+}
+
+/// This function converts Gray with alpha to linear
+///
+/// This function converts Gray with alpha to Linear, Alpha channel is normalized. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains Gray with alpha data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive Linear data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn gray_alpha_to_linear(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_linear::<{ ImageConfiguration::GrayAlpha as u8 }, true>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{linear_to_abgr, linear_to_argb, linear_to_bgra, linear_to_rgba};
+
+    /// Moves alpha of every four channel pixel from the last position to the first
+    fn alpha_to_front<T: Copy>(pixels: &[T]) -> Vec<T> {
+        pixels
+            .chunks_exact(4)
+            .flat_map(|px| [px[3], px[0], px[1], px[2]])
+            .collect()
+    }
+
+    #[test]
+    fn alpha_first_layouts_match_alpha_last_ones() {
+        // Width isn't a multiple of any vector length, so SIMD and scalar tails both run
+        let (width, height) = (67u32, 3u32);
+        let rgba: Vec<u8> = (0..width * height * 4)
+            .map(|i| ((i * 97 + 13) % 256) as u8)
+            .collect();
+        let argb = alpha_to_front(&rgba);
+        let stride = width * 4 * std::mem::size_of::<f32>() as u32;
+        type Forward = fn(&[u8], u32, &mut [f32], u32, u32, u32, TransferFunction);
+        type Inverse = fn(&[f32], u32, &mut [u8], u32, u32, u32, TransferFunction);
+        let converters: [(Forward, Forward, Inverse, Inverse); 2] = [
+            (
+                rgba_to_linear,
+                argb_to_linear,
+                linear_to_rgba,
+                linear_to_argb,
+            ),
+            (
+                bgra_to_linear,
+                abgr_to_linear,
+                linear_to_bgra,
+                linear_to_abgr,
+            ),
+        ];
+
+        for (forward, forward_alpha_first, inverse, inverse_alpha_first) in converters {
+            let mut expected = vec![0f32; rgba.len()];
+            let mut actual = vec![0f32; rgba.len()];
+            forward(
+                &rgba,
+                width * 4,
+                &mut expected,
+                stride,
+                width,
+                height,
+                TransferFunction::Srgb,
+            );
+            forward_alpha_first(
+                &argb,
+                width * 4,
+                &mut actual,
+                stride,
+                width,
+                height,
+                TransferFunction::Srgb,
+            );
+            assert_eq!(actual, alpha_to_front(&expected));
+
+            let mut expected_rgba = vec![0u8; rgba.len()];
+            let mut actual_argb = vec![0u8; rgba.len()];
+            inverse(
+                &expected,
+                stride,
+                &mut expected_rgba,
+                width * 4,
+                width,
+                height,
+                TransferFunction::Srgb,
+            );
+            inverse_alpha_first(
+                &actual,
+                stride,
+                &mut actual_argb,
+                width * 4,
+                width,
+                height,
+                TransferFunction::Srgb,
+            );
+            assert_eq!(actual_argb, alpha_to_front(&expected_rgba));
+        }
+    }
+}
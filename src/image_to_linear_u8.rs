@@ -6,14 +6,15 @@
  */
 use crate::gamma_curves::TransferFunction;
 use crate::image::ImageConfiguration;
+use crate::threading::ForEachRow;
 use crate::Rgb;
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 
 #[allow(clippy::type_complexity)]
-fn channels_to_linear<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
+pub(crate) fn channels_to_linear<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
     l_src: &[u8],
     src_stride: u32,
     l_dst: &mut [u8],
@@ -48,7 +49,7 @@ fn channels_to_linear<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
             .zip(l_src.chunks_exact(src_stride as usize));
     }
 
-    iter.for_each(|(dst_row, src_row)| unsafe {
+    iter.for_each_row(width as usize, |(dst_row, src_row)| unsafe {
         let mut _cx = 0usize;
 
         for x in _cx..width as usize {
@@ -134,6 +135,36 @@ pub fn rgba_to_linear_u8(
     );
 }
 
+/// This function converts ARGB to Linear, Alpha channel is normalized. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive Linear data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn argb_to_linear_u8(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_linear::<{ ImageConfiguration::Argb as u8 }, true>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+    );
+}
+
 /// This function converts BGRA to Linear, Alpha channel is normalized. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -164,6 +195,36 @@ pub fn bgra_to_linear_u8(
     );
 }
 
+/// This function converts ABGR to Linear, Alpha channel is normalized. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive linear data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn abgr_to_linear_u8(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_linear::<{ ImageConfiguration::Abgr as u8 }, true>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+    );
+}
+
 /// This function converts BGR to Linear. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -193,3 +254,90 @@ pub fn bgr_to_linear_u8(
         transfer_function,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{linear_u8_to_abgr, linear_u8_to_argb, linear_u8_to_bgra, linear_u8_to_rgba};
+
+    /// Moves alpha of every four channel pixel from the last position to the first
+    fn alpha_to_front<T: Copy>(pixels: &[T]) -> Vec<T> {
+        pixels
+            .chunks_exact(4)
+            .flat_map(|px| [px[3], px[0], px[1], px[2]])
+            .collect()
+    }
+
+    #[test]
+    fn alpha_first_layouts_match_alpha_last_ones() {
+        // Width isn't a multiple of any vector length, so SIMD and scalar tails both run
+        let (width, height) = (67u32, 3u32);
+        let rgba: Vec<u8> = (0..width * height * 4)
+            .map(|i| ((i * 97 + 13) % 256) as u8)
+            .collect();
+        let argb = alpha_to_front(&rgba);
+        let stride = width * 4 * std::mem::size_of::<u8>() as u32;
+        type Forward = fn(&[u8], u32, &mut [u8], u32, u32, u32, TransferFunction);
+        type Inverse = fn(&[u8], u32, &mut [u8], u32, u32, u32, TransferFunction);
+        let converters: [(Forward, Forward, Inverse, Inverse); 2] = [
+            (
+                rgba_to_linear_u8,
+                argb_to_linear_u8,
+                linear_u8_to_rgba,
+                linear_u8_to_argb,
+            ),
+            (
+                bgra_to_linear_u8,
+                abgr_to_linear_u8,
+                linear_u8_to_bgra,
+                linear_u8_to_abgr,
+            ),
+        ];
+
+        for (forward, forward_alpha_first, inverse, inverse_alpha_first) in converters {
+            let mut expected = vec![0u8; rgba.len()];
+            let mut actual = vec![0u8; rgba.len()];
+            forward(
+                &rgba,
+                width * 4,
+                &mut expected,
+                stride,
+                width,
+                height,
+                TransferFunction::Srgb,
+            );
+            forward_alpha_first(
+                &argb,
+                width * 4,
+                &mut actual,
+                stride,
+                width,
+                height,
+                TransferFunction::Srgb,
+            );
+            assert_eq!(actual, alpha_to_front(&expected));
+
+            let mut expected_rgba = vec![0u8; rgba.len()];
+            let mut actual_argb = vec![0u8; rgba.len()];
+            inverse(
+                &expected,
+                stride,
+                &mut expected_rgba,
+                width * 4,
+                width,
+                height,
+                TransferFunction::Srgb,
+            );
+            inverse_alpha_first(
+                &actual,
+                stride,
+                &mut actual_argb,
+                width * 4,
+                width,
+                height,
+                TransferFunction::Srgb,
+            );
+            assert_eq!(actual_argb, alpha_to_front(&expected_rgba));
+        }
+    }
+}
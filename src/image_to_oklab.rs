@@ -6,19 +6,20 @@
  */
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::avx::avx_image_to_oklab;
+use crate::gamma_curves::linearization_table_u8;
 use crate::image::ImageConfiguration;
 #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
 use crate::neon::neon_image_to_oklab;
 use crate::oklch::Oklch;
+use crate::premultiply::linearize_premultiplied_row_u8;
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::sse::sse_image_to_oklab;
-use crate::{
-    bgr_to_linear, bgra_to_linear, rgb_to_linear, rgba_to_linear, Oklab, Rgb, TransferFunction,
-};
+use crate::threading::{current_threading, ForEachRow, ThreadingPolicy};
+use crate::{Oklab, Rgb, TransferFunction};
 #[cfg(feature = "rayon")]
-use rayon::iter::ParallelIterator;
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
-use rayon::prelude::ParallelSliceMut;
+use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 use std::slice;
 
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
@@ -39,8 +40,32 @@ impl From<u8> for OklabTarget {
     }
 }
 
-#[allow(clippy::type_complexity)]
-fn channels_to_oklab<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+pub(crate) type ChannelsToOklabRowHandler = unsafe fn(usize, u32, *mut f32, usize) -> usize;
+
+/// Selects the widest available SIMD row handler for the layout
+pub(crate) fn channels_to_oklab_row_handler<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+) -> Option<ChannelsToOklabRowHandler> {
+    let mut _wide_row_handle: Option<ChannelsToOklabRowHandler> = None;
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        _wide_row_handle = Some(neon_image_to_oklab::<CHANNELS_CONFIGURATION, TARGET>);
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        _wide_row_handle = Some(sse_image_to_oklab::<CHANNELS_CONFIGURATION, TARGET>);
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("avx2") {
+        _wide_row_handle = Some(avx_image_to_oklab::<CHANNELS_CONFIGURATION, TARGET>);
+    }
+
+    _wide_row_handle
+}
+
+pub(crate) fn channels_to_oklab<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
     src: &[u8],
     src_stride: u32,
     dst: &mut [f32],
@@ -48,45 +73,48 @@ fn channels_to_oklab<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
     width: u32,
     height: u32,
     transfer_function: TransferFunction,
+    premultiplied: bool,
 ) {
-    let target: OklabTarget = TARGET.into();
     let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
-
-    let channels = image_configuration.get_channels_count();
-
-    let callee = match image_configuration {
-        ImageConfiguration::Rgb => rgb_to_linear,
-        ImageConfiguration::Rgba => rgba_to_linear,
-        ImageConfiguration::Bgra => bgra_to_linear,
-        ImageConfiguration::Bgr => bgr_to_linear,
+    let unpremultiply = if premultiplied && image_configuration.has_alpha() {
+        Some(transfer_function)
+    } else {
+        None
     };
 
-    callee(
+    channels_to_oklab_impl::<CHANNELS_CONFIGURATION, TARGET>(
         src,
         src_stride,
         dst,
         dst_stride,
         width,
         height,
-        transfer_function,
+        &linearization_table_u8(transfer_function),
+        unpremultiply,
+        channels_to_oklab_row_handler::<CHANNELS_CONFIGURATION, TARGET>(),
+        &current_threading(),
     );
+}
 
-    let mut _wide_row_handle: Option<unsafe fn(usize, u32, *mut f32, usize) -> usize> = None;
-
-    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
-    {
-        _wide_row_handle = Some(neon_image_to_oklab::<CHANNELS_CONFIGURATION, TARGET>);
-    }
-
-    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
-    if std::arch::is_x86_feature_detected!("sse4.1") {
-        _wide_row_handle = Some(sse_image_to_oklab::<CHANNELS_CONFIGURATION, TARGET>);
-    }
+/// Converts image with precomputed linearization table and row handler.
+/// When `unpremultiply` is set source alpha is premultiplied, colours are divided by alpha
+/// in each linear row and linearized with that transfer function
+pub(crate) fn channels_to_oklab_impl<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    lut_table: &[f32],
+    unpremultiply: Option<TransferFunction>,
+    _wide_row_handle: Option<ChannelsToOklabRowHandler>,
+    policy: &ThreadingPolicy,
+) {
+    let target: OklabTarget = TARGET.into();
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
 
-    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
-    if std::arch::is_x86_feature_detected!("avx2") {
-        _wide_row_handle = Some(avx_image_to_oklab::<CHANNELS_CONFIGURATION, TARGET>);
-    }
+    let channels = image_configuration.get_channels_count();
 
     let dst_slice_safe_align = unsafe {
         slice::from_raw_parts_mut(
@@ -98,18 +126,53 @@ fn channels_to_oklab<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
     let iter;
     #[cfg(feature = "rayon")]
     {
-        iter = dst_slice_safe_align.par_chunks_exact_mut(dst_stride as usize);
+        iter = dst_slice_safe_align
+            .par_chunks_exact_mut(dst_stride as usize)
+            .zip(src.par_chunks_exact(src_stride as usize));
     }
 
     #[cfg(not(feature = "rayon"))]
     {
-        iter = dst_slice_safe_align.chunks_exact_mut(dst_stride as usize);
+        iter = dst_slice_safe_align
+            .chunks_exact_mut(dst_stride as usize)
+            .zip(src.chunks_exact(src_stride as usize));
     }
 
-    iter.for_each(|dst| unsafe {
+    iter.for_each_row_with(policy, width as usize, |(dst, src)| unsafe {
         let mut _cx = 0usize;
 
-        let dst_ptr = dst.as_mut_ptr() as *mut f32;
+        let dst_row =
+            slice::from_raw_parts_mut(dst.as_mut_ptr() as *mut f32, width as usize * channels);
+
+        // Row is linearized in place first, Oklab is computed from it afterwards
+        match unpremultiply {
+            Some(transfer_function) => linearize_premultiplied_row_u8::<CHANNELS_CONFIGURATION>(
+                src,
+                dst_row,
+                lut_table,
+                transfer_function,
+            ),
+            None => {
+                for (dst_chunk, src_chunk) in dst_row
+                    .chunks_exact_mut(channels)
+                    .zip(src.chunks_exact(channels))
+                {
+                    for offset in [
+                        image_configuration.get_r_channel_offset(),
+                        image_configuration.get_g_channel_offset(),
+                        image_configuration.get_b_channel_offset(),
+                    ] {
+                        dst_chunk[offset] = *lut_table.get_unchecked(src_chunk[offset] as usize);
+                    }
+                    if image_configuration.has_alpha() {
+                        let a_offset = image_configuration.get_a_channel_offset();
+                        dst_chunk[a_offset] = src_chunk[a_offset] as f32 * (1. / 255.);
+                    }
+                }
+            }
+        }
+
+        let dst_ptr = dst_row.as_mut_ptr();
 
         if let Some(dispatcher) = _wide_row_handle {
             _cx = dispatcher(_cx, width, dst_ptr, 0)
@@ -129,6 +192,11 @@ fn channels_to_oklab<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
                 .add(image_configuration.get_b_channel_offset())
                 .read_unaligned();
 
+            // Alpha may be first in the pixel, so it's read before in place store
+            let a = src
+                .add(image_configuration.get_a_channel_offset())
+                .read_unaligned();
+
             let rgb = Rgb::<f32>::new(r, g, b);
             let dst_store = dst_ptr.add(px);
 
@@ -148,9 +216,6 @@ fn channels_to_oklab<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
             }
 
             if image_configuration.has_alpha() {
-                let a = src
-                    .add(image_configuration.get_a_channel_offset())
-                    .read_unaligned();
                 dst_store.add(3).write_unaligned(a);
             }
         }
@@ -184,6 +249,7 @@ pub fn rgb_to_oklab(
         width,
         height,
         transfer_function,
+        false,
     );
 }
 
@@ -214,6 +280,100 @@ pub fn rgba_to_oklab(
         width,
         height,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ARGB to Oklab against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn argb_to_oklab(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_oklab::<{ ImageConfiguration::Argb as u8 }, { OklabTarget::Oklab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        false,
+    );
+}
+
+/// This function converts RGBA to Oklab against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation. Colour channels of the source are premultiplied by alpha and are unpremultiplied before conversion
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn rgba_to_oklab_premultiplied(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_oklab::<{ ImageConfiguration::Rgba as u8 }, { OklabTarget::Oklab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        true,
+    );
+}
+
+/// This function converts ARGB to Oklab against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation. Colour channels of the source are premultiplied by alpha and are unpremultiplied before conversion
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn argb_to_oklab_premultiplied(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_oklab::<{ ImageConfiguration::Argb as u8 }, { OklabTarget::Oklab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        true,
     );
 }
 
@@ -244,6 +404,100 @@ pub fn bgra_to_oklab(
         width,
         height,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ABGR to Oklab against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn abgr_to_oklab(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_oklab::<{ ImageConfiguration::Abgr as u8 }, { OklabTarget::Oklab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        false,
+    );
+}
+
+/// This function converts BGRA to Oklab against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation. Colour channels of the source are premultiplied by alpha and are unpremultiplied before conversion
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn bgra_to_oklab_premultiplied(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_oklab::<{ ImageConfiguration::Bgra as u8 }, { OklabTarget::Oklab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        true,
+    );
+}
+
+/// This function converts ABGR to Oklab against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation. Colour channels of the source are premultiplied by alpha and are unpremultiplied before conversion
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn abgr_to_oklab_premultiplied(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_oklab::<{ ImageConfiguration::Abgr as u8 }, { OklabTarget::Oklab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        true,
     );
 }
 
@@ -274,6 +528,7 @@ pub fn bgr_to_oklab(
         width,
         height,
         transfer_function,
+        false,
     );
 }
 
@@ -304,6 +559,7 @@ pub fn rgb_to_oklch(
         width,
         height,
         transfer_function,
+        false,
     );
 }
 
@@ -334,6 +590,100 @@ pub fn rgba_to_oklch(
         width,
         height,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ARGB to Oklch against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LCH(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn argb_to_oklch(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_oklab::<{ ImageConfiguration::Argb as u8 }, { OklabTarget::Oklch as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        false,
+    );
+}
+
+/// This function converts RGBA to Oklch against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation. Colour channels of the source are premultiplied by alpha and are unpremultiplied before conversion
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LCH(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn rgba_to_oklch_premultiplied(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_oklab::<{ ImageConfiguration::Rgba as u8 }, { OklabTarget::Oklch as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        true,
+    );
+}
+
+/// This function converts ARGB to Oklch against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation. Colour channels of the source are premultiplied by alpha and are unpremultiplied before conversion
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LCH(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn argb_to_oklch_premultiplied(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_oklab::<{ ImageConfiguration::Argb as u8 }, { OklabTarget::Oklch as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        true,
     );
 }
 
@@ -364,6 +714,100 @@ pub fn bgra_to_oklch(
         width,
         height,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ABGR to Oklch against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LCH(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn abgr_to_oklch(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_oklab::<{ ImageConfiguration::Abgr as u8 }, { OklabTarget::Oklch as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        false,
+    );
+}
+
+/// This function converts BGRA to Oklch against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation. Colour channels of the source are premultiplied by alpha and are unpremultiplied before conversion
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LCH(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn bgra_to_oklch_premultiplied(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_oklab::<{ ImageConfiguration::Bgra as u8 }, { OklabTarget::Oklch as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        true,
+    );
+}
+
+/// This function converts ABGR to Oklch against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation. Colour channels of the source are premultiplied by alpha and are unpremultiplied before conversion
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LCH(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `transfer_function` - transfer function to linear colorspace
+pub fn abgr_to_oklch_premultiplied(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    channels_to_oklab::<{ ImageConfiguration::Abgr as u8 }, { OklabTarget::Oklch as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        true,
     );
 }
 
@@ -394,5 +838,101 @@ pub fn bgr_to_oklch(
         width,
         height,
         transfer_function,
+        false,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        oklab_to_abgr, oklab_to_argb, oklab_to_argb_premultiplied, oklab_to_bgra, oklab_to_rgba,
+        oklab_to_rgba_premultiplied, oklch_to_abgr, oklch_to_abgr_premultiplied, oklch_to_argb,
+        oklch_to_bgra, oklch_to_bgra_premultiplied, oklch_to_rgba,
+    };
+
+    /// Moves alpha of every four channel pixel from the last position to the first
+    fn alpha_to_front<T: Copy>(pixels: &[T]) -> Vec<T> {
+        pixels
+            .chunks_exact(4)
+            .flat_map(|px| [px[3], px[0], px[1], px[2]])
+            .collect()
+    }
+
+    #[test]
+    fn alpha_first_layouts_match_alpha_last_ones() {
+        // Width isn't a multiple of any vector length, so SIMD and scalar tails both run
+        let (width, height) = (67u32, 3u32);
+        let rgba: Vec<u8> = (0..width * height * 4)
+            .map(|i| ((i * 97 + 13) % 256) as u8)
+            .collect();
+        let argb = alpha_to_front(&rgba);
+        let stride = width * 4 * std::mem::size_of::<f32>() as u32;
+        type Forward = fn(&[u8], u32, &mut [f32], u32, u32, u32, TransferFunction);
+        type Inverse = fn(&[f32], u32, &mut [u8], u32, u32, u32, TransferFunction);
+        let converters: [(Forward, Forward, Inverse, Inverse); 6] = [
+            (rgba_to_oklab, argb_to_oklab, oklab_to_rgba, oklab_to_argb),
+            (bgra_to_oklab, abgr_to_oklab, oklab_to_bgra, oklab_to_abgr),
+            (rgba_to_oklch, argb_to_oklch, oklch_to_rgba, oklch_to_argb),
+            (bgra_to_oklch, abgr_to_oklch, oklch_to_bgra, oklch_to_abgr),
+            (
+                rgba_to_oklab_premultiplied,
+                argb_to_oklab_premultiplied,
+                oklab_to_rgba_premultiplied,
+                oklab_to_argb_premultiplied,
+            ),
+            (
+                bgra_to_oklch_premultiplied,
+                abgr_to_oklch_premultiplied,
+                oklch_to_bgra_premultiplied,
+                oklch_to_abgr_premultiplied,
+            ),
+        ];
+
+        for (forward, forward_alpha_first, inverse, inverse_alpha_first) in converters {
+            let mut expected = vec![0f32; rgba.len()];
+            let mut actual = vec![0f32; rgba.len()];
+            forward(
+                &rgba,
+                width * 4,
+                &mut expected,
+                stride,
+                width,
+                height,
+                TransferFunction::Srgb,
+            );
+            forward_alpha_first(
+                &argb,
+                width * 4,
+                &mut actual,
+                stride,
+                width,
+                height,
+                TransferFunction::Srgb,
+            );
+            assert_eq!(actual, expected);
+
+            let mut expected_rgba = vec![0u8; rgba.len()];
+            let mut actual_argb = vec![0u8; rgba.len()];
+            inverse(
+                &expected,
+                stride,
+                &mut expected_rgba,
+                width * 4,
+                width,
+                height,
+                TransferFunction::Srgb,
+            );
+            inverse_alpha_first(
+                &actual,
+                stride,
+                &mut actual_argb,
+                width * 4,
+                width,
+                height,
+                TransferFunction::Srgb,
+            );
+            assert_eq!(actual_argb, alpha_to_front(&expected_rgba));
+        }
+    }
+}
@@ -7,6 +7,7 @@
 
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::avx::avx_image_to_sigmoidal_row;
+use crate::threading::ForEachRow;
 
 use crate::image::ImageConfiguration;
 #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
@@ -15,13 +16,13 @@ use crate::neon::neon_image_to_sigmoidal;
 use crate::sse::sse_image_to_sigmoidal_row;
 use crate::Rgb;
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 use std::slice;
 
 #[allow(clippy::type_complexity)]
-fn image_to_sigmoidal<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
+pub(crate) fn image_to_sigmoidal<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
     src: &[u8],
     src_stride: u32,
     dst: &mut [f32],
@@ -77,7 +78,7 @@ fn image_to_sigmoidal<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
             .zip(src.chunks_exact(src_stride as usize));
     }
 
-    iter.for_each(|(dst, src)| unsafe {
+    iter.for_each_row(width as usize, |(dst, src)| unsafe {
         let mut _cx = 0usize;
 
         let src_ptr = src.as_ptr();
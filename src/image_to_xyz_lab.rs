@@ -6,54 +6,43 @@
  */
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::avx::avx2_image_to_xyz_lab;
-use crate::gamma_curves::TransferFunction;
+use crate::gamma_curves::{linearization_table_u8, TransferFunction};
 use crate::image::ImageConfiguration;
 #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
 use crate::neon::neon_channels_to_xyz_or_lab;
+use crate::premultiply::linearize_premultiplied_row_u8;
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::sse::sse_channels_to_xyz_or_lab;
+#[cfg(feature = "rayon")]
+use crate::threading::ForEachRow;
+use crate::threading::{current_threading, ThreadingPolicy};
 use crate::xyz_target::XyzTarget;
 use crate::{LCh, Lab, Luv, Rgb, Xyz, SRGB_TO_XYZ_D65};
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 use std::slice;
 
-#[allow(clippy::type_complexity)]
-fn channels_to_xyz<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool, const TARGET: u8>(
-    src: &[u8],
-    src_stride: u32,
-    dst: &mut [f32],
-    dst_stride: u32,
-    a_channel: &mut [f32],
-    a_stride: u32,
-    width: u32,
-    height: u32,
-    matrix: &[[f32; 3]; 3],
-    transfer_function: TransferFunction,
-) {
-    let target: XyzTarget = TARGET.into();
-    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
-    if USE_ALPHA && !image_configuration.has_alpha() {
-        panic!("Alpha may be set only on images with alpha");
-    }
-
-    let channels = image_configuration.get_channels_count();
+pub(crate) type ChannelsToXyzRowHandler = unsafe fn(
+    usize,
+    *const f32,
+    usize,
+    u32,
+    *mut f32,
+    usize,
+    *mut f32,
+    usize,
+    &[[f32; 3]; 3],
+) -> usize;
 
-    let mut _wide_row_handler: Option<
-        unsafe fn(
-            usize,
-            *const f32,
-            usize,
-            u32,
-            *mut f32,
-            usize,
-            *mut f32,
-            usize,
-            &[[f32; 3]; 3],
-        ) -> usize,
-    > = None;
+/// Selects the widest available SIMD row handler for the layout
+pub(crate) fn channels_to_xyz_row_handler<
+    const CHANNELS_CONFIGURATION: u8,
+    const USE_ALPHA: bool,
+    const TARGET: u8,
+>() -> Option<ChannelsToXyzRowHandler> {
+    let mut _wide_row_handler: Option<ChannelsToXyzRowHandler> = None;
 
     #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
     {
@@ -73,11 +62,80 @@ fn channels_to_xyz<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool, cons
             Some(avx2_image_to_xyz_lab::<CHANNELS_CONFIGURATION, USE_ALPHA, TARGET>);
     }
 
-    let mut lut_table = vec![0f32; 256];
-    for (i, lut) in lut_table.iter_mut().enumerate() {
-        *lut = transfer_function.linearize(i as f32 * (1. / 255.0));
+    _wide_row_handler
+}
+
+pub(crate) fn channels_to_xyz<
+    const CHANNELS_CONFIGURATION: u8,
+    const USE_ALPHA: bool,
+    const TARGET: u8,
+>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    a_channel: &mut [f32],
+    a_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+    premultiplied: bool,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let unpremultiply = if premultiplied && USE_ALPHA && image_configuration.has_alpha() {
+        Some(transfer_function)
+    } else {
+        None
+    };
+
+    channels_to_xyz_impl::<CHANNELS_CONFIGURATION, USE_ALPHA, TARGET>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        a_channel,
+        a_stride,
+        width,
+        height,
+        matrix,
+        &linearization_table_u8(transfer_function),
+        unpremultiply,
+        channels_to_xyz_row_handler::<CHANNELS_CONFIGURATION, USE_ALPHA, TARGET>(),
+        &current_threading(),
+    );
+}
+
+/// Converts image with precomputed linearization table and row handler.
+/// When `unpremultiply` is set source alpha is premultiplied, colours are divided by alpha
+/// in transient row and linearized with that transfer function
+pub(crate) fn channels_to_xyz_impl<
+    const CHANNELS_CONFIGURATION: u8,
+    const USE_ALPHA: bool,
+    const TARGET: u8,
+>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    a_channel: &mut [f32],
+    a_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    lut_table: &[f32],
+    unpremultiply: Option<TransferFunction>,
+    _wide_row_handler: Option<ChannelsToXyzRowHandler>,
+    policy: &ThreadingPolicy,
+) {
+    let target: XyzTarget = TARGET.into();
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    if USE_ALPHA && !image_configuration.has_alpha() {
+        panic!("Alpha may be set only on images with alpha");
     }
 
+    let channels = image_configuration.get_channels_count();
+
     let dst_slice_safe_align = unsafe {
         slice::from_raw_parts_mut(
             dst.as_mut_ptr() as *mut u8,
@@ -99,30 +157,39 @@ fn channels_to_xyz<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool, cons
                 .par_chunks_exact_mut(dst_stride as usize)
                 .zip(src.par_chunks_exact(src_stride as usize))
                 .zip(a_slice_safe_align.par_chunks_exact_mut(a_stride as usize))
-                .for_each(|((dst, src), a_channel)| unsafe {
+                .for_each_row_with(policy, width as usize, |((dst, src), a_channel)| unsafe {
                     let mut _cx = 0usize;
 
                     let mut transient_row = vec![0f32; width as usize * channels];
 
-                    for (dst_chunk, src_chunks) in transient_row
-                        .chunks_exact_mut(channels)
-                        .zip(src.chunks_exact(channels))
-                    {
-                        dst_chunk[image_configuration.get_r_channel_offset()] = *lut_table
-                            .get_unchecked(
-                                src_chunks[image_configuration.get_r_channel_offset()] as usize,
-                            );
-                        dst_chunk[image_configuration.get_g_channel_offset()] = *lut_table
-                            .get_unchecked(
-                                src_chunks[image_configuration.get_g_channel_offset()] as usize,
-                            );
-                        dst_chunk[image_configuration.get_b_channel_offset()] = *lut_table
-                            .get_unchecked(
-                                src_chunks[image_configuration.get_b_channel_offset()] as usize,
-                            );
-                        dst_chunk[image_configuration.get_a_channel_offset()] =
-                            src_chunks[image_configuration.get_a_channel_offset()] as f32
-                                * (1. / 255.0);
+                    if let Some(transfer_function) = unpremultiply {
+                        linearize_premultiplied_row_u8::<CHANNELS_CONFIGURATION>(
+                            src,
+                            &mut transient_row,
+                            lut_table,
+                            transfer_function,
+                        );
+                    } else {
+                        for (dst_chunk, src_chunks) in transient_row
+                            .chunks_exact_mut(channels)
+                            .zip(src.chunks_exact(channels))
+                        {
+                            dst_chunk[image_configuration.get_r_channel_offset()] = *lut_table
+                                .get_unchecked(
+                                    src_chunks[image_configuration.get_r_channel_offset()] as usize,
+                                );
+                            dst_chunk[image_configuration.get_g_channel_offset()] = *lut_table
+                                .get_unchecked(
+                                    src_chunks[image_configuration.get_g_channel_offset()] as usize,
+                                );
+                            dst_chunk[image_configuration.get_b_channel_offset()] = *lut_table
+                                .get_unchecked(
+                                    src_chunks[image_configuration.get_b_channel_offset()] as usize,
+                                );
+                            dst_chunk[image_configuration.get_a_channel_offset()] =
+                                src_chunks[image_configuration.get_a_channel_offset()] as f32
+                                    * (1. / 255.0);
+                        }
                     }
 
                     if let Some(dispatcher) = _wide_row_handler {
@@ -191,7 +258,7 @@ fn channels_to_xyz<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool, cons
             dst_slice_safe_align
                 .par_chunks_exact_mut(dst_stride as usize)
                 .zip(src.par_chunks_exact(src_stride as usize))
-                .for_each(|(dst, src)| unsafe {
+                .for_each_row_with(policy, width as usize, |(dst, src)| unsafe {
                     let mut _cx = 0usize;
 
                     let mut transient_row = vec![0f32; width as usize * channels];
@@ -275,6 +342,8 @@ fn channels_to_xyz<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool, cons
 
     #[cfg(not(feature = "rayon"))]
     {
+        // Without rayon rows always run on the calling thread
+        let _ = policy;
         if USE_ALPHA {
             let a_slice_safe_align = unsafe {
                 slice::from_raw_parts_mut(
@@ -293,25 +362,34 @@ fn channels_to_xyz<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool, cons
 
                     let mut transient_row = vec![0f32; width as usize * channels];
 
-                    for (dst_chunk, src_chunks) in transient_row
-                        .chunks_exact_mut(channels)
-                        .zip(src.chunks_exact(channels))
-                    {
-                        dst_chunk[image_configuration.get_r_channel_offset()] = *lut_table
-                            .get_unchecked(
-                                src_chunks[image_configuration.get_r_channel_offset()] as usize,
-                            );
-                        dst_chunk[image_configuration.get_g_channel_offset()] = *lut_table
-                            .get_unchecked(
-                                src_chunks[image_configuration.get_g_channel_offset()] as usize,
-                            );
-                        dst_chunk[image_configuration.get_b_channel_offset()] = *lut_table
-                            .get_unchecked(
-                                src_chunks[image_configuration.get_b_channel_offset()] as usize,
-                            );
-                        dst_chunk[image_configuration.get_a_channel_offset()] =
-                            src_chunks[image_configuration.get_a_channel_offset()] as f32
-                                * (1. / 255.0);
+                    if let Some(transfer_function) = unpremultiply {
+                        linearize_premultiplied_row_u8::<CHANNELS_CONFIGURATION>(
+                            src,
+                            &mut transient_row,
+                            lut_table,
+                            transfer_function,
+                        );
+                    } else {
+                        for (dst_chunk, src_chunks) in transient_row
+                            .chunks_exact_mut(channels)
+                            .zip(src.chunks_exact(channels))
+                        {
+                            dst_chunk[image_configuration.get_r_channel_offset()] = *lut_table
+                                .get_unchecked(
+                                    src_chunks[image_configuration.get_r_channel_offset()] as usize,
+                                );
+                            dst_chunk[image_configuration.get_g_channel_offset()] = *lut_table
+                                .get_unchecked(
+                                    src_chunks[image_configuration.get_g_channel_offset()] as usize,
+                                );
+                            dst_chunk[image_configuration.get_b_channel_offset()] = *lut_table
+                                .get_unchecked(
+                                    src_chunks[image_configuration.get_b_channel_offset()] as usize,
+                                );
+                            dst_chunk[image_configuration.get_a_channel_offset()] =
+                                src_chunks[image_configuration.get_a_channel_offset()] as f32
+                                    * (1. / 255.0);
+                        }
                     }
 
                     if let Some(dispatcher) = _wide_row_handler {
@@ -499,6 +577,7 @@ pub fn rgb_to_xyz(
         height,
         matrix,
         transfer_function,
+        false,
     );
 }
 
@@ -535,6 +614,7 @@ pub fn bgr_to_xyz(
         height,
         matrix,
         transfer_function,
+        false,
     );
 }
 
@@ -569,6 +649,7 @@ pub fn srgb_to_xyz(
         height,
         &SRGB_TO_XYZ_D65,
         TransferFunction::Srgb,
+        false,
     );
 }
 
@@ -605,6 +686,7 @@ pub fn rgb_to_lab(
         height,
         matrix,
         transfer_function,
+        false,
     );
 }
 
@@ -641,6 +723,44 @@ pub fn rgba_to_xyz(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ARGB to XYZ. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive XYZ data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn argb_to_xyz(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    let mut empty_vec = vec![];
+    channels_to_xyz::<{ ImageConfiguration::Argb as u8 }, false, { XyzTarget::Xyz as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        &mut empty_vec,
+        0,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
     );
 }
 
@@ -675,6 +795,7 @@ pub fn srgba_to_xyz(
         height,
         &SRGB_TO_XYZ_D65,
         TransferFunction::Srgb,
+        false,
     );
 }
 
@@ -714,6 +835,47 @@ pub fn rgba_to_xyza(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ARGB to XYZ with preserving and linearizing alpha channels. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive XYZ data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `a_plane` - A mutable slice to receive XYZ data
+/// * `a_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn argb_to_xyza(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    a_plane: &mut [f32],
+    a_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz::<{ ImageConfiguration::Argb as u8 }, true, { XyzTarget::Xyz as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        a_plane,
+        a_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
     );
 }
 
@@ -749,6 +911,7 @@ pub fn srgba_to_xyza(
         height,
         &SRGB_TO_XYZ_D65,
         TransferFunction::Srgb,
+        false,
     );
 }
 
@@ -788,6 +951,47 @@ pub fn rgba_to_lab(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ARGB to CIE L*ab against D65 white point without alpha. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `a_plane` - A mutable slice to receive XYZ data
+/// * `a_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn argb_to_lab(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    a_plane: &mut [f32],
+    a_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz::<{ ImageConfiguration::Argb as u8 }, false, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        a_plane,
+        a_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
     );
 }
 
@@ -827,6 +1031,127 @@ pub fn rgba_to_laba(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ARGB to CIE L*ab against D65 white point and preserving and normalizing alpha channels. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `a_plane` - A mutable slice to receive XYZ data
+/// * `a_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn argb_to_laba(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    a_plane: &mut [f32],
+    a_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz::<{ ImageConfiguration::Argb as u8 }, true, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        a_plane,
+        a_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
+    );
+}
+
+/// This function converts RGBA to CIE L*ab against D65 white point and preserving and normalizing alpha channels. This is much more effective than naive direct transformation. Colour channels of the source are premultiplied by alpha and are unpremultiplied before conversion
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `a_plane` - A mutable slice to receive XYZ data
+/// * `a_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn rgba_to_laba_premultiplied(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    a_plane: &mut [f32],
+    a_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz::<{ ImageConfiguration::Rgba as u8 }, true, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        a_plane,
+        a_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        true,
+    );
+}
+
+/// This function converts ARGB to CIE L*ab against D65 white point and preserving and normalizing alpha channels. This is much more effective than naive direct transformation. Colour channels of the source are premultiplied by alpha and are unpremultiplied before conversion
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `a_plane` - A mutable slice to receive XYZ data
+/// * `a_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn argb_to_laba_premultiplied(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    a_plane: &mut [f32],
+    a_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz::<{ ImageConfiguration::Argb as u8 }, true, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        a_plane,
+        a_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        true,
     );
 }
 
@@ -864,6 +1189,121 @@ pub fn bgra_to_laba(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ABGR to CIE L*ab against D65 white point and preserving and linearizing alpha channels. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn abgr_to_laba(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    a_plane: &mut [f32],
+    a_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz::<{ ImageConfiguration::Abgr as u8 }, true, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        a_plane,
+        a_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
+    );
+}
+
+/// This function converts BGRA to CIE L*ab against D65 white point and preserving and linearizing alpha channels. This is much more effective than naive direct transformation. Colour channels of the source are premultiplied by alpha and are unpremultiplied before conversion
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn bgra_to_laba_premultiplied(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    a_plane: &mut [f32],
+    a_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz::<{ ImageConfiguration::Bgra as u8 }, true, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        a_plane,
+        a_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        true,
+    );
+}
+
+/// This function converts ABGR to CIE L*ab against D65 white point and preserving and linearizing alpha channels. This is much more effective than naive direct transformation. Colour channels of the source are premultiplied by alpha and are unpremultiplied before conversion
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn abgr_to_laba_premultiplied(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    a_plane: &mut [f32],
+    a_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz::<{ ImageConfiguration::Abgr as u8 }, true, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        a_plane,
+        a_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        true,
     );
 }
 
@@ -900,6 +1340,7 @@ pub fn bgr_to_lab(
         height,
         matrix,
         transfer_function,
+        false,
     );
 }
 
@@ -936,6 +1377,7 @@ pub fn rgb_to_luv(
         height,
         matrix,
         transfer_function,
+        false,
     );
 }
 
@@ -972,6 +1414,7 @@ pub fn bgr_to_luv(
         height,
         matrix,
         transfer_function,
+        false,
     );
 }
 
@@ -1008,6 +1451,7 @@ pub fn rgb_to_lch(
         height,
         matrix,
         transfer_function,
+        false,
     );
 }
 
@@ -1044,5 +1488,6 @@ pub fn bgr_to_lch(
         height,
         matrix,
         transfer_function,
+        false,
     );
 }
@@ -0,0 +1,516 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::color_space::ImageSample;
+use crate::image::ImageConfiguration;
+use std::fmt;
+use std::ops::Range;
+
+/// Error produced when image view doesn't fit into its buffer
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ImageViewError {
+    /// Row stride in samples is smaller than `width * channels`
+    StrideTooSmall { stride: usize, required: usize },
+    /// Buffer holds fewer samples than the view addresses
+    BufferTooSmall { len: usize, required: usize },
+    /// Requested sub rectangle doesn't lie inside the view
+    RegionOutOfBounds {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+}
+
+impl fmt::Display for ImageViewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageViewError::StrideTooSmall { stride, required } => write!(
+                f,
+                "stride {} samples is smaller than row of {} samples",
+                stride, required
+            ),
+            ImageViewError::BufferTooSmall { len, required } => write!(
+                f,
+                "buffer of {} samples is smaller than required {} samples",
+                len, required
+            ),
+            ImageViewError::RegionOutOfBounds {
+                x,
+                y,
+                width,
+                height,
+            } => write!(
+                f,
+                "region {}x{} at ({}, {}) is out of image bounds",
+                width, height, x, y
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ImageViewError {}
+
+/// Checks view geometry and returns samples count of the row
+pub(crate) fn validate(
+    len: usize,
+    stride: usize,
+    width: u32,
+    height: u32,
+    layout: ImageConfiguration,
+) -> Result<usize, ImageViewError> {
+    let row_length = width as usize * layout.get_channels_count();
+    if stride < row_length {
+        return Err(ImageViewError::StrideTooSmall {
+            stride,
+            required: row_length,
+        });
+    }
+    let required = if width == 0 || height == 0 {
+        0
+    } else {
+        stride * (height as usize - 1) + row_length
+    };
+    if len < required {
+        return Err(ImageViewError::BufferTooSmall { len, required });
+    }
+    Ok(row_length)
+}
+
+/// Returns offset of the region start in samples
+fn region_offset(
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    view_width: u32,
+    view_height: u32,
+    stride: usize,
+    layout: ImageConfiguration,
+) -> Result<usize, ImageViewError> {
+    if x as u64 + width as u64 > view_width as u64 || y as u64 + height as u64 > view_height as u64
+    {
+        return Err(ImageViewError::RegionOutOfBounds {
+            x,
+            y,
+            width,
+            height,
+        });
+    }
+    Ok(y as usize * stride + x as usize * layout.get_channels_count())
+}
+
+/// Rectangle of an image in pixels
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ImageRegion {
+    /// Left column
+    pub x: u32,
+    /// Top row
+    pub y: u32,
+    /// Width in pixels
+    pub width: u32,
+    /// Height in rows
+    pub height: u32,
+}
+
+impl ImageRegion {
+    /// Creates region `width` x `height` pixels with top left corner at (`x`, `y`)
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        ImageRegion {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Creates region covering rows `rows` of an image `width` pixels wide
+    pub fn rows(rows: Range<u32>, width: u32) -> Self {
+        ImageRegion {
+            x: 0,
+            y: rows.start,
+            width,
+            height: rows.end.saturating_sub(rows.start),
+        }
+    }
+}
+
+/// Converts byte stride into samples stride
+#[inline]
+#[allow(clippy::manual_is_multiple_of)] // `usize::is_multiple_of` needs Rust 1.87
+pub(crate) fn samples_stride<T>(byte_stride: u32) -> usize {
+    let size = std::mem::size_of::<T>();
+    if byte_stride as usize % size != 0 {
+        panic!(
+            "Stride {} bytes is not a multiple of sample size {}",
+            byte_stride, size
+        );
+    }
+    byte_stride as usize / size
+}
+
+/// Checks that image with byte stride `byte_stride` fits into slice of `len` samples
+/// and returns stride in samples
+///
+/// # Panics
+/// Panics if stride is not a multiple of sample size, smaller than the row or image doesn't fit into slice
+pub(crate) fn check_image<T>(
+    len: usize,
+    byte_stride: u32,
+    width: u32,
+    height: u32,
+    layout: ImageConfiguration,
+    what: &str,
+) -> usize {
+    let stride = samples_stride::<T>(byte_stride);
+    if let Err(err) = validate(len, stride, width, height, layout) {
+        panic!("{} image doesn't fit into slice: {}", what, err);
+    }
+    stride
+}
+
+/// Immutable view of interleaved image.
+///
+/// Stride is measured in samples, so for `f32` images it is four times smaller than byte stride
+/// expected by slice based converters, use [ImageRef::byte_stride] to pass the view there.
+#[derive(Debug, Copy, Clone)]
+pub struct ImageRef<'a, T: ImageSample> {
+    data: &'a [T],
+    stride: usize,
+    width: u32,
+    height: u32,
+    layout: ImageConfiguration,
+}
+
+impl<'a, T: ImageSample> ImageRef<'a, T> {
+    /// Creates view of tightly packed image
+    ///
+    /// # Arguments
+    /// * `data` - Samples of the image
+    /// * `width` - Image width
+    /// * `height` - Image height
+    /// * `layout` - Interleaved channel layout
+    pub fn new(
+        data: &'a [T],
+        width: u32,
+        height: u32,
+        layout: ImageConfiguration,
+    ) -> Result<Self, ImageViewError> {
+        let stride = width as usize * layout.get_channels_count();
+        ImageRef::with_stride(data, stride, width, height, layout)
+    }
+
+    /// Creates view of image with padded rows
+    ///
+    /// # Arguments
+    /// * `data` - Samples of the image
+    /// * `stride` - Samples per row, not bytes
+    /// * `width` - Image width
+    /// * `height` - Image height
+    /// * `layout` - Interleaved channel layout
+    pub fn with_stride(
+        data: &'a [T],
+        stride: usize,
+        width: u32,
+        height: u32,
+        layout: ImageConfiguration,
+    ) -> Result<Self, ImageViewError> {
+        validate(data.len(), stride, width, height, layout)?;
+        Ok(ImageRef {
+            data,
+            stride,
+            width,
+            height,
+            layout,
+        })
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    #[inline]
+    pub fn layout(&self) -> ImageConfiguration {
+        self.layout
+    }
+
+    /// Samples per row
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Bytes per row, as expected by slice based converters
+    #[inline]
+    pub fn byte_stride(&self) -> u32 {
+        (self.stride * std::mem::size_of::<T>()) as u32
+    }
+
+    /// Returns samples starting at the first pixel of the view
+    #[inline]
+    pub fn data(&self) -> &'a [T] {
+        self.data
+    }
+
+    /// Returns `width * channels` samples of the row `y`
+    #[inline]
+    pub fn row(&self, y: u32) -> &'a [T] {
+        assert!(y < self.height, "Row {} is out of bounds", y);
+        let start = y as usize * self.stride;
+        &self.data[start..start + self.width as usize * self.layout.get_channels_count()]
+    }
+
+    /// Creates view of the region inside this view, it keeps stride and layout of this view.
+    ///
+    /// Any view overload such as [view_to_lab](crate::view_to_lab) converts a region of interest
+    /// or a strip of rows this way, layouts are taken from the views.
+    ///
+    /// # Arguments
+    /// * `region` - Region of this view, use [ImageRegion::rows] for a range of rows
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use colorutils_rs::*;
+    /// let (width, height) = (64u32, 48u32);
+    /// let bgra = vec![0u8; width as usize * height as usize * 4];
+    /// let mut lab = vec![0f32; width as usize * height as usize * 4];
+    /// let region = ImageRegion::new(8, 4, 16, 16);
+    ///
+    /// let src = ImageRef::new(&bgra, width, height, ImageConfiguration::Bgra).unwrap();
+    /// let mut dst = ImageMut::new(&mut lab, width, height, ImageConfiguration::Rgba).unwrap();
+    /// view_to_lab(
+    ///     &src.sub_view(region).unwrap(),
+    ///     &mut dst.sub_view_mut(region).unwrap(),
+    ///     &SRGB_TO_XYZ_D65,
+    ///     TransferFunction::Srgb,
+    /// );
+    /// ```
+    pub fn sub_view(&self, region: ImageRegion) -> Result<ImageRef<'a, T>, ImageViewError> {
+        let offset = region_offset(
+            region.x,
+            region.y,
+            region.width,
+            region.height,
+            self.width,
+            self.height,
+            self.stride,
+            self.layout,
+        )?;
+        let data = &self.data[offset.min(self.data.len())..];
+        ImageRef::with_stride(data, self.stride, region.width, region.height, self.layout)
+    }
+}
+
+/// Mutable view of interleaved image.
+///
+/// Stride is measured in samples, so for `f32` images it is four times smaller than byte stride
+/// expected by slice based converters, use [ImageMut::byte_stride] to pass the view there.
+#[derive(Debug)]
+pub struct ImageMut<'a, T: ImageSample> {
+    data: &'a mut [T],
+    stride: usize,
+    width: u32,
+    height: u32,
+    layout: ImageConfiguration,
+}
+
+impl<'a, T: ImageSample> ImageMut<'a, T> {
+    /// Creates view of tightly packed image
+    ///
+    /// # Arguments
+    /// * `data` - Samples of the image
+    /// * `width` - Image width
+    /// * `height` - Image height
+    /// * `layout` - Interleaved channel layout
+    pub fn new(
+        data: &'a mut [T],
+        width: u32,
+        height: u32,
+        layout: ImageConfiguration,
+    ) -> Result<Self, ImageViewError> {
+        let stride = width as usize * layout.get_channels_count();
+        ImageMut::with_stride(data, stride, width, height, layout)
+    }
+
+    /// Creates view of image with padded rows
+    ///
+    /// # Arguments
+    /// * `data` - Samples of the image
+    /// * `stride` - Samples per row, not bytes
+    /// * `width` - Image width
+    /// * `height` - Image height
+    /// * `layout` - Interleaved channel layout
+    pub fn with_stride(
+        data: &'a mut [T],
+        stride: usize,
+        width: u32,
+        height: u32,
+        layout: ImageConfiguration,
+    ) -> Result<Self, ImageViewError> {
+        validate(data.len(), stride, width, height, layout)?;
+        Ok(ImageMut {
+            data,
+            stride,
+            width,
+            height,
+            layout,
+        })
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    #[inline]
+    pub fn layout(&self) -> ImageConfiguration {
+        self.layout
+    }
+
+    /// Samples per row
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Bytes per row, as expected by slice based converters
+    #[inline]
+    pub fn byte_stride(&self) -> u32 {
+        (self.stride * std::mem::size_of::<T>()) as u32
+    }
+
+    /// Returns samples starting at the first pixel of the view
+    #[inline]
+    pub fn data(&self) -> &[T] {
+        self.data
+    }
+
+    /// Returns mutable samples starting at the first pixel of the view
+    #[inline]
+    pub fn data_mut(&mut self) -> &mut [T] {
+        self.data
+    }
+
+    /// Returns `width * channels` samples of the row `y`
+    #[inline]
+    pub fn row(&self, y: u32) -> &[T] {
+        self.as_image_ref().row(y)
+    }
+
+    /// Returns mutable `width * channels` samples of the row `y`
+    #[inline]
+    pub fn row_mut(&mut self, y: u32) -> &mut [T] {
+        assert!(y < self.height, "Row {} is out of bounds", y);
+        let start = y as usize * self.stride;
+        let row_length = self.width as usize * self.layout.get_channels_count();
+        &mut self.data[start..start + row_length]
+    }
+
+    /// Reborrows view as immutable
+    #[inline]
+    pub fn as_image_ref(&self) -> ImageRef<'_, T> {
+        ImageRef {
+            data: self.data,
+            stride: self.stride,
+            width: self.width,
+            height: self.height,
+            layout: self.layout,
+        }
+    }
+
+    /// Creates mutable view of the region inside this view, it keeps stride and layout of this view
+    ///
+    /// # Arguments
+    /// * `region` - Region of this view, use [ImageRegion::rows] for a range of rows
+    pub fn sub_view_mut(&mut self, region: ImageRegion) -> Result<ImageMut<'_, T>, ImageViewError> {
+        let offset = region_offset(
+            region.x,
+            region.y,
+            region.width,
+            region.height,
+            self.width,
+            self.height,
+            self.stride,
+            self.layout,
+        )?;
+        let start = offset.min(self.data.len());
+        ImageMut::with_stride(
+            &mut self.data[start..],
+            self.stride,
+            region.width,
+            region.height,
+            self.layout,
+        )
+    }
+}
+
+/// This function runs converter written for slices and byte strides over image views.
+/// Any converter of the crate may be used this way, for example
+/// `convert_views(&src, &mut dst, |s, ss, d, ds, w, h| rgb_to_lab(s, ss, d, ds, w, h, &SRGB_TO_XYZ_D65, TransferFunction::Srgb))`.
+/// Converter receives only rows covered by the views, so views may be regions of larger images.
+/// Layouts of the views are not checked against the converter, they must match its channel order.
+///
+/// # Arguments
+/// * `src` - Source view
+/// * `dst` - Destination view
+/// * `converter` - Function receiving source, source stride in bytes, destination,
+///   destination stride in bytes, width and height
+///
+/// # Panics
+/// Panics if views have different dimensions
+pub fn convert_views<S: ImageSample, D: ImageSample>(
+    src: &ImageRef<S>,
+    dst: &mut ImageMut<D>,
+    converter: impl Fn(&[S], u32, &mut [D], u32, u32, u32),
+) {
+    if src.width() != dst.width() || src.height() != dst.height() {
+        panic!(
+            "Source view {}x{} and destination view {}x{} must have the same size",
+            src.width(),
+            src.height(),
+            dst.width(),
+            dst.height()
+        );
+    }
+    let (width, height) = (src.width(), src.height());
+    if width == 0 || height == 0 {
+        return;
+    }
+
+    // Last row of a region may be shorter than stride, so it is converted as an image of its own
+    let body_rows = height as usize - 1;
+    if body_rows > 0 {
+        let src_stride = src.stride();
+        let dst_stride = dst.stride();
+        let dst_byte_stride = dst.byte_stride();
+        converter(
+            &src.data()[..src_stride * body_rows],
+            src.byte_stride(),
+            &mut dst.data_mut()[..dst_stride * body_rows],
+            dst_byte_stride,
+            width,
+            body_rows as u32,
+        );
+    }
+
+    let src_row = src.row(height - 1);
+    let src_row_stride = std::mem::size_of_val(src_row) as u32;
+    let dst_row = dst.row_mut(height - 1);
+    let dst_row_stride = std::mem::size_of_val(dst_row) as u32;
+    converter(src_row, src_row_stride, dst_row, dst_row_stride, width, 1);
+}
@@ -0,0 +1,1418 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::gamma_curves::TransferFunction;
+use crate::gray_lab::{
+    gray_to_lab_impl, gray_to_lightness_table_u8, lab_to_gray_impl, lightness_to_gray_table_u8,
+};
+use crate::hsv_to_image::hsv_u16_to_channels;
+use crate::image::ImageConfiguration;
+use crate::image_to_hsv::channels_to_hsv_u16;
+use crate::image_to_hsv_support::HsvTarget;
+use crate::image_to_jzazbz::{channels_to_jzaz, JzazbzTarget};
+use crate::image_to_lalphabeta::channels_to_lalphabeta;
+use crate::image_to_linear::channels_to_linear;
+use crate::image_to_linear_u8::channels_to_linear as channels_to_linear_u8;
+use crate::image_to_oklab::{channels_to_oklab, OklabTarget};
+use crate::image_to_sigmoidal::image_to_sigmoidal;
+use crate::image_to_xyz_lab::channels_to_xyz;
+use crate::image_view::{convert_views, ImageMut, ImageRef};
+use crate::image_xyza_laba::channels_to_xyz_with_alpha;
+use crate::jzazbz_to_image::jzazbz_to_image;
+use crate::lalphabeta_to_image::lalphabeta_to_image;
+use crate::linear_to_image::linear_to_gamma_channels;
+use crate::linear_to_image_u8::linear_to_gamma_channels as linear_u8_to_gamma_channels;
+use crate::oklab_to_image::oklab_to_image;
+use crate::sigmoidal_to_image::sigmoidal_to_image;
+use crate::threading::current_threading;
+use crate::xyz_lab_to_image::xyz_to_channels;
+use crate::xyz_target::XyzTarget;
+use crate::xyza_laba_to_image::xyz_with_alpha_to_channels;
+
+/// Dispatches converter generic over the layout of RGB side view,
+/// gray views are rejected here since colour kernels have no gray instances
+macro_rules! with_view_layout {
+    ($layout:expr, $func:ident, [$($generics:tt)*], ($($args:expr),* $(,)?)) => {
+        match $layout {
+            ImageConfiguration::Rgb => {
+                $func::<{ ImageConfiguration::Rgb as u8 }, $($generics)*>($($args),*)
+            }
+            ImageConfiguration::Rgba => {
+                $func::<{ ImageConfiguration::Rgba as u8 }, $($generics)*>($($args),*)
+            }
+            ImageConfiguration::Bgra => {
+                $func::<{ ImageConfiguration::Bgra as u8 }, $($generics)*>($($args),*)
+            }
+            ImageConfiguration::Bgr => {
+                $func::<{ ImageConfiguration::Bgr as u8 }, $($generics)*>($($args),*)
+            }
+            ImageConfiguration::Argb => {
+                $func::<{ ImageConfiguration::Argb as u8 }, $($generics)*>($($args),*)
+            }
+            ImageConfiguration::Abgr => {
+                $func::<{ ImageConfiguration::Abgr as u8 }, $($generics)*>($($args),*)
+            }
+            ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                panic!("Gray views are supported only by linear and CIE L*ab converters")
+            }
+        }
+    };
+}
+
+/// Dispatches gray kernels over gray layout of the view
+macro_rules! with_gray_view_layout {
+    ($layout:expr, $func:ident, ($($args:expr),* $(,)?)) => {
+        match $layout {
+            ImageConfiguration::Gray => $func::<{ ImageConfiguration::Gray as u8 }>($($args),*),
+            ImageConfiguration::GrayAlpha => {
+                $func::<{ ImageConfiguration::GrayAlpha as u8 }>($($args),*)
+            }
+            _ => unreachable!("Colour views are dispatched by with_view_layout"),
+        }
+    };
+}
+
+/// Returns true if view of colour model channels carries alpha.
+/// Model channels are stored in R, G, B order, so only [ImageConfiguration::Rgb]
+/// and [ImageConfiguration::Rgba] describe them.
+fn model_has_alpha(layout: ImageConfiguration, what: &str) -> bool {
+    match layout {
+        ImageConfiguration::Rgb => false,
+        ImageConfiguration::Rgba => true,
+        _ => panic!(
+            "{} view of colour model channels must have Rgb or Rgba layout, got {:?}",
+            what, layout
+        ),
+    }
+}
+
+/// Checks that model view carries alpha exactly when RGB view does
+fn check_same_alpha(rgb_layout: ImageConfiguration, model_layout: ImageConfiguration) -> bool {
+    let has_alpha = model_has_alpha(model_layout, "Model");
+    if has_alpha != rgb_layout.has_alpha() {
+        panic!(
+            "Views of layouts {:?} and {:?} must both have alpha or both have none",
+            rgb_layout, model_layout
+        );
+    }
+    has_alpha
+}
+
+fn view_to_xyz_target<const TARGET: u8>(
+    src: &ImageRef<u8>,
+    dst: &mut ImageMut<f32>,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    let layout = src.layout();
+    let with_alpha = model_has_alpha(dst.layout(), "Destination");
+    if with_alpha && !layout.has_alpha() {
+        panic!(
+            "Destination view expects alpha but source layout {:?} has none",
+            layout
+        );
+    }
+    convert_views(
+        src,
+        dst,
+        |src, src_stride, dst, dst_stride, width, height| {
+            if with_alpha {
+                with_view_layout!(
+                    layout,
+                    channels_to_xyz_with_alpha,
+                    [TARGET],
+                    (
+                        src,
+                        src_stride,
+                        dst,
+                        dst_stride,
+                        width,
+                        height,
+                        matrix,
+                        transfer_function,
+                        false
+                    )
+                )
+            } else {
+                with_view_layout!(
+                    layout,
+                    channels_to_xyz,
+                    [false, TARGET],
+                    (
+                        src,
+                        src_stride,
+                        dst,
+                        dst_stride,
+                        &mut [],
+                        0,
+                        width,
+                        height,
+                        matrix,
+                        transfer_function,
+                        false
+                    )
+                )
+            }
+        },
+    );
+}
+
+fn xyz_target_to_view<const TARGET: u8>(
+    src: &ImageRef<f32>,
+    dst: &mut ImageMut<u8>,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    let layout = dst.layout();
+    let with_alpha = check_same_alpha(layout, src.layout());
+    convert_views(
+        src,
+        dst,
+        |src, src_stride, dst, dst_stride, width, height| {
+            if with_alpha {
+                with_view_layout!(
+                    layout,
+                    xyz_with_alpha_to_channels,
+                    [TARGET],
+                    (
+                        src,
+                        src_stride,
+                        dst,
+                        dst_stride,
+                        width,
+                        height,
+                        matrix,
+                        transfer_function,
+                        false
+                    )
+                )
+            } else {
+                with_view_layout!(
+                    layout,
+                    xyz_to_channels,
+                    [false, TARGET],
+                    (
+                        src,
+                        src_stride,
+                        &[],
+                        0,
+                        dst,
+                        dst_stride,
+                        width,
+                        height,
+                        matrix,
+                        transfer_function,
+                        false
+                    )
+                )
+            }
+        },
+    );
+}
+
+/// This function converts image view to CIE XYZ. Layout of the source view selects channel order,
+/// destination view with [ImageConfiguration::Rgba] layout receives X, Y, Z and normalized alpha,
+/// with [ImageConfiguration::Rgb] layout X, Y and Z only
+///
+/// # Arguments
+/// * `src` - Source view of RGB data in any colour layout
+/// * `dst` - Destination view to receive XYZ data, layout [ImageConfiguration::Rgb] or [ImageConfiguration::Rgba]
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+///
+/// # Panics
+/// Panics if views have different dimensions, source view is gray,
+/// or destination expects alpha the source doesn't have
+pub fn view_to_xyz(
+    src: &ImageRef<u8>,
+    dst: &mut ImageMut<f32>,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    view_to_xyz_target::<{ XyzTarget::Xyz as u8 }>(src, dst, matrix, transfer_function);
+}
+
+/// This function converts image view to CIE L\*ab against D65 white point. Layout of the source view
+/// selects channel order, destination view with [ImageConfiguration::Rgba] layout receives L, a, b
+/// and normalized alpha, with [ImageConfiguration::Rgb] layout L, a and b only.
+/// Gray source view holds luminance, destination of the same gray layout receives L alone
+/// and normalized alpha if the layout has it, `matrix` isn't used then
+///
+/// # Arguments
+/// * `src` - Source view of RGB data in any colour layout, or of luminance in gray layout
+/// * `dst` - Destination view to receive LAB data, layout [ImageConfiguration::Rgb] or [ImageConfiguration::Rgba],
+///   or the layout of gray source
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+///
+/// # Panics
+/// Panics if views have different dimensions, only one of the views is gray or gray layouts differ,
+/// or destination expects alpha the source doesn't have
+pub fn view_to_lab(
+    src: &ImageRef<u8>,
+    dst: &mut ImageMut<f32>,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    if src.layout().is_gray() {
+        let layout = src.layout();
+        check_same_layout(layout, dst.layout());
+        let lut_table = gray_to_lightness_table_u8(transfer_function);
+        let policy = current_threading();
+        convert_views(
+            src,
+            dst,
+            |src, src_stride, dst, dst_stride, width, height| {
+                with_gray_view_layout!(
+                    layout,
+                    gray_to_lab_impl,
+                    (src, src_stride, dst, dst_stride, width, height, &lut_table, &policy)
+                )
+            },
+        );
+        return;
+    }
+    view_to_xyz_target::<{ XyzTarget::Lab as u8 }>(src, dst, matrix, transfer_function);
+}
+
+/// This function converts image view to CIE L\*uv against D65 white point. Layout of the source view
+/// selects channel order, destination view with [ImageConfiguration::Rgba] layout receives L, u, v
+/// and normalized alpha, with [ImageConfiguration::Rgb] layout L, u and v only
+///
+/// # Arguments
+/// * `src` - Source view of RGB data in any colour layout
+/// * `dst` - Destination view to receive LUV data, layout [ImageConfiguration::Rgb] or [ImageConfiguration::Rgba]
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+///
+/// # Panics
+/// Panics if views have different dimensions, source view is gray,
+/// or destination expects alpha the source doesn't have
+pub fn view_to_luv(
+    src: &ImageRef<u8>,
+    dst: &mut ImageMut<f32>,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    view_to_xyz_target::<{ XyzTarget::Luv as u8 }>(src, dst, matrix, transfer_function);
+}
+
+/// This function converts CIE XYZ view to image view. Layout of the destination view selects channel order,
+/// source view with [ImageConfiguration::Rgba] layout holds X, Y, Z and normalized alpha
+///
+/// # Arguments
+/// * `src` - Source view of XYZ data, layout [ImageConfiguration::Rgb] or [ImageConfiguration::Rgba]
+/// * `dst` - Destination view to receive RGB data in any colour layout with alpha matching the source
+/// * `matrix` - Transformation matrix from XYZ to RGB. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+///
+/// # Panics
+/// Panics if views have different dimensions, destination view is gray, or only one of the views has alpha
+pub fn xyz_to_view(
+    src: &ImageRef<f32>,
+    dst: &mut ImageMut<u8>,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    xyz_target_to_view::<{ XyzTarget::Xyz as u8 }>(src, dst, matrix, transfer_function);
+}
+
+/// This function converts CIE L\*ab view to image view. Layout of the destination view selects channel order,
+/// source view with [ImageConfiguration::Rgba] layout holds L, a, b and normalized alpha.
+/// Gray destination view receives luminance from L alone, source then has the same gray layout
+/// and `matrix` isn't used
+///
+/// # Arguments
+/// * `src` - Source view of LAB data, layout [ImageConfiguration::Rgb] or [ImageConfiguration::Rgba],
+///   or the layout of gray destination
+/// * `dst` - Destination view to receive RGB data in any colour layout with alpha matching the source,
+///   or luminance in gray layout
+/// * `matrix` - Transformation matrix from XYZ to RGB. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+///
+/// # Panics
+/// Panics if views have different dimensions, only one of the views is gray or gray layouts differ,
+/// or only one of the views has alpha
+pub fn lab_to_view(
+    src: &ImageRef<f32>,
+    dst: &mut ImageMut<u8>,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    if dst.layout().is_gray() {
+        let layout = dst.layout();
+        check_same_layout(src.layout(), layout);
+        let lut_table = lightness_to_gray_table_u8(transfer_function);
+        let policy = current_threading();
+        convert_views(
+            src,
+            dst,
+            |src, src_stride, dst, dst_stride, width, height| {
+                with_gray_view_layout!(
+                    layout,
+                    lab_to_gray_impl,
+                    (src, src_stride, dst, dst_stride, width, height, &lut_table, &policy)
+                )
+            },
+        );
+        return;
+    }
+    xyz_target_to_view::<{ XyzTarget::Lab as u8 }>(src, dst, matrix, transfer_function);
+}
+
+/// This function converts CIE L\*uv view to image view. Layout of the destination view selects channel order,
+/// source view with [ImageConfiguration::Rgba] layout holds L, u, v and normalized alpha
+///
+/// # Arguments
+/// * `src` - Source view of LUV data, layout [ImageConfiguration::Rgb] or [ImageConfiguration::Rgba]
+/// * `dst` - Destination view to receive RGB data in any colour layout with alpha matching the source
+/// * `matrix` - Transformation matrix from XYZ to RGB. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+///
+/// # Panics
+/// Panics if views have different dimensions, destination view is gray, or only one of the views has alpha
+pub fn luv_to_view(
+    src: &ImageRef<f32>,
+    dst: &mut ImageMut<u8>,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    xyz_target_to_view::<{ XyzTarget::Luv as u8 }>(src, dst, matrix, transfer_function);
+}
+
+fn view_to_oklab_target<const TARGET: u8>(
+    src: &ImageRef<u8>,
+    dst: &mut ImageMut<f32>,
+    transfer_function: TransferFunction,
+) {
+    let layout = src.layout();
+    check_same_alpha(layout, dst.layout());
+    convert_views(
+        src,
+        dst,
+        |src, src_stride, dst, dst_stride, width, height| {
+            with_view_layout!(
+                layout,
+                channels_to_oklab,
+                [TARGET],
+                (
+                    src,
+                    src_stride,
+                    dst,
+                    dst_stride,
+                    width,
+                    height,
+                    transfer_function,
+                    false
+                )
+            )
+        },
+    );
+}
+
+fn oklab_target_to_view<const TARGET: u8>(
+    src: &ImageRef<f32>,
+    dst: &mut ImageMut<u8>,
+    transfer_function: TransferFunction,
+) {
+    let layout = dst.layout();
+    check_same_alpha(layout, src.layout());
+    convert_views(
+        src,
+        dst,
+        |src, src_stride, dst, dst_stride, width, height| {
+            with_view_layout!(
+                layout,
+                oklab_to_image,
+                [TARGET],
+                (
+                    src,
+                    src_stride,
+                    dst,
+                    dst_stride,
+                    width,
+                    height,
+                    transfer_function,
+                    false
+                )
+            )
+        },
+    );
+}
+
+/// This function converts image view to CIE LCh(uv) against D65 white point. Layout of the source view
+/// selects channel order, destination view with [ImageConfiguration::Rgba] layout receives L, C, h
+/// and normalized alpha, with [ImageConfiguration::Rgb] layout L, C and h only
+///
+/// # Arguments
+/// * `src` - Source view of RGB data in any colour layout
+/// * `dst` - Destination view to receive LCH data, layout [ImageConfiguration::Rgb] or [ImageConfiguration::Rgba]
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+///
+/// # Panics
+/// Panics if views have different dimensions, source view is gray,
+/// or destination expects alpha the source doesn't have
+pub fn view_to_lch(
+    src: &ImageRef<u8>,
+    dst: &mut ImageMut<f32>,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    view_to_xyz_target::<{ XyzTarget::Lch as u8 }>(src, dst, matrix, transfer_function);
+}
+
+/// This function converts CIE LCh(uv) view to image view. Layout of the destination view selects channel order,
+/// source view with [ImageConfiguration::Rgba] layout holds L, C, h and normalized alpha
+///
+/// # Arguments
+/// * `src` - Source view of LCH data, layout [ImageConfiguration::Rgb] or [ImageConfiguration::Rgba]
+/// * `dst` - Destination view to receive RGB data in any colour layout with alpha matching the source
+/// * `matrix` - Transformation matrix from XYZ to RGB. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+///
+/// # Panics
+/// Panics if views have different dimensions, destination view is gray, or only one of the views has alpha
+pub fn lch_to_view(
+    src: &ImageRef<f32>,
+    dst: &mut ImageMut<u8>,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    xyz_target_to_view::<{ XyzTarget::Lch as u8 }>(src, dst, matrix, transfer_function);
+}
+
+/// This function converts image view to Oklab. Layout of the source view selects channel order,
+/// alpha is normalized and kept when the views have it
+///
+/// # Arguments
+/// * `src` - Source view of RGB data in any colour layout
+/// * `dst` - Destination view to receive Oklab data, [ImageConfiguration::Rgba] layout if source has alpha,
+///   [ImageConfiguration::Rgb] otherwise
+/// * `transfer_function` - Transfer function into linear colorspace and its inverse
+///
+/// # Panics
+/// Panics if views have different dimensions, source view is gray, or only one of the views has alpha
+pub fn view_to_oklab(
+    src: &ImageRef<u8>,
+    dst: &mut ImageMut<f32>,
+    transfer_function: TransferFunction,
+) {
+    view_to_oklab_target::<{ OklabTarget::Oklab as u8 }>(src, dst, transfer_function);
+}
+
+/// This function converts Oklab view to image view. Layout of the destination view selects channel order,
+/// alpha is denormalized and kept when the views have it
+///
+/// # Arguments
+/// * `src` - Source view of Oklab data, [ImageConfiguration::Rgba] layout if destination has alpha,
+///   [ImageConfiguration::Rgb] otherwise
+/// * `dst` - Destination view to receive RGB data in any colour layout
+/// * `transfer_function` - Transfer function into linear colorspace and its inverse
+///
+/// # Panics
+/// Panics if views have different dimensions, destination view is gray, or only one of the views has alpha
+pub fn oklab_to_view(
+    src: &ImageRef<f32>,
+    dst: &mut ImageMut<u8>,
+    transfer_function: TransferFunction,
+) {
+    oklab_target_to_view::<{ OklabTarget::Oklab as u8 }>(src, dst, transfer_function);
+}
+
+/// This function converts image view to Oklch. Layout of the source view selects channel order,
+/// alpha is normalized and kept when the views have it
+///
+/// # Arguments
+/// * `src` - Source view of RGB data in any colour layout
+/// * `dst` - Destination view to receive Oklch data, [ImageConfiguration::Rgba] layout if source has alpha,
+///   [ImageConfiguration::Rgb] otherwise
+/// * `transfer_function` - Transfer function into linear colorspace and its inverse
+///
+/// # Panics
+/// Panics if views have different dimensions, source view is gray, or only one of the views has alpha
+pub fn view_to_oklch(
+    src: &ImageRef<u8>,
+    dst: &mut ImageMut<f32>,
+    transfer_function: TransferFunction,
+) {
+    view_to_oklab_target::<{ OklabTarget::Oklch as u8 }>(src, dst, transfer_function);
+}
+
+/// This function converts Oklch view to image view. Layout of the destination view selects channel order,
+/// alpha is denormalized and kept when the views have it
+///
+/// # Arguments
+/// * `src` - Source view of Oklch data, [ImageConfiguration::Rgba] layout if destination has alpha,
+///   [ImageConfiguration::Rgb] otherwise
+/// * `dst` - Destination view to receive RGB data in any colour layout
+/// * `transfer_function` - Transfer function into linear colorspace and its inverse
+///
+/// # Panics
+/// Panics if views have different dimensions, destination view is gray, or only one of the views has alpha
+pub fn oklch_to_view(
+    src: &ImageRef<f32>,
+    dst: &mut ImageMut<u8>,
+    transfer_function: TransferFunction,
+) {
+    oklab_target_to_view::<{ OklabTarget::Oklch as u8 }>(src, dst, transfer_function);
+}
+
+fn view_to_jzazbz_target<const TARGET: u8>(
+    src: &ImageRef<u8>,
+    dst: &mut ImageMut<f32>,
+    display_luminance: f32,
+    transfer_function: TransferFunction,
+) {
+    let layout = src.layout();
+    check_same_alpha(layout, dst.layout());
+    convert_views(
+        src,
+        dst,
+        |src, src_stride, dst, dst_stride, width, height| {
+            with_view_layout!(
+                layout,
+                channels_to_jzaz,
+                [TARGET],
+                (
+                    src,
+                    src_stride,
+                    dst,
+                    dst_stride,
+                    width,
+                    height,
+                    display_luminance,
+                    transfer_function
+                )
+            )
+        },
+    );
+}
+
+fn jzazbz_target_to_view<const TARGET: u8>(
+    src: &ImageRef<f32>,
+    dst: &mut ImageMut<u8>,
+    display_luminance: f32,
+    transfer_function: TransferFunction,
+) {
+    let layout = dst.layout();
+    check_same_alpha(layout, src.layout());
+    convert_views(
+        src,
+        dst,
+        |src, src_stride, dst, dst_stride, width, height| {
+            with_view_layout!(
+                layout,
+                jzazbz_to_image,
+                [TARGET],
+                (
+                    src,
+                    src_stride,
+                    dst,
+                    dst_stride,
+                    width,
+                    height,
+                    display_luminance,
+                    transfer_function
+                )
+            )
+        },
+    );
+}
+
+/// This function converts image view to Jzazbz against D65 white point. Layout of the source view
+/// selects channel order, alpha is normalized and kept when the views have it
+///
+/// # Arguments
+/// * `src` - Source view of RGB data in any colour layout
+/// * `dst` - Destination view to receive Jzazbz data, [ImageConfiguration::Rgba] layout if source has alpha,
+///   [ImageConfiguration::Rgb] otherwise
+/// * `display_luminance` - Target display luminance
+/// * `transfer_function` - Transfer function into linear colorspace
+///
+/// # Panics
+/// Panics if views have different dimensions, source view is gray, or only one of the views has alpha
+pub fn view_to_jzazbz(
+    src: &ImageRef<u8>,
+    dst: &mut ImageMut<f32>,
+    display_luminance: f32,
+    transfer_function: TransferFunction,
+) {
+    view_to_jzazbz_target::<{ JzazbzTarget::Jzazbz as u8 }>(
+        src,
+        dst,
+        display_luminance,
+        transfer_function,
+    );
+}
+
+/// This function converts Jzazbz view to image view. Layout of the destination view selects channel order,
+/// alpha is denormalized and kept when the views have it
+///
+/// # Arguments
+/// * `src` - Source view of Jzazbz data, [ImageConfiguration::Rgba] layout if destination has alpha,
+///   [ImageConfiguration::Rgb] otherwise
+/// * `dst` - Destination view to receive RGB data in any colour layout
+/// * `display_luminance` - Target display luminance
+/// * `transfer_function` - Transfer function into gamma colorspace
+///
+/// # Panics
+/// Panics if views have different dimensions, destination view is gray, or only one of the views has alpha
+pub fn jzazbz_to_view(
+    src: &ImageRef<f32>,
+    dst: &mut ImageMut<u8>,
+    display_luminance: f32,
+    transfer_function: TransferFunction,
+) {
+    jzazbz_target_to_view::<{ JzazbzTarget::Jzazbz as u8 }>(
+        src,
+        dst,
+        display_luminance,
+        transfer_function,
+    );
+}
+
+/// This function converts image view to Jzczhz against D65 white point. Layout of the source view
+/// selects channel order, alpha is normalized and kept when the views have it
+///
+/// # Arguments
+/// * `src` - Source view of RGB data in any colour layout
+/// * `dst` - Destination view to receive Jzczhz data, [ImageConfiguration::Rgba] layout if source has alpha,
+///   [ImageConfiguration::Rgb] otherwise
+/// * `display_luminance` - Target display luminance
+/// * `transfer_function` - Transfer function into linear colorspace
+///
+/// # Panics
+/// Panics if views have different dimensions, source view is gray, or only one of the views has alpha
+pub fn view_to_jzczhz(
+    src: &ImageRef<u8>,
+    dst: &mut ImageMut<f32>,
+    display_luminance: f32,
+    transfer_function: TransferFunction,
+) {
+    view_to_jzazbz_target::<{ JzazbzTarget::Jzczhz as u8 }>(
+        src,
+        dst,
+        display_luminance,
+        transfer_function,
+    );
+}
+
+/// This function converts Jzczhz view to image view. Layout of the destination view selects channel order,
+/// alpha is denormalized and kept when the views have it
+///
+/// # Arguments
+/// * `src` - Source view of Jzczhz data, [ImageConfiguration::Rgba] layout if destination has alpha,
+///   [ImageConfiguration::Rgb] otherwise
+/// * `dst` - Destination view to receive RGB data in any colour layout
+/// * `display_luminance` - Target display luminance
+/// * `transfer_function` - Transfer function into gamma colorspace
+///
+/// # Panics
+/// Panics if views have different dimensions, destination view is gray, or only one of the views has alpha
+pub fn jzczhz_to_view(
+    src: &ImageRef<f32>,
+    dst: &mut ImageMut<u8>,
+    display_luminance: f32,
+    transfer_function: TransferFunction,
+) {
+    jzazbz_target_to_view::<{ JzazbzTarget::Jzczhz as u8 }>(
+        src,
+        dst,
+        display_luminance,
+        transfer_function,
+    );
+}
+
+/// This function converts image view to *lαβ* against D65 white point. Layout of the source view
+/// selects channel order, alpha is normalized and kept when the views have it
+///
+/// # Arguments
+/// * `src` - Source view of RGB data in any colour layout
+/// * `dst` - Destination view to receive *lαβ* data, [ImageConfiguration::Rgba] layout if source has alpha,
+///   [ImageConfiguration::Rgb] otherwise
+/// * `transfer_function` - Transfer function into linear colorspace
+///
+/// # Panics
+/// Panics if views have different dimensions, source view is gray, or only one of the views has alpha
+pub fn view_to_lalphabeta(
+    src: &ImageRef<u8>,
+    dst: &mut ImageMut<f32>,
+    transfer_function: TransferFunction,
+) {
+    let layout = src.layout();
+    check_same_alpha(layout, dst.layout());
+    convert_views(
+        src,
+        dst,
+        |src, src_stride, dst, dst_stride, width, height| {
+            with_view_layout!(
+                layout,
+                channels_to_lalphabeta,
+                [],
+                (
+                    src,
+                    src_stride,
+                    dst,
+                    dst_stride,
+                    width,
+                    height,
+                    transfer_function
+                )
+            )
+        },
+    );
+}
+
+/// This function converts *lαβ* view to image view. Layout of the destination view selects channel order,
+/// alpha is denormalized and kept when the views have it
+///
+/// # Arguments
+/// * `src` - Source view of *lαβ* data, [ImageConfiguration::Rgba] layout if destination has alpha,
+///   [ImageConfiguration::Rgb] otherwise
+/// * `dst` - Destination view to receive RGB data in any colour layout
+/// * `transfer_function` - Transfer function into gamma colorspace
+///
+/// # Panics
+/// Panics if views have different dimensions, destination view is gray, or only one of the views has alpha
+pub fn lalphabeta_to_view(
+    src: &ImageRef<f32>,
+    dst: &mut ImageMut<u8>,
+    transfer_function: TransferFunction,
+) {
+    let layout = dst.layout();
+    check_same_alpha(layout, src.layout());
+    convert_views(
+        src,
+        dst,
+        |src, src_stride, dst, dst_stride, width, height| {
+            with_view_layout!(
+                layout,
+                lalphabeta_to_image,
+                [],
+                (
+                    src,
+                    src_stride,
+                    dst,
+                    dst_stride,
+                    width,
+                    height,
+                    transfer_function
+                )
+            )
+        },
+    );
+}
+
+/// Linear and gamma views keep channel order, so both views must have the same layout
+fn check_same_layout(src_layout: ImageConfiguration, dst_layout: ImageConfiguration) {
+    if src_layout != dst_layout {
+        panic!(
+            "Views of layouts {:?} and {:?} must have the same layout",
+            src_layout, dst_layout
+        );
+    }
+}
+
+/// Dispatches linear and gamma kernels, alpha is converted whenever layout has it.
+/// These kernels are scalar, so gray layouts run them with the single channel in place of R, G and B
+macro_rules! with_linear_layout {
+    ($layout:expr, $func:ident, ($($args:expr),* $(,)?)) => {
+        match $layout {
+            ImageConfiguration::Gray => {
+                $func::<{ ImageConfiguration::Gray as u8 }, false>($($args),*)
+            }
+            ImageConfiguration::GrayAlpha => {
+                $func::<{ ImageConfiguration::GrayAlpha as u8 }, true>($($args),*)
+            }
+            _ if $layout.has_alpha() => with_view_layout!($layout, $func, [true], ($($args),*)),
+            _ => with_view_layout!($layout, $func, [false], ($($args),*)),
+        }
+    };
+}
+
+/// This function converts image view to linear colorspace. Both views have the same layout,
+/// alpha is normalized when the layout has it
+///
+/// # Arguments
+/// * `src` - Source view of RGB data in any colour layout
+/// * `dst` - Destination view to receive linear data with the layout of the source
+/// * `transfer_function` - Transfer function into linear colorspace
+///
+/// # Panics
+/// Panics if views have different dimensions or layouts
+pub fn view_to_linear(
+    src: &ImageRef<u8>,
+    dst: &mut ImageMut<f32>,
+    transfer_function: TransferFunction,
+) {
+    let layout = src.layout();
+    check_same_layout(layout, dst.layout());
+    convert_views(
+        src,
+        dst,
+        |src, src_stride, dst, dst_stride, width, height| {
+            with_linear_layout!(
+                layout,
+                channels_to_linear,
+                (
+                    src,
+                    src_stride,
+                    dst,
+                    dst_stride,
+                    width,
+                    height,
+                    transfer_function
+                )
+            )
+        },
+    );
+}
+
+/// This function converts linear view to gamma image view. Both views have the same layout,
+/// alpha is denormalized when the layout has it
+///
+/// # Arguments
+/// * `src` - Source view of linear data
+/// * `dst` - Destination view to receive gamma encoded data with the layout of the source
+/// * `transfer_function` - Transfer function into gamma colorspace
+///
+/// # Panics
+/// Panics if views have different dimensions or layouts
+pub fn linear_to_view(
+    src: &ImageRef<f32>,
+    dst: &mut ImageMut<u8>,
+    transfer_function: TransferFunction,
+) {
+    let layout = dst.layout();
+    check_same_layout(src.layout(), layout);
+    convert_views(
+        src,
+        dst,
+        |src, src_stride, dst, dst_stride, width, height| {
+            with_linear_layout!(
+                layout,
+                linear_to_gamma_channels,
+                (
+                    src,
+                    src_stride,
+                    dst,
+                    dst_stride,
+                    width,
+                    height,
+                    transfer_function
+                )
+            )
+        },
+    );
+}
+
+/// This function converts image view to linear colorspace with 8 bit storage. Both views have the same layout,
+/// alpha is copied when the layout has it
+///
+/// # Arguments
+/// * `src` - Source view of RGB data in any colour layout
+/// * `dst` - Destination view to receive linear data with the layout of the source
+/// * `transfer_function` - Transfer function into linear colorspace
+///
+/// # Panics
+/// Panics if views have different dimensions or layouts
+pub fn view_to_linear_u8(
+    src: &ImageRef<u8>,
+    dst: &mut ImageMut<u8>,
+    transfer_function: TransferFunction,
+) {
+    let layout = src.layout();
+    check_same_layout(layout, dst.layout());
+    convert_views(
+        src,
+        dst,
+        |src, src_stride, dst, dst_stride, width, height| {
+            with_linear_layout!(
+                layout,
+                channels_to_linear_u8,
+                (
+                    src,
+                    src_stride,
+                    dst,
+                    dst_stride,
+                    width,
+                    height,
+                    transfer_function
+                )
+            )
+        },
+    );
+}
+
+/// This function converts linear view with 8 bit storage to gamma image view. Both views have the same layout,
+/// alpha is copied when the layout has it
+///
+/// # Arguments
+/// * `src` - Source view of linear data
+/// * `dst` - Destination view to receive gamma encoded data with the layout of the source
+/// * `transfer_function` - Transfer function into gamma colorspace
+///
+/// # Panics
+/// Panics if views have different dimensions or layouts
+pub fn linear_u8_to_view(
+    src: &ImageRef<u8>,
+    dst: &mut ImageMut<u8>,
+    transfer_function: TransferFunction,
+) {
+    let layout = dst.layout();
+    check_same_layout(src.layout(), layout);
+    convert_views(
+        src,
+        dst,
+        |src, src_stride, dst, dst_stride, width, height| {
+            with_linear_layout!(
+                layout,
+                linear_u8_to_gamma_channels,
+                (
+                    src,
+                    src_stride,
+                    dst,
+                    dst_stride,
+                    width,
+                    height,
+                    transfer_function
+                )
+            )
+        },
+    );
+}
+
+/// This function converts image view to HSV. Layout of the source view selects channel order,
+/// alpha is copied and leaved unchanged when the views have it
+///
+/// # Arguments
+/// * `src` - Source view of RGB data in any colour layout
+/// * `dst` - Destination view to receive HSV data, [ImageConfiguration::Rgba] layout if source has alpha,
+///   [ImageConfiguration::Rgb] otherwise
+/// * `scale` - Natural range for S and V is \[0,1\] it may be more convenient and required for u16 transformation to scale it by 100 or any other number to keep S,V in range [0, scale]
+///
+/// # Panics
+/// Panics if views have different dimensions, source view is gray, or only one of the views has alpha
+pub fn view_to_hsv(src: &ImageRef<u8>, dst: &mut ImageMut<u16>, scale: f32) {
+    let layout = src.layout();
+    let with_alpha = check_same_alpha(layout, dst.layout());
+    convert_views(
+        src,
+        dst,
+        |src, src_stride, dst, dst_stride, width, height| {
+            if with_alpha {
+                with_view_layout!(
+                    layout,
+                    channels_to_hsv_u16,
+                    [true, { HsvTarget::Hsv as u8 }],
+                    (src, src_stride, dst, dst_stride, width, height, scale)
+                )
+            } else {
+                with_view_layout!(
+                    layout,
+                    channels_to_hsv_u16,
+                    [false, { HsvTarget::Hsv as u8 }],
+                    (src, src_stride, dst, dst_stride, width, height, scale)
+                )
+            }
+        },
+    );
+}
+
+/// This function converts HSV view to image view. Layout of the destination view selects channel order,
+/// alpha is copied and leaved unchanged when the views have it
+///
+/// # Arguments
+/// * `src` - Source view of HSV data, [ImageConfiguration::Rgba] layout if destination has alpha,
+///   [ImageConfiguration::Rgb] otherwise
+/// * `dst` - Destination view to receive RGB data in any colour layout
+/// * `scale` - Natural range for S and V is \[0,1\] it may be more convenient and required for u16 transformation to scale it by 100 or any other number to keep S,V in range [0, scale]
+///
+/// # Panics
+/// Panics if views have different dimensions, destination view is gray, or only one of the views has alpha
+pub fn hsv_to_view(src: &ImageRef<u16>, dst: &mut ImageMut<u8>, scale: f32) {
+    let layout = dst.layout();
+    let with_alpha = check_same_alpha(layout, src.layout());
+    convert_views(
+        src,
+        dst,
+        |src, src_stride, dst, dst_stride, width, height| {
+            if with_alpha {
+                with_view_layout!(
+                    layout,
+                    hsv_u16_to_channels,
+                    [true, { HsvTarget::Hsv as u8 }],
+                    (src, src_stride, dst, dst_stride, width, height, scale)
+                )
+            } else {
+                with_view_layout!(
+                    layout,
+                    hsv_u16_to_channels,
+                    [false, { HsvTarget::Hsv as u8 }],
+                    (src, src_stride, dst, dst_stride, width, height, scale)
+                )
+            }
+        },
+    );
+}
+
+/// This function converts image view to Sigmoidal. Layout of the source view selects channel order,
+/// alpha is normalized and kept when the views have it
+///
+/// # Arguments
+/// * `src` - Source view of RGB data in any colour layout
+/// * `dst` - Destination view to receive Sigmoidal data, [ImageConfiguration::Rgba] layout if source has alpha,
+///   [ImageConfiguration::Rgb] otherwise
+///
+/// # Panics
+/// Panics if views have different dimensions, source view is gray, or only one of the views has alpha
+pub fn view_to_sigmoidal(src: &ImageRef<u8>, dst: &mut ImageMut<f32>) {
+    let layout = src.layout();
+    let with_alpha = check_same_alpha(layout, dst.layout());
+    convert_views(
+        src,
+        dst,
+        |src, src_stride, dst, dst_stride, width, height| {
+            if with_alpha {
+                with_view_layout!(
+                    layout,
+                    image_to_sigmoidal,
+                    [true],
+                    (src, src_stride, dst, dst_stride, width, height)
+                )
+            } else {
+                with_view_layout!(
+                    layout,
+                    image_to_sigmoidal,
+                    [false],
+                    (src, src_stride, dst, dst_stride, width, height)
+                )
+            }
+        },
+    );
+}
+
+/// This function converts Sigmoidal view to image view. Layout of the destination view selects channel order,
+/// alpha is denormalized and kept when the views have it
+///
+/// # Arguments
+/// * `src` - Source view of Sigmoidal data, [ImageConfiguration::Rgba] layout if destination has alpha,
+///   [ImageConfiguration::Rgb] otherwise
+/// * `dst` - Destination view to receive RGB data in any colour layout
+///
+/// # Panics
+/// Panics if views have different dimensions, destination view is gray, or only one of the views has alpha
+pub fn sigmoidal_to_view(src: &ImageRef<f32>, dst: &mut ImageMut<u8>) {
+    let layout = dst.layout();
+    let with_alpha = check_same_alpha(layout, src.layout());
+    convert_views(
+        src,
+        dst,
+        |src, src_stride, dst, dst_stride, width, height| {
+            if with_alpha {
+                with_view_layout!(
+                    layout,
+                    sigmoidal_to_image,
+                    [true],
+                    (src, src_stride, dst, dst_stride, width, height)
+                )
+            } else {
+                with_view_layout!(
+                    layout,
+                    sigmoidal_to_image,
+                    [false],
+                    (src, src_stride, dst, dst_stride, width, height)
+                )
+            }
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bgra_to_jzazbz, bgra_to_jzczhz, bgra_to_lalphabeta, bgra_to_linear, bgra_to_linear_u8,
+        bgra_to_oklch, gray_alpha_to_lab, gray_alpha_to_linear, gray_to_lab, jzazbz_to_bgra,
+        jzczhz_to_bgra, lab_to_gray_alpha, lalphabeta_to_bgra, lch_to_rgb, linear_to_bgra,
+        linear_u8_to_bgra, oklch_to_bgra, rgb_to_lch, ImageRegion, ImageSample, SRGB_TO_XYZ_D65,
+        XYZ_TO_SRGB_D65,
+    };
+
+    const WIDTH: u32 = 13;
+    const HEIGHT: u32 = 5;
+    /// Views are placed at (2, 1) of images padded by this count of pixels on both axes
+    const PADDING: u32 = 3;
+
+    fn rgb_samples(channels: usize) -> Vec<u8> {
+        (0..WIDTH as usize * HEIGHT as usize * channels)
+            .map(|i| (i * 37 % 251) as u8)
+            .collect()
+    }
+
+    /// Runs `view` over sub-views of padded images and `slice` over tight images,
+    /// then checks that both produced the same samples
+    fn assert_view_matches_slice<S: ImageSample + Default, D: ImageSample + Default + PartialEq>(
+        src: &[S],
+        src_layout: ImageConfiguration,
+        dst_layout: ImageConfiguration,
+        view: impl Fn(&ImageRef<S>, &mut ImageMut<D>),
+        slice: impl Fn(&[S], u32, &mut [D], u32, u32, u32),
+    ) {
+        let src_channels = src_layout.get_channels_count();
+        let dst_channels = dst_layout.get_channels_count();
+        let padded_width = WIDTH + PADDING;
+        let padded_height = HEIGHT + PADDING;
+
+        let mut padded_src =
+            vec![S::default(); (padded_width * padded_height) as usize * src_channels];
+        let src_stride = padded_width as usize * src_channels;
+        for (y, row) in src.chunks_exact(WIDTH as usize * src_channels).enumerate() {
+            let start = (y + 1) * src_stride + 2 * src_channels;
+            padded_src[start..start + row.len()].copy_from_slice(row);
+        }
+        let src_view = ImageRef::new(&padded_src, padded_width, padded_height, src_layout)
+            .unwrap()
+            .sub_view(ImageRegion::new(2, 1, WIDTH, HEIGHT))
+            .unwrap();
+        let mut padded_dst =
+            vec![D::default(); (padded_width * padded_height) as usize * dst_channels];
+        let mut dst_image =
+            ImageMut::new(&mut padded_dst, padded_width, padded_height, dst_layout).unwrap();
+        let mut dst_view = dst_image
+            .sub_view_mut(ImageRegion::new(2, 1, WIDTH, HEIGHT))
+            .unwrap();
+        view(&src_view, &mut dst_view);
+
+        let dst_row = WIDTH as usize * dst_channels;
+        let mut expected = vec![D::default(); dst_row * HEIGHT as usize];
+        slice(
+            src,
+            (WIDTH as usize * src_channels * std::mem::size_of::<S>()) as u32,
+            &mut expected,
+            (dst_row * std::mem::size_of::<D>()) as u32,
+            WIDTH,
+            HEIGHT,
+        );
+
+        let dst_stride = padded_width as usize * dst_channels;
+        for (y, expected_row) in expected.chunks_exact(dst_row).enumerate() {
+            let start = (y + 1) * dst_stride + 2 * dst_channels;
+            assert!(
+                padded_dst[start..start + dst_row] == *expected_row,
+                "row {} differs",
+                y
+            );
+        }
+    }
+
+    /// Converts tight BGRA samples with slice converter into model samples with alpha
+    fn bgra_to_model(convert: impl Fn(&[u8], u32, &mut [f32], u32, u32, u32)) -> Vec<f32> {
+        let bgra = rgb_samples(4);
+        let mut model = vec![0f32; bgra.len()];
+        convert(&bgra, WIDTH * 4, &mut model, WIDTH * 4 * 4, WIDTH, HEIGHT);
+        model
+    }
+
+    #[test]
+    fn jzazbz_views_match_slice_converters() {
+        let tf = TransferFunction::Srgb;
+        assert_view_matches_slice(
+            &rgb_samples(4),
+            ImageConfiguration::Bgra,
+            ImageConfiguration::Rgba,
+            |src, dst| view_to_jzazbz(src, dst, 200., tf),
+            |s, ss, d, ds, w, h| bgra_to_jzazbz(s, ss, d, ds, w, h, 200., tf),
+        );
+        assert_view_matches_slice(
+            &bgra_to_model(|s, ss, d, ds, w, h| bgra_to_jzazbz(s, ss, d, ds, w, h, 200., tf)),
+            ImageConfiguration::Rgba,
+            ImageConfiguration::Bgra,
+            |src, dst| jzazbz_to_view(src, dst, 200., tf),
+            |s, ss, d, ds, w, h| jzazbz_to_bgra(s, ss, d, ds, w, h, 200., tf),
+        );
+        assert_view_matches_slice(
+            &rgb_samples(4),
+            ImageConfiguration::Bgra,
+            ImageConfiguration::Rgba,
+            |src, dst| view_to_jzczhz(src, dst, 200., tf),
+            |s, ss, d, ds, w, h| bgra_to_jzczhz(s, ss, d, ds, w, h, 200., tf),
+        );
+        assert_view_matches_slice(
+            &bgra_to_model(|s, ss, d, ds, w, h| bgra_to_jzczhz(s, ss, d, ds, w, h, 200., tf)),
+            ImageConfiguration::Rgba,
+            ImageConfiguration::Bgra,
+            |src, dst| jzczhz_to_view(src, dst, 200., tf),
+            |s, ss, d, ds, w, h| jzczhz_to_bgra(s, ss, d, ds, w, h, 200., tf),
+        );
+    }
+
+    #[test]
+    fn lalphabeta_and_oklch_views_match_slice_converters() {
+        let tf = TransferFunction::Srgb;
+        assert_view_matches_slice(
+            &rgb_samples(4),
+            ImageConfiguration::Bgra,
+            ImageConfiguration::Rgba,
+            |src, dst| view_to_lalphabeta(src, dst, tf),
+            |s, ss, d, ds, w, h| bgra_to_lalphabeta(s, ss, d, ds, w, h, tf),
+        );
+        assert_view_matches_slice(
+            &bgra_to_model(|s, ss, d, ds, w, h| bgra_to_lalphabeta(s, ss, d, ds, w, h, tf)),
+            ImageConfiguration::Rgba,
+            ImageConfiguration::Bgra,
+            |src, dst| lalphabeta_to_view(src, dst, tf),
+            |s, ss, d, ds, w, h| lalphabeta_to_bgra(s, ss, d, ds, w, h, tf),
+        );
+        assert_view_matches_slice(
+            &rgb_samples(4),
+            ImageConfiguration::Bgra,
+            ImageConfiguration::Rgba,
+            |src, dst| view_to_oklch(src, dst, tf),
+            |s, ss, d, ds, w, h| bgra_to_oklch(s, ss, d, ds, w, h, tf),
+        );
+        assert_view_matches_slice(
+            &bgra_to_model(|s, ss, d, ds, w, h| bgra_to_oklch(s, ss, d, ds, w, h, tf)),
+            ImageConfiguration::Rgba,
+            ImageConfiguration::Bgra,
+            |src, dst| oklch_to_view(src, dst, tf),
+            |s, ss, d, ds, w, h| oklch_to_bgra(s, ss, d, ds, w, h, tf),
+        );
+    }
+
+    #[test]
+    fn lch_views_match_slice_converters() {
+        let tf = TransferFunction::Srgb;
+        let rgb = rgb_samples(3);
+        assert_view_matches_slice(
+            &rgb,
+            ImageConfiguration::Rgb,
+            ImageConfiguration::Rgb,
+            |src, dst| view_to_lch(src, dst, &SRGB_TO_XYZ_D65, tf),
+            |s, ss, d, ds, w, h| rgb_to_lch(s, ss, d, ds, w, h, &SRGB_TO_XYZ_D65, tf),
+        );
+        let mut lch = vec![0f32; rgb.len()];
+        rgb_to_lch(
+            &rgb,
+            WIDTH * 3,
+            &mut lch,
+            WIDTH * 3 * 4,
+            WIDTH,
+            HEIGHT,
+            &SRGB_TO_XYZ_D65,
+            tf,
+        );
+        assert_view_matches_slice(
+            &lch,
+            ImageConfiguration::Rgb,
+            ImageConfiguration::Rgb,
+            |src, dst| lch_to_view(src, dst, &XYZ_TO_SRGB_D65, tf),
+            |s, ss, d, ds, w, h| lch_to_rgb(s, ss, d, ds, w, h, &XYZ_TO_SRGB_D65, tf),
+        );
+    }
+
+    #[test]
+    fn linear_views_match_slice_converters() {
+        let tf = TransferFunction::Srgb;
+        assert_view_matches_slice(
+            &rgb_samples(4),
+            ImageConfiguration::Bgra,
+            ImageConfiguration::Bgra,
+            |src, dst| view_to_linear(src, dst, tf),
+            |s, ss, d, ds, w, h| bgra_to_linear(s, ss, d, ds, w, h, tf),
+        );
+        assert_view_matches_slice(
+            &bgra_to_model(|s, ss, d, ds, w, h| bgra_to_linear(s, ss, d, ds, w, h, tf)),
+            ImageConfiguration::Bgra,
+            ImageConfiguration::Bgra,
+            |src, dst| linear_to_view(src, dst, tf),
+            |s, ss, d, ds, w, h| linear_to_bgra(s, ss, d, ds, w, h, tf),
+        );
+        assert_view_matches_slice(
+            &rgb_samples(4),
+            ImageConfiguration::Bgra,
+            ImageConfiguration::Bgra,
+            |src, dst| view_to_linear_u8(src, dst, tf),
+            |s, ss, d, ds, w, h| bgra_to_linear_u8(s, ss, d, ds, w, h, tf),
+        );
+        assert_view_matches_slice(
+            &rgb_samples(4),
+            ImageConfiguration::Bgra,
+            ImageConfiguration::Bgra,
+            |src, dst| linear_u8_to_view(src, dst, tf),
+            |s, ss, d, ds, w, h| linear_u8_to_bgra(s, ss, d, ds, w, h, tf),
+        );
+    }
+
+    #[test]
+    fn gray_views_match_slice_converters() {
+        let tf = TransferFunction::Srgb;
+        assert_view_matches_slice(
+            &rgb_samples(2),
+            ImageConfiguration::GrayAlpha,
+            ImageConfiguration::GrayAlpha,
+            |src, dst| view_to_linear(src, dst, tf),
+            |s, ss, d, ds, w, h| gray_alpha_to_linear(s, ss, d, ds, w, h, tf),
+        );
+        assert_view_matches_slice(
+            &rgb_samples(1),
+            ImageConfiguration::Gray,
+            ImageConfiguration::Gray,
+            |src, dst| view_to_lab(src, dst, &SRGB_TO_XYZ_D65, tf),
+            |s, ss, d, ds, w, h| gray_to_lab(s, ss, d, ds, w, h, tf),
+        );
+        let mut lab = vec![0f32; WIDTH as usize * HEIGHT as usize * 2];
+        gray_alpha_to_lab(
+            &rgb_samples(2),
+            WIDTH * 2,
+            &mut lab,
+            WIDTH * 8,
+            WIDTH,
+            HEIGHT,
+            tf,
+        );
+        assert_view_matches_slice(
+            &lab,
+            ImageConfiguration::GrayAlpha,
+            ImageConfiguration::GrayAlpha,
+            |src, dst| lab_to_view(src, dst, &XYZ_TO_SRGB_D65, tf),
+            |s, ss, d, ds, w, h| lab_to_gray_alpha(s, ss, d, ds, w, h, tf),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "must have the same layout")]
+    fn linear_view_rejects_different_layouts() {
+        let src = vec![0u8; 4 * 4];
+        let mut dst = vec![0f32; 4 * 4];
+        let src = ImageRef::new(&src, 2, 2, ImageConfiguration::Bgra).unwrap();
+        let mut dst = ImageMut::new(&mut dst, 2, 2, ImageConfiguration::Rgba).unwrap();
+        view_to_linear(&src, &mut dst, TransferFunction::Srgb);
+    }
+
+    #[test]
+    fn sub_view_converts_only_region() {
+        let (width, height) = (8u32, 6u32);
+        let rgba = vec![200u8; (width * height * 4) as usize];
+        let mut oklab = vec![-1f32; (width * height * 4) as usize];
+        let src = ImageRef::new(&rgba, width, height, ImageConfiguration::Rgba).unwrap();
+        let mut dst = ImageMut::new(&mut oklab, width, height, ImageConfiguration::Rgba).unwrap();
+        let rows = ImageRegion::rows(2..4, width);
+        view_to_oklab(
+            &src.sub_view(rows).unwrap(),
+            &mut dst.sub_view_mut(rows).unwrap(),
+            TransferFunction::Srgb,
+        );
+        for (y, row) in oklab.chunks_exact((width * 4) as usize).enumerate() {
+            let converted = row.iter().all(|&v| v != -1.);
+            assert_eq!(converted, (2..4).contains(&y), "row {}", y);
+        }
+
+        assert!(src.sub_view(ImageRegion::new(4, 0, 5, 1)).is_err());
+        assert!(src.sub_view(ImageRegion::rows(5..7, width)).is_err());
+    }
+}
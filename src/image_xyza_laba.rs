@@ -5,39 +5,29 @@
  * // license that can be found in the LICENSE file.
  */
 
+use crate::gamma_curves::linearization_table_u8;
 use crate::image::ImageConfiguration;
 #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
 use crate::neon::neon_channels_to_xyza_or_laba;
+use crate::premultiply::linearize_premultiplied_row_u8;
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::sse::sse_channels_to_xyza_laba;
+use crate::threading::{current_threading, ForEachRow, ThreadingPolicy};
 use crate::xyz_target::XyzTarget;
 use crate::{LCh, Lab, Luv, Rgb, TransferFunction, Xyz};
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 use std::slice;
 
-#[allow(clippy::type_complexity)]
-fn channels_to_xyz_with_alpha<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
-    src: &[u8],
-    src_stride: u32,
-    dst: &mut [f32],
-    dst_stride: u32,
-    width: u32,
-    height: u32,
-    matrix: &[[f32; 3]; 3],
-    transfer_function: TransferFunction,
-) {
-    let target: XyzTarget = TARGET.into();
-    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
-    if !image_configuration.has_alpha() {
-        panic!("Alpha may be set only on images with alpha");
-    }
+pub(crate) type ChannelsToXyzaRowHandler =
+    unsafe fn(usize, *const f32, usize, u32, *mut f32, usize, &[[f32; 3]; 3]) -> usize;
 
-    let mut _wide_row_handler: Option<
-        unsafe fn(usize, *const f32, usize, u32, *mut f32, usize, &[[f32; 3]; 3]) -> usize,
-    > = None;
+/// Selects the widest available SIMD row handler for the layout
+pub(crate) fn channels_to_xyza_row_handler<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+) -> Option<ChannelsToXyzaRowHandler> {
+    let mut _wide_row_handler: Option<ChannelsToXyzaRowHandler> = None;
 
     #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
     {
@@ -51,13 +41,69 @@ fn channels_to_xyz_with_alpha<const CHANNELS_CONFIGURATION: u8, const TARGET: u8
         _wide_row_handler = Some(neon_channels_to_xyza_or_laba::<CHANNELS_CONFIGURATION, TARGET>);
     }
 
-    let channels = image_configuration.get_channels_count();
+    _wide_row_handler
+}
+
+pub(crate) fn channels_to_xyz_with_alpha<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+    premultiplied: bool,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let unpremultiply = if premultiplied && image_configuration.has_alpha() {
+        Some(transfer_function)
+    } else {
+        None
+    };
 
-    let mut lut_table = vec![0f32; 256];
-    for (i, lut) in lut_table.iter_mut().enumerate() {
-        *lut = transfer_function.linearize(i as f32 * (1. / 255.0));
+    channels_to_xyz_with_alpha_impl::<CHANNELS_CONFIGURATION, TARGET>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        &linearization_table_u8(transfer_function),
+        unpremultiply,
+        channels_to_xyza_row_handler::<CHANNELS_CONFIGURATION, TARGET>(),
+        &current_threading(),
+    );
+}
+
+/// Converts image with precomputed linearization table and row handler.
+/// When `unpremultiply` is set source alpha is premultiplied, colours are divided by alpha
+/// in transient row and linearized with that transfer function
+pub(crate) fn channels_to_xyz_with_alpha_impl<
+    const CHANNELS_CONFIGURATION: u8,
+    const TARGET: u8,
+>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    lut_table: &[f32],
+    unpremultiply: Option<TransferFunction>,
+    _wide_row_handler: Option<ChannelsToXyzaRowHandler>,
+    policy: &ThreadingPolicy,
+) {
+    let target: XyzTarget = TARGET.into();
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    if !image_configuration.has_alpha() {
+        panic!("Alpha may be set only on images with alpha");
     }
 
+    let channels = image_configuration.get_channels_count();
+
     let dst_slice_safe_align = unsafe {
         slice::from_raw_parts_mut(
             dst.as_mut_ptr() as *mut u8,
@@ -80,23 +126,32 @@ fn channels_to_xyz_with_alpha<const CHANNELS_CONFIGURATION: u8, const TARGET: u8
             .zip(src.chunks_exact(src_stride as usize));
     }
 
-    iter.for_each(|(dst, src)| unsafe {
+    iter.for_each_row_with(policy, width as usize, |(dst, src)| unsafe {
         let mut _cx = 0usize;
 
         let mut transient_row = vec![0f32; width as usize * channels];
 
-        for (dst_chunk, src_chunks) in transient_row
-            .chunks_exact_mut(channels)
-            .zip(src.chunks_exact(channels))
-        {
-            dst_chunk[image_configuration.get_r_channel_offset()] = *lut_table
-                .get_unchecked(src_chunks[image_configuration.get_r_channel_offset()] as usize);
-            dst_chunk[image_configuration.get_g_channel_offset()] = *lut_table
-                .get_unchecked(src_chunks[image_configuration.get_g_channel_offset()] as usize);
-            dst_chunk[image_configuration.get_b_channel_offset()] = *lut_table
-                .get_unchecked(src_chunks[image_configuration.get_b_channel_offset()] as usize);
-            dst_chunk[image_configuration.get_a_channel_offset()] =
-                src_chunks[image_configuration.get_a_channel_offset()] as f32 * (1. / 255.0);
+        if let Some(transfer_function) = unpremultiply {
+            linearize_premultiplied_row_u8::<CHANNELS_CONFIGURATION>(
+                src,
+                &mut transient_row,
+                lut_table,
+                transfer_function,
+            );
+        } else {
+            for (dst_chunk, src_chunks) in transient_row
+                .chunks_exact_mut(channels)
+                .zip(src.chunks_exact(channels))
+            {
+                dst_chunk[image_configuration.get_r_channel_offset()] = *lut_table
+                    .get_unchecked(src_chunks[image_configuration.get_r_channel_offset()] as usize);
+                dst_chunk[image_configuration.get_g_channel_offset()] = *lut_table
+                    .get_unchecked(src_chunks[image_configuration.get_g_channel_offset()] as usize);
+                dst_chunk[image_configuration.get_b_channel_offset()] = *lut_table
+                    .get_unchecked(src_chunks[image_configuration.get_b_channel_offset()] as usize);
+                dst_chunk[image_configuration.get_a_channel_offset()] =
+                    src_chunks[image_configuration.get_a_channel_offset()] as f32 * (1. / 255.0);
+            }
         }
 
         if let Some(dispatcher) = _wide_row_handler {
@@ -194,6 +249,124 @@ pub fn rgba_to_lab_with_alpha(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ARGB to CIE L*ab.
+///
+/// This function converts ARGB to CIE L*ab against D65 white point and preserving
+/// and normalizing alpha channels keeping it at last positions.
+/// This is much more effective than naive direct transformation
+///
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn argb_to_lab_with_alpha(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz_with_alpha::<{ ImageConfiguration::Argb as u8 }, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
+    );
+}
+
+/// This function converts RGBA to CIE L*ab. Colour channels of the source are premultiplied by alpha and are unpremultiplied before conversion
+///
+/// This function converts RGBA to CIE L*ab against D65 white point and preserving
+/// and normalizing alpha channels keeping it at last positions.
+/// This is much more effective than naive direct transformation
+///
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn rgba_to_lab_with_alpha_premultiplied(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz_with_alpha::<{ ImageConfiguration::Rgba as u8 }, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        true,
+    );
+}
+
+/// This function converts ARGB to CIE L*ab. Colour channels of the source are premultiplied by alpha and are unpremultiplied before conversion
+///
+/// This function converts ARGB to CIE L*ab against D65 white point and preserving
+/// and normalizing alpha channels keeping it at last positions.
+/// This is much more effective than naive direct transformation
+///
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn argb_to_lab_with_alpha_premultiplied(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz_with_alpha::<{ ImageConfiguration::Argb as u8 }, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        true,
     );
 }
 
@@ -231,6 +404,121 @@ pub fn bgra_to_lab_with_alpha(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ABGR to CIE L*ab.
+///
+/// This function converts ABGR to CIE L*ab against D65 white point
+/// and preserving and normalizing alpha channels keeping it at last positions.
+/// This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn abgr_to_lab_with_alpha(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz_with_alpha::<{ ImageConfiguration::Abgr as u8 }, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
+    );
+}
+
+/// This function converts BGRA to CIE L*ab. Colour channels of the source are premultiplied by alpha and are unpremultiplied before conversion
+///
+/// This function converts BGRA to CIE L*ab against D65 white point
+/// and preserving and normalizing alpha channels keeping it at last positions.
+/// This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn bgra_to_lab_with_alpha_premultiplied(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz_with_alpha::<{ ImageConfiguration::Bgra as u8 }, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        true,
+    );
+}
+
+/// This function converts ABGR to CIE L*ab. Colour channels of the source are premultiplied by alpha and are unpremultiplied before conversion
+///
+/// This function converts ABGR to CIE L*ab against D65 white point
+/// and preserving and normalizing alpha channels keeping it at last positions.
+/// This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn abgr_to_lab_with_alpha_premultiplied(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz_with_alpha::<{ ImageConfiguration::Abgr as u8 }, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        true,
     );
 }
 
@@ -268,6 +556,45 @@ pub fn rgba_to_luv_with_alpha(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ARGB to CIE L*uv.
+///
+/// This function converts ARGB to CIE L*uv against D65 white point and preserving
+/// and normalizing alpha channels keeping it at last positions.
+/// This is much more effective than naive direct transformation.
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn argb_to_luv_with_alpha(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz_with_alpha::<{ ImageConfiguration::Argb as u8 }, { XyzTarget::Luv as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
     );
 }
 
@@ -305,6 +632,45 @@ pub fn bgra_to_luv_with_alpha(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ABGR to CIE L*uv.
+///
+/// This function converts ABGR to CIE L*uv against D65 white point
+/// and preserving and normalizing alpha channels keeping it at last positions.
+/// This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LAB(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn abgr_to_luv_with_alpha(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz_with_alpha::<{ ImageConfiguration::Abgr as u8 }, { XyzTarget::Luv as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
     );
 }
 
@@ -338,6 +704,41 @@ pub fn rgba_to_xyz_with_alpha(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ARGB to CIE XYZ against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive XYZ(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn argb_to_xyz_with_alpha(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz_with_alpha::<{ ImageConfiguration::Argb as u8 }, { XyzTarget::Xyz as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
     );
 }
 
@@ -371,6 +772,41 @@ pub fn bgra_to_xyz_with_alpha(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ABGR to CIE XYZ against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive XYZ data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn abgr_to_xyz_with_alpha(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz_with_alpha::<{ ImageConfiguration::Abgr as u8 }, { XyzTarget::Xyz as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
     );
 }
 
@@ -404,6 +840,41 @@ pub fn rgba_to_lch_with_alpha(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ARGB to CIE LCH against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LCH(a) data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn argb_to_lch_with_alpha(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz_with_alpha::<{ ImageConfiguration::Argb as u8 }, { XyzTarget::Lch as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
     );
 }
 
@@ -437,5 +908,161 @@ pub fn bgra_to_lch_with_alpha(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts ABGR to CIE LCH against D65 white point and preserving and normalizing alpha channels keeping it at last positions. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `dst` - A mutable slice to receive LCH data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn abgr_to_lch_with_alpha(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    channels_to_xyz_with_alpha::<{ ImageConfiguration::Abgr as u8 }, { XyzTarget::Lch as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lab_with_alpha_to_abgr, lab_with_alpha_to_abgr_premultiplied, lab_with_alpha_to_argb,
+        lab_with_alpha_to_bgra, lab_with_alpha_to_bgra_premultiplied, lab_with_alpha_to_rgba,
+        lch_with_alpha_to_argb, lch_with_alpha_to_rgba, luv_with_alpha_to_argb,
+        luv_with_alpha_to_rgba, xyz_with_alpha_to_abgr, xyz_with_alpha_to_bgra, SRGB_TO_XYZ_D65,
+        XYZ_TO_SRGB_D65,
+    };
+
+    /// Moves alpha of every four channel pixel from the last position to the first
+    fn alpha_to_front<T: Copy>(pixels: &[T]) -> Vec<T> {
+        pixels
+            .chunks_exact(4)
+            .flat_map(|px| [px[3], px[0], px[1], px[2]])
+            .collect()
+    }
+
+    #[test]
+    fn alpha_first_layouts_match_alpha_last_ones() {
+        // Width isn't a multiple of any vector length, so SIMD and scalar tails both run
+        let (width, height) = (67u32, 3u32);
+        let rgba: Vec<u8> = (0..width * height * 4)
+            .map(|i| ((i * 97 + 13) % 256) as u8)
+            .collect();
+        let argb = alpha_to_front(&rgba);
+        let stride = width * 4 * std::mem::size_of::<f32>() as u32;
+        type Forward = fn(&[u8], u32, &mut [f32], u32, u32, u32, &[[f32; 3]; 3], TransferFunction);
+        type Inverse = fn(&[f32], u32, &mut [u8], u32, u32, u32, &[[f32; 3]; 3], TransferFunction);
+        let converters: [(Forward, Forward, Inverse, Inverse); 6] = [
+            (
+                rgba_to_lab_with_alpha,
+                argb_to_lab_with_alpha,
+                lab_with_alpha_to_rgba,
+                lab_with_alpha_to_argb,
+            ),
+            (
+                bgra_to_lab_with_alpha,
+                abgr_to_lab_with_alpha,
+                lab_with_alpha_to_bgra,
+                lab_with_alpha_to_abgr,
+            ),
+            (
+                bgra_to_lab_with_alpha_premultiplied,
+                abgr_to_lab_with_alpha_premultiplied,
+                lab_with_alpha_to_bgra_premultiplied,
+                lab_with_alpha_to_abgr_premultiplied,
+            ),
+            (
+                rgba_to_luv_with_alpha,
+                argb_to_luv_with_alpha,
+                luv_with_alpha_to_rgba,
+                luv_with_alpha_to_argb,
+            ),
+            (
+                bgra_to_xyz_with_alpha,
+                abgr_to_xyz_with_alpha,
+                xyz_with_alpha_to_bgra,
+                xyz_with_alpha_to_abgr,
+            ),
+            (
+                rgba_to_lch_with_alpha,
+                argb_to_lch_with_alpha,
+                lch_with_alpha_to_rgba,
+                lch_with_alpha_to_argb,
+            ),
+        ];
+
+        for (forward, forward_alpha_first, inverse, inverse_alpha_first) in converters {
+            let mut expected = vec![0f32; rgba.len()];
+            let mut actual = vec![0f32; rgba.len()];
+            forward(
+                &rgba,
+                width * 4,
+                &mut expected,
+                stride,
+                width,
+                height,
+                &SRGB_TO_XYZ_D65,
+                TransferFunction::Srgb,
+            );
+            forward_alpha_first(
+                &argb,
+                width * 4,
+                &mut actual,
+                stride,
+                width,
+                height,
+                &SRGB_TO_XYZ_D65,
+                TransferFunction::Srgb,
+            );
+            assert_eq!(actual, expected);
+
+            let mut expected_rgba = vec![0u8; rgba.len()];
+            let mut actual_argb = vec![0u8; rgba.len()];
+            inverse(
+                &expected,
+                stride,
+                &mut expected_rgba,
+                width * 4,
+                width,
+                height,
+                &XYZ_TO_SRGB_D65,
+                TransferFunction::Srgb,
+            );
+            inverse_alpha_first(
+                &actual,
+                stride,
+                &mut actual_argb,
+                width * 4,
+                width,
+                height,
+                &XYZ_TO_SRGB_D65,
+                TransferFunction::Srgb,
+            );
+            assert_eq!(actual_argb, alpha_to_front(&expected_rgba));
+        }
+    }
+}
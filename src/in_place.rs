@@ -0,0 +1,634 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::gamma_curves::{gamma_table_u8, linearization_table_u8, TransferFunction};
+use crate::image::ImageConfiguration;
+use crate::image_view::check_image;
+use crate::matrix::{matrix_mul, matrix_vec_mul};
+use crate::threading::ForEachRow;
+use crate::{ImageSample, Jzazbz, Jzczhz, Lab, Luv, Oklab, Oklch, Xyz};
+#[cfg(feature = "rayon")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "rayon")]
+use rayon::prelude::ParallelSliceMut;
+
+/// Runs `transform` over every pixel of interleaved image
+fn transform_in_place<T: Copy + Send + Sync>(
+    data: &mut [T],
+    stride: u32,
+    width: u32,
+    height: u32,
+    layout: ImageConfiguration,
+    transform: impl Fn(&mut [T]) + Send + Sync,
+) {
+    if width == 0 || height == 0 {
+        return;
+    }
+    let row_length = check_image::<T>(data.len(), stride, width, height, layout, "In-place");
+    let channels = layout.get_channels_count();
+    let row_samples = width as usize * channels;
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = data.par_chunks_mut(row_length).take(height as usize);
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = data.chunks_mut(row_length).take(height as usize);
+    }
+
+    iter.for_each_row(width as usize, |row| {
+        for pixel in row[..row_samples].chunks_exact_mut(channels) {
+            transform(pixel);
+        }
+    });
+}
+
+#[inline]
+fn transfer_f32_in_place(
+    data: &mut [f32],
+    stride: u32,
+    width: u32,
+    height: u32,
+    layout: ImageConfiguration,
+    transfer: impl Fn(f32) -> f32 + Send + Sync,
+) {
+    transform_in_place(data, stride, width, height, layout, |pixel| {
+        for v in pixel[..3].iter_mut() {
+            *v = transfer(*v);
+        }
+    });
+}
+
+fn transfer_u8_in_place(
+    data: &mut [u8],
+    stride: u32,
+    width: u32,
+    height: u32,
+    layout: ImageConfiguration,
+    lut_table: &[u8; 256],
+) {
+    transform_in_place(data, stride, width, height, layout, |pixel| {
+        for v in pixel[..3].iter_mut() {
+            *v = lut_table[*v as usize];
+        }
+    });
+}
+
+fn linearization_table_u8_to_u8(transfer_function: TransferFunction) -> [u8; 256] {
+    let mut lut_table = [0u8; 256];
+    for (i, lut) in lut_table.iter_mut().enumerate() {
+        *lut = (transfer_function.linearize(i as f32 * (1. / 255.0)) * 255.).min(255.) as u8;
+    }
+    lut_table
+}
+
+fn gamma_table_u8_to_u8(transfer_function: TransferFunction) -> [u8; 256] {
+    let mut lut_table = [0u8; 256];
+    for (i, lut) in lut_table.iter_mut().enumerate() {
+        *lut = (transfer_function.gamma(i as f32 * (1. / 255.0)) * 255.).min(255.) as u8;
+    }
+    lut_table
+}
+
+/// This function linearizes RGB f32 image in place, works for BGR as well
+///
+/// # Arguments
+/// * `data` - A mutable slice contains RGB data in range [0, 1]
+/// * `stride` - Bytes per row for data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn rgb_f32_to_linear_in_place(
+    data: &mut [f32],
+    stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    transfer_f32_in_place(data, stride, width, height, ImageConfiguration::Rgb, |v| {
+        transfer_function.linearize(v)
+    });
+}
+
+/// This function linearizes RGBA f32 image in place, alpha is kept as is, works for BGRA as well
+///
+/// # Arguments
+/// * `data` - A mutable slice contains RGBA data in range [0, 1]
+/// * `stride` - Bytes per row for data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn rgba_f32_to_linear_in_place(
+    data: &mut [f32],
+    stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    transfer_f32_in_place(data, stride, width, height, ImageConfiguration::Rgba, |v| {
+        transfer_function.linearize(v)
+    });
+}
+
+/// This function applies gamma to linear RGB f32 image in place, works for BGR as well
+///
+/// # Arguments
+/// * `data` - A mutable slice contains linear RGB data
+/// * `stride` - Bytes per row for data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear to gamma space. If you don't have specific pick `Srgb`
+pub fn linear_to_rgb_f32_in_place(
+    data: &mut [f32],
+    stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    transfer_f32_in_place(data, stride, width, height, ImageConfiguration::Rgb, |v| {
+        transfer_function.gamma(v)
+    });
+}
+
+/// This function applies gamma to linear RGBA f32 image in place, alpha is kept as is, works for BGRA as well
+///
+/// # Arguments
+/// * `data` - A mutable slice contains linear RGBA data
+/// * `stride` - Bytes per row for data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear to gamma space. If you don't have specific pick `Srgb`
+pub fn linear_to_rgba_f32_in_place(
+    data: &mut [f32],
+    stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    transfer_f32_in_place(data, stride, width, height, ImageConfiguration::Rgba, |v| {
+        transfer_function.gamma(v)
+    });
+}
+
+/// This function linearizes RGB u8 image in place, the same as [crate::rgb_to_linear_u8], works for BGR as well
+///
+/// # Arguments
+/// * `data` - A mutable slice contains RGB data
+/// * `stride` - Bytes per row for data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn rgb_to_linear_u8_in_place(
+    data: &mut [u8],
+    stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    let lut_table = linearization_table_u8_to_u8(transfer_function);
+    transfer_u8_in_place(
+        data,
+        stride,
+        width,
+        height,
+        ImageConfiguration::Rgb,
+        &lut_table,
+    );
+}
+
+/// This function linearizes RGBA u8 image in place, the same as [crate::rgba_to_linear_u8], works for BGRA as well
+///
+/// # Arguments
+/// * `data` - A mutable slice contains RGBA data
+/// * `stride` - Bytes per row for data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn rgba_to_linear_u8_in_place(
+    data: &mut [u8],
+    stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    let lut_table = linearization_table_u8_to_u8(transfer_function);
+    transfer_u8_in_place(
+        data,
+        stride,
+        width,
+        height,
+        ImageConfiguration::Rgba,
+        &lut_table,
+    );
+}
+
+/// This function applies gamma to linear RGB u8 image in place, the same as [crate::linear_u8_to_rgb], works for BGR as well
+///
+/// # Arguments
+/// * `data` - A mutable slice contains linear RGB data
+/// * `stride` - Bytes per row for data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear to gamma space. If you don't have specific pick `Srgb`
+pub fn linear_u8_to_rgb_in_place(
+    data: &mut [u8],
+    stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    let lut_table = gamma_table_u8_to_u8(transfer_function);
+    transfer_u8_in_place(
+        data,
+        stride,
+        width,
+        height,
+        ImageConfiguration::Rgb,
+        &lut_table,
+    );
+}
+
+/// This function applies gamma to linear RGBA u8 image in place, the same as [crate::linear_u8_to_rgba], works for BGRA as well
+///
+/// # Arguments
+/// * `data` - A mutable slice contains linear RGBA data
+/// * `stride` - Bytes per row for data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear to gamma space. If you don't have specific pick `Srgb`
+pub fn linear_u8_to_rgba_in_place(
+    data: &mut [u8],
+    stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    let lut_table = gamma_table_u8_to_u8(transfer_function);
+    transfer_u8_in_place(
+        data,
+        stride,
+        width,
+        height,
+        ImageConfiguration::Rgba,
+        &lut_table,
+    );
+}
+
+macro_rules! model_in_place {
+    ($name: ident, $name_with_alpha: ident, $from: literal, $to: literal, |$x: ident, $y: ident, $z: ident| $body: expr) => {
+        #[doc = concat!("This function converts ", $from, " image into ", $to, " in place")]
+        ///
+        /// # Arguments
+        #[doc = concat!("* `data` - A mutable slice contains ", $from, " data")]
+        /// * `stride` - Bytes per row for data
+        /// * `width` - Image width
+        /// * `height` - Image height
+        pub fn $name(data: &mut [f32], stride: u32, width: u32, height: u32) {
+            transform_in_place(data, stride, width, height, ImageConfiguration::Rgb, |pixel| {
+                let ($x, $y, $z) = (pixel[0], pixel[1], pixel[2]);
+                let (v0, v1, v2) = $body;
+                pixel[0] = v0;
+                pixel[1] = v1;
+                pixel[2] = v2;
+            });
+        }
+
+        #[doc = concat!("This function converts ", $from, " image with alpha into ", $to, " with alpha in place, alpha is kept as is")]
+        ///
+        /// # Arguments
+        #[doc = concat!("* `data` - A mutable slice contains ", $from, " data with alpha at last position")]
+        /// * `stride` - Bytes per row for data
+        /// * `width` - Image width
+        /// * `height` - Image height
+        pub fn $name_with_alpha(data: &mut [f32], stride: u32, width: u32, height: u32) {
+            transform_in_place(data, stride, width, height, ImageConfiguration::Rgba, |pixel| {
+                let ($x, $y, $z) = (pixel[0], pixel[1], pixel[2]);
+                let (v0, v1, v2) = $body;
+                pixel[0] = v0;
+                pixel[1] = v1;
+                pixel[2] = v2;
+            });
+        }
+    };
+}
+
+model_in_place!(
+    xyz_to_lab_in_place,
+    xyza_to_laba_in_place,
+    "XYZ",
+    "CIE L*a*b*",
+    |x, y, z| {
+        let lab = Lab::from_xyz(Xyz::new(x, y, z));
+        (lab.l, lab.a, lab.b)
+    }
+);
+
+model_in_place!(
+    lab_to_xyz_in_place,
+    laba_to_xyza_in_place,
+    "CIE L*a*b*",
+    "XYZ",
+    |l, a, b| {
+        let xyz = Lab::new(l, a, b).to_xyz();
+        (xyz.x, xyz.y, xyz.z)
+    }
+);
+
+model_in_place!(
+    xyz_to_luv_in_place,
+    xyza_to_luva_in_place,
+    "XYZ",
+    "CIE L*u*v*",
+    |x, y, z| {
+        let luv = Luv::from_xyz(Xyz::new(x, y, z));
+        (luv.l, luv.u, luv.v)
+    }
+);
+
+model_in_place!(
+    luv_to_xyz_in_place,
+    luva_to_xyza_in_place,
+    "CIE L*u*v*",
+    "XYZ",
+    |l, u, v| {
+        let xyz = Luv::new(l, u, v).to_xyz();
+        (xyz.x, xyz.y, xyz.z)
+    }
+);
+
+model_in_place!(
+    oklab_to_oklch_in_place,
+    oklaba_to_oklcha_in_place,
+    "Oklab",
+    "Oklch",
+    |l, a, b| {
+        let oklch = Oklch::from_oklab(Oklab::new(l, a, b));
+        (oklch.l, oklch.c, oklch.h)
+    }
+);
+
+model_in_place!(
+    oklch_to_oklab_in_place,
+    oklcha_to_oklaba_in_place,
+    "Oklch",
+    "Oklab",
+    |l, c, h| {
+        let oklab = Oklch::new(l, c, h).to_oklab();
+        (oklab.l, oklab.a, oklab.b)
+    }
+);
+
+model_in_place!(
+    jzazbz_to_jzczhz_in_place,
+    jzazbza_to_jzczhza_in_place,
+    "Jzazbz",
+    "Jzczhz",
+    |jz, az, bz| {
+        let jzczhz = Jzczhz::from_jzazbz(Jzazbz::new(jz, az, bz));
+        (jzczhz.jz, jzczhz.cz, jzczhz.hz)
+    }
+);
+
+model_in_place!(
+    jzczhz_to_jzazbz_in_place,
+    jzczhza_to_jzazbza_in_place,
+    "Jzczhz",
+    "Jzazbz",
+    |jz, cz, hz| {
+        let jzazbz = Jzczhz::new(jz, cz, hz).to_jzazbz();
+        (jzazbz.jz, jzazbz.az, jzazbz.bz)
+    }
+);
+
+fn gamut_f32_in_place(
+    data: &mut [f32],
+    stride: u32,
+    width: u32,
+    height: u32,
+    layout: ImageConfiguration,
+    src_to_xyz: &[[f32; 3]; 3],
+    src_transfer_function: TransferFunction,
+    xyz_to_dst: &[[f32; 3]; 3],
+    dst_transfer_function: TransferFunction,
+) {
+    let matrix = matrix_mul(xyz_to_dst, src_to_xyz);
+    transform_in_place(data, stride, width, height, layout, |pixel| {
+        let linear = [
+            src_transfer_function.linearize(pixel[0]),
+            src_transfer_function.linearize(pixel[1]),
+            src_transfer_function.linearize(pixel[2]),
+        ];
+        let converted = matrix_vec_mul(&matrix, linear);
+        for (dst, v) in pixel[..3].iter_mut().zip(converted) {
+            *dst = dst_transfer_function.gamma(v.max(0.).min(1.));
+        }
+    });
+}
+
+fn gamut_u8_in_place(
+    data: &mut [u8],
+    stride: u32,
+    width: u32,
+    height: u32,
+    layout: ImageConfiguration,
+    src_to_xyz: &[[f32; 3]; 3],
+    src_transfer_function: TransferFunction,
+    xyz_to_dst: &[[f32; 3]; 3],
+    dst_transfer_function: TransferFunction,
+) {
+    let matrix = matrix_mul(xyz_to_dst, src_to_xyz);
+    let linearization_table = linearization_table_u8(src_transfer_function);
+    let gamma_table = gamma_table_u8(dst_transfer_function);
+    transform_in_place(data, stride, width, height, layout, |pixel| {
+        let linear = [
+            linearization_table[pixel[0] as usize],
+            linearization_table[pixel[1] as usize],
+            linearization_table[pixel[2] as usize],
+        ];
+        let converted = matrix_vec_mul(&matrix, linear);
+        for (dst, v) in pixel[..3].iter_mut().zip(converted) {
+            *dst = gamma_table[(v.max(0.).min(1.) * 2048.).round() as usize];
+        }
+    });
+}
+
+/// This function converts RGB f32 image between RGB spaces with different primaries in place.
+/// Both matrices must be defined for the same white point, out of gamut colours are clipped
+///
+/// # Arguments
+/// * `data` - A mutable slice contains RGB data in range [0, 1]
+/// * `stride` - Bytes per row for data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `src_to_xyz` - Matrix from source linear RGB into XYZ, e.g. [crate::BT2020_TO_XYZ_D65]
+/// * `src_transfer_function` - Transfer function of source
+/// * `xyz_to_dst` - Matrix from XYZ into target linear RGB, e.g. [crate::XYZ_TO_SRGB_D65]
+/// * `dst_transfer_function` - Transfer function of target
+pub fn rgb_f32_gamut_in_place(
+    data: &mut [f32],
+    stride: u32,
+    width: u32,
+    height: u32,
+    src_to_xyz: &[[f32; 3]; 3],
+    src_transfer_function: TransferFunction,
+    xyz_to_dst: &[[f32; 3]; 3],
+    dst_transfer_function: TransferFunction,
+) {
+    gamut_f32_in_place(
+        data,
+        stride,
+        width,
+        height,
+        ImageConfiguration::Rgb,
+        src_to_xyz,
+        src_transfer_function,
+        xyz_to_dst,
+        dst_transfer_function,
+    );
+}
+
+/// This function converts RGBA f32 image between RGB spaces with different primaries in place, alpha is kept as is.
+/// Both matrices must be defined for the same white point, out of gamut colours are clipped
+///
+/// # Arguments
+/// * `data` - A mutable slice contains RGBA data in range [0, 1]
+/// * `stride` - Bytes per row for data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `src_to_xyz` - Matrix from source linear RGB into XYZ, e.g. [crate::BT2020_TO_XYZ_D65]
+/// * `src_transfer_function` - Transfer function of source
+/// * `xyz_to_dst` - Matrix from XYZ into target linear RGB, e.g. [crate::XYZ_TO_SRGB_D65]
+/// * `dst_transfer_function` - Transfer function of target
+pub fn rgba_f32_gamut_in_place(
+    data: &mut [f32],
+    stride: u32,
+    width: u32,
+    height: u32,
+    src_to_xyz: &[[f32; 3]; 3],
+    src_transfer_function: TransferFunction,
+    xyz_to_dst: &[[f32; 3]; 3],
+    dst_transfer_function: TransferFunction,
+) {
+    gamut_f32_in_place(
+        data,
+        stride,
+        width,
+        height,
+        ImageConfiguration::Rgba,
+        src_to_xyz,
+        src_transfer_function,
+        xyz_to_dst,
+        dst_transfer_function,
+    );
+}
+
+/// This function converts RGB image between RGB spaces with different primaries in place.
+/// Both matrices must be defined for the same white point, out of gamut colours are clipped
+///
+/// # Arguments
+/// * `data` - A mutable slice contains RGB data
+/// * `stride` - Bytes per row for data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `src_to_xyz` - Matrix from source linear RGB into XYZ, e.g. [crate::DISPLAY_P3_TO_XYZ_D65]
+/// * `src_transfer_function` - Transfer function of source
+/// * `xyz_to_dst` - Matrix from XYZ into target linear RGB, e.g. [crate::XYZ_TO_SRGB_D65]
+/// * `dst_transfer_function` - Transfer function of target
+pub fn rgb_gamut_in_place(
+    data: &mut [u8],
+    stride: u32,
+    width: u32,
+    height: u32,
+    src_to_xyz: &[[f32; 3]; 3],
+    src_transfer_function: TransferFunction,
+    xyz_to_dst: &[[f32; 3]; 3],
+    dst_transfer_function: TransferFunction,
+) {
+    gamut_u8_in_place(
+        data,
+        stride,
+        width,
+        height,
+        ImageConfiguration::Rgb,
+        src_to_xyz,
+        src_transfer_function,
+        xyz_to_dst,
+        dst_transfer_function,
+    );
+}
+
+/// This function converts RGBA image between RGB spaces with different primaries in place, alpha is kept as is.
+/// Both matrices must be defined for the same white point, out of gamut colours are clipped
+///
+/// # Arguments
+/// * `data` - A mutable slice contains RGBA data
+/// * `stride` - Bytes per row for data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `src_to_xyz` - Matrix from source linear RGB into XYZ, e.g. [crate::DISPLAY_P3_TO_XYZ_D65]
+/// * `src_transfer_function` - Transfer function of source
+/// * `xyz_to_dst` - Matrix from XYZ into target linear RGB, e.g. [crate::XYZ_TO_SRGB_D65]
+/// * `dst_transfer_function` - Transfer function of target
+pub fn rgba_gamut_in_place(
+    data: &mut [u8],
+    stride: u32,
+    width: u32,
+    height: u32,
+    src_to_xyz: &[[f32; 3]; 3],
+    src_transfer_function: TransferFunction,
+    xyz_to_dst: &[[f32; 3]; 3],
+    dst_transfer_function: TransferFunction,
+) {
+    gamut_u8_in_place(
+        data,
+        stride,
+        width,
+        height,
+        ImageConfiguration::Rgba,
+        src_to_xyz,
+        src_transfer_function,
+        xyz_to_dst,
+        dst_transfer_function,
+    );
+}
+
+/// This function swaps R and B channels of RGB image in place, converting RGB into BGR or BGR into RGB
+///
+/// # Arguments
+/// * `data` - A mutable slice contains RGB or BGR data, `u8`, `u16` or `f32`
+/// * `stride` - Bytes per row for data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn rgb_to_bgr_in_place<T: ImageSample>(data: &mut [T], stride: u32, width: u32, height: u32) {
+    transform_in_place(
+        data,
+        stride,
+        width,
+        height,
+        ImageConfiguration::Rgb,
+        |pixel| pixel.swap(0, 2),
+    );
+}
+
+/// This function swaps R and B channels of RGBA image in place, converting RGBA into BGRA or BGRA into RGBA
+///
+/// # Arguments
+/// * `data` - A mutable slice contains RGBA or BGRA data, `u8`, `u16` or `f32`
+/// * `stride` - Bytes per row for data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn rgba_to_bgra_in_place<T: ImageSample>(data: &mut [T], stride: u32, width: u32, height: u32) {
+    transform_in_place(
+        data,
+        stride,
+        width,
+        height,
+        ImageConfiguration::Rgba,
+        |pixel| pixel.swap(0, 2),
+    );
+}
@@ -0,0 +1,476 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::gamma_curves::TransferFunction;
+use crate::threading::ForEachRow;
+use crate::utils::mlaf;
+use crate::xyz_transform::{SRGB_TO_XYZ_D65, XYZ_TO_SRGB_D65};
+use crate::{Jzazbz, Jzczhz, LCh, Lab, Luv, Oklab, Oklch, Rgb, Rgba, Xyz};
+#[cfg(feature = "rayon")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "rayon")]
+use rayon::prelude::ParallelSliceMut;
+use std::f32::consts::{PI, TAU};
+
+/// Hue interpolation method as CSS Color 4 defines it
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
+pub enum HueInterpolation {
+    /// Takes the shortest arc, default in CSS
+    #[default]
+    Shorter = 0,
+    /// Takes the longest arc
+    Longer = 1,
+    /// Hue always increases
+    Increasing = 2,
+    /// Hue always decreases
+    Decreasing = 3,
+}
+
+/// Colour space where interpolation happens.
+///
+/// Polar spaces carry hue interpolation method
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum InterpolationSpace {
+    /// Gamma encoded sRGB
+    Srgb,
+    /// Linear sRGB
+    LinearRgb,
+    /// CIE Lab, D65
+    Lab,
+    /// CIE LCh(uv), D65
+    LCh(HueInterpolation),
+    Oklab,
+    Oklch(HueInterpolation),
+    /// Jzazbz with 200 nits display luminance
+    Jzazbz,
+    /// Jzczhz with 200 nits display luminance
+    Jzczhz(HueInterpolation),
+    /// CIE XYZ, D65
+    Xyz,
+}
+
+impl InterpolationSpace {
+    #[inline]
+    fn hue_interpolation(&self) -> Option<HueInterpolation> {
+        match self {
+            InterpolationSpace::LCh(method)
+            | InterpolationSpace::Oklch(method)
+            | InterpolationSpace::Jzczhz(method) => Some(*method),
+            _ => None,
+        }
+    }
+
+    /// Chroma threshold below which hue considered powerless
+    #[inline]
+    fn achromatic_threshold(&self) -> f32 {
+        match self {
+            InterpolationSpace::LCh(_) => 0.02,
+            _ => 2e-4,
+        }
+    }
+
+    /// Converts gamma encoded sRGB into interpolation space components.
+    /// For polar spaces components are lightness, chroma and hue in radians
+    #[inline]
+    fn encode(&self, rgb: Rgb<f32>) -> [f32; 3] {
+        if *self == InterpolationSpace::Srgb {
+            return [rgb.r, rgb.g, rgb.b];
+        }
+        let linear = rgb.linearize(TransferFunction::Srgb);
+        match self {
+            InterpolationSpace::Srgb => unreachable!(),
+            InterpolationSpace::LinearRgb => [linear.r, linear.g, linear.b],
+            InterpolationSpace::Oklab => {
+                let oklab = Oklab::from_linear_rgb(linear);
+                [oklab.l, oklab.a, oklab.b]
+            }
+            InterpolationSpace::Oklch(_) => {
+                let oklch = Oklch::from_linear_rgb(linear);
+                [oklch.l, oklch.c, oklch.h]
+            }
+            _ => {
+                let xyz = Xyz::from_linear_rgb(linear, &SRGB_TO_XYZ_D65);
+                match self {
+                    InterpolationSpace::Lab => {
+                        let lab = Lab::from_xyz(xyz);
+                        [lab.l, lab.a, lab.b]
+                    }
+                    InterpolationSpace::LCh(_) => {
+                        let lch = LCh::from_luv(Luv::from_xyz(xyz));
+                        [lch.l, lch.c, lch.h]
+                    }
+                    InterpolationSpace::Jzazbz => {
+                        let jzazbz = Jzazbz::from_xyz(xyz);
+                        [jzazbz.jz, jzazbz.az, jzazbz.bz]
+                    }
+                    InterpolationSpace::Jzczhz(_) => {
+                        let jzczhz = Jzczhz::from_jzazbz(Jzazbz::from_xyz(xyz));
+                        [jzczhz.jz, jzczhz.cz, jzczhz.hz]
+                    }
+                    _ => [xyz.x, xyz.y, xyz.z],
+                }
+            }
+        }
+    }
+
+    /// Converts interpolation space components back to gamma encoded sRGB, result is not clamped
+    #[inline]
+    fn decode(&self, v: [f32; 3]) -> Rgb<f32> {
+        let linear = match self {
+            InterpolationSpace::Srgb => return Rgb::<f32>::new(v[0], v[1], v[2]),
+            InterpolationSpace::LinearRgb => Rgb::<f32>::new(v[0], v[1], v[2]),
+            InterpolationSpace::Lab => Lab::new(v[0], v[1], v[2]).to_linear_rgb(&XYZ_TO_SRGB_D65),
+            InterpolationSpace::LCh(_) => {
+                LCh::new(v[0], v[1], v[2]).to_linear_rgb(&XYZ_TO_SRGB_D65)
+            }
+            InterpolationSpace::Oklab => Oklab::new(v[0], v[1], v[2]).to_linear_rgb(),
+            InterpolationSpace::Oklch(_) => Oklch::new(v[0], v[1], v[2]).to_linear_rgb(),
+            InterpolationSpace::Jzazbz => Jzazbz::new(v[0], v[1], v[2]).to_linear_rgb(),
+            InterpolationSpace::Jzczhz(_) => {
+                Jzczhz::new(v[0], v[1], v[2]).to_jzazbz().to_linear_rgb()
+            }
+            InterpolationSpace::Xyz => Xyz::new(v[0], v[1], v[2]).to_linear_rgb(&XYZ_TO_SRGB_D65),
+        };
+        Rgb::<f32>::new(
+            linear.r.max(0.).min(1.),
+            linear.g.max(0.).min(1.),
+            linear.b.max(0.).min(1.),
+        )
+        .gamma(TransferFunction::Srgb)
+    }
+}
+
+/// Colour prepared for interpolation, components are premultiplied except hue
+#[derive(Debug, Copy, Clone)]
+struct InterpolationColor {
+    components: [f32; 3],
+    alpha: f32,
+}
+
+impl InterpolationColor {
+    #[inline]
+    fn new(rgba: Rgba<u8>, space: &InterpolationSpace) -> Self {
+        const SCALE: f32 = 1f32 / 255f32;
+        let rgb = Rgb::<f32>::new(
+            rgba.r as f32 * SCALE,
+            rgba.g as f32 * SCALE,
+            rgba.b as f32 * SCALE,
+        );
+        let alpha = rgba.a as f32 * SCALE;
+        let mut components = space.encode(rgb);
+        let has_hue = space.hue_interpolation().is_some();
+        for (i, component) in components.iter_mut().enumerate() {
+            if !(has_hue && i == 2) {
+                *component *= alpha;
+            }
+        }
+        if has_hue {
+            components[2] = components[2].rem_euclid(TAU);
+        }
+        InterpolationColor { components, alpha }
+    }
+
+    #[inline]
+    fn is_achromatic(&self, space: &InterpolationSpace) -> bool {
+        // Chroma is premultiplied, so compare it against premultiplied threshold
+        self.components[1] <= space.achromatic_threshold() * self.alpha
+    }
+}
+
+#[inline]
+fn fixup_hues(h1: f32, h2: f32, method: HueInterpolation) -> (f32, f32) {
+    let diff = h2 - h1;
+    match method {
+        HueInterpolation::Shorter => {
+            if diff > PI {
+                (h1 + TAU, h2)
+            } else if diff < -PI {
+                (h1, h2 + TAU)
+            } else {
+                (h1, h2)
+            }
+        }
+        HueInterpolation::Longer => {
+            if diff > 0. && diff < PI {
+                (h1 + TAU, h2)
+            } else if diff > -PI && diff <= 0. {
+                (h1, h2 + TAU)
+            } else {
+                (h1, h2)
+            }
+        }
+        HueInterpolation::Increasing => {
+            if diff < 0. {
+                (h1, h2 + TAU)
+            } else {
+                (h1, h2)
+            }
+        }
+        HueInterpolation::Decreasing => {
+            if diff > 0. {
+                (h1 + TAU, h2)
+            } else {
+                (h1, h2)
+            }
+        }
+    }
+}
+
+#[inline]
+fn interpolate(
+    a: &InterpolationColor,
+    b: &InterpolationColor,
+    t: f32,
+    space: &InterpolationSpace,
+) -> Rgba<u8> {
+    let alpha = mlaf(a.alpha, b.alpha - a.alpha, t);
+    if alpha <= 0. {
+        return Rgba::<u8>::new(0, 0, 0, 0);
+    }
+    let mut components = [0f32; 3];
+    for (i, dst) in components.iter_mut().enumerate() {
+        *dst = mlaf(a.components[i], b.components[i] - a.components[i], t);
+    }
+    if let Some(method) = space.hue_interpolation() {
+        let a_powerless = a.is_achromatic(space);
+        let b_powerless = b.is_achromatic(space);
+        let (h1, h2) = match (a_powerless, b_powerless) {
+            (true, false) => (b.components[2], b.components[2]),
+            (false, true) => (a.components[2], a.components[2]),
+            _ => fixup_hues(a.components[2], b.components[2], method),
+        };
+        components[2] = mlaf(h1, h2 - h1, t);
+    }
+    let has_hue = space.hue_interpolation().is_some();
+    let recip_alpha = 1. / alpha;
+    for (i, component) in components.iter_mut().enumerate() {
+        if !(has_hue && i == 2) {
+            *component *= recip_alpha;
+        }
+    }
+    let rgb = space.decode(components);
+    Rgba::<u8>::new(
+        (rgb.r * 255.).round().max(0.).min(255.) as u8,
+        (rgb.g * 255.).round().max(0.).min(255.) as u8,
+        (rgb.b * 255.).round().max(0.).min(255.) as u8,
+        (alpha * 255.).round().max(0.).min(255.) as u8,
+    )
+}
+
+/// Mixes two sRGB colours in requested colour space.
+///
+/// Interpolation is done on premultiplied alpha as CSS Color 4 defines it, hue is never
+/// premultiplied and powerless hue of achromatic colour is taken from the other colour
+///
+/// # Arguments
+/// * `a` - First colour, sRGB encoded
+/// * `b` - Second colour, sRGB encoded
+/// * `t` - Interpolation factor in range [0, 1], 0 results in `a`
+/// * `space` - Colour space where interpolation is performed
+pub fn mix(a: Rgba<u8>, b: Rgba<u8>, t: f32, space: InterpolationSpace) -> Rgba<u8> {
+    let first = InterpolationColor::new(a, &space);
+    let second = InterpolationColor::new(b, &space);
+    interpolate(&first, &second, t.max(0.).min(1.), &space)
+}
+
+/// Gradient colour stop
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GradientStop {
+    /// Stop position, generally in range [0, 1]
+    pub position: f32,
+    /// Stop colour, sRGB encoded
+    pub color: Rgba<u8>,
+}
+
+impl GradientStop {
+    #[inline]
+    pub fn new(position: f32, color: Rgba<u8>) -> GradientStop {
+        GradientStop { position, color }
+    }
+}
+
+struct PreparedGradient {
+    positions: Vec<f32>,
+    colors: Vec<InterpolationColor>,
+    space: InterpolationSpace,
+}
+
+impl PreparedGradient {
+    fn new(stops: &[GradientStop], space: InterpolationSpace) -> PreparedGradient {
+        if stops.is_empty() {
+            panic!("Gradient must have at least one stop");
+        }
+        let mut positions = Vec::with_capacity(stops.len());
+        let mut colors = Vec::with_capacity(stops.len());
+        let mut last_position = f32::MIN;
+        for stop in stops.iter() {
+            // As in CSS, stop positioned before previous one is moved to previous position
+            last_position = stop.position.max(last_position);
+            positions.push(last_position);
+            colors.push(InterpolationColor::new(stop.color, &space));
+        }
+        PreparedGradient {
+            positions,
+            colors,
+            space,
+        }
+    }
+
+    #[inline]
+    fn sample(&self, t: f32) -> Rgba<u8> {
+        let last = self.positions.len() - 1;
+        if t <= self.positions[0] {
+            return interpolate(&self.colors[0], &self.colors[0], 0., &self.space);
+        }
+        if t >= self.positions[last] {
+            return interpolate(&self.colors[last], &self.colors[last], 0., &self.space);
+        }
+        let next = self.positions.partition_point(|&position| position <= t);
+        let previous = next - 1;
+        let range = self.positions[next] - self.positions[previous];
+        let local_t = if range > 0. {
+            (t - self.positions[previous]) / range
+        } else {
+            1.
+        };
+        interpolate(
+            &self.colors[previous],
+            &self.colors[next],
+            local_t,
+            &self.space,
+        )
+    }
+}
+
+/// Samples multi-stop gradient at position.
+///
+/// Stops expected to be ordered by position, stop positioned before its predecessor
+/// is moved to predecessor position. Positions outside the stops take the edge colour
+///
+/// # Arguments
+/// * `stops` - Gradient stops, at least one is required
+/// * `t` - Sampling position
+/// * `space` - Colour space where interpolation is performed
+pub fn sample_gradient(stops: &[GradientStop], t: f32, space: InterpolationSpace) -> Rgba<u8> {
+    PreparedGradient::new(stops, space).sample(t)
+}
+
+/// This function renders horizontal multi-stop gradient into RGBA row,
+/// first pixel is sampled at 0 and last pixel at 1
+///
+/// # Arguments
+/// * `dst` - A mutable slice to receive RGBA row, at least `width * 4` bytes
+/// * `width` - Row width
+/// * `stops` - Gradient stops, at least one is required
+/// * `space` - Colour space where interpolation is performed
+pub fn gradient_row_to_rgba(
+    dst: &mut [u8],
+    width: u32,
+    stops: &[GradientStop],
+    space: InterpolationSpace,
+) {
+    let gradient = PreparedGradient::new(stops, space);
+    let scale = if width > 1 {
+        1f32 / (width - 1) as f32
+    } else {
+        0f32
+    };
+    for (x, dst) in dst.chunks_exact_mut(4).take(width as usize).enumerate() {
+        let color = gradient.sample(x as f32 * scale);
+        dst[0] = color.r;
+        dst[1] = color.g;
+        dst[2] = color.b;
+        dst[3] = color.a;
+    }
+}
+
+/// This function renders linear multi-stop gradient into RGBA image.
+///
+/// Gradient goes along the line from `start` to `end` in pixel coordinates,
+/// position 0 is at `start` and 1 at `end`. Gradient is sampled once into a ramp
+/// with at least one entry per pixel of the gradient line and then pixels are filled from it
+///
+/// # Arguments
+/// * `dst` - A mutable slice to receive RGBA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `stops` - Gradient stops, at least one is required
+/// * `start` - Gradient line start point (x, y)
+/// * `end` - Gradient line end point (x, y)
+/// * `space` - Colour space where interpolation is performed
+pub fn linear_gradient_to_rgba(
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    stops: &[GradientStop],
+    start: (f32, f32),
+    end: (f32, f32),
+    space: InterpolationSpace,
+) {
+    let gradient = PreparedGradient::new(stops, space);
+
+    let dx = end.0 - start.0;
+    let dy = end.1 - start.1;
+    let length_squared = mlaf(dx * dx, dy, dy);
+    let ramp_size = (length_squared.sqrt().ceil() as usize + 1)
+        .max(256)
+        .min(65536);
+
+    let first_position = gradient.positions[0].min(0.);
+    let last_position = gradient.positions[gradient.positions.len() - 1].max(1.);
+    let position_range = last_position - first_position;
+
+    let ramp_scale = position_range / (ramp_size - 1) as f32;
+    let ramp = (0..ramp_size)
+        .map(|i| gradient.sample(mlaf(first_position, i as f32, ramp_scale)))
+        .collect::<Vec<Rgba<u8>>>();
+
+    let (step_x, step_y) = if length_squared > 0. {
+        (dx / length_squared, dy / length_squared)
+    } else {
+        (0., 0.)
+    };
+
+    let ramp_max = (ramp_size - 1) as f32;
+    let ramp_factor = ramp_max / position_range;
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = dst
+            .par_chunks_exact_mut(dst_stride as usize)
+            .take(height as usize)
+            .enumerate();
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = dst
+            .chunks_exact_mut(dst_stride as usize)
+            .take(height as usize)
+            .enumerate();
+    }
+
+    iter.for_each_row(width as usize, |(y, dst_row)| {
+        let py = y as f32 + 0.5 - start.1;
+        let row_start = py * step_y;
+        for (x, dst) in dst_row.chunks_exact_mut(4).take(width as usize).enumerate() {
+            let px = x as f32 + 0.5 - start.0;
+            let t = mlaf(row_start, px, step_x);
+            let ramp_position = ((t - first_position) * ramp_factor)
+                .round()
+                .max(0.)
+                .min(ramp_max) as usize;
+            let color = unsafe { *ramp.get_unchecked(ramp_position) };
+            dst[0] = color.r;
+            dst[1] = color.g;
+            dst[2] = color.b;
+            dst[3] = color.a;
+        }
+    });
+}
@@ -4,36 +4,28 @@
  * // Use of this source code is governed by a BSD-style
  * // license that can be found in the LICENSE file.
  */
+use crate::gamma_curves::gamma_table_u8;
 use crate::image::ImageConfiguration;
 use crate::image_to_jzazbz::JzazbzTarget;
 #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
 use crate::neon::neon_jzazbz_to_image;
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::sse::sse_jzazbz_to_image;
+use crate::threading::{current_threading, ForEachRow, ThreadingPolicy};
 use crate::{Jzazbz, Jzczhz, TransferFunction};
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 use std::slice;
 
-#[allow(clippy::type_complexity)]
-fn jzazbz_to_image<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
-    src: &[f32],
-    src_stride: u32,
-    dst: &mut [u8],
-    dst_stride: u32,
-    width: u32,
-    height: u32,
-    display_luminance: f32,
-    transfer_function: TransferFunction,
-) {
-    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
-    let target: JzazbzTarget = TARGET.into();
+pub(crate) type JzazbzToImageRowHandler =
+    unsafe fn(usize, *const f32, u32, *mut f32, u32, u32, f32) -> usize;
 
-    let mut _wide_row_handle: Option<
-        unsafe fn(usize, *const f32, u32, *mut f32, u32, u32, f32) -> usize,
-    > = None;
+/// Selects the widest available SIMD row handler for the layout
+pub(crate) fn jzazbz_to_image_row_handler<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+) -> Option<JzazbzToImageRowHandler> {
+    let mut _wide_row_handle: Option<JzazbzToImageRowHandler> = None;
 
     #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
     if std::arch::is_x86_feature_detected!("sse4.1") {
@@ -45,10 +37,48 @@ fn jzazbz_to_image<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
         _wide_row_handle = Some(neon_jzazbz_to_image::<CHANNELS_CONFIGURATION, TARGET>);
     }
 
-    let mut lut_table = vec![0u8; 2049];
-    for (i, lut) in lut_table.iter_mut().enumerate() {
-        *lut = (transfer_function.gamma(i as f32 * (1. / 2048.0)) * 255.).min(255.) as u8;
-    }
+    _wide_row_handle
+}
+
+pub(crate) fn jzazbz_to_image<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    display_luminance: f32,
+    transfer_function: TransferFunction,
+) {
+    jzazbz_to_image_impl::<CHANNELS_CONFIGURATION, TARGET>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        display_luminance,
+        &gamma_table_u8(transfer_function),
+        jzazbz_to_image_row_handler::<CHANNELS_CONFIGURATION, TARGET>(),
+        &current_threading(),
+    );
+}
+
+/// Converts image with precomputed gamma table and row handler
+pub(crate) fn jzazbz_to_image_impl<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    display_luminance: f32,
+    lut_table: &[u8],
+    _wide_row_handle: Option<JzazbzToImageRowHandler>,
+    policy: &ThreadingPolicy,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let target: JzazbzTarget = TARGET.into();
 
     let src_slice_safe_align = unsafe {
         slice::from_raw_parts(
@@ -72,7 +102,7 @@ fn jzazbz_to_image<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
             .zip(src_slice_safe_align.chunks_exact(src_stride as usize));
     }
 
-    iter.for_each(|(dst, src)| unsafe {
+    iter.for_each_row_with(policy, width as usize, |(dst, src)| unsafe {
         let channels = image_configuration.get_channels_count();
 
         let mut _cx = 0usize;
@@ -188,6 +218,38 @@ pub fn jzazbz_to_rgba(
     );
 }
 
+/// This function converts Jzazbz with interleaved alpha channel to ARGB. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn jzazbz_to_argb(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    display_luminance: f32,
+    transfer_function: TransferFunction,
+) {
+    jzazbz_to_image::<{ ImageConfiguration::Argb as u8 }, { JzazbzTarget::Jzazbz as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        display_luminance,
+        transfer_function,
+    );
+}
+
 /// This function converts Jzazbz to RGB. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -284,6 +346,38 @@ pub fn jzazbz_to_bgra(
     );
 }
 
+/// This function converts Jzazbz with interleaved alpha channel to ABGR. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains Jzazbz data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn jzazbz_to_abgr(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    display_luminance: f32,
+    transfer_function: TransferFunction,
+) {
+    jzazbz_to_image::<{ ImageConfiguration::Abgr as u8 }, { JzazbzTarget::Jzazbz as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        display_luminance,
+        transfer_function,
+    );
+}
+
 /// This function converts Jzczhz with interleaved alpha channel to RGBA. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -316,6 +410,38 @@ pub fn jzczhz_to_rgba(
     );
 }
 
+/// This function converts Jzczhz with interleaved alpha channel to ARGB. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains Jzczhz data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn jzczhz_to_argb(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    display_luminance: f32,
+    transfer_function: TransferFunction,
+) {
+    jzazbz_to_image::<{ ImageConfiguration::Argb as u8 }, { JzazbzTarget::Jzczhz as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        display_luminance,
+        transfer_function,
+    );
+}
+
 /// This function converts Jzczhz to RGB. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -411,3 +537,35 @@ pub fn jzczhz_to_bgra(
         transfer_function,
     );
 }
+
+/// This function converts Jzczhz with interleaved alpha channel to ABGR. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains Jzczhz data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn jzczhz_to_abgr(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    display_luminance: f32,
+    transfer_function: TransferFunction,
+) {
+    jzazbz_to_image::<{ ImageConfiguration::Abgr as u8 }, { JzazbzTarget::Jzczhz as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        display_luminance,
+        transfer_function,
+    );
+}
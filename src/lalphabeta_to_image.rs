@@ -5,14 +5,15 @@
  * // license that can be found in the LICENSE file.
  */
 use crate::image::ImageConfiguration;
+use crate::threading::ForEachRow;
 use crate::{LAlphaBeta, Rgb, TransferFunction, XYZ_TO_SRGB_D65};
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 use std::slice;
 
-fn lalphabeta_to_image<const CHANNELS_CONFIGURATION: u8>(
+pub(crate) fn lalphabeta_to_image<const CHANNELS_CONFIGURATION: u8>(
     src: &[f32],
     src_stride: u32,
     dst: &mut [u8],
@@ -51,7 +52,7 @@ fn lalphabeta_to_image<const CHANNELS_CONFIGURATION: u8>(
             .zip(src_slice_safe_align.chunks_exact(src_stride as usize));
     }
 
-    iter.for_each(|(dst, src)| unsafe {
+    iter.for_each_row(width as usize, |(dst, src)| unsafe {
         let mut _cx = 0usize;
 
         let src_ptr = src.as_ptr() as *mut f32;
@@ -137,6 +138,36 @@ pub fn lalphabeta_to_rgba(
     );
 }
 
+/// This function converts *lαβ* with interleaved alpha channel to ARGB. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn lalphabeta_to_argb(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    lalphabeta_to_image::<{ ImageConfiguration::Argb as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+    );
+}
+
 /// This function converts *lαβ* to RGB. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -226,3 +257,33 @@ pub fn lalphabeta_to_bgra(
         transfer_function,
     );
 }
+
+/// This function converts *lαβ* with interleaved alpha channel to ABGR. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn lalphabeta_to_abgr(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    lalphabeta_to_image::<{ ImageConfiguration::Abgr as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+    );
+}
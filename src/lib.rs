@@ -11,13 +11,30 @@
 )]
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 mod avx;
+mod blend;
+mod cct;
+mod chromatic_adaptation;
+mod color_adjustments;
+mod color_rendering;
+mod color_space;
+mod color_transfer;
 mod concat_alpha;
+mod contrast;
+mod conversion_plan;
+mod convert_image;
+mod css;
+mod css_named_colors;
+mod cvd;
 mod euclidean;
 mod gamma_curves;
+mod gray_lab;
+mod hlg;
 mod hsl;
 mod hsv;
 mod hsv_to_image;
 mod image;
+mod image_blend;
+mod image_cvd;
 mod image_to_hsv;
 mod image_to_hsv_support;
 mod image_to_jzazbz;
@@ -27,7 +44,11 @@ mod image_to_linear_u8;
 mod image_to_oklab;
 mod image_to_sigmoidal;
 mod image_to_xyz_lab;
+mod image_view;
+mod image_view_converters;
 mod image_xyza_laba;
+mod in_place;
+mod interpolation;
 mod jzazbz;
 mod jzazbz_to_image;
 mod jzczhz;
@@ -37,22 +58,34 @@ mod lalphabeta_to_image;
 mod linear_to_image;
 mod linear_to_image_u8;
 pub mod linear_to_planar;
+mod luma;
 mod luv;
+mod matrix;
 #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
 mod neon;
 mod oklab;
 mod oklab_to_image;
 mod oklch;
+mod packed;
+mod palette;
+mod palette_mapping;
+mod planar;
+mod planar_converters;
 pub mod planar_to_linear;
+mod premultiply;
 mod rgb;
 mod rgb_expand;
 mod rgba;
 mod routines;
 mod sigmoidal;
 mod sigmoidal_to_image;
+mod spectral;
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 mod sse;
+mod swizzle;
 mod taxicab;
+mod threading;
+mod tone_mapping;
 mod utils;
 mod xyb;
 mod xyz;
@@ -60,44 +93,142 @@ mod xyz_lab_to_image;
 mod xyz_target;
 mod xyz_transform;
 mod xyza_laba_to_image;
+mod ycbcr;
 
+pub use blend::{BlendMode, BlendingSpace};
+pub use cct::{
+    bgra_white_balance, daylight_chromaticity, planckian_chromaticity, rgba_white_balance,
+    CctMethod, ColorTemperature, MAX_PLANCKIAN_TEMPERATURE, MIN_PLANCKIAN_TEMPERATURE,
+};
+pub use chromatic_adaptation::{
+    adapt_xyz, adaptation_matrix, ChromaticAdaptation, BRADFORD, CAT02, VON_KRIES, WHITE_POINT_A,
+    WHITE_POINT_D50, WHITE_POINT_D65, WHITE_POINT_E,
+};
+pub use color_adjustments::{
+    bgra_adjust_colors, bgra_f32_adjust_colors, rgba_adjust_colors, rgba_f32_adjust_colors,
+    AdjustmentSpace, ColorAdjustments, LightnessCurve,
+};
+pub use color_rendering::{
+    color_rendering_index, color_rendering_index_with_samples, tm30_metrics, ColorRenderingIndex,
+    Tm30Metrics, CRI_GENERAL_SAMPLES, CRI_TEST_SAMPLES, TM30_EVALUATION_SAMPLES,
+};
+pub use color_space::{ColorModel, ColorSpace, ImageSample, SampleType};
+pub use color_transfer::{
+    bgr_color_transfer, bgra_color_transfer, rgb_color_transfer, rgba_color_transfer,
+    ColorTransferSpace,
+};
 pub use concat_alpha::append_alpha;
+pub use contrast::{
+    adjust_lightness_for_contrast, apca_contrast, apca_luminance, contrast_ratio, passes_wcag,
+    passes_wcag_aa, passes_wcag_aaa, relative_luminance, ContrastMetric, LightnessSpace, WcagLevel,
+    WcagTextSize,
+};
+pub use conversion_plan::ConversionPlan;
+pub use convert_image::convert_image;
+pub use css::{CssColor, CssColorSpace, CssParseError, CssParseErrorKind};
+pub use cvd::{
+    daltonize_linear, machado_cvd_matrix, simulate_cvd_linear, ColorVisionDeficiency, CvdModel,
+};
 pub use gamma_curves::*;
+pub use gray_lab::{gray_alpha_to_lab, gray_to_lab, lab_to_gray, lab_to_gray_alpha};
+pub use hlg::{
+    hlg_from_display_light, hlg_inverse_oetf, hlg_inverse_ootf, hlg_oetf, hlg_ootf,
+    hlg_system_gamma, hlg_to_display_light, rgb_u16_hlg_to_pq, rgb_u16_pq_to_hlg,
+    rgba_u16_hlg_to_pq, rgba_u16_pq_to_hlg, HLG_REFERENCE_PEAK_NITS,
+};
 pub use hsl::Hsl;
 pub use hsv::Hsv;
 pub use hsv_to_image::*;
+pub use image::ImageConfiguration;
+pub use image_blend::*;
+pub use image_cvd::*;
 pub use image_to_hsv::*;
 pub use image_to_linear::*;
 pub use image_to_linear_u8::*;
+pub use image_to_xyz_lab::abgr_to_laba;
+pub use image_to_xyz_lab::abgr_to_laba_premultiplied;
+pub use image_to_xyz_lab::argb_to_lab;
+pub use image_to_xyz_lab::argb_to_laba;
+pub use image_to_xyz_lab::argb_to_laba_premultiplied;
+pub use image_to_xyz_lab::argb_to_xyz;
+pub use image_to_xyz_lab::argb_to_xyza;
 pub use image_to_xyz_lab::bgr_to_lab;
 pub use image_to_xyz_lab::bgr_to_lch;
 pub use image_to_xyz_lab::bgr_to_luv;
 pub use image_to_xyz_lab::bgr_to_xyz;
 pub use image_to_xyz_lab::bgra_to_laba;
+pub use image_to_xyz_lab::bgra_to_laba_premultiplied;
 pub use image_to_xyz_lab::rgb_to_lab;
 pub use image_to_xyz_lab::rgb_to_lch;
 pub use image_to_xyz_lab::rgb_to_luv;
 pub use image_to_xyz_lab::rgb_to_xyz;
 pub use image_to_xyz_lab::rgba_to_lab;
 pub use image_to_xyz_lab::rgba_to_laba;
+pub use image_to_xyz_lab::rgba_to_laba_premultiplied;
 pub use image_to_xyz_lab::rgba_to_xyz;
 pub use image_to_xyz_lab::rgba_to_xyza;
 pub use image_to_xyz_lab::srgb_to_xyz;
 pub use image_to_xyz_lab::srgba_to_xyz;
 pub use image_to_xyz_lab::srgba_to_xyza;
+pub use image_view::{convert_views, ImageMut, ImageRef, ImageRegion, ImageViewError};
+pub use image_view_converters::{
+    hsv_to_view, jzazbz_to_view, jzczhz_to_view, lab_to_view, lalphabeta_to_view, lch_to_view,
+    linear_to_view, linear_u8_to_view, luv_to_view, oklab_to_view, oklch_to_view,
+    sigmoidal_to_view, view_to_hsv, view_to_jzazbz, view_to_jzczhz, view_to_lab,
+    view_to_lalphabeta, view_to_lch, view_to_linear, view_to_linear_u8, view_to_luv, view_to_oklab,
+    view_to_oklch, view_to_sigmoidal, view_to_xyz, xyz_to_view,
+};
+pub use image_xyza_laba::abgr_to_lab_with_alpha;
+pub use image_xyza_laba::abgr_to_lab_with_alpha_premultiplied;
+pub use image_xyza_laba::abgr_to_lch_with_alpha;
+pub use image_xyza_laba::abgr_to_luv_with_alpha;
+pub use image_xyza_laba::abgr_to_xyz_with_alpha;
+pub use image_xyza_laba::argb_to_lab_with_alpha;
+pub use image_xyza_laba::argb_to_lab_with_alpha_premultiplied;
+pub use image_xyza_laba::argb_to_lch_with_alpha;
+pub use image_xyza_laba::argb_to_luv_with_alpha;
+pub use image_xyza_laba::argb_to_xyz_with_alpha;
 pub use image_xyza_laba::bgra_to_lab_with_alpha;
+pub use image_xyza_laba::bgra_to_lab_with_alpha_premultiplied;
 pub use image_xyza_laba::bgra_to_lch_with_alpha;
 pub use image_xyza_laba::bgra_to_luv_with_alpha;
 pub use image_xyza_laba::bgra_to_xyz_with_alpha;
 pub use image_xyza_laba::rgba_to_lab_with_alpha;
+pub use image_xyza_laba::rgba_to_lab_with_alpha_premultiplied;
 pub use image_xyza_laba::rgba_to_lch_with_alpha;
 pub use image_xyza_laba::rgba_to_luv_with_alpha;
 pub use image_xyza_laba::rgba_to_xyz_with_alpha;
+pub use in_place::{
+    jzazbz_to_jzczhz_in_place, jzazbza_to_jzczhza_in_place, jzczhz_to_jzazbz_in_place,
+    jzczhza_to_jzazbza_in_place, lab_to_xyz_in_place, laba_to_xyza_in_place,
+    linear_to_rgb_f32_in_place, linear_to_rgba_f32_in_place, linear_u8_to_rgb_in_place,
+    linear_u8_to_rgba_in_place, luv_to_xyz_in_place, luva_to_xyza_in_place,
+    oklab_to_oklch_in_place, oklaba_to_oklcha_in_place, oklch_to_oklab_in_place,
+    oklcha_to_oklaba_in_place, rgb_f32_gamut_in_place, rgb_f32_to_linear_in_place,
+    rgb_gamut_in_place, rgb_to_bgr_in_place, rgb_to_linear_u8_in_place, rgba_f32_gamut_in_place,
+    rgba_f32_to_linear_in_place, rgba_gamut_in_place, rgba_to_bgra_in_place,
+    rgba_to_linear_u8_in_place, xyz_to_lab_in_place, xyz_to_luv_in_place, xyza_to_laba_in_place,
+    xyza_to_luva_in_place,
+};
+pub use interpolation::{
+    gradient_row_to_rgba, linear_gradient_to_rgba, mix, sample_gradient, GradientStop,
+    HueInterpolation, InterpolationSpace,
+};
 pub use lab::Lab;
 pub use linear_to_image::*;
 pub use linear_to_image_u8::*;
+pub use luma::{gray_to_rgb, rgb_to_gray_f32, rgb_to_gray_u16, rgb_to_gray_u8, Luma};
 pub use luv::LCh;
 pub use luv::Luv;
+pub use packed::{
+    packed_to_rgba16, packed_to_rgba8, packed_to_rgba_f32, rgba16_to_packed, rgba8_to_packed,
+    rgba_f32_to_packed, ByteOrder, PackedFormat,
+};
+pub use planar::{interleaved_to_planar, planar_to_interleaved};
+pub use planar_converters::{
+    lab_planar_to_rgb, linear_planar_to_rgb, oklab_planar_to_rgb, rgb_to_lab_planar,
+    rgb_to_linear_planar, rgb_to_oklab_planar,
+};
 pub use rgb::Rgb;
 pub use rgba::Rgb565;
 pub use rgba::Rgba;
@@ -107,12 +238,14 @@ pub use rgba::ToRgba1010102;
 pub use rgba::ToRgba8;
 pub use rgba::ToRgbaF16;
 pub use rgba::ToRgbaF32;
+pub use swizzle::swizzle_channels;
 pub use xyb::Xyb;
 pub use xyz::Xyz;
 pub use xyz_lab_to_image::lab_to_bgr;
 pub use xyz_lab_to_image::lab_to_rgb;
 pub use xyz_lab_to_image::lab_to_srgb;
 pub use xyz_lab_to_image::laba_to_srgb;
+pub use xyz_lab_to_image::laba_to_srgb_premultiplied;
 pub use xyz_lab_to_image::lch_to_bgr;
 pub use xyz_lab_to_image::lch_to_rgb;
 pub use xyz_lab_to_image::luv_to_bgr;
@@ -120,18 +253,36 @@ pub use xyz_lab_to_image::luv_to_rgb;
 pub use xyz_lab_to_image::xyz_to_bgr;
 pub use xyz_lab_to_image::xyz_to_rgb;
 pub use xyz_lab_to_image::xyz_to_srgb;
+pub use xyz_lab_to_image::xyza_to_argb;
 pub use xyz_lab_to_image::xyza_to_rgba;
 pub use xyz_transform::*;
+pub use xyza_laba_to_image::lab_with_alpha_to_abgr;
+pub use xyza_laba_to_image::lab_with_alpha_to_abgr_premultiplied;
+pub use xyza_laba_to_image::lab_with_alpha_to_argb;
+pub use xyza_laba_to_image::lab_with_alpha_to_argb_premultiplied;
 pub use xyza_laba_to_image::lab_with_alpha_to_bgra;
+pub use xyza_laba_to_image::lab_with_alpha_to_bgra_premultiplied;
 pub use xyza_laba_to_image::lab_with_alpha_to_rgba;
+pub use xyza_laba_to_image::lab_with_alpha_to_rgba_premultiplied;
+pub use xyza_laba_to_image::lch_with_alpha_to_abgr;
+pub use xyza_laba_to_image::lch_with_alpha_to_argb;
 pub use xyza_laba_to_image::lch_with_alpha_to_bgra;
 pub use xyza_laba_to_image::lch_with_alpha_to_rgba;
+pub use xyza_laba_to_image::luv_with_alpha_to_abgr;
+pub use xyza_laba_to_image::luv_with_alpha_to_argb;
 pub use xyza_laba_to_image::luv_with_alpha_to_bgra;
 pub use xyza_laba_to_image::luv_with_alpha_to_rgba;
+pub use xyza_laba_to_image::xyz_with_alpha_to_abgr;
+pub use xyza_laba_to_image::xyz_with_alpha_to_argb;
 pub use xyza_laba_to_image::xyz_with_alpha_to_bgra;
 pub use xyza_laba_to_image::xyz_with_alpha_to_rgba;
+pub use ycbcr::{rgb_to_ycbcr_planar, ycbcr_planar_to_rgb, YCbCrMatrix, YCbCrRange};
 
 pub use euclidean::EuclideanDistance;
+pub use image_to_jzazbz::abgr_to_jzazbz;
+pub use image_to_jzazbz::abgr_to_jzczhz;
+pub use image_to_jzazbz::argb_to_jzazbz;
+pub use image_to_jzazbz::argb_to_jzczhz;
 pub use image_to_jzazbz::bgr_to_jzazbz;
 pub use image_to_jzazbz::bgr_to_jzczhz;
 pub use image_to_jzazbz::bgra_to_jzazbz;
@@ -140,49 +291,100 @@ pub use image_to_jzazbz::rgb_to_jzazbz;
 pub use image_to_jzazbz::rgb_to_jzczhz;
 pub use image_to_jzazbz::rgba_to_jzazbz;
 pub use image_to_jzazbz::rgba_to_jzczhz;
+pub use image_to_lalphabeta::abgr_to_lalphabeta;
+pub use image_to_lalphabeta::argb_to_lalphabeta;
 pub use image_to_lalphabeta::bgr_to_lalphabeta;
 pub use image_to_lalphabeta::bgra_to_lalphabeta;
 pub use image_to_lalphabeta::rgb_to_lalphabeta;
 pub use image_to_lalphabeta::rgba_to_lalphabeta;
+pub use image_to_oklab::abgr_to_oklab;
+pub use image_to_oklab::abgr_to_oklab_premultiplied;
+pub use image_to_oklab::abgr_to_oklch;
+pub use image_to_oklab::abgr_to_oklch_premultiplied;
+pub use image_to_oklab::argb_to_oklab;
+pub use image_to_oklab::argb_to_oklab_premultiplied;
+pub use image_to_oklab::argb_to_oklch;
+pub use image_to_oklab::argb_to_oklch_premultiplied;
 pub use image_to_oklab::bgr_to_oklab;
 pub use image_to_oklab::bgr_to_oklch;
 pub use image_to_oklab::bgra_to_oklab;
+pub use image_to_oklab::bgra_to_oklab_premultiplied;
 pub use image_to_oklab::bgra_to_oklch;
+pub use image_to_oklab::bgra_to_oklch_premultiplied;
 pub use image_to_oklab::rgb_to_oklab;
 pub use image_to_oklab::rgb_to_oklch;
 pub use image_to_oklab::rgba_to_oklab;
+pub use image_to_oklab::rgba_to_oklab_premultiplied;
 pub use image_to_oklab::rgba_to_oklch;
+pub use image_to_oklab::rgba_to_oklch_premultiplied;
 pub use image_to_sigmoidal::bgra_to_sigmoidal;
 pub use image_to_sigmoidal::rgb_to_sigmoidal;
 pub use image_to_sigmoidal::rgba_to_sigmoidal;
 pub use jzazbz::Jzazbz;
+pub use jzazbz_to_image::jzazbz_to_abgr;
+pub use jzazbz_to_image::jzazbz_to_argb;
 pub use jzazbz_to_image::jzazbz_to_bgr;
 pub use jzazbz_to_image::jzazbz_to_bgra;
 pub use jzazbz_to_image::jzazbz_to_rgb;
 pub use jzazbz_to_image::jzazbz_to_rgba;
+pub use jzazbz_to_image::jzczhz_to_abgr;
+pub use jzazbz_to_image::jzczhz_to_argb;
 pub use jzazbz_to_image::jzczhz_to_bgr;
 pub use jzazbz_to_image::jzczhz_to_bgra;
 pub use jzazbz_to_image::jzczhz_to_rgb;
 pub use jzazbz_to_image::jzczhz_to_rgba;
 pub use jzczhz::Jzczhz;
 pub use lalphabeta::LAlphaBeta;
+pub use lalphabeta_to_image::lalphabeta_to_abgr;
+pub use lalphabeta_to_image::lalphabeta_to_argb;
 pub use lalphabeta_to_image::lalphabeta_to_bgr;
 pub use lalphabeta_to_image::lalphabeta_to_bgra;
 pub use lalphabeta_to_image::lalphabeta_to_rgb;
 pub use lalphabeta_to_image::lalphabeta_to_rgba;
 pub use oklab::Oklab;
+pub use oklab_to_image::oklab_to_abgr;
+pub use oklab_to_image::oklab_to_abgr_premultiplied;
+pub use oklab_to_image::oklab_to_argb;
+pub use oklab_to_image::oklab_to_argb_premultiplied;
 pub use oklab_to_image::oklab_to_bgr;
 pub use oklab_to_image::oklab_to_bgra;
+pub use oklab_to_image::oklab_to_bgra_premultiplied;
 pub use oklab_to_image::oklab_to_rgb;
 pub use oklab_to_image::oklab_to_rgba;
+pub use oklab_to_image::oklab_to_rgba_premultiplied;
+pub use oklab_to_image::oklch_to_abgr;
+pub use oklab_to_image::oklch_to_abgr_premultiplied;
+pub use oklab_to_image::oklch_to_argb;
+pub use oklab_to_image::oklch_to_argb_premultiplied;
 pub use oklab_to_image::oklch_to_bgr;
 pub use oklab_to_image::oklch_to_bgra;
+pub use oklab_to_image::oklch_to_bgra_premultiplied;
 pub use oklab_to_image::oklch_to_rgb;
 pub use oklab_to_image::oklch_to_rgba;
+pub use oklab_to_image::oklch_to_rgba_premultiplied;
 pub use oklch::Oklch;
+pub use palette::{
+    rgba_extract_palette, PaletteColor, PaletteOptions, PaletteSpace, QuantizationMethod,
+};
+pub use palette_mapping::{
+    bgra_apply_palette, bgra_to_palette_indices, rgba_apply_palette, rgba_to_palette_indices,
+    Dithering, PaletteDistance, PaletteMapper,
+};
+pub use premultiply::*;
 pub use rgb_expand::*;
 pub use sigmoidal::Sigmoidal;
 pub use sigmoidal_to_image::sigmoidal_to_bgra;
 pub use sigmoidal_to_image::sigmoidal_to_rgb;
 pub use sigmoidal_to_image::sigmoidal_to_rgba;
+pub use spectral::{
+    Spectrum, StandardIlluminant, StandardObserver, CMF_END_WAVELENGTH, CMF_START_WAVELENGTH,
+};
 pub use taxicab::TaxicabDistance;
+pub use threading::{
+    current_threading, with_threading, ThreadingPolicy, DEFAULT_SERIAL_BELOW_PIXELS,
+};
+pub use tone_mapping::{
+    bgr_u16_tone_map_to_srgb, bgra_u16_tone_map_to_srgb, rgb_u16_tone_map_to_srgb,
+    rgba_u16_tone_map_to_srgb, HdrTransferFunction, ToneMapping, ToneMappingMode,
+    ToneMappingOperator,
+};
@@ -4,17 +4,18 @@
  * // Use of this source code is governed by a BSD-style
  * // license that can be found in the LICENSE file.
  */
-use crate::gamma_curves::TransferFunction;
+use crate::gamma_curves::{gamma_table_u8, TransferFunction};
 use crate::image::ImageConfiguration;
+use crate::threading::{current_threading, ForEachRow, ThreadingPolicy};
 use crate::Rgb;
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 use std::slice;
 
 #[allow(clippy::type_complexity)]
-fn linear_to_gamma_channels<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
+pub(crate) fn linear_to_gamma_channels<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
     src: &[f32],
     src_stride: u32,
     dst: &mut [u8],
@@ -22,6 +23,32 @@ fn linear_to_gamma_channels<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: b
     width: u32,
     height: u32,
     transfer_function: TransferFunction,
+) {
+    linear_to_gamma_channels_impl::<CHANNELS_CONFIGURATION, USE_ALPHA>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        &gamma_table_u8(transfer_function),
+        &current_threading(),
+    );
+}
+
+/// Converts image with precomputed gamma table
+pub(crate) fn linear_to_gamma_channels_impl<
+    const CHANNELS_CONFIGURATION: u8,
+    const USE_ALPHA: bool,
+>(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    lut_table: &[u8],
+    policy: &ThreadingPolicy,
 ) {
     let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
     if USE_ALPHA && !image_configuration.has_alpha() {
@@ -30,11 +57,6 @@ fn linear_to_gamma_channels<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: b
 
     let channels = image_configuration.get_channels_count();
 
-    let mut lut_table = vec![0u8; 2049];
-    for (i, lut) in lut_table.iter_mut().enumerate() {
-        *lut = (transfer_function.gamma(i as f32 * (1. / 2048.0)) * 255.).min(255.) as u8;
-    }
-
     let src_slice_safe_align = unsafe {
         slice::from_raw_parts(
             src.as_ptr() as *const u8,
@@ -56,7 +78,7 @@ fn linear_to_gamma_channels<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: b
             .zip(src_slice_safe_align.chunks_exact(src_stride as usize));
     }
 
-    iter.for_each(|(dst, src)| unsafe {
+    iter.for_each_row_with(policy, width as usize, |(dst, src)| unsafe {
         let mut _cx = 0usize;
 
         let src_ptr = src.as_ptr() as *const f32;
@@ -164,6 +186,36 @@ pub fn linear_to_rgba(
     );
 }
 
+/// This function converts Linear ARGB to ARGB, Alpha channel will be denormalized. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains Linear ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn linear_to_argb(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    linear_to_gamma_channels::<{ ImageConfiguration::Argb as u8 }, true>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+    );
+}
+
 /// This function converts Linear BGRA to BGRA, Alpha channel will de dernormalizaed. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -194,6 +246,36 @@ pub fn linear_to_bgra(
     );
 }
 
+/// This function converts Linear ABGR to ABGR, Alpha channel will de dernormalizaed. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains Linear ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive Gamma ABGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn linear_to_abgr(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    linear_to_gamma_channels::<{ ImageConfiguration::Abgr as u8 }, true>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+    );
+}
+
 /// This function converts Linear BGR to Gamma BGR. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -223,3 +305,63 @@ pub fn linear_to_bgr(
         transfer_function,
     );
 }
+
+/// This function converts Linear Gray to Gamma Gray. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains Linear Gray data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive Gray data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn linear_to_gray(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    linear_to_gamma_channels::<{ ImageConfiguration::Gray as u8 }, false>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+    );
+}
+
+/// This function converts Linear Gray with alpha to Gamma Gray with alpha. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains Linear Gray with alpha data, alpha is normalized
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive Gray with alpha data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn linear_to_gray_alpha(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    linear_to_gamma_channels::<{ ImageConfiguration::GrayAlpha as u8 }, true>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+    );
+}
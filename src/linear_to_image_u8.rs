@@ -7,14 +7,16 @@
 
 use crate::gamma_curves::TransferFunction;
 use crate::image::ImageConfiguration;
+#[cfg(feature = "rayon")]
+use crate::threading::ForEachRow;
 use crate::Rgb;
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 
 #[allow(clippy::type_complexity)]
-fn linear_to_gamma_channels<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
+pub(crate) fn linear_to_gamma_channels<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
     src: &[u8],
     src_stride: u32,
     dst: &mut [u8],
@@ -39,7 +41,7 @@ fn linear_to_gamma_channels<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: b
     {
         dst.par_chunks_exact_mut(dst_stride as usize)
             .zip(src.par_chunks_exact(src_stride as usize))
-            .for_each(|(dst, src)| unsafe {
+            .for_each_row(width as usize, |(dst, src)| unsafe {
                 let mut _cx = 0usize;
 
                 for x in _cx..width as usize {
@@ -163,6 +165,36 @@ pub fn linear_u8_to_rgba(
     );
 }
 
+/// This function converts Linear ARGB to ARGB, Alpha channel will be denormalized. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains Linear ARGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn linear_u8_to_argb(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    linear_to_gamma_channels::<{ ImageConfiguration::Argb as u8 }, true>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+    );
+}
+
 /// This function converts Linear BGRA to BGRA, Alpha channel will de dernormalizaed. This is much more effective than naive direct transformation
 ///
 /// # Arguments
@@ -193,6 +225,36 @@ pub fn linear_u8_to_bgra(
     );
 }
 
+/// This function converts Linear ABGR to ABGR, Alpha channel will de dernormalizaed. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains Linear ABGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive Gamma ABGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from gamma to linear space. If you don't have specific pick `Srgb`
+pub fn linear_u8_to_abgr(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    linear_to_gamma_channels::<{ ImageConfiguration::Abgr as u8 }, true>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+    );
+}
+
 /// This function converts Linear BGR to Gamma BGR. This is much more effective than naive direct transformation
 ///
 /// # Arguments
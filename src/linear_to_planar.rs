@@ -5,9 +5,11 @@
  * // license that can be found in the LICENSE file.
  */
 
+#[cfg(feature = "rayon")]
+use crate::threading::ForEachRow;
 use crate::TransferFunction;
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 use std::slice;
@@ -38,7 +40,7 @@ fn linear_to_gamma_channels(
     {
         dst.par_chunks_exact_mut(dst_stride as usize)
             .zip(src_slice_safe_align.par_chunks_exact(src_stride as usize))
-            .for_each(|(dst, src)| unsafe {
+            .for_each_row(width as usize, |(dst, src)| unsafe {
                 let mut _cx = 0usize;
 
                 let src_ptr = src.as_ptr() as *const f32;
@@ -0,0 +1,558 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::image::ImageConfiguration;
+use crate::image_view::check_image;
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use crate::neon::{neon_rgb_to_luma_f32, neon_rgb_to_luma_u16, neon_rgb_to_luma_u8};
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+use crate::sse::{sse_rgb_to_luma_f32, sse_rgb_to_luma_u16, sse_rgb_to_luma_u8};
+use crate::swizzle::swizzle_channels;
+use crate::threading::ForEachRow;
+use crate::{ImageSample, TransferFunction};
+#[cfg(feature = "rayon")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "rayon")]
+use rayon::prelude::{ParallelSlice, ParallelSliceMut};
+
+/// Declares how RGB is reduced into single gray channel
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Luma {
+    /// Rec. ITU-R BT.601 luma `0.299 R' + 0.587 G' + 0.114 B'` of transfer encoded values
+    Bt601,
+    /// Rec. ITU-R BT.709 luma `0.2126 R' + 0.7152 G' + 0.0722 B'` of transfer encoded values
+    Bt709,
+    /// Rec. ITU-R BT.2020 luma `0.2627 R' + 0.6780 G' + 0.0593 B'` of transfer encoded values
+    Bt2020,
+    /// Relative luminance Y of linearized RGB encoded back with the same transfer function.
+    /// Coefficients are taken from second row of `matrix` which converts linear RGB into XYZ
+    Luminance {
+        matrix: [[f32; 3]; 3],
+        transfer_function: TransferFunction,
+    },
+}
+
+impl Luma {
+    /// Returns weights applied directly to stored RGB values, `None` for [Luma::Luminance]
+    pub const fn weights(&self) -> Option<[f32; 3]> {
+        match self {
+            Luma::Bt601 => Some([0.299f32, 0.587f32, 0.114f32]),
+            Luma::Bt709 => Some([0.2126f32, 0.7152f32, 0.0722f32]),
+            Luma::Bt2020 => Some([0.2627f32, 0.6780f32, 0.0593f32]),
+            Luma::Luminance { .. } => None,
+        }
+    }
+}
+
+type LumaRowHandler<T> = unsafe fn(usize, *const T, *mut T, usize, [f32; 3], T) -> usize;
+
+macro_rules! with_luma_layouts {
+    ($src_layout:expr, $dst_layout:expr, $func:ident, ($($args:expr),* $(,)?)) => {
+        match $src_layout {
+            ImageConfiguration::Rgb => with_luma_layouts!(@target Rgb, $dst_layout, $func, ($($args),*)),
+            ImageConfiguration::Rgba => with_luma_layouts!(@target Rgba, $dst_layout, $func, ($($args),*)),
+            ImageConfiguration::Bgra => with_luma_layouts!(@target Bgra, $dst_layout, $func, ($($args),*)),
+            ImageConfiguration::Bgr => with_luma_layouts!(@target Bgr, $dst_layout, $func, ($($args),*)),
+            ImageConfiguration::Argb => with_luma_layouts!(@target Argb, $dst_layout, $func, ($($args),*)),
+            ImageConfiguration::Abgr => with_luma_layouts!(@target Abgr, $dst_layout, $func, ($($args),*)),
+            ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                panic!("Source of luma must be a colour layout, but {:?} was given", $src_layout)
+            }
+        }
+    };
+    (@target $src:ident, $dst_layout:expr, $func:ident, ($($args:expr),*)) => {
+        match $dst_layout {
+            ImageConfiguration::Gray => $func::<
+                { ImageConfiguration::$src as u8 },
+                { ImageConfiguration::Gray as u8 },
+            >($($args),*),
+            ImageConfiguration::GrayAlpha => $func::<
+                { ImageConfiguration::$src as u8 },
+                { ImageConfiguration::GrayAlpha as u8 },
+            >($($args),*),
+            _ => panic!("Luma destination must be Gray or GrayAlpha, but {:?} was given", $dst_layout),
+        }
+    };
+}
+
+/// Rounds half up non negative value and saturates to `max`, same as SIMD handlers do
+#[inline(always)]
+fn quantize_luma(value: f32, max: u32) -> u32 {
+    ((value + 0.5f32) as u32).min(max)
+}
+
+#[inline(always)]
+fn weighted_luma(r: f32, g: f32, b: f32, weights: [f32; 3]) -> f32 {
+    r * weights[0] + g * weights[1] + b * weights[2]
+}
+
+#[allow(clippy::too_many_arguments)]
+fn luma_rows<T: ImageSample, const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    src: &[T],
+    src_stride: u32,
+    dst: &mut [T],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    max: T,
+    weights: [f32; 3],
+    row_handler: Option<LumaRowHandler<T>>,
+    luma: impl Fn(T, T, T) -> T + Send + Sync,
+) {
+    if width == 0 || height == 0 {
+        return;
+    }
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let target: ImageConfiguration = TARGET.into();
+    let src_row_length = check_image::<T>(
+        src.len(),
+        src_stride,
+        width,
+        height,
+        image_configuration,
+        "Source",
+    );
+    let dst_row_length =
+        check_image::<T>(dst.len(), dst_stride, width, height, target, "Destination");
+    let src_channels = image_configuration.get_channels_count();
+    let dst_channels = target.get_channels_count();
+    let width = width as usize;
+    let src_row_samples = width * src_channels;
+    let dst_row_samples = width * dst_channels;
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = dst
+            .par_chunks_mut(dst_row_length)
+            .zip(src.par_chunks(src_row_length))
+            .take(height as usize);
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = dst
+            .chunks_mut(dst_row_length)
+            .zip(src.chunks(src_row_length))
+            .take(height as usize);
+    }
+
+    iter.for_each_row(width, |(dst_row, src_row)| {
+        let mut _cx = 0usize;
+
+        if let Some(handler) = row_handler {
+            unsafe {
+                _cx = handler(
+                    _cx,
+                    src_row.as_ptr(),
+                    dst_row.as_mut_ptr(),
+                    width,
+                    weights,
+                    max,
+                );
+            }
+        }
+
+        for (src_pixel, dst_pixel) in src_row[_cx * src_channels..src_row_samples]
+            .chunks_exact(src_channels)
+            .zip(dst_row[_cx * dst_channels..dst_row_samples].chunks_exact_mut(dst_channels))
+        {
+            dst_pixel[0] = luma(
+                src_pixel[image_configuration.get_r_channel_offset()],
+                src_pixel[image_configuration.get_g_channel_offset()],
+                src_pixel[image_configuration.get_b_channel_offset()],
+            );
+            if target.has_alpha() {
+                dst_pixel[1] = if image_configuration.has_alpha() {
+                    src_pixel[image_configuration.get_a_channel_offset()]
+                } else {
+                    max
+                };
+            }
+        }
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn rgb_to_gray_u8_impl<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    luma: Luma,
+) {
+    match luma.weights() {
+        Some(weights) => {
+            let mut _row_handler: Option<LumaRowHandler<u8>> = None;
+
+            #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+            {
+                _row_handler = Some(neon_rgb_to_luma_u8::<CHANNELS_CONFIGURATION, TARGET>);
+            }
+
+            #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+            if std::arch::is_x86_feature_detected!("sse4.1") {
+                _row_handler = Some(sse_rgb_to_luma_u8::<CHANNELS_CONFIGURATION, TARGET>);
+            }
+
+            luma_rows::<u8, CHANNELS_CONFIGURATION, TARGET>(
+                src,
+                src_stride,
+                dst,
+                dst_stride,
+                width,
+                height,
+                u8::MAX,
+                weights,
+                _row_handler,
+                |r, g, b| {
+                    quantize_luma(
+                        weighted_luma(r as f32, g as f32, b as f32, weights),
+                        u8::MAX as u32,
+                    ) as u8
+                },
+            );
+        }
+        None => {
+            let Luma::Luminance {
+                matrix,
+                transfer_function,
+            } = luma
+            else {
+                unreachable!()
+            };
+            let row = matrix[1];
+            let mut linear_table = [0f32; 256];
+            for (i, value) in linear_table.iter_mut().enumerate() {
+                *value = transfer_function.linearize(i as f32 * (1f32 / 255f32));
+            }
+            luma_rows::<u8, CHANNELS_CONFIGURATION, TARGET>(
+                src,
+                src_stride,
+                dst,
+                dst_stride,
+                width,
+                height,
+                u8::MAX,
+                row,
+                None,
+                |r, g, b| {
+                    let y = weighted_luma(
+                        linear_table[r as usize],
+                        linear_table[g as usize],
+                        linear_table[b as usize],
+                        row,
+                    );
+                    quantize_luma(
+                        transfer_function.gamma(y.max(0f32)) * 255f32,
+                        u8::MAX as u32,
+                    ) as u8
+                },
+            );
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn rgb_to_gray_u16_impl<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+    luma: Luma,
+) {
+    let max_colors = (1u32 << bit_depth) - 1;
+    match luma.weights() {
+        Some(weights) => {
+            let mut _row_handler: Option<LumaRowHandler<u16>> = None;
+
+            #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+            {
+                _row_handler = Some(neon_rgb_to_luma_u16::<CHANNELS_CONFIGURATION, TARGET>);
+            }
+
+            #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+            if std::arch::is_x86_feature_detected!("sse4.1") {
+                _row_handler = Some(sse_rgb_to_luma_u16::<CHANNELS_CONFIGURATION, TARGET>);
+            }
+
+            luma_rows::<u16, CHANNELS_CONFIGURATION, TARGET>(
+                src,
+                src_stride,
+                dst,
+                dst_stride,
+                width,
+                height,
+                max_colors as u16,
+                weights,
+                _row_handler,
+                |r, g, b| {
+                    quantize_luma(
+                        weighted_luma(r as f32, g as f32, b as f32, weights),
+                        max_colors,
+                    ) as u16
+                },
+            );
+        }
+        None => {
+            let Luma::Luminance {
+                matrix,
+                transfer_function,
+            } = luma
+            else {
+                unreachable!()
+            };
+            let row = matrix[1];
+            let scale = 1f32 / max_colors as f32;
+            let linear_table = (0..=max_colors)
+                .map(|i| transfer_function.linearize(i as f32 * scale))
+                .collect::<Vec<f32>>();
+            luma_rows::<u16, CHANNELS_CONFIGURATION, TARGET>(
+                src,
+                src_stride,
+                dst,
+                dst_stride,
+                width,
+                height,
+                max_colors as u16,
+                row,
+                None,
+                |r, g, b| {
+                    let linearize = |v: u16| linear_table[(v as u32).min(max_colors) as usize];
+                    let y = weighted_luma(linearize(r), linearize(g), linearize(b), row);
+                    quantize_luma(
+                        transfer_function.gamma(y.max(0f32)) * max_colors as f32,
+                        max_colors,
+                    ) as u16
+                },
+            );
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn rgb_to_gray_f32_impl<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    luma: Luma,
+) {
+    match luma.weights() {
+        Some(weights) => {
+            let mut _row_handler: Option<LumaRowHandler<f32>> = None;
+
+            #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+            {
+                _row_handler = Some(neon_rgb_to_luma_f32::<CHANNELS_CONFIGURATION, TARGET>);
+            }
+
+            #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+            if std::arch::is_x86_feature_detected!("sse4.1") {
+                _row_handler = Some(sse_rgb_to_luma_f32::<CHANNELS_CONFIGURATION, TARGET>);
+            }
+
+            luma_rows::<f32, CHANNELS_CONFIGURATION, TARGET>(
+                src,
+                src_stride,
+                dst,
+                dst_stride,
+                width,
+                height,
+                1f32,
+                weights,
+                _row_handler,
+                |r, g, b| weighted_luma(r, g, b, weights),
+            );
+        }
+        None => {
+            let Luma::Luminance {
+                matrix,
+                transfer_function,
+            } = luma
+            else {
+                unreachable!()
+            };
+            let row = matrix[1];
+            luma_rows::<f32, CHANNELS_CONFIGURATION, TARGET>(
+                src,
+                src_stride,
+                dst,
+                dst_stride,
+                width,
+                height,
+                1f32,
+                row,
+                None,
+                |r, g, b| {
+                    let y = weighted_luma(
+                        transfer_function.linearize(r),
+                        transfer_function.linearize(g),
+                        transfer_function.linearize(b),
+                        row,
+                    );
+                    transfer_function.gamma(y)
+                },
+            );
+        }
+    }
+}
+
+/// This function converts RGB into gray or gray with alpha. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains RGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_layout` - Colour layout of the source, any of 3 and 4 channels layouts
+/// * `dst` - A mutable slice to receive gray data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_layout` - [ImageConfiguration::Gray] or [ImageConfiguration::GrayAlpha], alpha is copied or filled with 255
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `luma` - Coefficients of the reduction, see [Luma]
+///
+/// # Panics
+/// Panics if layouts are not colour and gray ones or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn rgb_to_gray_u8(
+    src: &[u8],
+    src_stride: u32,
+    src_layout: ImageConfiguration,
+    dst: &mut [u8],
+    dst_stride: u32,
+    dst_layout: ImageConfiguration,
+    width: u32,
+    height: u32,
+    luma: Luma,
+) {
+    with_luma_layouts!(
+        src_layout,
+        dst_layout,
+        rgb_to_gray_u8_impl,
+        (src, src_stride, dst, dst_stride, width, height, luma)
+    );
+}
+
+/// This function converts RGB with 16 bit storage into gray or gray with alpha. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains RGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_layout` - Colour layout of the source, any of 3 and 4 channels layouts
+/// * `dst` - A mutable slice to receive gray data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_layout` - [ImageConfiguration::Gray] or [ImageConfiguration::GrayAlpha], alpha is copied or filled with maximum value
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of the samples, 1..=16
+/// * `luma` - Coefficients of the reduction, see [Luma]
+///
+/// # Panics
+/// Panics if layouts are not colour and gray ones, bit depth is invalid or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn rgb_to_gray_u16(
+    src: &[u16],
+    src_stride: u32,
+    src_layout: ImageConfiguration,
+    dst: &mut [u16],
+    dst_stride: u32,
+    dst_layout: ImageConfiguration,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+    luma: Luma,
+) {
+    if bit_depth == 0 || bit_depth > 16 {
+        panic!("Bit depth must be in 1..=16, but {} was given", bit_depth);
+    }
+    with_luma_layouts!(
+        src_layout,
+        dst_layout,
+        rgb_to_gray_u16_impl,
+        (src, src_stride, dst, dst_stride, width, height, bit_depth, luma)
+    );
+}
+
+/// This function converts RGB with f32 storage into gray or gray with alpha. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains RGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_layout` - Colour layout of the source, any of 3 and 4 channels layouts
+/// * `dst` - A mutable slice to receive gray data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_layout` - [ImageConfiguration::Gray] or [ImageConfiguration::GrayAlpha], alpha is copied or filled with 1.0
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `luma` - Coefficients of the reduction, see [Luma]
+///
+/// # Panics
+/// Panics if layouts are not colour and gray ones or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn rgb_to_gray_f32(
+    src: &[f32],
+    src_stride: u32,
+    src_layout: ImageConfiguration,
+    dst: &mut [f32],
+    dst_stride: u32,
+    dst_layout: ImageConfiguration,
+    width: u32,
+    height: u32,
+    luma: Luma,
+) {
+    with_luma_layouts!(
+        src_layout,
+        dst_layout,
+        rgb_to_gray_f32_impl,
+        (src, src_stride, dst, dst_stride, width, height, luma)
+    );
+}
+
+/// This function expands gray or gray with alpha into any of 3 and 4 channels layouts,
+/// gray is replicated into every colour channel.
+/// Works for `u8`, `u16` and `f32` samples.
+///
+/// # Arguments
+/// * `src` - A slice contains gray data
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_layout` - [ImageConfiguration::Gray] or [ImageConfiguration::GrayAlpha]
+/// * `dst` - A mutable slice to receive RGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_layout` - Colour layout of the destination
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `alpha` - Alpha value written when source has no alpha, for example 255 for `u8` or 1.0 for `f32`
+///
+/// # Panics
+/// Panics if layouts are not gray and colour ones or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn gray_to_rgb<T: ImageSample>(
+    src: &[T],
+    src_stride: u32,
+    src_layout: ImageConfiguration,
+    dst: &mut [T],
+    dst_stride: u32,
+    dst_layout: ImageConfiguration,
+    width: u32,
+    height: u32,
+    alpha: T,
+) {
+    if !src_layout.is_gray() || dst_layout.is_gray() {
+        panic!(
+            "Gray expansion works from gray into colour layouts, but {:?} into {:?} was given",
+            src_layout, dst_layout
+        );
+    }
+    swizzle_channels(
+        src, src_stride, src_layout, dst, dst_stride, dst_layout, width, height, alpha,
+    );
+}
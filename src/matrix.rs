@@ -0,0 +1,75 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::utils::mlaf;
+
+/// Multiplies two 3x3 matrices
+#[inline]
+pub(crate) fn matrix_mul(a: &[[f32; 3]; 3], b: &[[f32; 3]; 3]) -> [[f32; 3]; 3] {
+    let mut product = [[0f32; 3]; 3];
+    for (i, row) in product.iter_mut().enumerate() {
+        for (j, dst) in row.iter_mut().enumerate() {
+            *dst = mlaf(mlaf(a[i][0] * b[0][j], a[i][1], b[1][j]), a[i][2], b[2][j]);
+        }
+    }
+    product
+}
+
+/// Multiplies 3x3 matrix by a vector
+#[inline(always)]
+pub(crate) fn matrix_vec_mul(matrix: &[[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    [
+        mlaf(
+            mlaf(matrix[0][0] * v[0], matrix[0][1], v[1]),
+            matrix[0][2],
+            v[2],
+        ),
+        mlaf(
+            mlaf(matrix[1][0] * v[0], matrix[1][1], v[1]),
+            matrix[1][2],
+            v[2],
+        ),
+        mlaf(
+            mlaf(matrix[2][0] * v[0], matrix[2][1], v[1]),
+            matrix[2][2],
+            v[2],
+        ),
+    ]
+}
+
+/// Inverts 3x3 matrix, returns `None` for singular matrix
+pub(crate) fn matrix_inverse(m: &[[f32; 3]; 3]) -> Option<[[f32; 3]; 3]> {
+    let m = [
+        [m[0][0] as f64, m[0][1] as f64, m[0][2] as f64],
+        [m[1][0] as f64, m[1][1] as f64, m[1][2] as f64],
+        [m[2][0] as f64, m[2][1] as f64, m[2][2] as f64],
+    ];
+    let c00 = m[1][1] * m[2][2] - m[1][2] * m[2][1];
+    let c01 = m[1][2] * m[2][0] - m[1][0] * m[2][2];
+    let c02 = m[1][0] * m[2][1] - m[1][1] * m[2][0];
+    let det = m[0][0] * c00 + m[0][1] * c01 + m[0][2] * c02;
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    let inv_det = 1. / det;
+    Some([
+        [
+            (c00 * inv_det) as f32,
+            ((m[0][2] * m[2][1] - m[0][1] * m[2][2]) * inv_det) as f32,
+            ((m[0][1] * m[1][2] - m[0][2] * m[1][1]) * inv_det) as f32,
+        ],
+        [
+            (c01 * inv_det) as f32,
+            ((m[0][0] * m[2][2] - m[0][2] * m[2][0]) * inv_det) as f32,
+            ((m[0][2] * m[1][0] - m[0][0] * m[1][2]) * inv_det) as f32,
+        ],
+        [
+            (c02 * inv_det) as f32,
+            ((m[0][1] * m[2][0] - m[0][0] * m[2][1]) * inv_det) as f32,
+            ((m[0][0] * m[1][1] - m[0][1] * m[1][0]) * inv_det) as f32,
+        ],
+    ])
+}
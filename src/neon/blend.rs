@@ -0,0 +1,82 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+
+use crate::blend::BlendMode;
+use std::arch::aarch64::*;
+
+#[inline(always)]
+unsafe fn neon_blend_function<const MODE: u8>(
+    backdrop: float32x4_t,
+    source: float32x4_t,
+) -> float32x4_t {
+    let mode: BlendMode = MODE.into();
+    match mode {
+        BlendMode::Multiply => vmulq_f32(backdrop, source),
+        BlendMode::Screen => vsubq_f32(vaddq_f32(backdrop, source), vmulq_f32(backdrop, source)),
+        BlendMode::Darken => vminq_f32(backdrop, source),
+        BlendMode::Lighten => vmaxq_f32(backdrop, source),
+        BlendMode::Difference => vabdq_f32(backdrop, source),
+        BlendMode::Exclusion => vsubq_f32(
+            vaddq_f32(backdrop, source),
+            vmulq_n_f32(vmulq_f32(backdrop, source), 2.),
+        ),
+        BlendMode::LinearBurn => vmaxq_f32(
+            vsubq_f32(vaddq_f32(backdrop, source), vdupq_n_f32(1.)),
+            vdupq_n_f32(0.),
+        ),
+        _ => source,
+    }
+}
+
+#[inline(always)]
+unsafe fn neon_composite_pixel<const MODE: u8>(
+    source: float32x4_t,
+    backdrop: float32x4_t,
+) -> float32x4_t {
+    let source_alpha = vdupq_laneq_f32::<3>(source);
+    let backdrop_alpha = vdupq_laneq_f32::<3>(backdrop);
+    let both = vmulq_f32(source_alpha, backdrop_alpha);
+    let alpha = vsubq_f32(vaddq_f32(source_alpha, backdrop_alpha), both);
+    let blended = neon_blend_function::<MODE>(backdrop, source);
+    let color = vaddq_f32(
+        vaddq_f32(
+            vmulq_f32(source, vsubq_f32(source_alpha, both)),
+            vmulq_f32(backdrop, vsubq_f32(backdrop_alpha, both)),
+        ),
+        vmulq_f32(blended, both),
+    );
+    let non_zero = vmvnq_u32(vceqzq_f32(alpha));
+    let color = vreinterpretq_f32_u32(vandq_u32(
+        vreinterpretq_u32_f32(vdivq_f32(color, alpha)),
+        non_zero,
+    ));
+    vcopyq_laneq_f32::<3, 3>(color, alpha)
+}
+
+/// Composites RGBA f32 rows with separable blend mode, result is written into backdrop
+#[inline(always)]
+pub unsafe fn neon_blend_row<const MODE: u8>(
+    start_cx: usize,
+    source: *const f32,
+    backdrop: *mut f32,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+
+    while cx + 2 < width {
+        let src_ptr = source.add(cx * 4);
+        let dst_ptr = backdrop.add(cx * 4);
+        let result0 = neon_composite_pixel::<MODE>(vld1q_f32(src_ptr), vld1q_f32(dst_ptr));
+        let result1 =
+            neon_composite_pixel::<MODE>(vld1q_f32(src_ptr.add(4)), vld1q_f32(dst_ptr.add(4)));
+        vst1q_f32(dst_ptr, result0);
+        vst1q_f32(dst_ptr.add(4), result1);
+        cx += 2;
+    }
+
+    cx
+}
@@ -0,0 +1,95 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::color_adjustments::{evaluate_lightness_table, AdjustmentParameters};
+use crate::image::ImageConfiguration;
+use crate::load_f32_and_deinterleave;
+use crate::neon::image_to_oklab::neon_linear_rgb_to_oklab;
+use crate::neon::math::prefer_vfmaq_f32;
+use crate::neon::oklab_to_image::neon_oklab_to_linear_rgb;
+use erydanos::vhypotq_fast_f32;
+use std::arch::aarch64::*;
+
+#[inline(always)]
+unsafe fn neon_evaluate_lightness(table: &[f32], lightness: float32x4_t) -> float32x4_t {
+    let mut lanes = [0f32; 4];
+    vst1q_f32(lanes.as_mut_ptr(), lightness);
+    for lane in lanes.iter_mut() {
+        *lane = evaluate_lightness_table(table, *lane);
+    }
+    vld1q_f32(lanes.as_ptr())
+}
+
+#[inline(always)]
+pub unsafe fn neon_adjust_colors_row<const CHANNELS_CONFIGURATION: u8>(
+    start_cx: usize,
+    width: u32,
+    row: *mut f32,
+    parameters: &AdjustmentParameters,
+) -> usize {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+    let mut cx = start_cx;
+
+    let exposure_gain = vdupq_n_f32(parameters.exposure_gain);
+    let hue_cos = vdupq_n_f32(parameters.hue_cos);
+    let hue_sin = vdupq_n_f32(parameters.hue_sin);
+    let chroma = vdupq_n_f32(parameters.chroma);
+    let vibrance = vdupq_n_f32(parameters.vibrance);
+    let recip_reference_chroma = vdupq_n_f32(parameters.recip_reference_chroma);
+    let ones = vdupq_n_f32(1f32);
+    let zeros = vdupq_n_f32(0f32);
+
+    while cx + 4 < width as usize {
+        let in_place_ptr = row.add(cx * channels);
+        let (r_chan, g_chan, b_chan, a_chan) =
+            load_f32_and_deinterleave!(in_place_ptr, image_configuration);
+
+        let r_chan = vmulq_f32(r_chan, exposure_gain);
+        let g_chan = vmulq_f32(g_chan, exposure_gain);
+        let b_chan = vmulq_f32(b_chan, exposure_gain);
+
+        let (mut lightness, a, b) = neon_linear_rgb_to_oklab(r_chan, g_chan, b_chan);
+
+        if let Some(table) = parameters.lightness {
+            lightness = neon_evaluate_lightness(table, lightness);
+        }
+
+        let mut scale = chroma;
+        if parameters.vibrance != 0. {
+            let saturation = vminq_f32(
+                vmulq_f32(vhypotq_fast_f32(a, b), recip_reference_chroma),
+                ones,
+            );
+            let remainder = vsubq_f32(ones, saturation);
+            let boost = vmaxq_f32(
+                prefer_vfmaq_f32(ones, vibrance, vmulq_f32(remainder, remainder)),
+                zeros,
+            );
+            scale = vmulq_f32(scale, boost);
+        }
+
+        let rotated_a = vmulq_f32(
+            vsubq_f32(vmulq_f32(a, hue_cos), vmulq_f32(b, hue_sin)),
+            scale,
+        );
+        let rotated_b = vmulq_f32(prefer_vfmaq_f32(vmulq_f32(b, hue_cos), a, hue_sin), scale);
+
+        let (r_chan, g_chan, b_chan) = neon_oklab_to_linear_rgb(lightness, rotated_a, rotated_b);
+
+        let pixels = match image_configuration {
+            ImageConfiguration::Bgra => float32x4x4_t(b_chan, g_chan, r_chan, a_chan),
+            ImageConfiguration::Argb => float32x4x4_t(a_chan, r_chan, g_chan, b_chan),
+            ImageConfiguration::Abgr => float32x4x4_t(a_chan, b_chan, g_chan, r_chan),
+            _ => float32x4x4_t(r_chan, g_chan, b_chan, a_chan),
+        };
+        vst4q_f32(in_place_ptr, pixels);
+
+        cx += 4;
+    }
+
+    cx
+}
@@ -77,27 +77,25 @@ pub unsafe fn neon_from_sigmoidal_row<const CHANNELS_CONFIGURATION: u8>(
 
         let dst_ptr = dst.add(cx * channels);
 
-        match image_configuration {
-            ImageConfiguration::Rgb => {
-                let rgb = uint8x16x3_t(r_row, g_row, b_row);
-                vst3q_u8(dst_ptr, rgb);
-            }
-            ImageConfiguration::Rgba | ImageConfiguration::Bgra => {
-                let a_row01 = vcombine_u16(vqmovn_u32(a_row0_), vqmovn_u32(a_row1_));
-                let a_row23 = vcombine_u16(vqmovn_u32(a_row2_), vqmovn_u32(a_row3_));
-                let a_row = vcombine_u8(vqmovn_u16(a_row01), vqmovn_u16(a_row23));
-                if image_configuration == ImageConfiguration::Rgba {
-                    let rgba = uint8x16x4_t(r_row, g_row, b_row, a_row);
-                    vst4q_u8(dst_ptr, rgba);
-                } else {
-                    let bgra = uint8x16x4_t(b_row, g_row, r_row, a_row);
-                    vst4q_u8(dst_ptr, bgra);
-                }
-            }
-            ImageConfiguration::Bgr => {
-                let bgr = uint8x16x3_t(b_row, g_row, r_row);
-                vst3q_u8(dst_ptr, bgr);
-            }
+        if image_configuration.has_alpha() {
+            let a_row01 = vcombine_u16(vqmovn_u32(a_row0_), vqmovn_u32(a_row1_));
+            let a_row23 = vcombine_u16(vqmovn_u32(a_row2_), vqmovn_u32(a_row3_));
+            let a_row = vcombine_u8(vqmovn_u16(a_row01), vqmovn_u16(a_row23));
+            let mut lanes = [a_row; 4];
+            lanes[image_configuration.get_r_channel_offset()] = r_row;
+            lanes[image_configuration.get_g_channel_offset()] = g_row;
+            lanes[image_configuration.get_b_channel_offset()] = b_row;
+            lanes[image_configuration.get_a_channel_offset()] = a_row;
+            vst4q_u8(
+                dst_ptr,
+                uint8x16x4_t(lanes[0], lanes[1], lanes[2], lanes[3]),
+            );
+        } else {
+            let mut lanes = [r_row; 3];
+            lanes[image_configuration.get_r_channel_offset()] = r_row;
+            lanes[image_configuration.get_g_channel_offset()] = g_row;
+            lanes[image_configuration.get_b_channel_offset()] = b_row;
+            vst3q_u8(dst_ptr, uint8x16x3_t(lanes[0], lanes[1], lanes[2]));
         }
 
         cx += 16;
@@ -126,26 +124,21 @@ pub unsafe fn neon_from_sigmoidal_row<const CHANNELS_CONFIGURATION: u8>(
 
         let dst_ptr = dst.add(cx * channels);
 
-        match image_configuration {
-            ImageConfiguration::Rgb => {
-                let rgb = uint8x8x3_t(r_row, g_row, b_row);
-                vst3_u8(dst_ptr, rgb);
-            }
-            ImageConfiguration::Rgba | ImageConfiguration::Bgra => {
-                let a_row01 = vcombine_u16(vqmovn_u32(a_row0_), vqmovn_u32(a_row1_));
-                let a_row = vqmovn_u16(a_row01);
-                if image_configuration == ImageConfiguration::Rgba {
-                    let rgba = uint8x8x4_t(r_row, g_row, b_row, a_row);
-                    vst4_u8(dst_ptr, rgba);
-                } else {
-                    let bgra = uint8x8x4_t(b_row, g_row, r_row, a_row);
-                    vst4_u8(dst_ptr, bgra);
-                }
-            }
-            ImageConfiguration::Bgr => {
-                let bgr = uint8x8x3_t(b_row, g_row, r_row);
-                vst3_u8(dst_ptr, bgr);
-            }
+        if image_configuration.has_alpha() {
+            let a_row01 = vcombine_u16(vqmovn_u32(a_row0_), vqmovn_u32(a_row1_));
+            let a_row = vqmovn_u16(a_row01);
+            let mut lanes = [a_row; 4];
+            lanes[image_configuration.get_r_channel_offset()] = r_row;
+            lanes[image_configuration.get_g_channel_offset()] = g_row;
+            lanes[image_configuration.get_b_channel_offset()] = b_row;
+            lanes[image_configuration.get_a_channel_offset()] = a_row;
+            vst4_u8(dst_ptr, uint8x8x4_t(lanes[0], lanes[1], lanes[2], lanes[3]));
+        } else {
+            let mut lanes = [r_row; 3];
+            lanes[image_configuration.get_r_channel_offset()] = r_row;
+            lanes[image_configuration.get_g_channel_offset()] = g_row;
+            lanes[image_configuration.get_b_channel_offset()] = b_row;
+            vst3_u8(dst_ptr, uint8x8x3_t(lanes[0], lanes[1], lanes[2]));
         }
 
         cx += 8;
@@ -40,21 +40,18 @@ pub unsafe fn neon_hsv_u16_to_image<
         let (h_chan, s_chan, v_chan, a_chan_lo);
         let src_ptr = ((src as *const u8).add(src_offset) as *const u16).add(cx * channels);
 
-        match image_configuration {
-            ImageConfiguration::Rgb | ImageConfiguration::Bgr => {
-                let hsv_pixel = vld3q_u16(src_ptr);
-                h_chan = hsv_pixel.0;
-                s_chan = hsv_pixel.1;
-                v_chan = hsv_pixel.2;
-                a_chan_lo = vdupq_n_u16(255);
-            }
-            ImageConfiguration::Rgba | ImageConfiguration::Bgra => {
-                let hsv_pixel = vld4q_u16(src_ptr);
-                h_chan = hsv_pixel.0;
-                s_chan = hsv_pixel.1;
-                v_chan = hsv_pixel.2;
-                a_chan_lo = hsv_pixel.3;
-            }
+        if image_configuration.has_alpha() {
+            let hsv_pixel = vld4q_u16(src_ptr);
+            h_chan = hsv_pixel.0;
+            s_chan = hsv_pixel.1;
+            v_chan = hsv_pixel.2;
+            a_chan_lo = hsv_pixel.3;
+        } else {
+            let hsv_pixel = vld3q_u16(src_ptr);
+            h_chan = hsv_pixel.0;
+            s_chan = hsv_pixel.1;
+            v_chan = hsv_pixel.2;
+            a_chan_lo = vdupq_n_u16(255);
         }
 
         let h_low = vcvtq_f32_u32(vmovl_u16(vget_low_u16(h_chan)));
@@ -81,21 +78,18 @@ pub unsafe fn neon_hsv_u16_to_image<
 
         let src_ptr = src_ptr.add(8 * channels);
         let (h_chan, s_chan, v_chan, a_chan_hi);
-        match image_configuration {
-            ImageConfiguration::Rgb | ImageConfiguration::Bgr => {
-                let hsv_pixel = vld3q_u16(src_ptr);
-                h_chan = hsv_pixel.0;
-                s_chan = hsv_pixel.1;
-                v_chan = hsv_pixel.2;
-                a_chan_hi = vdupq_n_u16(255);
-            }
-            ImageConfiguration::Rgba | ImageConfiguration::Bgra => {
-                let hsv_pixel = vld4q_u16(src_ptr);
-                h_chan = hsv_pixel.0;
-                s_chan = hsv_pixel.1;
-                v_chan = hsv_pixel.2;
-                a_chan_hi = hsv_pixel.3;
-            }
+        if image_configuration.has_alpha() {
+            let hsv_pixel = vld4q_u16(src_ptr);
+            h_chan = hsv_pixel.0;
+            s_chan = hsv_pixel.1;
+            v_chan = hsv_pixel.2;
+            a_chan_hi = hsv_pixel.3;
+        } else {
+            let hsv_pixel = vld3q_u16(src_ptr);
+            h_chan = hsv_pixel.0;
+            s_chan = hsv_pixel.1;
+            v_chan = hsv_pixel.2;
+            a_chan_hi = vdupq_n_u16(255);
         }
 
         let h_low = vcvtq_f32_u32(vmovl_u16(vget_low_u16(h_chan)));
@@ -126,23 +120,20 @@ pub unsafe fn neon_hsv_u16_to_image<
 
         if USE_ALPHA {
             let a_chan = vcombine_u8(vqmovn_u16(a_chan_lo), vqmovn_u16(a_chan_hi));
-            let pixel_set = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
-                    uint8x16x4_t(r_chan, g_chan, b_chan, a_chan)
-                }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
-                    uint8x16x4_t(b_chan, g_chan, r_chan, a_chan)
-                }
-            };
+            let mut lanes = [a_chan; 4];
+            lanes[image_configuration.get_r_channel_offset()] = r_chan;
+            lanes[image_configuration.get_g_channel_offset()] = g_chan;
+            lanes[image_configuration.get_b_channel_offset()] = b_chan;
+            lanes[image_configuration.get_a_channel_offset()] = a_chan;
+            let pixel_set = uint8x16x4_t(lanes[0], lanes[1], lanes[2], lanes[3]);
             vst4q_u8(dst_ptr.add(cx * channels), pixel_set);
         } else {
-            let pixel_set = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
-                    uint8x16x3_t(r_chan, g_chan, b_chan)
-                }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
-                    uint8x16x3_t(b_chan, g_chan, r_chan)
-                }
+            let pixel_set = if image_configuration.get_r_channel_offset()
+                < image_configuration.get_b_channel_offset()
+            {
+                uint8x16x3_t(r_chan, g_chan, b_chan)
+            } else {
+                uint8x16x3_t(b_chan, g_chan, r_chan)
             };
             vst3q_u8(dst_ptr.add(cx * channels), pixel_set);
         }
@@ -154,21 +145,18 @@ pub unsafe fn neon_hsv_u16_to_image<
         let (h_chan, s_chan, v_chan, a_chan);
         let src_ptr = ((src as *const u8).add(src_offset) as *const u16).add(cx * channels);
 
-        match image_configuration {
-            ImageConfiguration::Rgb | ImageConfiguration::Bgr => {
-                let hsv_pixel = vld3q_u16(src_ptr);
-                h_chan = hsv_pixel.0;
-                s_chan = hsv_pixel.1;
-                v_chan = hsv_pixel.2;
-                a_chan = vdupq_n_u16(255);
-            }
-            ImageConfiguration::Rgba | ImageConfiguration::Bgra => {
-                let hsv_pixel = vld4q_u16(src_ptr);
-                h_chan = hsv_pixel.0;
-                s_chan = hsv_pixel.1;
-                v_chan = hsv_pixel.2;
-                a_chan = hsv_pixel.3;
-            }
+        if image_configuration.has_alpha() {
+            let hsv_pixel = vld4q_u16(src_ptr);
+            h_chan = hsv_pixel.0;
+            s_chan = hsv_pixel.1;
+            v_chan = hsv_pixel.2;
+            a_chan = hsv_pixel.3;
+        } else {
+            let hsv_pixel = vld3q_u16(src_ptr);
+            h_chan = hsv_pixel.0;
+            s_chan = hsv_pixel.1;
+            v_chan = hsv_pixel.2;
+            a_chan = vdupq_n_u16(255);
         }
 
         let h_low = vcvtq_f32_u32(vmovl_u16(vget_low_u16(h_chan)));
@@ -198,37 +186,27 @@ pub unsafe fn neon_hsv_u16_to_image<
 
         if USE_ALPHA {
             let a_chan = vqmovn_u16(a_chan);
-            match image_configuration {
-                ImageConfiguration::Rgb => {}
-                ImageConfiguration::Rgba => {
-                    let pixel_set = uint8x8x4_t(r_chan, g_chan, b_chan, a_chan);
-                    vst4_u8(dst_ptr.add(cx * channels), pixel_set);
-                }
-                ImageConfiguration::Bgra => {
-                    let pixel_set = uint8x8x4_t(b_chan, g_chan, r_chan, a_chan);
-                    vst4_u8(dst_ptr.add(cx * channels), pixel_set);
-                }
-                ImageConfiguration::Bgr => {}
-            }
+            let mut lanes = [a_chan; 4];
+            lanes[image_configuration.get_r_channel_offset()] = r_chan;
+            lanes[image_configuration.get_g_channel_offset()] = g_chan;
+            lanes[image_configuration.get_b_channel_offset()] = b_chan;
+            lanes[image_configuration.get_a_channel_offset()] = a_chan;
+            let pixel_set = uint8x8x4_t(lanes[0], lanes[1], lanes[2], lanes[3]);
+            vst4_u8(dst_ptr.add(cx * channels), pixel_set);
+        } else if image_configuration.has_alpha() {
+            let mut lanes = [vdup_n_u8(0); 4];
+            lanes[image_configuration.get_r_channel_offset()] = r_chan;
+            lanes[image_configuration.get_g_channel_offset()] = g_chan;
+            lanes[image_configuration.get_b_channel_offset()] = b_chan;
+            let pixel_set = uint8x8x4_t(lanes[0], lanes[1], lanes[2], lanes[3]);
+            vst4_u8(dst_ptr.add(cx * channels), pixel_set);
         } else {
-            match image_configuration {
-                ImageConfiguration::Rgb => {
-                    let pixel_set = uint8x8x3_t(r_chan, g_chan, b_chan);
-                    vst3_u8(dst_ptr.add(cx * channels), pixel_set);
-                }
-                ImageConfiguration::Rgba => {
-                    let pixel_set = uint8x8x4_t(r_chan, g_chan, b_chan, vdup_n_u8(0));
-                    vst4_u8(dst_ptr.add(cx * channels), pixel_set);
-                }
-                ImageConfiguration::Bgra => {
-                    let pixel_set = uint8x8x4_t(b_chan, g_chan, r_chan, vdup_n_u8(0));
-                    vst4_u8(dst_ptr.add(cx * channels), pixel_set);
-                }
-                ImageConfiguration::Bgr => {
-                    let pixel_set = uint8x8x3_t(b_chan, g_chan, r_chan);
-                    vst3_u8(dst_ptr.add(cx * channels), pixel_set);
-                }
-            }
+            let mut lanes = [r_chan; 3];
+            lanes[image_configuration.get_r_channel_offset()] = r_chan;
+            lanes[image_configuration.get_g_channel_offset()] = g_chan;
+            lanes[image_configuration.get_b_channel_offset()] = b_chan;
+            let pixel_set = uint8x8x3_t(lanes[0], lanes[1], lanes[2]);
+            vst3_u8(dst_ptr.add(cx * channels), pixel_set);
         }
 
         cx += 8;
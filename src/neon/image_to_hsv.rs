@@ -42,34 +42,20 @@ pub unsafe fn neon_channels_to_hsv<
     while cx + 16 < width as usize {
         let (r_chan, g_chan, b_chan, a_chan);
         let src_ptr = src.add(src_offset + cx * channels);
-        match image_configuration {
-            ImageConfiguration::Rgb | ImageConfiguration::Bgr => {
-                let ldr = vld3q_u8(src_ptr);
-                if image_configuration == ImageConfiguration::Rgb {
-                    r_chan = ldr.0;
-                    g_chan = ldr.1;
-                    b_chan = ldr.2;
-                } else {
-                    r_chan = ldr.2;
-                    g_chan = ldr.1;
-                    b_chan = ldr.0;
-                }
-                a_chan = vdupq_n_u8(0);
-            }
-            ImageConfiguration::Rgba => {
-                let ldr = vld4q_u8(src_ptr);
-                r_chan = ldr.0;
-                g_chan = ldr.1;
-                b_chan = ldr.2;
-                a_chan = ldr.3;
-            }
-            ImageConfiguration::Bgra => {
-                let ldr = vld4q_u8(src_ptr);
-                r_chan = ldr.2;
-                g_chan = ldr.1;
-                b_chan = ldr.0;
-                a_chan = ldr.3;
-            }
+        if image_configuration.has_alpha() {
+            let ldr = vld4q_u8(src_ptr);
+            let lanes = [ldr.0, ldr.1, ldr.2, ldr.3];
+            r_chan = lanes[image_configuration.get_r_channel_offset()];
+            g_chan = lanes[image_configuration.get_g_channel_offset()];
+            b_chan = lanes[image_configuration.get_b_channel_offset()];
+            a_chan = lanes[image_configuration.get_a_channel_offset()];
+        } else {
+            let ldr = vld3q_u8(src_ptr);
+            let lanes = [ldr.0, ldr.1, ldr.2];
+            r_chan = lanes[image_configuration.get_r_channel_offset()];
+            g_chan = lanes[image_configuration.get_g_channel_offset()];
+            b_chan = lanes[image_configuration.get_b_channel_offset()];
+            a_chan = vdupq_n_u8(0);
         }
 
         let r_low = vmovl_u8(vget_low_u8(r_chan));
@@ -138,23 +138,20 @@ pub unsafe fn neon_jzazbz_to_image<const CHANNELS_CONFIGURATION: u8, const TARGE
         let dst_ptr = ((dst as *mut u8).add(dst_offset as usize) as *mut f32).add(cx * channels);
 
         if image_configuration.has_alpha() {
-            let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
-                    float32x4x4_t(r_row0_, g_row0_, b_row0_, a_row0_)
-                }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
-                    float32x4x4_t(b_row0_, g_row0_, r_row0_, a_row0_)
-                }
-            };
+            let mut lanes = [a_row0_; 4];
+            lanes[image_configuration.get_r_channel_offset()] = r_row0_;
+            lanes[image_configuration.get_g_channel_offset()] = g_row0_;
+            lanes[image_configuration.get_b_channel_offset()] = b_row0_;
+            lanes[image_configuration.get_a_channel_offset()] = a_row0_;
+            let store_rows = float32x4x4_t(lanes[0], lanes[1], lanes[2], lanes[3]);
             vst4q_f32(dst_ptr, store_rows);
         } else {
-            let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
-                    float32x4x3_t(r_row0_, g_row0_, b_row0_)
-                }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
-                    float32x4x3_t(b_row0_, g_row0_, r_row0_)
-                }
+            let store_rows = if image_configuration.get_r_channel_offset()
+                < image_configuration.get_b_channel_offset()
+            {
+                float32x4x3_t(r_row0_, g_row0_, b_row0_)
+            } else {
+                float32x4x3_t(b_row0_, g_row0_, r_row0_)
             };
             vst3q_f32(dst_ptr, store_rows);
         }
@@ -0,0 +1,230 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+
+use crate::image::ImageConfiguration;
+use std::arch::aarch64::*;
+
+#[inline(always)]
+unsafe fn neon_luma_ps(
+    r: float32x4_t,
+    g: float32x4_t,
+    b: float32x4_t,
+    weights: (float32x4_t, float32x4_t, float32x4_t),
+) -> float32x4_t {
+    vaddq_f32(
+        vaddq_f32(vmulq_f32(r, weights.0), vmulq_f32(g, weights.1)),
+        vmulq_f32(b, weights.2),
+    )
+}
+
+/// Computes luma of 8 unsigned 16 bit pixels, rounds half up and saturates to `max`
+#[inline(always)]
+unsafe fn neon_luma_u16(
+    r: uint16x8_t,
+    g: uint16x8_t,
+    b: uint16x8_t,
+    weights: (float32x4_t, float32x4_t, float32x4_t),
+    max: uint16x8_t,
+) -> uint16x8_t {
+    let half = vdupq_n_f32(0.5f32);
+    let y_low = neon_luma_ps(
+        vcvtq_f32_u32(vmovl_u16(vget_low_u16(r))),
+        vcvtq_f32_u32(vmovl_u16(vget_low_u16(g))),
+        vcvtq_f32_u32(vmovl_u16(vget_low_u16(b))),
+        weights,
+    );
+    let y_high = neon_luma_ps(
+        vcvtq_f32_u32(vmovl_high_u16(r)),
+        vcvtq_f32_u32(vmovl_high_u16(g)),
+        vcvtq_f32_u32(vmovl_high_u16(b)),
+        weights,
+    );
+    let y = vcombine_u16(
+        vqmovn_u32(vcvtq_u32_f32(vaddq_f32(y_low, half))),
+        vqmovn_u32(vcvtq_u32_f32(vaddq_f32(y_high, half))),
+    );
+    vminq_u16(y, max)
+}
+
+#[inline(always)]
+pub unsafe fn neon_rgb_to_luma_u8<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    start_cx: usize,
+    src: *const u8,
+    dst: *mut u8,
+    width: usize,
+    weights: [f32; 3],
+    max: u8,
+) -> usize {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let target: ImageConfiguration = TARGET.into();
+    let channels = image_configuration.get_channels_count();
+    let mut cx = start_cx;
+
+    let v_weights = (
+        vdupq_n_f32(weights[0]),
+        vdupq_n_f32(weights[1]),
+        vdupq_n_f32(weights[2]),
+    );
+    let v_max = vdupq_n_u16(max as u16);
+    let v_fill = vdupq_n_u8(max);
+
+    while cx + 16 < width {
+        let src_ptr = src.add(cx * channels);
+        let pixels = if image_configuration.has_alpha() {
+            let rgba = vld4q_u8(src_ptr);
+            [rgba.0, rgba.1, rgba.2, rgba.3]
+        } else {
+            let rgb = vld3q_u8(src_ptr);
+            [rgb.0, rgb.1, rgb.2, v_fill]
+        };
+        let r_chan = pixels[image_configuration.get_r_channel_offset()];
+        let g_chan = pixels[image_configuration.get_g_channel_offset()];
+        let b_chan = pixels[image_configuration.get_b_channel_offset()];
+
+        let y_low = neon_luma_u16(
+            vmovl_u8(vget_low_u8(r_chan)),
+            vmovl_u8(vget_low_u8(g_chan)),
+            vmovl_u8(vget_low_u8(b_chan)),
+            v_weights,
+            v_max,
+        );
+        let y_high = neon_luma_u16(
+            vmovl_high_u8(r_chan),
+            vmovl_high_u8(g_chan),
+            vmovl_high_u8(b_chan),
+            v_weights,
+            v_max,
+        );
+        let y = vcombine_u8(vqmovn_u16(y_low), vqmovn_u16(y_high));
+
+        if target.has_alpha() {
+            let a = if image_configuration.has_alpha() {
+                pixels[image_configuration.get_a_channel_offset()]
+            } else {
+                v_fill
+            };
+            vst2q_u8(dst.add(cx * 2), uint8x16x2_t(y, a));
+        } else {
+            vst1q_u8(dst.add(cx), y);
+        }
+
+        cx += 16;
+    }
+
+    cx
+}
+
+#[inline(always)]
+pub unsafe fn neon_rgb_to_luma_u16<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    start_cx: usize,
+    src: *const u16,
+    dst: *mut u16,
+    width: usize,
+    weights: [f32; 3],
+    max: u16,
+) -> usize {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let target: ImageConfiguration = TARGET.into();
+    let channels = image_configuration.get_channels_count();
+    let mut cx = start_cx;
+
+    let v_weights = (
+        vdupq_n_f32(weights[0]),
+        vdupq_n_f32(weights[1]),
+        vdupq_n_f32(weights[2]),
+    );
+    let v_max = vdupq_n_u16(max);
+
+    while cx + 8 < width {
+        let src_ptr = src.add(cx * channels);
+        let pixels = if image_configuration.has_alpha() {
+            let rgba = vld4q_u16(src_ptr);
+            [rgba.0, rgba.1, rgba.2, rgba.3]
+        } else {
+            let rgb = vld3q_u16(src_ptr);
+            [rgb.0, rgb.1, rgb.2, v_max]
+        };
+
+        let y = neon_luma_u16(
+            pixels[image_configuration.get_r_channel_offset()],
+            pixels[image_configuration.get_g_channel_offset()],
+            pixels[image_configuration.get_b_channel_offset()],
+            v_weights,
+            v_max,
+        );
+
+        if target.has_alpha() {
+            let a = if image_configuration.has_alpha() {
+                pixels[image_configuration.get_a_channel_offset()]
+            } else {
+                v_max
+            };
+            vst2q_u16(dst.add(cx * 2), uint16x8x2_t(y, a));
+        } else {
+            vst1q_u16(dst.add(cx), y);
+        }
+
+        cx += 8;
+    }
+
+    cx
+}
+
+#[inline(always)]
+pub unsafe fn neon_rgb_to_luma_f32<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    start_cx: usize,
+    src: *const f32,
+    dst: *mut f32,
+    width: usize,
+    weights: [f32; 3],
+    max: f32,
+) -> usize {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let target: ImageConfiguration = TARGET.into();
+    let channels = image_configuration.get_channels_count();
+    let mut cx = start_cx;
+
+    let v_weights = (
+        vdupq_n_f32(weights[0]),
+        vdupq_n_f32(weights[1]),
+        vdupq_n_f32(weights[2]),
+    );
+    let v_fill = vdupq_n_f32(max);
+
+    while cx + 4 < width {
+        let src_ptr = src.add(cx * channels);
+        let pixels = if image_configuration.has_alpha() {
+            let rgba = vld4q_f32(src_ptr);
+            [rgba.0, rgba.1, rgba.2, rgba.3]
+        } else {
+            let rgb = vld3q_f32(src_ptr);
+            [rgb.0, rgb.1, rgb.2, v_fill]
+        };
+
+        let y = neon_luma_ps(
+            pixels[image_configuration.get_r_channel_offset()],
+            pixels[image_configuration.get_g_channel_offset()],
+            pixels[image_configuration.get_b_channel_offset()],
+            v_weights,
+        );
+
+        if target.has_alpha() {
+            let a = if image_configuration.has_alpha() {
+                pixels[image_configuration.get_a_channel_offset()]
+            } else {
+                v_fill
+            };
+            vst2q_f32(dst.add(cx * 2), float32x4x2_t(y, a));
+        } else {
+            vst1q_f32(dst.add(cx), y);
+        }
+
+        cx += 4;
+    }
+
+    cx
+}
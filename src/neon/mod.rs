@@ -5,7 +5,9 @@
  * // license that can be found in the LICENSE file.
  */
 
+mod blend;
 mod cie;
+mod color_adjustments;
 mod colors;
 mod from_sigmoidal;
 mod gamma_curves;
@@ -14,16 +16,23 @@ mod image_to_hsv;
 mod image_to_jzazbz;
 mod image_to_oklab;
 mod jzazbz_to_image;
+mod luma;
 mod math;
 mod oklab_to_image;
+mod packed;
+mod planar;
+mod premultiply;
 mod routines;
 mod sigmoidal;
+mod swizzle;
 mod to_sigmoidal;
 mod to_xyz_lab;
 mod to_xyza_laba;
 mod xyz_lab_to_image;
 mod xyza_laba_to_image;
 
+pub use blend::neon_blend_row;
+pub use color_adjustments::neon_adjust_colors_row;
 pub use colors::*;
 pub use from_sigmoidal::neon_from_sigmoidal_row;
 pub use hsv_to_image::*;
@@ -31,7 +40,15 @@ pub use image_to_hsv::*;
 pub use image_to_jzazbz::neon_image_to_jzazbz;
 pub use image_to_oklab::neon_image_to_oklab;
 pub use jzazbz_to_image::neon_jzazbz_to_image;
+pub use luma::{neon_rgb_to_luma_f32, neon_rgb_to_luma_u16, neon_rgb_to_luma_u8};
 pub use oklab_to_image::neon_oklab_to_image;
+pub use packed::{neon_pack_row, neon_unpack_row};
+pub use planar::{
+    neon_deinterleave_planes_f32, neon_deinterleave_planes_u8, neon_interleave_planes_f32,
+    neon_interleave_planes_u8,
+};
+pub use premultiply::*;
+pub use swizzle::neon_swizzle_row;
 pub use to_sigmoidal::neon_image_to_sigmoidal;
 pub use to_xyz_lab::*;
 pub use to_xyza_laba::*;
@@ -11,29 +11,49 @@ use erydanos::{vcosq_f32, vsinq_f32};
 use crate::image::ImageConfiguration;
 use crate::image_to_oklab::OklabTarget;
 use crate::load_f32_and_deinterleave_direct;
-use crate::neon::math::vcolorq_matrix_f32;
+use crate::neon::math::{vcolorq_matrix_f32, vcubeq_f32};
+
+/// Converts Oklab to linear RGB
+#[inline(always)]
+pub(crate) unsafe fn neon_oklab_to_linear_rgb(
+    l: float32x4_t,
+    a: float32x4_t,
+    b: float32x4_t,
+) -> (float32x4_t, float32x4_t, float32x4_t) {
+    let (l_l, l_m, l_s) = vcolorq_matrix_f32(
+        l,
+        a,
+        b,
+        vdupq_n_f32(1f32),
+        vdupq_n_f32(0.3963377774f32),
+        vdupq_n_f32(0.2158037573f32),
+        vdupq_n_f32(1f32),
+        vdupq_n_f32(-0.1055613458f32),
+        vdupq_n_f32(-0.0638541728f32),
+        vdupq_n_f32(1f32),
+        vdupq_n_f32(-0.0894841775f32),
+        vdupq_n_f32(-1.2914855480f32),
+    );
+
+    vcolorq_matrix_f32(
+        vcubeq_f32(l_l),
+        vcubeq_f32(l_m),
+        vcubeq_f32(l_s),
+        vdupq_n_f32(4.0767416621f32),
+        vdupq_n_f32(-3.3077115913f32),
+        vdupq_n_f32(0.2309699292f32),
+        vdupq_n_f32(-1.2684380046f32),
+        vdupq_n_f32(2.6097574011f32),
+        vdupq_n_f32(-0.3413193965f32),
+        vdupq_n_f32(-0.0041960863f32),
+        vdupq_n_f32(-0.7034186147f32),
+        vdupq_n_f32(1.7076147010f32),
+    )
+}
 
 #[inline(always)]
 unsafe fn neon_oklab_gamma_vld<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
     src: *const f32,
-    m0: float32x4_t,
-    m1: float32x4_t,
-    m2: float32x4_t,
-    m3: float32x4_t,
-    m4: float32x4_t,
-    m5: float32x4_t,
-    m6: float32x4_t,
-    m7: float32x4_t,
-    m8: float32x4_t,
-    c0: float32x4_t,
-    c1: float32x4_t,
-    c2: float32x4_t,
-    c3: float32x4_t,
-    c4: float32x4_t,
-    c5: float32x4_t,
-    c6: float32x4_t,
-    c7: float32x4_t,
-    c8: float32x4_t,
 ) -> (float32x4_t, float32x4_t, float32x4_t, float32x4_t) {
     let target: OklabTarget = TARGET.into();
     let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
@@ -46,14 +66,7 @@ unsafe fn neon_oklab_gamma_vld<const CHANNELS_CONFIGURATION: u8, const TARGET: u
         b = b0;
     }
 
-    let (mut l_l, mut l_m, mut l_s) =
-        vcolorq_matrix_f32(l, a, b, m0, m1, m2, m3, m4, m5, m6, m7, m8);
-
-    l_l = vmulq_f32(vmulq_f32(l_l, l_l), l_l);
-    l_m = vmulq_f32(vmulq_f32(l_m, l_m), l_m);
-    l_s = vmulq_f32(vmulq_f32(l_s, l_s), l_s);
-
-    let (r_l, g_l, b_l) = vcolorq_matrix_f32(l_l, l_m, l_s, c0, c1, c2, c3, c4, c5, c6, c7, c8);
+    let (r_l, g_l, b_l) = neon_oklab_to_linear_rgb(l, a, b);
     (r_l, g_l, b_l, a_f32)
 }
 
@@ -70,59 +83,30 @@ pub unsafe fn neon_oklab_to_image<const CHANNELS_CONFIGURATION: u8, const TARGET
     let channels = image_configuration.get_channels_count();
     let mut cx = start_cx;
 
-    let (m0, m1, m2, m3, m4, m5, m6, m7, m8) = (
-        vdupq_n_f32(1f32),
-        vdupq_n_f32(0.3963377774f32),
-        vdupq_n_f32(0.2158037573f32),
-        vdupq_n_f32(1f32),
-        vdupq_n_f32(-0.1055613458f32),
-        vdupq_n_f32(-0.0638541728f32),
-        vdupq_n_f32(1f32),
-        vdupq_n_f32(-0.0894841775f32),
-        vdupq_n_f32(-1.2914855480f32),
-    );
-
-    let (c0, c1, c2, c3, c4, c5, c6, c7, c8) = (
-        vdupq_n_f32(4.0767416621f32),
-        vdupq_n_f32(-3.3077115913f32),
-        vdupq_n_f32(0.2309699292f32),
-        vdupq_n_f32(-1.2684380046f32),
-        vdupq_n_f32(2.6097574011f32),
-        vdupq_n_f32(-0.3413193965f32),
-        vdupq_n_f32(-0.0041960863f32),
-        vdupq_n_f32(-0.7034186147f32),
-        vdupq_n_f32(1.7076147010f32),
-    );
-
     while cx + 4 < width as usize {
         let v_src_ptr = ((src as *mut u8).add(src_offset) as *mut f32).add(cx * channels);
 
         let (r_row0_, g_row0_, b_row0_, a_row0_) =
-            neon_oklab_gamma_vld::<CHANNELS_CONFIGURATION, TARGET>(
-                v_src_ptr, m0, m1, m2, m3, m4, m5, m6, m7, m8, c0, c1, c2, c3, c4, c5, c6, c7, c8,
-            );
+            neon_oklab_gamma_vld::<CHANNELS_CONFIGURATION, TARGET>(v_src_ptr);
 
         let in_place_ptr =
             ((dst as *mut u8).add(dst_offset as usize) as *mut f32).add(cx * channels);
 
         if image_configuration.has_alpha() {
-            let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
-                    float32x4x4_t(r_row0_, g_row0_, b_row0_, a_row0_)
-                }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
-                    float32x4x4_t(b_row0_, g_row0_, r_row0_, a_row0_)
-                }
-            };
+            let mut lanes = [a_row0_; 4];
+            lanes[image_configuration.get_r_channel_offset()] = r_row0_;
+            lanes[image_configuration.get_g_channel_offset()] = g_row0_;
+            lanes[image_configuration.get_b_channel_offset()] = b_row0_;
+            lanes[image_configuration.get_a_channel_offset()] = a_row0_;
+            let store_rows = float32x4x4_t(lanes[0], lanes[1], lanes[2], lanes[3]);
             vst4q_f32(in_place_ptr, store_rows);
         } else {
-            let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
-                    float32x4x3_t(r_row0_, g_row0_, b_row0_)
-                }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
-                    float32x4x3_t(b_row0_, g_row0_, r_row0_)
-                }
+            let store_rows = if image_configuration.get_r_channel_offset()
+                < image_configuration.get_b_channel_offset()
+            {
+                float32x4x3_t(r_row0_, g_row0_, b_row0_)
+            } else {
+                float32x4x3_t(b_row0_, g_row0_, r_row0_)
             };
             vst3q_f32(in_place_ptr, store_rows);
         }
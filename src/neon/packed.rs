@@ -0,0 +1,383 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+
+use crate::image::ImageConfiguration;
+use crate::packed::PackedFormat;
+use std::arch::aarch64::*;
+
+#[inline(always)]
+unsafe fn neon_swap_bytes_u32(v: uint32x4_t) -> uint32x4_t {
+    vreinterpretq_u32_u8(vrev32q_u8(vreinterpretq_u8_u32(v)))
+}
+
+#[inline(always)]
+unsafe fn neon_swap_bytes_u16(v: uint16x4_t) -> uint16x4_t {
+    vreinterpret_u16_u8(vrev16_u8(vreinterpret_u8_u16(v)))
+}
+
+/// Converts unsigned IEEE half float bits stored in 32 bit lanes into floats, sign is taken from bit 15
+#[inline(always)]
+unsafe fn neon_half_to_float(h: uint32x4_t) -> float32x4_t {
+    let shifted_exp = vdupq_n_u32(0x7c00 << 13);
+    let o = vshlq_n_u32::<13>(vandq_u32(h, vdupq_n_u32(0x7fff)));
+    let exp = vandq_u32(o, shifted_exp);
+    let o = vaddq_u32(o, vdupq_n_u32((127 - 15) << 23));
+    let is_inf_nan = vceqq_u32(exp, shifted_exp);
+    let o = vaddq_u32(o, vandq_u32(is_inf_nan, vdupq_n_u32((128 - 16) << 23)));
+    let is_denormal = vceqq_u32(exp, vdupq_n_u32(0));
+    let denormal = vsubq_f32(
+        vreinterpretq_f32_u32(vaddq_u32(o, vdupq_n_u32(1 << 23))),
+        vreinterpretq_f32_u32(vdupq_n_u32(113 << 23)),
+    );
+    let o = vbslq_u32(is_denormal, vreinterpretq_u32_f32(denormal), o);
+    let sign = vshlq_n_u32::<16>(vandq_u32(h, vdupq_n_u32(0x8000)));
+    vreinterpretq_f32_u32(vorrq_u32(o, sign))
+}
+
+/// Converts floats into IEEE half float bits in 32 bit lanes with rounding to nearest even
+#[inline(always)]
+unsafe fn neon_float_to_half(v: float32x4_t) -> uint32x4_t {
+    let bits = vreinterpretq_u32_f32(v);
+    let sign = vandq_u32(bits, vdupq_n_u32(0x80000000));
+    let abs = veorq_u32(bits, sign);
+    let is_nan = vcgtq_u32(abs, vdupq_n_u32(255 << 23));
+    let is_regular = vcltq_u32(abs, vdupq_n_u32((127 + 16) << 23));
+    let inf_or_nan = vorrq_u32(vandq_u32(is_nan, vdupq_n_u32(0x200)), vdupq_n_u32(0x7c00));
+    let is_subnormal = vcltq_u32(abs, vdupq_n_u32((127 - 14) << 23));
+    let subnormal_magic = vdupq_n_u32(((127 - 15) + (23 - 10) + 1) << 23);
+    let subnormal = vsubq_u32(
+        vreinterpretq_u32_f32(vaddq_f32(
+            vreinterpretq_f32_u32(abs),
+            vreinterpretq_f32_u32(subnormal_magic),
+        )),
+        subnormal_magic,
+    );
+    let odd = vandq_u32(vshrq_n_u32::<13>(abs), vdupq_n_u32(1));
+    let normal = vshrq_n_u32::<13>(vaddq_u32(
+        vaddq_u32(abs, vdupq_n_u32(0xfffu32.wrapping_sub((127 - 15) << 23))),
+        odd,
+    ));
+    let finite = vbslq_u32(is_subnormal, subnormal, normal);
+    let joined = vbslq_u32(is_regular, finite, inf_or_nan);
+    vorrq_u32(joined, vshrq_n_u32::<16>(sign))
+}
+
+/// Encodes non negative floats into unsigned floats with 5 bits exponent and `MANTISSA` bits mantissa,
+/// NaN and negative values become zero, values above maximum are saturated
+#[inline(always)]
+unsafe fn neon_float_to_small_float<const MANTISSA: i32>(v: float32x4_t) -> uint32x4_t {
+    let max_value = ((1 << (MANTISSA + 1)) - 1) as f32 * (1 << (15 - MANTISSA)) as f32;
+    let v = vminq_f32(vmaxnmq_f32(v, vdupq_n_f32(0f32)), vdupq_n_f32(max_value));
+    let abs = vreinterpretq_u32_f32(v);
+    let is_subnormal = vcltq_u32(abs, vdupq_n_u32((127 - 14) << 23));
+    let subnormal_magic = vdupq_n_u32(((136 - MANTISSA) as u32) << 23);
+    let subnormal = vsubq_u32(
+        vreinterpretq_u32_f32(vaddq_f32(v, vreinterpretq_f32_u32(subnormal_magic))),
+        subnormal_magic,
+    );
+    let shift = vdupq_n_s32(-(23 - MANTISSA));
+    let odd = vandq_u32(vshlq_u32(abs, shift), vdupq_n_u32(1));
+    let bias = ((1u32 << (22 - MANTISSA)) - 1).wrapping_sub((127 - 15) << 23);
+    let normal = vshlq_u32(vaddq_u32(vaddq_u32(abs, vdupq_n_u32(bias)), odd), shift);
+    vbslq_u32(is_subnormal, subnormal, normal)
+}
+
+/// Clamps normalized value into [0, 1], scales by `max` and rounds half up
+#[inline(always)]
+unsafe fn neon_quantize_unorm(v: float32x4_t, max: f32) -> uint32x4_t {
+    let clamped = vminq_f32(vmaxnmq_f32(v, vdupq_n_f32(0f32)), vdupq_n_f32(1f32));
+    vcvtq_u32_f32(vaddq_f32(vmulq_n_f32(clamped, max), vdupq_n_f32(0.5f32)))
+}
+
+#[inline(always)]
+unsafe fn neon_unorm_to_float(v: uint32x4_t, max: f32) -> float32x4_t {
+    vdivq_f32(vcvtq_f32_u32(v), vdupq_n_f32(max))
+}
+
+/// Scales value by 2^(15 + 9 - `exponent`) and rounds half up into 9 bits mantissa of RGB9E5
+#[inline(always)]
+unsafe fn neon_shared_mantissa(v: float32x4_t, exponent: uint32x4_t) -> uint32x4_t {
+    let scale = vreinterpretq_f32_u32(vshlq_n_u32::<23>(vsubq_u32(
+        vdupq_n_u32(127 + 15 + 9),
+        exponent,
+    )));
+    vcvtaq_u32_f32(vmulq_f32(v, scale))
+}
+
+/// Loads 4 packed pixels and decodes them into R, G, B and A
+#[inline(always)]
+unsafe fn neon_unpack_pixels<const FORMAT: u8>(
+    src: *const u8,
+    big_endian: bool,
+) -> (float32x4_t, float32x4_t, float32x4_t, float32x4_t) {
+    let format: PackedFormat = FORMAT.into();
+    let ones = vdupq_n_f32(1f32);
+    match format {
+        PackedFormat::Rgb565 => {
+            let mut words = vld1_u16(src as *const u16);
+            if big_endian {
+                words = neon_swap_bytes_u16(words);
+            }
+            let words = vmovl_u16(words);
+            let r = vshrq_n_u32::<11>(words);
+            let g = vandq_u32(vshrq_n_u32::<5>(words), vdupq_n_u32(0x3f));
+            let b = vandq_u32(words, vdupq_n_u32(0x1f));
+            (
+                neon_unorm_to_float(r, 31f32),
+                neon_unorm_to_float(g, 63f32),
+                neon_unorm_to_float(b, 31f32),
+                ones,
+            )
+        }
+        PackedFormat::Rgba1010102 => {
+            let mut words = vld1q_u32(src as *const u32);
+            if big_endian {
+                words = neon_swap_bytes_u32(words);
+            }
+            let mask = vdupq_n_u32(0x3ff);
+            let r = vandq_u32(words, mask);
+            let g = vandq_u32(vshrq_n_u32::<10>(words), mask);
+            let b = vandq_u32(vshrq_n_u32::<20>(words), mask);
+            let a = vshrq_n_u32::<30>(words);
+            (
+                neon_unorm_to_float(r, 1023f32),
+                neon_unorm_to_float(g, 1023f32),
+                neon_unorm_to_float(b, 1023f32),
+                neon_unorm_to_float(a, 3f32),
+            )
+        }
+        PackedFormat::RgbaF16 => {
+            let mut halves = vld4_u16(src as *const u16);
+            if big_endian {
+                halves = uint16x4x4_t(
+                    neon_swap_bytes_u16(halves.0),
+                    neon_swap_bytes_u16(halves.1),
+                    neon_swap_bytes_u16(halves.2),
+                    neon_swap_bytes_u16(halves.3),
+                );
+            }
+            (
+                neon_half_to_float(vmovl_u16(halves.0)),
+                neon_half_to_float(vmovl_u16(halves.1)),
+                neon_half_to_float(vmovl_u16(halves.2)),
+                neon_half_to_float(vmovl_u16(halves.3)),
+            )
+        }
+        PackedFormat::R11G11B10F => {
+            let mut words = vld1q_u32(src as *const u32);
+            if big_endian {
+                words = neon_swap_bytes_u32(words);
+            }
+            let mask = vdupq_n_u32(0x7ff);
+            let r = vshlq_n_u32::<4>(vandq_u32(words, mask));
+            let g = vshlq_n_u32::<4>(vandq_u32(vshrq_n_u32::<11>(words), mask));
+            let b = vshlq_n_u32::<5>(vshrq_n_u32::<22>(words));
+            (
+                neon_half_to_float(r),
+                neon_half_to_float(g),
+                neon_half_to_float(b),
+                ones,
+            )
+        }
+        PackedFormat::Rgb9E5 => {
+            let mut words = vld1q_u32(src as *const u32);
+            if big_endian {
+                words = neon_swap_bytes_u32(words);
+            }
+            let mask = vdupq_n_u32(0x1ff);
+            let r = vandq_u32(words, mask);
+            let g = vandq_u32(vshrq_n_u32::<9>(words), mask);
+            let b = vandq_u32(vshrq_n_u32::<18>(words), mask);
+            let exponent = vshrq_n_u32::<27>(words);
+            let scale = vreinterpretq_f32_u32(vshlq_n_u32::<23>(vaddq_u32(
+                exponent,
+                vdupq_n_u32(127 - 15 - 9),
+            )));
+            (
+                vmulq_f32(vcvtq_f32_u32(r), scale),
+                vmulq_f32(vcvtq_f32_u32(g), scale),
+                vmulq_f32(vcvtq_f32_u32(b), scale),
+                ones,
+            )
+        }
+    }
+}
+
+/// Encodes R, G, B and A of 4 pixels and stores them packed
+#[inline(always)]
+unsafe fn neon_pack_pixels<const FORMAT: u8>(
+    r: float32x4_t,
+    g: float32x4_t,
+    b: float32x4_t,
+    a: float32x4_t,
+    dst: *mut u8,
+    big_endian: bool,
+) {
+    let format: PackedFormat = FORMAT.into();
+    match format {
+        PackedFormat::Rgb565 => {
+            let words = vorrq_u32(
+                vorrq_u32(
+                    vshlq_n_u32::<11>(neon_quantize_unorm(r, 31f32)),
+                    vshlq_n_u32::<5>(neon_quantize_unorm(g, 63f32)),
+                ),
+                neon_quantize_unorm(b, 31f32),
+            );
+            let mut words = vmovn_u32(words);
+            if big_endian {
+                words = neon_swap_bytes_u16(words);
+            }
+            vst1_u16(dst as *mut u16, words);
+        }
+        PackedFormat::Rgba1010102 => {
+            let mut words = vorrq_u32(
+                vorrq_u32(
+                    neon_quantize_unorm(r, 1023f32),
+                    vshlq_n_u32::<10>(neon_quantize_unorm(g, 1023f32)),
+                ),
+                vorrq_u32(
+                    vshlq_n_u32::<20>(neon_quantize_unorm(b, 1023f32)),
+                    vshlq_n_u32::<30>(neon_quantize_unorm(a, 3f32)),
+                ),
+            );
+            if big_endian {
+                words = neon_swap_bytes_u32(words);
+            }
+            vst1q_u32(dst as *mut u32, words);
+        }
+        PackedFormat::RgbaF16 => {
+            let mut halves = uint16x4x4_t(
+                vmovn_u32(neon_float_to_half(r)),
+                vmovn_u32(neon_float_to_half(g)),
+                vmovn_u32(neon_float_to_half(b)),
+                vmovn_u32(neon_float_to_half(a)),
+            );
+            if big_endian {
+                halves = uint16x4x4_t(
+                    neon_swap_bytes_u16(halves.0),
+                    neon_swap_bytes_u16(halves.1),
+                    neon_swap_bytes_u16(halves.2),
+                    neon_swap_bytes_u16(halves.3),
+                );
+            }
+            vst4_u16(dst as *mut u16, halves);
+        }
+        PackedFormat::R11G11B10F => {
+            let mut words = vorrq_u32(
+                vorrq_u32(
+                    neon_float_to_small_float::<6>(r),
+                    vshlq_n_u32::<11>(neon_float_to_small_float::<6>(g)),
+                ),
+                vshlq_n_u32::<22>(neon_float_to_small_float::<5>(b)),
+            );
+            if big_endian {
+                words = neon_swap_bytes_u32(words);
+            }
+            vst1q_u32(dst as *mut u32, words);
+        }
+        PackedFormat::Rgb9E5 => {
+            let zeros = vdupq_n_f32(0f32);
+            let max_value = vdupq_n_f32(65408f32);
+            let r = vminq_f32(vmaxnmq_f32(r, zeros), max_value);
+            let g = vminq_f32(vmaxnmq_f32(g, zeros), max_value);
+            let b = vminq_f32(vmaxnmq_f32(b, zeros), max_value);
+            let max_channel = vmaxq_f32(vmaxq_f32(r, g), b);
+            let biased_exponent = vshrq_n_u32::<23>(vreinterpretq_u32_f32(max_channel));
+            let exponent = vqsubq_u32(biased_exponent, vdupq_n_u32(127 - 16));
+            let max_mantissa = neon_shared_mantissa(max_channel, exponent);
+            let overflow = vceqq_u32(max_mantissa, vdupq_n_u32(512));
+            let exponent = vsubq_u32(exponent, overflow);
+            let mut words = vorrq_u32(
+                vorrq_u32(
+                    neon_shared_mantissa(r, exponent),
+                    vshlq_n_u32::<9>(neon_shared_mantissa(g, exponent)),
+                ),
+                vorrq_u32(
+                    vshlq_n_u32::<18>(neon_shared_mantissa(b, exponent)),
+                    vshlq_n_u32::<27>(exponent),
+                ),
+            );
+            if big_endian {
+                words = neon_swap_bytes_u32(words);
+            }
+            vst1q_u32(dst as *mut u32, words);
+        }
+    }
+}
+
+/// Decodes row of packed pixels into normalized floats in the order of `CHANNELS_CONFIGURATION`
+#[inline(always)]
+pub unsafe fn neon_unpack_row<const FORMAT: u8, const CHANNELS_CONFIGURATION: u8>(
+    start_cx: usize,
+    src: *const u8,
+    dst: *mut f32,
+    width: usize,
+    big_endian: bool,
+) -> usize {
+    let format: PackedFormat = FORMAT.into();
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+    let bytes_per_pixel = format.bytes_per_pixel();
+    let mut cx = start_cx;
+
+    while cx + 4 < width {
+        let (r, g, b, a) = neon_unpack_pixels::<FORMAT>(src.add(cx * bytes_per_pixel), big_endian);
+        let mut v = [r; 4];
+        v[image_configuration.get_r_channel_offset()] = r;
+        v[image_configuration.get_g_channel_offset()] = g;
+        v[image_configuration.get_b_channel_offset()] = b;
+        let dst_ptr = dst.add(cx * channels);
+        if image_configuration.has_alpha() {
+            v[image_configuration.get_a_channel_offset()] = a;
+            vst4q_f32(dst_ptr, float32x4x4_t(v[0], v[1], v[2], v[3]));
+        } else {
+            vst3q_f32(dst_ptr, float32x4x3_t(v[0], v[1], v[2]));
+        }
+        cx += 4;
+    }
+
+    cx
+}
+
+/// Encodes row of normalized floats in the order of `CHANNELS_CONFIGURATION` into packed pixels
+#[inline(always)]
+pub unsafe fn neon_pack_row<const FORMAT: u8, const CHANNELS_CONFIGURATION: u8>(
+    start_cx: usize,
+    src: *const f32,
+    dst: *mut u8,
+    width: usize,
+    big_endian: bool,
+) -> usize {
+    let format: PackedFormat = FORMAT.into();
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+    let bytes_per_pixel = format.bytes_per_pixel();
+    let mut cx = start_cx;
+
+    while cx + 4 < width {
+        let src_ptr = src.add(cx * channels);
+        let (v, a) = if image_configuration.has_alpha() {
+            let pixels = vld4q_f32(src_ptr);
+            let v = [pixels.0, pixels.1, pixels.2, pixels.3];
+            (v, v[image_configuration.get_a_channel_offset()])
+        } else {
+            let pixels = vld3q_f32(src_ptr);
+            ([pixels.0, pixels.1, pixels.2, pixels.2], vdupq_n_f32(1f32))
+        };
+        neon_pack_pixels::<FORMAT>(
+            v[image_configuration.get_r_channel_offset()],
+            v[image_configuration.get_g_channel_offset()],
+            v[image_configuration.get_b_channel_offset()],
+            a,
+            dst.add(cx * bytes_per_pixel),
+            big_endian,
+        );
+        cx += 4;
+    }
+
+    cx
+}
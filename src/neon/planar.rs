@@ -0,0 +1,122 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+
+use std::arch::aarch64::*;
+
+/// Interleaves 3 or 4 planes of 8 bit samples into one row
+#[inline(always)]
+pub unsafe fn neon_interleave_planes_u8<const CHANNELS: usize>(
+    start_cx: usize,
+    planes: &[*const u8; 4],
+    dst: *mut u8,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+
+    while cx + 16 < width {
+        let c0 = vld1q_u8(planes[0].add(cx));
+        let c1 = vld1q_u8(planes[1].add(cx));
+        let c2 = vld1q_u8(planes[2].add(cx));
+        let dst_ptr = dst.add(cx * CHANNELS);
+        if CHANNELS == 4 {
+            let c3 = vld1q_u8(planes[3].add(cx));
+            vst4q_u8(dst_ptr, uint8x16x4_t(c0, c1, c2, c3));
+        } else {
+            vst3q_u8(dst_ptr, uint8x16x3_t(c0, c1, c2));
+        }
+        cx += 16;
+    }
+
+    cx
+}
+
+/// Splits row of 8 bit samples into 3 or 4 planes
+#[inline(always)]
+pub unsafe fn neon_deinterleave_planes_u8<const CHANNELS: usize>(
+    start_cx: usize,
+    src: *const u8,
+    planes: &[*mut u8; 4],
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+
+    while cx + 16 < width {
+        let src_ptr = src.add(cx * CHANNELS);
+        if CHANNELS == 4 {
+            let pixels = vld4q_u8(src_ptr);
+            vst1q_u8(planes[0].add(cx), pixels.0);
+            vst1q_u8(planes[1].add(cx), pixels.1);
+            vst1q_u8(planes[2].add(cx), pixels.2);
+            vst1q_u8(planes[3].add(cx), pixels.3);
+        } else {
+            let pixels = vld3q_u8(src_ptr);
+            vst1q_u8(planes[0].add(cx), pixels.0);
+            vst1q_u8(planes[1].add(cx), pixels.1);
+            vst1q_u8(planes[2].add(cx), pixels.2);
+        }
+        cx += 16;
+    }
+
+    cx
+}
+
+/// Interleaves 3 or 4 planes of 32 bit samples into one row
+#[inline(always)]
+pub unsafe fn neon_interleave_planes_f32<const CHANNELS: usize>(
+    start_cx: usize,
+    planes: &[*const f32; 4],
+    dst: *mut f32,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+
+    while cx + 4 < width {
+        let c0 = vld1q_f32(planes[0].add(cx));
+        let c1 = vld1q_f32(planes[1].add(cx));
+        let c2 = vld1q_f32(planes[2].add(cx));
+        let dst_ptr = dst.add(cx * CHANNELS);
+        if CHANNELS == 4 {
+            let c3 = vld1q_f32(planes[3].add(cx));
+            vst4q_f32(dst_ptr, float32x4x4_t(c0, c1, c2, c3));
+        } else {
+            vst3q_f32(dst_ptr, float32x4x3_t(c0, c1, c2));
+        }
+        cx += 4;
+    }
+
+    cx
+}
+
+/// Splits row of 32 bit samples into 3 or 4 planes
+#[inline(always)]
+pub unsafe fn neon_deinterleave_planes_f32<const CHANNELS: usize>(
+    start_cx: usize,
+    src: *const f32,
+    planes: &[*mut f32; 4],
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+
+    while cx + 4 < width {
+        let src_ptr = src.add(cx * CHANNELS);
+        if CHANNELS == 4 {
+            let pixels = vld4q_f32(src_ptr);
+            vst1q_f32(planes[0].add(cx), pixels.0);
+            vst1q_f32(planes[1].add(cx), pixels.1);
+            vst1q_f32(planes[2].add(cx), pixels.2);
+            vst1q_f32(planes[3].add(cx), pixels.3);
+        } else {
+            let pixels = vld3q_f32(src_ptr);
+            vst1q_f32(planes[0].add(cx), pixels.0);
+            vst1q_f32(planes[1].add(cx), pixels.1);
+            vst1q_f32(planes[2].add(cx), pixels.2);
+        }
+        cx += 4;
+    }
+
+    cx
+}
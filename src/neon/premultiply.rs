@@ -0,0 +1,248 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+
+use std::arch::aarch64::*;
+
+/// Rounding division by 255 of 16-bit products
+#[inline(always)]
+unsafe fn neon_div_by_255_n_u16(v: uint16x8_t) -> uint8x8_t {
+    vraddhn_u16(v, vrshrq_n_u16::<8>(v))
+}
+
+#[inline(always)]
+unsafe fn neon_premultiply_channel(channel: uint8x16_t, alpha: uint8x16_t) -> uint8x16_t {
+    let low = vmull_u8(vget_low_u8(channel), vget_low_u8(alpha));
+    let high = vmull_high_u8(channel, alpha);
+    vcombine_u8(neon_div_by_255_n_u16(low), neon_div_by_255_n_u16(high))
+}
+
+/// Broadcasts alpha lane of the pixel
+#[inline(always)]
+unsafe fn neon_alpha_f32<const ALPHA_FIRST: bool>(pixel: float32x4_t) -> float32x4_t {
+    if ALPHA_FIRST {
+        vdupq_laneq_f32::<0>(pixel)
+    } else {
+        vdupq_laneq_f32::<3>(pixel)
+    }
+}
+
+/// Takes alpha lane from `pixel` and colour lanes from `colour`
+#[inline(always)]
+unsafe fn neon_keep_alpha_f32<const ALPHA_FIRST: bool>(
+    colour: float32x4_t,
+    pixel: float32x4_t,
+) -> float32x4_t {
+    if ALPHA_FIRST {
+        vcopyq_laneq_f32::<0, 0>(colour, pixel)
+    } else {
+        vcopyq_laneq_f32::<3, 3>(colour, pixel)
+    }
+}
+
+/// Premultiplies four channel u8 pixels with alpha first or last, `src` and `dst` may point to the same row
+#[inline(always)]
+pub unsafe fn neon_premultiply_alpha_row_u8<const ALPHA_FIRST: bool>(
+    start_cx: usize,
+    src: *const u8,
+    dst: *mut u8,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+
+    while cx + 16 < width {
+        let pixels = vld4q_u8(src.add(cx * 4));
+        let premultiplied = if ALPHA_FIRST {
+            uint8x16x4_t(
+                pixels.0,
+                neon_premultiply_channel(pixels.1, pixels.0),
+                neon_premultiply_channel(pixels.2, pixels.0),
+                neon_premultiply_channel(pixels.3, pixels.0),
+            )
+        } else {
+            uint8x16x4_t(
+                neon_premultiply_channel(pixels.0, pixels.3),
+                neon_premultiply_channel(pixels.1, pixels.3),
+                neon_premultiply_channel(pixels.2, pixels.3),
+                pixels.3,
+            )
+        };
+        vst4q_u8(dst.add(cx * 4), premultiplied);
+        cx += 16;
+    }
+
+    cx
+}
+
+/// Divides colour by alpha and scales it to `max`, alpha lane is kept, zero alpha produces zero colour
+#[inline(always)]
+unsafe fn neon_unpremultiply_pixel_f32<const ALPHA_FIRST: bool>(
+    pixel: float32x4_t,
+    max: float32x4_t,
+) -> uint32x4_t {
+    let alpha = neon_alpha_f32::<ALPHA_FIRST>(pixel);
+    let non_zero = vmvnq_u32(vceqzq_f32(alpha));
+    let straight = vreinterpretq_f32_u32(vandq_u32(
+        vreinterpretq_u32_f32(vdivq_f32(vmulq_f32(pixel, max), alpha)),
+        non_zero,
+    ));
+    let rounded = vminq_f32(vrndmq_f32(vaddq_f32(straight, vdupq_n_f32(0.5))), max);
+    vcvtq_u32_f32(neon_keep_alpha_f32::<ALPHA_FIRST>(rounded, pixel))
+}
+
+/// Multiplies colour by alpha and divides by `max`, alpha lane is kept
+#[inline(always)]
+unsafe fn neon_premultiply_pixel_f32<const ALPHA_FIRST: bool>(
+    pixel: float32x4_t,
+    max: float32x4_t,
+) -> uint32x4_t {
+    let alpha = neon_alpha_f32::<ALPHA_FIRST>(pixel);
+    let premultiplied = vdivq_f32(vmulq_f32(pixel, alpha), max);
+    let rounded = vrndmq_f32(vaddq_f32(premultiplied, vdupq_n_f32(0.5)));
+    vcvtq_u32_f32(neon_keep_alpha_f32::<ALPHA_FIRST>(rounded, pixel))
+}
+
+/// Unpremultiplies four channel u8 pixels with alpha first or last, `src` and `dst` may point to the same row
+#[inline(always)]
+pub unsafe fn neon_unpremultiply_alpha_row_u8<const ALPHA_FIRST: bool>(
+    start_cx: usize,
+    src: *const u8,
+    dst: *mut u8,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+    let max = vdupq_n_f32(255.);
+
+    while cx + 4 < width {
+        let pixels = vld1q_u8(src.add(cx * 4));
+        let low = vmovl_u8(vget_low_u8(pixels));
+        let high = vmovl_high_u8(pixels);
+        let p0 = vcvtq_f32_u32(vmovl_u16(vget_low_u16(low)));
+        let p1 = vcvtq_f32_u32(vmovl_high_u16(low));
+        let p2 = vcvtq_f32_u32(vmovl_u16(vget_low_u16(high)));
+        let p3 = vcvtq_f32_u32(vmovl_high_u16(high));
+        let r0 = neon_unpremultiply_pixel_f32::<ALPHA_FIRST>(p0, max);
+        let r1 = neon_unpremultiply_pixel_f32::<ALPHA_FIRST>(p1, max);
+        let r2 = neon_unpremultiply_pixel_f32::<ALPHA_FIRST>(p2, max);
+        let r3 = neon_unpremultiply_pixel_f32::<ALPHA_FIRST>(p3, max);
+        let low = vcombine_u16(vqmovn_u32(r0), vqmovn_u32(r1));
+        let high = vcombine_u16(vqmovn_u32(r2), vqmovn_u32(r3));
+        vst1q_u8(
+            dst.add(cx * 4),
+            vcombine_u8(vqmovn_u16(low), vqmovn_u16(high)),
+        );
+        cx += 4;
+    }
+
+    cx
+}
+
+/// Premultiplies four channel u16 pixels with alpha first or last, `src` and `dst` may point to the same row
+#[inline(always)]
+pub unsafe fn neon_premultiply_alpha_row_u16<const ALPHA_FIRST: bool>(
+    start_cx: usize,
+    src: *const u16,
+    dst: *mut u16,
+    width: usize,
+    max_value: f32,
+) -> usize {
+    let mut cx = start_cx;
+    let max = vdupq_n_f32(max_value);
+
+    while cx + 2 < width {
+        let pixels = vld1q_u16(src.add(cx * 4));
+        let p0 = vcvtq_f32_u32(vmovl_u16(vget_low_u16(pixels)));
+        let p1 = vcvtq_f32_u32(vmovl_high_u16(pixels));
+        let r0 = neon_premultiply_pixel_f32::<ALPHA_FIRST>(p0, max);
+        let r1 = neon_premultiply_pixel_f32::<ALPHA_FIRST>(p1, max);
+        vst1q_u16(
+            dst.add(cx * 4),
+            vcombine_u16(vqmovn_u32(r0), vqmovn_u32(r1)),
+        );
+        cx += 2;
+    }
+
+    cx
+}
+
+/// Unpremultiplies four channel u16 pixels with alpha first or last, `src` and `dst` may point to the same row
+#[inline(always)]
+pub unsafe fn neon_unpremultiply_alpha_row_u16<const ALPHA_FIRST: bool>(
+    start_cx: usize,
+    src: *const u16,
+    dst: *mut u16,
+    width: usize,
+    max_value: f32,
+) -> usize {
+    let mut cx = start_cx;
+    let max = vdupq_n_f32(max_value);
+
+    while cx + 2 < width {
+        let pixels = vld1q_u16(src.add(cx * 4));
+        let p0 = vcvtq_f32_u32(vmovl_u16(vget_low_u16(pixels)));
+        let p1 = vcvtq_f32_u32(vmovl_high_u16(pixels));
+        let r0 = neon_unpremultiply_pixel_f32::<ALPHA_FIRST>(p0, max);
+        let r1 = neon_unpremultiply_pixel_f32::<ALPHA_FIRST>(p1, max);
+        vst1q_u16(
+            dst.add(cx * 4),
+            vcombine_u16(vqmovn_u32(r0), vqmovn_u32(r1)),
+        );
+        cx += 2;
+    }
+
+    cx
+}
+
+/// Premultiplies four channel f32 pixels with alpha first or last, `src` and `dst` may point to the same row
+#[inline(always)]
+pub unsafe fn neon_premultiply_alpha_row_f32<const ALPHA_FIRST: bool>(
+    start_cx: usize,
+    src: *const f32,
+    dst: *mut f32,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+
+    while cx + 1 < width {
+        let pixel = vld1q_f32(src.add(cx * 4));
+        let alpha = neon_keep_alpha_f32::<ALPHA_FIRST>(
+            neon_alpha_f32::<ALPHA_FIRST>(pixel),
+            vdupq_n_f32(1.),
+        );
+        vst1q_f32(dst.add(cx * 4), vmulq_f32(pixel, alpha));
+        cx += 1;
+    }
+
+    cx
+}
+
+/// Unpremultiplies four channel f32 pixels with alpha first or last, `src` and `dst` may point to the same row
+#[inline(always)]
+pub unsafe fn neon_unpremultiply_alpha_row_f32<const ALPHA_FIRST: bool>(
+    start_cx: usize,
+    src: *const f32,
+    dst: *mut f32,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+
+    while cx + 1 < width {
+        let pixel = vld1q_f32(src.add(cx * 4));
+        let alpha = neon_alpha_f32::<ALPHA_FIRST>(pixel);
+        let non_zero = vmvnq_u32(vceqzq_f32(alpha));
+        let straight = vreinterpretq_f32_u32(vandq_u32(
+            vreinterpretq_u32_f32(vdivq_f32(pixel, alpha)),
+            non_zero,
+        ));
+        vst1q_f32(
+            dst.add(cx * 4),
+            neon_keep_alpha_f32::<ALPHA_FIRST>(straight, pixel),
+        );
+        cx += 1;
+    }
+
+    cx
+}
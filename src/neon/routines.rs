@@ -9,34 +9,20 @@
 macro_rules! load_u8_and_deinterleave {
     ($ptr: expr, $image_configuration: expr) => {{
         let (r_chan, g_chan, b_chan, a_chan);
-        match $image_configuration {
-            ImageConfiguration::Rgb | ImageConfiguration::Bgr => {
-                let ldr = vld3q_u8($ptr);
-                if $image_configuration == ImageConfiguration::Rgb {
-                    r_chan = ldr.0;
-                    g_chan = ldr.1;
-                    b_chan = ldr.2;
-                } else {
-                    r_chan = ldr.2;
-                    g_chan = ldr.1;
-                    b_chan = ldr.0;
-                }
-                a_chan = vdupq_n_u8(0);
-            }
-            ImageConfiguration::Rgba => {
-                let ldr = vld4q_u8($ptr);
-                r_chan = ldr.0;
-                g_chan = ldr.1;
-                b_chan = ldr.2;
-                a_chan = ldr.3;
-            }
-            ImageConfiguration::Bgra => {
-                let ldr = vld4q_u8($ptr);
-                r_chan = ldr.2;
-                g_chan = ldr.1;
-                b_chan = ldr.0;
-                a_chan = ldr.3;
-            }
+        if $image_configuration.has_alpha() {
+            let ldr = vld4q_u8($ptr);
+            let channels = [ldr.0, ldr.1, ldr.2, ldr.3];
+            r_chan = channels[$image_configuration.get_r_channel_offset()];
+            g_chan = channels[$image_configuration.get_g_channel_offset()];
+            b_chan = channels[$image_configuration.get_b_channel_offset()];
+            a_chan = channels[$image_configuration.get_a_channel_offset()];
+        } else {
+            let ldr = vld3q_u8($ptr);
+            let channels = [ldr.0, ldr.1, ldr.2];
+            r_chan = channels[$image_configuration.get_r_channel_offset()];
+            g_chan = channels[$image_configuration.get_g_channel_offset()];
+            b_chan = channels[$image_configuration.get_b_channel_offset()];
+            a_chan = vdupq_n_u8(0);
         }
         (r_chan, g_chan, b_chan, a_chan)
     }};
@@ -46,34 +32,20 @@ macro_rules! load_u8_and_deinterleave {
 macro_rules! load_u8_and_deinterleave_half {
     ($ptr: expr, $image_configuration: expr) => {{
         let (r_chan, g_chan, b_chan, a_chan);
-        match $image_configuration {
-            ImageConfiguration::Rgb | ImageConfiguration::Bgr => {
-                let ldr = vld3_u8($ptr);
-                if $image_configuration == ImageConfiguration::Rgb {
-                    r_chan = ldr.0;
-                    g_chan = ldr.1;
-                    b_chan = ldr.2;
-                } else {
-                    r_chan = ldr.2;
-                    g_chan = ldr.1;
-                    b_chan = ldr.0;
-                }
-                a_chan = vdup_n_u8(0);
-            }
-            ImageConfiguration::Rgba => {
-                let ldr = vld4_u8($ptr);
-                r_chan = ldr.0;
-                g_chan = ldr.1;
-                b_chan = ldr.2;
-                a_chan = ldr.3;
-            }
-            ImageConfiguration::Bgra => {
-                let ldr = vld4_u8($ptr);
-                r_chan = ldr.2;
-                g_chan = ldr.1;
-                b_chan = ldr.0;
-                a_chan = ldr.3;
-            }
+        if $image_configuration.has_alpha() {
+            let ldr = vld4_u8($ptr);
+            let channels = [ldr.0, ldr.1, ldr.2, ldr.3];
+            r_chan = channels[$image_configuration.get_r_channel_offset()];
+            g_chan = channels[$image_configuration.get_g_channel_offset()];
+            b_chan = channels[$image_configuration.get_b_channel_offset()];
+            a_chan = channels[$image_configuration.get_a_channel_offset()];
+        } else {
+            let ldr = vld3_u8($ptr);
+            let channels = [ldr.0, ldr.1, ldr.2];
+            r_chan = channels[$image_configuration.get_r_channel_offset()];
+            g_chan = channels[$image_configuration.get_g_channel_offset()];
+            b_chan = channels[$image_configuration.get_b_channel_offset()];
+            a_chan = vdup_n_u8(0);
         }
         let zero_lane = vdup_n_u8(0);
         (
@@ -90,37 +62,22 @@ macro_rules! load_u8_and_deinterleave_quarter {
     ($ptr: expr, $image_configuration: expr) => {{
         let (r_chan, g_chan, b_chan, a_chan);
         let mut transient: [u8; 32] = [0; 32];
-        match $image_configuration {
-            ImageConfiguration::Rgb | ImageConfiguration::Bgr => {
-                std::ptr::copy_nonoverlapping($ptr, transient.as_mut_ptr(), 3 * 4);
-                let ldr = vld3_u8(transient.as_mut_ptr());
-                if $image_configuration == ImageConfiguration::Rgb {
-                    r_chan = ldr.0;
-                    g_chan = ldr.1;
-                    b_chan = ldr.2;
-                } else {
-                    r_chan = ldr.2;
-                    g_chan = ldr.1;
-                    b_chan = ldr.0;
-                }
-                a_chan = vdup_n_u8(0);
-            }
-            ImageConfiguration::Rgba => {
-                std::ptr::copy_nonoverlapping($ptr, transient.as_mut_ptr(), 4 * 4);
-                let ldr = vld4_u8(transient.as_mut_ptr());
-                r_chan = ldr.0;
-                g_chan = ldr.1;
-                b_chan = ldr.2;
-                a_chan = ldr.3;
-            }
-            ImageConfiguration::Bgra => {
-                std::ptr::copy_nonoverlapping($ptr, transient.as_mut_ptr(), 4 * 4);
-                let ldr = vld4_u8(transient.as_mut_ptr());
-                r_chan = ldr.2;
-                g_chan = ldr.1;
-                b_chan = ldr.0;
-                a_chan = ldr.3;
-            }
+        if $image_configuration.has_alpha() {
+            std::ptr::copy_nonoverlapping($ptr, transient.as_mut_ptr(), 4 * 4);
+            let ldr = vld4_u8(transient.as_mut_ptr());
+            let channels = [ldr.0, ldr.1, ldr.2, ldr.3];
+            r_chan = channels[$image_configuration.get_r_channel_offset()];
+            g_chan = channels[$image_configuration.get_g_channel_offset()];
+            b_chan = channels[$image_configuration.get_b_channel_offset()];
+            a_chan = channels[$image_configuration.get_a_channel_offset()];
+        } else {
+            std::ptr::copy_nonoverlapping($ptr, transient.as_mut_ptr(), 3 * 4);
+            let ldr = vld3_u8(transient.as_mut_ptr());
+            let channels = [ldr.0, ldr.1, ldr.2];
+            r_chan = channels[$image_configuration.get_r_channel_offset()];
+            g_chan = channels[$image_configuration.get_g_channel_offset()];
+            b_chan = channels[$image_configuration.get_b_channel_offset()];
+            a_chan = vdup_n_u8(0);
         }
         let zero_lane = vdup_n_u8(0);
         (
@@ -137,33 +94,20 @@ macro_rules! load_f32_and_deinterleave {
     ($ptr: expr, $image_configuration: expr) => {{
         let d_alpha = vdupq_n_f32(1f32);
         let (r_f32, g_f32, b_f32, a_f32);
-        match $image_configuration {
-            ImageConfiguration::Rgba | ImageConfiguration::Bgra => {
-                let rgba_pixels = vld4q_f32($ptr);
-                if $image_configuration == ImageConfiguration::Rgba {
-                    r_f32 = rgba_pixels.0;
-                    g_f32 = rgba_pixels.1;
-                    b_f32 = rgba_pixels.2;
-                } else {
-                    r_f32 = rgba_pixels.2;
-                    g_f32 = rgba_pixels.1;
-                    b_f32 = rgba_pixels.0;
-                }
-                a_f32 = rgba_pixels.3;
-            }
-            ImageConfiguration::Bgr | ImageConfiguration::Rgb => {
-                let rgb_pixels = vld3q_f32($ptr);
-                if $image_configuration == ImageConfiguration::Rgb {
-                    r_f32 = rgb_pixels.0;
-                    g_f32 = rgb_pixels.1;
-                    b_f32 = rgb_pixels.2;
-                } else {
-                    r_f32 = rgb_pixels.2;
-                    g_f32 = rgb_pixels.1;
-                    b_f32 = rgb_pixels.0;
-                }
-                a_f32 = d_alpha;
-            }
+        if $image_configuration.has_alpha() {
+            let rgba_pixels = vld4q_f32($ptr);
+            let channels = [rgba_pixels.0, rgba_pixels.1, rgba_pixels.2, rgba_pixels.3];
+            r_f32 = channels[$image_configuration.get_r_channel_offset()];
+            g_f32 = channels[$image_configuration.get_g_channel_offset()];
+            b_f32 = channels[$image_configuration.get_b_channel_offset()];
+            a_f32 = channels[$image_configuration.get_a_channel_offset()];
+        } else {
+            let rgb_pixels = vld3q_f32($ptr);
+            let channels = [rgb_pixels.0, rgb_pixels.1, rgb_pixels.2];
+            r_f32 = channels[$image_configuration.get_r_channel_offset()];
+            g_f32 = channels[$image_configuration.get_g_channel_offset()];
+            b_f32 = channels[$image_configuration.get_b_channel_offset()];
+            a_f32 = d_alpha;
         }
         (r_f32, g_f32, b_f32, a_f32)
     }};
@@ -174,21 +118,18 @@ macro_rules! load_f32_and_deinterleave_direct {
     ($ptr: expr, $image_configuration: expr) => {{
         let d_alpha = vdupq_n_f32(1f32);
         let (r_f32, g_f32, b_f32, a_f32);
-        match $image_configuration {
-            ImageConfiguration::Rgba | ImageConfiguration::Bgra => {
-                let rgba_pixels = vld4q_f32($ptr);
-                r_f32 = rgba_pixels.0;
-                g_f32 = rgba_pixels.1;
-                b_f32 = rgba_pixels.2;
-                a_f32 = rgba_pixels.3;
-            }
-            ImageConfiguration::Bgr | ImageConfiguration::Rgb => {
-                let rgb_pixels = vld3q_f32($ptr);
-                r_f32 = rgb_pixels.0;
-                g_f32 = rgb_pixels.1;
-                b_f32 = rgb_pixels.2;
-                a_f32 = d_alpha;
-            }
+        if $image_configuration.has_alpha() {
+            let rgba_pixels = vld4q_f32($ptr);
+            r_f32 = rgba_pixels.0;
+            g_f32 = rgba_pixels.1;
+            b_f32 = rgba_pixels.2;
+            a_f32 = rgba_pixels.3;
+        } else {
+            let rgb_pixels = vld3q_f32($ptr);
+            r_f32 = rgb_pixels.0;
+            g_f32 = rgb_pixels.1;
+            b_f32 = rgb_pixels.2;
+            a_f32 = d_alpha;
         }
         (r_f32, g_f32, b_f32, a_f32)
     }};
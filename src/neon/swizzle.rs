@@ -0,0 +1,35 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+
+use crate::swizzle::SwizzleShuffle;
+use std::arch::aarch64::*;
+
+/// Reorders channels of a row block by block, stops when next block doesn't fit into the row
+#[inline(always)]
+pub unsafe fn neon_swizzle_row(
+    start_cx: usize,
+    src: *const u8,
+    dst: *mut u8,
+    width: usize,
+    shuffle: &SwizzleShuffle,
+) -> usize {
+    let mut cx = start_cx;
+
+    let mask = vld1q_u8(shuffle.mask.as_ptr());
+    let fill = vld1q_u8(shuffle.fill.as_ptr());
+
+    while (width - cx) * shuffle.src_pixel_bytes >= 16
+        && (width - cx) * shuffle.dst_pixel_bytes >= 16
+    {
+        let pixels = vld1q_u8(src.add(cx * shuffle.src_pixel_bytes));
+        let reordered = vorrq_u8(vqtbl1q_u8(pixels, mask), fill);
+        vst1q_u8(dst.add(cx * shuffle.dst_pixel_bytes), reordered);
+        cx += shuffle.pixels;
+    }
+
+    cx
+}
@@ -50,22 +50,28 @@ pub unsafe fn neon_image_to_sigmoidal<const CHANNELS_CONFIGURATION: u8, const US
             let a_low_low =
                 vmulq_n_f32(vcvtq_f32_u32(vmovl_u16(vget_low_u16(a_low))), 1f32 / 255f32);
             let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
+                ImageConfiguration::Rgb | ImageConfiguration::Rgba | ImageConfiguration::Argb => {
                     float32x4x4_t(x_low_low, y_low_low, z_low_low, a_low_low)
                 }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
+                ImageConfiguration::Bgra | ImageConfiguration::Bgr | ImageConfiguration::Abgr => {
                     float32x4x4_t(z_low_low, y_low_low, x_low_low, a_low_low)
                 }
+                ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                    unreachable!("Gray layouts are not supported by colour kernels")
+                }
             };
             vst4q_f32(dst_ptr.add(cx * channels), store_rows);
         } else {
             let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
+                ImageConfiguration::Rgb | ImageConfiguration::Rgba | ImageConfiguration::Argb => {
                     float32x4x3_t(x_low_low, y_low_low, z_low_low)
                 }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
+                ImageConfiguration::Bgra | ImageConfiguration::Bgr | ImageConfiguration::Abgr => {
                     float32x4x3_t(z_low_low, y_low_low, x_low_low)
                 }
+                ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                    unreachable!("Gray layouts are not supported by colour kernels")
+                }
             };
             vst3q_f32(dst_ptr.add(cx * channels), store_rows);
         }
@@ -79,22 +85,28 @@ pub unsafe fn neon_image_to_sigmoidal<const CHANNELS_CONFIGURATION: u8, const US
         if USE_ALPHA {
             let a_low_high = vmulq_n_f32(vcvtq_f32_u32(vmovl_high_u16(a_low)), 1f32 / 255f32);
             let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
+                ImageConfiguration::Rgb | ImageConfiguration::Rgba | ImageConfiguration::Argb => {
                     float32x4x4_t(x_low_high, y_low_high, z_low_high, a_low_high)
                 }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
+                ImageConfiguration::Bgra | ImageConfiguration::Bgr | ImageConfiguration::Abgr => {
                     float32x4x4_t(z_low_high, y_low_high, x_low_high, a_low_high)
                 }
+                ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                    unreachable!("Gray layouts are not supported by colour kernels")
+                }
             };
             vst4q_f32(dst_ptr.add(cx * channels + 4 * channels), store_rows);
         } else {
             let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
+                ImageConfiguration::Rgb | ImageConfiguration::Rgba | ImageConfiguration::Argb => {
                     float32x4x3_t(x_low_high, y_low_high, z_low_high)
                 }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
+                ImageConfiguration::Bgra | ImageConfiguration::Bgr | ImageConfiguration::Abgr => {
                     float32x4x3_t(z_low_high, y_low_high, x_low_high)
                 }
+                ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                    unreachable!("Gray layouts are not supported by colour kernels")
+                }
             };
             vst3q_f32(dst_ptr.add(cx * channels + 4 * channels), store_rows);
         }
@@ -118,22 +130,28 @@ pub unsafe fn neon_image_to_sigmoidal<const CHANNELS_CONFIGURATION: u8, const US
                 1f32 / 255f32,
             );
             let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
+                ImageConfiguration::Rgb | ImageConfiguration::Rgba | ImageConfiguration::Argb => {
                     float32x4x4_t(x_high_low, y_high_low, z_high_low, a_high_low)
                 }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
+                ImageConfiguration::Bgra | ImageConfiguration::Bgr | ImageConfiguration::Abgr => {
                     float32x4x4_t(z_high_low, y_high_low, x_high_low, a_high_low)
                 }
+                ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                    unreachable!("Gray layouts are not supported by colour kernels")
+                }
             };
             vst4q_f32(dst_ptr.add(cx * channels + 4 * channels * 2), store_rows);
         } else {
             let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
+                ImageConfiguration::Rgb | ImageConfiguration::Rgba | ImageConfiguration::Argb => {
                     float32x4x3_t(x_high_low, y_high_low, z_high_low)
                 }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
+                ImageConfiguration::Bgra | ImageConfiguration::Bgr | ImageConfiguration::Abgr => {
                     float32x4x3_t(z_high_low, y_high_low, x_high_low)
                 }
+                ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                    unreachable!("Gray layouts are not supported by colour kernels")
+                }
             };
             vst3q_f32(dst_ptr.add(cx * channels + 4 * channels * 2), store_rows);
         }
@@ -148,22 +166,28 @@ pub unsafe fn neon_image_to_sigmoidal<const CHANNELS_CONFIGURATION: u8, const US
         if USE_ALPHA {
             let a_high_high = vmulq_n_f32(vcvtq_f32_u32(vmovl_high_u16(a_high)), 1f32 / 255f32);
             let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
+                ImageConfiguration::Rgb | ImageConfiguration::Rgba | ImageConfiguration::Argb => {
                     float32x4x4_t(x_high_high, y_high_high, z_high_high, a_high_high)
                 }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
+                ImageConfiguration::Bgra | ImageConfiguration::Bgr | ImageConfiguration::Abgr => {
                     float32x4x4_t(z_high_high, y_high_high, x_high_high, a_high_high)
                 }
+                ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                    unreachable!("Gray layouts are not supported by colour kernels")
+                }
             };
             vst4q_f32(dst_ptr.add(cx * channels + 4 * channels * 3), store_rows);
         } else {
             let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
+                ImageConfiguration::Rgb | ImageConfiguration::Rgba | ImageConfiguration::Argb => {
                     float32x4x3_t(x_high_high, y_high_high, z_high_high)
                 }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
+                ImageConfiguration::Bgra | ImageConfiguration::Bgr | ImageConfiguration::Abgr => {
                     float32x4x3_t(z_high_high, y_high_high, x_high_high)
                 }
+                ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                    unreachable!("Gray layouts are not supported by colour kernels")
+                }
             };
             vst3q_f32(dst_ptr.add(cx * channels + 4 * channels * 3), store_rows);
         }
@@ -192,22 +216,28 @@ pub unsafe fn neon_image_to_sigmoidal<const CHANNELS_CONFIGURATION: u8, const US
             let a_low_low =
                 vmulq_n_f32(vcvtq_f32_u32(vmovl_u16(vget_low_u16(a_low))), 1f32 / 255f32);
             let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
+                ImageConfiguration::Rgb | ImageConfiguration::Rgba | ImageConfiguration::Argb => {
                     float32x4x4_t(x_low_low, y_low_low, z_low_low, a_low_low)
                 }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
+                ImageConfiguration::Bgra | ImageConfiguration::Bgr | ImageConfiguration::Abgr => {
                     float32x4x4_t(z_low_low, y_low_low, x_low_low, a_low_low)
                 }
+                ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                    unreachable!("Gray layouts are not supported by colour kernels")
+                }
             };
             vst4q_f32(dst_ptr.add(cx * channels), store_rows);
         } else {
             let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
+                ImageConfiguration::Rgb | ImageConfiguration::Rgba | ImageConfiguration::Argb => {
                     float32x4x3_t(x_low_low, y_low_low, z_low_low)
                 }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
+                ImageConfiguration::Bgra | ImageConfiguration::Bgr | ImageConfiguration::Abgr => {
                     float32x4x3_t(z_low_low, y_low_low, x_low_low)
                 }
+                ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                    unreachable!("Gray layouts are not supported by colour kernels")
+                }
             };
             vst3q_f32(dst_ptr.add(cx * channels), store_rows);
         }
@@ -221,22 +251,28 @@ pub unsafe fn neon_image_to_sigmoidal<const CHANNELS_CONFIGURATION: u8, const US
         if USE_ALPHA {
             let a_low_high = vmulq_n_f32(vcvtq_f32_u32(vmovl_high_u16(a_low)), 1f32 / 255f32);
             let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
+                ImageConfiguration::Rgb | ImageConfiguration::Rgba | ImageConfiguration::Argb => {
                     float32x4x4_t(x_low_high, y_low_high, z_low_high, a_low_high)
                 }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
+                ImageConfiguration::Bgra | ImageConfiguration::Bgr | ImageConfiguration::Abgr => {
                     float32x4x4_t(z_low_high, y_low_high, x_low_high, a_low_high)
                 }
+                ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                    unreachable!("Gray layouts are not supported by colour kernels")
+                }
             };
             vst4q_f32(dst_ptr.add(cx * channels + 4 * channels), store_rows);
         } else {
             let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
+                ImageConfiguration::Rgb | ImageConfiguration::Rgba | ImageConfiguration::Argb => {
                     float32x4x3_t(x_low_high, y_low_high, z_low_high)
                 }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
+                ImageConfiguration::Bgra | ImageConfiguration::Bgr | ImageConfiguration::Abgr => {
                     float32x4x3_t(z_low_high, y_low_high, x_low_high)
                 }
+                ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                    unreachable!("Gray layouts are not supported by colour kernels")
+                }
             };
             vst3q_f32(dst_ptr.add(cx * channels + 4 * channels), store_rows);
         }
@@ -265,22 +301,28 @@ pub unsafe fn neon_image_to_sigmoidal<const CHANNELS_CONFIGURATION: u8, const US
             let a_low_low =
                 vmulq_n_f32(vcvtq_f32_u32(vmovl_u16(vget_low_u16(a_low))), 1f32 / 255f32);
             let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
+                ImageConfiguration::Rgb | ImageConfiguration::Rgba | ImageConfiguration::Argb => {
                     float32x4x4_t(x_low_low, y_low_low, z_low_low, a_low_low)
                 }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
+                ImageConfiguration::Bgra | ImageConfiguration::Bgr | ImageConfiguration::Abgr => {
                     float32x4x4_t(z_low_low, y_low_low, x_low_low, a_low_low)
                 }
+                ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                    unreachable!("Gray layouts are not supported by colour kernels")
+                }
             };
             vst4q_f32(dst_ptr.add(cx * channels), store_rows);
         } else {
             let store_rows = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
+                ImageConfiguration::Rgb | ImageConfiguration::Rgba | ImageConfiguration::Argb => {
                     float32x4x3_t(x_low_low, y_low_low, z_low_low)
                 }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
+                ImageConfiguration::Bgra | ImageConfiguration::Bgr | ImageConfiguration::Abgr => {
                     float32x4x3_t(z_low_low, y_low_low, x_low_low)
                 }
+                ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                    unreachable!("Gray layouts are not supported by colour kernels")
+                }
             };
             vst3q_f32(dst_ptr.add(cx * channels), store_rows);
         }
@@ -6,36 +6,30 @@
  */
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::avx::avx_oklab_to_image;
+use crate::gamma_curves::gamma_table_u8;
 use crate::image::ImageConfiguration;
 use crate::image_to_oklab::OklabTarget;
 #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
 use crate::neon::neon_oklab_to_image;
 use crate::oklch::Oklch;
+use crate::premultiply::premultiply_row_u8;
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::sse::sse_oklab_to_image;
+use crate::threading::{current_threading, ForEachRow, ThreadingPolicy};
 use crate::{Oklab, Rgb, TransferFunction};
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 use std::slice;
 
-#[allow(clippy::type_complexity)]
-fn oklab_to_image<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
-    src: &[f32],
-    src_stride: u32,
-    dst: &mut [u8],
-    dst_stride: u32,
-    width: u32,
-    height: u32,
-    transfer_function: TransferFunction,
-) {
-    let target: OklabTarget = TARGET.into();
-    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+pub(crate) type OklabToImageRowHandler =
+    unsafe fn(usize, *const f32, usize, *mut f32, u32, u32) -> usize;
 
-    let mut _wide_row_handle: Option<
-        unsafe fn(usize, *const f32, usize, *mut f32, u32, u32) -> usize,
-    > = None;
+/// Selects the widest available SIMD row handler for the layout
+pub(crate) fn oklab_to_image_row_handler<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+) -> Option<OklabToImageRowHandler> {
+    let mut _wide_row_handle: Option<OklabToImageRowHandler> = None;
 
     #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
     if std::arch::is_x86_feature_detected!("sse4.1") {
@@ -52,10 +46,52 @@ fn oklab_to_image<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
         _wide_row_handle = Some(neon_oklab_to_image::<CHANNELS_CONFIGURATION, TARGET>);
     }
 
-    let mut lut_table = vec![0u8; 2049];
-    for (i, lut) in lut_table.iter_mut().enumerate() {
-        *lut = (transfer_function.gamma(i as f32 * (1. / 2048.0)) * 255.).min(255.) as u8;
-    }
+    _wide_row_handle
+}
+
+pub(crate) fn oklab_to_image<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+    premultiplied: bool,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let premultiply = premultiplied && image_configuration.has_alpha();
+
+    oklab_to_image_impl::<CHANNELS_CONFIGURATION, TARGET>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        &gamma_table_u8(transfer_function),
+        premultiply,
+        oklab_to_image_row_handler::<CHANNELS_CONFIGURATION, TARGET>(),
+        &current_threading(),
+    );
+}
+
+/// Converts image with precomputed gamma table and row handler,
+/// rows are premultiplied by alpha after encoding when `premultiply` is set
+pub(crate) fn oklab_to_image_impl<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    lut_table: &[u8],
+    premultiply: bool,
+    _wide_row_handle: Option<OklabToImageRowHandler>,
+    policy: &ThreadingPolicy,
+) {
+    let target: OklabTarget = TARGET.into();
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
 
     let channels = image_configuration.get_channels_count();
 
@@ -81,7 +117,7 @@ fn oklab_to_image<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
             .zip(src_slice_safe_align.chunks_exact(src_stride as usize));
     }
 
-    iter.for_each(|(dst, src)| unsafe {
+    iter.for_each_row_with(policy, width as usize, |(dst, src)| unsafe {
         let mut _cx = 0usize;
 
         let mut transient_row = vec![0f32; width as usize * channels];
@@ -144,10 +180,15 @@ fn oklab_to_image<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
             dst_chunks[image_configuration.get_b_channel_offset()] =
                 *lut_table.get_unchecked((rgb.b as usize).min(2048));
             if image_configuration.has_alpha() {
-                let a_lin = (src_chunks[3] * 255f32).round() as u8;
+                let a_lin =
+                    (src_chunks[image_configuration.get_a_channel_offset()] * 255f32).round() as u8;
                 dst_chunks[image_configuration.get_a_channel_offset()] = a_lin;
             }
         }
+
+        if premultiply {
+            premultiply_row_u8::<CHANNELS_CONFIGURATION>(dst, width as usize);
+        }
     });
 }
 
@@ -178,6 +219,100 @@ pub fn oklab_to_rgba(
         width,
         height,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts Oklab with interleaved alpha channel to ARGB. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn oklab_to_argb(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    oklab_to_image::<{ ImageConfiguration::Argb as u8 }, { OklabTarget::Oklab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        false,
+    );
+}
+
+/// This function converts Oklab with interleaved alpha channel to RGBA. This is much more effective than naive direct transformation. Alpha is premultiplied into colour channels of the output
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGBA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn oklab_to_rgba_premultiplied(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    oklab_to_image::<{ ImageConfiguration::Rgba as u8 }, { OklabTarget::Oklab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        true,
+    );
+}
+
+/// This function converts Oklab with interleaved alpha channel to ARGB. This is much more effective than naive direct transformation. Alpha is premultiplied into colour channels of the output
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn oklab_to_argb_premultiplied(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    oklab_to_image::<{ ImageConfiguration::Argb as u8 }, { OklabTarget::Oklab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        true,
     );
 }
 
@@ -208,6 +343,7 @@ pub fn oklab_to_rgb(
         width,
         height,
         transfer_function,
+        false,
     );
 }
 
@@ -238,6 +374,7 @@ pub fn oklab_to_bgr(
         width,
         height,
         transfer_function,
+        false,
     );
 }
 
@@ -268,6 +405,100 @@ pub fn oklab_to_bgra(
         width,
         height,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts Oklab with interleaved alpha channel to ABGR. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn oklab_to_abgr(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    oklab_to_image::<{ ImageConfiguration::Abgr as u8 }, { OklabTarget::Oklab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        false,
+    );
+}
+
+/// This function converts Oklab with interleaved alpha channel to BGRA. This is much more effective than naive direct transformation. Alpha is premultiplied into colour channels of the output
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGRA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn oklab_to_bgra_premultiplied(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    oklab_to_image::<{ ImageConfiguration::Bgra as u8 }, { OklabTarget::Oklab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        true,
+    );
+}
+
+/// This function converts Oklab with interleaved alpha channel to ABGR. This is much more effective than naive direct transformation. Alpha is premultiplied into colour channels of the output
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn oklab_to_abgr_premultiplied(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    oklab_to_image::<{ ImageConfiguration::Abgr as u8 }, { OklabTarget::Oklab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        true,
     );
 }
 
@@ -298,6 +529,100 @@ pub fn oklch_to_rgba(
         width,
         height,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts *Oklch* with interleaved alpha channel to ARGB. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains LCH data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn oklch_to_argb(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    oklab_to_image::<{ ImageConfiguration::Argb as u8 }, { OklabTarget::Oklch as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        false,
+    );
+}
+
+/// This function converts *Oklch* with interleaved alpha channel to RGBA. This is much more effective than naive direct transformation. Alpha is premultiplied into colour channels of the output
+///
+/// # Arguments
+/// * `src` - A slice contains LCH data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGBA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn oklch_to_rgba_premultiplied(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    oklab_to_image::<{ ImageConfiguration::Rgba as u8 }, { OklabTarget::Oklch as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        true,
+    );
+}
+
+/// This function converts *Oklch* with interleaved alpha channel to ARGB. This is much more effective than naive direct transformation. Alpha is premultiplied into colour channels of the output
+///
+/// # Arguments
+/// * `src` - A slice contains LCH data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn oklch_to_argb_premultiplied(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    oklab_to_image::<{ ImageConfiguration::Argb as u8 }, { OklabTarget::Oklch as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        true,
     );
 }
 
@@ -328,6 +653,7 @@ pub fn oklch_to_rgb(
         width,
         height,
         transfer_function,
+        false,
     );
 }
 
@@ -358,6 +684,7 @@ pub fn oklch_to_bgr(
         width,
         height,
         transfer_function,
+        false,
     );
 }
 
@@ -388,5 +715,99 @@ pub fn oklch_to_bgra(
         width,
         height,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts *Oklch* with interleaved alpha channel to ABGR. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains LCH data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn oklch_to_abgr(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    oklab_to_image::<{ ImageConfiguration::Abgr as u8 }, { OklabTarget::Oklch as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        false,
+    );
+}
+
+/// This function converts *Oklch* with interleaved alpha channel to BGRA. This is much more effective than naive direct transformation. Alpha is premultiplied into colour channels of the output
+///
+/// # Arguments
+/// * `src` - A slice contains LCH data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGRA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn oklch_to_bgra_premultiplied(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    oklab_to_image::<{ ImageConfiguration::Bgra as u8 }, { OklabTarget::Oklch as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        true,
+    );
+}
+
+/// This function converts *Oklch* with interleaved alpha channel to ABGR. This is much more effective than naive direct transformation. Alpha is premultiplied into colour channels of the output
+///
+/// # Arguments
+/// * `src` - A slice contains LCH data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function from linear colorspace to gamma
+pub fn oklch_to_abgr_premultiplied(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    oklab_to_image::<{ ImageConfiguration::Abgr as u8 }, { OklabTarget::Oklch as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        transfer_function,
+        true,
     );
 }
@@ -0,0 +1,1039 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::image::ImageConfiguration;
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use crate::neon::{neon_pack_row, neon_unpack_row};
+use crate::planar::{convert_rows, interleaved_rows, interleaved_rows_mut};
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+use crate::sse::{sse_pack_row, sse_unpack_row};
+use crate::threading::current_threading;
+use crate::ImageSample;
+use half::f16;
+
+/// Packed pixel format of framebuffers and GPU textures, every pixel is stored in one or several
+/// machine words which byte order is given by [ByteOrder]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PackedFormat {
+    /// 16 bit word, red in 5 most significant bits, then 6 bits of green and blue in 5 least significant bits
+    Rgb565 = 0,
+    /// 32 bit word, red in bits 0..10, green in bits 10..20, blue in bits 20..30 and alpha in bits 30..32
+    Rgba1010102 = 1,
+    /// Four IEEE 754 half floats R, G, B and A, 8 bytes per pixel
+    RgbaF16 = 2,
+    /// 32 bit word of unsigned floats, 11 bits red in bits 0..11, 11 bits green in bits 11..22
+    /// and 10 bits blue in bits 22..32, same as `VK_FORMAT_B10G11R11_UFLOAT_PACK32`
+    R11G11B10F = 3,
+    /// 32 bit word, 9 bits mantissas of red, green and blue in bits 0..9, 9..18 and 18..27 sharing
+    /// 5 bits exponent in bits 27..32, same as `VK_FORMAT_E5B9G9R9_UFLOAT_PACK32`
+    Rgb9E5 = 4,
+}
+
+impl PackedFormat {
+    /// Returns count of bytes of one pixel
+    pub const fn bytes_per_pixel(&self) -> usize {
+        match self {
+            PackedFormat::Rgb565 => 2,
+            PackedFormat::Rgba1010102 => 4,
+            PackedFormat::RgbaF16 => 8,
+            PackedFormat::R11G11B10F => 4,
+            PackedFormat::Rgb9E5 => 4,
+        }
+    }
+
+    /// Returns true if format stores alpha
+    pub const fn has_alpha(&self) -> bool {
+        matches!(self, PackedFormat::Rgba1010102 | PackedFormat::RgbaF16)
+    }
+}
+
+impl From<u8> for PackedFormat {
+    #[inline(always)]
+    fn from(value: u8) -> Self {
+        match value {
+            0 => PackedFormat::Rgb565,
+            1 => PackedFormat::Rgba1010102,
+            2 => PackedFormat::RgbaF16,
+            3 => PackedFormat::R11G11B10F,
+            4 => PackedFormat::Rgb9E5,
+            _ => {
+                panic!("Unknown value")
+            }
+        }
+    }
+}
+
+/// Byte order of packed words in memory
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ByteOrder {
+    LittleEndian,
+    BigEndian,
+}
+
+type UnpackRowHandler = unsafe fn(usize, *const u8, *mut f32, usize, bool) -> usize;
+
+type PackRowHandler = unsafe fn(usize, *const f32, *mut u8, usize, bool) -> usize;
+
+macro_rules! with_packed_layouts {
+    ($format:expr, $layout:expr, $func:ident, ($($args:expr),* $(,)?)) => {
+        match $format {
+            PackedFormat::Rgb565 => with_packed_layouts!(@layout Rgb565, $layout, $func, ($($args),*)),
+            PackedFormat::Rgba1010102 => with_packed_layouts!(@layout Rgba1010102, $layout, $func, ($($args),*)),
+            PackedFormat::RgbaF16 => with_packed_layouts!(@layout RgbaF16, $layout, $func, ($($args),*)),
+            PackedFormat::R11G11B10F => with_packed_layouts!(@layout R11G11B10F, $layout, $func, ($($args),*)),
+            PackedFormat::Rgb9E5 => with_packed_layouts!(@layout Rgb9E5, $layout, $func, ($($args),*)),
+        }
+    };
+    (@layout $format:ident, $layout:expr, $func:ident, ($($args:expr),*)) => {
+        match $layout {
+            ImageConfiguration::Rgb => with_packed_layouts!(@call $format, Rgb, $func, ($($args),*)),
+            ImageConfiguration::Rgba => with_packed_layouts!(@call $format, Rgba, $func, ($($args),*)),
+            ImageConfiguration::Bgra => with_packed_layouts!(@call $format, Bgra, $func, ($($args),*)),
+            ImageConfiguration::Bgr => with_packed_layouts!(@call $format, Bgr, $func, ($($args),*)),
+            ImageConfiguration::Argb => with_packed_layouts!(@call $format, Argb, $func, ($($args),*)),
+            ImageConfiguration::Abgr => with_packed_layouts!(@call $format, Abgr, $func, ($($args),*)),
+            ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                panic!("Packed formats may be converted only with colour layouts, but {:?} was given", $layout)
+            }
+        }
+    };
+    (@call $format:ident, $layout:ident, $func:ident, ($($args:expr),*)) => {
+        $func::<_, { PackedFormat::$format as u8 }, { ImageConfiguration::$layout as u8 }>($($args),*)
+    };
+}
+
+#[inline(always)]
+fn read_u16(bytes: &[u8], big_endian: bool) -> u16 {
+    let bytes = [bytes[0], bytes[1]];
+    if big_endian {
+        u16::from_be_bytes(bytes)
+    } else {
+        u16::from_le_bytes(bytes)
+    }
+}
+
+#[inline(always)]
+fn read_u32(bytes: &[u8], big_endian: bool) -> u32 {
+    let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+    if big_endian {
+        u32::from_be_bytes(bytes)
+    } else {
+        u32::from_le_bytes(bytes)
+    }
+}
+
+#[inline(always)]
+fn write_u16(bytes: &mut [u8], value: u16, big_endian: bool) {
+    let value = if big_endian {
+        value.to_be_bytes()
+    } else {
+        value.to_le_bytes()
+    };
+    bytes[..2].copy_from_slice(&value);
+}
+
+#[inline(always)]
+fn write_u32(bytes: &mut [u8], value: u32, big_endian: bool) {
+    let value = if big_endian {
+        value.to_be_bytes()
+    } else {
+        value.to_le_bytes()
+    };
+    bytes[..4].copy_from_slice(&value);
+}
+
+#[inline(always)]
+fn unorm_to_float(value: u32, max: f32) -> f32 {
+    value as f32 / max
+}
+
+/// Clamps normalized value into [0, 1], scales by `max` and rounds half up, same as SIMD handlers do
+#[inline(always)]
+fn quantize_unorm(value: f32, max: f32) -> u32 {
+    (value.max(0f32).min(1f32) * max + 0.5f32) as u32
+}
+
+/// Clamps value into [0, `max`], NaN and negative values including -0 become +0
+#[inline(always)]
+fn clamp_unsigned_float(value: f32, max: f32) -> f32 {
+    if value > 0f32 {
+        value.min(max)
+    } else {
+        0f32
+    }
+}
+
+/// Encodes value into unsigned float with 5 bits exponent and `mantissa` bits mantissa
+/// rounding to nearest even, NaN and negative values become zero, values above maximum are saturated
+#[inline(always)]
+fn float_to_small_float(value: f32, mantissa: u32) -> u32 {
+    let max_value = ((1u32 << (mantissa + 1)) - 1) as f32 * (1u32 << (15 - mantissa)) as f32;
+    let value = clamp_unsigned_float(value, max_value);
+    let bits = value.to_bits();
+    if bits < (127 - 14) << 23 {
+        let magic = f32::from_bits((136 - mantissa) << 23);
+        (value + magic).to_bits() - magic.to_bits()
+    } else {
+        let odd = (bits >> (23 - mantissa)) & 1;
+        (bits - ((127 - 15) << 23) + ((1 << (22 - mantissa)) - 1) + odd) >> (23 - mantissa)
+    }
+}
+
+/// Decodes unsigned float with 5 bits exponent and `mantissa` bits mantissa
+#[inline(always)]
+fn small_float_to_float(value: u32, mantissa: u32) -> f32 {
+    f16::from_bits((value << (10 - mantissa)) as u16).to_f32()
+}
+
+/// Scales value by 2^(15 + 9 - `exponent`) and rounds half up into 9 bits mantissa of RGB9E5
+#[inline(always)]
+fn shared_mantissa(value: f32, exponent: u32) -> u32 {
+    (value * f32::from_bits((127 + 15 + 9 - exponent) << 23)).round() as u32
+}
+
+/// Encodes RGB into RGB9E5 as described in `EXT_texture_shared_exponent`
+#[inline(always)]
+fn float_to_rgb9e5(r: f32, g: f32, b: f32) -> u32 {
+    const MAX_VALUE: f32 = 65408f32;
+    let r = clamp_unsigned_float(r, MAX_VALUE);
+    let g = clamp_unsigned_float(g, MAX_VALUE);
+    let b = clamp_unsigned_float(b, MAX_VALUE);
+    let max_channel = r.max(g).max(b);
+    let biased_exponent = (max_channel.to_bits() >> 23) as i32;
+    let mut exponent = (biased_exponent - (127 - 16)).max(0) as u32;
+    if shared_mantissa(max_channel, exponent) == 512 {
+        exponent += 1;
+    }
+    shared_mantissa(r, exponent)
+        | (shared_mantissa(g, exponent) << 9)
+        | (shared_mantissa(b, exponent) << 18)
+        | (exponent << 27)
+}
+
+/// Decodes one packed pixel into R, G, B and A, alpha is 1 when format has no alpha
+#[inline(always)]
+fn unpack_pixel(format: PackedFormat, bytes: &[u8], big_endian: bool) -> [f32; 4] {
+    match format {
+        PackedFormat::Rgb565 => {
+            let word = read_u16(bytes, big_endian) as u32;
+            [
+                unorm_to_float(word >> 11, 31f32),
+                unorm_to_float((word >> 5) & 0x3f, 63f32),
+                unorm_to_float(word & 0x1f, 31f32),
+                1f32,
+            ]
+        }
+        PackedFormat::Rgba1010102 => {
+            let word = read_u32(bytes, big_endian);
+            [
+                unorm_to_float(word & 0x3ff, 1023f32),
+                unorm_to_float((word >> 10) & 0x3ff, 1023f32),
+                unorm_to_float((word >> 20) & 0x3ff, 1023f32),
+                unorm_to_float(word >> 30, 3f32),
+            ]
+        }
+        PackedFormat::RgbaF16 => {
+            let mut pixel = [0f32; 4];
+            for (channel, half) in pixel.iter_mut().zip(bytes.chunks_exact(2)) {
+                *channel = f16::from_bits(read_u16(half, big_endian)).to_f32();
+            }
+            pixel
+        }
+        PackedFormat::R11G11B10F => {
+            let word = read_u32(bytes, big_endian);
+            [
+                small_float_to_float(word & 0x7ff, 6),
+                small_float_to_float((word >> 11) & 0x7ff, 6),
+                small_float_to_float(word >> 22, 5),
+                1f32,
+            ]
+        }
+        PackedFormat::Rgb9E5 => {
+            let word = read_u32(bytes, big_endian);
+            let scale = f32::from_bits(((word >> 27) + 127 - 15 - 9) << 23);
+            [
+                (word & 0x1ff) as f32 * scale,
+                ((word >> 9) & 0x1ff) as f32 * scale,
+                ((word >> 18) & 0x1ff) as f32 * scale,
+                1f32,
+            ]
+        }
+    }
+}
+
+/// Encodes R, G, B and A into one packed pixel
+#[inline(always)]
+fn pack_pixel(format: PackedFormat, pixel: [f32; 4], bytes: &mut [u8], big_endian: bool) {
+    let [r, g, b, a] = pixel;
+    match format {
+        PackedFormat::Rgb565 => {
+            let word = (quantize_unorm(r, 31f32) << 11)
+                | (quantize_unorm(g, 63f32) << 5)
+                | quantize_unorm(b, 31f32);
+            write_u16(bytes, word as u16, big_endian);
+        }
+        PackedFormat::Rgba1010102 => {
+            let word = quantize_unorm(r, 1023f32)
+                | (quantize_unorm(g, 1023f32) << 10)
+                | (quantize_unorm(b, 1023f32) << 20)
+                | (quantize_unorm(a, 3f32) << 30);
+            write_u32(bytes, word, big_endian);
+        }
+        PackedFormat::RgbaF16 => {
+            for (half, channel) in bytes.chunks_exact_mut(2).zip(pixel) {
+                write_u16(half, f16::from_f32(channel).to_bits(), big_endian);
+            }
+        }
+        PackedFormat::R11G11B10F => {
+            let word = float_to_small_float(r, 6)
+                | (float_to_small_float(g, 6) << 11)
+                | (float_to_small_float(b, 5) << 22);
+            write_u32(bytes, word, big_endian);
+        }
+        PackedFormat::Rgb9E5 => {
+            write_u32(bytes, float_to_rgb9e5(r, g, b), big_endian);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn packed_to_rgba_impl<T: ImageSample, const FORMAT: u8, const CHANNELS_CONFIGURATION: u8>(
+    src: &[u8],
+    src_stride: u32,
+    byte_order: ByteOrder,
+    dst: &mut [T],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    store: impl Fn(f32) -> T + Send + Sync,
+) {
+    let format: PackedFormat = FORMAT.into();
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+    let bytes_per_pixel = format.bytes_per_pixel();
+    let big_endian = byte_order == ByteOrder::BigEndian;
+
+    let mut _row_handler: Option<UnpackRowHandler> = None;
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        _row_handler = Some(neon_unpack_row::<FORMAT, CHANNELS_CONFIGURATION>);
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        _row_handler = Some(sse_unpack_row::<FORMAT, CHANNELS_CONFIGURATION>);
+    }
+
+    let src_rows = interleaved_rows(src, src_stride, bytes_per_pixel, width, height);
+    let dst_rows = interleaved_rows_mut(dst, dst_stride, channels, width, height);
+    let row_width = width as usize;
+
+    convert_rows(
+        src_rows,
+        bytes_per_pixel,
+        dst_rows,
+        channels,
+        width,
+        &current_threading(),
+        |src, dst| {
+            let mut normalized = vec![0f32; row_width * channels];
+            let mut _cx = 0usize;
+
+            if let Some(handler) = _row_handler {
+                unsafe {
+                    _cx = handler(
+                        _cx,
+                        src.as_ptr(),
+                        normalized.as_mut_ptr(),
+                        row_width,
+                        big_endian,
+                    );
+                }
+            }
+
+            for (bytes, pixel) in src[_cx * bytes_per_pixel..]
+                .chunks_exact(bytes_per_pixel)
+                .zip(normalized[_cx * channels..].chunks_exact_mut(channels))
+            {
+                let [r, g, b, a] = unpack_pixel(format, bytes, big_endian);
+                pixel[image_configuration.get_r_channel_offset()] = r;
+                pixel[image_configuration.get_g_channel_offset()] = g;
+                pixel[image_configuration.get_b_channel_offset()] = b;
+                if image_configuration.has_alpha() {
+                    pixel[image_configuration.get_a_channel_offset()] = a;
+                }
+            }
+
+            for (dst, &value) in dst.iter_mut().zip(normalized.iter()) {
+                *dst = store(value);
+            }
+        },
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+fn rgba_to_packed_impl<T: ImageSample, const FORMAT: u8, const CHANNELS_CONFIGURATION: u8>(
+    src: &[T],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    byte_order: ByteOrder,
+    width: u32,
+    height: u32,
+    load: impl Fn(T) -> f32 + Send + Sync,
+) {
+    let format: PackedFormat = FORMAT.into();
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+    let bytes_per_pixel = format.bytes_per_pixel();
+    let big_endian = byte_order == ByteOrder::BigEndian;
+
+    let mut _row_handler: Option<PackRowHandler> = None;
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        _row_handler = Some(neon_pack_row::<FORMAT, CHANNELS_CONFIGURATION>);
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        _row_handler = Some(sse_pack_row::<FORMAT, CHANNELS_CONFIGURATION>);
+    }
+
+    let src_rows = interleaved_rows(src, src_stride, channels, width, height);
+    let dst_rows = interleaved_rows_mut(dst, dst_stride, bytes_per_pixel, width, height);
+    let row_width = width as usize;
+
+    convert_rows(
+        src_rows,
+        channels,
+        dst_rows,
+        bytes_per_pixel,
+        width,
+        &current_threading(),
+        |src, dst| {
+            let normalized: Vec<f32> = src.iter().map(|&value| load(value)).collect();
+            let mut _cx = 0usize;
+
+            if let Some(handler) = _row_handler {
+                unsafe {
+                    _cx = handler(
+                        _cx,
+                        normalized.as_ptr(),
+                        dst.as_mut_ptr(),
+                        row_width,
+                        big_endian,
+                    );
+                }
+            }
+
+            for (pixel, bytes) in normalized[_cx * channels..]
+                .chunks_exact(channels)
+                .zip(dst[_cx * bytes_per_pixel..].chunks_exact_mut(bytes_per_pixel))
+            {
+                let a = if image_configuration.has_alpha() {
+                    pixel[image_configuration.get_a_channel_offset()]
+                } else {
+                    1f32
+                };
+                pack_pixel(
+                    format,
+                    [
+                        pixel[image_configuration.get_r_channel_offset()],
+                        pixel[image_configuration.get_g_channel_offset()],
+                        pixel[image_configuration.get_b_channel_offset()],
+                        a,
+                    ],
+                    bytes,
+                    big_endian,
+                );
+            }
+        },
+    );
+}
+
+fn check_bit_depth(bit_depth: u32) {
+    if bit_depth == 0 || bit_depth > 16 {
+        panic!("Bit depth must be in 1..=16, but {} was given", bit_depth);
+    }
+}
+
+/// This function converts packed pixels into RGBA with 8 bit samples.
+/// Floating point formats are clamped into [0, 1]
+///
+/// # Arguments
+/// * `src` - A slice contains packed pixels
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_format` - Packed format of the source, see [PackedFormat]
+/// * `byte_order` - Byte order of packed words, see [ByteOrder]
+/// * `dst` - A mutable slice to receive RGBA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_layout` - Colour layout of the destination, alpha is 255 when format has no alpha
+/// * `width` - Image width
+/// * `height` - Image height
+///
+/// # Panics
+/// Panics if destination is gray or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn packed_to_rgba8(
+    src: &[u8],
+    src_stride: u32,
+    src_format: PackedFormat,
+    byte_order: ByteOrder,
+    dst: &mut [u8],
+    dst_stride: u32,
+    dst_layout: ImageConfiguration,
+    width: u32,
+    height: u32,
+) {
+    with_packed_layouts!(
+        src_format,
+        dst_layout,
+        packed_to_rgba_impl,
+        (
+            src,
+            src_stride,
+            byte_order,
+            dst,
+            dst_stride,
+            width,
+            height,
+            |v| quantize_unorm(v, 255f32) as u8
+        )
+    );
+}
+
+/// This function converts packed pixels into RGBA with 16 bit samples.
+/// Floating point formats are clamped into [0, 1]
+///
+/// # Arguments
+/// * `src` - A slice contains packed pixels
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_format` - Packed format of the source, see [PackedFormat]
+/// * `byte_order` - Byte order of packed words, see [ByteOrder]
+/// * `dst` - A mutable slice to receive RGBA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_layout` - Colour layout of the destination, alpha is maximum value when format has no alpha
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of the destination samples, 1..=16
+///
+/// # Panics
+/// Panics if destination is gray, bit depth is invalid or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn packed_to_rgba16(
+    src: &[u8],
+    src_stride: u32,
+    src_format: PackedFormat,
+    byte_order: ByteOrder,
+    dst: &mut [u16],
+    dst_stride: u32,
+    dst_layout: ImageConfiguration,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+) {
+    check_bit_depth(bit_depth);
+    let max_colors = ((1u32 << bit_depth) - 1) as f32;
+    with_packed_layouts!(
+        src_format,
+        dst_layout,
+        packed_to_rgba_impl,
+        (
+            src,
+            src_stride,
+            byte_order,
+            dst,
+            dst_stride,
+            width,
+            height,
+            |v| quantize_unorm(v, max_colors) as u16
+        )
+    );
+}
+
+/// This function converts packed pixels into RGBA with f32 samples.
+/// Normalized formats are scaled into [0, 1], floating point formats are kept as is
+///
+/// # Arguments
+/// * `src` - A slice contains packed pixels
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_format` - Packed format of the source, see [PackedFormat]
+/// * `byte_order` - Byte order of packed words, see [ByteOrder]
+/// * `dst` - A mutable slice to receive RGBA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_layout` - Colour layout of the destination, alpha is 1 when format has no alpha
+/// * `width` - Image width
+/// * `height` - Image height
+///
+/// # Panics
+/// Panics if destination is gray or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn packed_to_rgba_f32(
+    src: &[u8],
+    src_stride: u32,
+    src_format: PackedFormat,
+    byte_order: ByteOrder,
+    dst: &mut [f32],
+    dst_stride: u32,
+    dst_layout: ImageConfiguration,
+    width: u32,
+    height: u32,
+) {
+    with_packed_layouts!(
+        src_format,
+        dst_layout,
+        packed_to_rgba_impl,
+        (
+            src,
+            src_stride,
+            byte_order,
+            dst,
+            dst_stride,
+            width,
+            height,
+            |v| v
+        )
+    );
+}
+
+/// This function converts RGBA with 8 bit samples into packed pixels.
+/// Values are rounded to nearest, shared exponent and small floats follow Vulkan rules
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_layout` - Colour layout of the source, pixels are opaque when it has no alpha
+/// * `dst` - A mutable slice to receive packed pixels
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_format` - Packed format of the destination, see [PackedFormat]
+/// * `byte_order` - Byte order of packed words, see [ByteOrder]
+/// * `width` - Image width
+/// * `height` - Image height
+///
+/// # Panics
+/// Panics if source is gray or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn rgba8_to_packed(
+    src: &[u8],
+    src_stride: u32,
+    src_layout: ImageConfiguration,
+    dst: &mut [u8],
+    dst_stride: u32,
+    dst_format: PackedFormat,
+    byte_order: ByteOrder,
+    width: u32,
+    height: u32,
+) {
+    with_packed_layouts!(
+        dst_format,
+        src_layout,
+        rgba_to_packed_impl,
+        (
+            src,
+            src_stride,
+            dst,
+            dst_stride,
+            byte_order,
+            width,
+            height,
+            |v: u8| unorm_to_float(v as u32, 255f32)
+        )
+    );
+}
+
+/// This function converts RGBA with 16 bit samples into packed pixels.
+/// Values are rounded to nearest, shared exponent and small floats follow Vulkan rules
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_layout` - Colour layout of the source, pixels are opaque when it has no alpha
+/// * `dst` - A mutable slice to receive packed pixels
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_format` - Packed format of the destination, see [PackedFormat]
+/// * `byte_order` - Byte order of packed words, see [ByteOrder]
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of the source samples, 1..=16
+///
+/// # Panics
+/// Panics if source is gray, bit depth is invalid or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn rgba16_to_packed(
+    src: &[u16],
+    src_stride: u32,
+    src_layout: ImageConfiguration,
+    dst: &mut [u8],
+    dst_stride: u32,
+    dst_format: PackedFormat,
+    byte_order: ByteOrder,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+) {
+    check_bit_depth(bit_depth);
+    let max_colors = ((1u32 << bit_depth) - 1) as f32;
+    with_packed_layouts!(
+        dst_format,
+        src_layout,
+        rgba_to_packed_impl,
+        (
+            src,
+            src_stride,
+            dst,
+            dst_stride,
+            byte_order,
+            width,
+            height,
+            |v: u16| unorm_to_float(v as u32, max_colors)
+        )
+    );
+}
+
+/// This function converts RGBA with f32 samples into packed pixels.
+/// Normalized formats clamp values into [0, 1], half floats keep sign, infinities and NaN,
+/// unsigned floats clamp negative and NaN values to zero and saturate large values
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_layout` - Colour layout of the source, pixels are opaque when it has no alpha
+/// * `dst` - A mutable slice to receive packed pixels
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_format` - Packed format of the destination, see [PackedFormat]
+/// * `byte_order` - Byte order of packed words, see [ByteOrder]
+/// * `width` - Image width
+/// * `height` - Image height
+///
+/// # Panics
+/// Panics if source is gray or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn rgba_f32_to_packed(
+    src: &[f32],
+    src_stride: u32,
+    src_layout: ImageConfiguration,
+    dst: &mut [u8],
+    dst_stride: u32,
+    dst_format: PackedFormat,
+    byte_order: ByteOrder,
+    width: u32,
+    height: u32,
+) {
+    with_packed_layouts!(
+        dst_format,
+        src_layout,
+        rgba_to_packed_impl,
+        (
+            src,
+            src_stride,
+            dst,
+            dst_stride,
+            byte_order,
+            width,
+            height,
+            |v: f32| v
+        )
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORMATS: [PackedFormat; 5] = [
+        PackedFormat::Rgb565,
+        PackedFormat::Rgba1010102,
+        PackedFormat::RgbaF16,
+        PackedFormat::R11G11B10F,
+        PackedFormat::Rgb9E5,
+    ];
+
+    /// Decodes unsigned float by its definition, exponent 31 is not a finite value
+    fn small_float_reference(code: u32, mantissa: u32) -> f64 {
+        let exponent = code >> mantissa;
+        let fraction = (code & ((1 << mantissa) - 1)) as f64 / (1u32 << mantissa) as f64;
+        if exponent == 0 {
+            fraction * 2f64.powi(-14)
+        } else {
+            (1. + fraction) * 2f64.powi(exponent as i32 - 15)
+        }
+    }
+
+    /// Reference encoder from `EXT_texture_shared_exponent` specification
+    fn rgb9e5_reference(rgb: [f32; 3]) -> u32 {
+        const MAX_RGB9E5: f64 = 511. / 512. * 65536.;
+        let clamped = rgb.map(|c| {
+            let c = c as f64;
+            if c > 0. {
+                c.min(MAX_RGB9E5)
+            } else {
+                0.
+            }
+        });
+        let max_rgb = clamped[0].max(clamped[1]).max(clamped[2]);
+        let floor_log2 = if max_rgb > 0. {
+            max_rgb.log2().floor() as i32
+        } else {
+            -16
+        };
+        let mut exp_shared = (-16).max(floor_log2) + 1 + 15;
+        let mut denom = 2f64.powi(exp_shared - 15 - 9);
+        if (max_rgb / denom + 0.5).floor() as u32 == 512 {
+            denom *= 2.;
+            exp_shared += 1;
+        }
+        let [r, g, b] = clamped.map(|c| (c / denom + 0.5).floor() as u32);
+        r | (g << 9) | (b << 18) | ((exp_shared as u32) << 27)
+    }
+
+    #[test]
+    fn small_float_codes_round_trip() {
+        for mantissa in [6, 5] {
+            let finite_codes = 31u32 << mantissa;
+            for code in 0..finite_codes {
+                let value = small_float_to_float(code, mantissa);
+                assert_eq!(value as f64, small_float_reference(code, mantissa));
+                assert_eq!(float_to_small_float(value, mantissa), code);
+            }
+            // Halfway between neighbours rounds to even code
+            for code in 0..finite_codes - 1 {
+                let low = small_float_to_float(code, mantissa);
+                let high = small_float_to_float(code + 1, mantissa);
+                let middle = (low + high) / 2.;
+                let even = if code % 2 == 0 { code } else { code + 1 };
+                assert_eq!(float_to_small_float(middle, mantissa), even);
+                assert_eq!(
+                    float_to_small_float(f32::from_bits(middle.to_bits() - 1), mantissa),
+                    code
+                );
+                assert_eq!(
+                    float_to_small_float(f32::from_bits(middle.to_bits() + 1), mantissa),
+                    code + 1
+                );
+            }
+            let max_code = finite_codes - 1;
+            assert_eq!(float_to_small_float(f32::INFINITY, mantissa), max_code);
+            assert_eq!(float_to_small_float(1e9, mantissa), max_code);
+            assert_eq!(float_to_small_float(f32::NAN, mantissa), 0);
+            assert_eq!(float_to_small_float(-1., mantissa), 0);
+            assert_eq!(float_to_small_float(-0., mantissa), 0);
+        }
+    }
+
+    #[test]
+    fn rgb9e5_matches_specification() {
+        assert_eq!(
+            float_to_rgb9e5(1., 1., 1.),
+            256 | (256 << 9) | (256 << 18) | (16 << 27)
+        );
+        assert_eq!(float_to_rgb9e5(65408., 65408., 65408.), u32::MAX);
+        assert_eq!(float_to_rgb9e5(0., 0., 0.), 0);
+
+        let mut values = vec![0., 1e-9, 2f32.powi(-24), 0.5, 1., 65408., 65536., 1e9];
+        for exponent in -24..=16 {
+            let power = 2f32.powi(exponent);
+            let bits = power.to_bits();
+            values.extend([
+                power,
+                f32::from_bits(bits - 1),
+                f32::from_bits(bits + 1),
+                power * 1.999,
+            ]);
+        }
+        // Mantissa of the largest channel rounding up to 512 bumps the exponent
+        values.extend([511.5 / 512., 511.49 / 512., 1023.5 / 1024.]);
+        values.extend((0..200).map(|i| (i as f32 * 0.37).exp2() / 4096.));
+        for &r in &values {
+            for g in [0., r * 0.5, r * 0.013, 0.75] {
+                for b in [0., 2f32.powi(-15), 3.] {
+                    assert_eq!(
+                        float_to_rgb9e5(r, g, b),
+                        rgb9e5_reference([r, g, b]),
+                        "{} {} {}",
+                        r,
+                        g,
+                        b
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn special_inputs_are_packed_as_documented() {
+        let pack = |format: PackedFormat, value: f32| {
+            let mut bytes = [0u8; 8];
+            rgba_f32_to_packed(
+                &[value, value, value, value],
+                16,
+                ImageConfiguration::Rgba,
+                &mut bytes,
+                8,
+                format,
+                ByteOrder::LittleEndian,
+                1,
+                1,
+            );
+            bytes
+        };
+        let word = |bytes: [u8; 8]| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+
+        for value in [0., -0., -1., f32::NAN] {
+            assert_eq!(word(pack(PackedFormat::Rgb565, value)), 0);
+            assert_eq!(word(pack(PackedFormat::Rgba1010102, value)), 0);
+            assert_eq!(word(pack(PackedFormat::R11G11B10F, value)), 0);
+            assert_eq!(word(pack(PackedFormat::Rgb9E5, value)), 0);
+        }
+        for value in [1., 2.] {
+            assert_eq!(word(pack(PackedFormat::Rgb565, value)), 0xffff);
+            assert_eq!(word(pack(PackedFormat::Rgba1010102, value)), u32::MAX);
+        }
+        assert_eq!(
+            word(pack(PackedFormat::R11G11B10F, 1.)),
+            (15 << 6) | (15 << 17) | (15 << 27)
+        );
+        assert_eq!(
+            word(pack(PackedFormat::R11G11B10F, f32::MAX)),
+            0x7bf | (0x7bf << 11) | (0x3df << 22)
+        );
+        assert_eq!(
+            word(pack(PackedFormat::Rgb9E5, 1.)),
+            256 | (256 << 9) | (256 << 18) | (16 << 27)
+        );
+        assert_eq!(word(pack(PackedFormat::Rgb9E5, f32::MAX)), u32::MAX);
+
+        let half = |bytes: [u8; 8]| f16::from_bits(u16::from_le_bytes([bytes[0], bytes[1]]));
+        assert_eq!(half(pack(PackedFormat::RgbaF16, -1.)), f16::from_f32(-1.));
+        assert_eq!(half(pack(PackedFormat::RgbaF16, f32::MAX)), f16::INFINITY);
+        assert!(half(pack(PackedFormat::RgbaF16, f32::NAN)).is_nan());
+    }
+
+    #[test]
+    fn rows_match_scalar_pixels() {
+        let samples: Vec<f32> = (0..37 * 4)
+            .map(|i| match i % 7 {
+                0 => -0.25,
+                1 => 1.5,
+                2 => -0.,
+                3 => 300.7,
+                _ => ((i * 131) % 257) as f32 / 256.,
+            })
+            .collect();
+        for format in FORMATS {
+            let bytes_per_pixel = format.bytes_per_pixel();
+            // Widths aren't multiples of any vector length, so SIMD and scalar tails both run
+            for width in [1usize, 3, 5, 7, 13, 17, 31, 37] {
+                for byte_order in [ByteOrder::LittleEndian, ByteOrder::BigEndian] {
+                    let big_endian = byte_order == ByteOrder::BigEndian;
+                    let src = &samples[..width * 4];
+                    let mut packed = vec![0u8; width * bytes_per_pixel];
+                    rgba_f32_to_packed(
+                        src,
+                        width as u32 * 16,
+                        ImageConfiguration::Rgba,
+                        &mut packed,
+                        (width * bytes_per_pixel) as u32,
+                        format,
+                        byte_order,
+                        width as u32,
+                        1,
+                    );
+                    let mut expected = vec![0u8; packed.len()];
+                    for (pixel, bytes) in src
+                        .chunks_exact(4)
+                        .zip(expected.chunks_exact_mut(bytes_per_pixel))
+                    {
+                        pack_pixel(
+                            format,
+                            [pixel[0], pixel[1], pixel[2], pixel[3]],
+                            bytes,
+                            big_endian,
+                        );
+                    }
+                    assert_eq!(packed, expected, "{:?} {} {:?}", format, width, byte_order);
+
+                    let mut unpacked = vec![0f32; width * 4];
+                    packed_to_rgba_f32(
+                        &packed,
+                        (width * bytes_per_pixel) as u32,
+                        format,
+                        byte_order,
+                        &mut unpacked,
+                        width as u32 * 16,
+                        ImageConfiguration::Rgba,
+                        width as u32,
+                        1,
+                    );
+                    for (bytes, pixel) in packed
+                        .chunks_exact(bytes_per_pixel)
+                        .zip(unpacked.chunks_exact(4))
+                    {
+                        let expected = unpack_pixel(format, bytes, big_endian);
+                        assert_eq!(pixel, expected, "{:?} {} {:?}", format, width, byte_order);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn big_endian_swaps_bytes_of_every_word() {
+        let src: Vec<u8> = (0..11 * 4).map(|i| ((i * 67) % 256) as u8).collect();
+        for format in FORMATS {
+            let bytes_per_pixel = format.bytes_per_pixel();
+            let word_size = if format == PackedFormat::Rgb565 || format == PackedFormat::RgbaF16 {
+                2
+            } else {
+                4
+            };
+            let mut packed = [Vec::new(), Vec::new()];
+            for (packed, byte_order) in packed
+                .iter_mut()
+                .zip([ByteOrder::LittleEndian, ByteOrder::BigEndian])
+            {
+                *packed = vec![0u8; 11 * bytes_per_pixel];
+                rgba8_to_packed(
+                    &src,
+                    11 * 4,
+                    ImageConfiguration::Rgba,
+                    packed,
+                    (11 * bytes_per_pixel) as u32,
+                    format,
+                    byte_order,
+                    11,
+                    1,
+                );
+            }
+            let swapped: Vec<u8> = packed[0]
+                .chunks_exact(word_size)
+                .flat_map(|word| word.iter().rev().copied())
+                .collect();
+            assert_eq!(packed[1], swapped, "{:?}", format);
+
+            let mut unpacked = [vec![0u8; src.len()], vec![0u8; src.len()]];
+            for ((unpacked, packed), byte_order) in unpacked
+                .iter_mut()
+                .zip(&packed)
+                .zip([ByteOrder::LittleEndian, ByteOrder::BigEndian])
+            {
+                packed_to_rgba8(
+                    packed,
+                    (11 * bytes_per_pixel) as u32,
+                    format,
+                    byte_order,
+                    unpacked,
+                    11 * 4,
+                    ImageConfiguration::Rgba,
+                    11,
+                    1,
+                );
+            }
+            assert_eq!(unpacked[0], unpacked[1], "{:?}", format);
+        }
+    }
+}
@@ -0,0 +1,435 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::gamma_curves::TransferFunction;
+use crate::image::ImageConfiguration;
+use crate::image_view::check_image;
+use crate::threading::ForEachRow;
+use crate::xyz_transform::{SRGB_TO_XYZ_D65, XYZ_TO_SRGB_D65};
+use crate::{rgba_to_lab_with_alpha, rgba_to_oklab, Lab, Oklab, Rgb};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator};
+#[cfg(feature = "rayon")]
+use rayon::prelude::{IntoParallelRefIterator, ParallelSlice, ParallelSliceMut};
+
+/// Quantization algorithm used for palette extraction
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum QuantizationMethod {
+    /// K-means with k-means++ seeding, `seed` makes results reproducible
+    KMeans { seed: u64, max_iterations: u32 },
+    /// Median cut, splits box with the largest squared error at the median of its
+    /// most varying axis until palette is filled
+    MedianCut,
+}
+
+impl Default for QuantizationMethod {
+    fn default() -> Self {
+        QuantizationMethod::KMeans {
+            seed: 0x5EED,
+            max_iterations: 16,
+        }
+    }
+}
+
+/// Colour space where pixels are clustered
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
+pub enum PaletteSpace {
+    #[default]
+    Oklab,
+    /// CIE Lab, D65
+    Lab,
+}
+
+/// Options for palette extraction
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PaletteOptions {
+    /// Maximum count of colours in palette
+    pub colors: usize,
+    pub method: QuantizationMethod,
+    pub space: PaletteSpace,
+    /// Pixels with alpha below threshold are ignored
+    pub alpha_threshold: u8,
+    /// Only every `subsample` pixel of every `subsample` row is taken, 1 takes all pixels
+    pub subsample: u32,
+}
+
+impl Default for PaletteOptions {
+    fn default() -> Self {
+        PaletteOptions {
+            colors: 8,
+            method: QuantizationMethod::default(),
+            space: PaletteSpace::default(),
+            alpha_threshold: 128,
+            subsample: 1,
+        }
+    }
+}
+
+/// Palette entry
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PaletteColor {
+    /// Colour in sRGB
+    pub rgb: Rgb<u8>,
+    /// Colour in Oklab
+    pub oklab: Oklab,
+    /// Fraction of counted pixels belonging to the colour, weights of palette sum to 1
+    pub weight: f32,
+}
+
+/// SplitMix64 generator, small and deterministic for k-means++ seeding
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    #[inline]
+    fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in [0, 1)
+    #[inline]
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1. / (1u64 << 53) as f64)
+    }
+}
+
+#[inline(always)]
+fn distance_squared(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    let d0 = a[0] - b[0];
+    let d1 = a[1] - b[1];
+    let d2 = a[2] - b[2];
+    d0 * d0 + d1 * d1 + d2 * d2
+}
+
+#[inline]
+fn nearest_center(point: &[f32; 3], centers: &[[f32; 3]]) -> usize {
+    let mut best = 0usize;
+    let mut best_distance = f32::MAX;
+    for (i, center) in centers.iter().enumerate() {
+        let distance = distance_squared(point, center);
+        if distance < best_distance {
+            best_distance = distance;
+            best = i;
+        }
+    }
+    best
+}
+
+/// Returns cluster centers and count of points in each cluster
+fn k_means(
+    points: &[[f32; 3]],
+    colors: usize,
+    seed: u64,
+    max_iterations: u32,
+) -> Vec<([f32; 3], usize)> {
+    let mut random = SplitMix64::new(seed);
+    let mut centers: Vec<[f32; 3]> = Vec::with_capacity(colors);
+    centers.push(points[(random.next_u64() % points.len() as u64) as usize]);
+
+    let mut distances: Vec<f32> = points
+        .iter()
+        .map(|point| distance_squared(point, &centers[0]))
+        .collect();
+
+    while centers.len() < colors {
+        let total: f64 = distances.iter().map(|&d| d as f64).sum();
+        if total <= 0. {
+            // Every point already coincides with some center
+            break;
+        }
+        let target = random.next_f64() * total;
+        let mut accumulated = 0f64;
+        let mut chosen = points.len() - 1;
+        for (i, &distance) in distances.iter().enumerate() {
+            accumulated += distance as f64;
+            if accumulated > target {
+                chosen = i;
+                break;
+            }
+        }
+        let center = points[chosen];
+        centers.push(center);
+        for (distance, point) in distances.iter_mut().zip(points.iter()) {
+            *distance = distance.min(distance_squared(point, &center));
+        }
+    }
+
+    let mut labels = vec![usize::MAX; points.len()];
+
+    for _ in 0..max_iterations.max(1) {
+        let changed;
+        #[cfg(feature = "rayon")]
+        {
+            changed = labels
+                .par_iter_mut()
+                .zip(points.par_iter())
+                .map(|(label, point)| {
+                    let nearest = nearest_center(point, &centers);
+                    let is_changed = *label != nearest;
+                    *label = nearest;
+                    is_changed as usize
+                })
+                .sum::<usize>();
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            changed = labels
+                .iter_mut()
+                .zip(points.iter())
+                .map(|(label, point)| {
+                    let nearest = nearest_center(point, &centers);
+                    let is_changed = *label != nearest;
+                    *label = nearest;
+                    is_changed as usize
+                })
+                .sum::<usize>();
+        }
+
+        let mut sums = vec![[0f64; 3]; centers.len()];
+        let mut counts = vec![0usize; centers.len()];
+        for (&label, point) in labels.iter().zip(points.iter()) {
+            sums[label][0] += point[0] as f64;
+            sums[label][1] += point[1] as f64;
+            sums[label][2] += point[2] as f64;
+            counts[label] += 1;
+        }
+        for ((center, sum), &count) in centers.iter_mut().zip(sums.iter()).zip(counts.iter()) {
+            if count != 0 {
+                let recip = 1. / count as f64;
+                *center = [
+                    (sum[0] * recip) as f32,
+                    (sum[1] * recip) as f32,
+                    (sum[2] * recip) as f32,
+                ];
+            }
+        }
+
+        if changed == 0 {
+            break;
+        }
+    }
+
+    let mut counts = vec![0usize; centers.len()];
+    for &label in labels.iter() {
+        counts[label] += 1;
+    }
+    centers.into_iter().zip(counts).collect()
+}
+
+/// Returns box centers and count of points in each box
+fn median_cut(points: &mut [[f32; 3]], colors: usize) -> Vec<([f32; 3], usize)> {
+    /// Returns axis with the largest variance and total squared error of box
+    #[inline]
+    fn box_statistics(points: &[[f32; 3]]) -> (usize, f64) {
+        let mut sum = [0f64; 3];
+        let mut sum_squared = [0f64; 3];
+        for point in points.iter() {
+            for c in 0..3 {
+                sum[c] += point[c] as f64;
+                sum_squared[c] += point[c] as f64 * point[c] as f64;
+            }
+        }
+        let count = points.len() as f64;
+        let mut errors = [0f64; 3];
+        for c in 0..3 {
+            errors[c] = (sum_squared[c] - sum[c] * sum[c] / count).max(0.);
+        }
+        let mut axis = 0usize;
+        for c in 1..3 {
+            if errors[c] > errors[axis] {
+                axis = c;
+            }
+        }
+        (axis, errors[0] + errors[1] + errors[2])
+    }
+
+    let mut boxes: Vec<(usize, usize)> = vec![(0, points.len())];
+
+    while boxes.len() < colors {
+        let mut candidate: Option<(usize, usize, f64)> = None;
+        for (i, &(start, end)) in boxes.iter().enumerate() {
+            if end - start < 2 {
+                continue;
+            }
+            let (axis, error) = box_statistics(&points[start..end]);
+            if error > 0. && candidate.map(|c| error > c.2).unwrap_or(true) {
+                candidate = Some((i, axis, error));
+            }
+        }
+        let Some((index, axis, _)) = candidate else {
+            break;
+        };
+        let (start, end) = boxes[index];
+        let slice = &mut points[start..end];
+        slice.sort_unstable_by(|a, b| a[axis].total_cmp(&b[axis]));
+        let median = start + slice.len() / 2;
+        boxes[index] = (start, median);
+        boxes.push((median, end));
+    }
+
+    boxes
+        .into_iter()
+        .map(|(start, end)| {
+            let mut sum = [0f64; 3];
+            for point in points[start..end].iter() {
+                sum[0] += point[0] as f64;
+                sum[1] += point[1] as f64;
+                sum[2] += point[2] as f64;
+            }
+            let recip = 1. / (end - start) as f64;
+            (
+                [
+                    (sum[0] * recip) as f32,
+                    (sum[1] * recip) as f32,
+                    (sum[2] * recip) as f32,
+                ],
+                end - start,
+            )
+        })
+        .collect()
+}
+
+/// This function extracts dominant colours of RGBA image.
+///
+/// Pixels are converted into Oklab or CIE Lab and clustered with k-means++ or median cut.
+/// Returned colours are sorted by weight in descending order, empty clusters are dropped,
+/// so palette may contain less colours than requested.
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `options` - See [PaletteOptions]
+pub fn rgba_extract_palette(
+    src: &[u8],
+    src_stride: u32,
+    width: u32,
+    height: u32,
+    options: PaletteOptions,
+) -> Vec<PaletteColor> {
+    if options.colors == 0 {
+        return vec![];
+    }
+    let step = options.subsample.max(1) as usize;
+    let src_stride = check_image::<u8>(
+        src.len(),
+        src_stride,
+        width,
+        height,
+        ImageConfiguration::Rgba,
+        "Source",
+    );
+    if width == 0 || height == 0 {
+        return vec![];
+    }
+
+    let row_samples = (width as usize).div_ceil(step);
+    let mut candidates = vec![0u8; (height as usize).div_ceil(step) * row_samples * 4];
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = candidates
+            .par_chunks_exact_mut(row_samples * 4)
+            .zip(src.par_chunks(src_stride).step_by(step));
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = candidates
+            .chunks_exact_mut(row_samples * 4)
+            .zip(src.chunks(src_stride).step_by(step));
+    }
+
+    iter.for_each_row(row_samples, |(candidates_row, src_row)| {
+        for (dst, pixel) in candidates_row
+            .chunks_exact_mut(4)
+            .zip(src_row[..width as usize * 4].chunks_exact(4).step_by(step))
+        {
+            dst.copy_from_slice(pixel);
+        }
+    });
+
+    let samples: Vec<u8> = candidates
+        .chunks_exact(4)
+        .filter(|pixel| pixel[3] >= options.alpha_threshold)
+        .flatten()
+        .copied()
+        .collect();
+
+    let samples_count = samples.len() / 4;
+    if samples_count == 0 {
+        return vec![];
+    }
+
+    let mut converted = vec![0f32; samples_count * 4];
+    let converted_stride = samples_count as u32 * 4 * std::mem::size_of::<f32>() as u32;
+    match options.space {
+        PaletteSpace::Oklab => rgba_to_oklab(
+            &samples,
+            samples_count as u32 * 4,
+            &mut converted,
+            converted_stride,
+            samples_count as u32,
+            1,
+            TransferFunction::Srgb,
+        ),
+        PaletteSpace::Lab => rgba_to_lab_with_alpha(
+            &samples,
+            samples_count as u32 * 4,
+            &mut converted,
+            converted_stride,
+            samples_count as u32,
+            1,
+            &SRGB_TO_XYZ_D65,
+            TransferFunction::Srgb,
+        ),
+    }
+
+    let mut points: Vec<[f32; 3]> = converted
+        .chunks_exact(4)
+        .map(|chunk| [chunk[0], chunk[1], chunk[2]])
+        .collect();
+
+    let clusters = match options.method {
+        QuantizationMethod::KMeans {
+            seed,
+            max_iterations,
+        } => k_means(&points, options.colors, seed, max_iterations),
+        QuantizationMethod::MedianCut => median_cut(&mut points, options.colors),
+    };
+
+    let recip_total = 1. / samples_count as f32;
+    let mut palette: Vec<PaletteColor> = clusters
+        .into_iter()
+        .filter(|&(_, count)| count != 0)
+        .map(|(center, count)| {
+            let oklab = match options.space {
+                PaletteSpace::Oklab => Oklab::new(center[0], center[1], center[2]),
+                PaletteSpace::Lab => Oklab::from_linear_rgb(
+                    Lab::new(center[0], center[1], center[2]).to_linear_rgb(&XYZ_TO_SRGB_D65),
+                ),
+            };
+            PaletteColor {
+                rgb: oklab.to_srgb(),
+                oklab,
+                weight: count as f32 * recip_total,
+            }
+        })
+        .collect();
+    palette.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+    palette
+}
@@ -0,0 +1,766 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::euclidean::EuclideanDistance;
+use crate::gamma_curves::TransferFunction;
+use crate::image::ImageConfiguration;
+use crate::threading::ForEachRow;
+use crate::xyz_transform::SRGB_TO_XYZ_D65;
+use crate::{Lab, Oklab, Rgb, Xyz};
+#[cfg(feature = "rayon")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "rayon")]
+use rayon::prelude::{ParallelSlice, ParallelSliceMut};
+
+/// Distance used to find the nearest palette colour
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum PaletteDistance {
+    /// Euclidean distance in Oklab
+    #[default]
+    Oklab,
+    /// CIE ΔE*76, Euclidean distance in CIE Lab, D65
+    Lab,
+    /// Euclidean distance of gamma encoded RGB where every channel is scaled by its weight
+    WeightedRgb { r: f32, g: f32, b: f32 },
+}
+
+/// Dithering applied while image is mapped to palette
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
+pub enum Dithering {
+    /// Every pixel is replaced with the nearest colour
+    #[default]
+    None,
+    FloydSteinberg,
+    /// Atkinson diffusion, only 3/4 of error is propagated
+    Atkinson,
+    JarvisJudiceNinke,
+    /// Three rows Sierra filter
+    Sierra,
+    /// Ordered dithering with 2x2 Bayer matrix
+    Bayer2,
+    /// Ordered dithering with 4x4 Bayer matrix
+    Bayer4,
+    /// Ordered dithering with 8x8 Bayer matrix
+    Bayer8,
+}
+
+impl Dithering {
+    /// Error diffusion kernel as (dx, dy, weight) and sum of weights
+    #[allow(clippy::type_complexity)]
+    fn diffusion_kernel(&self) -> Option<(&'static [(i32, usize, f32)], f32)> {
+        match self {
+            Dithering::FloydSteinberg => {
+                Some((&[(1, 0, 7.), (-1, 1, 3.), (0, 1, 5.), (1, 1, 1.)], 16.))
+            }
+            Dithering::Atkinson => Some((
+                &[
+                    (1, 0, 1.),
+                    (2, 0, 1.),
+                    (-1, 1, 1.),
+                    (0, 1, 1.),
+                    (1, 1, 1.),
+                    (0, 2, 1.),
+                ],
+                8.,
+            )),
+            Dithering::JarvisJudiceNinke => Some((
+                &[
+                    (1, 0, 7.),
+                    (2, 0, 5.),
+                    (-2, 1, 3.),
+                    (-1, 1, 5.),
+                    (0, 1, 7.),
+                    (1, 1, 5.),
+                    (2, 1, 3.),
+                    (-2, 2, 1.),
+                    (-1, 2, 3.),
+                    (0, 2, 5.),
+                    (1, 2, 3.),
+                    (2, 2, 1.),
+                ],
+                48.,
+            )),
+            Dithering::Sierra => Some((
+                &[
+                    (1, 0, 5.),
+                    (2, 0, 3.),
+                    (-2, 1, 2.),
+                    (-1, 1, 4.),
+                    (0, 1, 5.),
+                    (1, 1, 4.),
+                    (2, 1, 2.),
+                    (-1, 2, 2.),
+                    (0, 2, 3.),
+                    (1, 2, 2.),
+                ],
+                32.,
+            )),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn bayer_size(&self) -> Option<usize> {
+        match self {
+            Dithering::Bayer2 => Some(2),
+            Dithering::Bayer4 => Some(4),
+            Dithering::Bayer8 => Some(8),
+            _ => None,
+        }
+    }
+}
+
+/// Returns Bayer threshold matrix normalized into (-0.5, 0.5), `size` must be power of two
+fn bayer_matrix(size: usize) -> Vec<f32> {
+    let order = size.trailing_zeros();
+    let scale = 1. / (size * size) as f32;
+    let mut matrix = vec![0f32; size * size];
+    for y in 0..size {
+        for x in 0..size {
+            let mut value = 0usize;
+            for bit in 0..order {
+                let x_bit = (x >> bit) & 1;
+                let y_bit = (y >> bit) & 1;
+                value |= (((x_bit ^ y_bit) << 1) | y_bit) << (2 * (order - 1 - bit));
+            }
+            matrix[y * size + x] = (value as f32 + 0.5) * scale - 0.5;
+        }
+    }
+    matrix
+}
+
+struct KdNode {
+    point: [f32; 3],
+    index: usize,
+    axis: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// Finds nearest colours of fixed palette.
+///
+/// Palette colours are stored in k-d tree, so lookup is logarithmic in palette size
+pub struct PaletteMapper {
+    palette: Vec<Rgb<u8>>,
+    distance: PaletteDistance,
+    transfer_function: TransferFunction,
+    nodes: Vec<KdNode>,
+    root: Option<usize>,
+    palette_gamma: Vec<[f32; 3]>,
+    palette_linear: Vec<[f32; 3]>,
+    transparent: Option<(u8, u8)>,
+}
+
+impl PaletteMapper {
+    /// Creates mapper for palette.
+    ///
+    /// # Arguments
+    /// * `palette` - Palette colours, up to 256 entries
+    /// * `distance` - Distance used to find the nearest colour
+    /// * `transfer_function` - Transfer function of images and palette
+    pub fn new(
+        palette: &[Rgb<u8>],
+        distance: PaletteDistance,
+        transfer_function: TransferFunction,
+    ) -> PaletteMapper {
+        if palette.is_empty() || palette.len() > 256 {
+            panic!(
+                "Palette must contain from 1 to 256 colours, but it has {}",
+                palette.len()
+            );
+        }
+        let palette_gamma: Vec<[f32; 3]> = palette
+            .iter()
+            .map(|rgb| {
+                [
+                    rgb.r as f32 * (1. / 255.),
+                    rgb.g as f32 * (1. / 255.),
+                    rgb.b as f32 * (1. / 255.),
+                ]
+            })
+            .collect();
+        let palette_linear: Vec<[f32; 3]> = palette_gamma
+            .iter()
+            .map(|v| {
+                [
+                    transfer_function.linearize(v[0]),
+                    transfer_function.linearize(v[1]),
+                    transfer_function.linearize(v[2]),
+                ]
+            })
+            .collect();
+        let mut mapper = PaletteMapper {
+            palette: palette.to_vec(),
+            distance,
+            transfer_function,
+            nodes: Vec::with_capacity(palette.len()),
+            root: None,
+            palette_gamma,
+            palette_linear,
+            transparent: None,
+        };
+        let mut points: Vec<([f32; 3], usize)> = (0..palette.len())
+            .map(|i| {
+                (
+                    mapper.coordinates(mapper.palette_gamma[i], mapper.palette_linear[i]),
+                    i,
+                )
+            })
+            .collect();
+        mapper.root = mapper.build(&mut points, 0);
+        mapper
+    }
+
+    /// Pixels with alpha below `alpha_threshold` are mapped to `index`, error isn't diffused from them
+    pub fn with_transparent_index(mut self, index: u8, alpha_threshold: u8) -> PaletteMapper {
+        if index as usize >= self.palette.len() {
+            panic!("Transparent index {} is out of palette", index);
+        }
+        self.transparent = Some((index, alpha_threshold));
+        self
+    }
+
+    /// Palette colours
+    pub fn palette(&self) -> &[Rgb<u8>] {
+        &self.palette
+    }
+
+    /// Returns index of the nearest palette colour
+    pub fn nearest(&self, rgb: Rgb<u8>) -> usize {
+        let gamma = [
+            rgb.r as f32 * (1. / 255.),
+            rgb.g as f32 * (1. / 255.),
+            rgb.b as f32 * (1. / 255.),
+        ];
+        let linear = [
+            self.transfer_function.linearize(gamma[0]),
+            self.transfer_function.linearize(gamma[1]),
+            self.transfer_function.linearize(gamma[2]),
+        ];
+        self.nearest_coordinates(self.coordinates(gamma, linear))
+    }
+
+    fn build(&mut self, points: &mut [([f32; 3], usize)], depth: usize) -> Option<usize> {
+        if points.is_empty() {
+            return None;
+        }
+        let axis = depth % 3;
+        points.sort_unstable_by(|a, b| a.0[axis].total_cmp(&b.0[axis]));
+        let median = points.len() / 2;
+        let (point, index) = points[median];
+        let node = self.nodes.len();
+        self.nodes.push(KdNode {
+            point,
+            index,
+            axis,
+            left: None,
+            right: None,
+        });
+        let (left_points, rest) = points.split_at_mut(median);
+        let left = self.build(left_points, depth + 1);
+        let right = self.build(&mut rest[1..], depth + 1);
+        self.nodes[node].left = left;
+        self.nodes[node].right = right;
+        Some(node)
+    }
+
+    /// Point where distance is Euclidean
+    #[inline]
+    fn coordinates(&self, gamma: [f32; 3], linear: [f32; 3]) -> [f32; 3] {
+        match self.distance {
+            PaletteDistance::Oklab => {
+                let oklab =
+                    Oklab::from_linear_rgb(Rgb::<f32>::new(linear[0], linear[1], linear[2]));
+                [oklab.l, oklab.a, oklab.b]
+            }
+            PaletteDistance::Lab => {
+                let xyz = Xyz::from_linear_rgb(
+                    Rgb::<f32>::new(linear[0], linear[1], linear[2]),
+                    &SRGB_TO_XYZ_D65,
+                );
+                let lab = Lab::from_xyz(xyz);
+                [lab.l, lab.a, lab.b]
+            }
+            PaletteDistance::WeightedRgb { r, g, b } => [
+                gamma[0] * 255. * r.sqrt(),
+                gamma[1] * 255. * g.sqrt(),
+                gamma[2] * 255. * b.sqrt(),
+            ],
+        }
+    }
+
+    #[inline]
+    fn metric(&self, a: [f32; 3], b: [f32; 3]) -> f32 {
+        match self.distance {
+            PaletteDistance::Oklab => {
+                Oklab::new(a[0], a[1], a[2]).euclidean_distance(Oklab::new(b[0], b[1], b[2]))
+            }
+            PaletteDistance::Lab => {
+                Lab::new(a[0], a[1], a[2]).euclidean_distance(Lab::new(b[0], b[1], b[2]))
+            }
+            PaletteDistance::WeightedRgb { .. } => Rgb::<f32>::new(a[0], a[1], a[2])
+                .euclidean_distance(Rgb::<f32>::new(b[0], b[1], b[2])),
+        }
+    }
+
+    fn nearest_coordinates(&self, point: [f32; 3]) -> usize {
+        let mut best = (usize::MAX, f32::MAX);
+        let mut stack: Vec<usize> = Vec::with_capacity(32);
+        if let Some(root) = self.root {
+            stack.push(root);
+        }
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+            let distance = self.metric(point, node.point);
+            if distance < best.1 || (distance == best.1 && node.index < best.0) {
+                best = (node.index, distance);
+            }
+            let delta = point[node.axis] - node.point[node.axis];
+            let (near, far) = if delta < 0. {
+                (node.left, node.right)
+            } else {
+                (node.right, node.left)
+            };
+            if let Some(far) = far {
+                if delta.abs() <= best.1 {
+                    stack.push(far);
+                }
+            }
+            if let Some(near) = near {
+                stack.push(near);
+            }
+        }
+        best.0
+    }
+
+    /// Finds nearest colour of working value, that is gamma encoded or linear
+    #[inline]
+    fn nearest_working(&self, value: [f32; 3], linear_light: bool) -> usize {
+        let (gamma, linear) = if linear_light {
+            (
+                [
+                    self.transfer_function.gamma(value[0]),
+                    self.transfer_function.gamma(value[1]),
+                    self.transfer_function.gamma(value[2]),
+                ],
+                value,
+            )
+        } else {
+            (
+                value,
+                [
+                    self.transfer_function.linearize(value[0]),
+                    self.transfer_function.linearize(value[1]),
+                    self.transfer_function.linearize(value[2]),
+                ],
+            )
+        };
+        self.nearest_coordinates(self.coordinates(gamma, linear))
+    }
+
+    #[inline]
+    fn working_palette(&self, linear_light: bool) -> &[[f32; 3]] {
+        if linear_light {
+            &self.palette_linear
+        } else {
+            &self.palette_gamma
+        }
+    }
+
+    #[inline]
+    fn transparent_index(&self, alpha: u8) -> Option<u8> {
+        match self.transparent {
+            Some((index, threshold)) if alpha < threshold => Some(index),
+            _ => None,
+        }
+    }
+}
+
+/// Maps image into palette indices, `indices` is tightly packed
+fn map_indices<const CHANNELS_CONFIGURATION: u8>(
+    src: &[u8],
+    src_stride: u32,
+    indices: &mut [u8],
+    width: u32,
+    height: u32,
+    mapper: &PaletteMapper,
+    dithering: Dithering,
+    linear_light: bool,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+    let width = width as usize;
+
+    let mut lut_table = [0f32; 256];
+    for (i, lut) in lut_table.iter_mut().enumerate() {
+        let value = i as f32 * (1. / 255.);
+        *lut = if linear_light {
+            mapper.transfer_function.linearize(value)
+        } else {
+            value
+        };
+    }
+
+    #[inline(always)]
+    fn load(
+        pixel: &[u8],
+        image_configuration: ImageConfiguration,
+        lut_table: &[f32; 256],
+    ) -> ([f32; 3], u8) {
+        let alpha = if image_configuration.has_alpha() {
+            pixel[image_configuration.get_a_channel_offset()]
+        } else {
+            255
+        };
+        (
+            [
+                lut_table[pixel[image_configuration.get_r_channel_offset()] as usize],
+                lut_table[pixel[image_configuration.get_g_channel_offset()] as usize],
+                lut_table[pixel[image_configuration.get_b_channel_offset()] as usize],
+            ],
+            alpha,
+        )
+    }
+
+    if let Some((kernel, divisor)) = dithering.diffusion_kernel() {
+        // Rows of error are rotated, padding of 2 keeps kernels inside the buffer
+        let padded_width = width + 4;
+        let mut errors = vec![vec![[0f32; 3]; padded_width]; 3];
+        let working_palette = mapper.working_palette(linear_light);
+        let recip_divisor = 1. / divisor;
+
+        for (src_row, indices_row) in src
+            .chunks(src_stride as usize)
+            .zip(indices.chunks_exact_mut(width))
+            .take(height as usize)
+        {
+            for (x, (pixel, index)) in src_row
+                .chunks_exact(channels)
+                .zip(indices_row.iter_mut())
+                .enumerate()
+            {
+                let (value, alpha) = load(pixel, image_configuration, &lut_table);
+                if let Some(transparent) = mapper.transparent_index(alpha) {
+                    *index = transparent;
+                    continue;
+                }
+                let error = errors[0][x + 2];
+                let adjusted = [
+                    (value[0] + error[0]).max(0.).min(1.),
+                    (value[1] + error[1]).max(0.).min(1.),
+                    (value[2] + error[2]).max(0.).min(1.),
+                ];
+                let nearest = mapper.nearest_working(adjusted, linear_light);
+                *index = nearest as u8;
+                let chosen = working_palette[nearest];
+                let quantization_error = [
+                    (adjusted[0] - chosen[0]) * recip_divisor,
+                    (adjusted[1] - chosen[1]) * recip_divisor,
+                    (adjusted[2] - chosen[2]) * recip_divisor,
+                ];
+                for &(dx, dy, weight) in kernel.iter() {
+                    let target = &mut errors[dy][(x as i32 + 2 + dx) as usize];
+                    target[0] += quantization_error[0] * weight;
+                    target[1] += quantization_error[1] * weight;
+                    target[2] += quantization_error[2] * weight;
+                }
+            }
+            errors.rotate_left(1);
+            errors[2].fill([0f32; 3]);
+        }
+        return;
+    }
+
+    let bayer = dithering.bayer_size().map(|size| {
+        // Threshold covers spacing of palette levels per channel, 2 levels for 8 colours etc.
+        let spread = 1. / ((mapper.palette.len() as f32).cbrt() - 1.).max(1.);
+        let matrix: Vec<f32> = bayer_matrix(size).iter().map(|&v| v * spread).collect();
+        (size, matrix)
+    });
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = indices
+            .par_chunks_exact_mut(width)
+            .zip(src.par_chunks(src_stride as usize))
+            .take(height as usize)
+            .enumerate();
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = indices
+            .chunks_exact_mut(width)
+            .zip(src.chunks(src_stride as usize))
+            .take(height as usize)
+            .enumerate();
+    }
+
+    iter.for_each_row(width, |(y, (indices_row, src_row))| {
+        for (x, (pixel, index)) in src_row
+            .chunks_exact(channels)
+            .zip(indices_row.iter_mut())
+            .enumerate()
+        {
+            let (value, alpha) = load(pixel, image_configuration, &lut_table);
+            if let Some(transparent) = mapper.transparent_index(alpha) {
+                *index = transparent;
+                continue;
+            }
+            let nearest = match &bayer {
+                Some((size, matrix)) => {
+                    let threshold = matrix[(y % size) * size + (x % size)];
+                    mapper.nearest_working(
+                        [
+                            (value[0] + threshold).max(0.).min(1.),
+                            (value[1] + threshold).max(0.).min(1.),
+                            (value[2] + threshold).max(0.).min(1.),
+                        ],
+                        linear_light,
+                    )
+                }
+                None => mapper.nearest_working(value, linear_light),
+            };
+            *index = nearest as u8;
+        }
+    });
+}
+
+fn image_to_palette_indices<const CHANNELS_CONFIGURATION: u8>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    mapper: &PaletteMapper,
+    dithering: Dithering,
+    linear_light: bool,
+) {
+    let mut indices = vec![0u8; width as usize * height as usize];
+    map_indices::<CHANNELS_CONFIGURATION>(
+        src,
+        src_stride,
+        &mut indices,
+        width,
+        height,
+        mapper,
+        dithering,
+        linear_light,
+    );
+    for (dst_row, indices_row) in dst
+        .chunks_mut(dst_stride as usize)
+        .zip(indices.chunks_exact(width as usize))
+        .take(height as usize)
+    {
+        dst_row[..width as usize].copy_from_slice(indices_row);
+    }
+}
+
+fn image_to_palette_colors<const CHANNELS_CONFIGURATION: u8>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    mapper: &PaletteMapper,
+    dithering: Dithering,
+    linear_light: bool,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+    let mut indices = vec![0u8; width as usize * height as usize];
+    map_indices::<CHANNELS_CONFIGURATION>(
+        src,
+        src_stride,
+        &mut indices,
+        width,
+        height,
+        mapper,
+        dithering,
+        linear_light,
+    );
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = dst
+            .par_chunks_mut(dst_stride as usize)
+            .zip(src.par_chunks(src_stride as usize))
+            .zip(indices.par_chunks_exact(width as usize))
+            .take(height as usize);
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = dst
+            .chunks_mut(dst_stride as usize)
+            .zip(src.chunks(src_stride as usize))
+            .zip(indices.chunks_exact(width as usize))
+            .take(height as usize);
+    }
+
+    iter.for_each_row(width as usize, |((dst_row, src_row), indices_row)| {
+        for ((dst, src), &index) in dst_row
+            .chunks_exact_mut(channels)
+            .zip(src_row.chunks_exact(channels))
+            .zip(indices_row.iter())
+        {
+            let color = mapper.palette[index as usize];
+            dst[image_configuration.get_r_channel_offset()] = color.r;
+            dst[image_configuration.get_g_channel_offset()] = color.g;
+            dst[image_configuration.get_b_channel_offset()] = color.b;
+            if image_configuration.has_alpha() {
+                dst[image_configuration.get_a_channel_offset()] =
+                    src[image_configuration.get_a_channel_offset()];
+            }
+        }
+    });
+}
+
+/// This function maps RGBA image to palette and stores palette index of every pixel
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive palette indices, one byte per pixel
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `mapper` - Palette mapper, see [PaletteMapper]
+/// * `dithering` - Dithering method, see [Dithering]
+/// * `linear_light` - if set error is diffused and thresholds are applied in linear light
+pub fn rgba_to_palette_indices(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    mapper: &PaletteMapper,
+    dithering: Dithering,
+    linear_light: bool,
+) {
+    image_to_palette_indices::<{ ImageConfiguration::Rgba as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        mapper,
+        dithering,
+        linear_light,
+    );
+}
+
+/// This function maps BGRA image to palette and stores palette index of every pixel
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive palette indices, one byte per pixel
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `mapper` - Palette mapper, see [PaletteMapper]
+/// * `dithering` - Dithering method, see [Dithering]
+/// * `linear_light` - if set error is diffused and thresholds are applied in linear light
+pub fn bgra_to_palette_indices(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    mapper: &PaletteMapper,
+    dithering: Dithering,
+    linear_light: bool,
+) {
+    image_to_palette_indices::<{ ImageConfiguration::Bgra as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        mapper,
+        dithering,
+        linear_light,
+    );
+}
+
+/// This function replaces RGBA pixels with palette colours, alpha is preserved
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGBA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `mapper` - Palette mapper, see [PaletteMapper]
+/// * `dithering` - Dithering method, see [Dithering]
+/// * `linear_light` - if set error is diffused and thresholds are applied in linear light
+pub fn rgba_apply_palette(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    mapper: &PaletteMapper,
+    dithering: Dithering,
+    linear_light: bool,
+) {
+    image_to_palette_colors::<{ ImageConfiguration::Rgba as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        mapper,
+        dithering,
+        linear_light,
+    );
+}
+
+/// This function replaces BGRA pixels with palette colours, alpha is preserved
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGRA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `mapper` - Palette mapper, see [PaletteMapper]
+/// * `dithering` - Dithering method, see [Dithering]
+/// * `linear_light` - if set error is diffused and thresholds are applied in linear light
+pub fn bgra_apply_palette(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    mapper: &PaletteMapper,
+    dithering: Dithering,
+    linear_light: bool,
+) {
+    image_to_palette_colors::<{ ImageConfiguration::Bgra as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        mapper,
+        dithering,
+        linear_light,
+    );
+}
@@ -0,0 +1,501 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::image::ImageConfiguration;
+use crate::image_view::samples_stride;
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use crate::neon::{
+    neon_deinterleave_planes_f32, neon_deinterleave_planes_u8, neon_interleave_planes_f32,
+    neon_interleave_planes_u8,
+};
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+use crate::sse::{
+    sse_deinterleave_planes_f32, sse_deinterleave_planes_u8, sse_interleave_planes_f32,
+    sse_interleave_planes_u8,
+};
+use crate::threading::{current_threading, ForEachRow, ThreadingPolicy};
+use crate::ImageSample;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator};
+
+/// Row of source image, planar rows hold one slice per channel
+pub(crate) enum SourceRow<'a, T> {
+    Interleaved(&'a [T]),
+    Planar(Vec<&'a [T]>),
+}
+
+/// Row of destination image, planar rows hold one slice per channel
+pub(crate) enum TargetRow<'a, T> {
+    Interleaved(&'a mut [T]),
+    Planar(Vec<&'a mut [T]>),
+}
+
+fn check_plane(len: usize, stride: usize, row_length: usize, height: u32, what: &str) {
+    if stride < row_length {
+        panic!(
+            "{} stride {} samples is smaller than row of {} samples",
+            what, stride, row_length
+        );
+    }
+    let required = stride * (height as usize - 1) + row_length;
+    if len < required {
+        panic!(
+            "{} buffer of {} samples is smaller than required {} samples",
+            what, len, required
+        );
+    }
+}
+
+/// Splits interleaved image into `height` rows of `width` pixels
+pub(crate) fn interleaved_rows<T>(
+    data: &[T],
+    stride: u32,
+    channels: usize,
+    width: u32,
+    height: u32,
+) -> Vec<SourceRow<'_, T>> {
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+    let stride = samples_stride::<T>(stride);
+    let row_length = width as usize * channels;
+    check_plane(data.len(), stride, row_length, height, "Image");
+    data.chunks(stride)
+        .take(height as usize)
+        .map(|row| SourceRow::Interleaved(&row[..row_length]))
+        .collect()
+}
+
+/// Splits interleaved mutable image into `height` rows of `width` pixels
+pub(crate) fn interleaved_rows_mut<T>(
+    data: &mut [T],
+    stride: u32,
+    channels: usize,
+    width: u32,
+    height: u32,
+) -> Vec<TargetRow<'_, T>> {
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+    let stride = samples_stride::<T>(stride);
+    let row_length = width as usize * channels;
+    check_plane(data.len(), stride, row_length, height, "Image");
+    data.chunks_mut(stride)
+        .take(height as usize)
+        .map(|row| TargetRow::Interleaved(&mut row[..row_length]))
+        .collect()
+}
+
+fn check_planes_count(planes: usize, strides: usize, channels: usize) {
+    if planes != channels || strides != channels {
+        panic!(
+            "Expected {} planes and strides, but {} planes and {} strides were given",
+            channels, planes, strides
+        );
+    }
+}
+
+/// Splits planes into `height` rows, every row holds `width` samples of each plane
+pub(crate) fn planar_rows<'a, T>(
+    planes: &[&'a [T]],
+    strides: &[u32],
+    channels: usize,
+    width: u32,
+    height: u32,
+) -> Vec<SourceRow<'a, T>> {
+    check_planes_count(planes.len(), strides.len(), channels);
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+    let width = width as usize;
+    let mut plane_rows = planes
+        .iter()
+        .zip(strides.iter())
+        .map(|(&plane, &stride)| {
+            let stride = samples_stride::<T>(stride);
+            check_plane(plane.len(), stride, width, height, "Plane");
+            plane.chunks(stride)
+        })
+        .collect::<Vec<_>>();
+    (0..height)
+        .map(|_| {
+            SourceRow::Planar(
+                plane_rows
+                    .iter_mut()
+                    .map(|rows| &rows.next().unwrap()[..width])
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+/// Splits mutable planes into `height` rows, every row holds `width` samples of each plane
+pub(crate) fn planar_rows_mut<'a, T>(
+    planes: &'a mut [&mut [T]],
+    strides: &[u32],
+    channels: usize,
+    width: u32,
+    height: u32,
+) -> Vec<TargetRow<'a, T>> {
+    check_planes_count(planes.len(), strides.len(), channels);
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+    let width = width as usize;
+    let mut plane_rows = planes
+        .iter_mut()
+        .zip(strides.iter())
+        .map(|(plane, &stride)| {
+            let stride = samples_stride::<T>(stride);
+            check_plane(plane.len(), stride, width, height, "Plane");
+            plane.chunks_mut(stride)
+        })
+        .collect::<Vec<_>>();
+    (0..height)
+        .map(|_| {
+            TargetRow::Planar(
+                plane_rows
+                    .iter_mut()
+                    .map(|rows| &mut rows.next().unwrap()[..width])
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+#[inline]
+unsafe fn interleave_u8<const CHANNELS: usize>(
+    planes: &[*const u8; 4],
+    dst: *mut u8,
+    width: usize,
+) -> usize {
+    let mut _cx = 0usize;
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        _cx = neon_interleave_planes_u8::<CHANNELS>(_cx, planes, dst, width);
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        _cx = sse_interleave_planes_u8::<CHANNELS>(_cx, planes, dst, width);
+    }
+
+    _cx
+}
+
+#[inline]
+unsafe fn interleave_f32<const CHANNELS: usize>(
+    planes: &[*const f32; 4],
+    dst: *mut f32,
+    width: usize,
+) -> usize {
+    let mut _cx = 0usize;
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        _cx = neon_interleave_planes_f32::<CHANNELS>(_cx, planes, dst, width);
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        _cx = sse_interleave_planes_f32::<CHANNELS>(_cx, planes, dst, width);
+    }
+
+    _cx
+}
+
+#[inline]
+unsafe fn deinterleave_u8<const CHANNELS: usize>(
+    src: *const u8,
+    planes: &[*mut u8; 4],
+    width: usize,
+) -> usize {
+    let mut _cx = 0usize;
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        _cx = neon_deinterleave_planes_u8::<CHANNELS>(_cx, src, planes, width);
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        _cx = sse_deinterleave_planes_u8::<CHANNELS>(_cx, src, planes, width);
+    }
+
+    _cx
+}
+
+#[inline]
+unsafe fn deinterleave_f32<const CHANNELS: usize>(
+    src: *const f32,
+    planes: &[*mut f32; 4],
+    width: usize,
+) -> usize {
+    let mut _cx = 0usize;
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        _cx = neon_deinterleave_planes_f32::<CHANNELS>(_cx, src, planes, width);
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        _cx = sse_deinterleave_planes_f32::<CHANNELS>(_cx, src, planes, width);
+    }
+
+    _cx
+}
+
+/// Interleaves one row of planes into `dst`.
+/// Samples are only moved, so 8 bit and 32 bit samples share SIMD paths of `u8` and `f32`.
+pub(crate) fn interleave_row<T: ImageSample>(planes: &[&[T]], dst: &mut [T], width: usize) {
+    let channels = planes.len();
+    let mut _cx = 0usize;
+
+    if channels == 3 || channels == 4 {
+        let mut pointers = [planes[0].as_ptr(); 4];
+        for (pointer, plane) in pointers.iter_mut().zip(planes.iter()) {
+            *pointer = plane.as_ptr();
+        }
+        unsafe {
+            match (std::mem::size_of::<T>(), channels) {
+                (1, 3) => {
+                    _cx = interleave_u8::<3>(
+                        &pointers.map(|p| p as *const u8),
+                        dst.as_mut_ptr() as *mut u8,
+                        width,
+                    )
+                }
+                (1, 4) => {
+                    _cx = interleave_u8::<4>(
+                        &pointers.map(|p| p as *const u8),
+                        dst.as_mut_ptr() as *mut u8,
+                        width,
+                    )
+                }
+                (4, 3) => {
+                    _cx = interleave_f32::<3>(
+                        &pointers.map(|p| p as *const f32),
+                        dst.as_mut_ptr() as *mut f32,
+                        width,
+                    )
+                }
+                (4, 4) => {
+                    _cx = interleave_f32::<4>(
+                        &pointers.map(|p| p as *const f32),
+                        dst.as_mut_ptr() as *mut f32,
+                        width,
+                    )
+                }
+                _ => {}
+            }
+        }
+    }
+
+    for (x, dst_pixel) in dst[_cx * channels..width * channels]
+        .chunks_exact_mut(channels)
+        .enumerate()
+    {
+        for (dst_value, plane) in dst_pixel.iter_mut().zip(planes.iter()) {
+            *dst_value = plane[_cx + x];
+        }
+    }
+}
+
+/// Splits one interleaved row into planes.
+/// Samples are only moved, so 8 bit and 32 bit samples share SIMD paths of `u8` and `f32`.
+pub(crate) fn deinterleave_row<T: ImageSample>(src: &[T], planes: &mut [&mut [T]], width: usize) {
+    let channels = planes.len();
+    let mut _cx = 0usize;
+
+    if channels == 3 || channels == 4 {
+        let mut pointers = [planes[0].as_mut_ptr(); 4];
+        for (pointer, plane) in pointers.iter_mut().zip(planes.iter_mut()) {
+            *pointer = plane.as_mut_ptr();
+        }
+        unsafe {
+            match (std::mem::size_of::<T>(), channels) {
+                (1, 3) => {
+                    _cx = deinterleave_u8::<3>(
+                        src.as_ptr() as *const u8,
+                        &pointers.map(|p| p as *mut u8),
+                        width,
+                    )
+                }
+                (1, 4) => {
+                    _cx = deinterleave_u8::<4>(
+                        src.as_ptr() as *const u8,
+                        &pointers.map(|p| p as *mut u8),
+                        width,
+                    )
+                }
+                (4, 3) => {
+                    _cx = deinterleave_f32::<3>(
+                        src.as_ptr() as *const f32,
+                        &pointers.map(|p| p as *mut f32),
+                        width,
+                    )
+                }
+                (4, 4) => {
+                    _cx = deinterleave_f32::<4>(
+                        src.as_ptr() as *const f32,
+                        &pointers.map(|p| p as *mut f32),
+                        width,
+                    )
+                }
+                _ => {}
+            }
+        }
+    }
+
+    for (x, src_pixel) in src[_cx * channels..width * channels]
+        .chunks_exact(channels)
+        .enumerate()
+    {
+        for (&src_value, plane) in src_pixel.iter().zip(planes.iter_mut()) {
+            plane[_cx + x] = src_value;
+        }
+    }
+}
+
+/// Runs `f` for every pair of destination and source rows according to `policy`
+fn for_each_row_pair<'a, S: ImageSample, D: ImageSample>(
+    src_rows: Vec<SourceRow<'a, S>>,
+    dst_rows: Vec<TargetRow<'a, D>>,
+    width: usize,
+    policy: &ThreadingPolicy,
+    f: impl Fn(TargetRow<'a, D>, SourceRow<'a, S>) + Send + Sync,
+) {
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = dst_rows.into_par_iter().zip(src_rows.into_par_iter());
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = dst_rows.into_iter().zip(src_rows);
+    }
+
+    iter.for_each_row_with(policy, width, |(dst_row, src_row)| f(dst_row, src_row));
+}
+
+/// Runs `convert` over every row, planar rows are interleaved into scratch rows before
+/// and split back after the conversion, so `convert` always sees interleaved rows
+pub(crate) fn convert_rows<S: ImageSample, D: ImageSample>(
+    src_rows: Vec<SourceRow<S>>,
+    src_channels: usize,
+    dst_rows: Vec<TargetRow<D>>,
+    dst_channels: usize,
+    width: u32,
+    policy: &ThreadingPolicy,
+    convert: impl Fn(&[S], &mut [D]) + Send + Sync,
+) {
+    let width = width as usize;
+    for_each_row_pair(src_rows, dst_rows, width, policy, |dst_row, src_row| {
+        let src_scratch;
+        let src_row = match &src_row {
+            SourceRow::Interleaved(row) => *row,
+            SourceRow::Planar(planes) => {
+                let mut scratch = vec![S::default(); width * src_channels];
+                interleave_row(planes, &mut scratch, width);
+                src_scratch = scratch;
+                &src_scratch
+            }
+        };
+        match dst_row {
+            TargetRow::Interleaved(row) => convert(src_row, row),
+            TargetRow::Planar(mut planes) => {
+                let mut scratch = vec![D::default(); width * dst_channels];
+                convert(src_row, &mut scratch);
+                deinterleave_row(&scratch, &mut planes, width);
+            }
+        }
+    });
+}
+
+/// This function splits interleaved image into separate planes, one plane per channel in order of the layout,
+/// so [ImageConfiguration::Bgra] gives B, G, R and A planes.
+/// Rows of 3 and 4 channels images with `u8` and `f32` samples are split with SIMD.
+///
+/// # Arguments
+/// * `src` - A slice contains interleaved image
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_layout` - Channel layout of the source
+/// * `planes` - Mutable slices to receive planes, one for every channel
+/// * `plane_strides` - Bytes per row of every plane
+/// * `width` - Image width
+/// * `height` - Image height
+///
+/// # Panics
+/// Panics if count of planes doesn't match layout or images doesn't fit into slices
+pub fn interleaved_to_planar<T: ImageSample>(
+    src: &[T],
+    src_stride: u32,
+    src_layout: ImageConfiguration,
+    planes: &mut [&mut [T]],
+    plane_strides: &[u32],
+    width: u32,
+    height: u32,
+) {
+    let channels = src_layout.get_channels_count();
+    let src_rows = interleaved_rows(src, src_stride, channels, width, height);
+    let dst_rows = planar_rows_mut(planes, plane_strides, channels, width, height);
+    let width = width as usize;
+    for_each_row_pair(
+        src_rows,
+        dst_rows,
+        width,
+        &current_threading(),
+        |dst_row, src_row| {
+            if let (TargetRow::Planar(mut planes), SourceRow::Interleaved(row)) = (dst_row, src_row)
+            {
+                deinterleave_row(row, &mut planes, width);
+            }
+        },
+    );
+}
+
+/// This function merges separate planes into interleaved image, one plane per channel in order of the layout,
+/// so [ImageConfiguration::Bgra] expects B, G, R and A planes.
+/// Rows of 3 and 4 channels images with `u8` and `f32` samples are merged with SIMD.
+///
+/// # Arguments
+/// * `planes` - Slices contains planes, one for every channel
+/// * `plane_strides` - Bytes per row of every plane
+/// * `dst` - A mutable slice to receive interleaved image
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_layout` - Channel layout of the destination
+/// * `width` - Image width
+/// * `height` - Image height
+///
+/// # Panics
+/// Panics if count of planes doesn't match layout or images doesn't fit into slices
+pub fn planar_to_interleaved<T: ImageSample>(
+    planes: &[&[T]],
+    plane_strides: &[u32],
+    dst: &mut [T],
+    dst_stride: u32,
+    dst_layout: ImageConfiguration,
+    width: u32,
+    height: u32,
+) {
+    let channels = dst_layout.get_channels_count();
+    let src_rows = planar_rows(planes, plane_strides, channels, width, height);
+    let dst_rows = interleaved_rows_mut(dst, dst_stride, channels, width, height);
+    let width = width as usize;
+    for_each_row_pair(
+        src_rows,
+        dst_rows,
+        width,
+        &current_threading(),
+        |dst_row, src_row| {
+            if let (TargetRow::Interleaved(row), SourceRow::Planar(planes)) = (dst_row, src_row) {
+                interleave_row(&planes, row, width);
+            }
+        },
+    );
+}
@@ -0,0 +1,304 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::color_space::{ColorModel, ColorSpace, SampleType};
+use crate::conversion_plan::ConversionPlan;
+use crate::image::ImageConfiguration;
+use crate::{TransferFunction, WHITE_POINT_D65};
+
+/// Returns layout of `planes` planes, alpha is the fourth plane
+fn planes_layout(planes: usize) -> ImageConfiguration {
+    match planes {
+        3 => ImageConfiguration::Rgb,
+        4 => ImageConfiguration::Rgba,
+        _ => panic!("Expected 3 or 4 planes, but {} were given", planes),
+    }
+}
+
+fn rgb_space(
+    layout: ImageConfiguration,
+    sample_type: SampleType,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) -> ColorSpace {
+    ColorSpace::rgb(
+        layout,
+        sample_type,
+        *matrix,
+        transfer_function,
+        WHITE_POINT_D65,
+    )
+}
+
+fn model_space(
+    layout: ImageConfiguration,
+    model: ColorModel,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) -> ColorSpace {
+    ColorSpace {
+        model,
+        ..rgb_space(layout, SampleType::F32, matrix, transfer_function)
+    }
+}
+
+/// This function converts RGB into planar CIE L\*a\*b\*, planes are L, a, b and optionally alpha scaled to [0, 1].
+/// This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains RGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_layout` - Channel layout of the source
+/// * `dst_planes` - Mutable slices to receive 3 or 4 planes
+/// * `dst_strides` - Bytes per row of every plane
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Transformation matrix from RGB to XYZ, for example `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function into linear colorspace and its inverse
+///
+/// # Panics
+/// Panics if count of planes is not 3 or 4 or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn rgb_to_lab_planar(
+    src: &[u8],
+    src_stride: u32,
+    src_layout: ImageConfiguration,
+    dst_planes: &mut [&mut [f32]],
+    dst_strides: &[u32],
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    let source = rgb_space(src_layout, SampleType::U8, matrix, transfer_function);
+    let target = model_space(
+        planes_layout(dst_planes.len()),
+        ColorModel::Lab,
+        matrix,
+        transfer_function,
+    );
+    ConversionPlan::new(&source, &target).apply_to_planar(
+        src,
+        src_stride,
+        dst_planes,
+        dst_strides,
+        width,
+        height,
+    );
+}
+
+/// This function converts planar CIE L\*a\*b\* into RGB, planes are L, a, b and optionally alpha in [0, 1].
+/// This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src_planes` - Slices contains 3 or 4 planes
+/// * `src_strides` - Bytes per row of every plane
+/// * `dst` - A mutable slice to receive RGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_layout` - Channel layout of the destination, alpha is 255 when there is no alpha plane
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Transformation matrix from RGB to XYZ, for example `SRGB_TO_XYZ_D65`
+/// * `transfer_function` - Transfer function into linear colorspace and its inverse
+///
+/// # Panics
+/// Panics if count of planes is not 3 or 4 or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn lab_planar_to_rgb(
+    src_planes: &[&[f32]],
+    src_strides: &[u32],
+    dst: &mut [u8],
+    dst_stride: u32,
+    dst_layout: ImageConfiguration,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    let source = model_space(
+        planes_layout(src_planes.len()),
+        ColorModel::Lab,
+        matrix,
+        transfer_function,
+    );
+    let target = rgb_space(dst_layout, SampleType::U8, matrix, transfer_function);
+    ConversionPlan::new(&source, &target).apply_from_planar(
+        src_planes,
+        src_strides,
+        dst,
+        dst_stride,
+        width,
+        height,
+    );
+}
+
+/// This function converts RGB into planar Oklab, planes are L, a, b and optionally alpha scaled to [0, 1].
+/// This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains RGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_layout` - Channel layout of the source
+/// * `dst_planes` - Mutable slices to receive 3 or 4 planes
+/// * `dst_strides` - Bytes per row of every plane
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function into linear colorspace and its inverse
+///
+/// # Panics
+/// Panics if count of planes is not 3 or 4 or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn rgb_to_oklab_planar(
+    src: &[u8],
+    src_stride: u32,
+    src_layout: ImageConfiguration,
+    dst_planes: &mut [&mut [f32]],
+    dst_strides: &[u32],
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    let mut source = ColorSpace::srgb(src_layout, SampleType::U8);
+    source.transfer_function = transfer_function;
+    let mut target = ColorSpace::new(
+        planes_layout(dst_planes.len()),
+        SampleType::F32,
+        ColorModel::Oklab,
+    );
+    target.transfer_function = transfer_function;
+    ConversionPlan::new(&source, &target).apply_to_planar(
+        src,
+        src_stride,
+        dst_planes,
+        dst_strides,
+        width,
+        height,
+    );
+}
+
+/// This function converts planar Oklab into RGB, planes are L, a, b and optionally alpha in [0, 1].
+/// This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src_planes` - Slices contains 3 or 4 planes
+/// * `src_strides` - Bytes per row of every plane
+/// * `dst` - A mutable slice to receive RGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_layout` - Channel layout of the destination, alpha is 255 when there is no alpha plane
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function into linear colorspace and its inverse
+///
+/// # Panics
+/// Panics if count of planes is not 3 or 4 or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn oklab_planar_to_rgb(
+    src_planes: &[&[f32]],
+    src_strides: &[u32],
+    dst: &mut [u8],
+    dst_stride: u32,
+    dst_layout: ImageConfiguration,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    let mut source = ColorSpace::new(
+        planes_layout(src_planes.len()),
+        SampleType::F32,
+        ColorModel::Oklab,
+    );
+    source.transfer_function = transfer_function;
+    let mut target = ColorSpace::srgb(dst_layout, SampleType::U8);
+    target.transfer_function = transfer_function;
+    ConversionPlan::new(&source, &target).apply_from_planar(
+        src_planes,
+        src_strides,
+        dst,
+        dst_stride,
+        width,
+        height,
+    );
+}
+
+/// This function converts RGB into planar linear RGB, planes are R, G, B and optionally alpha scaled to [0, 1].
+/// This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains RGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_layout` - Channel layout of the source
+/// * `dst_planes` - Mutable slices to receive 3 or 4 planes
+/// * `dst_strides` - Bytes per row of every plane
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function into linear colorspace and its inverse
+///
+/// # Panics
+/// Panics if count of planes is not 3 or 4 or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn rgb_to_linear_planar(
+    src: &[u8],
+    src_stride: u32,
+    src_layout: ImageConfiguration,
+    dst_planes: &mut [&mut [f32]],
+    dst_strides: &[u32],
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    let mut source = ColorSpace::srgb(src_layout, SampleType::U8);
+    source.transfer_function = transfer_function;
+    let mut target = ColorSpace::srgb(planes_layout(dst_planes.len()), SampleType::F32);
+    target.transfer_function = TransferFunction::Linear;
+    ConversionPlan::new(&source, &target).apply_to_planar(
+        src,
+        src_stride,
+        dst_planes,
+        dst_strides,
+        width,
+        height,
+    );
+}
+
+/// This function converts planar linear RGB into RGB, planes are R, G, B and optionally alpha in [0, 1].
+/// This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src_planes` - Slices contains 3 or 4 planes
+/// * `src_strides` - Bytes per row of every plane
+/// * `dst` - A mutable slice to receive RGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_layout` - Channel layout of the destination, alpha is 255 when there is no alpha plane
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `transfer_function` - Transfer function into linear colorspace and its inverse
+///
+/// # Panics
+/// Panics if count of planes is not 3 or 4 or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn linear_planar_to_rgb(
+    src_planes: &[&[f32]],
+    src_strides: &[u32],
+    dst: &mut [u8],
+    dst_stride: u32,
+    dst_layout: ImageConfiguration,
+    width: u32,
+    height: u32,
+    transfer_function: TransferFunction,
+) {
+    let mut source = ColorSpace::srgb(planes_layout(src_planes.len()), SampleType::F32);
+    source.transfer_function = TransferFunction::Linear;
+    let mut target = ColorSpace::srgb(dst_layout, SampleType::U8);
+    target.transfer_function = transfer_function;
+    ConversionPlan::new(&source, &target).apply_from_planar(
+        src_planes,
+        src_strides,
+        dst,
+        dst_stride,
+        width,
+        height,
+    );
+}
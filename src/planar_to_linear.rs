@@ -5,9 +5,10 @@
  * // license that can be found in the LICENSE file.
  */
 
+use crate::threading::ForEachRow;
 use crate::TransferFunction;
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 use std::slice;
@@ -49,7 +50,7 @@ fn channels_to_linear(
             .zip(src.chunks_exact(src_stride as usize));
     }
 
-    iter.for_each(|(dst, src)| unsafe {
+    iter.for_each_row(width as usize, |(dst, src)| unsafe {
         let mut _cx = 0usize;
 
         let src_ptr = src.as_ptr();
@@ -0,0 +1,1552 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::gamma_curves::TransferFunction;
+use crate::image::ImageConfiguration;
+use crate::image_view::check_image;
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use crate::neon::{
+    neon_premultiply_alpha_row_f32, neon_premultiply_alpha_row_u16, neon_premultiply_alpha_row_u8,
+    neon_unpremultiply_alpha_row_f32, neon_unpremultiply_alpha_row_u16,
+    neon_unpremultiply_alpha_row_u8,
+};
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+use crate::sse::{
+    sse_premultiply_alpha_row_f16, sse_premultiply_alpha_row_f32, sse_premultiply_alpha_row_u16,
+    sse_premultiply_alpha_row_u8, sse_unpremultiply_alpha_row_f16, sse_unpremultiply_alpha_row_f32,
+    sse_unpremultiply_alpha_row_u16, sse_unpremultiply_alpha_row_u8,
+};
+use crate::threading::ForEachRow;
+use half::f16;
+use half::slice::HalfFloatSliceExt;
+#[cfg(feature = "rayon")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "rayon")]
+use rayon::prelude::{ParallelSlice, ParallelSliceMut};
+
+/// Premultiplies row of four channel u8 pixels with alpha first or last, `src` and `dst` may point to the same row
+unsafe fn premultiply_alpha_row_u8_impl(
+    src: *const u8,
+    dst: *mut u8,
+    width: usize,
+    alpha_first: bool,
+) {
+    let mut _cx = 0usize;
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        _cx = if alpha_first {
+            neon_premultiply_alpha_row_u8::<true>(_cx, src, dst, width)
+        } else {
+            neon_premultiply_alpha_row_u8::<false>(_cx, src, dst, width)
+        };
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        _cx = if alpha_first {
+            sse_premultiply_alpha_row_u8::<true>(_cx, src, dst, width)
+        } else {
+            sse_premultiply_alpha_row_u8::<false>(_cx, src, dst, width)
+        };
+    }
+
+    let (a_pos, c_pos) = if alpha_first { (0, 1) } else { (3, 0) };
+
+    for x in _cx..width {
+        let px = x * 4;
+        let a = src.add(px + a_pos).read() as u16;
+        for c in 0..3 {
+            let product = src.add(px + c_pos + c).read() as u16 * a + 128;
+            dst.add(px + c_pos + c)
+                .write(((product + (product >> 8)) >> 8) as u8);
+        }
+        dst.add(px + a_pos).write(a as u8);
+    }
+}
+
+/// Unpremultiplies row of four channel u8 pixels with alpha first or last, `src` and `dst` may point to the same row
+unsafe fn unpremultiply_alpha_row_u8_impl(
+    src: *const u8,
+    dst: *mut u8,
+    width: usize,
+    alpha_first: bool,
+) {
+    let mut _cx = 0usize;
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        _cx = if alpha_first {
+            neon_unpremultiply_alpha_row_u8::<true>(_cx, src, dst, width)
+        } else {
+            neon_unpremultiply_alpha_row_u8::<false>(_cx, src, dst, width)
+        };
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        _cx = if alpha_first {
+            sse_unpremultiply_alpha_row_u8::<true>(_cx, src, dst, width)
+        } else {
+            sse_unpremultiply_alpha_row_u8::<false>(_cx, src, dst, width)
+        };
+    }
+
+    let (a_pos, c_pos) = if alpha_first { (0, 1) } else { (3, 0) };
+
+    for x in _cx..width {
+        let px = x * 4;
+        let a = src.add(px + a_pos).read();
+        for c in 0..3 {
+            let value = if a == 0 {
+                0
+            } else {
+                ((src.add(px + c_pos + c).read() as f32 * 255.) / a as f32 + 0.5)
+                    .floor()
+                    .min(255.) as u8
+            };
+            dst.add(px + c_pos + c).write(value);
+        }
+        dst.add(px + a_pos).write(a);
+    }
+}
+
+/// Premultiplies row of four channel u16 pixels with alpha first or last, `src` and `dst` may point to the same row
+unsafe fn premultiply_alpha_row_u16_impl(
+    src: *const u16,
+    dst: *mut u16,
+    width: usize,
+    max_value: f32,
+    alpha_first: bool,
+) {
+    let mut _cx = 0usize;
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        _cx = if alpha_first {
+            neon_premultiply_alpha_row_u16::<true>(_cx, src, dst, width, max_value)
+        } else {
+            neon_premultiply_alpha_row_u16::<false>(_cx, src, dst, width, max_value)
+        };
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        _cx = if alpha_first {
+            sse_premultiply_alpha_row_u16::<true>(_cx, src, dst, width, max_value)
+        } else {
+            sse_premultiply_alpha_row_u16::<false>(_cx, src, dst, width, max_value)
+        };
+    }
+
+    let (a_pos, c_pos) = if alpha_first { (0, 1) } else { (3, 0) };
+
+    for x in _cx..width {
+        let px = x * 4;
+        let a = src.add(px + a_pos).read();
+        for c in 0..3 {
+            let value = ((src.add(px + c_pos + c).read() as f32 * a as f32) / max_value + 0.5)
+                .floor() as u16;
+            dst.add(px + c_pos + c).write(value);
+        }
+        dst.add(px + a_pos).write(a);
+    }
+}
+
+/// Unpremultiplies row of four channel u16 pixels with alpha first or last, `src` and `dst` may point to the same row
+unsafe fn unpremultiply_alpha_row_u16_impl(
+    src: *const u16,
+    dst: *mut u16,
+    width: usize,
+    max_value: f32,
+    alpha_first: bool,
+) {
+    let mut _cx = 0usize;
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        _cx = if alpha_first {
+            neon_unpremultiply_alpha_row_u16::<true>(_cx, src, dst, width, max_value)
+        } else {
+            neon_unpremultiply_alpha_row_u16::<false>(_cx, src, dst, width, max_value)
+        };
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        _cx = if alpha_first {
+            sse_unpremultiply_alpha_row_u16::<true>(_cx, src, dst, width, max_value)
+        } else {
+            sse_unpremultiply_alpha_row_u16::<false>(_cx, src, dst, width, max_value)
+        };
+    }
+
+    let (a_pos, c_pos) = if alpha_first { (0, 1) } else { (3, 0) };
+
+    for x in _cx..width {
+        let px = x * 4;
+        let a = src.add(px + a_pos).read();
+        for c in 0..3 {
+            let value = if a == 0 {
+                0
+            } else {
+                ((src.add(px + c_pos + c).read() as f32 * max_value) / a as f32 + 0.5)
+                    .floor()
+                    .min(max_value) as u16
+            };
+            dst.add(px + c_pos + c).write(value);
+        }
+        dst.add(px + a_pos).write(a);
+    }
+}
+
+/// Premultiplies row of four channel f32 pixels with alpha first or last, `src` and `dst` may point to the same row
+unsafe fn premultiply_alpha_row_f32_impl(
+    src: *const f32,
+    dst: *mut f32,
+    width: usize,
+    alpha_first: bool,
+) {
+    let mut _cx = 0usize;
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        _cx = if alpha_first {
+            neon_premultiply_alpha_row_f32::<true>(_cx, src, dst, width)
+        } else {
+            neon_premultiply_alpha_row_f32::<false>(_cx, src, dst, width)
+        };
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        _cx = if alpha_first {
+            sse_premultiply_alpha_row_f32::<true>(_cx, src, dst, width)
+        } else {
+            sse_premultiply_alpha_row_f32::<false>(_cx, src, dst, width)
+        };
+    }
+
+    let (a_pos, c_pos) = if alpha_first { (0, 1) } else { (3, 0) };
+
+    for x in _cx..width {
+        let px = x * 4;
+        let a = src.add(px + a_pos).read();
+        for c in 0..3 {
+            dst.add(px + c_pos + c)
+                .write(src.add(px + c_pos + c).read() * a);
+        }
+        dst.add(px + a_pos).write(a);
+    }
+}
+
+/// Unpremultiplies row of four channel f32 pixels with alpha first or last, `src` and `dst` may point to the same row
+unsafe fn unpremultiply_alpha_row_f32_impl(
+    src: *const f32,
+    dst: *mut f32,
+    width: usize,
+    alpha_first: bool,
+) {
+    let mut _cx = 0usize;
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        _cx = if alpha_first {
+            neon_unpremultiply_alpha_row_f32::<true>(_cx, src, dst, width)
+        } else {
+            neon_unpremultiply_alpha_row_f32::<false>(_cx, src, dst, width)
+        };
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        _cx = if alpha_first {
+            sse_unpremultiply_alpha_row_f32::<true>(_cx, src, dst, width)
+        } else {
+            sse_unpremultiply_alpha_row_f32::<false>(_cx, src, dst, width)
+        };
+    }
+
+    let (a_pos, c_pos) = if alpha_first { (0, 1) } else { (3, 0) };
+
+    for x in _cx..width {
+        let px = x * 4;
+        let a = src.add(px + a_pos).read();
+        for c in 0..3 {
+            let value = if a == 0. {
+                0.
+            } else {
+                src.add(px + c_pos + c).read() / a
+            };
+            dst.add(px + c_pos + c).write(value);
+        }
+        dst.add(px + a_pos).write(a);
+    }
+}
+
+/// Count of f16 pixels widened to f32 at once when SIMD can't handle them
+const F16_CHUNK_PIXELS: usize = 64;
+
+/// Premultiplies or unpremultiplies row of four channel f16 pixels with alpha first or last.
+/// Pixels left after SIMD are widened to f32 through a stack buffer, so rows don't allocate
+fn process_alpha_row_f16<const PREMULTIPLY: bool>(
+    src: &[f16],
+    dst: &mut [f16],
+    width: usize,
+    alpha_first: bool,
+) {
+    let mut _cx = 0usize;
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") && std::arch::is_x86_feature_detected!("f16c")
+    {
+        let src_ptr = src.as_ptr() as *const u16;
+        let dst_ptr = dst.as_mut_ptr() as *mut u16;
+        unsafe {
+            _cx = match (PREMULTIPLY, alpha_first) {
+                (true, true) => sse_premultiply_alpha_row_f16::<true>(_cx, src_ptr, dst_ptr, width),
+                (true, false) => {
+                    sse_premultiply_alpha_row_f16::<false>(_cx, src_ptr, dst_ptr, width)
+                }
+                (false, true) => {
+                    sse_unpremultiply_alpha_row_f16::<true>(_cx, src_ptr, dst_ptr, width)
+                }
+                (false, false) => {
+                    sse_unpremultiply_alpha_row_f16::<false>(_cx, src_ptr, dst_ptr, width)
+                }
+            };
+        }
+    }
+
+    let mut transient = [0f32; F16_CHUNK_PIXELS * 4];
+    while _cx < width {
+        let count = (width - _cx).min(F16_CHUNK_PIXELS);
+        let samples = _cx * 4..(_cx + count) * 4;
+        let transient = &mut transient[..count * 4];
+        src[samples.clone()].convert_to_f32_slice(transient);
+        unsafe {
+            if PREMULTIPLY {
+                premultiply_alpha_row_f32_impl(
+                    transient.as_ptr(),
+                    transient.as_mut_ptr(),
+                    count,
+                    alpha_first,
+                );
+            } else {
+                unpremultiply_alpha_row_f32_impl(
+                    transient.as_ptr(),
+                    transient.as_mut_ptr(),
+                    count,
+                    alpha_first,
+                );
+            }
+        }
+        dst[samples].convert_from_f32_slice(transient);
+        _cx += count;
+    }
+}
+
+fn process_alpha_image<T: Copy + Send + Sync, const CHANNELS_CONFIGURATION: u8>(
+    src: &[T],
+    src_stride: u32,
+    dst: &mut [T],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    row_handler: impl Fn(&[T], &mut [T], usize, bool) + Sync,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    if !image_configuration.has_alpha() {
+        panic!("Alpha may be premultiplied only on images with alpha channel");
+    }
+    if width == 0 || height == 0 {
+        return;
+    }
+    let alpha_first = image_configuration.get_a_channel_offset() == 0;
+    let src_row_length = check_image::<T>(
+        src.len(),
+        src_stride,
+        width,
+        height,
+        image_configuration,
+        "Source",
+    );
+    let dst_row_length = check_image::<T>(
+        dst.len(),
+        dst_stride,
+        width,
+        height,
+        image_configuration,
+        "Destination",
+    );
+    let width = width as usize;
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = dst
+            .par_chunks_mut(dst_row_length)
+            .zip(src.par_chunks(src_row_length))
+            .take(height as usize);
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = dst
+            .chunks_mut(dst_row_length)
+            .zip(src.chunks(src_row_length))
+            .take(height as usize);
+    }
+
+    iter.for_each_row(width, |(dst_row, src_row)| {
+        row_handler(src_row, dst_row, width, alpha_first);
+    });
+}
+
+fn premultiply_alpha_u8<const CHANNELS_CONFIGURATION: u8>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    process_alpha_image::<u8, CHANNELS_CONFIGURATION>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        |src, dst, width, alpha_first| unsafe {
+            premultiply_alpha_row_u8_impl(src.as_ptr(), dst.as_mut_ptr(), width, alpha_first);
+        },
+    );
+}
+
+fn unpremultiply_alpha_u8<const CHANNELS_CONFIGURATION: u8>(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    process_alpha_image::<u8, CHANNELS_CONFIGURATION>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        |src, dst, width, alpha_first| unsafe {
+            unpremultiply_alpha_row_u8_impl(src.as_ptr(), dst.as_mut_ptr(), width, alpha_first);
+        },
+    );
+}
+
+/// Fills f32 transient row with linear colours of premultiplied RGBA or BGRA u8 row and normalized alpha.
+/// Colours are divided by alpha in f32 and linearized with transfer function, so they aren't rounded
+/// in between, opaque pixels use the same linearization table as unpremultiplied input
+pub(crate) fn linearize_premultiplied_row_u8<const CHANNELS_CONFIGURATION: u8>(
+    src: &[u8],
+    transient_row: &mut [f32],
+    lut_table: &[f32],
+    transfer_function: TransferFunction,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+    let a_offset = image_configuration.get_a_channel_offset();
+    let colour_offsets = [
+        image_configuration.get_r_channel_offset(),
+        image_configuration.get_g_channel_offset(),
+        image_configuration.get_b_channel_offset(),
+    ];
+
+    for (dst, src) in transient_row
+        .chunks_exact_mut(channels)
+        .zip(src.chunks_exact(channels))
+    {
+        let a = src[a_offset];
+        for offset in colour_offsets {
+            dst[offset] = match a {
+                0 => 0.,
+                255 => lut_table[src[offset] as usize],
+                _ => transfer_function.linearize((src[offset] as f32 / a as f32).min(1.)),
+            };
+        }
+        dst[a_offset] = a as f32 * (1. / 255.);
+    }
+}
+
+/// Premultiplies freshly encoded RGBA or BGRA u8 row in place,
+/// converters producing premultiplied output call it on each row they write
+pub(crate) fn premultiply_row_u8<const CHANNELS_CONFIGURATION: u8>(row: &mut [u8], width: usize) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    if !image_configuration.has_alpha() {
+        panic!("Alpha may be premultiplied only on images with alpha channel");
+    }
+    let alpha_first = image_configuration.get_a_channel_offset() == 0;
+    assert!(row.len() >= width * 4);
+    unsafe {
+        premultiply_alpha_row_u8_impl(row.as_ptr(), row.as_mut_ptr(), width, alpha_first);
+    }
+}
+
+#[inline]
+fn max_value_for_bit_depth(bit_depth: u32) -> f32 {
+    if !(1..=16).contains(&bit_depth) {
+        panic!(
+            "Bit depth must be in range [1, 16], but it was {}",
+            bit_depth
+        );
+    }
+    ((1u32 << bit_depth) - 1) as f32
+}
+
+fn premultiply_alpha_u16<const CHANNELS_CONFIGURATION: u8>(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+) {
+    let max_value = max_value_for_bit_depth(bit_depth);
+    process_alpha_image::<u16, CHANNELS_CONFIGURATION>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        |src, dst, width, alpha_first| unsafe {
+            premultiply_alpha_row_u16_impl(
+                src.as_ptr(),
+                dst.as_mut_ptr(),
+                width,
+                max_value,
+                alpha_first,
+            );
+        },
+    );
+}
+
+fn unpremultiply_alpha_u16<const CHANNELS_CONFIGURATION: u8>(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+) {
+    let max_value = max_value_for_bit_depth(bit_depth);
+    process_alpha_image::<u16, CHANNELS_CONFIGURATION>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        |src, dst, width, alpha_first| unsafe {
+            unpremultiply_alpha_row_u16_impl(
+                src.as_ptr(),
+                dst.as_mut_ptr(),
+                width,
+                max_value,
+                alpha_first,
+            );
+        },
+    );
+}
+
+fn premultiply_alpha_f16<const CHANNELS_CONFIGURATION: u8, const PREMULTIPLY: bool>(
+    src: &[f16],
+    src_stride: u32,
+    dst: &mut [f16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    process_alpha_image::<f16, CHANNELS_CONFIGURATION>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        |src, dst, width, alpha_first| {
+            process_alpha_row_f16::<PREMULTIPLY>(src, dst, width, alpha_first);
+        },
+    );
+}
+
+fn premultiply_alpha_f32<const CHANNELS_CONFIGURATION: u8, const PREMULTIPLY: bool>(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    process_alpha_image::<f32, CHANNELS_CONFIGURATION>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        |src, dst, width, alpha_first| unsafe {
+            if PREMULTIPLY {
+                premultiply_alpha_row_f32_impl(src.as_ptr(), dst.as_mut_ptr(), width, alpha_first);
+            } else {
+                unpremultiply_alpha_row_f32_impl(
+                    src.as_ptr(),
+                    dst.as_mut_ptr(),
+                    width,
+                    alpha_first,
+                );
+            }
+        },
+    );
+}
+
+/// This function premultiplies RGBA colour channels by alpha, results are correctly rounded
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data with straight alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGBA data with premultiplied alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn rgba_premultiply_alpha(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_u8::<{ ImageConfiguration::Rgba as u8 }>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function premultiplies ARGB colour channels by alpha, results are correctly rounded
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data with straight alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data with premultiplied alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn argb_premultiply_alpha(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_u8::<{ ImageConfiguration::Argb as u8 }>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function premultiplies BGRA colour channels by alpha, results are correctly rounded
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data with straight alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGRA data with premultiplied alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn bgra_premultiply_alpha(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_u8::<{ ImageConfiguration::Bgra as u8 }>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function premultiplies ABGR colour channels by alpha, results are correctly rounded
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data with straight alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data with premultiplied alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn abgr_premultiply_alpha(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_u8::<{ ImageConfiguration::Abgr as u8 }>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function divides premultiplied RGBA colour channels by alpha, fully transparent pixels become zero
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data with premultiplied alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGBA data with straight alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn rgba_unpremultiply_alpha(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    unpremultiply_alpha_u8::<{ ImageConfiguration::Rgba as u8 }>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function divides premultiplied ARGB colour channels by alpha, fully transparent pixels become zero
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data with premultiplied alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data with straight alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn argb_unpremultiply_alpha(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    unpremultiply_alpha_u8::<{ ImageConfiguration::Argb as u8 }>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function divides premultiplied BGRA colour channels by alpha, fully transparent pixels become zero
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data with premultiplied alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGRA data with straight alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn bgra_unpremultiply_alpha(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    unpremultiply_alpha_u8::<{ ImageConfiguration::Bgra as u8 }>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function divides premultiplied ABGR colour channels by alpha, fully transparent pixels become zero
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data with premultiplied alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data with straight alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn abgr_unpremultiply_alpha(
+    src: &[u8],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    unpremultiply_alpha_u8::<{ ImageConfiguration::Abgr as u8 }>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function premultiplies RGBA colour channels by alpha for 16 bit storage, results are correctly rounded
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data with straight alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGBA data with premultiplied alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of samples, 10, 12 or 16 etc.
+pub fn rgba_u16_premultiply_alpha(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+) {
+    premultiply_alpha_u16::<{ ImageConfiguration::Rgba as u8 }>(
+        src, src_stride, dst, dst_stride, width, height, bit_depth,
+    );
+}
+
+/// This function premultiplies ARGB colour channels by alpha for 16 bit storage, results are correctly rounded
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data with straight alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data with premultiplied alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of samples, 10, 12 or 16 etc.
+pub fn argb_u16_premultiply_alpha(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+) {
+    premultiply_alpha_u16::<{ ImageConfiguration::Argb as u8 }>(
+        src, src_stride, dst, dst_stride, width, height, bit_depth,
+    );
+}
+
+/// This function premultiplies BGRA colour channels by alpha for 16 bit storage, results are correctly rounded
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data with straight alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGRA data with premultiplied alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of samples, 10, 12 or 16 etc.
+pub fn bgra_u16_premultiply_alpha(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+) {
+    premultiply_alpha_u16::<{ ImageConfiguration::Bgra as u8 }>(
+        src, src_stride, dst, dst_stride, width, height, bit_depth,
+    );
+}
+
+/// This function premultiplies ABGR colour channels by alpha for 16 bit storage, results are correctly rounded
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data with straight alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data with premultiplied alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of samples, 10, 12 or 16 etc.
+pub fn abgr_u16_premultiply_alpha(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+) {
+    premultiply_alpha_u16::<{ ImageConfiguration::Abgr as u8 }>(
+        src, src_stride, dst, dst_stride, width, height, bit_depth,
+    );
+}
+
+/// This function divides premultiplied RGBA colour channels by alpha for 16 bit storage
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data with premultiplied alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGBA data with straight alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of samples, 10, 12 or 16 etc.
+pub fn rgba_u16_unpremultiply_alpha(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+) {
+    unpremultiply_alpha_u16::<{ ImageConfiguration::Rgba as u8 }>(
+        src, src_stride, dst, dst_stride, width, height, bit_depth,
+    );
+}
+
+/// This function divides premultiplied ARGB colour channels by alpha for 16 bit storage
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data with premultiplied alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data with straight alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of samples, 10, 12 or 16 etc.
+pub fn argb_u16_unpremultiply_alpha(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+) {
+    unpremultiply_alpha_u16::<{ ImageConfiguration::Argb as u8 }>(
+        src, src_stride, dst, dst_stride, width, height, bit_depth,
+    );
+}
+
+/// This function divides premultiplied BGRA colour channels by alpha for 16 bit storage
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data with premultiplied alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGRA data with straight alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of samples, 10, 12 or 16 etc.
+pub fn bgra_u16_unpremultiply_alpha(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+) {
+    unpremultiply_alpha_u16::<{ ImageConfiguration::Bgra as u8 }>(
+        src, src_stride, dst, dst_stride, width, height, bit_depth,
+    );
+}
+
+/// This function divides premultiplied ABGR colour channels by alpha for 16 bit storage
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data with premultiplied alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data with straight alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of samples, 10, 12 or 16 etc.
+pub fn abgr_u16_unpremultiply_alpha(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+) {
+    unpremultiply_alpha_u16::<{ ImageConfiguration::Abgr as u8 }>(
+        src, src_stride, dst, dst_stride, width, height, bit_depth,
+    );
+}
+
+/// This function premultiplies RGBA f16 colour channels by alpha
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data with straight alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGBA data with premultiplied alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn rgba_f16_premultiply_alpha(
+    src: &[f16],
+    src_stride: u32,
+    dst: &mut [f16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_f16::<{ ImageConfiguration::Rgba as u8 }, true>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function premultiplies ARGB f16 colour channels by alpha
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data with straight alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data with premultiplied alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn argb_f16_premultiply_alpha(
+    src: &[f16],
+    src_stride: u32,
+    dst: &mut [f16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_f16::<{ ImageConfiguration::Argb as u8 }, true>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function premultiplies BGRA f16 colour channels by alpha
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data with straight alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGRA data with premultiplied alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn bgra_f16_premultiply_alpha(
+    src: &[f16],
+    src_stride: u32,
+    dst: &mut [f16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_f16::<{ ImageConfiguration::Bgra as u8 }, true>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function premultiplies ABGR f16 colour channels by alpha
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data with straight alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data with premultiplied alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn abgr_f16_premultiply_alpha(
+    src: &[f16],
+    src_stride: u32,
+    dst: &mut [f16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_f16::<{ ImageConfiguration::Abgr as u8 }, true>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function divides premultiplied RGBA f16 colour channels by alpha, fully transparent pixels become zero
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data with premultiplied alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGBA data with straight alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn rgba_f16_unpremultiply_alpha(
+    src: &[f16],
+    src_stride: u32,
+    dst: &mut [f16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_f16::<{ ImageConfiguration::Rgba as u8 }, false>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function divides premultiplied ARGB f16 colour channels by alpha, fully transparent pixels become zero
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data with premultiplied alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data with straight alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn argb_f16_unpremultiply_alpha(
+    src: &[f16],
+    src_stride: u32,
+    dst: &mut [f16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_f16::<{ ImageConfiguration::Argb as u8 }, false>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function divides premultiplied BGRA f16 colour channels by alpha, fully transparent pixels become zero
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data with premultiplied alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGRA data with straight alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn bgra_f16_unpremultiply_alpha(
+    src: &[f16],
+    src_stride: u32,
+    dst: &mut [f16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_f16::<{ ImageConfiguration::Bgra as u8 }, false>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function divides premultiplied ABGR f16 colour channels by alpha, fully transparent pixels become zero
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data with premultiplied alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data with straight alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn abgr_f16_unpremultiply_alpha(
+    src: &[f16],
+    src_stride: u32,
+    dst: &mut [f16],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_f16::<{ ImageConfiguration::Abgr as u8 }, false>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function premultiplies RGBA f32 colour channels by alpha
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data with straight alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGBA data with premultiplied alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn rgba_f32_premultiply_alpha(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_f32::<{ ImageConfiguration::Rgba as u8 }, true>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function premultiplies ARGB f32 colour channels by alpha
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data with straight alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data with premultiplied alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn argb_f32_premultiply_alpha(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_f32::<{ ImageConfiguration::Argb as u8 }, true>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function premultiplies BGRA f32 colour channels by alpha
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data with straight alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGRA data with premultiplied alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn bgra_f32_premultiply_alpha(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_f32::<{ ImageConfiguration::Bgra as u8 }, true>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function premultiplies ABGR f32 colour channels by alpha
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data with straight alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data with premultiplied alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn abgr_f32_premultiply_alpha(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_f32::<{ ImageConfiguration::Abgr as u8 }, true>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function divides premultiplied RGBA f32 colour channels by alpha, fully transparent pixels become zero
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data with premultiplied alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGBA data with straight alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn rgba_f32_unpremultiply_alpha(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_f32::<{ ImageConfiguration::Rgba as u8 }, false>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function divides premultiplied ARGB f32 colour channels by alpha, fully transparent pixels become zero
+///
+/// # Arguments
+/// * `src` - A slice contains ARGB data with premultiplied alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data with straight alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn argb_f32_unpremultiply_alpha(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_f32::<{ ImageConfiguration::Argb as u8 }, false>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function divides premultiplied BGRA f32 colour channels by alpha, fully transparent pixels become zero
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data with premultiplied alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGRA data with straight alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn bgra_f32_unpremultiply_alpha(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_f32::<{ ImageConfiguration::Bgra as u8 }, false>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+/// This function divides premultiplied ABGR f32 colour channels by alpha, fully transparent pixels become zero
+///
+/// # Arguments
+/// * `src` - A slice contains ABGR data with premultiplied alpha
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data with straight alpha
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn abgr_f32_unpremultiply_alpha(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [f32],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    premultiply_alpha_f32::<{ ImageConfiguration::Abgr as u8 }, false>(
+        src, src_stride, dst, dst_stride, width, height,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Moves alpha of every RGBA pixel to the front
+    fn rgba_to_argb<T: Copy>(rgba: &[T]) -> Vec<T> {
+        rgba.chunks_exact(4)
+            .flat_map(|px| [px[3], px[0], px[1], px[2]])
+            .collect()
+    }
+
+    #[test]
+    fn u8_premultiply_is_correctly_rounded_and_reversible() {
+        // Row holds every colour value, column holds every alpha
+        let src: Vec<u8> = (0..256 * 256)
+            .flat_map(|i| {
+                let (c, a) = ((i % 256) as u8, (i / 256) as u8);
+                [c, 255 - c, c, a]
+            })
+            .collect();
+        let stride = 256 * 4;
+        let mut premultiplied = vec![0u8; src.len()];
+        rgba_premultiply_alpha(&src, stride, &mut premultiplied, stride, 256, 256);
+        let mut straight = vec![0u8; src.len()];
+        rgba_unpremultiply_alpha(&premultiplied, stride, &mut straight, stride, 256, 256);
+
+        for ((src, premultiplied), straight) in src
+            .chunks_exact(4)
+            .zip(premultiplied.chunks_exact(4))
+            .zip(straight.chunks_exact(4))
+        {
+            let a = src[3] as u32;
+            assert_eq!(premultiplied[3], src[3]);
+            assert_eq!(straight[3], src[3]);
+            for c in 0..3 {
+                let value = src[c] as u32;
+                let expected = (2 * value * a + 255) / 510;
+                assert_eq!(premultiplied[c] as u32, expected, "{} * {}", value, a);
+                if a == 0 {
+                    assert_eq!(straight[c], 0);
+                    continue;
+                }
+                let expected = ((expected * 255) as f64 / a as f64 + 0.5).floor().min(255.);
+                assert_eq!(straight[c] as f64, expected, "{} / {}", premultiplied[c], a);
+                let tolerance = 127.5 / a as f64 + 0.5;
+                assert!((straight[c] as f64 - value as f64).abs() <= tolerance);
+            }
+        }
+
+        let argb = rgba_to_argb(&src);
+        let mut argb_premultiplied = vec![0u8; src.len()];
+        premultiply_alpha_u8::<{ ImageConfiguration::Argb as u8 }>(
+            &argb,
+            stride,
+            &mut argb_premultiplied,
+            stride,
+            256,
+            256,
+        );
+        assert_eq!(argb_premultiplied, rgba_to_argb(&premultiplied));
+        let mut argb_straight = vec![0u8; src.len()];
+        unpremultiply_alpha_u8::<{ ImageConfiguration::Argb as u8 }>(
+            &argb_premultiplied,
+            stride,
+            &mut argb_straight,
+            stride,
+            256,
+            256,
+        );
+        assert_eq!(argb_straight, rgba_to_argb(&straight));
+    }
+
+    #[test]
+    fn alpha_first_matches_alpha_last() {
+        // Width isn't a multiple of any vector length, so SIMD and scalar tails both run
+        let width = 67usize;
+        let height = 3usize;
+        let src: Vec<u16> = (0..width * height * 4)
+            .map(|i| ((i * 2731) % 1024) as u16)
+            .collect();
+        let stride = (width * 4 * 2) as u32;
+        let mut rgba = vec![0u16; src.len()];
+        let mut argb = vec![0u16; src.len()];
+        let argb_src = rgba_to_argb(&src);
+        premultiply_alpha_u16::<{ ImageConfiguration::Rgba as u8 }>(
+            &src,
+            stride,
+            &mut rgba,
+            stride,
+            width as u32,
+            height as u32,
+            10,
+        );
+        premultiply_alpha_u16::<{ ImageConfiguration::Argb as u8 }>(
+            &argb_src,
+            stride,
+            &mut argb,
+            stride,
+            width as u32,
+            height as u32,
+            10,
+        );
+        assert_eq!(argb, rgba_to_argb(&rgba));
+        unpremultiply_alpha_u16::<{ ImageConfiguration::Rgba as u8 }>(
+            &src,
+            stride,
+            &mut rgba,
+            stride,
+            width as u32,
+            height as u32,
+            10,
+        );
+        unpremultiply_alpha_u16::<{ ImageConfiguration::Argb as u8 }>(
+            &argb_src,
+            stride,
+            &mut argb,
+            stride,
+            width as u32,
+            height as u32,
+            10,
+        );
+        assert_eq!(argb, rgba_to_argb(&rgba));
+
+        let src: Vec<f32> = src.iter().map(|&v| v as f32 / 1023.).collect();
+        let argb_src = rgba_to_argb(&src);
+        let stride = (width * 4 * 4) as u32;
+        let mut rgba = vec![0f32; src.len()];
+        let mut argb = vec![0f32; src.len()];
+        for premultiply in [true, false] {
+            if premultiply {
+                rgba_f32_premultiply_alpha(&src, stride, &mut rgba, stride, 67, 3);
+                premultiply_alpha_f32::<{ ImageConfiguration::Argb as u8 }, true>(
+                    &argb_src, stride, &mut argb, stride, 67, 3,
+                );
+            } else {
+                rgba_f32_unpremultiply_alpha(&src, stride, &mut rgba, stride, 67, 3);
+                premultiply_alpha_f32::<{ ImageConfiguration::Argb as u8 }, false>(
+                    &argb_src, stride, &mut argb, stride, 67, 3,
+                );
+            }
+            assert_eq!(argb, rgba_to_argb(&rgba));
+        }
+    }
+
+    #[test]
+    fn f16_matches_f32_reference() {
+        let width = 67usize;
+        let src: Vec<f16> = (0..width * 4)
+            .map(|i| f16::from_f32(((i * 37) % 101) as f32 / 100.))
+            .collect();
+        let stride = (width * 4 * 2) as u32;
+        let mut dst = vec![f16::ZERO; src.len()];
+        for premultiply in [true, false] {
+            if premultiply {
+                rgba_f16_premultiply_alpha(&src, stride, &mut dst, stride, width as u32, 1);
+            } else {
+                rgba_f16_unpremultiply_alpha(&src, stride, &mut dst, stride, width as u32, 1);
+            }
+            for (src, dst) in src.chunks_exact(4).zip(dst.chunks_exact(4)) {
+                let a = src[3].to_f32();
+                assert_eq!(dst[3], src[3]);
+                for c in 0..3 {
+                    let value = src[c].to_f32();
+                    let expected = match (premultiply, a == 0.) {
+                        (true, _) => value * a,
+                        (false, true) => 0.,
+                        (false, false) => value / a,
+                    };
+                    assert_eq!(dst[c], f16::from_f32(expected));
+                }
+            }
+
+            let argb_src = rgba_to_argb(&src);
+            let mut argb = vec![f16::ZERO; src.len()];
+            if premultiply {
+                premultiply_alpha_f16::<{ ImageConfiguration::Argb as u8 }, true>(
+                    &argb_src,
+                    stride,
+                    &mut argb,
+                    stride,
+                    width as u32,
+                    1,
+                );
+            } else {
+                premultiply_alpha_f16::<{ ImageConfiguration::Argb as u8 }, false>(
+                    &argb_src,
+                    stride,
+                    &mut argb,
+                    stride,
+                    width as u32,
+                    1,
+                );
+            }
+            assert_eq!(argb, rgba_to_argb(&dst));
+        }
+    }
+
+    #[test]
+    fn last_row_may_be_shorter_than_stride() {
+        let src = vec![255u8; 3 * 8 + 4];
+        let mut dst = vec![0u8; src.len()];
+        rgba_premultiply_alpha(&src, 8, &mut dst, 8, 1, 4);
+        for row in dst.chunks(8) {
+            assert_eq!(row[..4], [255; 4]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Destination image doesn't fit into slice")]
+    fn short_destination_panics() {
+        let src = vec![0u8; 4 * 4 * 4];
+        let mut dst = vec![0u8; 4 * 4 * 3];
+        rgba_premultiply_alpha(&src, 16, &mut dst, 16, 4, 4);
+    }
+}
@@ -4,6 +4,7 @@
  * // Use of this source code is governed by a BSD-style
  * // license that can be found in the LICENSE file.
  */
+use crate::cvd::{daltonize_linear, simulate_cvd_linear, ColorVisionDeficiency, CvdModel};
 use crate::euclidean::EuclideanDistance;
 use crate::hsv::Hsv;
 use crate::lab::Lab;
@@ -159,6 +160,46 @@ impl Rgb<u8> {
         LAlphaBeta::from_rgb(*self, transfer_function)
     }
 
+    /// Simulates colour vision deficiency
+    ///
+    /// # Arguments
+    /// `deficiency` - Colour vision deficiency type
+    /// `severity` - Deficiency severity in range [0, 1], 1 is dichromacy
+    /// `model` - Simulation model
+    /// `transfer_function` - Transfer function to convert into linear colorspace and backwards
+    #[inline]
+    pub fn simulate_cvd(
+        &self,
+        deficiency: ColorVisionDeficiency,
+        severity: f32,
+        model: CvdModel,
+        transfer_function: TransferFunction,
+    ) -> Rgb<u8> {
+        let linear = self.to_linear(transfer_function);
+        let simulated = simulate_cvd_linear(linear, deficiency, severity, model);
+        Rgb::<u8>::from_linear(simulated, transfer_function)
+    }
+
+    /// Daltonizes colour for the colour vision deficiency
+    ///
+    /// # Arguments
+    /// `deficiency` - Colour vision deficiency type
+    /// `severity` - Deficiency severity in range [0, 1], 1 is dichromacy
+    /// `model` - Simulation model
+    /// `transfer_function` - Transfer function to convert into linear colorspace and backwards
+    #[inline]
+    pub fn daltonize(
+        &self,
+        deficiency: ColorVisionDeficiency,
+        severity: f32,
+        model: CvdModel,
+        transfer_function: TransferFunction,
+    ) -> Rgb<u8> {
+        let linear = self.to_linear(transfer_function);
+        let daltonized = daltonize_linear(linear, deficiency, severity, model);
+        Rgb::<u8>::from_linear(daltonized, transfer_function)
+    }
+
     #[inline]
     pub fn blend_add(&self, other: Rgb<u8>) -> Rgb<u8> {
         let in_color = self.to_rgb_f32();
@@ -458,12 +458,26 @@ impl Rgba<f32> {
         self.pdf_set_lum(lum)
     }
 
+    /// f(cA,cB) = set_lum(set_sat(cB, sat(cA)), lum(cB))
     #[inline]
     pub fn blend_hsl_saturation(&self, backdrop: Rgba<f32>) -> Rgba<f32> {
         let j1 = backdrop.pdf_set_sat(self.pdf_sat());
         j1.pdf_set_lum(pdf_lum!(backdrop))
     }
 
+    /// f(cA,cB) = set_lum(set_sat(cA, sat(cB)), lum(cB))
+    #[inline]
+    pub fn blend_hsl_hue(&self, backdrop: Rgba<f32>) -> Rgba<f32> {
+        let j1 = self.pdf_set_sat(backdrop.pdf_sat());
+        j1.pdf_set_lum(pdf_lum!(backdrop))
+    }
+
+    /// f(cA,cB) = set_lum(cB, lum(cA))
+    #[inline]
+    pub fn blend_hsl_luminosity(&self, backdrop: Rgba<f32>) -> Rgba<f32> {
+        backdrop.blend_hsl_color(*self)
+    }
+
     #[inline]
     pub fn contrast(&self, contrast: f32) -> Rgba<f32> {
         let new_r = self.r * contrast + -0.5f32 * contrast + 0.5f32;
@@ -528,13 +542,19 @@ impl Rgba<u8> {
 
     /// f(cA,cB) = set_lum(cB, lum(cA))
     #[inline]
-    pub fn blend_hsl_lumonosity(&self, backdrop: Rgba<u8>) -> Rgba<u8> {
+    pub fn blend_hsl_luminosity(&self, backdrop: Rgba<u8>) -> Rgba<u8> {
         let source = self.to_rgba_f32();
         let backdrop = backdrop.to_rgba_f32();
         let blended = backdrop.blend_hsl_color(source);
         blended.to_rgba8()
     }
 
+    #[deprecated(note = "Use `blend_hsl_luminosity` instead")]
+    #[inline]
+    pub fn blend_hsl_lumonosity(&self, backdrop: Rgba<u8>) -> Rgba<u8> {
+        self.blend_hsl_luminosity(backdrop)
+    }
+
     #[inline]
     pub fn blend_overlay(&self, backdrop: Rgba<u8>) -> Rgba<u8> {
         let source = self.to_rgba_f32();
@@ -880,6 +900,9 @@ impl ToRgbaF16 for Rgba<u8> {
 
 #[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
 /// Represents RGB 565 color in one u16
+///
+/// Red is in bits 11..16, green in bits 5..11 and blue in bits 0..5.
+/// Earlier versions did not shift channels into place for float colors, see CHANGELOG.md.
 pub struct Rgb565 {
     pub rgb565: u16,
 }
@@ -906,8 +929,8 @@ static SCALE_RGB565_6BIT: f32 = 1f32 / 63f32;
 impl ToRgbaF16 for Rgb565 {
     #[inline]
     fn to_rgba_f16(&self) -> Rgba<f16> {
-        let red5 = (self.rgb565 & 0b1111100000000000) as f32 * SCALE_RGB565_5BIT;
-        let green6 = (self.rgb565 & 0b11111100000) as f32 * SCALE_RGB565_6BIT;
+        let red5 = (self.rgb565 >> 11) as f32 * SCALE_RGB565_5BIT;
+        let green6 = ((self.rgb565 >> 5) & 0b111111) as f32 * SCALE_RGB565_6BIT;
         let blue5 = (self.rgb565 & 0b11111) as f32 * SCALE_RGB565_5BIT;
         Rgba::<f16>::from_rgb(
             f16::from_f32(red5),
@@ -937,7 +960,7 @@ impl ToRgb565 for Rgba<f16> {
         let green6 = (self.g.to_f32() * 63f32).min(63f32).max(0f32) as u16;
         let blue5 = (self.b.to_f32() * 31f32).min(31f32).max(0f32) as u16;
         Rgb565 {
-            rgb565: red5 | green6 | blue5,
+            rgb565: (red5 << 11) | (green6 << 5) | blue5,
         }
     }
 }
@@ -950,13 +973,17 @@ impl ToRgb565 for Rgba<f32> {
         let green6 = (self.g * 63f32).min(63f32).max(0f32) as u16;
         let blue5 = (self.b * 31f32).min(31f32).max(0f32) as u16;
         Rgb565 {
-            rgb565: red5 | green6 | blue5,
+            rgb565: (red5 << 11) | (green6 << 5) | blue5,
         }
     }
 }
 
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
 /// Represents RGBA 1010102 in one u32 store
+///
+/// Red is in bits 0..10, green in bits 10..20, blue in bits 20..30 and alpha in bits 30..32.
+/// The 2-bit alpha codes 0..=3 map to 0, 85, 170 and 255 in 8 bits.
+/// Earlier versions swapped red and blue and scaled alpha by shifting, see CHANGELOG.md.
 pub struct Rgba1010102 {
     pub rgba: u32,
 }
@@ -980,7 +1007,7 @@ impl ToRgba8 for Rgba1010102 {
             (r >> 2) as u8,
             (g >> 2) as u8,
             (b >> 2) as u8,
-            (a << 6) as u8,
+            (a * 85) as u8,
         )
     }
 }
@@ -1016,8 +1043,8 @@ impl ToRgba1010102 for Rgba<u8> {
         let r = (self.r as u32) << 2;
         let g = (self.g as u32) << 2;
         let b = (self.b as u32) << 2;
-        let a = (self.a as u32) >> 6;
-        let rgba1010102 = (a << 30) | (r << 20) | (g << 10) | b;
+        let a = (self.a as u32 * 3 + 127) / 255;
+        let rgba1010102 = (a << 30) | (b << 20) | (g << 10) | r;
         Rgba1010102 { rgba: rgba1010102 }
     }
 }
@@ -1030,7 +1057,7 @@ impl ToRgba1010102 for Rgba<f16> {
         let g = (self.g.to_f32() * 1023f32).min(1023f32).max(0f32) as u32;
         let b = (self.b.to_f32() * 1023f32).min(1023f32).max(0f32) as u32;
         let a = (self.a.to_f32() * 3f32).min(3f32).max(0f32) as u32;
-        let rgba1010102 = (a << 30) | (r << 20) | (g << 10) | b;
+        let rgba1010102 = (a << 30) | (b << 20) | (g << 10) | r;
         Rgba1010102 { rgba: rgba1010102 }
     }
 }
@@ -1358,3 +1385,85 @@ where
         dr.abs() + dg.abs() + db.abs() + da.abs()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rgb565_layout_has_red_in_high_bits() {
+        let red = Rgba::<f32>::new(1., 0., 0., 1.).to_rgb_565().rgb565;
+        let green = Rgba::<f32>::new(0., 1., 0., 1.).to_rgb_565().rgb565;
+        let blue = Rgba::<f32>::new(0., 0., 1., 1.).to_rgb_565().rgb565;
+        assert_eq!((red, green, blue), (0xF800, 0x07E0, 0x001F));
+        assert_eq!(Rgba::<u8>::new(255, 0, 0, 255).to_rgb_565().rgb565, 0xF800);
+        let one = f16::from_f32(1.);
+        let zero = f16::from_f32(0.);
+        assert_eq!(
+            Rgba::<f16>::new(zero, one, zero, one).to_rgb_565().rgb565,
+            0x07E0
+        );
+
+        let decoded = Rgb565::new(0xF800).to_rgba_f16();
+        assert_eq!(
+            (decoded.r.to_f32(), decoded.g.to_f32(), decoded.b.to_f32()),
+            (1., 0., 0.)
+        );
+        let decoded = Rgb565::new(0x001F).to_rgba8();
+        assert_eq!((decoded.r, decoded.g, decoded.b), (0, 0, 248));
+    }
+
+    #[test]
+    fn rgba1010102_layout_has_red_in_low_bits() {
+        assert_eq!(
+            Rgba::<u8>::new(255, 0, 0, 255).to_rgba1010102().rgba,
+            0xC000_03FC
+        );
+        assert_eq!(
+            Rgba::<u8>::new(0, 0, 255, 0).to_rgba1010102().rgba,
+            0x3FC0_0000
+        );
+        let one = f16::from_f32(1.);
+        let zero = f16::from_f32(0.);
+        assert_eq!(
+            Rgba::<f16>::new(one, zero, zero, one).to_rgba1010102().rgba,
+            0xC000_03FF
+        );
+        assert_eq!(
+            Rgba::<f16>::new(zero, one, zero, zero)
+                .to_rgba1010102()
+                .rgba,
+            0x000F_FC00
+        );
+
+        let decoded = Rgba1010102::new(0xC000_03FF).to_rgba8();
+        assert_eq!(
+            (decoded.r, decoded.g, decoded.b, decoded.a),
+            (255, 0, 0, 255)
+        );
+    }
+
+    #[test]
+    fn rgba1010102_alpha_spans_full_range() {
+        for (code, alpha8) in [(0u32, 0u8), (1, 85), (2, 170), (3, 255)] {
+            let decoded = Rgba1010102::new(code << 30).to_rgba8();
+            assert_eq!(decoded.a, alpha8);
+            let decoded = Rgba1010102::new(code << 30).to_rgba_f16();
+            assert_eq!(decoded.a.to_f32(), f16::from_f32(code as f32 / 3.).to_f32());
+            let encoded = Rgba::<u8>::new(0, 0, 0, alpha8).to_rgba1010102();
+            assert_eq!(encoded.rgba >> 30, code);
+        }
+        let rounded = [
+            (42u8, 0u32),
+            (43, 1),
+            (127, 1),
+            (128, 2),
+            (212, 2),
+            (213, 3),
+        ];
+        for (alpha8, code) in rounded {
+            let encoded = Rgba::<u8>::new(0, 0, 0, alpha8).to_rgba1010102();
+            assert_eq!(encoded.rgba >> 30, code, "alpha {alpha8}");
+        }
+    }
+}
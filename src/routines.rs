@@ -302,3 +302,8 @@ pub(crate) fn op_hard_light(a: f32, b: f32) -> f32 {
 pub(crate) fn op_soft_light(a: f32, b: f32) -> f32 {
     color_soft_light!(a, b)
 }
+
+#[inline]
+pub(crate) fn op_multiply(a: f32, b: f32) -> f32 {
+    a * b
+}
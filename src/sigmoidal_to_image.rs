@@ -12,15 +12,16 @@ use crate::image::ImageConfiguration;
 use crate::neon::neon_from_sigmoidal_row;
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::sse::sse_from_sigmoidal_row;
+use crate::threading::ForEachRow;
 use crate::{Rgb, Sigmoidal};
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 use std::slice;
 
 #[allow(clippy::type_complexity)]
-fn sigmoidal_to_image<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
+pub(crate) fn sigmoidal_to_image<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
     src: &[f32],
     src_stride: u32,
     dst: &mut [u8],
@@ -74,7 +75,7 @@ fn sigmoidal_to_image<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool>(
             .zip(src_slice_safe_align.chunks_exact(src_stride as usize));
     }
 
-    iter.for_each(|(dst, src)| unsafe {
+    iter.for_each_row(width as usize, |(dst, src)| unsafe {
         let mut _cx = 0usize;
 
         let src_ptr = src.as_ptr() as *const f32;
@@ -0,0 +1,77 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+use crate::blend::BlendMode;
+
+#[inline(always)]
+unsafe fn sse_blend_function<const MODE: u8>(backdrop: __m128, source: __m128) -> __m128 {
+    let mode: BlendMode = MODE.into();
+    match mode {
+        BlendMode::Multiply => _mm_mul_ps(backdrop, source),
+        BlendMode::Screen => _mm_sub_ps(_mm_add_ps(backdrop, source), _mm_mul_ps(backdrop, source)),
+        BlendMode::Darken => _mm_min_ps(backdrop, source),
+        BlendMode::Lighten => _mm_max_ps(backdrop, source),
+        BlendMode::Difference => _mm_andnot_ps(_mm_set1_ps(-0.), _mm_sub_ps(backdrop, source)),
+        BlendMode::Exclusion => _mm_sub_ps(
+            _mm_add_ps(backdrop, source),
+            _mm_mul_ps(_mm_set1_ps(2.), _mm_mul_ps(backdrop, source)),
+        ),
+        BlendMode::LinearBurn => _mm_max_ps(
+            _mm_sub_ps(_mm_add_ps(backdrop, source), _mm_set1_ps(1.)),
+            _mm_setzero_ps(),
+        ),
+        _ => source,
+    }
+}
+
+#[inline(always)]
+unsafe fn sse_composite_pixel<const MODE: u8>(source: __m128, backdrop: __m128) -> __m128 {
+    let source_alpha = _mm_shuffle_ps::<0xFF>(source, source);
+    let backdrop_alpha = _mm_shuffle_ps::<0xFF>(backdrop, backdrop);
+    let both = _mm_mul_ps(source_alpha, backdrop_alpha);
+    let alpha = _mm_sub_ps(_mm_add_ps(source_alpha, backdrop_alpha), both);
+    let blended = sse_blend_function::<MODE>(backdrop, source);
+    let color = _mm_add_ps(
+        _mm_add_ps(
+            _mm_mul_ps(source, _mm_sub_ps(source_alpha, both)),
+            _mm_mul_ps(backdrop, _mm_sub_ps(backdrop_alpha, both)),
+        ),
+        _mm_mul_ps(blended, both),
+    );
+    let non_zero = _mm_cmpneq_ps(alpha, _mm_setzero_ps());
+    let color = _mm_and_ps(_mm_div_ps(color, alpha), non_zero);
+    _mm_blend_ps::<0b1000>(color, alpha)
+}
+
+/// Composites RGBA f32 rows with separable blend mode, result is written into backdrop
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_blend_row<const MODE: u8>(
+    start_cx: usize,
+    source: *const f32,
+    backdrop: *mut f32,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+
+    while cx + 2 < width {
+        let src_ptr = source.add(cx * 4);
+        let dst_ptr = backdrop.add(cx * 4);
+        let result0 = sse_composite_pixel::<MODE>(_mm_loadu_ps(src_ptr), _mm_loadu_ps(dst_ptr));
+        let result1 =
+            sse_composite_pixel::<MODE>(_mm_loadu_ps(src_ptr.add(4)), _mm_loadu_ps(dst_ptr.add(4)));
+        _mm_storeu_ps(dst_ptr, result0);
+        _mm_storeu_ps(dst_ptr.add(4), result1);
+        cx += 2;
+    }
+
+    cx
+}
@@ -0,0 +1,102 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::color_adjustments::{evaluate_lightness_table, AdjustmentParameters};
+use crate::image::ImageConfiguration;
+use crate::sse::image_to_oklab::sse_linear_rgb_to_oklab;
+use crate::sse::oklab_to_image::sse_oklab_to_linear_rgb;
+use crate::sse::{
+    _mm_prefer_fma_ps, sse_deinterleave_rgb_ps, sse_deinterleave_rgba_ps, sse_interleave_ps_rgb,
+    sse_interleave_ps_rgba,
+};
+use crate::{load_f32_and_deinterleave, store_and_interleave_v4_f32};
+use erydanos::_mm_hypot_fast_ps;
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+#[inline(always)]
+unsafe fn sse_evaluate_lightness(table: &[f32], lightness: __m128) -> __m128 {
+    let mut lanes = [0f32; 4];
+    _mm_storeu_ps(lanes.as_mut_ptr(), lightness);
+    for lane in lanes.iter_mut() {
+        *lane = evaluate_lightness_table(table, *lane);
+    }
+    _mm_loadu_ps(lanes.as_ptr())
+}
+
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_adjust_colors_row<const CHANNELS_CONFIGURATION: u8>(
+    start_cx: usize,
+    width: u32,
+    row: *mut f32,
+    parameters: &AdjustmentParameters,
+) -> usize {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+    let mut cx = start_cx;
+
+    let exposure_gain = _mm_set1_ps(parameters.exposure_gain);
+    let hue_cos = _mm_set1_ps(parameters.hue_cos);
+    let hue_sin = _mm_set1_ps(parameters.hue_sin);
+    let chroma = _mm_set1_ps(parameters.chroma);
+    let vibrance = _mm_set1_ps(parameters.vibrance);
+    let recip_reference_chroma = _mm_set1_ps(parameters.recip_reference_chroma);
+    let ones = _mm_set1_ps(1f32);
+    let zeros = _mm_setzero_ps();
+
+    while cx + 4 < width as usize {
+        let in_place_ptr = row.add(cx * channels);
+        let (r_chan, g_chan, b_chan, a_chan) =
+            load_f32_and_deinterleave!(in_place_ptr, image_configuration);
+
+        let r_chan = _mm_mul_ps(r_chan, exposure_gain);
+        let g_chan = _mm_mul_ps(g_chan, exposure_gain);
+        let b_chan = _mm_mul_ps(b_chan, exposure_gain);
+
+        let (mut lightness, a, b) = sse_linear_rgb_to_oklab(r_chan, g_chan, b_chan);
+
+        if let Some(table) = parameters.lightness {
+            lightness = sse_evaluate_lightness(table, lightness);
+        }
+
+        let mut scale = chroma;
+        if parameters.vibrance != 0. {
+            let saturation = _mm_min_ps(
+                _mm_mul_ps(_mm_hypot_fast_ps(a, b), recip_reference_chroma),
+                ones,
+            );
+            let remainder = _mm_sub_ps(ones, saturation);
+            let boost = _mm_max_ps(
+                _mm_prefer_fma_ps(ones, vibrance, _mm_mul_ps(remainder, remainder)),
+                zeros,
+            );
+            scale = _mm_mul_ps(scale, boost);
+        }
+
+        let rotated_a = _mm_mul_ps(
+            _mm_sub_ps(_mm_mul_ps(a, hue_cos), _mm_mul_ps(b, hue_sin)),
+            scale,
+        );
+        let rotated_b = _mm_mul_ps(_mm_prefer_fma_ps(_mm_mul_ps(b, hue_cos), a, hue_sin), scale);
+
+        let (r_chan, g_chan, b_chan) = sse_oklab_to_linear_rgb(lightness, rotated_a, rotated_b);
+
+        store_and_interleave_v4_f32!(
+            in_place_ptr,
+            image_configuration,
+            r_chan,
+            g_chan,
+            b_chan,
+            a_chan
+        );
+
+        cx += 4;
+    }
+
+    cx
+}
@@ -127,6 +127,19 @@ pub unsafe fn sse_from_sigmoidal_row<const CHANNELS_CONFIGURATION: u8>(
             ImageConfiguration::Bgr => {
                 store_and_interleave_v3_u8!(dst_ptr, image_configuration, b_row, g_row, r_row);
             }
+            ImageConfiguration::Argb | ImageConfiguration::Abgr => {
+                store_and_interleave_v4_u8!(
+                    dst_ptr,
+                    image_configuration,
+                    r_row,
+                    g_row,
+                    b_row,
+                    a_row
+                );
+            }
+            ImageConfiguration::Gray | ImageConfiguration::GrayAlpha => {
+                unreachable!("Gray layouts are not supported by colour kernels")
+            }
         }
         cx += 16;
     }
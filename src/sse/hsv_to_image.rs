@@ -53,22 +53,19 @@ pub unsafe fn sse_hsv_u16_to_image<
         let row1 = _mm_loadu_si128(src_ptr.add(8) as *const __m128i);
         let row2 = _mm_loadu_si128(src_ptr.add(16) as *const __m128i);
 
-        match image_configuration {
-            ImageConfiguration::Rgb | ImageConfiguration::Bgr => {
-                let (h_c, s_c, v_c) = sse_deinterleave_rgb_epi16(row0, row1, row2);
-                h_chan = h_c;
-                s_chan = s_c;
-                v_chan = v_c;
-                a_chan_lo = _mm_set1_epi16(255);
-            }
-            ImageConfiguration::Rgba | ImageConfiguration::Bgra => {
-                let row3 = _mm_loadu_si128(src_ptr.add(24) as *const __m128i);
-                let (h_c, s_c, v_c, a_c) = sse_deinterleave_rgba_epi16(row0, row1, row2, row3);
-                h_chan = h_c;
-                s_chan = s_c;
-                v_chan = v_c;
-                a_chan_lo = a_c;
-            }
+        if image_configuration.has_alpha() {
+            let row3 = _mm_loadu_si128(src_ptr.add(24) as *const __m128i);
+            let (h_c, s_c, v_c, a_c) = sse_deinterleave_rgba_epi16(row0, row1, row2, row3);
+            h_chan = h_c;
+            s_chan = s_c;
+            v_chan = v_c;
+            a_chan_lo = a_c;
+        } else {
+            let (h_c, s_c, v_c) = sse_deinterleave_rgb_epi16(row0, row1, row2);
+            h_chan = h_c;
+            s_chan = s_c;
+            v_chan = v_c;
+            a_chan_lo = _mm_set1_epi16(255);
         }
 
         let h_low = _mm_cvtepi32_ps(_mm_cvtepu16_epi32(h_chan));
@@ -103,22 +100,19 @@ pub unsafe fn sse_hsv_u16_to_image<
         let row1 = _mm_loadu_si128(src_ptr.add(8) as *const __m128i);
         let row2 = _mm_loadu_si128(src_ptr.add(16) as *const __m128i);
 
-        match image_configuration {
-            ImageConfiguration::Rgb | ImageConfiguration::Bgr => {
-                let (h_c, s_c, v_c) = sse_deinterleave_rgb_epi16(row0, row1, row2);
-                h_chan = h_c;
-                s_chan = s_c;
-                v_chan = v_c;
-                a_chan_hi = _mm_set1_epi16(255);
-            }
-            ImageConfiguration::Rgba | ImageConfiguration::Bgra => {
-                let row3 = _mm_loadu_si128(src_ptr.add(24) as *const __m128i);
-                let (h_c, s_c, v_c, a_c) = sse_deinterleave_rgba_epi16(row0, row1, row2, row3);
-                h_chan = h_c;
-                s_chan = s_c;
-                v_chan = v_c;
-                a_chan_hi = a_c;
-            }
+        if image_configuration.has_alpha() {
+            let row3 = _mm_loadu_si128(src_ptr.add(24) as *const __m128i);
+            let (h_c, s_c, v_c, a_c) = sse_deinterleave_rgba_epi16(row0, row1, row2, row3);
+            h_chan = h_c;
+            s_chan = s_c;
+            v_chan = v_c;
+            a_chan_hi = a_c;
+        } else {
+            let (h_c, s_c, v_c) = sse_deinterleave_rgb_epi16(row0, row1, row2);
+            h_chan = h_c;
+            s_chan = s_c;
+            v_chan = v_c;
+            a_chan_hi = _mm_set1_epi16(255);
         }
 
         let h_low = _mm_cvtepi32_ps(_mm_cvtepu16_epi32(h_chan));
@@ -152,26 +146,24 @@ pub unsafe fn sse_hsv_u16_to_image<
         let ptr = dst_ptr.add(cx * channels);
         if USE_ALPHA {
             let a_chan = _mm_packus_epi16(a_chan_lo, a_chan_hi);
-            let (rgba0, rgba1, rgba2, rgba3) = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
-                    sse_interleave_rgba(r_chan, g_chan, b_chan, a_chan)
-                }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
-                    sse_interleave_rgba(b_chan, g_chan, r_chan, a_chan)
-                }
-            };
+            let mut lanes = [a_chan; 4];
+            lanes[image_configuration.get_r_channel_offset()] = r_chan;
+            lanes[image_configuration.get_g_channel_offset()] = g_chan;
+            lanes[image_configuration.get_b_channel_offset()] = b_chan;
+            lanes[image_configuration.get_a_channel_offset()] = a_chan;
+            let (rgba0, rgba1, rgba2, rgba3) =
+                sse_interleave_rgba(lanes[0], lanes[1], lanes[2], lanes[3]);
             _mm_storeu_si128(ptr as *mut __m128i, rgba0);
             _mm_storeu_si128(ptr.add(16) as *mut __m128i, rgba1);
             _mm_storeu_si128(ptr.add(32) as *mut __m128i, rgba2);
             _mm_storeu_si128(ptr.add(48) as *mut __m128i, rgba3);
         } else {
-            let (rgba0, rgba1, rgba2) = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
-                    sse_interleave_rgb(r_chan, g_chan, b_chan)
-                }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
-                    sse_interleave_rgb(b_chan, g_chan, r_chan)
-                }
+            let (rgba0, rgba1, rgba2) = if image_configuration.get_r_channel_offset()
+                < image_configuration.get_b_channel_offset()
+            {
+                sse_interleave_rgb(r_chan, g_chan, b_chan)
+            } else {
+                sse_interleave_rgb(b_chan, g_chan, r_chan)
             };
             _mm_storeu_si128(ptr as *mut __m128i, rgba0);
             _mm_storeu_si128(ptr.add(16) as *mut __m128i, rgba1);
@@ -189,22 +181,19 @@ pub unsafe fn sse_hsv_u16_to_image<
         let row1 = _mm_loadu_si128(src_ptr.add(8) as *const __m128i);
         let row2 = _mm_loadu_si128(src_ptr.add(16) as *const __m128i);
 
-        match image_configuration {
-            ImageConfiguration::Rgb | ImageConfiguration::Bgr => {
-                let (h_c, s_c, v_c) = sse_deinterleave_rgb_epi16(row0, row1, row2);
-                h_chan = h_c;
-                s_chan = s_c;
-                v_chan = v_c;
-                a_chan_lo = _mm_set1_epi16(255);
-            }
-            ImageConfiguration::Rgba | ImageConfiguration::Bgra => {
-                let row3 = _mm_loadu_si128(src_ptr.add(24) as *const __m128i);
-                let (h_c, s_c, v_c, a_c) = sse_deinterleave_rgba_epi16(row0, row1, row2, row3);
-                h_chan = h_c;
-                s_chan = s_c;
-                v_chan = v_c;
-                a_chan_lo = a_c;
-            }
+        if image_configuration.has_alpha() {
+            let row3 = _mm_loadu_si128(src_ptr.add(24) as *const __m128i);
+            let (h_c, s_c, v_c, a_c) = sse_deinterleave_rgba_epi16(row0, row1, row2, row3);
+            h_chan = h_c;
+            s_chan = s_c;
+            v_chan = v_c;
+            a_chan_lo = a_c;
+        } else {
+            let (h_c, s_c, v_c) = sse_deinterleave_rgb_epi16(row0, row1, row2);
+            h_chan = h_c;
+            s_chan = s_c;
+            v_chan = v_c;
+            a_chan_lo = _mm_set1_epi16(255);
         }
 
         let h_low = _mm_cvtepi32_ps(_mm_cvtepu16_epi32(h_chan));
@@ -238,24 +227,21 @@ pub unsafe fn sse_hsv_u16_to_image<
         let ptr = dst_ptr.add(cx * channels);
         if USE_ALPHA {
             let a_chan = _mm_packus_epi16(a_chan_lo, _mm_setzero_si128());
-            let (rgba0, rgba1, _, _) = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
-                    sse_interleave_rgba(r_chan, g_chan, b_chan, a_chan)
-                }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
-                    sse_interleave_rgba(b_chan, g_chan, r_chan, a_chan)
-                }
-            };
+            let mut lanes = [a_chan; 4];
+            lanes[image_configuration.get_r_channel_offset()] = r_chan;
+            lanes[image_configuration.get_g_channel_offset()] = g_chan;
+            lanes[image_configuration.get_b_channel_offset()] = b_chan;
+            lanes[image_configuration.get_a_channel_offset()] = a_chan;
+            let (rgba0, rgba1, _, _) = sse_interleave_rgba(lanes[0], lanes[1], lanes[2], lanes[3]);
             _mm_storeu_si128(ptr as *mut __m128i, rgba0);
             _mm_storeu_si128(ptr.add(16) as *mut __m128i, rgba1);
         } else {
-            let (rgba0, rgba1, _) = match image_configuration {
-                ImageConfiguration::Rgb | ImageConfiguration::Rgba => {
-                    sse_interleave_rgb(r_chan, g_chan, b_chan)
-                }
-                ImageConfiguration::Bgra | ImageConfiguration::Bgr => {
-                    sse_interleave_rgb(b_chan, g_chan, r_chan)
-                }
+            let (rgba0, rgba1, _) = if image_configuration.get_r_channel_offset()
+                < image_configuration.get_b_channel_offset()
+            {
+                sse_interleave_rgb(r_chan, g_chan, b_chan)
+            } else {
+                sse_interleave_rgb(b_chan, g_chan, r_chan)
             };
             _mm_storeu_si128(ptr as *mut __m128i, rgba0);
             std::ptr::copy_nonoverlapping(&rgba1 as *const _ as *const u8, ptr.add(16), 8);
@@ -0,0 +1,228 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+
+use crate::image::ImageConfiguration;
+use crate::sse::*;
+use crate::{load_f32_and_deinterleave, load_u8_and_deinterleave};
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+#[inline(always)]
+unsafe fn sse_luma_ps(
+    r: __m128,
+    g: __m128,
+    b: __m128,
+    weights: (__m128, __m128, __m128),
+) -> __m128 {
+    _mm_add_ps(
+        _mm_add_ps(_mm_mul_ps(r, weights.0), _mm_mul_ps(g, weights.1)),
+        _mm_mul_ps(b, weights.2),
+    )
+}
+
+/// Computes luma of 8 unsigned 16 bit pixels, rounds half up and saturates to `max`
+#[inline(always)]
+unsafe fn sse_luma_epi16(
+    r: __m128i,
+    g: __m128i,
+    b: __m128i,
+    weights: (__m128, __m128, __m128),
+    max: __m128i,
+) -> __m128i {
+    let zeros = _mm_setzero_si128();
+    let half = _mm_set1_ps(0.5f32);
+    let y_low = sse_luma_ps(
+        _mm_cvtepi32_ps(_mm_unpacklo_epi16(r, zeros)),
+        _mm_cvtepi32_ps(_mm_unpacklo_epi16(g, zeros)),
+        _mm_cvtepi32_ps(_mm_unpacklo_epi16(b, zeros)),
+        weights,
+    );
+    let y_high = sse_luma_ps(
+        _mm_cvtepi32_ps(_mm_unpackhi_epi16(r, zeros)),
+        _mm_cvtepi32_ps(_mm_unpackhi_epi16(g, zeros)),
+        _mm_cvtepi32_ps(_mm_unpackhi_epi16(b, zeros)),
+        weights,
+    );
+    let y = _mm_packus_epi32(
+        _mm_cvttps_epi32(_mm_add_ps(y_low, half)),
+        _mm_cvttps_epi32(_mm_add_ps(y_high, half)),
+    );
+    _mm_min_epu16(y, max)
+}
+
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_rgb_to_luma_u8<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    start_cx: usize,
+    src: *const u8,
+    dst: *mut u8,
+    width: usize,
+    weights: [f32; 3],
+    max: u8,
+) -> usize {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let target: ImageConfiguration = TARGET.into();
+    let channels = image_configuration.get_channels_count();
+    let mut cx = start_cx;
+
+    let v_weights = (
+        _mm_set1_ps(weights[0]),
+        _mm_set1_ps(weights[1]),
+        _mm_set1_ps(weights[2]),
+    );
+    let v_max = _mm_set1_epi16(max as i16);
+    let v_fill = _mm_set1_epi8(max as i8);
+    let zeros = _mm_setzero_si128();
+
+    while cx + 16 < width {
+        let src_ptr = src.add(cx * channels);
+        let (r_chan, g_chan, b_chan, a_chan) =
+            load_u8_and_deinterleave!(src_ptr, image_configuration);
+
+        let y_low = sse_luma_epi16(
+            _mm_unpacklo_epi8(r_chan, zeros),
+            _mm_unpacklo_epi8(g_chan, zeros),
+            _mm_unpacklo_epi8(b_chan, zeros),
+            v_weights,
+            v_max,
+        );
+        let y_high = sse_luma_epi16(
+            _mm_unpackhi_epi8(r_chan, zeros),
+            _mm_unpackhi_epi8(g_chan, zeros),
+            _mm_unpackhi_epi8(b_chan, zeros),
+            v_weights,
+            v_max,
+        );
+        let y = _mm_packus_epi16(y_low, y_high);
+
+        if target.has_alpha() {
+            let a = if image_configuration.has_alpha() {
+                a_chan
+            } else {
+                v_fill
+            };
+            let dst_ptr = dst.add(cx * 2);
+            _mm_storeu_si128(dst_ptr as *mut __m128i, _mm_unpacklo_epi8(y, a));
+            _mm_storeu_si128(dst_ptr.add(16) as *mut __m128i, _mm_unpackhi_epi8(y, a));
+        } else {
+            _mm_storeu_si128(dst.add(cx) as *mut __m128i, y);
+        }
+
+        cx += 16;
+    }
+
+    cx
+}
+
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_rgb_to_luma_u16<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    start_cx: usize,
+    src: *const u16,
+    dst: *mut u16,
+    width: usize,
+    weights: [f32; 3],
+    max: u16,
+) -> usize {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let target: ImageConfiguration = TARGET.into();
+    let channels = image_configuration.get_channels_count();
+    let mut cx = start_cx;
+
+    let v_weights = (
+        _mm_set1_ps(weights[0]),
+        _mm_set1_ps(weights[1]),
+        _mm_set1_ps(weights[2]),
+    );
+    let v_max = _mm_set1_epi16(max as i16);
+
+    while cx + 8 < width {
+        let src_ptr = src.add(cx * channels);
+        let row0 = _mm_loadu_si128(src_ptr as *const __m128i);
+        let row1 = _mm_loadu_si128(src_ptr.add(8) as *const __m128i);
+        let row2 = _mm_loadu_si128(src_ptr.add(16) as *const __m128i);
+        let pixels = if image_configuration.has_alpha() {
+            let row3 = _mm_loadu_si128(src_ptr.add(24) as *const __m128i);
+            let (c0, c1, c2, c3) = sse_deinterleave_rgba_epi16(row0, row1, row2, row3);
+            [c0, c1, c2, c3]
+        } else {
+            let (c0, c1, c2) = sse_deinterleave_rgb_epi16(row0, row1, row2);
+            [c0, c1, c2, c2]
+        };
+
+        let y = sse_luma_epi16(
+            pixels[image_configuration.get_r_channel_offset()],
+            pixels[image_configuration.get_g_channel_offset()],
+            pixels[image_configuration.get_b_channel_offset()],
+            v_weights,
+            v_max,
+        );
+
+        if target.has_alpha() {
+            let a = if image_configuration.has_alpha() {
+                pixels[image_configuration.get_a_channel_offset()]
+            } else {
+                v_max
+            };
+            let dst_ptr = dst.add(cx * 2);
+            _mm_storeu_si128(dst_ptr as *mut __m128i, _mm_unpacklo_epi16(y, a));
+            _mm_storeu_si128(dst_ptr.add(8) as *mut __m128i, _mm_unpackhi_epi16(y, a));
+        } else {
+            _mm_storeu_si128(dst.add(cx) as *mut __m128i, y);
+        }
+
+        cx += 8;
+    }
+
+    cx
+}
+
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_rgb_to_luma_f32<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    start_cx: usize,
+    src: *const f32,
+    dst: *mut f32,
+    width: usize,
+    weights: [f32; 3],
+    max: f32,
+) -> usize {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let target: ImageConfiguration = TARGET.into();
+    let channels = image_configuration.get_channels_count();
+    let mut cx = start_cx;
+
+    let v_weights = (
+        _mm_set1_ps(weights[0]),
+        _mm_set1_ps(weights[1]),
+        _mm_set1_ps(weights[2]),
+    );
+    let v_fill = _mm_set1_ps(max);
+
+    while cx + 4 < width {
+        let src_ptr = src.add(cx * channels);
+        let (r_f32, g_f32, b_f32, a_f32) = load_f32_and_deinterleave!(src_ptr, image_configuration);
+
+        let y = sse_luma_ps(r_f32, g_f32, b_f32, v_weights);
+
+        if target.has_alpha() {
+            let a = if image_configuration.has_alpha() {
+                a_f32
+            } else {
+                v_fill
+            };
+            let dst_ptr = dst.add(cx * 2);
+            _mm_storeu_ps(dst_ptr, _mm_unpacklo_ps(y, a));
+            _mm_storeu_ps(dst_ptr.add(4), _mm_unpackhi_ps(y, a));
+        } else {
+            _mm_storeu_ps(dst.add(cx), y);
+        }
+
+        cx += 4;
+    }
+
+    cx
+}
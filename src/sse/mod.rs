@@ -22,27 +22,44 @@ mod to_xyza_laba;
 
 mod xyz_lab_to_image;
 
+mod blend;
 mod cie;
+mod color_adjustments;
 mod from_sigmoidal;
 mod image_to_jzazbz;
 mod image_to_oklab;
 mod jzazbz_to_image;
+mod luma;
 mod oklab_to_image;
+mod packed;
+mod planar;
+mod premultiply;
 mod routines;
 mod sigmoidal;
+mod swizzle;
 mod to_sigmoidal;
 mod xyza_laba_to_image;
 
+pub use blend::sse_blend_row;
 pub use cie::*;
+pub use color_adjustments::sse_adjust_colors_row;
 pub use from_sigmoidal::sse_from_sigmoidal_row;
 pub use hsv_to_image::*;
 pub use image_to_hsv::*;
 pub use image_to_jzazbz::sse_image_to_jzazbz;
 pub use image_to_oklab::sse_image_to_oklab;
 pub use jzazbz_to_image::sse_jzazbz_to_image;
+pub use luma::{sse_rgb_to_luma_f32, sse_rgb_to_luma_u16, sse_rgb_to_luma_u8};
 pub use math::*;
 pub use oklab_to_image::sse_oklab_to_image;
+pub use packed::{sse_pack_row, sse_unpack_row};
+pub use planar::{
+    sse_deinterleave_planes_f32, sse_deinterleave_planes_u8, sse_interleave_planes_f32,
+    sse_interleave_planes_u8,
+};
+pub use premultiply::*;
 pub use support::*;
+pub use swizzle::sse_swizzle_row;
 pub use to_sigmoidal::sse_image_to_sigmoidal_row;
 pub use to_xyz_lab::*;
 pub use to_xyza_laba::*;
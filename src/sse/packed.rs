@@ -0,0 +1,413 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+
+use crate::image::ImageConfiguration;
+use crate::packed::PackedFormat;
+use crate::sse::*;
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+#[inline(always)]
+unsafe fn sse_swap_bytes(v: __m128i, bytes_per_word: usize) -> __m128i {
+    let shuffle = if bytes_per_word == 2 {
+        _mm_setr_epi8(1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14)
+    } else {
+        _mm_setr_epi8(3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12)
+    };
+    _mm_shuffle_epi8(v, shuffle)
+}
+
+/// Converts unsigned IEEE half float bits stored in 32 bit lanes into floats, sign is taken from bit 15
+#[inline(always)]
+unsafe fn sse_half_to_float(h: __m128i) -> __m128 {
+    let shifted_exp = _mm_set1_epi32(0x7c00 << 13);
+    let o = _mm_slli_epi32::<13>(_mm_and_si128(h, _mm_set1_epi32(0x7fff)));
+    let exp = _mm_and_si128(o, shifted_exp);
+    let o = _mm_add_epi32(o, _mm_set1_epi32((127 - 15) << 23));
+    let is_inf_nan = _mm_cmpeq_epi32(exp, shifted_exp);
+    let o = _mm_add_epi32(
+        o,
+        _mm_and_si128(is_inf_nan, _mm_set1_epi32((128 - 16) << 23)),
+    );
+    let is_denormal = _mm_cmpeq_epi32(exp, _mm_setzero_si128());
+    let denormal = _mm_sub_ps(
+        _mm_castsi128_ps(_mm_add_epi32(o, _mm_set1_epi32(1 << 23))),
+        _mm_castsi128_ps(_mm_set1_epi32(113 << 23)),
+    );
+    let o = _mm_select_si128(is_denormal, _mm_castps_si128(denormal), o);
+    let sign = _mm_slli_epi32::<16>(_mm_and_si128(h, _mm_set1_epi32(0x8000)));
+    _mm_castsi128_ps(_mm_or_si128(o, sign))
+}
+
+/// Converts floats into IEEE half float bits in 32 bit lanes with rounding to nearest even
+#[inline(always)]
+unsafe fn sse_float_to_half(v: __m128) -> __m128i {
+    let bits = _mm_castps_si128(v);
+    let sign = _mm_and_si128(bits, _mm_set1_epi32(0x80000000u32 as i32));
+    let abs = _mm_xor_si128(bits, sign);
+    let is_nan = _mm_cmpgt_epi32(abs, _mm_set1_epi32(255 << 23));
+    let is_regular = _mm_cmpgt_epi32(_mm_set1_epi32((127 + 16) << 23), abs);
+    let inf_or_nan = _mm_or_si128(
+        _mm_and_si128(is_nan, _mm_set1_epi32(0x200)),
+        _mm_set1_epi32(0x7c00),
+    );
+    let is_subnormal = _mm_cmpgt_epi32(_mm_set1_epi32((127 - 14) << 23), abs);
+    let subnormal_magic = _mm_set1_epi32(((127 - 15) + (23 - 10) + 1) << 23);
+    let subnormal = _mm_sub_epi32(
+        _mm_castps_si128(_mm_add_ps(
+            _mm_castsi128_ps(abs),
+            _mm_castsi128_ps(subnormal_magic),
+        )),
+        subnormal_magic,
+    );
+    let odd = _mm_srai_epi32::<31>(_mm_slli_epi32::<{ 31 - 13 }>(abs));
+    let normal = _mm_srli_epi32::<13>(_mm_sub_epi32(
+        _mm_add_epi32(abs, _mm_set1_epi32(0xfff - ((127 - 15) << 23))),
+        odd,
+    ));
+    let finite = _mm_select_si128(is_subnormal, subnormal, normal);
+    let joined = _mm_select_si128(is_regular, finite, inf_or_nan);
+    _mm_or_si128(joined, _mm_srli_epi32::<16>(sign))
+}
+
+/// Encodes non negative floats into unsigned floats with 5 bits exponent and `MANTISSA` bits mantissa,
+/// NaN and negative values become zero, values above maximum are saturated
+#[inline(always)]
+unsafe fn sse_float_to_small_float<const MANTISSA: i32>(v: __m128) -> __m128i {
+    let max_value = ((1 << (MANTISSA + 1)) - 1) as f32 * (1 << (15 - MANTISSA)) as f32;
+    let v = _mm_min_ps(_mm_max_ps(v, _mm_setzero_ps()), _mm_set1_ps(max_value));
+    let abs = _mm_castps_si128(v);
+    let is_subnormal = _mm_cmpgt_epi32(_mm_set1_epi32((127 - 14) << 23), abs);
+    let subnormal_magic = _mm_set1_epi32((136 - MANTISSA) << 23);
+    let subnormal = _mm_sub_epi32(
+        _mm_castps_si128(_mm_add_ps(v, _mm_castsi128_ps(subnormal_magic))),
+        subnormal_magic,
+    );
+    let shift = _mm_cvtsi32_si128(23 - MANTISSA);
+    let odd = _mm_and_si128(_mm_srl_epi32(abs, shift), _mm_set1_epi32(1));
+    let normal = _mm_srl_epi32(
+        _mm_add_epi32(
+            _mm_add_epi32(
+                abs,
+                _mm_set1_epi32(((1 << (22 - MANTISSA)) - 1) - ((127 - 15) << 23)),
+            ),
+            odd,
+        ),
+        shift,
+    );
+    _mm_select_si128(is_subnormal, subnormal, normal)
+}
+
+/// Clamps normalized value into [0, 1], scales by `max` and rounds half up
+#[inline(always)]
+unsafe fn sse_quantize_unorm(v: __m128, max: f32) -> __m128i {
+    let clamped = _mm_min_ps(_mm_max_ps(v, _mm_setzero_ps()), _mm_set1_ps(1f32));
+    _mm_cvttps_epi32(_mm_add_ps(
+        _mm_mul_ps(clamped, _mm_set1_ps(max)),
+        _mm_set1_ps(0.5f32),
+    ))
+}
+
+#[inline(always)]
+unsafe fn sse_unorm_to_float(v: __m128i, max: f32) -> __m128 {
+    _mm_div_ps(_mm_cvtepi32_ps(v), _mm_set1_ps(max))
+}
+
+/// Scales value by 2^(15 + 9 - `exponent`) and rounds half up into 9 bits mantissa of RGB9E5
+#[inline(always)]
+unsafe fn sse_shared_mantissa(v: __m128, exponent: __m128i) -> __m128i {
+    let scale = _mm_castsi128_ps(_mm_slli_epi32::<23>(_mm_sub_epi32(
+        _mm_set1_epi32(127 + 15 + 9),
+        exponent,
+    )));
+    // Adding 0.5 before truncation would round values just below one half up
+    let scaled = _mm_mul_ps(v, scale);
+    let floor = _mm_floor_ps(scaled);
+    let round_up = _mm_cmpge_ps(_mm_sub_ps(scaled, floor), _mm_set1_ps(0.5f32));
+    _mm_cvttps_epi32(_mm_add_ps(floor, _mm_and_ps(round_up, _mm_set1_ps(1f32))))
+}
+
+/// Loads 4 packed pixels and decodes them into R, G, B and A
+#[inline(always)]
+unsafe fn sse_unpack_pixels<const FORMAT: u8>(
+    src: *const u8,
+    big_endian: bool,
+) -> (__m128, __m128, __m128, __m128) {
+    let format: PackedFormat = FORMAT.into();
+    let ones = _mm_set1_ps(1f32);
+    match format {
+        PackedFormat::Rgb565 => {
+            let mut words = _mm_loadl_epi64(src as *const __m128i);
+            if big_endian {
+                words = sse_swap_bytes(words, 2);
+            }
+            let words = _mm_cvtepu16_epi32(words);
+            let r = _mm_srli_epi32::<11>(words);
+            let g = _mm_and_si128(_mm_srli_epi32::<5>(words), _mm_set1_epi32(0x3f));
+            let b = _mm_and_si128(words, _mm_set1_epi32(0x1f));
+            (
+                sse_unorm_to_float(r, 31f32),
+                sse_unorm_to_float(g, 63f32),
+                sse_unorm_to_float(b, 31f32),
+                ones,
+            )
+        }
+        PackedFormat::Rgba1010102 => {
+            let mut words = _mm_loadu_si128(src as *const __m128i);
+            if big_endian {
+                words = sse_swap_bytes(words, 4);
+            }
+            let mask = _mm_set1_epi32(0x3ff);
+            let r = _mm_and_si128(words, mask);
+            let g = _mm_and_si128(_mm_srli_epi32::<10>(words), mask);
+            let b = _mm_and_si128(_mm_srli_epi32::<20>(words), mask);
+            let a = _mm_srli_epi32::<30>(words);
+            (
+                sse_unorm_to_float(r, 1023f32),
+                sse_unorm_to_float(g, 1023f32),
+                sse_unorm_to_float(b, 1023f32),
+                sse_unorm_to_float(a, 3f32),
+            )
+        }
+        PackedFormat::RgbaF16 => {
+            let mut lo = _mm_loadu_si128(src as *const __m128i);
+            let mut hi = _mm_loadu_si128(src.add(16) as *const __m128i);
+            if big_endian {
+                lo = sse_swap_bytes(lo, 2);
+                hi = sse_swap_bytes(hi, 2);
+            }
+            let zeros = _mm_setzero_si128();
+            let p0 = sse_half_to_float(_mm_unpacklo_epi16(lo, zeros));
+            let p1 = sse_half_to_float(_mm_unpackhi_epi16(lo, zeros));
+            let p2 = sse_half_to_float(_mm_unpacklo_epi16(hi, zeros));
+            let p3 = sse_half_to_float(_mm_unpackhi_epi16(hi, zeros));
+            sse_deinterleave_rgba_ps(p0, p1, p2, p3)
+        }
+        PackedFormat::R11G11B10F => {
+            let mut words = _mm_loadu_si128(src as *const __m128i);
+            if big_endian {
+                words = sse_swap_bytes(words, 4);
+            }
+            let mask = _mm_set1_epi32(0x7ff);
+            let r = _mm_slli_epi32::<4>(_mm_and_si128(words, mask));
+            let g = _mm_slli_epi32::<4>(_mm_and_si128(_mm_srli_epi32::<11>(words), mask));
+            let b = _mm_slli_epi32::<5>(_mm_srli_epi32::<22>(words));
+            (
+                sse_half_to_float(r),
+                sse_half_to_float(g),
+                sse_half_to_float(b),
+                ones,
+            )
+        }
+        PackedFormat::Rgb9E5 => {
+            let mut words = _mm_loadu_si128(src as *const __m128i);
+            if big_endian {
+                words = sse_swap_bytes(words, 4);
+            }
+            let mask = _mm_set1_epi32(0x1ff);
+            let r = _mm_and_si128(words, mask);
+            let g = _mm_and_si128(_mm_srli_epi32::<9>(words), mask);
+            let b = _mm_and_si128(_mm_srli_epi32::<18>(words), mask);
+            let exponent = _mm_srli_epi32::<27>(words);
+            let scale = _mm_castsi128_ps(_mm_slli_epi32::<23>(_mm_add_epi32(
+                exponent,
+                _mm_set1_epi32(127 - 15 - 9),
+            )));
+            (
+                _mm_mul_ps(_mm_cvtepi32_ps(r), scale),
+                _mm_mul_ps(_mm_cvtepi32_ps(g), scale),
+                _mm_mul_ps(_mm_cvtepi32_ps(b), scale),
+                ones,
+            )
+        }
+    }
+}
+
+/// Encodes R, G, B and A of 4 pixels and stores them packed
+#[inline(always)]
+unsafe fn sse_pack_pixels<const FORMAT: u8>(
+    r: __m128,
+    g: __m128,
+    b: __m128,
+    a: __m128,
+    dst: *mut u8,
+    big_endian: bool,
+) {
+    let format: PackedFormat = FORMAT.into();
+    match format {
+        PackedFormat::Rgb565 => {
+            let words = _mm_or_si128(
+                _mm_or_si128(
+                    _mm_slli_epi32::<11>(sse_quantize_unorm(r, 31f32)),
+                    _mm_slli_epi32::<5>(sse_quantize_unorm(g, 63f32)),
+                ),
+                sse_quantize_unorm(b, 31f32),
+            );
+            let mut words = _mm_packus_epi32(words, words);
+            if big_endian {
+                words = sse_swap_bytes(words, 2);
+            }
+            _mm_storel_epi64(dst as *mut __m128i, words);
+        }
+        PackedFormat::Rgba1010102 => {
+            let mut words = _mm_or_si128(
+                _mm_or_si128(
+                    sse_quantize_unorm(r, 1023f32),
+                    _mm_slli_epi32::<10>(sse_quantize_unorm(g, 1023f32)),
+                ),
+                _mm_or_si128(
+                    _mm_slli_epi32::<20>(sse_quantize_unorm(b, 1023f32)),
+                    _mm_slli_epi32::<30>(sse_quantize_unorm(a, 3f32)),
+                ),
+            );
+            if big_endian {
+                words = sse_swap_bytes(words, 4);
+            }
+            _mm_storeu_si128(dst as *mut __m128i, words);
+        }
+        PackedFormat::RgbaF16 => {
+            let (p0, p1, p2, p3) = sse_interleave_ps_rgba(r, g, b, a);
+            let mut lo = _mm_packus_epi32(sse_float_to_half(p0), sse_float_to_half(p1));
+            let mut hi = _mm_packus_epi32(sse_float_to_half(p2), sse_float_to_half(p3));
+            if big_endian {
+                lo = sse_swap_bytes(lo, 2);
+                hi = sse_swap_bytes(hi, 2);
+            }
+            _mm_storeu_si128(dst as *mut __m128i, lo);
+            _mm_storeu_si128(dst.add(16) as *mut __m128i, hi);
+        }
+        PackedFormat::R11G11B10F => {
+            let mut words = _mm_or_si128(
+                _mm_or_si128(
+                    sse_float_to_small_float::<6>(r),
+                    _mm_slli_epi32::<11>(sse_float_to_small_float::<6>(g)),
+                ),
+                _mm_slli_epi32::<22>(sse_float_to_small_float::<5>(b)),
+            );
+            if big_endian {
+                words = sse_swap_bytes(words, 4);
+            }
+            _mm_storeu_si128(dst as *mut __m128i, words);
+        }
+        PackedFormat::Rgb9E5 => {
+            let zeros = _mm_setzero_ps();
+            let max_value = _mm_set1_ps(65408f32);
+            let r = _mm_min_ps(_mm_max_ps(r, zeros), max_value);
+            let g = _mm_min_ps(_mm_max_ps(g, zeros), max_value);
+            let b = _mm_min_ps(_mm_max_ps(b, zeros), max_value);
+            let max_channel = _mm_max_ps(_mm_max_ps(r, g), b);
+            let biased_exponent = _mm_srli_epi32::<23>(_mm_castps_si128(max_channel));
+            let exponent = _mm_max_epi32(
+                _mm_sub_epi32(biased_exponent, _mm_set1_epi32(127 - 16)),
+                _mm_setzero_si128(),
+            );
+            let max_mantissa = sse_shared_mantissa(max_channel, exponent);
+            let overflow = _mm_cmpeq_epi32(max_mantissa, _mm_set1_epi32(512));
+            let exponent = _mm_sub_epi32(exponent, overflow);
+            let mut words = _mm_or_si128(
+                _mm_or_si128(
+                    sse_shared_mantissa(r, exponent),
+                    _mm_slli_epi32::<9>(sse_shared_mantissa(g, exponent)),
+                ),
+                _mm_or_si128(
+                    _mm_slli_epi32::<18>(sse_shared_mantissa(b, exponent)),
+                    _mm_slli_epi32::<27>(exponent),
+                ),
+            );
+            if big_endian {
+                words = sse_swap_bytes(words, 4);
+            }
+            _mm_storeu_si128(dst as *mut __m128i, words);
+        }
+    }
+}
+
+/// Decodes row of packed pixels into normalized floats in the order of `CHANNELS_CONFIGURATION`
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_unpack_row<const FORMAT: u8, const CHANNELS_CONFIGURATION: u8>(
+    start_cx: usize,
+    src: *const u8,
+    dst: *mut f32,
+    width: usize,
+    big_endian: bool,
+) -> usize {
+    let format: PackedFormat = FORMAT.into();
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+    let bytes_per_pixel = format.bytes_per_pixel();
+    let mut cx = start_cx;
+
+    while cx + 4 < width {
+        let (r, g, b, a) = sse_unpack_pixels::<FORMAT>(src.add(cx * bytes_per_pixel), big_endian);
+        let mut v = [r; 4];
+        v[image_configuration.get_r_channel_offset()] = r;
+        v[image_configuration.get_g_channel_offset()] = g;
+        v[image_configuration.get_b_channel_offset()] = b;
+        let dst_ptr = dst.add(cx * channels);
+        if image_configuration.has_alpha() {
+            v[image_configuration.get_a_channel_offset()] = a;
+            let (v0, v1, v2, v3) = sse_interleave_ps_rgba(v[0], v[1], v[2], v[3]);
+            _mm_storeu_ps(dst_ptr, v0);
+            _mm_storeu_ps(dst_ptr.add(4), v1);
+            _mm_storeu_ps(dst_ptr.add(8), v2);
+            _mm_storeu_ps(dst_ptr.add(12), v3);
+        } else {
+            let (v0, v1, v2) = sse_interleave_ps_rgb(v[0], v[1], v[2]);
+            _mm_storeu_ps(dst_ptr, v0);
+            _mm_storeu_ps(dst_ptr.add(4), v1);
+            _mm_storeu_ps(dst_ptr.add(8), v2);
+        }
+        cx += 4;
+    }
+
+    cx
+}
+
+/// Encodes row of normalized floats in the order of `CHANNELS_CONFIGURATION` into packed pixels
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_pack_row<const FORMAT: u8, const CHANNELS_CONFIGURATION: u8>(
+    start_cx: usize,
+    src: *const f32,
+    dst: *mut u8,
+    width: usize,
+    big_endian: bool,
+) -> usize {
+    let format: PackedFormat = FORMAT.into();
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+    let bytes_per_pixel = format.bytes_per_pixel();
+    let mut cx = start_cx;
+
+    while cx + 4 < width {
+        let src_ptr = src.add(cx * channels);
+        let row0 = _mm_loadu_ps(src_ptr);
+        let row1 = _mm_loadu_ps(src_ptr.add(4));
+        let row2 = _mm_loadu_ps(src_ptr.add(8));
+        let (v, a) = if image_configuration.has_alpha() {
+            let row3 = _mm_loadu_ps(src_ptr.add(12));
+            let (v0, v1, v2, v3) = sse_deinterleave_rgba_ps(row0, row1, row2, row3);
+            let v = [v0, v1, v2, v3];
+            (v, v[image_configuration.get_a_channel_offset()])
+        } else {
+            let (v0, v1, v2) = sse_deinterleave_rgb_ps(row0, row1, row2);
+            ([v0, v1, v2, v2], _mm_set1_ps(1f32))
+        };
+        sse_pack_pixels::<FORMAT>(
+            v[image_configuration.get_r_channel_offset()],
+            v[image_configuration.get_g_channel_offset()],
+            v[image_configuration.get_b_channel_offset()],
+            a,
+            dst.add(cx * bytes_per_pixel),
+            big_endian,
+        );
+        cx += 4;
+    }
+
+    cx
+}
@@ -0,0 +1,148 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+
+use crate::sse::*;
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+/// Interleaves 3 or 4 planes of 8 bit samples into one row
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_interleave_planes_u8<const CHANNELS: usize>(
+    start_cx: usize,
+    planes: &[*const u8; 4],
+    dst: *mut u8,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+
+    while cx + 16 < width {
+        let c0 = _mm_loadu_si128(planes[0].add(cx) as *const __m128i);
+        let c1 = _mm_loadu_si128(planes[1].add(cx) as *const __m128i);
+        let c2 = _mm_loadu_si128(planes[2].add(cx) as *const __m128i);
+        let dst_ptr = dst.add(cx * CHANNELS);
+        if CHANNELS == 4 {
+            let c3 = _mm_loadu_si128(planes[3].add(cx) as *const __m128i);
+            let (v0, v1, v2, v3) = sse_interleave_rgba(c0, c1, c2, c3);
+            _mm_storeu_si128(dst_ptr as *mut __m128i, v0);
+            _mm_storeu_si128(dst_ptr.add(16) as *mut __m128i, v1);
+            _mm_storeu_si128(dst_ptr.add(32) as *mut __m128i, v2);
+            _mm_storeu_si128(dst_ptr.add(48) as *mut __m128i, v3);
+        } else {
+            let (v0, v1, v2) = sse_interleave_rgb(c0, c1, c2);
+            _mm_storeu_si128(dst_ptr as *mut __m128i, v0);
+            _mm_storeu_si128(dst_ptr.add(16) as *mut __m128i, v1);
+            _mm_storeu_si128(dst_ptr.add(32) as *mut __m128i, v2);
+        }
+        cx += 16;
+    }
+
+    cx
+}
+
+/// Splits row of 8 bit samples into 3 or 4 planes
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_deinterleave_planes_u8<const CHANNELS: usize>(
+    start_cx: usize,
+    src: *const u8,
+    planes: &[*mut u8; 4],
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+
+    while cx + 16 < width {
+        let src_ptr = src.add(cx * CHANNELS);
+        let row0 = _mm_loadu_si128(src_ptr as *const __m128i);
+        let row1 = _mm_loadu_si128(src_ptr.add(16) as *const __m128i);
+        let row2 = _mm_loadu_si128(src_ptr.add(32) as *const __m128i);
+        if CHANNELS == 4 {
+            let row3 = _mm_loadu_si128(src_ptr.add(48) as *const __m128i);
+            let (c0, c1, c2, c3) = sse_deinterleave_rgba(row0, row1, row2, row3);
+            _mm_storeu_si128(planes[0].add(cx) as *mut __m128i, c0);
+            _mm_storeu_si128(planes[1].add(cx) as *mut __m128i, c1);
+            _mm_storeu_si128(planes[2].add(cx) as *mut __m128i, c2);
+            _mm_storeu_si128(planes[3].add(cx) as *mut __m128i, c3);
+        } else {
+            let (c0, c1, c2) = sse_deinterleave_rgb(row0, row1, row2);
+            _mm_storeu_si128(planes[0].add(cx) as *mut __m128i, c0);
+            _mm_storeu_si128(planes[1].add(cx) as *mut __m128i, c1);
+            _mm_storeu_si128(planes[2].add(cx) as *mut __m128i, c2);
+        }
+        cx += 16;
+    }
+
+    cx
+}
+
+/// Interleaves 3 or 4 planes of 32 bit samples into one row
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_interleave_planes_f32<const CHANNELS: usize>(
+    start_cx: usize,
+    planes: &[*const f32; 4],
+    dst: *mut f32,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+
+    while cx + 4 < width {
+        let c0 = _mm_loadu_ps(planes[0].add(cx));
+        let c1 = _mm_loadu_ps(planes[1].add(cx));
+        let c2 = _mm_loadu_ps(planes[2].add(cx));
+        let dst_ptr = dst.add(cx * CHANNELS);
+        if CHANNELS == 4 {
+            let c3 = _mm_loadu_ps(planes[3].add(cx));
+            let (v0, v1, v2, v3) = sse_interleave_ps_rgba(c0, c1, c2, c3);
+            _mm_storeu_ps(dst_ptr, v0);
+            _mm_storeu_ps(dst_ptr.add(4), v1);
+            _mm_storeu_ps(dst_ptr.add(8), v2);
+            _mm_storeu_ps(dst_ptr.add(12), v3);
+        } else {
+            let (v0, v1, v2) = sse_interleave_ps_rgb(c0, c1, c2);
+            _mm_storeu_ps(dst_ptr, v0);
+            _mm_storeu_ps(dst_ptr.add(4), v1);
+            _mm_storeu_ps(dst_ptr.add(8), v2);
+        }
+        cx += 4;
+    }
+
+    cx
+}
+
+/// Splits row of 32 bit samples into 3 or 4 planes
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_deinterleave_planes_f32<const CHANNELS: usize>(
+    start_cx: usize,
+    src: *const f32,
+    planes: &[*mut f32; 4],
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+
+    while cx + 4 < width {
+        let src_ptr = src.add(cx * CHANNELS);
+        let row0 = _mm_loadu_ps(src_ptr);
+        let row1 = _mm_loadu_ps(src_ptr.add(4));
+        let row2 = _mm_loadu_ps(src_ptr.add(8));
+        if CHANNELS == 4 {
+            let row3 = _mm_loadu_ps(src_ptr.add(12));
+            let (c0, c1, c2, c3) = sse_deinterleave_rgba_ps(row0, row1, row2, row3);
+            _mm_storeu_ps(planes[0].add(cx), c0);
+            _mm_storeu_ps(planes[1].add(cx), c1);
+            _mm_storeu_ps(planes[2].add(cx), c2);
+            _mm_storeu_ps(planes[3].add(cx), c3);
+        } else {
+            let (c0, c1, c2) = sse_deinterleave_rgb_ps(row0, row1, row2);
+            _mm_storeu_ps(planes[0].add(cx), c0);
+            _mm_storeu_ps(planes[1].add(cx), c1);
+            _mm_storeu_ps(planes[2].add(cx), c2);
+        }
+        cx += 4;
+    }
+
+    cx
+}
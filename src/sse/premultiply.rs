@@ -0,0 +1,274 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+#[inline(always)]
+unsafe fn sse_div_by_255_epi16(v: __m128i) -> __m128i {
+    let rounding = _mm_add_epi16(v, _mm_set1_epi16(128));
+    _mm_srli_epi16::<8>(_mm_add_epi16(rounding, _mm_srli_epi16::<8>(rounding)))
+}
+
+#[inline(always)]
+unsafe fn sse_premultiply_two_pixels_u8<const ALPHA_FIRST: bool>(pixels: __m128i) -> __m128i {
+    // Alpha is multiplied by 255 so it stays the same after division
+    let alpha = if ALPHA_FIRST {
+        let alpha_shuffle = _mm_setr_epi8(0, 1, 0, 1, 0, 1, 0, 1, 8, 9, 8, 9, 8, 9, 8, 9);
+        _mm_blend_epi16::<0b00010001>(_mm_shuffle_epi8(pixels, alpha_shuffle), _mm_set1_epi16(255))
+    } else {
+        let alpha_shuffle = _mm_setr_epi8(6, 7, 6, 7, 6, 7, 6, 7, 14, 15, 14, 15, 14, 15, 14, 15);
+        _mm_blend_epi16::<0b10001000>(_mm_shuffle_epi8(pixels, alpha_shuffle), _mm_set1_epi16(255))
+    };
+    sse_div_by_255_epi16(_mm_mullo_epi16(pixels, alpha))
+}
+
+/// Broadcasts alpha lane of the pixel
+#[inline(always)]
+unsafe fn sse_alpha_ps<const ALPHA_FIRST: bool>(pixel: __m128) -> __m128 {
+    if ALPHA_FIRST {
+        _mm_shuffle_ps::<0x00>(pixel, pixel)
+    } else {
+        _mm_shuffle_ps::<0xFF>(pixel, pixel)
+    }
+}
+
+/// Takes alpha lane from `pixel` and colour lanes from `colour`
+#[inline(always)]
+unsafe fn sse_keep_alpha_ps<const ALPHA_FIRST: bool>(colour: __m128, pixel: __m128) -> __m128 {
+    if ALPHA_FIRST {
+        _mm_blend_ps::<0b0001>(colour, pixel)
+    } else {
+        _mm_blend_ps::<0b1000>(colour, pixel)
+    }
+}
+
+/// Premultiplies four channel u8 pixels with alpha first or last, `src` and `dst` may point to the same row
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_premultiply_alpha_row_u8<const ALPHA_FIRST: bool>(
+    start_cx: usize,
+    src: *const u8,
+    dst: *mut u8,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+    let zeros = _mm_setzero_si128();
+
+    while cx + 4 < width {
+        let pixels = _mm_loadu_si128(src.add(cx * 4) as *const __m128i);
+        let low = sse_premultiply_two_pixels_u8::<ALPHA_FIRST>(_mm_unpacklo_epi8(pixels, zeros));
+        let high = sse_premultiply_two_pixels_u8::<ALPHA_FIRST>(_mm_unpackhi_epi8(pixels, zeros));
+        _mm_storeu_si128(dst.add(cx * 4) as *mut __m128i, _mm_packus_epi16(low, high));
+        cx += 4;
+    }
+
+    cx
+}
+
+/// Divides colour by alpha and scales it to `max`, alpha lane is kept, zero alpha produces zero colour
+#[inline(always)]
+unsafe fn sse_unpremultiply_pixel_ps<const ALPHA_FIRST: bool>(
+    pixel: __m128,
+    max: __m128,
+) -> __m128 {
+    let alpha = sse_alpha_ps::<ALPHA_FIRST>(pixel);
+    let non_zero = _mm_cmpneq_ps(alpha, _mm_setzero_ps());
+    let straight = _mm_and_ps(_mm_div_ps(_mm_mul_ps(pixel, max), alpha), non_zero);
+    let rounded = _mm_min_ps(_mm_floor_ps(_mm_add_ps(straight, _mm_set1_ps(0.5))), max);
+    sse_keep_alpha_ps::<ALPHA_FIRST>(rounded, pixel)
+}
+
+/// Multiplies colour by alpha and divides by `max`, alpha lane is kept
+#[inline(always)]
+unsafe fn sse_premultiply_pixel_ps<const ALPHA_FIRST: bool>(pixel: __m128, max: __m128) -> __m128 {
+    let alpha = sse_alpha_ps::<ALPHA_FIRST>(pixel);
+    let premultiplied = _mm_div_ps(_mm_mul_ps(pixel, alpha), max);
+    let rounded = _mm_floor_ps(_mm_add_ps(premultiplied, _mm_set1_ps(0.5)));
+    sse_keep_alpha_ps::<ALPHA_FIRST>(rounded, pixel)
+}
+
+/// Unpremultiplies four channel u8 pixels with alpha first or last, `src` and `dst` may point to the same row
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_unpremultiply_alpha_row_u8<const ALPHA_FIRST: bool>(
+    start_cx: usize,
+    src: *const u8,
+    dst: *mut u8,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+    let max = _mm_set1_ps(255.);
+
+    while cx + 4 < width {
+        let pixels = _mm_loadu_si128(src.add(cx * 4) as *const __m128i);
+        let p0 = _mm_cvtepi32_ps(_mm_cvtepu8_epi32(pixels));
+        let p1 = _mm_cvtepi32_ps(_mm_cvtepu8_epi32(_mm_srli_si128::<4>(pixels)));
+        let p2 = _mm_cvtepi32_ps(_mm_cvtepu8_epi32(_mm_srli_si128::<8>(pixels)));
+        let p3 = _mm_cvtepi32_ps(_mm_cvtepu8_epi32(_mm_srli_si128::<12>(pixels)));
+        let r0 = _mm_cvtps_epi32(sse_unpremultiply_pixel_ps::<ALPHA_FIRST>(p0, max));
+        let r1 = _mm_cvtps_epi32(sse_unpremultiply_pixel_ps::<ALPHA_FIRST>(p1, max));
+        let r2 = _mm_cvtps_epi32(sse_unpremultiply_pixel_ps::<ALPHA_FIRST>(p2, max));
+        let r3 = _mm_cvtps_epi32(sse_unpremultiply_pixel_ps::<ALPHA_FIRST>(p3, max));
+        let packed = _mm_packus_epi16(_mm_packus_epi32(r0, r1), _mm_packus_epi32(r2, r3));
+        _mm_storeu_si128(dst.add(cx * 4) as *mut __m128i, packed);
+        cx += 4;
+    }
+
+    cx
+}
+
+/// Premultiplies four channel u16 pixels with alpha first or last, `src` and `dst` may point to the same row
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_premultiply_alpha_row_u16<const ALPHA_FIRST: bool>(
+    start_cx: usize,
+    src: *const u16,
+    dst: *mut u16,
+    width: usize,
+    max_value: f32,
+) -> usize {
+    let mut cx = start_cx;
+    let max = _mm_set1_ps(max_value);
+
+    while cx + 2 < width {
+        let pixels = _mm_loadu_si128(src.add(cx * 4) as *const __m128i);
+        let p0 = _mm_cvtepi32_ps(_mm_cvtepu16_epi32(pixels));
+        let p1 = _mm_cvtepi32_ps(_mm_cvtepu16_epi32(_mm_srli_si128::<8>(pixels)));
+        let r0 = _mm_cvtps_epi32(sse_premultiply_pixel_ps::<ALPHA_FIRST>(p0, max));
+        let r1 = _mm_cvtps_epi32(sse_premultiply_pixel_ps::<ALPHA_FIRST>(p1, max));
+        _mm_storeu_si128(dst.add(cx * 4) as *mut __m128i, _mm_packus_epi32(r0, r1));
+        cx += 2;
+    }
+
+    cx
+}
+
+/// Unpremultiplies four channel u16 pixels with alpha first or last, `src` and `dst` may point to the same row
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_unpremultiply_alpha_row_u16<const ALPHA_FIRST: bool>(
+    start_cx: usize,
+    src: *const u16,
+    dst: *mut u16,
+    width: usize,
+    max_value: f32,
+) -> usize {
+    let mut cx = start_cx;
+    let max = _mm_set1_ps(max_value);
+
+    while cx + 2 < width {
+        let pixels = _mm_loadu_si128(src.add(cx * 4) as *const __m128i);
+        let p0 = _mm_cvtepi32_ps(_mm_cvtepu16_epi32(pixels));
+        let p1 = _mm_cvtepi32_ps(_mm_cvtepu16_epi32(_mm_srli_si128::<8>(pixels)));
+        let r0 = _mm_cvtps_epi32(sse_unpremultiply_pixel_ps::<ALPHA_FIRST>(p0, max));
+        let r1 = _mm_cvtps_epi32(sse_unpremultiply_pixel_ps::<ALPHA_FIRST>(p1, max));
+        _mm_storeu_si128(dst.add(cx * 4) as *mut __m128i, _mm_packus_epi32(r0, r1));
+        cx += 2;
+    }
+
+    cx
+}
+
+/// Premultiplies four channel f32 pixels with alpha first or last, `src` and `dst` may point to the same row
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_premultiply_alpha_row_f32<const ALPHA_FIRST: bool>(
+    start_cx: usize,
+    src: *const f32,
+    dst: *mut f32,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+    let ones = _mm_set1_ps(1.);
+
+    while cx + 1 < width {
+        let pixel = _mm_loadu_ps(src.add(cx * 4));
+        let alpha = sse_keep_alpha_ps::<ALPHA_FIRST>(sse_alpha_ps::<ALPHA_FIRST>(pixel), ones);
+        _mm_storeu_ps(dst.add(cx * 4), _mm_mul_ps(pixel, alpha));
+        cx += 1;
+    }
+
+    cx
+}
+
+/// Unpremultiplies four channel f32 pixels with alpha first or last, `src` and `dst` may point to the same row
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_unpremultiply_alpha_row_f32<const ALPHA_FIRST: bool>(
+    start_cx: usize,
+    src: *const f32,
+    dst: *mut f32,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+
+    while cx + 1 < width {
+        let pixel = _mm_loadu_ps(src.add(cx * 4));
+        let alpha = sse_alpha_ps::<ALPHA_FIRST>(pixel);
+        let non_zero = _mm_cmpneq_ps(alpha, _mm_setzero_ps());
+        let straight = _mm_and_ps(_mm_div_ps(pixel, alpha), non_zero);
+        _mm_storeu_ps(
+            dst.add(cx * 4),
+            sse_keep_alpha_ps::<ALPHA_FIRST>(straight, pixel),
+        );
+        cx += 1;
+    }
+
+    cx
+}
+
+/// Premultiplies four channel f16 pixels with alpha first or last, `src` and `dst` may point to the same row
+#[target_feature(enable = "sse4.1,f16c")]
+pub unsafe fn sse_premultiply_alpha_row_f16<const ALPHA_FIRST: bool>(
+    start_cx: usize,
+    src: *const u16,
+    dst: *mut u16,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+    let ones = _mm_set1_ps(1.);
+
+    while cx + 2 < width {
+        let pixels = _mm_loadu_si128(src.add(cx * 4) as *const __m128i);
+        let p0 = _mm_cvtph_ps(pixels);
+        let p1 = _mm_cvtph_ps(_mm_srli_si128::<8>(pixels));
+        let a0 = sse_keep_alpha_ps::<ALPHA_FIRST>(sse_alpha_ps::<ALPHA_FIRST>(p0), ones);
+        let a1 = sse_keep_alpha_ps::<ALPHA_FIRST>(sse_alpha_ps::<ALPHA_FIRST>(p1), ones);
+        let r0 = _mm_cvtps_ph::<_MM_FROUND_TO_NEAREST_INT>(_mm_mul_ps(p0, a0));
+        let r1 = _mm_cvtps_ph::<_MM_FROUND_TO_NEAREST_INT>(_mm_mul_ps(p1, a1));
+        _mm_storeu_si128(dst.add(cx * 4) as *mut __m128i, _mm_unpacklo_epi64(r0, r1));
+        cx += 2;
+    }
+
+    cx
+}
+
+/// Unpremultiplies four channel f16 pixels with alpha first or last, `src` and `dst` may point to the same row
+#[target_feature(enable = "sse4.1,f16c")]
+pub unsafe fn sse_unpremultiply_alpha_row_f16<const ALPHA_FIRST: bool>(
+    start_cx: usize,
+    src: *const u16,
+    dst: *mut u16,
+    width: usize,
+) -> usize {
+    let mut cx = start_cx;
+
+    while cx + 2 < width {
+        let pixels = _mm_loadu_si128(src.add(cx * 4) as *const __m128i);
+        let p0 = _mm_cvtph_ps(pixels);
+        let p1 = _mm_cvtph_ps(_mm_srli_si128::<8>(pixels));
+        let a0 = sse_alpha_ps::<ALPHA_FIRST>(p0);
+        let a1 = sse_alpha_ps::<ALPHA_FIRST>(p1);
+        let s0 = _mm_and_ps(_mm_div_ps(p0, a0), _mm_cmpneq_ps(a0, _mm_setzero_ps()));
+        let s1 = _mm_and_ps(_mm_div_ps(p1, a1), _mm_cmpneq_ps(a1, _mm_setzero_ps()));
+        let r0 =
+            _mm_cvtps_ph::<_MM_FROUND_TO_NEAREST_INT>(sse_keep_alpha_ps::<ALPHA_FIRST>(s0, p0));
+        let r1 =
+            _mm_cvtps_ph::<_MM_FROUND_TO_NEAREST_INT>(sse_keep_alpha_ps::<ALPHA_FIRST>(s1, p1));
+        _mm_storeu_si128(dst.add(cx * 4) as *mut __m128i, _mm_unpacklo_epi64(r0, r1));
+        cx += 2;
+    }
+
+    cx
+}
@@ -0,0 +1,41 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+
+use crate::swizzle::SwizzleShuffle;
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+/// Reorders channels of a row block by block, stops when next block doesn't fit into the row
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn sse_swizzle_row(
+    start_cx: usize,
+    src: *const u8,
+    dst: *mut u8,
+    width: usize,
+    shuffle: &SwizzleShuffle,
+) -> usize {
+    let mut cx = start_cx;
+
+    let mask = _mm_loadu_si128(shuffle.mask.as_ptr() as *const __m128i);
+    let fill = _mm_loadu_si128(shuffle.fill.as_ptr() as *const __m128i);
+
+    while (width - cx) * shuffle.src_pixel_bytes >= 16
+        && (width - cx) * shuffle.dst_pixel_bytes >= 16
+    {
+        let pixels = _mm_loadu_si128(src.add(cx * shuffle.src_pixel_bytes) as *const __m128i);
+        let reordered = _mm_or_si128(_mm_shuffle_epi8(pixels, mask), fill);
+        _mm_storeu_si128(
+            dst.add(cx * shuffle.dst_pixel_bytes) as *mut __m128i,
+            reordered,
+        );
+        cx += shuffle.pixels;
+    }
+
+    cx
+}
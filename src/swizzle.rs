@@ -0,0 +1,221 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+use crate::avx::avx_swizzle_row;
+use crate::image::ImageConfiguration;
+use crate::image_view::check_image;
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use crate::neon::neon_swizzle_row;
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+use crate::sse::sse_swizzle_row;
+use crate::threading::ForEachRow;
+use crate::ImageSample;
+#[cfg(feature = "rayon")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "rayon")]
+use rayon::prelude::{ParallelSlice, ParallelSliceMut};
+
+/// Byte shuffle moving channels of a 16 bytes block from one layout into another.
+/// Mask entries with high bit set produce zero byte which is then ORed with `fill`,
+/// this is how missing alpha is filled.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct SwizzleShuffle {
+    pub(crate) mask: [u8; 16],
+    pub(crate) fill: [u8; 16],
+    /// Pixels moved by one block
+    pub(crate) pixels: usize,
+    pub(crate) src_pixel_bytes: usize,
+    pub(crate) dst_pixel_bytes: usize,
+}
+
+pub(crate) type SwizzleRowHandler =
+    unsafe fn(usize, *const u8, *mut u8, usize, &SwizzleShuffle) -> usize;
+
+/// Returns position of source channel for every destination channel, `None` is alpha to fill
+fn source_positions(
+    src_layout: ImageConfiguration,
+    dst_layout: ImageConfiguration,
+) -> [Option<usize>; 4] {
+    let mut positions = [None; 4];
+    positions[dst_layout.get_r_channel_offset()] = Some(src_layout.get_r_channel_offset());
+    positions[dst_layout.get_g_channel_offset()] = Some(src_layout.get_g_channel_offset());
+    positions[dst_layout.get_b_channel_offset()] = Some(src_layout.get_b_channel_offset());
+    if dst_layout.has_alpha() {
+        positions[dst_layout.get_a_channel_offset()] = if src_layout.has_alpha() {
+            Some(src_layout.get_a_channel_offset())
+        } else {
+            None
+        };
+    }
+    positions
+}
+
+impl SwizzleShuffle {
+    pub(crate) fn new(
+        src_layout: ImageConfiguration,
+        dst_layout: ImageConfiguration,
+        sample_size: usize,
+        alpha: &[u8],
+    ) -> SwizzleShuffle {
+        let src_pixel_bytes = src_layout.get_channels_count() * sample_size;
+        let dst_pixel_bytes = dst_layout.get_channels_count() * sample_size;
+        let pixels = 16 / src_pixel_bytes.max(dst_pixel_bytes);
+        let positions = source_positions(src_layout, dst_layout);
+
+        let mut mask = [0x80u8; 16];
+        let mut fill = [0u8; 16];
+        for pixel in 0..pixels {
+            for (channel, position) in positions
+                .iter()
+                .take(dst_layout.get_channels_count())
+                .enumerate()
+            {
+                for (byte, &alpha_byte) in alpha.iter().enumerate() {
+                    let dst_index = pixel * dst_pixel_bytes + channel * sample_size + byte;
+                    match position {
+                        Some(src_channel) => {
+                            mask[dst_index] =
+                                (pixel * src_pixel_bytes + src_channel * sample_size + byte) as u8;
+                        }
+                        None => fill[dst_index] = alpha_byte,
+                    }
+                }
+            }
+        }
+
+        SwizzleShuffle {
+            mask,
+            fill,
+            pixels,
+            src_pixel_bytes,
+            dst_pixel_bytes,
+        }
+    }
+}
+
+/// This function reorders channels of interleaved image between any of 3 and 4 channel layouts.
+/// Alpha is dropped when destination has no alpha, and filled with `alpha` when source has no alpha.
+/// Gray layouts may be reordered between themselves or expanded into colour ones,
+/// reducing colour into gray needs luma, see [crate::rgb_to_gray_u8].
+/// Works for `u8`, `u16` and `f32` samples.
+///
+/// # Arguments
+/// * `src` - A slice contains source image
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_layout` - Channel layout of the source
+/// * `dst` - A mutable slice to receive reordered image
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_layout` - Channel layout of the destination
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `alpha` - Alpha value written when source has no alpha, for example 255 for `u8` or 1.0 for `f32`
+///
+/// # Panics
+/// Panics if colour layout is requested to become gray or images doesn't fit into slices
+pub fn swizzle_channels<T: ImageSample>(
+    src: &[T],
+    src_stride: u32,
+    src_layout: ImageConfiguration,
+    dst: &mut [T],
+    dst_stride: u32,
+    dst_layout: ImageConfiguration,
+    width: u32,
+    height: u32,
+    alpha: T,
+) {
+    if dst_layout.is_gray() && !src_layout.is_gray() {
+        panic!(
+            "Colour layout {:?} can't be swizzled into {:?}, gray must be computed as luma",
+            src_layout, dst_layout
+        );
+    }
+    if width == 0 || height == 0 {
+        return;
+    }
+    let src_row_length =
+        check_image::<T>(src.len(), src_stride, width, height, src_layout, "Source");
+    let dst_row_length = check_image::<T>(
+        dst.len(),
+        dst_stride,
+        width,
+        height,
+        dst_layout,
+        "Destination",
+    );
+    let src_channels = src_layout.get_channels_count();
+    let dst_channels = dst_layout.get_channels_count();
+    let width = width as usize;
+    let src_row_samples = width * src_channels;
+    let dst_row_samples = width * dst_channels;
+
+    let sample_size = std::mem::size_of::<T>();
+    let alpha_bytes =
+        unsafe { std::slice::from_raw_parts(&alpha as *const T as *const u8, sample_size) };
+    let shuffle = SwizzleShuffle::new(src_layout, dst_layout, sample_size, alpha_bytes);
+    let positions = source_positions(src_layout, dst_layout);
+
+    let mut _row_handler: Option<SwizzleRowHandler> = None;
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        _row_handler = Some(neon_swizzle_row);
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        _row_handler = Some(sse_swizzle_row);
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("avx2") {
+        _row_handler = Some(avx_swizzle_row);
+    }
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = dst
+            .par_chunks_mut(dst_row_length)
+            .zip(src.par_chunks(src_row_length))
+            .take(height as usize);
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = dst
+            .chunks_mut(dst_row_length)
+            .zip(src.chunks(src_row_length))
+            .take(height as usize);
+    }
+
+    iter.for_each_row(width, |(dst_row, src_row)| {
+        let mut _cx = 0usize;
+
+        if let Some(handler) = _row_handler {
+            unsafe {
+                _cx = handler(
+                    _cx,
+                    src_row.as_ptr() as *const u8,
+                    dst_row.as_mut_ptr() as *mut u8,
+                    width,
+                    &shuffle,
+                );
+            }
+        }
+
+        for (src_pixel, dst_pixel) in src_row[_cx * src_channels..src_row_samples]
+            .chunks_exact(src_channels)
+            .zip(dst_row[_cx * dst_channels..dst_row_samples].chunks_exact_mut(dst_channels))
+        {
+            for (dst_value, position) in dst_pixel.iter_mut().zip(positions.iter()) {
+                *dst_value = match position {
+                    Some(src_channel) => src_pixel[*src_channel],
+                    None => alpha,
+                };
+            }
+        }
+    });
+}
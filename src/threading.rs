@@ -0,0 +1,382 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use std::cell::RefCell;
+#[cfg(feature = "rayon")]
+use std::marker::PhantomData;
+#[cfg(feature = "rayon")]
+use std::sync::Arc;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::plumbing::{Producer, ProducerCallback};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+#[cfg(feature = "rayon")]
+use rayon::ThreadPool;
+
+/// Images with less pixels than this are processed on the calling thread by default
+pub const DEFAULT_SERIAL_BELOW_PIXELS: usize = 16384;
+
+#[derive(Clone, Debug)]
+enum ThreadingMode {
+    Single,
+    Global,
+    #[cfg(feature = "rayon")]
+    Pool(Arc<ThreadPool>),
+}
+
+/// Describes how image rows are split between threads.
+///
+/// [crate::ConversionPlan] configured with [crate::ConversionPlan::with_threading] carries its policy
+/// explicitly into every row it converts, so the policy holds for each call of the plan.
+/// Other image functions take the policy of the calling thread set by [with_threading].
+/// Serial images are iterated in order on the calling thread without rayon.
+/// Without `rayon` feature everything runs on the calling thread.
+#[derive(Clone, Debug)]
+pub struct ThreadingPolicy {
+    mode: ThreadingMode,
+    min_rows_per_task: usize,
+    serial_below_pixels: usize,
+}
+
+impl ThreadingPolicy {
+    /// Runs everything on the calling thread
+    pub fn single_threaded() -> Self {
+        ThreadingPolicy {
+            mode: ThreadingMode::Single,
+            min_rows_per_task: 1,
+            serial_below_pixels: 0,
+        }
+    }
+
+    /// Splits rows on the global rayon pool, this is the default
+    pub fn global() -> Self {
+        ThreadingPolicy {
+            mode: ThreadingMode::Global,
+            min_rows_per_task: 1,
+            serial_below_pixels: DEFAULT_SERIAL_BELOW_PIXELS,
+        }
+    }
+
+    /// Splits rows on provided rayon pool
+    #[cfg(feature = "rayon")]
+    pub fn pool(pool: Arc<ThreadPool>) -> Self {
+        ThreadingPolicy {
+            mode: ThreadingMode::Pool(pool),
+            min_rows_per_task: 1,
+            serial_below_pixels: DEFAULT_SERIAL_BELOW_PIXELS,
+        }
+    }
+
+    /// Sets minimum count of rows processed by one task
+    pub fn with_min_rows_per_task(mut self, rows: usize) -> Self {
+        self.min_rows_per_task = rows.max(1);
+        self
+    }
+
+    /// Images with less pixels than `pixels` will be processed on the calling thread, 0 disables the heuristic
+    pub fn with_serial_below_pixels(mut self, pixels: usize) -> Self {
+        self.serial_below_pixels = pixels;
+        self
+    }
+
+    pub fn is_single_threaded(&self) -> bool {
+        matches!(self.mode, ThreadingMode::Single)
+    }
+
+    pub fn min_rows_per_task(&self) -> usize {
+        self.min_rows_per_task
+    }
+
+    pub fn serial_below_pixels(&self) -> usize {
+        self.serial_below_pixels
+    }
+
+    /// Checks if image with `rows` rows `row_pixels` each should be processed on the calling thread
+    pub fn is_serial_for(&self, rows: usize, row_pixels: usize) -> bool {
+        self.is_single_threaded()
+            || rows <= self.min_rows_per_task
+            || rows.saturating_mul(row_pixels) < self.serial_below_pixels
+    }
+}
+
+impl Default for ThreadingPolicy {
+    fn default() -> Self {
+        ThreadingPolicy::global()
+    }
+}
+
+thread_local! {
+    static CURRENT_POLICY: RefCell<Option<ThreadingPolicy>> = const { RefCell::new(None) };
+}
+
+struct PolicyGuard {
+    previous: Option<ThreadingPolicy>,
+}
+
+impl Drop for PolicyGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT_POLICY.with(|policy| *policy.borrow_mut() = previous);
+    }
+}
+
+/// This function runs `f` with threading policy applied to all image functions called on this thread inside.
+/// Previous policy is restored after `f` returns, calls may be nested.
+///
+/// The policy is thread local: image functions called from other threads started inside `f`,
+/// including rayon workers, don't see it. Use [crate::ConversionPlan::with_threading] to pass
+/// the policy with the conversion itself.
+///
+/// # Arguments
+/// * `policy` - Threading policy
+/// * `f` - Function to run
+pub fn with_threading<R>(policy: &ThreadingPolicy, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT_POLICY.with(|current| current.replace(Some(policy.clone())));
+    let _guard = PolicyGuard { previous };
+    f()
+}
+
+/// Returns policy active on the calling thread
+pub fn current_threading() -> ThreadingPolicy {
+    CURRENT_POLICY.with(|policy| policy.borrow().clone().unwrap_or_default())
+}
+
+/// Runs row iterators according to threading policy
+pub(crate) trait ForEachRow: Sized {
+    type Item;
+
+    /// Runs `f` for every item with policy of the calling thread, `row_pixels` is count of pixels in one item
+    fn for_each_row<F: Fn(Self::Item) + Send + Sync>(self, row_pixels: usize, f: F) {
+        self.for_each_row_with(&current_threading(), row_pixels, f);
+    }
+
+    /// Runs `f` for every item according to `policy`, serial images are iterated
+    /// in order on the calling thread
+    fn for_each_row_with<F: Fn(Self::Item) + Send + Sync>(
+        self,
+        policy: &ThreadingPolicy,
+        row_pixels: usize,
+        f: F,
+    );
+
+    /// Runs `f` for every item with scratch state created by `init` once per worker task
+    /// instead of once per item
+    fn for_each_row_init<
+        S,
+        INIT: Fn() -> S + Send + Sync,
+        F: Fn(&mut S, Self::Item) + Send + Sync,
+    >(
+        self,
+        row_pixels: usize,
+        init: INIT,
+        f: F,
+    ) {
+        self.for_each_row_init_with(&current_threading(), row_pixels, init, f);
+    }
+
+    /// Same as [ForEachRow::for_each_row_init] according to `policy`,
+    /// serial images create the state once
+    fn for_each_row_init_with<
+        S,
+        INIT: Fn() -> S + Send + Sync,
+        F: Fn(&mut S, Self::Item) + Send + Sync,
+    >(
+        self,
+        policy: &ThreadingPolicy,
+        row_pixels: usize,
+        init: INIT,
+        f: F,
+    );
+}
+
+/// Drains producer of indexed parallel iterator as plain iterator on the calling thread
+#[cfg(feature = "rayon")]
+struct SerialRows<S, INIT, F> {
+    init: INIT,
+    f: F,
+    _state: PhantomData<S>,
+}
+
+#[cfg(feature = "rayon")]
+impl<T, S, INIT: FnOnce() -> S, F: Fn(&mut S, T)> ProducerCallback<T> for SerialRows<S, INIT, F> {
+    type Output = ();
+
+    fn callback<P: Producer<Item = T>>(self, producer: P) {
+        let mut state = (self.init)();
+        for item in producer.into_iter() {
+            (self.f)(&mut state, item);
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<I: IndexedParallelIterator> ForEachRow for I {
+    type Item = I::Item;
+
+    fn for_each_row_with<F: Fn(Self::Item) + Send + Sync>(
+        self,
+        policy: &ThreadingPolicy,
+        row_pixels: usize,
+        f: F,
+    ) {
+        if policy.is_serial_for(self.len(), row_pixels) {
+            self.with_producer(SerialRows {
+                init: || (),
+                f: |_: &mut (), item| f(item),
+                _state: PhantomData,
+            });
+            return;
+        }
+        let min_rows = policy.min_rows_per_task;
+        match &policy.mode {
+            ThreadingMode::Pool(pool) => pool.install(|| self.with_min_len(min_rows).for_each(f)),
+            _ => self.with_min_len(min_rows).for_each(f),
+        }
+    }
+
+    fn for_each_row_init_with<
+        S,
+        INIT: Fn() -> S + Send + Sync,
+        F: Fn(&mut S, Self::Item) + Send + Sync,
+    >(
+        self,
+        policy: &ThreadingPolicy,
+        row_pixels: usize,
+        init: INIT,
+        f: F,
+    ) {
+        if policy.is_serial_for(self.len(), row_pixels) {
+            self.with_producer(SerialRows {
+                init,
+                f,
+                _state: PhantomData,
+            });
+            return;
+        }
+        let min_rows = policy.min_rows_per_task;
+        match &policy.mode {
+            ThreadingMode::Pool(pool) => {
+                pool.install(|| self.with_min_len(min_rows).for_each_init(init, f))
+            }
+            _ => self.with_min_len(min_rows).for_each_init(init, f),
+        }
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
+impl<I: Iterator> ForEachRow for I {
+    type Item = I::Item;
+
+    fn for_each_row_with<F: Fn(Self::Item) + Send + Sync>(
+        self,
+        _: &ThreadingPolicy,
+        _: usize,
+        f: F,
+    ) {
+        self.for_each(f);
+    }
+
+    fn for_each_row_init_with<
+        S,
+        INIT: Fn() -> S + Send + Sync,
+        F: Fn(&mut S, Self::Item) + Send + Sync,
+    >(
+        self,
+        _: &ThreadingPolicy,
+        _: usize,
+        init: INIT,
+        f: F,
+    ) {
+        let mut state = init();
+        self.for_each(|item| f(&mut state, item));
+    }
+}
+
+#[cfg(all(test, feature = "rayon"))]
+mod tests {
+    use super::*;
+    use crate::{ColorModel, ColorSpace, ConversionPlan, ImageConfiguration, SampleType};
+    use rayon::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::thread;
+
+    #[test]
+    fn serial_policy_iterates_in_order_on_calling_thread() {
+        let caller = thread::current().id();
+        let visited = Mutex::new(Vec::new());
+        let inits = AtomicUsize::new(0);
+        (0..64usize).into_par_iter().for_each_row_init_with(
+            &ThreadingPolicy::single_threaded(),
+            1,
+            || inits.fetch_add(1, Ordering::Relaxed),
+            |_, row| {
+                assert_eq!(thread::current().id(), caller);
+                visited.lock().unwrap().push(row);
+            },
+        );
+        assert_eq!(visited.into_inner().unwrap(), (0..64).collect::<Vec<_>>());
+        assert_eq!(inits.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn pool_policy_runs_rows_on_its_pool() {
+        let pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(2)
+                .build()
+                .unwrap(),
+        );
+        let policy = ThreadingPolicy::pool(pool.clone()).with_serial_below_pixels(0);
+        let outside = AtomicUsize::new(0);
+        (0..64usize)
+            .into_par_iter()
+            .for_each_row_with(&policy, 1, |_| {
+                if pool.current_thread_index().is_none() {
+                    outside.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        assert_eq!(outside.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn plan_policy_is_independent_of_calling_thread() {
+        let (width, height) = (37u32, 29u32);
+        let rgb: Vec<u8> = (0..width * height * 3)
+            .map(|i| (i * 29 % 256) as u8)
+            .collect();
+        let source = ColorSpace::srgb(ImageConfiguration::Rgb, SampleType::U8);
+        // Chain without dedicated kernel and dedicated kernel both take the policy of the plan
+        for model in [ColorModel::Hsl, ColorModel::Lab] {
+            let target = ColorSpace::new(ImageConfiguration::Rgb, SampleType::F32, model);
+            let mut expected = vec![0f32; rgb.len()];
+            ConversionPlan::new(&source, &target).apply(
+                &rgb,
+                width * 3,
+                &mut expected,
+                width * 12,
+                width,
+                height,
+            );
+
+            let pool = Arc::new(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(3)
+                    .build()
+                    .unwrap(),
+            );
+            let plan = ConversionPlan::new(&source, &target)
+                .with_threading(ThreadingPolicy::pool(pool).with_serial_below_pixels(0));
+            let mut converted = vec![0f32; rgb.len()];
+            // Scoped policy of the calling thread doesn't override policy of the plan
+            with_threading(&ThreadingPolicy::single_threaded(), || {
+                plan.apply(&rgb, width * 3, &mut converted, width * 12, width, height)
+            });
+            assert_eq!(converted, expected, "{:?}", model);
+        }
+    }
+}
@@ -0,0 +1,445 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::gamma_curves::{pq_from_linear, pq_to_linear, TransferFunction};
+use crate::hlg::{hlg_inverse_oetf, hlg_ootf};
+use crate::image::ImageConfiguration;
+use crate::matrix::{matrix_mul, matrix_vec_mul};
+use crate::threading::ForEachRow;
+use crate::xyz_transform::{BT2020_TO_XYZ_D65, XYZ_TO_SRGB_D65};
+use crate::Rgb;
+#[cfg(feature = "rayon")]
+use rayon::iter::IndexedParallelIterator;
+#[cfg(feature = "rayon")]
+use rayon::prelude::{ParallelSlice, ParallelSliceMut};
+
+/// Reference white of extended SDR range used by `pq_to_linear` and `pq_from_linear`
+pub(crate) const SDR_WHITE_NITS: f32 = 203.;
+
+/// BT.2020 luminance coefficients
+pub(crate) const BT2020_LUMINANCE: [f32; 3] =
+    [0.2627002120112671, 0.6779980715188708, 0.05930171646986196];
+
+/// Tone mapping curve
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
+pub enum ToneMappingOperator {
+    /// Reinhard `x / (1 + x)`, source peak is not taken into account
+    Reinhard,
+    /// Extended Reinhard where source peak maps to target peak
+    #[default]
+    ReinhardExtended,
+    /// John Hable's Uncharted 2 filmic curve
+    Hable,
+    /// Krzysztof Narkowicz ACES filmic fit
+    AcesNarkowicz,
+    /// Stephen Hill ACES RRT and ODT fit
+    AcesHill,
+    /// AgX base contrast curve, log2 encoding followed by polynomial sigmoid
+    Agx,
+    /// ITU-R BT.2390 EETF, Hermite knee in PQ domain
+    Bt2390,
+}
+
+/// Value tone mapping curve is applied to, result is scaled back into RGB
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
+pub enum ToneMappingMode {
+    /// BT.2020 luminance, hue and saturation are kept
+    #[default]
+    Luminance,
+    /// Maximum of RGB channels, keeps channels within target range without clipping
+    MaxRgb,
+}
+
+/// HDR transfer function of source image
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum HdrTransferFunction {
+    /// SMPTE ST 2084 perceptual quantizer, display referred with absolute luminance
+    Pq,
+    /// ARIB STD-B67 hybrid log gamma, nominal peak is the source peak
+    Hlg,
+}
+
+/// Tone mapping of HDR colours in BT.2020 primaries into target display range
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ToneMapping {
+    pub operator: ToneMappingOperator,
+    pub mode: ToneMappingMode,
+    /// Peak luminance of source in nits, mastering display peak for PQ and nominal peak for HLG
+    pub source_peak_nits: f32,
+    /// Peak luminance of target display in nits
+    pub target_peak_nits: f32,
+}
+
+impl Default for ToneMapping {
+    fn default() -> Self {
+        ToneMapping {
+            operator: ToneMappingOperator::default(),
+            mode: ToneMappingMode::default(),
+            source_peak_nits: 1000.,
+            target_peak_nits: SDR_WHITE_NITS,
+        }
+    }
+}
+
+#[inline]
+fn hable_partial(x: f32) -> f32 {
+    const A: f32 = 0.15;
+    const B: f32 = 0.50;
+    const C: f32 = 0.10;
+    const D: f32 = 0.20;
+    const E: f32 = 0.02;
+    const F: f32 = 0.30;
+    ((x * (A * x + C * B) + D * E) / (x * (A * x + B) + D * F)) - E / F
+}
+
+#[inline]
+fn aces_narkowicz(x: f32) -> f32 {
+    const A: f32 = 2.51;
+    const B: f32 = 0.03;
+    const C: f32 = 2.43;
+    const D: f32 = 0.59;
+    const E: f32 = 0.14;
+    (x * (A * x + B)) / (x * (C * x + D) + E)
+}
+
+#[inline]
+fn aces_hill(x: f32) -> f32 {
+    let a = x * (x + 0.0245786) - 0.000090537;
+    let b = x * (0.983729 * x + 0.4329510) + 0.238081;
+    a / b
+}
+
+#[inline]
+fn agx(x: f32) -> f32 {
+    const MIN_EV: f32 = -12.47393;
+    const MAX_EV: f32 = 4.026069;
+    if x <= 0. {
+        return 0.;
+    }
+    let v = ((x.log2() - MIN_EV) / (MAX_EV - MIN_EV)).max(0.).min(1.);
+    let v2 = v * v;
+    let v4 = v2 * v2;
+    let curve =
+        15.5 * v4 * v2 - 40.14 * v4 * v + 31.96 * v4 - 6.868 * v2 * v + 0.4298 * v2 + 0.1191 * v
+            - 0.00232;
+    curve.max(0.).powf(2.2)
+}
+
+impl ToneMapping {
+    /// Creates tone mapping
+    pub fn new(
+        operator: ToneMappingOperator,
+        mode: ToneMappingMode,
+        source_peak_nits: f32,
+        target_peak_nits: f32,
+    ) -> ToneMapping {
+        ToneMapping {
+            operator,
+            mode,
+            source_peak_nits,
+            target_peak_nits,
+        }
+    }
+
+    /// Maps luminance in nits into fraction of target peak in range [0, 1]
+    pub fn map_luminance(&self, nits: f32) -> f32 {
+        if nits <= 0. {
+            return 0.;
+        }
+        let x = nits / self.target_peak_nits;
+        let white = (self.source_peak_nits / self.target_peak_nits).max(1.);
+        let mapped = match self.operator {
+            ToneMappingOperator::Reinhard => x / (1. + x),
+            ToneMappingOperator::ReinhardExtended => x * (1. + x / (white * white)) / (1. + x),
+            ToneMappingOperator::Hable => hable_partial(x) / hable_partial(white),
+            ToneMappingOperator::AcesNarkowicz => aces_narkowicz(x) / aces_narkowicz(white),
+            ToneMappingOperator::AcesHill => aces_hill(x) / aces_hill(white),
+            ToneMappingOperator::Agx => agx(x) / agx(white),
+            ToneMappingOperator::Bt2390 => self.bt2390_eetf(nits) / self.target_peak_nits,
+        };
+        mapped.max(0.).min(1.)
+    }
+
+    /// BT.2390 EETF with zero black levels, returns nits
+    fn bt2390_eetf(&self, nits: f32) -> f32 {
+        #[inline]
+        fn pq(nits: f32) -> f32 {
+            pq_from_linear(nits / SDR_WHITE_NITS)
+        }
+        let source_peak = pq(self.source_peak_nits);
+        if source_peak <= 0. {
+            return 0.;
+        }
+        let e1 = pq(nits) / source_peak;
+        let max_luminance = pq(self.target_peak_nits) / source_peak;
+        let knee_start = 1.5 * max_luminance - 0.5;
+        let e2 = if e1 < knee_start || knee_start >= 1. {
+            e1
+        } else {
+            let t = (e1 - knee_start) / (1. - knee_start);
+            let t2 = t * t;
+            let t3 = t2 * t;
+            (2. * t3 - 3. * t2 + 1.) * knee_start
+                + (t3 - 2. * t2 + t) * (1. - knee_start)
+                + (-2. * t3 + 3. * t2) * max_luminance
+        };
+        pq_to_linear((e2 * source_peak).min(1.)) * SDR_WHITE_NITS
+    }
+
+    /// Maps linear BT.2020 colour in nits into fraction of target peak, channels may exceed [0, 1]
+    /// when luminance mode is used for saturated colours
+    pub fn map_rgb(&self, rgb: Rgb<f32>) -> Rgb<f32> {
+        let value = match self.mode {
+            ToneMappingMode::Luminance => {
+                rgb.r * BT2020_LUMINANCE[0]
+                    + rgb.g * BT2020_LUMINANCE[1]
+                    + rgb.b * BT2020_LUMINANCE[2]
+            }
+            ToneMappingMode::MaxRgb => rgb.r.max(rgb.g).max(rgb.b),
+        };
+        if value <= 0. {
+            return Rgb::<f32>::new(0., 0., 0.);
+        }
+        let scale = self.map_luminance(value) / value;
+        Rgb::<f32>::new(rgb.r * scale, rgb.g * scale, rgb.b * scale)
+    }
+}
+
+fn tone_map_image<const CHANNELS_CONFIGURATION: u8>(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+    transfer_function: HdrTransferFunction,
+    tone_mapping: ToneMapping,
+) {
+    if !(1..=16).contains(&bit_depth) {
+        panic!(
+            "Bit depth must be in range [1, 16], but it was {}",
+            bit_depth
+        );
+    }
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let channels = image_configuration.get_channels_count();
+    let max_value = ((1u32 << bit_depth) - 1) as f32;
+
+    // Decodes every code value into nits for PQ and into scene linear light for HLG
+    let mut decoding_table = vec![0f32; 1usize << bit_depth];
+    for (i, value) in decoding_table.iter_mut().enumerate() {
+        let encoded = i as f32 / max_value;
+        *value = match transfer_function {
+            HdrTransferFunction::Pq => pq_to_linear(encoded) * SDR_WHITE_NITS,
+            HdrTransferFunction::Hlg => hlg_inverse_oetf(encoded),
+        };
+    }
+
+    let mut encoding_table = vec![0u8; 4097];
+    for (i, value) in encoding_table.iter_mut().enumerate() {
+        *value = (TransferFunction::Srgb.gamma(i as f32 * (1. / 4096.)) * 255.)
+            .round()
+            .min(255.) as u8;
+    }
+
+    let gamut_matrix = matrix_mul(&XYZ_TO_SRGB_D65, &BT2020_TO_XYZ_D65);
+    let alpha_scale = 255. / max_value;
+    let src_row_length = src_stride as usize / std::mem::size_of::<u16>();
+
+    let iter;
+    #[cfg(feature = "rayon")]
+    {
+        iter = dst
+            .par_chunks_exact_mut(dst_stride as usize)
+            .zip(src.par_chunks_exact(src_row_length))
+            .take(height as usize);
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        iter = dst
+            .chunks_exact_mut(dst_stride as usize)
+            .zip(src.chunks_exact(src_row_length))
+            .take(height as usize);
+    }
+
+    iter.for_each_row(width as usize, |(dst_row, src_row)| {
+        for (dst, src) in dst_row
+            .chunks_exact_mut(channels)
+            .zip(src_row.chunks_exact(channels))
+            .take(width as usize)
+        {
+            let decode = |offset: usize| -> f32 {
+                decoding_table[(src[offset] as usize).min(decoding_table.len() - 1)]
+            };
+            let rgb = Rgb::<f32>::new(
+                decode(image_configuration.get_r_channel_offset()),
+                decode(image_configuration.get_g_channel_offset()),
+                decode(image_configuration.get_b_channel_offset()),
+            );
+            let rgb = match transfer_function {
+                HdrTransferFunction::Pq => rgb,
+                HdrTransferFunction::Hlg => hlg_ootf(rgb, tone_mapping.source_peak_nits),
+            };
+            let mapped = tone_mapping.map_rgb(rgb);
+            let linear = matrix_vec_mul(&gamut_matrix, [mapped.r, mapped.g, mapped.b]);
+            let encode =
+                |v: f32| -> u8 { encoding_table[(v.max(0.).min(1.) * 4096.).round() as usize] };
+            dst[image_configuration.get_r_channel_offset()] = encode(linear[0]);
+            dst[image_configuration.get_g_channel_offset()] = encode(linear[1]);
+            dst[image_configuration.get_b_channel_offset()] = encode(linear[2]);
+            if image_configuration.has_alpha() {
+                let a = src[image_configuration.get_a_channel_offset()] as f32;
+                dst[image_configuration.get_a_channel_offset()] =
+                    (a * alpha_scale).round().min(255.) as u8;
+            }
+        }
+    });
+}
+
+/// This function tone maps PQ or HLG encoded BT.2020 RGB image into sRGB
+///
+/// # Arguments
+/// * `src` - A slice contains RGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive sRGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of source, 10, 12 or 16 etc.
+/// * `transfer_function` - Transfer function of source
+/// * `tone_mapping` - Tone mapping operator and luminance ranges, see [ToneMapping]
+pub fn rgb_u16_tone_map_to_srgb(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+    transfer_function: HdrTransferFunction,
+    tone_mapping: ToneMapping,
+) {
+    tone_map_image::<{ ImageConfiguration::Rgb as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        bit_depth,
+        transfer_function,
+        tone_mapping,
+    );
+}
+
+/// This function tone maps PQ or HLG encoded BT.2020 RGBA image into sRGB, alpha is rescaled into 8 bit
+///
+/// # Arguments
+/// * `src` - A slice contains RGBA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive sRGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of source, 10, 12 or 16 etc.
+/// * `transfer_function` - Transfer function of source
+/// * `tone_mapping` - Tone mapping operator and luminance ranges, see [ToneMapping]
+pub fn rgba_u16_tone_map_to_srgb(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+    transfer_function: HdrTransferFunction,
+    tone_mapping: ToneMapping,
+) {
+    tone_map_image::<{ ImageConfiguration::Rgba as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        bit_depth,
+        transfer_function,
+        tone_mapping,
+    );
+}
+
+/// This function tone maps PQ or HLG encoded BT.2020 BGRA image into sRGB, alpha is rescaled into 8 bit
+///
+/// # Arguments
+/// * `src` - A slice contains BGRA data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive sRGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of source, 10, 12 or 16 etc.
+/// * `transfer_function` - Transfer function of source
+/// * `tone_mapping` - Tone mapping operator and luminance ranges, see [ToneMapping]
+pub fn bgra_u16_tone_map_to_srgb(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+    transfer_function: HdrTransferFunction,
+    tone_mapping: ToneMapping,
+) {
+    tone_map_image::<{ ImageConfiguration::Bgra as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        bit_depth,
+        transfer_function,
+        tone_mapping,
+    );
+}
+
+/// This function tone maps PQ or HLG encoded BT.2020 BGR image into sRGB
+///
+/// # Arguments
+/// * `src` - A slice contains BGR data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive sRGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `bit_depth` - Bit depth of source, 10, 12 or 16 etc.
+/// * `transfer_function` - Transfer function of source
+/// * `tone_mapping` - Tone mapping operator and luminance ranges, see [ToneMapping]
+pub fn bgr_u16_tone_map_to_srgb(
+    src: &[u16],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    bit_depth: u32,
+    transfer_function: HdrTransferFunction,
+    tone_mapping: ToneMapping,
+) {
+    tone_map_image::<{ ImageConfiguration::Bgr as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        bit_depth,
+        transfer_function,
+        tone_mapping,
+    );
+}
@@ -5,6 +5,7 @@
  * // license that can be found in the LICENSE file.
  */
 use crate::gamma_curves::TransferFunction;
+use crate::matrix::matrix_vec_mul;
 use crate::rgb::Rgb;
 use crate::utils::mlaf;
 use crate::{EuclideanDistance, Jzazbz, SRGB_TO_XYZ_D65, XYZ_TO_SRGB_D65};
@@ -56,6 +57,13 @@ impl Xyz {
         }
     }
 
+    /// Applies 3x3 matrix to XYZ, for example chromatic adaptation matrix
+    #[inline]
+    pub fn transform(&self, matrix: &[[f32; 3]; 3]) -> Xyz {
+        let v = matrix_vec_mul(matrix, [self.x, self.y, self.z]);
+        Xyz::new(v[0], v[1], v[2])
+    }
+
     /// Scales XYZ to absolute luminance against display
     #[inline]
     pub fn to_absolute_luminance(&self, display_nits: f32) -> Xyz {
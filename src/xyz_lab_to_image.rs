@@ -7,21 +7,68 @@
 
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::avx::avx_xyz_to_channels;
-use crate::gamma_curves::TransferFunction;
+use crate::gamma_curves::{gamma_table_u8, TransferFunction};
 use crate::image::ImageConfiguration;
 #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
 use crate::neon::neon_xyz_to_channels;
+use crate::premultiply::premultiply_row_u8;
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::sse::sse_xyz_to_channels;
+#[cfg(feature = "rayon")]
+use crate::threading::ForEachRow;
+use crate::threading::{current_threading, ThreadingPolicy};
 use crate::xyz_target::XyzTarget;
 use crate::{LCh, Lab, Luv, Xyz, XYZ_TO_SRGB_D65};
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 use std::slice;
 
-fn xyz_to_channels<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool, const TARGET: u8>(
+#[allow(clippy::type_complexity)]
+pub(crate) type XyzToChannelsRowHandler = unsafe fn(
+    usize,
+    *const f32,
+    usize,
+    *const f32,
+    usize,
+    *mut f32,
+    usize,
+    u32,
+    &[[f32; 3]; 3],
+) -> usize;
+
+/// Selects the widest available SIMD row handler for the layout
+pub(crate) fn xyz_to_channels_row_handler<
+    const CHANNELS_CONFIGURATION: u8,
+    const USE_ALPHA: bool,
+    const TARGET: u8,
+>() -> Option<XyzToChannelsRowHandler> {
+    let mut _wide_row_handler: Option<XyzToChannelsRowHandler> = None;
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        _wide_row_handler = Some(sse_xyz_to_channels::<CHANNELS_CONFIGURATION, USE_ALPHA, TARGET>);
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    if std::arch::is_x86_feature_detected!("avx2") {
+        _wide_row_handler = Some(avx_xyz_to_channels::<CHANNELS_CONFIGURATION, USE_ALPHA, TARGET>);
+    }
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        _wide_row_handler = Some(neon_xyz_to_channels::<CHANNELS_CONFIGURATION, USE_ALPHA, TARGET>);
+    }
+
+    _wide_row_handler
+}
+
+pub(crate) fn xyz_to_channels<
+    const CHANNELS_CONFIGURATION: u8,
+    const USE_ALPHA: bool,
+    const TARGET: u8,
+>(
     src: &[f32],
     src_stride: u32,
     a_channel: &[f32],
@@ -32,6 +79,48 @@ fn xyz_to_channels<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool, cons
     height: u32,
     matrix: &[[f32; 3]; 3],
     transfer_function: TransferFunction,
+    premultiplied: bool,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let premultiply = premultiplied && USE_ALPHA && image_configuration.has_alpha();
+
+    xyz_to_channels_impl::<CHANNELS_CONFIGURATION, USE_ALPHA, TARGET>(
+        src,
+        src_stride,
+        a_channel,
+        a_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        &gamma_table_u8(transfer_function),
+        premultiply,
+        xyz_to_channels_row_handler::<CHANNELS_CONFIGURATION, USE_ALPHA, TARGET>(),
+        &current_threading(),
+    );
+}
+
+/// Converts image with precomputed gamma table and row handler,
+/// rows are premultiplied by alpha after encoding when `premultiply` is set
+pub(crate) fn xyz_to_channels_impl<
+    const CHANNELS_CONFIGURATION: u8,
+    const USE_ALPHA: bool,
+    const TARGET: u8,
+>(
+    src: &[f32],
+    src_stride: u32,
+    a_channel: &[f32],
+    a_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    lut_table: &[u8],
+    premultiply: bool,
+    _wide_row_handler: Option<XyzToChannelsRowHandler>,
+    policy: &ThreadingPolicy,
 ) {
     let source: XyzTarget = TARGET.into();
     let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
@@ -41,36 +130,6 @@ fn xyz_to_channels<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool, cons
 
     let channels = image_configuration.get_channels_count();
 
-    #[allow(clippy::type_complexity)]
-    let mut _wide_row_handler: Option<
-        unsafe fn(
-            usize,
-            *const f32,
-            usize,
-            *const f32,
-            usize,
-            *mut f32,
-            usize,
-            u32,
-            &[[f32; 3]; 3],
-        ) -> usize,
-    > = None;
-
-    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
-    if std::arch::is_x86_feature_detected!("sse4.1") {
-        _wide_row_handler = Some(sse_xyz_to_channels::<CHANNELS_CONFIGURATION, USE_ALPHA, TARGET>);
-    }
-
-    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
-    if std::arch::is_x86_feature_detected!("avx2") {
-        _wide_row_handler = Some(avx_xyz_to_channels::<CHANNELS_CONFIGURATION, USE_ALPHA, TARGET>);
-    }
-
-    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
-    {
-        _wide_row_handler = Some(neon_xyz_to_channels::<CHANNELS_CONFIGURATION, USE_ALPHA, TARGET>);
-    }
-
     let src_slice_safe_align = unsafe {
         slice::from_raw_parts_mut(
             src.as_ptr() as *mut u8,
@@ -78,11 +137,6 @@ fn xyz_to_channels<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool, cons
         )
     };
 
-    let mut lut_table = vec![0u8; 2049];
-    for (i, element) in lut_table.iter_mut().enumerate() {
-        *element = (transfer_function.gamma(i as f32 * (1. / 2048.0)) * 255.).min(255.) as u8;
-    }
-
     #[cfg(feature = "rayon")]
     {
         if USE_ALPHA {
@@ -96,7 +150,7 @@ fn xyz_to_channels<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool, cons
             dst.par_chunks_exact_mut(dst_stride as usize)
                 .zip(src_slice_safe_align.par_chunks_exact_mut(src_stride as usize))
                 .zip(a_slice_safe_align.par_chunks_exact(a_stride as usize))
-                .for_each(|((dst, src), a_channel)| unsafe {
+                .for_each_row_with(policy, width as usize, |((dst, src), a_channel)| unsafe {
                     let mut _cx = 0usize;
 
                     let mut transient_row = vec![0f32; width as usize * channels];
@@ -188,11 +242,15 @@ fn xyz_to_channels<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool, cons
                             dst_chunk[image_configuration.get_a_channel_offset()] = a_cast;
                         }
                     }
+
+                    if premultiply {
+                        premultiply_row_u8::<CHANNELS_CONFIGURATION>(dst, width as usize);
+                    }
                 });
         } else {
             dst.par_chunks_exact_mut(dst_stride as usize)
                 .zip(src_slice_safe_align.par_chunks_exact_mut(src_stride as usize))
-                .for_each(|(dst, src)| unsafe {
+                .for_each_row_with(policy, width as usize, |(dst, src)| unsafe {
                     let mut _cx = 0usize;
 
                     let mut transient_row = vec![0f32; width as usize * channels];
@@ -276,6 +334,8 @@ fn xyz_to_channels<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool, cons
 
     #[cfg(not(feature = "rayon"))]
     {
+        // Without rayon rows always run on the calling thread
+        let _ = policy;
         if USE_ALPHA {
             let a_slice_safe_align = unsafe {
                 slice::from_raw_parts_mut(
@@ -381,6 +441,10 @@ fn xyz_to_channels<const CHANNELS_CONFIGURATION: u8, const USE_ALPHA: bool, cons
                             dst_chunk[image_configuration.get_a_channel_offset()] = a_cast;
                         }
                     }
+
+                    if premultiply {
+                        premultiply_row_u8::<CHANNELS_CONFIGURATION>(dst, width as usize);
+                    }
                 }
             }
         } else {
@@ -505,6 +569,7 @@ pub fn xyz_to_rgb(
         height,
         matrix,
         transfer_function,
+        false,
     );
 }
 
@@ -541,6 +606,7 @@ pub fn xyz_to_bgr(
         height,
         matrix,
         transfer_function,
+        false,
     );
 }
 
@@ -573,6 +639,7 @@ pub fn xyz_to_srgb(
         height,
         &XYZ_TO_SRGB_D65,
         TransferFunction::Srgb,
+        false,
     );
 }
 
@@ -609,6 +676,7 @@ pub fn lab_to_rgb(
         height,
         matrix,
         transfer_function,
+        false,
     );
 }
 
@@ -645,6 +713,7 @@ pub fn lab_to_bgr(
         height,
         matrix,
         transfer_function,
+        false,
     );
 }
 
@@ -677,6 +746,7 @@ pub fn lab_to_srgb(
         height,
         &XYZ_TO_SRGB_D65,
         TransferFunction::Srgb,
+        false,
     );
 }
 
@@ -712,6 +782,43 @@ pub fn laba_to_srgb(
         height,
         &XYZ_TO_SRGB_D65,
         TransferFunction::Srgb,
+        false,
+    );
+}
+
+/// This function converts LAB with separate alpha channel to RGBA. This is much more effective than naive direct transformation. Alpha is premultiplied into colour channels of the output
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `a_plane` - A slice contains Alpha data
+/// * `a_stride` - Bytes per row for alpha plane data
+/// * `dst` - A mutable slice to receive RGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+pub fn laba_to_srgb_premultiplied(
+    src: &[f32],
+    src_stride: u32,
+    a_plane: &[f32],
+    a_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+) {
+    xyz_to_channels::<{ ImageConfiguration::Rgba as u8 }, true, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        a_plane,
+        a_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        &XYZ_TO_SRGB_D65,
+        TransferFunction::Srgb,
+        true,
     );
 }
 
@@ -751,6 +858,47 @@ pub fn xyza_to_rgba(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts XYZ with separate alpha channel to ARGB. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `a_plane` - A slice contains Alpha data
+/// * `a_stride` - Bytes per row for alpha plane data
+/// * `dst` - A mutable slice to receive RGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn xyza_to_argb(
+    src: &[f32],
+    src_stride: u32,
+    a_plane: &[f32],
+    a_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    xyz_to_channels::<{ ImageConfiguration::Argb as u8 }, true, { XyzTarget::Xyz as u8 }>(
+        src,
+        src_stride,
+        a_plane,
+        a_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
     );
 }
 
@@ -787,6 +935,7 @@ pub fn luv_to_rgb(
         height,
         matrix,
         transfer_function,
+        false,
     );
 }
 
@@ -823,6 +972,7 @@ pub fn luv_to_bgr(
         height,
         matrix,
         transfer_function,
+        false,
     );
 }
 
@@ -859,6 +1009,7 @@ pub fn lch_to_rgb(
         height,
         matrix,
         transfer_function,
+        false,
     );
 }
 
@@ -895,5 +1046,6 @@ pub fn lch_to_bgr(
         height,
         matrix,
         transfer_function,
+        false,
     );
 }
@@ -32,3 +32,147 @@ pub const XYZ_TO_SRGB_D50: [[f32; 3]; 3] = [
     [-0.9787684f32, 1.9161415f32, 0.0334540f32],
     [0.0719453f32, -0.2289914f32, 1.4052427f32],
 ];
+
+/// Display P3 to XYZ transformation matrix, D65 White point
+pub const DISPLAY_P3_TO_XYZ_D65: [[f32; 3]; 3] = [
+    [
+        0.4865709486482162f32,
+        0.26566769316909306f32,
+        0.1982172852343625f32,
+    ],
+    [
+        0.2289745640697488f32,
+        0.6917385218365064f32,
+        0.079286914093745f32,
+    ],
+    [
+        0.0000000000000000f32,
+        0.04511338185890264f32,
+        1.043944368900976f32,
+    ],
+];
+
+/// XYZ to Display P3 transformation matrix, D65 White point
+pub const XYZ_TO_DISPLAY_P3_D65: [[f32; 3]; 3] = [
+    [
+        2.493496911941425f32,
+        -0.9313836179191239f32,
+        -0.40271078445071684f32,
+    ],
+    [
+        -0.8294889695615747f32,
+        1.7626640603183463f32,
+        0.023624685841943577f32,
+    ],
+    [
+        0.03584583024378447f32,
+        -0.07617238926804182f32,
+        0.9568845240076872f32,
+    ],
+];
+
+/// BT.2020 to XYZ transformation matrix, D65 White point
+pub const BT2020_TO_XYZ_D65: [[f32; 3]; 3] = [
+    [
+        0.6369580483012914f32,
+        0.14461690358620832f32,
+        0.1688809751641721f32,
+    ],
+    [
+        0.2627002120112671f32,
+        0.6779980715188708f32,
+        0.05930171646986196f32,
+    ],
+    [
+        0.000000000000000f32,
+        0.028072693049087428f32,
+        1.060985057710791f32,
+    ],
+];
+
+/// XYZ to BT.2020 transformation matrix, D65 White point
+pub const XYZ_TO_BT2020_D65: [[f32; 3]; 3] = [
+    [
+        1.7166511879712674f32,
+        -0.35567078377639233f32,
+        -0.25336628137365974f32,
+    ],
+    [
+        -0.6666843518324892f32,
+        1.6164812366349395f32,
+        0.01576854581391113f32,
+    ],
+    [
+        0.017639857445310783f32,
+        -0.042770613257808524f32,
+        0.9421031212354738f32,
+    ],
+];
+
+/// Adobe RGB (1998) to XYZ transformation matrix, D65 White point
+pub const A98_RGB_TO_XYZ_D65: [[f32; 3]; 3] = [
+    [
+        0.5766690429101305f32,
+        0.1855582379065463f32,
+        0.1882286462349947f32,
+    ],
+    [
+        0.29734497525053605f32,
+        0.6273635662554661f32,
+        0.07529145849399788f32,
+    ],
+    [
+        0.02703136138641234f32,
+        0.07068885253582723f32,
+        0.9913375368376388f32,
+    ],
+];
+
+/// XYZ to Adobe RGB (1998) transformation matrix, D65 White point
+pub const XYZ_TO_A98_RGB_D65: [[f32; 3]; 3] = [
+    [
+        2.0415879038107465f32,
+        -0.5650069742788596f32,
+        -0.34473135077832956f32,
+    ],
+    [
+        -0.9692436362808795f32,
+        1.8759675015077202f32,
+        0.04155505740717557f32,
+    ],
+    [
+        0.013444280632031142f32,
+        -0.11836239223101838f32,
+        1.0151749943912054f32,
+    ],
+];
+
+/// ProPhoto RGB to XYZ transformation matrix, D50 White point
+pub const PROPHOTO_RGB_TO_XYZ_D50: [[f32; 3]; 3] = [
+    [
+        0.7977604896723027f32,
+        0.13518583717574031f32,
+        0.0313493495815248f32,
+    ],
+    [
+        0.2880711282292934f32,
+        0.7118432178101014f32,
+        0.00008565396060525902f32,
+    ],
+    [0.0f32, 0.0f32, 0.8251046025104601f32],
+];
+
+/// XYZ to ProPhoto RGB transformation matrix, D50 White point
+pub const XYZ_TO_PROPHOTO_RGB_D50: [[f32; 3]; 3] = [
+    [
+        1.3457989731028281f32,
+        -0.25558010007997534f32,
+        -0.05110628506753401f32,
+    ],
+    [
+        -0.5446224939028347f32,
+        1.5082327413132781f32,
+        0.02053603239147973f32,
+    ],
+    [0.0f32, 0.0f32, 1.2119675456389454f32],
+];
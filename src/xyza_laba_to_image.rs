@@ -7,40 +7,29 @@
 
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::avx::avx_xyza_to_image;
-use crate::gamma_curves::TransferFunction;
+use crate::gamma_curves::{gamma_table_u8, TransferFunction};
 use crate::image::ImageConfiguration;
 #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
 use crate::neon::neon_xyza_to_image;
+use crate::premultiply::premultiply_row_u8;
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::sse::sse_xyza_to_image;
+use crate::threading::{current_threading, ForEachRow, ThreadingPolicy};
 use crate::xyz_target::XyzTarget;
 use crate::{LCh, Lab, Luv, Xyz};
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rayon::iter::IndexedParallelIterator;
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
 use std::slice;
 
-#[allow(clippy::type_complexity)]
-fn xyz_with_alpha_to_channels<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
-    src: &[f32],
-    src_stride: u32,
-    dst: &mut [u8],
-    dst_stride: u32,
-    width: u32,
-    height: u32,
-    matrix: &[[f32; 3]; 3],
-    transfer_function: TransferFunction,
-) {
-    let source: XyzTarget = TARGET.into();
-    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
-    if !image_configuration.has_alpha() {
-        panic!("Alpha may be set only on images with alpha");
-    }
+pub(crate) type XyzaToChannelsRowHandler =
+    unsafe fn(usize, *const f32, usize, *mut f32, usize, u32, &[[f32; 3]; 3]) -> usize;
 
-    let mut _wide_row_handler: Option<
-        unsafe fn(usize, *const f32, usize, *mut f32, usize, u32, &[[f32; 3]; 3]) -> usize,
-    > = None;
+/// Selects the widest available SIMD row handler for the layout
+pub(crate) fn xyza_to_channels_row_handler<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+) -> Option<XyzaToChannelsRowHandler> {
+    let mut _wide_row_handler: Option<XyzaToChannelsRowHandler> = None;
 
     #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
     {
@@ -57,9 +46,60 @@ fn xyz_with_alpha_to_channels<const CHANNELS_CONFIGURATION: u8, const TARGET: u8
         _wide_row_handler = Some(avx_xyza_to_image::<CHANNELS_CONFIGURATION, TARGET>);
     }
 
-    let mut lut_table = vec![0u8; 2049];
-    for (i, element) in lut_table.iter_mut().enumerate() {
-        *element = (transfer_function.gamma(i as f32 * (1. / 2048.0)) * 255.).min(255.) as u8;
+    _wide_row_handler
+}
+
+pub(crate) fn xyz_with_alpha_to_channels<const CHANNELS_CONFIGURATION: u8, const TARGET: u8>(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+    premultiplied: bool,
+) {
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    let premultiply = premultiplied && image_configuration.has_alpha();
+
+    xyz_with_alpha_to_channels_impl::<CHANNELS_CONFIGURATION, TARGET>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        &gamma_table_u8(transfer_function),
+        premultiply,
+        xyza_to_channels_row_handler::<CHANNELS_CONFIGURATION, TARGET>(),
+        &current_threading(),
+    );
+}
+
+/// Converts image with precomputed gamma table and row handler,
+/// rows are premultiplied by alpha after encoding when `premultiply` is set
+pub(crate) fn xyz_with_alpha_to_channels_impl<
+    const CHANNELS_CONFIGURATION: u8,
+    const TARGET: u8,
+>(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    lut_table: &[u8],
+    premultiply: bool,
+    _wide_row_handler: Option<XyzaToChannelsRowHandler>,
+    policy: &ThreadingPolicy,
+) {
+    let source: XyzTarget = TARGET.into();
+    let image_configuration: ImageConfiguration = CHANNELS_CONFIGURATION.into();
+    if !image_configuration.has_alpha() {
+        panic!("Alpha may be set only on images with alpha");
     }
 
     let src_slice_safe_align = unsafe {
@@ -83,7 +123,7 @@ fn xyz_with_alpha_to_channels<const CHANNELS_CONFIGURATION: u8, const TARGET: u8
             .zip(src_slice_safe_align.chunks_exact(src_stride as usize));
     }
 
-    iter.for_each(|(dst, src)| unsafe {
+    iter.for_each_row_with(policy, width as usize, |(dst, src)| unsafe {
         let channels = image_configuration.get_channels_count();
 
         let mut _cx = 0usize;
@@ -167,6 +207,10 @@ fn xyz_with_alpha_to_channels<const CHANNELS_CONFIGURATION: u8, const TARGET: u8
                 *lut_table.get_unchecked(b_cast as usize);
             dst_chunk[image_configuration.get_a_channel_offset()] = a_cast;
         }
+
+        if premultiply {
+            premultiply_row_u8::<CHANNELS_CONFIGURATION>(dst, width as usize);
+        }
     });
 }
 
@@ -200,6 +244,109 @@ pub fn lab_with_alpha_to_rgba(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts LAB with interleaved alpha channel to ARGB. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn lab_with_alpha_to_argb(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    xyz_with_alpha_to_channels::<{ ImageConfiguration::Argb as u8 }, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
+    );
+}
+
+/// This function converts LAB with interleaved alpha channel to RGBA. This is much more effective than naive direct transformation. Alpha is premultiplied into colour channels of the output
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive RGBA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn lab_with_alpha_to_rgba_premultiplied(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    xyz_with_alpha_to_channels::<{ ImageConfiguration::Rgba as u8 }, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        true,
+    );
+}
+
+/// This function converts LAB with interleaved alpha channel to ARGB. This is much more effective than naive direct transformation. Alpha is premultiplied into colour channels of the output
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn lab_with_alpha_to_argb_premultiplied(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    xyz_with_alpha_to_channels::<{ ImageConfiguration::Argb as u8 }, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        true,
     );
 }
 
@@ -233,6 +380,109 @@ pub fn lab_with_alpha_to_bgra(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts LAB with separate alpha channel to ABGR. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn lab_with_alpha_to_abgr(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    xyz_with_alpha_to_channels::<{ ImageConfiguration::Abgr as u8 }, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
+    );
+}
+
+/// This function converts LAB with separate alpha channel to BGRA. This is much more effective than naive direct transformation. Alpha is premultiplied into colour channels of the output
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive BGRA data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn lab_with_alpha_to_bgra_premultiplied(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    xyz_with_alpha_to_channels::<{ ImageConfiguration::Bgra as u8 }, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        true,
+    );
+}
+
+/// This function converts LAB with separate alpha channel to ABGR. This is much more effective than naive direct transformation. Alpha is premultiplied into colour channels of the output
+///
+/// # Arguments
+/// * `src` - A slice contains LAB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn lab_with_alpha_to_abgr_premultiplied(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    xyz_with_alpha_to_channels::<{ ImageConfiguration::Abgr as u8 }, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        true,
     );
 }
 
@@ -266,6 +516,41 @@ pub fn luv_with_alpha_to_rgba(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts LUV with separate alpha channel to ARGB. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains LUV data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn luv_with_alpha_to_argb(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    xyz_with_alpha_to_channels::<{ ImageConfiguration::Argb as u8 }, { XyzTarget::Luv as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
     );
 }
 
@@ -301,6 +586,43 @@ pub fn luv_with_alpha_to_bgra(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts LUV with separate alpha channel to ABGR. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains LUV data
+/// * `src_stride` - Bytes per row for src data.
+/// * `a_plane` - A slice contains Alpha data
+/// * `a_stride` - Bytes per row for alpha plane data
+/// * `dst` - A mutable slice to receive ABGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn luv_with_alpha_to_abgr(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    xyz_with_alpha_to_channels::<{ ImageConfiguration::Abgr as u8 }, { XyzTarget::Lab as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
     );
 }
 
@@ -334,6 +656,41 @@ pub fn xyz_with_alpha_to_rgba(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts XYZ with separate alpha channel to ARGB. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains XYZa data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn xyz_with_alpha_to_argb(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    xyz_with_alpha_to_channels::<{ ImageConfiguration::Argb as u8 }, { XyzTarget::Xyz as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
     );
 }
 
@@ -367,6 +724,41 @@ pub fn xyz_with_alpha_to_bgra(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts XYZ with separate alpha channel to ABGR. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains XYZ data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn xyz_with_alpha_to_abgr(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    xyz_with_alpha_to_channels::<{ ImageConfiguration::Abgr as u8 }, { XyzTarget::Xyz as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
     );
 }
 
@@ -400,6 +792,41 @@ pub fn lch_with_alpha_to_rgba(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts LCH with separate alpha channel to ARGB. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains LCHa data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ARGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn lch_with_alpha_to_argb(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    xyz_with_alpha_to_channels::<{ ImageConfiguration::Argb as u8 }, { XyzTarget::Lch as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
     );
 }
 
@@ -433,5 +860,40 @@ pub fn lch_with_alpha_to_bgra(
         height,
         matrix,
         transfer_function,
+        false,
+    );
+}
+
+/// This function converts LCH with separate alpha channel to ABGR. This is much more effective than naive direct transformation
+///
+/// # Arguments
+/// * `src` - A slice contains LCHa data
+/// * `src_stride` - Bytes per row for src data.
+/// * `dst` - A mutable slice to receive ABGR data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Transformation matrix from RGB to XYZ. If you don't have specific just pick `XYZ_TO_SRGB_D65`
+/// * `transfer_function` - Transfer function. If you don't have specific pick `Srgb`
+pub fn lch_with_alpha_to_abgr(
+    src: &[f32],
+    src_stride: u32,
+    dst: &mut [u8],
+    dst_stride: u32,
+    width: u32,
+    height: u32,
+    matrix: &[[f32; 3]; 3],
+    transfer_function: TransferFunction,
+) {
+    xyz_with_alpha_to_channels::<{ ImageConfiguration::Abgr as u8 }, { XyzTarget::Lch as u8 }>(
+        src,
+        src_stride,
+        dst,
+        dst_stride,
+        width,
+        height,
+        matrix,
+        transfer_function,
+        false,
     );
 }
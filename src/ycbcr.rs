@@ -0,0 +1,190 @@
+/*
+ * // Copyright 2024 (c) the Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Use of this source code is governed by a BSD-style
+ * // license that can be found in the LICENSE file.
+ */
+use crate::image::ImageConfiguration;
+use crate::planar::{
+    convert_rows, interleaved_rows, interleaved_rows_mut, planar_rows, planar_rows_mut,
+};
+use crate::threading::current_threading;
+
+/// Matrix coefficients of Y'CbCr
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum YCbCrMatrix {
+    /// Rec. ITU-R BT.601, Kr = 0.299, Kb = 0.114
+    Bt601,
+    /// Rec. ITU-R BT.709, Kr = 0.2126, Kb = 0.0722
+    Bt709,
+    /// Rec. ITU-R BT.2020 non constant luminance, Kr = 0.2627, Kb = 0.0593
+    Bt2020,
+}
+
+impl YCbCrMatrix {
+    /// Returns Kr and Kb coefficients
+    pub const fn coefficients(&self) -> (f32, f32) {
+        match self {
+            YCbCrMatrix::Bt601 => (0.299f32, 0.114f32),
+            YCbCrMatrix::Bt709 => (0.2126f32, 0.0722f32),
+            YCbCrMatrix::Bt2020 => (0.2627f32, 0.0593f32),
+        }
+    }
+}
+
+/// Quantization range of 8 bit Y'CbCr
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum YCbCrRange {
+    /// Y' and chroma use all of [0, 255], chroma is centered at 128
+    Full,
+    /// Studio swing, Y' in [16, 235] and chroma in [16, 240]
+    Limited,
+}
+
+impl YCbCrRange {
+    /// Returns bias and scale of Y' and scale of chroma
+    const fn scales(&self) -> (f32, f32, f32) {
+        match self {
+            YCbCrRange::Full => (0f32, 1f32, 1f32),
+            YCbCrRange::Limited => (16f32, 219f32 / 255f32, 224f32 / 255f32),
+        }
+    }
+}
+
+#[inline(always)]
+fn quantize(value: f32) -> u8 {
+    value.round().max(0f32).min(255f32) as u8
+}
+
+/// This function converts RGB into planar Y'CbCr 4:4:4, planes are Y', Cb and Cr
+///
+/// # Arguments
+/// * `src` - A slice contains RGB data
+/// * `src_stride` - Bytes per row for src data.
+/// * `src_layout` - Channel layout of the source, alpha is ignored
+/// * `dst_planes` - Mutable slices to receive Y', Cb and Cr planes
+/// * `dst_strides` - Bytes per row of every plane
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Matrix coefficients, see [YCbCrMatrix]
+/// * `range` - Quantization range, see [YCbCrRange]
+///
+/// # Panics
+/// Panics if count of planes is not 3, source is gray or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn rgb_to_ycbcr_planar(
+    src: &[u8],
+    src_stride: u32,
+    src_layout: ImageConfiguration,
+    dst_planes: &mut [&mut [u8]],
+    dst_strides: &[u32],
+    width: u32,
+    height: u32,
+    matrix: YCbCrMatrix,
+    range: YCbCrRange,
+) {
+    if src_layout.is_gray() {
+        panic!("Y'CbCr may be computed only from colour layouts");
+    }
+    let src_channels = src_layout.get_channels_count();
+    let src_rows = interleaved_rows(src, src_stride, src_channels, width, height);
+    let dst_rows = planar_rows_mut(dst_planes, dst_strides, 3, width, height);
+
+    let (kr, kb) = matrix.coefficients();
+    let kg = 1f32 - kr - kb;
+    let (bias, y_scale, c_scale) = range.scales();
+    let cb_scale = c_scale / (2f32 * (1f32 - kb));
+    let cr_scale = c_scale / (2f32 * (1f32 - kr));
+
+    convert_rows(
+        src_rows,
+        src_channels,
+        dst_rows,
+        3,
+        width,
+        &current_threading(),
+        |src, dst| {
+            for (src_pixel, dst_pixel) in
+                src.chunks_exact(src_channels).zip(dst.chunks_exact_mut(3))
+            {
+                let r = src_pixel[src_layout.get_r_channel_offset()] as f32;
+                let g = src_pixel[src_layout.get_g_channel_offset()] as f32;
+                let b = src_pixel[src_layout.get_b_channel_offset()] as f32;
+                let y = kr * r + kg * g + kb * b;
+                dst_pixel[0] = quantize(y * y_scale + bias);
+                dst_pixel[1] = quantize((b - y) * cb_scale + 128f32);
+                dst_pixel[2] = quantize((r - y) * cr_scale + 128f32);
+            }
+        },
+    );
+}
+
+/// This function converts planar Y'CbCr 4:4:4 into RGB, planes are Y', Cb and Cr
+///
+/// # Arguments
+/// * `src_planes` - Slices contains Y', Cb and Cr planes
+/// * `src_strides` - Bytes per row of every plane
+/// * `dst` - A mutable slice to receive RGB data
+/// * `dst_stride` - Bytes per row for dst data
+/// * `dst_layout` - Channel layout of the destination, alpha is filled with 255
+/// * `width` - Image width
+/// * `height` - Image height
+/// * `matrix` - Matrix coefficients, see [YCbCrMatrix]
+/// * `range` - Quantization range, see [YCbCrRange]
+///
+/// # Panics
+/// Panics if count of planes is not 3, destination is gray or images doesn't fit into slices
+#[allow(clippy::too_many_arguments)]
+pub fn ycbcr_planar_to_rgb(
+    src_planes: &[&[u8]],
+    src_strides: &[u32],
+    dst: &mut [u8],
+    dst_stride: u32,
+    dst_layout: ImageConfiguration,
+    width: u32,
+    height: u32,
+    matrix: YCbCrMatrix,
+    range: YCbCrRange,
+) {
+    if dst_layout.is_gray() {
+        panic!("Y'CbCr may be converted only into colour layouts");
+    }
+    let dst_channels = dst_layout.get_channels_count();
+    let src_rows = planar_rows(src_planes, src_strides, 3, width, height);
+    let dst_rows = interleaved_rows_mut(dst, dst_stride, dst_channels, width, height);
+
+    let (kr, kb) = matrix.coefficients();
+    let kg = 1f32 - kr - kb;
+    let (bias, y_scale, c_scale) = range.scales();
+    let y_scale = 1f32 / y_scale;
+    let cr_to_r = 2f32 * (1f32 - kr) / c_scale;
+    let cb_to_b = 2f32 * (1f32 - kb) / c_scale;
+    let inv_kg = 1f32 / kg;
+
+    convert_rows(
+        src_rows,
+        3,
+        dst_rows,
+        dst_channels,
+        width,
+        &current_threading(),
+        |src, dst| {
+            for (src_pixel, dst_pixel) in
+                src.chunks_exact(3).zip(dst.chunks_exact_mut(dst_channels))
+            {
+                let y = (src_pixel[0] as f32 - bias) * y_scale;
+                let cb = src_pixel[1] as f32 - 128f32;
+                let cr = src_pixel[2] as f32 - 128f32;
+                let r = y + cr_to_r * cr;
+                let b = y + cb_to_b * cb;
+                let g = (y - kr * r - kb * b) * inv_kg;
+                dst_pixel[dst_layout.get_r_channel_offset()] = quantize(r);
+                dst_pixel[dst_layout.get_g_channel_offset()] = quantize(g);
+                dst_pixel[dst_layout.get_b_channel_offset()] = quantize(b);
+                if dst_layout.has_alpha() {
+                    dst_pixel[dst_layout.get_a_channel_offset()] = 255;
+                }
+            }
+        },
+    );
+}